@@ -1,7 +1,13 @@
-use clap::ArgMatches;
-use eth2_wallet::PlainText;
+use crate::{KDF_COST_FLAG, KDF_FLAG, PBKDF2_KDF, SCRYPT_KDF};
+use clap::{Arg, ArgMatches};
+use eth2_keystore::{KdfParams, DEFAULT_SCRYPT_P, DEFAULT_SCRYPT_R};
+use eth2_wallet::{
+    bip39::{Language, Mnemonic},
+    PlainText,
+};
 use rand::{distributions::Alphanumeric, Rng};
-use std::fs::create_dir_all;
+use std::fs::{self, create_dir_all};
+use std::io::{self, BufRead};
 use std::path::{Path, PathBuf};
 
 /// The `Alphanumeric` crate only generates a-z, A-Z, 0-9, therefore it has a range of 62
@@ -38,6 +44,102 @@ pub fn base_wallet_dir(matches: &ArgMatches, arg: &'static str) -> Result<PathBu
     )
 }
 
+/// Returns the arguments which allow the user to choose the KDF used for encryption.
+///
+/// `kdf_help` describes what is encrypted and which KDF is used when `--kdf` is absent. Use
+/// `parse_kdf_params` to read the values of these arguments.
+pub fn kdf_args<'a, 'b>(kdf_help: &'b str) -> Vec<Arg<'a, 'b>> {
+    vec![
+        Arg::with_name(KDF_FLAG)
+            .long(KDF_FLAG)
+            .value_name("KDF")
+            .help(kdf_help)
+            .takes_value(true)
+            .possible_values(&[SCRYPT_KDF, PBKDF2_KDF]),
+        Arg::with_name(KDF_COST_FLAG)
+            .long(KDF_COST_FLAG)
+            .value_name("KDF_COST")
+            .help(
+                "The cost parameter of the --kdf: `n` for scrypt (a power of two) or `c` for \
+                pbkdf2. Defaults to 262144 for both. Lower values make keystores faster to \
+                decrypt, and faster to brute-force.",
+            )
+            .takes_value(true)
+            .requires(KDF_FLAG),
+    ]
+}
+
+/// Returns the `KdfParams` described by the arguments from `kdf_args`, or `None` if `--kdf` was
+/// not supplied.
+pub fn parse_kdf_params(matches: &ArgMatches) -> Result<Option<KdfParams>, String> {
+    let kdf: Option<String> = clap_utils::parse_optional(matches, KDF_FLAG)?;
+    let cost: Option<u32> = clap_utils::parse_optional(matches, KDF_COST_FLAG)?;
+
+    let kdf = match kdf {
+        Some(kdf) => kdf,
+        None => return Ok(None),
+    };
+
+    let kdf_params = match kdf.as_str() {
+        SCRYPT_KDF => match cost {
+            Some(n) if n <= 1 || !n.is_power_of_two() => Err(format!(
+                "--{} must be a power of two greater than 1 for scrypt",
+                KDF_COST_FLAG
+            )),
+            Some(n) => Ok(KdfParams::Scrypt {
+                n,
+                r: DEFAULT_SCRYPT_R,
+                p: DEFAULT_SCRYPT_P,
+            }),
+            None => Ok(KdfParams::scrypt()),
+        },
+        PBKDF2_KDF => match cost {
+            Some(0) => Err(format!(
+                "--{} must be greater than 0 for pbkdf2",
+                KDF_COST_FLAG
+            )),
+            Some(c) => Ok(KdfParams::Pbkdf2 { c }),
+            None => Ok(KdfParams::pbkdf2()),
+        },
+        other => Err(format!("Unknown --{}: {}", KDF_FLAG, other)),
+    }?;
+
+    Ok(Some(kdf_params))
+}
+
+/// Reads a BIP-39 mnemonic from the file at `mnemonic_path` or, if it is `None`, from the first
+/// line of stdin.
+pub fn read_mnemonic_from_cli(mnemonic_path: Option<PathBuf>) -> Result<Mnemonic, String> {
+    let phrase = match mnemonic_path {
+        Some(path) => fs::read(&path)
+            .map_err(|e| format!("Unable to read {:?}: {:?}", path, e))
+            .and_then(|bytes| {
+                String::from_utf8(strip_off_newlines(bytes))
+                    .map_err(|e| format!("Unable to parse mnemonic as UTF-8: {:?}", e))
+            })?,
+        None => {
+            eprintln!("Enter the mnemonic phrase:");
+            let mut line = String::new();
+            io::stdin()
+                .lock()
+                .read_line(&mut line)
+                .map_err(|e| format!("Unable to read mnemonic from stdin: {:?}", e))?;
+            line
+        }
+    };
+
+    Mnemonic::from_phrase(phrase.trim(), Language::English)
+        .map_err(|e| format!("Unable to derive mnemonic: {:?}", e))
+}
+
+/// Prints `prompt` to stderr and reads a password from the terminal, without echoing it.
+pub fn read_password_from_user(prompt: &str) -> Result<PlainText, String> {
+    eprintln!("{}", prompt);
+    rpassword::read_password_from_tty(None)
+        .map(|password| PlainText::from(password.into_bytes()))
+        .map_err(|e| format!("Unable to read password from terminal: {:?}", e))
+}
+
 /// Remove any number of newline or carriage returns from the end of a vector of bytes.
 pub fn strip_off_newlines(mut bytes: Vec<u8>) -> Vec<u8> {
     let mut strip_off = 0;
@@ -0,0 +1,21 @@
+pub mod reencrypt;
+
+use clap::{App, ArgMatches};
+
+pub const CMD: &str = "keystore";
+
+pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
+    App::new(CMD)
+        .about("Provides commands for managing existing EIP-2335 keystores.")
+        .subcommand(reencrypt::cli_app())
+}
+
+pub fn cli_run(matches: &ArgMatches) -> Result<(), String> {
+    match matches.subcommand() {
+        (reencrypt::CMD, Some(matches)) => reencrypt::cli_run(matches),
+        (unknown, _) => Err(format!(
+            "{} does not have a {} command. See --help",
+            CMD, unknown
+        )),
+    }
+}
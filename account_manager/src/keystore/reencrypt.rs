@@ -0,0 +1,145 @@
+use crate::common::{kdf_args, parse_kdf_params, read_password_from_user, strip_off_newlines};
+use clap::{App, Arg, ArgMatches};
+use eth2_keystore::{Error as KeystoreError, KdfParams, Keystore, PlainText};
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+pub const CMD: &str = "reencrypt";
+pub const KEYSTORE_FLAG: &str = "keystore";
+pub const PASSWORD_FLAG: &str = "password-file";
+pub const NEW_PASSWORD_FLAG: &str = "new-password-file";
+
+/// The extension of the temporary file that the new keystore is written to before it replaces
+/// the existing keystore.
+const TEMP_EXTENSION: &str = "tmp";
+
+pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
+    App::new(CMD)
+        .about(
+            "Re-encrypts an EIP-2335 keystore in place, with a new password and/or key \
+            derivation function. The UUID, path and public key of the keystore are unchanged.",
+        )
+        .arg(
+            Arg::with_name(KEYSTORE_FLAG)
+                .long(KEYSTORE_FLAG)
+                .value_name("KEYSTORE_PATH")
+                .help("Path to the keystore to be re-encrypted.")
+                .takes_value(true)
+                .required(true),
+        )
+        .arg(
+            Arg::with_name(PASSWORD_FLAG)
+                .long(PASSWORD_FLAG)
+                .value_name("PASSWORD_PATH")
+                .help(
+                    "A path to a file containing the current password of the keystore. If \
+                    absent, the password is read from the terminal.",
+                )
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(NEW_PASSWORD_FLAG)
+                .long(NEW_PASSWORD_FLAG)
+                .value_name("NEW_PASSWORD_PATH")
+                .help(
+                    "A path to a file containing the new password of the keystore. If absent, \
+                    the current password is kept. If the keystore belongs to a validator, \
+                    remember to also update its password in the secrets directory.",
+                )
+                .takes_value(true),
+        )
+        .args(&kdf_args(
+            "The key derivation function used to re-encrypt the keystore. If absent, the \
+            key derivation function and cost of the existing keystore are kept.",
+        ))
+}
+
+pub fn cli_run(matches: &ArgMatches) -> Result<(), String> {
+    let keystore_path: PathBuf = clap_utils::parse_required(matches, KEYSTORE_FLAG)?;
+    let password_path: Option<PathBuf> = clap_utils::parse_optional(matches, PASSWORD_FLAG)?;
+    let new_password_path: Option<PathBuf> =
+        clap_utils::parse_optional(matches, NEW_PASSWORD_FLAG)?;
+    let kdf_params = parse_kdf_params(matches)?;
+
+    let keystore = File::open(&keystore_path)
+        .map_err(|e| format!("Unable to open {:?}: {:?}", keystore_path, e))
+        .and_then(|file| {
+            Keystore::from_json_reader(file)
+                .map_err(|e| format!("Unable to parse keystore {:?}: {:?}", keystore_path, e))
+        })?;
+
+    let password = match password_path {
+        Some(path) => read_password_file(&path)?,
+        None => read_password_from_user(&format!(
+            "Enter the password for keystore {:?}:",
+            keystore_path
+        ))?,
+    };
+    let new_password = match new_password_path {
+        Some(path) => read_password_file(&path)?,
+        None => password.clone(),
+    };
+
+    let kdf_params = kdf_params.unwrap_or_else(|| KdfParams::from(keystore.kdf()));
+
+    let new_keystore = keystore
+        .reencrypt(password.as_bytes(), new_password.as_bytes(), &kdf_params)
+        .map_err(|e| match e {
+            KeystoreError::InvalidPassword => {
+                format!("Invalid password for keystore {:?}", keystore_path)
+            }
+            e => format!("Unable to re-encrypt keystore: {:?}", e),
+        })?;
+
+    replace_keystore(&keystore_path, &new_keystore)?;
+
+    println!("Re-encrypted keystore 0x{}", new_keystore.pubkey());
+
+    Ok(())
+}
+
+fn read_password_file(path: &Path) -> Result<PlainText, String> {
+    fs::read(path)
+        .map_err(|e| format!("Unable to read {:?}: {:?}", path, e))
+        .map(|bytes| PlainText::from(strip_off_newlines(bytes)))
+}
+
+/// Atomically replaces the keystore at `path` with `keystore`.
+///
+/// The new keystore is written to a temporary file in the same directory (with the same
+/// permissions as the original) and then renamed over the original, so that `path` always
+/// contains a complete keystore.
+fn replace_keystore(path: &Path, keystore: &Keystore) -> Result<(), String> {
+    let temp_path = path.with_extension(TEMP_EXTENSION);
+    let permissions = fs::metadata(path)
+        .map_err(|e| format!("Unable to read metadata of {:?}: {:?}", path, e))?
+        .permissions();
+
+    let write_temp = || -> Result<(), String> {
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&temp_path)
+            .map_err(|e| format!("Unable to create {:?}: {:?}", temp_path, e))?;
+        file.set_permissions(permissions)
+            .map_err(|e| format!("Unable to set permissions of {:?}: {:?}", temp_path, e))?;
+        keystore
+            .to_json_writer(&mut file)
+            .map_err(|e| format!("Unable to write {:?}: {:?}", temp_path, e))?;
+        file.flush()
+            .and_then(|()| file.sync_all())
+            .map_err(|e| format!("Unable to sync {:?}: {:?}", temp_path, e))
+    };
+
+    write_temp()
+        .and_then(|()| {
+            fs::rename(&temp_path, path)
+                .map_err(|e| format!("Unable to move {:?} to {:?}: {:?}", temp_path, path, e))
+        })
+        .map_err(|e| {
+            // Don't leave a partially written keystore lying around.
+            let _ = fs::remove_file(&temp_path);
+            e
+        })
+}
@@ -1,4 +1,5 @@
 mod common;
+pub mod keystore;
 pub mod upgrade_legacy_keypairs;
 pub mod validator;
 pub mod wallet;
@@ -12,6 +13,10 @@ pub const CMD: &str = "account_manager";
 pub const SECRETS_DIR_FLAG: &str = "secrets-dir";
 pub const VALIDATOR_DIR_FLAG: &str = "validator-dir";
 pub const BASE_DIR_FLAG: &str = "base-dir";
+pub const KDF_FLAG: &str = "kdf";
+pub const KDF_COST_FLAG: &str = "kdf-cost";
+pub const SCRYPT_KDF: &str = "scrypt";
+pub const PBKDF2_KDF: &str = "pbkdf2";
 
 pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
     App::new(CMD)
@@ -19,6 +24,7 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
         .about("Utilities for generating and managing Ethereum 2.0 accounts.")
         .subcommand(wallet::cli_app())
         .subcommand(validator::cli_app())
+        .subcommand(keystore::cli_app())
         .subcommand(upgrade_legacy_keypairs::cli_app())
 }
 
@@ -27,6 +33,7 @@ pub fn run<T: EthSpec>(matches: &ArgMatches<'_>, env: Environment<T>) -> Result<
     match matches.subcommand() {
         (wallet::CMD, Some(matches)) => wallet::cli_run(matches)?,
         (validator::CMD, Some(matches)) => validator::cli_run(matches, env)?,
+        (keystore::CMD, Some(matches)) => keystore::cli_run(matches)?,
         (upgrade_legacy_keypairs::CMD, Some(matches)) => upgrade_legacy_keypairs::cli_run(matches)?,
         (unknown, _) => {
             return Err(format!(
@@ -1,5 +1,5 @@
 use crate::{
-    common::{ensure_dir_exists, random_password, strip_off_newlines},
+    common::{ensure_dir_exists, kdf_args, parse_kdf_params, random_password, strip_off_newlines},
     SECRETS_DIR_FLAG, VALIDATOR_DIR_FLAG,
 };
 use clap::{App, Arg, ArgMatches};
@@ -100,6 +100,9 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 .conflicts_with("count")
                 .takes_value(true),
         )
+        .args(&kdf_args(
+            "The key derivation function used to encrypt the keystores. Defaults to scrypt.",
+        ))
 }
 
 pub fn cli_run<T: EthSpec>(
@@ -126,6 +129,7 @@ pub fn cli_run<T: EthSpec>(
         .unwrap_or_else(|| spec.max_effective_balance);
     let count: Option<usize> = clap_utils::parse_optional(matches, COUNT_FLAG)?;
     let at_most: Option<usize> = clap_utils::parse_optional(matches, AT_MOST_FLAG)?;
+    let kdf_params = parse_kdf_params(matches)?.unwrap_or_default();
 
     ensure_dir_exists(&validator_dir)?;
     ensure_dir_exists(&secrets_dir)?;
@@ -169,10 +173,11 @@ pub fn cli_run<T: EthSpec>(
         let withdrawal_password = random_password();
 
         let keystores = wallet
-            .next_validator(
+            .next_validator_with_kdf(
                 wallet_password.as_bytes(),
                 voting_password.as_bytes(),
                 withdrawal_password.as_bytes(),
+                &kdf_params,
             )
             .map_err(|e| format!("Unable to create validator keys: {:?}", e))?;
 
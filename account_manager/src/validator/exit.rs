@@ -0,0 +1,234 @@
+use crate::{SECRETS_DIR_FLAG, VALIDATOR_DIR_FLAG};
+use clap::{App, Arg, ArgMatches};
+use environment::Environment;
+use remote_beacon_node::RemoteBeaconNode;
+use std::io::{stdin, stdout, Write};
+use std::path::PathBuf;
+use types::{ChainSpec, Epoch, EthSpec, Keypair, SignedVoluntaryExit, VoluntaryExit};
+use validator_dir::Manager as ValidatorManager;
+
+pub const CMD: &str = "exit";
+pub const VALIDATOR_FLAG: &str = "validator";
+pub const BEACON_SERVER_FLAG: &str = "beacon-node";
+pub const NO_CONFIRMATION_FLAG: &str = "no-confirmation";
+pub const DEFAULT_BEACON_NODE: &str = "http://localhost:5052/";
+
+/// The exact phrase a user must type to confirm that they wish to exit their validator.
+pub const CONFIRMATION_PHRASE: &str = "Exit my validator";
+
+pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
+    App::new(CMD)
+        .about(
+            "Submits a VoluntaryExit to the beacon chain for a given validator. The voting \
+            keystore of the validator is decrypted using the password in --secrets-dir, the \
+            exit is signed for the current epoch of the beacon node and then published to the \
+            network via the beacon node HTTP API. Exiting is irreversible: once the exit is \
+            processed the validator can never validate again and the funds remain locked until \
+            withdrawals are enabled.",
+        )
+        .arg(
+            Arg::with_name(VALIDATOR_DIR_FLAG)
+                .long(VALIDATOR_DIR_FLAG)
+                .value_name("VALIDATOR_DIRECTORY")
+                .help(
+                    "The path the validator client data directory. \
+                    Defaults to ~/.lighthouse/validators",
+                )
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(SECRETS_DIR_FLAG)
+                .long(SECRETS_DIR_FLAG)
+                .value_name("SECRETS_DIR")
+                .help(
+                    "The path where the validator keystore passwords are stored. \
+                    Defaults to ~/.lighthouse/secrets",
+                )
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(VALIDATOR_FLAG)
+                .long(VALIDATOR_FLAG)
+                .value_name("VALIDATOR_NAME")
+                .help("The name of the directory in --validator-dir for the validator to exit.")
+                .takes_value(true)
+                .required(true),
+        )
+        .arg(
+            Arg::with_name(BEACON_SERVER_FLAG)
+                .long(BEACON_SERVER_FLAG)
+                .value_name("NETWORK_ADDRESS")
+                .help("Address to a beacon node HTTP API")
+                .default_value(DEFAULT_BEACON_NODE)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(NO_CONFIRMATION_FLAG)
+                .long(NO_CONFIRMATION_FLAG)
+                .help(
+                    "Exits the validator without showing the confirmation prompt. \
+                    Intended for use in scripts and testing only.",
+                ),
+        )
+}
+
+pub fn cli_run<T: EthSpec>(
+    matches: &ArgMatches<'_>,
+    mut env: Environment<T>,
+) -> Result<(), String> {
+    let spec = env.core_context().eth2_config.spec;
+
+    let validator_dir = clap_utils::parse_path_with_default_in_home_dir(
+        matches,
+        VALIDATOR_DIR_FLAG,
+        PathBuf::new().join(".lighthouse").join("validators"),
+    )?;
+    let secrets_dir = clap_utils::parse_path_with_default_in_home_dir(
+        matches,
+        SECRETS_DIR_FLAG,
+        PathBuf::new().join(".lighthouse").join("secrets"),
+    )?;
+    let validator: String = clap_utils::parse_required(matches, VALIDATOR_FLAG)?;
+    let server_url: String = clap_utils::parse_required(matches, BEACON_SERVER_FLAG)?;
+    let no_confirmation = matches.is_present(NO_CONFIRMATION_FLAG);
+
+    let manager = ValidatorManager::open(&validator_dir)
+        .map_err(|e| format!("Unable to read --{}: {:?}", VALIDATOR_DIR_FLAG, e))?;
+    let path = manager
+        .directory_names()
+        .map_err(|e| {
+            format!(
+                "Unable to read --{} directory names: {:?}",
+                VALIDATOR_DIR_FLAG, e
+            )
+        })?
+        .get(&validator)
+        .ok_or_else(|| format!("Unknown validator: {}", validator))?
+        .clone();
+    let keypair = manager
+        .open_validator(&path)
+        .map_err(|e| format!("Unable to open {}: {:?}", validator, e))?
+        .voting_keypair(&secrets_dir)
+        .map_err(|e| format!("Unable to decrypt voting keystore: {:?}", e))?;
+
+    let remote_node = RemoteBeaconNode::<T>::new(server_url)?;
+
+    env.runtime().block_on(publish_voluntary_exit::<T>(
+        &keypair,
+        &remote_node,
+        &spec,
+        no_confirmation,
+    ))
+}
+
+/// Signs a `VoluntaryExit` for the current epoch of `remote_node` and publishes it, after
+/// confirming with the user.
+async fn publish_voluntary_exit<E: EthSpec>(
+    keypair: &Keypair,
+    remote_node: &RemoteBeaconNode<E>,
+    spec: &ChainSpec,
+    no_confirmation: bool,
+) -> Result<(), String> {
+    let beacon = remote_node.http.beacon();
+
+    let genesis_validators_root = beacon
+        .get_genesis_validators_root()
+        .await
+        .map_err(|e| format!("Failed to get genesis validators root: {:?}", e))?;
+    let fork = beacon
+        .get_fork()
+        .await
+        .map_err(|e| format!("Failed to get fork: {:?}", e))?;
+    let head = beacon
+        .get_head()
+        .await
+        .map_err(|e| format!("Failed to get beacon chain head: {:?}", e))?;
+    let epoch = head.slot.epoch(E::slots_per_epoch());
+
+    let validator_response = beacon
+        .get_validators(vec![keypair.pk.clone()], None)
+        .await
+        .map_err(|e| format!("Failed to get validator details: {:?}", e))?
+        .into_iter()
+        .next()
+        .ok_or_else(|| "Beacon node returned no validator details".to_string())?;
+    let (validator_index, validator) = match (
+        validator_response.validator_index,
+        validator_response.validator,
+    ) {
+        (Some(index), Some(validator)) => Ok((index as u64, validator)),
+        _ => Err(format!(
+            "Validator {} is not known to the beacon node. Has the deposit been processed?",
+            keypair.pk.as_hex_string()
+        )),
+    }?;
+
+    if validator.exit_epoch != spec.far_future_epoch {
+        return Err(format!(
+            "Validator {} has already initiated an exit (exit epoch {})",
+            validator_index, validator.exit_epoch
+        ));
+    }
+
+    let signed_exit = VoluntaryExit {
+        epoch,
+        validator_index,
+    }
+    .sign(&keypair.sk, &fork, genesis_validators_root, spec);
+
+    if !no_confirmation && !confirm_exit(keypair, validator_index, epoch)? {
+        return Err("Voluntary exit was not confirmed, aborting".to_string());
+    }
+
+    submit_voluntary_exit(remote_node, signed_exit).await?;
+
+    println!(
+        "Successfully published voluntary exit for validator {}",
+        validator_index
+    );
+
+    Ok(())
+}
+
+async fn submit_voluntary_exit<E: EthSpec>(
+    remote_node: &RemoteBeaconNode<E>,
+    signed_exit: SignedVoluntaryExit,
+) -> Result<(), String> {
+    remote_node
+        .http
+        .beacon()
+        .voluntary_exit(signed_exit)
+        .await
+        .map_err(|e| format!("Failed to publish voluntary exit: {:?}", e))
+        .map(|_| ())
+}
+
+/// Displays a warning to the user and returns `Ok(true)` only if they type the
+/// `CONFIRMATION_PHRASE` exactly.
+fn confirm_exit(keypair: &Keypair, validator_index: u64, epoch: Epoch) -> Result<bool, String> {
+    println!();
+    println!("================================================================================");
+    println!("                    WARNING: THIS IS AN IRREVERSIBLE OPERATION");
+    println!("================================================================================");
+    println!();
+    println!("Publishing a voluntary exit for:");
+    println!();
+    println!("  validator pubkey: {}", keypair.pk.as_hex_string());
+    println!("  validator index:  {}", validator_index);
+    println!("  exit epoch:       {}", epoch);
+    println!();
+    println!("Once the exit is processed this validator can never validate again and its");
+    println!("balance cannot be withdrawn until withdrawals are enabled on the beacon chain.");
+    println!();
+    print!("Type \"{}\" to confirm: ", CONFIRMATION_PHRASE);
+    stdout()
+        .flush()
+        .map_err(|e| format!("Unable to flush stdout: {:?}", e))?;
+
+    let mut response = String::new();
+    stdin()
+        .read_line(&mut response)
+        .map_err(|e| format!("Unable to read from stdin: {:?}", e))?;
+
+    Ok(response.trim() == CONFIRMATION_PHRASE)
+}
@@ -0,0 +1,132 @@
+use super::deposit::VALIDATOR_FLAG;
+use crate::VALIDATOR_DIR_FLAG;
+use clap::{App, Arg, ArgMatches};
+use deposit_contract::StandardDepositDataJson;
+use environment::Environment;
+use std::fs::OpenOptions;
+use std::path::PathBuf;
+use types::EthSpec;
+use validator_dir::Manager as ValidatorManager;
+
+pub const CMD: &str = "export-deposit-data";
+pub const OUTPUT_FLAG: &str = "output";
+
+pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
+    App::new(CMD)
+        .about(
+            "Writes the deposits of one or more validators to a single JSON file, in the \
+            deposit_data-*.json format of the eth2.0-deposit-cli. The file can be used to \
+            submit the deposits without an Eth1 node on this machine. Validators which have \
+            already been deposited via the `deposit` command are skipped. The fork version is \
+            determined by the --testnet-dir flag on the primary Lighthouse binary.",
+        )
+        .arg(
+            Arg::with_name(VALIDATOR_DIR_FLAG)
+                .long(VALIDATOR_DIR_FLAG)
+                .value_name("VALIDATOR_DIRECTORY")
+                .help(
+                    "The path the validator client data directory. \
+                    Defaults to ~/.lighthouse/validators",
+                )
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(VALIDATOR_FLAG)
+                .long(VALIDATOR_FLAG)
+                .value_name("VALIDATOR_NAME")
+                .help(
+                    "The name of the directory in --validator-dir to export. \
+                    Set to 'all' to export all validators in the --validator-dir.",
+                )
+                .takes_value(true)
+                .required(true),
+        )
+        .arg(
+            Arg::with_name(OUTPUT_FLAG)
+                .long(OUTPUT_FLAG)
+                .value_name("OUTPUT_PATH")
+                .help("The path of the JSON file to create. Must not already exist.")
+                .takes_value(true)
+                .required(true),
+        )
+}
+
+pub fn cli_run<T: EthSpec>(matches: &ArgMatches, mut env: Environment<T>) -> Result<(), String> {
+    let spec = env.core_context().eth2_config.spec;
+
+    let validator_dir = clap_utils::parse_path_with_default_in_home_dir(
+        matches,
+        VALIDATOR_DIR_FLAG,
+        PathBuf::new().join(".lighthouse").join("validators"),
+    )?;
+    let validator: String = clap_utils::parse_required(matches, VALIDATOR_FLAG)?;
+    let output_path: PathBuf = clap_utils::parse_required(matches, OUTPUT_FLAG)?;
+
+    let manager = ValidatorManager::open(&validator_dir)
+        .map_err(|e| format!("Unable to read --{}: {:?}", VALIDATOR_DIR_FLAG, e))?;
+
+    let validators = match validator.as_ref() {
+        "all" => manager
+            .open_all_validators()
+            .map_err(|e| format!("Unable to read all validators: {:?}", e)),
+        name => {
+            let path = manager
+                .directory_names()
+                .map_err(|e| {
+                    format!(
+                        "Unable to read --{} directory names: {:?}",
+                        VALIDATOR_DIR_FLAG, e
+                    )
+                })?
+                .get(name)
+                .ok_or_else(|| format!("Unknown validator:  {}", name))?
+                .clone();
+
+            manager
+                .open_validator(&path)
+                .map_err(|e| format!("Unable to open {}: {:?}", name, e))
+                .map(|v| vec![v])
+        }
+    }?;
+
+    let deposits = validators
+        .into_iter()
+        .filter(|v| !v.eth1_deposit_tx_hash_exists())
+        .map(|v| match v.eth1_deposit_data() {
+            Ok(Some(data)) => Ok(StandardDepositDataJson::new(&data.deposit_data, &spec)),
+            Ok(None) => Err(format!(
+                "Validator is missing deposit data file: {:?}",
+                v.dir()
+            )),
+            Err(e) => Err(format!(
+                "Unable to read deposit data for {:?}: {:?}",
+                v.dir(),
+                e
+            )),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if deposits.is_empty() {
+        eprintln!("No validators to export.");
+        return Ok(());
+    }
+
+    let file = OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&output_path)
+        .map_err(|e| format!("Unable to create {:?}: {:?}", output_path, e))?;
+    serde_json::to_writer(file, &deposits)
+        .map_err(|e| format!("Unable to write {:?}: {:?}", output_path, e))?;
+
+    for (i, deposit) in deposits.iter().enumerate() {
+        println!(
+            "{}/{}\t0x{}",
+            i + 1,
+            deposits.len(),
+            hex::encode(deposit.pubkey.as_slice())
+        );
+    }
+
+    Ok(())
+}
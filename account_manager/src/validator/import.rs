@@ -0,0 +1,206 @@
+use crate::{
+    common::{ensure_dir_exists, read_password_from_user, strip_off_newlines},
+    SECRETS_DIR_FLAG, VALIDATOR_DIR_FLAG,
+};
+use clap::{App, Arg, ArgMatches};
+use eth2_keystore::{Error as KeystoreError, Keystore, PlainText};
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use validator_dir::{Builder as ValidatorDirBuilder, BuilderError, Manager as ValidatorManager};
+
+pub const CMD: &str = "import";
+pub const KEYSTORE_FLAG: &str = "keystore";
+pub const DIR_FLAG: &str = "directory";
+pub const PASSWORD_FLAG: &str = "password-file";
+
+pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
+    App::new(CMD)
+        .about(
+            "Imports one or more EIP-2335 keystores into a Lighthouse validator directory, \
+            storing their passwords in the secrets directory. Keystores for validators that \
+            already exist in the validator directory are refused.",
+        )
+        .arg(
+            Arg::with_name(KEYSTORE_FLAG)
+                .long(KEYSTORE_FLAG)
+                .value_name("KEYSTORE_PATH")
+                .help("Path to a single keystore to be imported.")
+                .conflicts_with(DIR_FLAG)
+                .required_unless(DIR_FLAG)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(DIR_FLAG)
+                .long(DIR_FLAG)
+                .value_name("KEYSTORES_DIRECTORY")
+                .help(
+                    "Path to a directory which contains zero or more keystores \
+                    for import. Only files with a name starting with \"keystore\" and a \
+                    \".json\" extension will be imported (e.g., the output of the \
+                    eth2.0-deposit-cli).",
+                )
+                .conflicts_with(KEYSTORE_FLAG)
+                .required_unless(KEYSTORE_FLAG)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(PASSWORD_FLAG)
+                .long(PASSWORD_FLAG)
+                .value_name("PASSWORD_PATH")
+                .help(
+                    "A path to a file containing the password of the keystores. The same \
+                    password is used for all keystores. If absent, the password for each \
+                    keystore is read from the terminal.",
+                )
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(VALIDATOR_DIR_FLAG)
+                .long(VALIDATOR_DIR_FLAG)
+                .value_name("VALIDATOR_DIRECTORY")
+                .help(
+                    "The path where the validator directories will be created. \
+                    Defaults to ~/.lighthouse/validators",
+                )
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(SECRETS_DIR_FLAG)
+                .long(SECRETS_DIR_FLAG)
+                .value_name("SECRETS_DIR")
+                .help(
+                    "The path where the validator keystore passwords will be stored. \
+                    Defaults to ~/.lighthouse/secrets",
+                )
+                .takes_value(true),
+        )
+}
+
+pub fn cli_run(matches: &ArgMatches) -> Result<(), String> {
+    let keystore: Option<PathBuf> = clap_utils::parse_optional(matches, KEYSTORE_FLAG)?;
+    let keystores_dir: Option<PathBuf> = clap_utils::parse_optional(matches, DIR_FLAG)?;
+    let password_path: Option<PathBuf> = clap_utils::parse_optional(matches, PASSWORD_FLAG)?;
+    let validator_dir = clap_utils::parse_path_with_default_in_home_dir(
+        matches,
+        VALIDATOR_DIR_FLAG,
+        PathBuf::new().join(".lighthouse").join("validators"),
+    )?;
+    let secrets_dir = clap_utils::parse_path_with_default_in_home_dir(
+        matches,
+        SECRETS_DIR_FLAG,
+        PathBuf::new().join(".lighthouse").join("secrets"),
+    )?;
+
+    let keystore_paths = match (keystore, keystores_dir) {
+        (Some(keystore), None) => vec![keystore],
+        (None, Some(keystores_dir)) => keystore_paths_in_dir(&keystores_dir)?,
+        _ => {
+            return Err(format!(
+                "Must supply either --{} or --{}",
+                KEYSTORE_FLAG, DIR_FLAG
+            ))
+        }
+    };
+
+    if keystore_paths.is_empty() {
+        eprintln!("No keystores found to import.");
+        return Ok(());
+    }
+
+    ensure_dir_exists(&validator_dir)?;
+    ensure_dir_exists(&secrets_dir)?;
+
+    let password_opt = password_path
+        .map(|path| {
+            fs::read(&path)
+                .map_err(|e| format!("Unable to read {:?}: {:?}", path, e))
+                .map(|bytes| PlainText::from(strip_off_newlines(bytes)))
+        })
+        .transpose()?;
+
+    // Read all the keystores before importing any of them, so that a duplicate is refused
+    // without leaving a partial import behind.
+    let mut known_pubkeys = ValidatorManager::open(&validator_dir)
+        .and_then(|manager| manager.voting_pubkeys())
+        .map_err(|e| format!("Unable to read --{}: {:?}", VALIDATOR_DIR_FLAG, e))?;
+    let keystores = keystore_paths
+        .into_iter()
+        .map(|path| {
+            let keystore = read_keystore(&path)?;
+            let pubkey = format!("0x{}", keystore.pubkey());
+
+            if let Some(existing) = known_pubkeys.insert(pubkey.clone(), path.clone()) {
+                return Err(format!(
+                    "Refusing to import {:?}, validator {} already exists at {:?}",
+                    path, pubkey, existing
+                ));
+            }
+
+            Ok((path, pubkey, keystore))
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    let n = keystores.len();
+    for (i, (path, pubkey, keystore)) in keystores.into_iter().enumerate() {
+        let password = match &password_opt {
+            Some(password) => password.clone(),
+            None => read_password_from_user(&format!(
+                "Enter the password for keystore {:?} ({}):",
+                path, pubkey
+            ))?,
+        };
+
+        ValidatorDirBuilder::new(validator_dir.clone(), secrets_dir.clone())
+            .voting_keystore(keystore, password.as_bytes())
+            .store_withdrawal_keystore(false)
+            .build()
+            .map_err(|e| match e {
+                BuilderError::KeystoreError(KeystoreError::InvalidPassword) => {
+                    format!("Invalid password for keystore {:?}", path)
+                }
+                e => format!("Unable to import keystore {:?}: {:?}", path, e),
+            })?;
+
+        println!("{}/{}\t{}", i + 1, n, pubkey);
+    }
+
+    Ok(())
+}
+
+/// Returns the paths of all the keystores in `dir`, sorted by file name.
+///
+/// Only files with a name starting with `keystore` and with a `.json` extension are returned.
+fn keystore_paths_in_dir(dir: &Path) -> Result<Vec<PathBuf>, String> {
+    let mut paths = fs::read_dir(dir)
+        .map_err(|e| format!("Unable to read {:?}: {:?}", dir, e))?
+        .map(|entry| {
+            entry
+                .map(|entry| entry.path())
+                .map_err(|e| format!("Unable to read {:?}: {:?}", dir, e))
+        })
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter()
+        .filter(|path| {
+            path.is_file()
+                && path.extension().map_or(false, |ext| ext == "json")
+                && path
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .map_or(false, |name| name.starts_with("keystore"))
+        })
+        .collect::<Vec<_>>();
+
+    paths.sort();
+
+    Ok(paths)
+}
+
+/// Reads a JSON keystore from `path`, without decrypting it.
+fn read_keystore(path: &Path) -> Result<Keystore, String> {
+    File::open(path)
+        .map_err(|e| format!("Unable to open {:?}: {:?}", path, e))
+        .and_then(|file| {
+            Keystore::from_json_reader(file)
+                .map_err(|e| format!("Unable to parse keystore {:?}: {:?}", path, e))
+        })
+}
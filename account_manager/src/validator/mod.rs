@@ -1,5 +1,10 @@
 pub mod create;
 pub mod deposit;
+pub mod exit;
+pub mod export_deposit_data;
+pub mod import;
+pub mod recover;
+pub mod slashing_protection;
 
 use crate::common::base_wallet_dir;
 use clap::{App, Arg, ArgMatches};
@@ -20,6 +25,11 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
         )
         .subcommand(create::cli_app())
         .subcommand(deposit::cli_app())
+        .subcommand(exit::cli_app())
+        .subcommand(export_deposit_data::cli_app())
+        .subcommand(import::cli_app())
+        .subcommand(recover::cli_app())
+        .subcommand(slashing_protection::cli_app())
 }
 
 pub fn cli_run<T: EthSpec>(matches: &ArgMatches, env: Environment<T>) -> Result<(), String> {
@@ -28,6 +38,15 @@ pub fn cli_run<T: EthSpec>(matches: &ArgMatches, env: Environment<T>) -> Result<
     match matches.subcommand() {
         (create::CMD, Some(matches)) => create::cli_run::<T>(matches, env, base_wallet_dir),
         (deposit::CMD, Some(matches)) => deposit::cli_run::<T>(matches, env),
+        (exit::CMD, Some(matches)) => exit::cli_run::<T>(matches, env),
+        (export_deposit_data::CMD, Some(matches)) => {
+            export_deposit_data::cli_run::<T>(matches, env)
+        }
+        (import::CMD, Some(matches)) => import::cli_run(matches),
+        (recover::CMD, Some(matches)) => recover::cli_run::<T>(matches, env),
+        (slashing_protection::CMD, Some(matches)) => {
+            slashing_protection::cli_run::<T>(matches, env)
+        }
         (unknown, _) => {
             return Err(format!(
                 "{} does not have a {} command. See --help",
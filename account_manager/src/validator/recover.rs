@@ -0,0 +1,183 @@
+use super::create::{COUNT_FLAG, DEPOSIT_GWEI_FLAG, STORE_WITHDRAW_FLAG};
+use crate::{
+    common::{
+        ensure_dir_exists, kdf_args, parse_kdf_params, random_password, read_mnemonic_from_cli,
+    },
+    SECRETS_DIR_FLAG, VALIDATOR_DIR_FLAG,
+};
+use clap::{App, Arg, ArgMatches};
+use environment::Environment;
+use eth2_keystore::{keypair_from_secret, KdfParams, Keystore, KeystoreBuilder, PlainText};
+use eth2_wallet::{bip39::Seed, recover_validator_secret_from_seed, KeyType};
+use std::path::PathBuf;
+use types::EthSpec;
+use validator_dir::Builder as ValidatorDirBuilder;
+
+pub const CMD: &str = "recover";
+pub const FIRST_INDEX_FLAG: &str = "first-index";
+pub const MNEMONIC_FLAG: &str = "mnemonic-path";
+
+pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
+    App::new(CMD)
+        .about(
+            "Recovers validator private keys from a BIP-39 mnemonic phrase, using the EIP-2333 \
+            HD key derivation scheme. By default, only the keys of the validator at index 0 \
+            are recovered, see --first-index and --count.",
+        )
+        .arg(
+            Arg::with_name(FIRST_INDEX_FLAG)
+                .long(FIRST_INDEX_FLAG)
+                .value_name("FIRST_INDEX")
+                .help("The first of consecutive key indexes you wish to recover.")
+                .takes_value(true)
+                .default_value("0"),
+        )
+        .arg(
+            Arg::with_name(COUNT_FLAG)
+                .long(COUNT_FLAG)
+                .value_name("COUNT")
+                .help(
+                    "The number of validator keys you wish to recover. Counted consecutively \
+                    from the provided --first-index.",
+                )
+                .takes_value(true)
+                .default_value("1"),
+        )
+        .arg(
+            Arg::with_name(MNEMONIC_FLAG)
+                .long(MNEMONIC_FLAG)
+                .value_name("MNEMONIC_PATH")
+                .help(
+                    "If present, the mnemonic will be read in from this file. Otherwise it is \
+                    read from stdin.",
+                )
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(VALIDATOR_DIR_FLAG)
+                .long(VALIDATOR_DIR_FLAG)
+                .value_name("VALIDATOR_DIRECTORY")
+                .help(
+                    "The path where the validator directories will be created. \
+                    Defaults to ~/.lighthouse/validators",
+                )
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(SECRETS_DIR_FLAG)
+                .long(SECRETS_DIR_FLAG)
+                .value_name("SECRETS_DIR")
+                .help(
+                    "The path where the validator keystore passwords will be stored. \
+                    Defaults to ~/.lighthouse/secrets",
+                )
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(DEPOSIT_GWEI_FLAG)
+                .long(DEPOSIT_GWEI_FLAG)
+                .value_name("DEPOSIT_GWEI")
+                .help(
+                    "The GWEI value of the deposit amount. Defaults to the minimum amount \
+                    required for an active validator (MAX_EFFECTIVE_BALANCE)",
+                )
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(STORE_WITHDRAW_FLAG)
+                .long(STORE_WITHDRAW_FLAG)
+                .help(
+                    "If present, the withdrawal keystore will be stored alongside the voting \
+                    keypair. It is generally recommended to *not* store the withdrawal key and \
+                    instead generate them from the wallet seed when required.",
+                ),
+        )
+        .args(&kdf_args(
+            "The key derivation function used to encrypt the keystores. Defaults to scrypt.",
+        ))
+}
+
+pub fn cli_run<T: EthSpec>(matches: &ArgMatches, mut env: Environment<T>) -> Result<(), String> {
+    let spec = env.core_context().eth2_config.spec;
+
+    let validator_dir = clap_utils::parse_path_with_default_in_home_dir(
+        matches,
+        VALIDATOR_DIR_FLAG,
+        PathBuf::new().join(".lighthouse").join("validators"),
+    )?;
+    let secrets_dir = clap_utils::parse_path_with_default_in_home_dir(
+        matches,
+        SECRETS_DIR_FLAG,
+        PathBuf::new().join(".lighthouse").join("secrets"),
+    )?;
+    let first_index: u32 = clap_utils::parse_required(matches, FIRST_INDEX_FLAG)?;
+    let count: u32 = clap_utils::parse_required(matches, COUNT_FLAG)?;
+    let mnemonic_path: Option<PathBuf> = clap_utils::parse_optional(matches, MNEMONIC_FLAG)?;
+    let deposit_gwei = clap_utils::parse_optional(matches, DEPOSIT_GWEI_FLAG)?
+        .unwrap_or_else(|| spec.max_effective_balance);
+    let kdf_params = parse_kdf_params(matches)?.unwrap_or_default();
+
+    let last_index = first_index
+        .checked_add(count)
+        .ok_or_else(|| format!("--{} + --{} is too large", FIRST_INDEX_FLAG, COUNT_FLAG))?;
+
+    ensure_dir_exists(&validator_dir)?;
+    ensure_dir_exists(&secrets_dir)?;
+
+    let mnemonic = read_mnemonic_from_cli(mnemonic_path)?;
+    let seed = Seed::new(&mnemonic, "");
+
+    for index in first_index..last_index {
+        let voting_password = random_password();
+        let withdrawal_password = random_password();
+
+        let voting = recover_keystore(
+            seed.as_bytes(),
+            index,
+            KeyType::Voting,
+            &voting_password,
+            &kdf_params,
+        )?;
+        let withdrawal = recover_keystore(
+            seed.as_bytes(),
+            index,
+            KeyType::Withdrawal,
+            &withdrawal_password,
+            &kdf_params,
+        )?;
+
+        let voting_pubkey = voting.pubkey().to_string();
+
+        ValidatorDirBuilder::new(validator_dir.clone(), secrets_dir.clone())
+            .voting_keystore(voting, voting_password.as_bytes())
+            .withdrawal_keystore(withdrawal, withdrawal_password.as_bytes())
+            .create_eth1_tx_data(deposit_gwei, &spec)
+            .store_withdrawal_keystore(matches.is_present(STORE_WITHDRAW_FLAG))
+            .build()
+            .map_err(|e| format!("Unable to build validator directory: {:?}", e))?;
+
+        println!("{}/{}\t0x{}", index - first_index + 1, count, voting_pubkey);
+    }
+
+    Ok(())
+}
+
+/// Derives the `key_type` secret key for the validator at `index` from the master `seed` and
+/// returns it in a keystore encrypted with `password`, via a KDF with the given `kdf_params`.
+fn recover_keystore(
+    seed: &[u8],
+    index: u32,
+    key_type: KeyType,
+    password: &PlainText,
+    kdf_params: &KdfParams,
+) -> Result<Keystore, String> {
+    let (secret, path) = recover_validator_secret_from_seed(seed, index, key_type)
+        .map_err(|e| format!("Unable to recover validator keys: {:?}", e))?;
+
+    let keypair = keypair_from_secret(secret.as_bytes())
+        .map_err(|e| format!("Unable to create keypair: {:?}", e))?;
+
+    KeystoreBuilder::new(&keypair, password.as_bytes(), format!("{}", path))
+        .and_then(|builder| builder.kdf_params(kdf_params).build())
+        .map_err(|e| format!("Unable to create keystore: {:?}", e))
+}
@@ -0,0 +1,171 @@
+use crate::VALIDATOR_DIR_FLAG;
+use clap::{App, Arg, ArgMatches};
+use environment::Environment;
+use slashing_protection::{
+    interchange::{Interchange, InterchangeFormat},
+    SlashingDatabase, SLASHING_PROTECTION_FILENAME,
+};
+use std::fs::File;
+use std::path::PathBuf;
+use types::EthSpec;
+
+pub const CMD: &str = "slashing-protection";
+pub const IMPORT_CMD: &str = "import";
+pub const EXPORT_CMD: &str = "export";
+
+pub const IMPORT_FILE_ARG: &str = "IMPORT-FILE";
+pub const EXPORT_FILE_ARG: &str = "EXPORT-FILE";
+pub const MINIMAL_FLAG: &str = "minimal";
+
+fn validator_dir_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name(VALIDATOR_DIR_FLAG)
+        .long(VALIDATOR_DIR_FLAG)
+        .value_name("VALIDATOR_DIRECTORY")
+        .help(
+            "The path the validator client data directory. \
+            Defaults to ~/.lighthouse/validators",
+        )
+        .takes_value(true)
+}
+
+pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
+    App::new(CMD)
+        .about("Import or export slashing protection data to or from another client")
+        .subcommand(
+            App::new(IMPORT_CMD)
+                .about(
+                    "Import an EIP-3076 slashing protection interchange file. Imported history \
+                    is merged with any existing history, so that the database only ever becomes \
+                    more restrictive.",
+                )
+                .arg(
+                    Arg::with_name(IMPORT_FILE_ARG)
+                        .takes_value(true)
+                        .value_name("FILE")
+                        .help("The slashing protection interchange file to import (.json)")
+                        .required(true),
+                )
+                .arg(validator_dir_arg()),
+        )
+        .subcommand(
+            App::new(EXPORT_CMD)
+                .about("Export an EIP-3076 slashing protection interchange file")
+                .arg(
+                    Arg::with_name(EXPORT_FILE_ARG)
+                        .takes_value(true)
+                        .value_name("FILE")
+                        .help("The filename to export the interchange file to")
+                        .required(true),
+                )
+                .arg(Arg::with_name(MINIMAL_FLAG).long(MINIMAL_FLAG).help(
+                    "Export only the most recent block slot and attestation epochs for \
+                            each validator, rather than the complete signing history.",
+                ))
+                .arg(validator_dir_arg()),
+        )
+}
+
+/// Returns the path to the slashing protection database, using the `--validator-dir` supplied to
+/// the `import` or `export` subcommand.
+fn slashing_protection_db_path(matches: &ArgMatches<'_>) -> Result<PathBuf, String> {
+    let validator_dir = clap_utils::parse_path_with_default_in_home_dir(
+        matches,
+        VALIDATOR_DIR_FLAG,
+        PathBuf::new().join(".lighthouse").join("validators"),
+    )?;
+    Ok(validator_dir.join(SLASHING_PROTECTION_FILENAME))
+}
+
+pub fn cli_run<T: EthSpec>(matches: &ArgMatches<'_>, env: Environment<T>) -> Result<(), String> {
+    let genesis_state = env
+        .testnet
+        .as_ref()
+        .ok_or_else(|| "Unable to run slashing protection without a testnet dir".to_string())?
+        .genesis_state
+        .as_ref()
+        .ok_or_else(|| {
+            "Unable to get genesis validators root, the testnet has not yet started".to_string()
+        })?;
+    let genesis_validators_root = genesis_state.genesis_validators_root;
+
+    match matches.subcommand() {
+        (IMPORT_CMD, Some(matches)) => {
+            let slashing_protection_db_path = slashing_protection_db_path(matches)?;
+            let import_filename: PathBuf = clap_utils::parse_required(matches, IMPORT_FILE_ARG)?;
+            let import_file = File::open(&import_filename).map_err(|e| {
+                format!(
+                    "Unable to open import file at {}: {:?}",
+                    import_filename.display(),
+                    e
+                )
+            })?;
+
+            let interchange = Interchange::from_json_reader(&import_file)
+                .map_err(|e| format!("Error parsing file for import: {:?}", e))?;
+
+            let slashing_protection_database =
+                SlashingDatabase::open_or_create(&slashing_protection_db_path).map_err(|e| {
+                    format!(
+                        "Unable to open database at {}: {:?}",
+                        slashing_protection_db_path.display(),
+                        e
+                    )
+                })?;
+
+            slashing_protection_database
+                .import_interchange_info(&interchange, genesis_validators_root)
+                .map_err(|e| format!("Error during import: {:?}", e))?;
+
+            eprintln!("Import completed successfully");
+
+            Ok(())
+        }
+        (EXPORT_CMD, Some(matches)) => {
+            let slashing_protection_db_path = slashing_protection_db_path(matches)?;
+            let export_filename: PathBuf = clap_utils::parse_required(matches, EXPORT_FILE_ARG)?;
+            let format = if matches.is_present(MINIMAL_FLAG) {
+                InterchangeFormat::Minimal
+            } else {
+                InterchangeFormat::Complete
+            };
+
+            if !slashing_protection_db_path.exists() {
+                return Err(format!(
+                    "No slashing protection database exists at: {}",
+                    slashing_protection_db_path.display()
+                ));
+            }
+
+            let slashing_protection_database = SlashingDatabase::open(&slashing_protection_db_path)
+                .map_err(|e| {
+                    format!(
+                        "Unable to open database at {}: {:?}",
+                        slashing_protection_db_path.display(),
+                        e
+                    )
+                })?;
+
+            let interchange = slashing_protection_database
+                .export_interchange_info(genesis_validators_root, format)
+                .map_err(|e| format!("Error during export: {:?}", e))?;
+
+            let output_file = File::create(&export_filename).map_err(|e| {
+                format!(
+                    "Error creating output file {}: {:?}",
+                    export_filename.display(),
+                    e
+                )
+            })?;
+
+            interchange
+                .write_to(&output_file)
+                .map_err(|e| format!("Error writing output file: {:?}", e))?;
+
+            eprintln!("Export completed successfully");
+
+            Ok(())
+        }
+        ("", _) => Err("No subcommand provided, see --help for options".to_string()),
+        (command, _) => Err(format!("No such subcommand `{}`", command)),
+    }
+}
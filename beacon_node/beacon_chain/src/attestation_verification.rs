@@ -332,6 +332,11 @@ impl<T: BeaconChainTypes> VerifiedAggregatedAttestation<T> {
             return Err(Error::InvalidSignature);
         }
 
+        // The attestation has a valid signature, so it can be checked for slashable votes.
+        if let Some(slasher) = chain.slasher.as_ref() {
+            slasher.accept_attestation(indexed_attestation.clone());
+        }
+
         // Observe the valid attestation so we do not re-process it.
         //
         // It's important to double check that the attestation is not already known, otherwise two
@@ -418,12 +423,16 @@ impl<T: BeaconChainTypes> VerifiedUnaggregatedAttestation<T> {
         /*
          * The attestation is the first valid attestation received for the participating validator
          * for the slot, attestation.data.slot.
+         *
+         * If a slasher is running, a repeated attestation may be slashable so its signature is
+         * verified before it is rejected.
          */
-        if chain
+        let prior_attestation_known = chain
             .observed_attesters
             .validator_has_been_observed(&attestation, validator_index as usize)
-            .map_err(|e| BeaconChainError::from(e))?
-        {
+            .map_err(|e| BeaconChainError::from(e))?;
+
+        if prior_attestation_known && chain.slasher.is_none() {
             return Err(Error::PriorAttestationKnown {
                 validator_index,
                 epoch: attestation.data.target.epoch,
@@ -433,6 +442,17 @@ impl<T: BeaconChainTypes> VerifiedUnaggregatedAttestation<T> {
         // The aggregate signature of the attestation is valid.
         verify_attestation_signature(chain, &indexed_attestation)?;
 
+        if let Some(slasher) = chain.slasher.as_ref() {
+            slasher.accept_attestation(indexed_attestation.clone());
+        }
+
+        if prior_attestation_known {
+            return Err(Error::PriorAttestationKnown {
+                validator_index,
+                epoch: attestation.data.target.epoch,
+            });
+        }
+
         // Now that the attestation has been fully verified, store that we have received a valid
         // attestation from this validator.
         //
@@ -482,7 +502,7 @@ impl<'a, T: BeaconChainTypes> ForkChoiceVerifiedAttestation<'a, T> {
     /// The supplied `indexed_attestation` MUST have a valid signature, this function WILL NOT
     /// CHECK THE SIGNATURE. Use the `VerifiedAggregatedAttestation` or
     /// `VerifiedUnaggregatedAttestation` structs to do signature verification.
-    fn from_signature_verified_components(
+    pub(crate) fn from_signature_verified_components(
         indexed_attestation: &'a IndexedAttestation<T::EthSpec>,
         chain: &BeaconChain<T>,
     ) -> Result<Self, Error> {
@@ -1,5 +1,6 @@
 use crate::attestation_verification::{
-    Error as AttestationError, ForkChoiceVerifiedAttestation, IntoForkChoiceVerifiedAttestation,
+    obtain_indexed_attestation, verify_attestation_signature, Error as AttestationError,
+    ForkChoiceVerifiedAttestation, IntoForkChoiceVerifiedAttestation,
     VerifiedAggregatedAttestation, VerifiedUnaggregatedAttestation,
 };
 use crate::block_verification::{
@@ -8,7 +9,7 @@ use crate::block_verification::{
 };
 use crate::errors::{BeaconChainError as Error, BlockProductionError};
 use crate::eth1_chain::{Eth1Chain, Eth1ChainBackend};
-use crate::events::{EventHandler, EventKind};
+use crate::events::{EventHandler, EventKind, EventType};
 use crate::fork_choice::{Error as ForkChoiceError, ForkChoice};
 use crate::head_tracker::HeadTracker;
 use crate::metrics;
@@ -17,6 +18,7 @@ use crate::naive_aggregation_pool::{Error as NaiveAggregationError, NaiveAggrega
 use crate::observed_attestations::{Error as AttestationObservationError, ObservedAttestations};
 use crate::observed_attesters::{ObservedAggregators, ObservedAttesters};
 use crate::observed_block_producers::ObservedBlockProducers;
+use crate::observed_operations::{ObservationOutcome, ObservedOperations};
 use crate::persisted_beacon_chain::PersistedBeaconChain;
 use crate::shuffling_cache::ShufflingCache;
 use crate::snapshot_cache::SnapshotCache;
@@ -25,13 +27,14 @@ use crate::validator_pubkey_cache::ValidatorPubkeyCache;
 use crate::BeaconSnapshot;
 use itertools::process_results;
 use operation_pool::{OperationPool, PersistedOperationPool};
+use slasher::Slasher;
 use slog::{crit, debug, error, info, trace, warn, Logger};
 use slot_clock::SlotClock;
-use state_processing::per_block_processing::errors::{
-    AttestationValidationError, AttesterSlashingValidationError, ExitValidationError,
-    ProposerSlashingValidationError,
+use state_processing::per_block_processing::errors::AttestationValidationError;
+use state_processing::{
+    common::get_indexed_attestation, per_block_processing, per_slot_processing,
+    BlockSignatureStrategy, SigVerifiedOp,
 };
-use state_processing::{per_block_processing, per_slot_processing, BlockSignatureStrategy};
 use std::borrow::Cow;
 use std::cmp::Ordering;
 use std::collections::HashMap;
@@ -184,6 +187,12 @@ pub struct BeaconChain<T: BeaconChainTypes> {
     pub observed_aggregators: ObservedAggregators<T::EthSpec>,
     /// Maintains a record of which validators have proposed blocks for each slot.
     pub observed_block_producers: ObservedBlockProducers<T::EthSpec>,
+    /// Maintains a record of which validators we've seen voluntary exits for.
+    pub observed_voluntary_exits: ObservedOperations<SignedVoluntaryExit, T::EthSpec>,
+    /// Maintains a record of which validators we've seen proposer slashings for.
+    pub observed_proposer_slashings: ObservedOperations<ProposerSlashing, T::EthSpec>,
+    /// Maintains a record of which validators we've seen attester slashings for.
+    pub observed_attester_slashings: ObservedOperations<AttesterSlashing<T::EthSpec>, T::EthSpec>,
     /// Provides information from the Ethereum 1 (PoW) chain.
     pub eth1_chain: Option<Eth1Chain<T::Eth1Chain, T::EthSpec, T::Store>>,
     /// Stores a "snapshot" of the chain at the time the head-of-the-chain block was received.
@@ -197,6 +206,8 @@ pub struct BeaconChain<T: BeaconChainTypes> {
     pub fork_choice: ForkChoice<T>,
     /// A handler for events generated by the beacon chain.
     pub event_handler: T::EventHandler,
+    /// An optional slasher, which is sent every verified attestation and block header.
+    pub slasher: Option<Arc<Slasher<T::EthSpec>>>,
     /// Used to track the heads of the beacon chain.
     pub(crate) head_tracker: Arc<HeadTracker>,
     /// A cache dedicated to block processing.
@@ -732,6 +743,19 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         self.naive_aggregation_pool.get(data).map_err(Into::into)
     }
 
+    /// Returns an aggregated `Attestation`, if any, that has a matching `attestation.data.slot`
+    /// and a `tree_hash_root` of `attestation.data` equal to `data_root`.
+    ///
+    /// The attestation will be obtained from `self.naive_aggregation_pool`.
+    pub fn get_aggregated_attestation_by_slot_and_root(
+        &self,
+        slot: Slot,
+        data_root: &Hash256,
+    ) -> Option<Attestation<T::EthSpec>> {
+        self.naive_aggregation_pool
+            .get_by_slot_and_root(slot, data_root)
+    }
+
     /// Produce an unaggregated `Attestation` that is valid for the given `slot` and `index`.
     ///
     /// The produced `Attestation` will not be valid until it has been signed by exactly one
@@ -905,6 +929,27 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         Ok(verified)
     }
 
+    /// Accepts an `attestation` that was not received from the gossip network (e.g., one supplied
+    /// by a fork choice test vector), verifies its signature and applies it to `self.fork_choice`.
+    ///
+    /// Unlike the gossip verification functions, this does not require the attestation to be
+    /// within the gossip propagation range or to be unaggregated.
+    pub fn process_attestation_for_fork_choice(
+        &self,
+        attestation: &Attestation<T::EthSpec>,
+    ) -> Result<(), AttestationError> {
+        let indexed_attestation = obtain_indexed_attestation(self, attestation)?;
+        verify_attestation_signature(self, &indexed_attestation)?;
+
+        let verified = ForkChoiceVerifiedAttestation::from_signature_verified_components(
+            &indexed_attestation,
+            self,
+        )?;
+        self.apply_attestation_to_fork_choice(&verified)?;
+
+        Ok(())
+    }
+
     /// Accepts an `VerifiedUnaggregatedAttestation` and attempts to apply it to the "naive
     /// aggregation pool".
     ///
@@ -922,13 +967,27 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         let attestation = unaggregated_attestation.attestation();
 
         match self.naive_aggregation_pool.insert(attestation) {
-            Ok(outcome) => trace!(
-                self.log,
-                "Stored unaggregated attestation";
-                "outcome" => format!("{:?}", outcome),
-                "index" => attestation.data.index,
-                "slot" => attestation.data.slot.as_u64(),
-            ),
+            Ok(outcome) => {
+                trace!(
+                    self.log,
+                    "Stored unaggregated attestation";
+                    "outcome" => format!("{:?}", outcome),
+                    "index" => attestation.data.index,
+                    "slot" => attestation.data.slot.as_u64(),
+                );
+
+                // Attestations are frequent, so avoid cloning them when nobody is listening.
+                if self
+                    .event_handler
+                    .has_subscribers(EventType::BeaconAttestationImported)
+                {
+                    let _ = self
+                        .event_handler
+                        .register(EventKind::BeaconAttestationImported {
+                            attestation: Box::new(attestation.clone()),
+                        });
+                }
+            }
             Err(NaiveAggregationError::SlotTooLow {
                 slot,
                 lowest_permissible_slot,
@@ -986,6 +1045,17 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                 .map_err(Error::from)?;
         }
 
+        if self
+            .event_handler
+            .has_subscribers(EventType::BeaconAttestationImported)
+        {
+            let _ = self
+                .event_handler
+                .register(EventKind::BeaconAttestationImported {
+                    attestation: Box::new(signed_aggregate.attestation().clone()),
+                });
+        }
+
         Ok(signed_aggregate)
     }
 
@@ -1059,81 +1129,83 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         }
     }
 
-    /// Accept some exit and queue it for inclusion in an appropriate block.
-    pub fn process_voluntary_exit(
+    /// Verify a voluntary exit received from the network (or HTTP API) for gossip propagation.
+    ///
+    /// Returns `ObservationOutcome::AlreadyKnown` if an exit for the same validator has already
+    /// been verified, in which case the exit should be neither propagated nor imported.
+    pub fn verify_voluntary_exit_for_gossip(
         &self,
         exit: SignedVoluntaryExit,
-    ) -> Result<(), ExitValidationError> {
-        match self.wall_clock_state() {
-            Ok(state) => {
-                if self.eth1_chain.is_some() {
-                    self.op_pool.insert_voluntary_exit(exit, &state, &self.spec)
-                } else {
-                    Ok(())
-                }
-            }
-            Err(e) => {
-                error!(
-                    &self.log,
-                    "Unable to process voluntary exit";
-                    "error" => format!("{:?}", e),
-                    "reason" => "no state"
-                );
-                Ok(())
-            }
+    ) -> Result<ObservationOutcome<SignedVoluntaryExit>, Error> {
+        // NOTE: this could be more efficient if it avoided cloning the head state
+        let wall_clock_state = self.wall_clock_state()?;
+        Ok(self
+            .observed_voluntary_exits
+            .verify_and_observe(exit, &wall_clock_state, &self.spec)?)
+    }
+
+    /// Accept a pre-verified exit and queue it for inclusion in an appropriate block.
+    pub fn import_voluntary_exit(&self, exit: SigVerifiedOp<SignedVoluntaryExit>) {
+        let _ = self
+            .event_handler
+            .register(EventKind::BeaconVoluntaryExitImported {
+                voluntary_exit: Box::new(exit.as_inner().clone()),
+            });
+
+        if self.eth1_chain.is_some() {
+            self.op_pool.insert_voluntary_exit(exit)
         }
     }
 
-    /// Accept some proposer slashing and queue it for inclusion in an appropriate block.
-    pub fn process_proposer_slashing(
+    /// Verify a proposer slashing received from the network (or HTTP API) for gossip
+    /// propagation.
+    pub fn verify_proposer_slashing_for_gossip(
         &self,
         proposer_slashing: ProposerSlashing,
-    ) -> Result<(), ProposerSlashingValidationError> {
-        match self.wall_clock_state() {
-            Ok(state) => {
-                if self.eth1_chain.is_some() {
-                    self.op_pool
-                        .insert_proposer_slashing(proposer_slashing, &state, &self.spec)
-                } else {
-                    Ok(())
-                }
-            }
-            Err(e) => {
-                error!(
-                    &self.log,
-                    "Unable to process proposer slashing";
-                    "error" => format!("{:?}", e),
-                    "reason" => "no state"
-                );
-                Ok(())
-            }
+    ) -> Result<ObservationOutcome<ProposerSlashing>, Error> {
+        let wall_clock_state = self.wall_clock_state()?;
+        Ok(self.observed_proposer_slashings.verify_and_observe(
+            proposer_slashing,
+            &wall_clock_state,
+            &self.spec,
+        )?)
+    }
+
+    /// Accept some proposer slashing and queue it for inclusion in an appropriate block.
+    pub fn import_proposer_slashing(&self, proposer_slashing: SigVerifiedOp<ProposerSlashing>) {
+        if self.eth1_chain.is_some() {
+            self.op_pool.insert_proposer_slashing(proposer_slashing)
         }
     }
 
-    /// Accept some attester slashing and queue it for inclusion in an appropriate block.
-    pub fn process_attester_slashing(
+    /// Verify an attester slashing received from the network (or HTTP API) for gossip
+    /// propagation.
+    pub fn verify_attester_slashing_for_gossip(
         &self,
         attester_slashing: AttesterSlashing<T::EthSpec>,
-    ) -> Result<(), AttesterSlashingValidationError> {
-        match self.wall_clock_state() {
-            Ok(state) => {
-                if self.eth1_chain.is_some() {
-                    self.op_pool
-                        .insert_attester_slashing(attester_slashing, &state, &self.spec)
-                } else {
-                    Ok(())
-                }
-            }
-            Err(e) => {
-                error!(
-                    &self.log,
-                    "Unable to process attester slashing";
-                    "error" => format!("{:?}", e),
-                    "reason" => "no state"
-                );
-                Ok(())
-            }
+    ) -> Result<ObservationOutcome<AttesterSlashing<T::EthSpec>>, Error> {
+        let wall_clock_state = self.wall_clock_state()?;
+        Ok(self.observed_attester_slashings.verify_and_observe(
+            attester_slashing,
+            &wall_clock_state,
+            &self.spec,
+        )?)
+    }
+
+    /// Accept some attester slashing and queue it for inclusion in an appropriate block.
+    pub fn import_attester_slashing(
+        &self,
+        attester_slashing: SigVerifiedOp<AttesterSlashing<T::EthSpec>>,
+    ) -> Result<(), Error> {
+        if self.eth1_chain.is_some() {
+            self.op_pool.insert_attester_slashing(
+                attester_slashing,
+                &self.head_info()?.fork,
+                self.genesis_validators_root,
+                &self.spec,
+            )
         }
+        Ok(())
     }
 
     /// Attempt to verify and import a chain of blocks to `self`.
@@ -1443,6 +1515,32 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
 
         metrics::stop_timer(attestation_observation_timer);
 
+        // All of the signatures in the block have been verified, so its header and attestations
+        // can be checked for slashable behaviour.
+        if let Some(slasher) = self.slasher.as_ref() {
+            slasher.accept_block_header(signed_block.signed_block_header());
+
+            for attestation in &block.body.attestations {
+                let indexed_attestation = state
+                    .get_beacon_committee(attestation.data.slot, attestation.data.index)
+                    .map_err(Error::from)
+                    .and_then(|committee| {
+                        get_indexed_attestation(committee.committee, attestation)
+                            .map_err(Error::from)
+                    });
+
+                match indexed_attestation {
+                    Ok(indexed_attestation) => slasher.accept_attestation(indexed_attestation),
+                    Err(e) => debug!(
+                        self.log,
+                        "Unable to send block attestation to slasher";
+                        "error" => format!("{:?}", e),
+                        "block_root" => format!("{}", block_root),
+                    ),
+                }
+            }
+        }
+
         let fork_choice_register_timer =
             metrics::start_timer(&metrics::BLOCK_PROCESSING_FORK_CHOICE_REGISTER);
 
@@ -1760,6 +1858,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
 
         if is_reorg {
             metrics::inc_counter(&metrics::FORK_CHOICE_REORG_COUNT);
+            let depth = self.reorg_depth(current_head.block_root, current_head.slot, &new_head);
             warn!(
                 self.log,
                 "Beacon chain re-org";
@@ -1767,8 +1866,22 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                 "previous_slot" => current_head.slot,
                 "new_head_parent" => format!("{}", new_head.beacon_block.parent_root()),
                 "new_head" => format!("{}", beacon_block_root),
-                "new_slot" => new_head.beacon_block.slot()
+                "new_slot" => new_head.beacon_block.slot(),
+                "depth" => depth,
             );
+
+            let _ = self.event_handler.register(EventKind::BeaconChainReorg {
+                slot: new_head.beacon_block.slot(),
+                depth,
+                old_head_block: current_head.block_root,
+                new_head_block: beacon_block_root,
+                old_head_state: current_head.state_root,
+                new_head_state: new_head.beacon_state_root,
+                epoch: new_head
+                    .beacon_block
+                    .slot()
+                    .epoch(T::EthSpec::slots_per_epoch()),
+            });
         } else {
             debug!(
                 self.log,
@@ -1804,6 +1917,9 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             self.persist_head_and_fork_choice()?;
         }
 
+        let new_head_slot = new_head.beacon_block.slot();
+        let new_head_state_root = new_head.beacon_state_root;
+
         let update_head_timer = metrics::start_timer(&metrics::UPDATE_HEAD_TIMES);
 
         // Update the snapshot that stores the head of the chain at the time it received the
@@ -1839,13 +1955,66 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
 
         let _ = self.event_handler.register(EventKind::BeaconHeadChanged {
             reorg: is_reorg,
+            slot: new_head_slot,
             previous_head_beacon_block_root: current_head.block_root,
             current_head_beacon_block_root: beacon_block_root,
+            current_head_beacon_state_root: new_head_state_root,
         });
 
         Ok(())
     }
 
+    /// Returns the number of slots between `old_head_slot` and the most recent ancestor of
+    /// `old_head_root` which is also an ancestor of `new_head`.
+    ///
+    /// If no common ancestor can be found within the block roots of `new_head.beacon_state` the
+    /// distance to the earliest slot checked is returned.
+    fn reorg_depth(
+        &self,
+        old_head_root: Hash256,
+        old_head_slot: Slot,
+        new_head: &BeaconSnapshot<T::EthSpec>,
+    ) -> u64 {
+        let new_head_state = &new_head.beacon_state;
+        let mut ancestor_slot = old_head_slot;
+
+        let iter = match self.rev_iter_block_roots_from(old_head_root) {
+            Ok(iter) => iter,
+            Err(e) => {
+                warn!(
+                    self.log,
+                    "Unable to determine re-org depth";
+                    "error" => format!("{:?}", e),
+                );
+                return 0;
+            }
+        };
+
+        for (root, slot) in iter.filter_map(Result::ok) {
+            ancestor_slot = slot;
+
+            let new_chain_root = if slot > new_head_state.slot {
+                continue;
+            } else if slot == new_head_state.slot {
+                new_head.beacon_block_root
+            } else {
+                match new_head_state.get_block_root(slot) {
+                    Ok(block_root) => *block_root,
+                    // The slot is too old to be in the block roots of the new head.
+                    Err(_) => break,
+                }
+            };
+
+            if new_chain_root == root {
+                break;
+            }
+        }
+
+        old_head_slot
+            .as_u64()
+            .saturating_sub(ancestor_slot.as_u64())
+    }
+
     /// Called by the timer on every slot.
     ///
     /// Performs slot-based pruning.
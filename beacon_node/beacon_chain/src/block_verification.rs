@@ -308,11 +308,15 @@ impl<T: BeaconChainTypes> GossipVerifiedBlock<T> {
         check_block_against_finalized_slot(&block.message, chain)?;
 
         // Check that we have not already received a block with a valid signature for this slot.
-        if chain
+        //
+        // If a slasher is running, a repeated proposal may be slashable so its signature is
+        // verified before it is rejected.
+        let proposer_already_observed = chain
             .observed_block_producers
             .proposer_has_been_observed(&block.message)
-            .map_err(|e| BlockError::BeaconChainError(e.into()))?
-        {
+            .map_err(|e| BlockError::BeaconChainError(e.into()))?;
+
+        if proposer_already_observed && chain.slasher.is_none() {
             return Err(BlockError::RepeatProposal {
                 proposer: block.message.proposer_index,
                 slot: block.message.slot,
@@ -346,15 +350,20 @@ impl<T: BeaconChainTypes> GossipVerifiedBlock<T> {
             return Err(BlockError::ProposalSignatureInvalid);
         }
 
+        if let Some(slasher) = chain.slasher.as_ref() {
+            slasher.accept_block_header(block.signed_block_header());
+        }
+
         // Now the signature is valid, store the proposal so we don't accept another from this
         // validator and slot.
         //
         // It's important to double-check that the proposer still hasn't been observed so we don't
         // have a race-condition when verifying two blocks simultaneously.
-        if chain
-            .observed_block_producers
-            .observe_proposer(&block.message)
-            .map_err(|e| BlockError::BeaconChainError(e.into()))?
+        if proposer_already_observed
+            || chain
+                .observed_block_producers
+                .observe_proposer(&block.message)
+                .map_err(|e| BlockError::BeaconChainError(e.into()))?
         {
             return Err(BlockError::RepeatProposal {
                 proposer: block.message.proposer_index,
@@ -565,7 +574,7 @@ impl<T: BeaconChainTypes> FullyVerifiedBlock<T> {
         let distance = block.slot().as_u64().saturating_sub(state.slot.as_u64());
         for i in 0..distance {
             let state_root = if i == 0 {
-                parent.beacon_block.state_root()
+                parent.beacon_state_root
             } else {
                 // This is a new state we've reached, so stage it for storage in the DB.
                 // Computing the state root here is time-equivalent to computing it during slot
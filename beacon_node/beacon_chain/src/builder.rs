@@ -18,13 +18,14 @@ use crate::{
 use eth1::Config as Eth1Config;
 use operation_pool::{OperationPool, PersistedOperationPool};
 use proto_array_fork_choice::ProtoArrayForkChoice;
+use slasher::Slasher;
 use slog::{info, Logger};
 use slot_clock::{SlotClock, TestingSlotClock};
 use std::marker::PhantomData;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
-use store::Store;
+use store::{AnchorInfo, Store};
 use types::{
     BeaconBlock, BeaconState, ChainSpec, EthSpec, Hash256, Signature, SignedBeaconBlock, Slot,
 };
@@ -82,6 +83,7 @@ pub struct BeaconChainBuilder<T: BeaconChainTypes> {
     fork_choice: Option<ForkChoice<T>>,
     eth1_chain: Option<Eth1Chain<T::Eth1Chain, T::EthSpec, T::Store>>,
     event_handler: Option<T::EventHandler>,
+    slasher: Option<Arc<Slasher<T::EthSpec>>>,
     slot_clock: Option<T::SlotClock>,
     head_tracker: Option<HeadTracker>,
     data_dir: Option<PathBuf>,
@@ -119,6 +121,7 @@ where
             fork_choice: None,
             eth1_chain: None,
             event_handler: None,
+            slasher: None,
             slot_clock: None,
             head_tracker: None,
             pubkey_cache_path: None,
@@ -340,6 +343,127 @@ where
         Ok(self.empty_op_pool())
     }
 
+    /// Starts a new chain from a trusted, finalized "weak subjectivity" checkpoint instead of
+    /// genesis.
+    ///
+    /// The `anchor_state` must lie on an epoch boundary and must be the post-state of
+    /// `anchor_block`, advanced through any skipped slots up to that boundary. The
+    /// `genesis_state` is only used to determine the genesis block root.
+    ///
+    /// The store will not contain any blocks or states prior to the anchor (other than the
+    /// genesis block).
+    pub fn weak_subjectivity_state(
+        mut self,
+        mut anchor_state: BeaconState<TEthSpec>,
+        anchor_block: SignedBeaconBlock<TEthSpec>,
+        mut genesis_state: BeaconState<TEthSpec>,
+    ) -> Result<Self, String> {
+        let store = self
+            .store
+            .clone()
+            .ok_or_else(|| "weak_subjectivity_state requires a store")?;
+        let log = self
+            .log
+            .as_ref()
+            .ok_or_else(|| "weak_subjectivity_state requires a log".to_string())?;
+
+        let anchor_slot = anchor_state.slot;
+
+        // The hot database only stores full states on epoch boundaries, so the anchor state must
+        // be one of them.
+        if anchor_slot % TEthSpec::slots_per_epoch() != 0 {
+            return Err(format!(
+                "Checkpoint state at slot {} is not on an epoch boundary",
+                anchor_slot
+            ));
+        }
+
+        if anchor_block.slot() > anchor_slot {
+            return Err(format!(
+                "Checkpoint block slot ({}) is later than checkpoint state slot ({})",
+                anchor_block.slot(),
+                anchor_slot
+            ));
+        }
+
+        if anchor_state.genesis_validators_root != genesis_state.genesis_validators_root {
+            return Err("Checkpoint state is not from the same chain as genesis".to_string());
+        }
+
+        anchor_state
+            .build_all_caches(&self.spec)
+            .map_err(|e| format!("Failed to build checkpoint state caches: {:?}", e))?;
+
+        let anchor_state_root = anchor_state
+            .update_tree_hash_cache()
+            .map_err(|e| format!("Error hashing checkpoint state: {:?}", e))?;
+
+        let anchor_block_root = anchor_block.canonical_root();
+
+        // The latest block header only has its state root filled in once the state has been
+        // advanced past the block's slot.
+        let mut latest_block_header = anchor_state.latest_block_header.clone();
+        if latest_block_header.state_root == Hash256::zero() {
+            latest_block_header.state_root = anchor_state_root;
+        }
+        if latest_block_header.canonical_root() != anchor_block_root {
+            return Err(format!(
+                "Checkpoint state ({:?}) is not a post-state of checkpoint block ({:?})",
+                anchor_state_root, anchor_block_root
+            ));
+        }
+
+        let genesis_block = genesis_block(&mut genesis_state, &self.spec)?;
+        let genesis_block_root = genesis_block.canonical_root();
+
+        info!(
+            log,
+            "Starting beacon chain";
+            "method" => "weak subjectivity checkpoint",
+            "slot" => anchor_slot,
+            "block_root" => format!("{:?}", anchor_block_root),
+        );
+
+        self.genesis_block_root = Some(genesis_block_root);
+
+        let anchor_info = AnchorInfo {
+            anchor_slot,
+            oldest_block_slot: anchor_block.slot(),
+            oldest_block_parent: anchor_block.parent_root(),
+        };
+        store
+            .init_anchor(anchor_state_root, anchor_block_root, anchor_info)
+            .map_err(|e| format!("Failed to set database anchor to checkpoint: {:?}", e))?;
+        store
+            .put_state(&anchor_state_root, &anchor_state)
+            .map_err(|e| format!("Failed to store checkpoint state: {:?}", e))?;
+        store
+            .put_item(&anchor_block_root, &anchor_block)
+            .map_err(|e| format!("Failed to store checkpoint block: {:?}", e))?;
+        store
+            .put_item(&genesis_block_root, &genesis_block)
+            .map_err(|e| format!("Failed to store genesis block: {:?}", e))?;
+
+        // Store the genesis block under the `ZERO_HASH` key.
+        store
+            .put_item(&Hash256::zero(), &genesis_block)
+            .map_err(|e| {
+                format!(
+                    "Failed to store genesis block under 0x00..00 alias: {:?}",
+                    e
+                )
+            })?;
+
+        self.finalized_snapshot = Some(BeaconSnapshot {
+            beacon_block_root: anchor_block_root,
+            beacon_block: anchor_block,
+            beacon_state_root: anchor_state_root,
+            beacon_state: anchor_state,
+        });
+
+        Ok(self.empty_op_pool())
+    }
+
     /// Sets the `BeaconChain` eth1 backend.
     pub fn eth1_backend(mut self, backend: Option<TEth1Backend>) -> Self {
         self.eth1_chain = backend.map(Eth1Chain::new);
@@ -354,6 +478,12 @@ where
         self
     }
 
+    /// Sets the slasher which will be sent every verified attestation and block header.
+    pub fn slasher(mut self, slasher: Arc<Slasher<TEthSpec>>) -> Self {
+        self.slasher = Some(slasher);
+        self
+    }
+
     /// Sets the `BeaconChain` slot clock.
     ///
     /// For example, provide `SystemTimeSlotClock` as a `clock`.
@@ -401,7 +531,11 @@ where
             .build_all_caches(&self.spec)
             .map_err(|e| format!("Failed to build state caches: {:?}", e))?;
 
-        if canonical_head.beacon_block.state_root() != canonical_head.beacon_state_root {
+        // A chain started from a weak subjectivity checkpoint may have a head state which was
+        // advanced through skipped slots beyond the post-state of the head block.
+        if canonical_head.beacon_block.state_root() != canonical_head.beacon_state_root
+            && canonical_head.beacon_block.slot() == canonical_head.beacon_state.slot
+        {
             return Err("beacon_block.state_root != beacon_state".to_string());
         }
 
@@ -441,6 +575,10 @@ where
             observed_aggregators: <_>::default(),
             // TODO: allow for persisting and loading the pool from disk.
             observed_block_producers: <_>::default(),
+            // TODO: allow for persisting and loading the pool from disk.
+            observed_voluntary_exits: <_>::default(),
+            observed_proposer_slashings: <_>::default(),
+            observed_attester_slashings: <_>::default(),
             eth1_chain: self.eth1_chain,
             genesis_validators_root: canonical_head.beacon_state.genesis_validators_root,
             canonical_head: TimeoutRwLock::new(canonical_head.clone()),
@@ -453,6 +591,7 @@ where
             event_handler: self
                 .event_handler
                 .ok_or_else(|| "Cannot build without an event handler".to_string())?,
+            slasher: self.slasher,
             head_tracker: Arc::new(self.head_tracker.unwrap_or_default()),
             snapshot_cache: TimeoutRwLock::new(SnapshotCache::new(
                 DEFAULT_SNAPSHOT_CACHE_SIZE,
@@ -514,9 +653,6 @@ where
                 .finalized_snapshot
                 .as_ref()
                 .ok_or_else(|| "reduced_tree_fork_choice requires a finalized_snapshot")?;
-            let genesis_block_root = self
-                .genesis_block_root
-                .ok_or_else(|| "reduced_tree_fork_choice requires a genesis_block_root")?;
 
             let backend = ProtoArrayForkChoice::new(
                 finalized_snapshot.beacon_block.message.slot,
@@ -530,9 +666,11 @@ where
                 finalized_snapshot.beacon_block_root,
             )?;
 
+            // The fork choice is rooted at the finalized block, which is only the genesis block
+            // when the chain did not start from a weak subjectivity checkpoint.
             ForkChoice::new(
                 backend,
-                genesis_block_root,
+                finalized_snapshot.beacon_block_root,
                 &finalized_snapshot.beacon_state,
             )
         };
@@ -751,6 +889,83 @@ mod test {
         );
     }
 
+    #[test]
+    fn weak_subjectivity_anchor() {
+        let validator_count = 8;
+        let genesis_time = 13_371_337;
+
+        let log = get_logger();
+        let store = Arc::new(MemoryStore::open());
+        let spec = MinimalEthSpec::default_spec();
+        let data_dir = tempdir().expect("should create temporary data_dir");
+
+        let genesis_state = interop_genesis_state(
+            &generate_deterministic_keypairs(validator_count),
+            genesis_time,
+            &spec,
+        )
+        .expect("should create interop genesis state");
+
+        let mut anchor_state = genesis_state.clone();
+        let anchor_block = genesis_block(&mut anchor_state, &spec).expect("should build block");
+
+        // The anchor must lie on an epoch boundary.
+        let mut unaligned_state = anchor_state.clone();
+        unaligned_state.slot += 1;
+        assert!(
+            BeaconChainBuilder::new(MinimalEthSpec)
+                .logger(log.clone())
+                .store(store.clone())
+                .store_migrator(NullMigrator)
+                .data_dir(data_dir.path().to_path_buf())
+                .weak_subjectivity_state(
+                    unaligned_state,
+                    anchor_block.clone(),
+                    genesis_state.clone()
+                )
+                .is_err(),
+            "should reject an anchor which is not on an epoch boundary"
+        );
+
+        let chain = BeaconChainBuilder::new(MinimalEthSpec)
+            .logger(log.clone())
+            .store(store)
+            .store_migrator(NullMigrator)
+            .data_dir(data_dir.path().to_path_buf())
+            .weak_subjectivity_state(anchor_state, anchor_block.clone(), genesis_state)
+            .expect("should build state from checkpoint")
+            .dummy_eth1_backend()
+            .expect("should build the dummy eth1 backend")
+            .null_event_handler()
+            .testing_slot_clock(Duration::from_secs(1))
+            .expect("should configure testing slot clock")
+            .reduced_tree_fork_choice()
+            .expect("should add fork choice to builder")
+            .build()
+            .expect("should build");
+
+        let head = chain.head().expect("should get head");
+
+        assert_eq!(
+            head.beacon_block_root,
+            anchor_block.canonical_root(),
+            "should start from the anchor block"
+        );
+        assert_eq!(
+            chain.fork_choice.finalized_checkpoint().root,
+            anchor_block.canonical_root(),
+            "fork choice should be rooted at the anchor block"
+        );
+        assert!(
+            chain
+                .store
+                .get_anchor_info()
+                .expect("should store anchor info")
+                .block_backfill_complete(),
+            "a genesis anchor should have no blocks to back-fill"
+        );
+    }
+
     #[test]
     fn interop_state() {
         let validator_count = 16;
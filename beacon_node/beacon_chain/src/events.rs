@@ -1,68 +1,158 @@
-use bus::Bus;
+use crate::metrics;
+use futures::channel::mpsc::{channel, Receiver, Sender};
 use parking_lot::Mutex;
 use serde_derive::{Deserialize, Serialize};
-use slog::{error, Logger};
 use std::marker::PhantomData;
 use std::sync::Arc;
-use types::{Attestation, Epoch, EthSpec, Hash256, SignedBeaconBlock, SignedBeaconBlockHash};
+use types::{Attestation, Epoch, EthSpec, Hash256, SignedBeaconBlock, SignedVoluntaryExit, Slot};
 pub use websocket_server::WebSocketSender;
 
 pub trait EventHandler<T: EthSpec>: Sized + Send + Sync {
     fn register(&self, kind: EventKind<T>) -> Result<(), String>;
+
+    /// Returns `true` if an event of `event_type` passed to `register` would be delivered to at
+    /// least one consumer.
+    ///
+    /// Allows expensive events to be skipped entirely when nobody is listening.
+    fn has_subscribers(&self, event_type: EventType) -> bool;
 }
 
 pub struct NullEventHandler<T: EthSpec>(PhantomData<T>);
 
 impl<T: EthSpec> EventHandler<T> for WebSocketSender<T> {
     fn register(&self, kind: EventKind<T>) -> Result<(), String> {
+        if kind.event_type() == EventType::BeaconAttestationImported {
+            return Ok(());
+        }
+
         self.send_string(
             serde_json::to_string(&kind)
                 .map_err(|e| format!("Unable to serialize event: {:?}", e))?,
         )
     }
+
+    fn has_subscribers(&self, event_type: EventType) -> bool {
+        // Imported attestations are only available via SSE, since broadcasting every attestation
+        // to all websocket clients is too costly.
+        event_type != EventType::BeaconAttestationImported && self.is_enabled()
+    }
+}
+
+/// The maximum number of events queued for each subscriber of `EventSubscribers`.
+///
+/// Events are dropped for a subscriber whose queue is full, so a slow subscriber cannot delay the
+/// beacon chain or other subscribers.
+pub const SUBSCRIBER_QUEUE_CAPACITY: usize = 256;
+
+/// The maximum number of concurrent subscribers of `EventSubscribers`.
+pub const MAX_SUBSCRIBERS: usize = 64;
+
+struct Subscriber<T: EthSpec> {
+    event_types: Vec<EventType>,
+    sender: Sender<EventKind<T>>,
+}
+
+/// Distributes events to any number of subscribers, each with its own bounded queue.
+pub struct EventSubscribers<T: EthSpec> {
+    subscribers: Mutex<Vec<Subscriber<T>>>,
+}
+
+impl<T: EthSpec> Default for EventSubscribers<T> {
+    fn default() -> Self {
+        Self {
+            subscribers: Mutex::new(vec![]),
+        }
+    }
+}
+
+impl<T: EthSpec> EventSubscribers<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Subscribe to all future events with one of the given `event_types`.
+    ///
+    /// The subscription is cancelled when the returned `Receiver` is dropped. Returns `None` if
+    /// there are already `MAX_SUBSCRIBERS` subscribers.
+    pub fn subscribe(&self, event_types: Vec<EventType>) -> Option<Receiver<EventKind<T>>> {
+        let mut subscribers = self.subscribers.lock();
+        subscribers.retain(|subscriber| !subscriber.sender.is_closed());
+
+        if subscribers.len() >= MAX_SUBSCRIBERS {
+            return None;
+        }
+
+        let (sender, receiver) = channel(SUBSCRIBER_QUEUE_CAPACITY);
+        subscribers.push(Subscriber {
+            event_types,
+            sender,
+        });
+        Some(receiver)
+    }
+
+    /// Returns the number of subscribers which have not yet unsubscribed.
+    pub fn num_subscribers(&self) -> usize {
+        self.subscribers
+            .lock()
+            .iter()
+            .filter(|subscriber| !subscriber.sender.is_closed())
+            .count()
+    }
+
+    /// Returns `true` if any subscriber is subscribed to events of `event_type`.
+    pub fn has_subscribers(&self, event_type: EventType) -> bool {
+        self.subscribers.lock().iter().any(|subscriber| {
+            !subscriber.sender.is_closed() && subscriber.event_types.contains(&event_type)
+        })
+    }
+
+    /// Queue `kind` for every interested subscriber, removing any which have unsubscribed.
+    pub fn publish(&self, kind: &EventKind<T>) {
+        let event_type = kind.event_type();
+        let mut subscribers = self.subscribers.lock();
+
+        for subscriber in subscribers
+            .iter_mut()
+            .filter(|subscriber| subscriber.event_types.contains(&event_type))
+        {
+            if let Err(e) = subscriber.sender.try_send(kind.clone()) {
+                if e.is_full() {
+                    metrics::inc_counter(&metrics::EVENT_SUBSCRIBER_DROPPED_EVENTS);
+                }
+            }
+        }
+
+        subscribers.retain(|subscriber| !subscriber.sender.is_closed());
+    }
 }
 
 pub struct ServerSentEvents<T: EthSpec> {
-    // Bus<> is itself Sync + Send.  We use Mutex<> here only because of the surrounding code does
-    // not enforce mutability statically (i.e. relies on interior mutability).
-    head_changed_queue: Arc<Mutex<Bus<SignedBeaconBlockHash>>>,
-    log: Logger,
-    _phantom: PhantomData<T>,
+    subscribers: Arc<EventSubscribers<T>>,
 }
 
 impl<T: EthSpec> ServerSentEvents<T> {
-    pub fn new(log: Logger) -> (Self, Arc<Mutex<Bus<SignedBeaconBlockHash>>>) {
-        let bus = Bus::new(T::slots_per_epoch() as usize);
-        let mutex = Mutex::new(bus);
-        let arc = Arc::new(mutex);
+    pub fn new() -> (Self, Arc<EventSubscribers<T>>) {
+        let subscribers = Arc::new(EventSubscribers::new());
         let this = Self {
-            head_changed_queue: arc.clone(),
-            log: log,
-            _phantom: PhantomData,
+            subscribers: subscribers.clone(),
         };
-        (this, arc)
+        (this, subscribers)
+    }
+
+    /// Returns the subscribers to which events are published.
+    pub fn subscribers(&self) -> &Arc<EventSubscribers<T>> {
+        &self.subscribers
     }
 }
 
 impl<T: EthSpec> EventHandler<T> for ServerSentEvents<T> {
     fn register(&self, kind: EventKind<T>) -> Result<(), String> {
-        match kind {
-            EventKind::BeaconHeadChanged {
-                current_head_beacon_block_root,
-                ..
-            } => {
-                let mut guard = self.head_changed_queue.lock();
-                if let Err(_) = guard.try_broadcast(current_head_beacon_block_root.into()) {
-                    error!(
-                        self.log,
-                        "Head change streaming queue full";
-                        "dropped_change" => format!("{}", current_head_beacon_block_root),
-                    );
-                }
-                Ok(())
-            }
-            _ => Ok(()),
-        }
+        self.subscribers.publish(&kind);
+        Ok(())
+    }
+
+    fn has_subscribers(&self, event_type: EventType) -> bool {
+        self.subscribers.has_subscribers(event_type)
     }
 }
 
@@ -76,15 +166,14 @@ pub struct TeeEventHandler<E: EthSpec> {
 
 impl<E: EthSpec> TeeEventHandler<E> {
     pub fn new(
-        log: Logger,
         websockets_handler: WebSocketSender<E>,
-    ) -> Result<(Self, Arc<Mutex<Bus<SignedBeaconBlockHash>>>), String> {
-        let (sse_handler, bus) = ServerSentEvents::new(log);
+    ) -> Result<(Self, Arc<EventSubscribers<E>>), String> {
+        let (sse_handler, subscribers) = ServerSentEvents::new();
         let result = Self {
             websockets_handler: websockets_handler,
             sse_handler: sse_handler,
         };
-        Ok((result, bus))
+        Ok((result, subscribers))
     }
 }
 
@@ -94,12 +183,21 @@ impl<E: EthSpec> EventHandler<E> for TeeEventHandler<E> {
         self.sse_handler.register(kind)?;
         Ok(())
     }
+
+    fn has_subscribers(&self, event_type: EventType) -> bool {
+        self.websockets_handler.has_subscribers(event_type)
+            || self.sse_handler.has_subscribers(event_type)
+    }
 }
 
 impl<T: EthSpec> EventHandler<T> for NullEventHandler<T> {
     fn register(&self, _kind: EventKind<T>) -> Result<(), String> {
         Ok(())
     }
+
+    fn has_subscribers(&self, _event_type: EventType) -> bool {
+        false
+    }
 }
 
 impl<T: EthSpec> Default for NullEventHandler<T> {
@@ -118,9 +216,20 @@ impl<T: EthSpec> Default for NullEventHandler<T> {
 pub enum EventKind<T: EthSpec> {
     BeaconHeadChanged {
         reorg: bool,
+        slot: Slot,
         current_head_beacon_block_root: Hash256,
+        current_head_beacon_state_root: Hash256,
         previous_head_beacon_block_root: Hash256,
     },
+    BeaconChainReorg {
+        slot: Slot,
+        depth: u64,
+        old_head_block: Hash256,
+        new_head_block: Hash256,
+        old_head_state: Hash256,
+        new_head_state: Hash256,
+        epoch: Epoch,
+    },
     BeaconFinalization {
         epoch: Epoch,
         root: Hash256,
@@ -140,4 +249,148 @@ pub enum EventKind<T: EthSpec> {
         reason: String,
         attestation: Box<Attestation<T>>,
     },
+    BeaconVoluntaryExitImported {
+        voluntary_exit: Box<SignedVoluntaryExit>,
+    },
+}
+
+/// The type of an `EventKind`, without its contents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventType {
+    BeaconHeadChanged,
+    BeaconChainReorg,
+    BeaconFinalization,
+    BeaconBlockImported,
+    BeaconBlockRejected,
+    BeaconAttestationImported,
+    BeaconAttestationRejected,
+    BeaconVoluntaryExitImported,
+}
+
+impl<T: EthSpec> EventKind<T> {
+    pub fn event_type(&self) -> EventType {
+        match self {
+            EventKind::BeaconHeadChanged { .. } => EventType::BeaconHeadChanged,
+            EventKind::BeaconChainReorg { .. } => EventType::BeaconChainReorg,
+            EventKind::BeaconFinalization { .. } => EventType::BeaconFinalization,
+            EventKind::BeaconBlockImported { .. } => EventType::BeaconBlockImported,
+            EventKind::BeaconBlockRejected { .. } => EventType::BeaconBlockRejected,
+            EventKind::BeaconAttestationImported { .. } => EventType::BeaconAttestationImported,
+            EventKind::BeaconAttestationRejected { .. } => EventType::BeaconAttestationRejected,
+            EventKind::BeaconVoluntaryExitImported { .. } => EventType::BeaconVoluntaryExitImported,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::MinimalEthSpec;
+
+    type E = MinimalEthSpec;
+
+    fn finalization(epoch: u64) -> EventKind<E> {
+        EventKind::BeaconFinalization {
+            epoch: Epoch::new(epoch),
+            root: Hash256::zero(),
+        }
+    }
+
+    #[test]
+    fn only_subscribed_event_types_are_delivered() {
+        let subscribers = EventSubscribers::<E>::new();
+        let mut receiver = subscribers
+            .subscribe(vec![EventType::BeaconFinalization])
+            .expect("should subscribe");
+
+        assert!(subscribers.has_subscribers(EventType::BeaconFinalization));
+        assert!(!subscribers.has_subscribers(EventType::BeaconAttestationImported));
+
+        subscribers.publish(&EventKind::BeaconHeadChanged {
+            reorg: false,
+            slot: Slot::new(1),
+            current_head_beacon_block_root: Hash256::zero(),
+            current_head_beacon_state_root: Hash256::zero(),
+            previous_head_beacon_block_root: Hash256::zero(),
+        });
+        subscribers.publish(&finalization(1));
+
+        match receiver.try_next() {
+            Ok(Some(EventKind::BeaconFinalization { epoch, .. })) => assert_eq!(epoch, 1),
+            other => panic!("expected finalization event, got {:?}", other),
+        }
+        assert!(receiver.try_next().is_err(), "no other events are queued");
+    }
+
+    #[test]
+    fn events_are_dropped_when_queue_is_full() {
+        let subscribers = EventSubscribers::<E>::new();
+        let mut slow = subscribers
+            .subscribe(vec![EventType::BeaconFinalization])
+            .expect("should subscribe");
+        let mut fast = subscribers
+            .subscribe(vec![EventType::BeaconFinalization])
+            .expect("should subscribe");
+
+        // Publish more events than the slow subscriber can queue, whilst the fast subscriber
+        // keeps up.
+        let num_events = SUBSCRIBER_QUEUE_CAPACITY as u64 * 2;
+        for epoch in 0..num_events {
+            subscribers.publish(&finalization(epoch));
+            assert!(fast.try_next().unwrap().is_some());
+        }
+
+        let mut received = 0;
+        while let Ok(Some(_)) = slow.try_next() {
+            received += 1;
+        }
+        assert!(received >= SUBSCRIBER_QUEUE_CAPACITY);
+        assert!(received < num_events as usize);
+
+        // A full queue does not cancel the subscription.
+        assert_eq!(subscribers.num_subscribers(), 2);
+        subscribers.publish(&finalization(num_events));
+        assert!(slow.try_next().unwrap().is_some());
+    }
+
+    #[test]
+    fn subscribers_are_removed_on_disconnect() {
+        let subscribers = EventSubscribers::<E>::new();
+        let receiver = subscribers
+            .subscribe(vec![EventType::BeaconFinalization])
+            .expect("should subscribe");
+        let _other = subscribers
+            .subscribe(vec![EventType::BeaconHeadChanged])
+            .expect("should subscribe");
+        assert_eq!(subscribers.num_subscribers(), 2);
+
+        drop(receiver);
+
+        assert!(!subscribers.has_subscribers(EventType::BeaconFinalization));
+        subscribers.publish(&finalization(1));
+        assert_eq!(subscribers.subscribers.lock().len(), 1);
+        assert_eq!(subscribers.num_subscribers(), 1);
+    }
+
+    #[test]
+    fn number_of_subscribers_is_capped() {
+        let subscribers = EventSubscribers::<E>::new();
+        let mut receivers = (0..MAX_SUBSCRIBERS)
+            .map(|_| {
+                subscribers
+                    .subscribe(vec![EventType::BeaconFinalization])
+                    .expect("should subscribe")
+            })
+            .collect::<Vec<_>>();
+
+        assert!(subscribers
+            .subscribe(vec![EventType::BeaconFinalization])
+            .is_none());
+
+        // A disconnected subscriber frees a slot.
+        receivers.pop();
+        assert!(subscribers
+            .subscribe(vec![EventType::BeaconFinalization])
+            .is_some());
+    }
 }
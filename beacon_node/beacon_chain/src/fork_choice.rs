@@ -9,7 +9,10 @@ use ssz_derive::{Decode, Encode};
 use state_processing::common::get_indexed_attestation;
 use std::marker::PhantomData;
 use store::{DBColumn, Error as StoreError, StoreItem};
-use types::{BeaconBlock, BeaconState, BeaconStateError, Epoch, Hash256, IndexedAttestation, Slot};
+use types::{
+    BeaconBlock, BeaconState, BeaconStateError, Checkpoint, Epoch, Hash256, IndexedAttestation,
+    Slot,
+};
 
 type Result<T> = std::result::Result<T, Error>;
 
@@ -213,6 +216,30 @@ impl<T: BeaconChainTypes> ForkChoice<T> {
         self.backend.latest_message(validator_index)
     }
 
+    /// Returns the justified checkpoint currently used by fork choice.
+    pub fn justified_checkpoint(&self) -> Checkpoint {
+        self.checkpoint_manager
+            .read()
+            .current
+            .justified
+            .clone()
+            .into()
+    }
+
+    /// Returns the best justified checkpoint known to fork choice, which will become the
+    /// justified checkpoint when it is safe to do so.
+    pub fn best_justified_checkpoint(&self) -> Checkpoint {
+        self.checkpoint_manager.read().best_justified()
+    }
+
+    /// Returns the finalized checkpoint currently used by fork choice.
+    ///
+    /// When the chain was started from a weak subjectivity checkpoint this will be the anchor
+    /// block, until the chain finalizes beyond it.
+    pub fn finalized_checkpoint(&self) -> Checkpoint {
+        self.checkpoint_manager.read().current.finalized.clone()
+    }
+
     /// Trigger a prune on the underlying fork choice backend.
     pub fn prune(&self) -> Result<()> {
         let finalized_root = self.checkpoint_manager.read().current.finalized.root;
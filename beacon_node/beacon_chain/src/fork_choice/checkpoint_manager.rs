@@ -187,6 +187,11 @@ impl CheckpointManager {
         }
     }
 
+    /// Returns the best-known justified checkpoint.
+    pub fn best_justified(&self) -> Checkpoint {
+        self.best.justified.clone().into()
+    }
+
     /// Potentially updates `self.current`, if the conditions are correct.
     ///
     /// Should be called before running the fork choice `find_head` function to ensure
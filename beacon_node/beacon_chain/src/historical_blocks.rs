@@ -0,0 +1,138 @@
+//! Provides the import of historical blocks, i.e. blocks which precede the anchor of a database
+//! that was started from a weak subjectivity checkpoint.
+//!
+//! Historical blocks are not applied to any state. Instead, they are verified by checking that
+//! they form a hash chain back from the oldest known block and that their proposer signatures are
+//! valid, then they are written straight to the database.
+use crate::beacon_chain::VALIDATOR_PUBKEY_CACHE_LOCK_TIMEOUT;
+use crate::{BeaconChain, BeaconChainError, BeaconChainTypes};
+use bls::verify_signature_sets;
+use state_processing::signature_sets::{
+    block_proposal_signature_set_from_parts, Error as SignatureSetError,
+};
+use std::borrow::Cow;
+use store::Store;
+use types::{Hash256, SignedBeaconBlock};
+
+#[derive(Debug)]
+pub enum HistoricalBlockError {
+    /// A block in the batch is not the parent of the block which follows it.
+    ///
+    /// The batch is invalid and the peer that provided it is faulty.
+    MismatchedBlockRoot {
+        block_root: Hash256,
+        expected_block_root: Hash256,
+    },
+    /// One or more of the proposer signatures in the batch is invalid.
+    ///
+    /// The batch is invalid and the peer that provided it is faulty.
+    InvalidSignature,
+    /// The database was not started from a checkpoint, so there are no blocks to back-fill.
+    NoAnchorInfo,
+    /// Unable to build a signature set for one of the blocks.
+    SignatureSetError(SignatureSetError),
+    /// Timed out waiting for the validator pubkey cache.
+    ValidatorPubkeyCacheTimeout,
+    /// An internal error occurred while verifying or storing the batch.
+    BeaconChainError(BeaconChainError),
+}
+
+impl From<BeaconChainError> for HistoricalBlockError {
+    fn from(e: BeaconChainError) -> Self {
+        HistoricalBlockError::BeaconChainError(e)
+    }
+}
+
+impl<T: BeaconChainTypes> BeaconChain<T> {
+    /// Import a batch of historical blocks, given in slot-ascending order.
+    ///
+    /// Blocks at or after the slot of the oldest block in the database are ignored. The remaining
+    /// blocks must form a chain which ends at the parent of the oldest block. If that chain
+    /// reaches the child of the genesis block, the (already known) genesis block is included too.
+    ///
+    /// Returns the number of blocks imported.
+    pub fn import_historical_block_batch(
+        &self,
+        blocks: &[SignedBeaconBlock<T::EthSpec>],
+    ) -> Result<usize, HistoricalBlockError> {
+        let anchor_info = self
+            .store
+            .get_anchor_info()
+            .ok_or_else(|| HistoricalBlockError::NoAnchorInfo)?;
+
+        // Walk backwards from the oldest known block, checking the hash chain.
+        let mut expected_block_root = anchor_info.oldest_block_parent;
+        let mut blocks_to_import = Vec::with_capacity(blocks.len());
+
+        for block in blocks
+            .iter()
+            .filter(|block| block.slot() < anchor_info.oldest_block_slot)
+            .rev()
+        {
+            let block_root = block.canonical_root();
+
+            if block_root != expected_block_root {
+                return Err(HistoricalBlockError::MismatchedBlockRoot {
+                    block_root,
+                    expected_block_root,
+                });
+            }
+
+            expected_block_root = block.parent_root();
+            blocks_to_import.push((block_root, block.clone()));
+        }
+
+        // The genesis block is always stored, so the back-fill is complete once it is reached.
+        if expected_block_root == self.genesis_block_root {
+            let genesis_block = self
+                .store
+                .get_block(&self.genesis_block_root)
+                .map_err(BeaconChainError::DBError)?
+                .ok_or_else(|| BeaconChainError::MissingBeaconBlock(self.genesis_block_root))?;
+            blocks_to_import.push((self.genesis_block_root, genesis_block));
+        }
+
+        if blocks_to_import.is_empty() {
+            return Ok(0);
+        }
+
+        // Verify all proposer signatures at once. The genesis block is not signed.
+        let fork = self.head_info()?.fork;
+        let signatures_valid = {
+            let pubkey_cache = self
+                .validator_pubkey_cache
+                .try_read_for(VALIDATOR_PUBKEY_CACHE_LOCK_TIMEOUT)
+                .ok_or_else(|| HistoricalBlockError::ValidatorPubkeyCacheTimeout)?;
+
+            let signature_sets = blocks_to_import
+                .iter()
+                .filter(|(_, block)| block.slot() > 0)
+                .map(|(block_root, block)| {
+                    block_proposal_signature_set_from_parts(
+                        block,
+                        Some(*block_root),
+                        |validator_index| pubkey_cache.get(validator_index).map(Cow::Borrowed),
+                        &fork,
+                        self.genesis_validators_root,
+                        &self.spec,
+                    )
+                })
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(HistoricalBlockError::SignatureSetError)?;
+
+            signature_sets.is_empty() || verify_signature_sets(signature_sets)
+        };
+
+        if !signatures_valid {
+            return Err(HistoricalBlockError::InvalidSignature);
+        }
+
+        let num_imported = blocks_to_import.len();
+
+        self.store
+            .put_historical_blocks(blocks_to_import)
+            .map_err(BeaconChainError::DBError)?;
+
+        Ok(num_imported)
+    }
+}
@@ -12,13 +12,16 @@ pub mod eth1_chain;
 pub mod events;
 mod fork_choice;
 mod head_tracker;
+mod historical_blocks;
 mod metrics;
 pub mod migrate;
 mod naive_aggregation_pool;
 mod observed_attestations;
 mod observed_attesters;
 mod observed_block_producers;
+pub mod observed_operations;
 mod persisted_beacon_chain;
+pub mod schema_change;
 mod shuffling_cache;
 mod snapshot_cache;
 pub mod test_utils;
@@ -36,6 +39,7 @@ pub use block_verification::{BlockError, BlockProcessingOutcome, GossipVerifiedB
 pub use eth1_chain::{Eth1Chain, Eth1ChainBackend};
 pub use events::EventHandler;
 pub use fork_choice::ForkChoice;
+pub use historical_blocks::HistoricalBlockError;
 pub use metrics::scrape_for_metrics;
 pub use parking_lot;
 pub use slot_clock;
@@ -261,6 +261,14 @@ lazy_static! {
     pub static ref DEFAULT_ETH1_VOTES: Result<IntCounter> =
         try_create_int_counter("beacon_eth1_default_votes", "Count of times we have voted default value for eth1 data");
 
+    /*
+     * Event subscribers
+     */
+    pub static ref EVENT_SUBSCRIBER_DROPPED_EVENTS: Result<IntCounter> = try_create_int_counter(
+        "beacon_event_subscriber_dropped_events_total",
+        "Count of events dropped because the queue of a subscriber was full"
+    );
+
     /*
      * Chain Head
      */
@@ -1,7 +1,8 @@
 use crate::metrics;
 use parking_lot::RwLock;
 use std::collections::HashMap;
-use types::{Attestation, AttestationData, EthSpec, Slot};
+use tree_hash::TreeHash;
+use types::{Attestation, AttestationData, EthSpec, Hash256, Slot};
 
 /// The number of slots that will be stored in the pool.
 ///
@@ -226,6 +227,17 @@ impl<E: EthSpec> NaiveAggregationPool<E> {
             .unwrap_or_else(|| Ok(None))
     }
 
+    /// Returns an aggregated `Attestation` at the given `slot` with a `tree_hash_root` of its
+    /// `attestation.data` equal to `data_root`, if any.
+    pub fn get_by_slot_and_root(&self, slot: Slot, data_root: &Hash256) -> Option<Attestation<E>> {
+        self.maps.read().get(&slot).and_then(|map| {
+            map.map
+                .iter()
+                .find(|(data, _)| data.tree_hash_root() == *data_root)
+                .map(|(_, attestation)| attestation.clone())
+        })
+    }
+
     /// Removes any attestations with a slot lower than `current_slot` and bars any future
     /// attestations with a slot lower than `current_slot - SLOTS_RETAINED`.
     pub fn prune(&self, current_slot: Slot) {
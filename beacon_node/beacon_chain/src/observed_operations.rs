@@ -0,0 +1,201 @@
+//! Provides the `ObservedOperations` struct which allows for rejecting gossip voluntary exits and
+//! slashings that only concern validators we have already seen an operation for.
+
+use parking_lot::Mutex;
+use smallvec::{smallvec, SmallVec};
+use state_processing::{SigVerifiedOp, VerifyOperation};
+use std::collections::HashSet;
+use std::iter::FromIterator;
+use std::marker::PhantomData;
+use types::{
+    AttesterSlashing, BeaconState, ChainSpec, EthSpec, ProposerSlashing, SignedVoluntaryExit,
+};
+
+/// Number of validator indices to store on the stack in `observed_validators`.
+pub const SMALL_VEC_SIZE: usize = 8;
+
+/// Stateful tracker for exit/slashing operations seen on the network.
+///
+/// Implements the conditions for gossip verification of exits and slashings from the P2P spec.
+pub struct ObservedOperations<T: ObservableOperation<E>, E: EthSpec> {
+    /// Indices of validators for whom we have already seen an instance of an operation `T`.
+    ///
+    /// For voluntary exits, this is the set of all `signed_voluntary_exit.message.validator_index`.
+    /// For proposer slashings, this is the set of all `proposer_slashing.index`.
+    /// For attester slashings, this is the set of all validators who would be slashed by
+    /// previously seen attester slashings, i.e. those validators in the intersection of
+    /// `attestation_1.attester_indices` and `attestation_2.attester_indices`.
+    observed_validator_indices: Mutex<HashSet<u64>>,
+    _phantom: PhantomData<(T, E)>,
+}
+
+/// The result of observing a gossip operation.
+pub enum ObservationOutcome<T> {
+    /// The operation is new and has been signature verified.
+    New(SigVerifiedOp<T>),
+    /// The operation only concerns validators that have already been observed.
+    AlreadyKnown,
+}
+
+/// Trait for exits and slashings which can be observed using `ObservedOperations`.
+pub trait ObservableOperation<E: EthSpec>: VerifyOperation<E> + Sized {
+    /// The set of validator indices involved in this operation.
+    ///
+    /// See the comment on `observed_validator_indices` above for detail.
+    fn observed_validators(&self) -> SmallVec<[u64; SMALL_VEC_SIZE]>;
+}
+
+impl<E: EthSpec> ObservableOperation<E> for SignedVoluntaryExit {
+    fn observed_validators(&self) -> SmallVec<[u64; SMALL_VEC_SIZE]> {
+        smallvec![self.message.validator_index]
+    }
+}
+
+impl<E: EthSpec> ObservableOperation<E> for ProposerSlashing {
+    fn observed_validators(&self) -> SmallVec<[u64; SMALL_VEC_SIZE]> {
+        smallvec![self.signed_header_1.message.proposer_index]
+    }
+}
+
+impl<E: EthSpec> ObservableOperation<E> for AttesterSlashing<E> {
+    fn observed_validators(&self) -> SmallVec<[u64; SMALL_VEC_SIZE]> {
+        let attestation_1_indices = self
+            .attestation_1
+            .attesting_indices
+            .iter()
+            .copied()
+            .collect::<HashSet<u64>>();
+        let attestation_2_indices = self
+            .attestation_2
+            .attesting_indices
+            .iter()
+            .copied()
+            .collect::<HashSet<u64>>();
+        SmallVec::from_iter(
+            attestation_1_indices
+                .intersection(&attestation_2_indices)
+                .copied(),
+        )
+    }
+}
+
+impl<T: ObservableOperation<E>, E: EthSpec> Default for ObservedOperations<T, E> {
+    fn default() -> Self {
+        Self {
+            observed_validator_indices: Mutex::new(HashSet::new()),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<T: ObservableOperation<E>, E: EthSpec> ObservedOperations<T, E> {
+    /// Verify the signature and validity of `op` against `head_state`, unless every validator
+    /// it concerns has already been observed.
+    ///
+    /// On success, all of the validators in `op` are marked as observed, so future calls for the
+    /// same validators will return `ObservationOutcome::AlreadyKnown`. Invalid operations are not
+    /// recorded.
+    pub fn verify_and_observe(
+        &self,
+        op: T,
+        head_state: &BeaconState<E>,
+        spec: &ChainSpec,
+    ) -> Result<ObservationOutcome<T>, T::Error> {
+        // Hold the lock for the duration of verification so that two copies of the same
+        // operation cannot both be reported as new.
+        let mut observed_validator_indices = self.observed_validator_indices.lock();
+        let new_validator_indices = op.observed_validators();
+
+        // If all of the new validator indices have been previously observed, short-circuit
+        // the validation. This implements the uniqueness check part of the spec, which for
+        // attester slashings reads:
+        //
+        // [IGNORE] At least one index in the intersection of the attesting indices of each
+        // attestation has not yet been seen in any prior attester_slashing.
+        if new_validator_indices
+            .iter()
+            .all(|index| observed_validator_indices.contains(index))
+        {
+            return Ok(ObservationOutcome::AlreadyKnown);
+        }
+
+        // Validate the op using operation-specific logic (`verify_attester_slashing`, etc).
+        let verified_op = op.validate(head_state, spec)?;
+
+        // Add the relevant indices to the set of known indices to prevent processing of
+        // duplicates in the future.
+        observed_validator_indices.extend(new_validator_indices);
+
+        Ok(ObservationOutcome::New(verified_op))
+    }
+
+    /// Returns `true` if `validator_index` has been observed in a valid operation of type `T`.
+    pub fn index_seen(&self, validator_index: u64) -> bool {
+        self.observed_validator_indices
+            .lock()
+            .contains(&validator_index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::test_utils::{
+        ProposerSlashingTestTask, TestingBeaconStateBuilder, TestingProposerSlashingBuilder,
+    };
+    use types::MainnetEthSpec;
+
+    type E = MainnetEthSpec;
+
+    #[test]
+    fn proposer_slashing_deduplication() {
+        let spec = E::default_spec();
+        let (state, keypairs) =
+            TestingBeaconStateBuilder::<E>::from_deterministic_keypairs(8, &spec).build();
+        let observed = ObservedOperations::<ProposerSlashing, E>::default();
+
+        let proposer_index = 1;
+        let slashing = TestingProposerSlashingBuilder::double_vote::<E>(
+            ProposerSlashingTestTask::Valid,
+            proposer_index,
+            &keypairs[proposer_index as usize].sk,
+            &state.fork,
+            state.genesis_validators_root,
+            &spec,
+        );
+
+        assert!(!observed.index_seen(proposer_index));
+        assert!(matches!(
+            observed.verify_and_observe(slashing.clone(), &state, &spec),
+            Ok(ObservationOutcome::New(_))
+        ));
+        assert!(observed.index_seen(proposer_index));
+        assert!(matches!(
+            observed.verify_and_observe(slashing, &state, &spec),
+            Ok(ObservationOutcome::AlreadyKnown)
+        ));
+    }
+
+    #[test]
+    fn invalid_proposer_slashing_is_not_observed() {
+        let spec = E::default_spec();
+        let (state, keypairs) =
+            TestingBeaconStateBuilder::<E>::from_deterministic_keypairs(8, &spec).build();
+        let observed = ObservedOperations::<ProposerSlashing, E>::default();
+
+        let proposer_index = 2;
+        let slashing = TestingProposerSlashingBuilder::double_vote::<E>(
+            ProposerSlashingTestTask::ProposalsIdentical,
+            proposer_index,
+            &keypairs[proposer_index as usize].sk,
+            &state.fork,
+            state.genesis_validators_root,
+            &spec,
+        );
+
+        assert!(observed
+            .verify_and_observe(slashing, &state, &spec)
+            .is_err());
+        assert!(!observed.index_seen(proposer_index));
+    }
+}
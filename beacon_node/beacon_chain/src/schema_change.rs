@@ -0,0 +1,65 @@
+//! Utilities for managing database schema changes.
+use slog::{info, Logger};
+use store::{Error as StoreError, HotColdDB, SchemaVersion, CURRENT_SCHEMA_VERSION};
+use types::EthSpec;
+
+/// Migrate the database to `CURRENT_SCHEMA_VERSION`, which must be run before the database is
+/// used by this version of Lighthouse.
+///
+/// Returns an error without modifying the database if its schema version is newer than
+/// `CURRENT_SCHEMA_VERSION`, since it would be unsafe to read with this binary.
+pub fn migrate_to_current_schema<E: EthSpec>(
+    db: &HotColdDB<E>,
+    log: &Logger,
+) -> Result<(), StoreError> {
+    let on_disk = db.load_schema_version()?;
+
+    if on_disk > CURRENT_SCHEMA_VERSION {
+        return Err(StoreError::SchemaVersionTooNew {
+            on_disk,
+            supported: CURRENT_SCHEMA_VERSION,
+        });
+    }
+
+    if on_disk != CURRENT_SCHEMA_VERSION {
+        info!(
+            log,
+            "Migrating database schema";
+            "from" => on_disk.as_u64(),
+            "to" => CURRENT_SCHEMA_VERSION.as_u64(),
+        );
+    }
+
+    migrate_schema(db, on_disk, CURRENT_SCHEMA_VERSION)
+}
+
+/// Migrate the database from one schema version to another, applying one version step at a time.
+pub fn migrate_schema<E: EthSpec>(
+    db: &HotColdDB<E>,
+    from: SchemaVersion,
+    to: SchemaVersion,
+) -> Result<(), StoreError> {
+    match (from, to) {
+        // Migrating to the same version is a no-op.
+        (_, _) if from == to => Ok(()),
+        // Upgrade across multiple versions by recursively migrating one step at a time.
+        (_, _) if from.as_u64() + 1 < to.as_u64() => {
+            let next = SchemaVersion(from.as_u64() + 1);
+            migrate_schema(db, from, next)?;
+            migrate_schema(db, next, to)
+        }
+        // Downgrade across multiple versions by recursively migrating one step at a time.
+        (_, _) if to.as_u64() + 1 < from.as_u64() => {
+            let next = SchemaVersion(from.as_u64() - 1);
+            migrate_schema(db, from, next)?;
+            migrate_schema(db, next, to)
+        }
+        // Databases created before schema versioning have the same layout as version 1, so only
+        // the version itself needs to change.
+        (SchemaVersion(0), SchemaVersion(1)) | (SchemaVersion(1), SchemaVersion(0)) => {
+            db.store_schema_version(to)
+        }
+        // Anything else is an error.
+        (_, _) => Err(StoreError::UnsupportedSchemaMigration { from, to }),
+    }
+}
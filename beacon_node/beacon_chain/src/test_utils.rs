@@ -6,11 +6,12 @@ pub use crate::persisted_beacon_chain::PersistedBeaconChain;
 use crate::{
     builder::{BeaconChainBuilder, Witness},
     eth1_chain::CachingEth1Backend,
-    events::NullEventHandler,
+    events::ServerSentEvents,
     BeaconChain, BeaconChainTypes, StateSkipConfig,
 };
 use genesis::interop_genesis_state;
 use rayon::prelude::*;
+use slasher::Slasher;
 use sloggers::{null::NullLoggerBuilder, Build};
 use slot_clock::TestingSlotClock;
 use state_processing::per_slot_processing;
@@ -40,7 +41,7 @@ pub type BaseHarnessType<TStore, TStoreMigrator, TEthSpec> = Witness<
     TestingSlotClock,
     CachingEth1Backend<TEthSpec, TStore>,
     TEthSpec,
-    NullEventHandler<TEthSpec>,
+    ServerSentEvents<TEthSpec>,
 >;
 
 pub type HarnessType<E> = BaseHarnessType<MemoryStore<E>, NullMigrator, E>;
@@ -119,7 +120,7 @@ impl<E: EthSpec> BeaconChainHarness<HarnessType<E>> {
             .expect("should build state using recent genesis")
             .dummy_eth1_backend()
             .expect("should build dummy backend")
-            .null_event_handler()
+            .event_handler(ServerSentEvents::new().0)
             .testing_slot_clock(HARNESS_SLOT_TIME)
             .expect("should configure testing slot clock")
             .reduced_tree_fork_choice()
@@ -136,6 +137,89 @@ impl<E: EthSpec> BeaconChainHarness<HarnessType<E>> {
     }
 }
 
+impl<E: EthSpec> BeaconChainHarness<HarnessType<E>> {
+    /// Instantiate a new harness which sends verified attestations and blocks to `slasher`.
+    pub fn new_with_slasher(
+        eth_spec_instance: E,
+        keypairs: Vec<Keypair>,
+        slasher: Arc<Slasher<E>>,
+    ) -> Self {
+        let data_dir = tempdir().expect("should create temporary data_dir");
+        let mut spec = E::default_spec();
+
+        // Make every validator an aggregator, as in `Self::new`.
+        spec.target_aggregators_per_committee = 1 << 32;
+
+        let log = NullLoggerBuilder.build().expect("logger should build");
+
+        let chain = BeaconChainBuilder::new(eth_spec_instance)
+            .logger(log)
+            .custom_spec(spec.clone())
+            .store(Arc::new(MemoryStore::open()))
+            .store_migrator(NullMigrator)
+            .data_dir(data_dir.path().to_path_buf())
+            .genesis_state(
+                interop_genesis_state::<E>(&keypairs, HARNESS_GENESIS_TIME, &spec)
+                    .expect("should generate interop state"),
+            )
+            .expect("should build state using recent genesis")
+            .dummy_eth1_backend()
+            .expect("should build dummy backend")
+            .event_handler(ServerSentEvents::new().0)
+            .slasher(slasher)
+            .testing_slot_clock(HARNESS_SLOT_TIME)
+            .expect("should configure testing slot clock")
+            .reduced_tree_fork_choice()
+            .expect("should add fork choice to builder")
+            .build()
+            .expect("should build");
+
+        Self {
+            spec: chain.spec.clone(),
+            chain,
+            keypairs,
+            data_dir,
+        }
+    }
+
+    /// Instantiate a new harness which starts from the given `genesis_state` (e.g., one supplied
+    /// by a test vector) rather than an interop genesis state.
+    ///
+    /// The harness has no keypairs, so it cannot produce blocks or attestations. Its slot clock
+    /// uses the slot duration from the spec, so it can be driven by setting the current time.
+    pub fn new_from_genesis_state(eth_spec_instance: E, genesis_state: BeaconState<E>) -> Self {
+        let data_dir = tempdir().expect("should create temporary data_dir");
+        let spec = E::default_spec();
+
+        let log = NullLoggerBuilder.build().expect("logger should build");
+
+        let chain = BeaconChainBuilder::new(eth_spec_instance)
+            .logger(log)
+            .custom_spec(spec.clone())
+            .store(Arc::new(MemoryStore::open()))
+            .store_migrator(NullMigrator)
+            .data_dir(data_dir.path().to_path_buf())
+            .genesis_state(genesis_state)
+            .expect("should build state using genesis state")
+            .dummy_eth1_backend()
+            .expect("should build dummy backend")
+            .event_handler(ServerSentEvents::new().0)
+            .testing_slot_clock(Duration::from_millis(spec.milliseconds_per_slot))
+            .expect("should configure testing slot clock")
+            .reduced_tree_fork_choice()
+            .expect("should add fork choice to builder")
+            .build()
+            .expect("should build");
+
+        Self {
+            spec: chain.spec.clone(),
+            chain,
+            keypairs: vec![],
+            data_dir,
+        }
+    }
+}
+
 impl<E: EthSpec> BeaconChainHarness<DiskHarnessType<E>> {
     /// Instantiate a new harness with `validator_count` initial validators.
     pub fn new_with_disk_store(
@@ -161,7 +245,52 @@ impl<E: EthSpec> BeaconChainHarness<DiskHarnessType<E>> {
             .expect("should build state using recent genesis")
             .dummy_eth1_backend()
             .expect("should build dummy backend")
-            .null_event_handler()
+            .event_handler(ServerSentEvents::new().0)
+            .testing_slot_clock(HARNESS_SLOT_TIME)
+            .expect("should configure testing slot clock")
+            .reduced_tree_fork_choice()
+            .expect("should add fork choice to builder")
+            .build()
+            .expect("should build");
+
+        Self {
+            spec: chain.spec.clone(),
+            chain,
+            keypairs,
+            data_dir,
+        }
+    }
+
+    /// Instantiate a new harness which starts from the given weak subjectivity checkpoint, rather
+    /// than from genesis.
+    pub fn new_from_checkpoint(
+        eth_spec_instance: E,
+        store: Arc<HotColdDB<E>>,
+        keypairs: Vec<Keypair>,
+        anchor_state: BeaconState<E>,
+        anchor_block: SignedBeaconBlock<E>,
+    ) -> Self {
+        let data_dir = tempdir().expect("should create temporary data_dir");
+        let spec = E::default_spec();
+
+        let log = NullLoggerBuilder.build().expect("logger should build");
+
+        let chain = BeaconChainBuilder::new(eth_spec_instance)
+            .logger(log.clone())
+            .custom_spec(spec.clone())
+            .store(store.clone())
+            .store_migrator(BlockingMigrator::new(store, log.clone()))
+            .data_dir(data_dir.path().to_path_buf())
+            .weak_subjectivity_state(
+                anchor_state,
+                anchor_block,
+                interop_genesis_state::<E>(&keypairs, HARNESS_GENESIS_TIME, &spec)
+                    .expect("should generate interop state"),
+            )
+            .expect("should build state from checkpoint")
+            .dummy_eth1_backend()
+            .expect("should build dummy backend")
+            .event_handler(ServerSentEvents::new().0)
             .testing_slot_clock(HARNESS_SLOT_TIME)
             .expect("should configure testing slot clock")
             .reduced_tree_fork_choice()
@@ -198,7 +327,7 @@ impl<E: EthSpec> BeaconChainHarness<DiskHarnessType<E>> {
             .expect("should resume beacon chain from db")
             .dummy_eth1_backend()
             .expect("should build dummy backend")
-            .null_event_handler()
+            .event_handler(ServerSentEvents::new().0)
             .testing_slot_clock(Duration::from_secs(1))
             .expect("should configure testing slot clock")
             .reduced_tree_fork_choice()
@@ -0,0 +1,213 @@
+#![cfg(not(debug_assertions))]
+
+#[macro_use]
+extern crate lazy_static;
+
+use beacon_chain::{
+    schema_change::{migrate_schema, migrate_to_current_schema},
+    test_utils::{AttestationStrategy, BeaconChainHarness, BlockStrategy},
+};
+use sloggers::{null::NullLoggerBuilder, Build};
+use std::sync::Arc;
+use store::{
+    hot_cold_store::SPLIT_DB_KEY, DBColumn, Error as StoreError, HotColdDB, KeyValueStore, LevelDB,
+    SchemaVersion, StoreConfig, CURRENT_SCHEMA_VERSION,
+};
+use tempfile::{tempdir, TempDir};
+use types::{EthSpec, Hash256, Keypair, MinimalEthSpec};
+
+type E = MinimalEthSpec;
+
+// Should ideally be divisible by 3.
+pub const VALIDATOR_COUNT: usize = 24;
+
+lazy_static! {
+    /// A cached set of keys.
+    static ref KEYPAIRS: Vec<Keypair> = types::test_utils::generate_deterministic_keypairs(VALIDATOR_COUNT);
+}
+
+fn get_store(db_path: &TempDir) -> Arc<HotColdDB<E>> {
+    let spec = E::default_spec();
+    let hot_path = db_path.path().join("hot_db");
+    let cold_path = db_path.path().join("cold_db");
+    let config = StoreConfig::default();
+    let log = NullLoggerBuilder.build().expect("logger should build");
+    Arc::new(
+        HotColdDB::open(&hot_path, &cold_path, config, spec, log)
+            .expect("disk store should initialize"),
+    )
+}
+
+fn null_logger() -> slog::Logger {
+    NullLoggerBuilder.build().expect("logger should build")
+}
+
+/// The columns of a database written before schema versioning was introduced.
+const LEGACY_COLUMNS: &[DBColumn] = &[
+    DBColumn::BeaconMeta,
+    DBColumn::BeaconBlock,
+    DBColumn::BeaconState,
+    DBColumn::BeaconChain,
+    DBColumn::OpPool,
+    DBColumn::Eth1Cache,
+    DBColumn::ForkChoice,
+    DBColumn::BeaconRestorePoint,
+    DBColumn::BeaconStateSummary,
+    DBColumn::BeaconBlockRoots,
+    DBColumn::BeaconStateRoots,
+    DBColumn::BeaconHistoricalRoots,
+    DBColumn::BeaconRandaoMixes,
+    DBColumn::DhtEnrs,
+];
+
+/// Copy the hot and cold databases in `source` to `dest` using the layout of a database written
+/// before schema versioning was introduced.
+///
+/// Only the legacy columns are copied, and the only metadata a legacy database holds is the split.
+fn write_legacy_database(source: &TempDir, dest: &TempDir) {
+    let split_key = Hash256::from_slice(SPLIT_DB_KEY.as_bytes());
+
+    for db_name in &["hot_db", "cold_db"] {
+        let source_db =
+            LevelDB::<E>::open(&source.path().join(db_name)).expect("source database should open");
+        let dest_db =
+            LevelDB::<E>::open(&dest.path().join(db_name)).expect("legacy database should open");
+
+        for column in LEGACY_COLUMNS {
+            let col: &str = (*column).into();
+            source_db
+                .for_each_in_column(*column, |key, value| {
+                    if *column != DBColumn::BeaconMeta || key == split_key.as_bytes() {
+                        dest_db.put_bytes(col, key, value)?;
+                    }
+                    Ok(true)
+                })
+                .expect("should copy column");
+        }
+    }
+}
+
+#[test]
+fn fresh_database_is_migrated_to_current() {
+    let db_path = tempdir().unwrap();
+    let store = get_store(&db_path);
+
+    migrate_to_current_schema(&store, &null_logger()).expect("should migrate");
+
+    assert_eq!(store.load_schema_version().unwrap(), CURRENT_SCHEMA_VERSION);
+}
+
+/// Databases written before schema versioning have no version on disk and none of the columns or
+/// metadata added since. Such a database containing a finalized chain should be upgraded and
+/// remain usable.
+#[test]
+fn legacy_database_is_upgraded_and_resumes() {
+    let num_blocks_produced = E::slots_per_epoch() * 5;
+
+    let db_path = tempdir().unwrap();
+    let store = get_store(&db_path);
+
+    let harness = BeaconChainHarness::new_with_disk_store(
+        MinimalEthSpec,
+        store.clone(),
+        KEYPAIRS[..].to_vec(),
+    );
+    harness.advance_slot();
+    harness.extend_chain(
+        num_blocks_produced as usize,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::AllValidators,
+    );
+    harness
+        .chain
+        .persist_head_and_fork_choice()
+        .expect("should persist the head and fork choice");
+    harness
+        .chain
+        .persist_op_pool()
+        .expect("should persist the op pool");
+    harness
+        .chain
+        .persist_eth1_cache()
+        .expect("should persist the eth1 cache");
+
+    let original_head = harness.chain.head().expect("should read head");
+    let data_dir = harness.data_dir;
+    drop(harness.chain);
+    drop(store);
+
+    let legacy_db_path = tempdir().unwrap();
+    write_legacy_database(&db_path, &legacy_db_path);
+
+    let store = get_store(&legacy_db_path);
+    assert_eq!(store.load_schema_version().unwrap(), SchemaVersion(0));
+
+    migrate_to_current_schema(&store, &null_logger()).expect("should migrate");
+    assert_eq!(store.load_schema_version().unwrap(), CURRENT_SCHEMA_VERSION);
+
+    let resumed_harness = BeaconChainHarness::resume_from_disk_store(
+        MinimalEthSpec,
+        store,
+        KEYPAIRS[..].to_vec(),
+        data_dir,
+    );
+
+    assert_eq!(
+        resumed_harness.chain.head().expect("should read head"),
+        original_head,
+        "head should be unchanged by the migration"
+    );
+}
+
+#[test]
+fn newer_database_is_refused() {
+    let db_path = tempdir().unwrap();
+    let store = get_store(&db_path);
+
+    let future_version = SchemaVersion(CURRENT_SCHEMA_VERSION.as_u64() + 1);
+    store.store_schema_version(future_version).unwrap();
+
+    match migrate_to_current_schema(&store, &null_logger()) {
+        Err(StoreError::SchemaVersionTooNew { on_disk, supported }) => {
+            assert_eq!(on_disk, future_version);
+            assert_eq!(supported, CURRENT_SCHEMA_VERSION);
+        }
+        other => panic!("expected SchemaVersionTooNew, got {:?}", other),
+    }
+
+    assert_eq!(
+        store.load_schema_version().unwrap(),
+        future_version,
+        "the database should not be modified"
+    );
+}
+
+#[test]
+fn downgrade_and_upgrade() {
+    let db_path = tempdir().unwrap();
+    let store = get_store(&db_path);
+
+    migrate_to_current_schema(&store, &null_logger()).unwrap();
+
+    migrate_schema(&store, CURRENT_SCHEMA_VERSION, SchemaVersion(0)).expect("should downgrade");
+    assert_eq!(store.load_schema_version().unwrap(), SchemaVersion(0));
+
+    migrate_schema(&store, SchemaVersion(0), CURRENT_SCHEMA_VERSION).expect("should upgrade");
+    assert_eq!(store.load_schema_version().unwrap(), CURRENT_SCHEMA_VERSION);
+}
+
+#[test]
+fn unsupported_migration_is_an_error() {
+    let db_path = tempdir().unwrap();
+    let store = get_store(&db_path);
+
+    let to = SchemaVersion(CURRENT_SCHEMA_VERSION.as_u64() + 1);
+
+    match migrate_schema(&store, CURRENT_SCHEMA_VERSION, to) {
+        Err(StoreError::UnsupportedSchemaMigration { from, to: err_to }) => {
+            assert_eq!(from, CURRENT_SCHEMA_VERSION);
+            assert_eq!(err_to, to);
+        }
+        other => panic!("expected UnsupportedSchemaMigration, got {:?}", other),
+    }
+}
@@ -0,0 +1,194 @@
+#![cfg(not(debug_assertions))]
+
+use beacon_chain::{
+    attestation_verification::Error as AttnError,
+    observed_operations::ObservationOutcome,
+    test_utils::{AttestationStrategy, BeaconChainHarness, BlockStrategy, HarnessType},
+    BlockError,
+};
+use slasher::{Config as SlasherConfig, Slasher};
+use sloggers::{null::NullLoggerBuilder, Build};
+use std::sync::Arc;
+use tempfile::{tempdir, TempDir};
+use types::{
+    test_utils::generate_deterministic_keypairs, Attestation, BeaconBlock, Domain, EthSpec,
+    MinimalEthSpec, Signature, SignedBeaconBlock, SignedRoot,
+};
+
+type E = MinimalEthSpec;
+
+pub const VALIDATOR_COUNT: usize = 32;
+
+/// Returns a harness which sends verified attestations and blocks to the returned slasher.
+fn get_harness() -> (BeaconChainHarness<HarnessType<E>>, Arc<Slasher<E>>, TempDir) {
+    let slasher_dir = tempdir().expect("should create temporary slasher dir");
+    let log = NullLoggerBuilder.build().expect("logger should build");
+    let slasher = Arc::new(
+        Slasher::open(SlasherConfig::new(slasher_dir.path().into()), log)
+            .expect("should open slasher"),
+    );
+
+    let harness = BeaconChainHarness::new_with_slasher(
+        MinimalEthSpec,
+        generate_deterministic_keypairs(VALIDATOR_COUNT),
+        slasher.clone(),
+    );
+    harness.advance_slot();
+
+    (harness, slasher, slasher_dir)
+}
+
+#[test]
+fn double_vote_and_double_proposal_reach_op_pool() {
+    let (harness, slasher, _slasher_dir) = get_harness();
+    let chain = &harness.chain;
+
+    // Build up to the middle of an epoch, so that the head and its parent share a target.
+    harness.extend_chain(
+        E::slots_per_epoch() as usize * 2 + 2,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::AllValidators,
+    );
+    harness.advance_slot();
+
+    let head = chain.head().expect("should get head");
+    let current_slot = chain.slot().expect("should get slot");
+    let fork = head.beacon_state.fork;
+
+    /*
+     * Double vote: a validator attests to both the head and its parent.
+     */
+
+    let attester = head
+        .beacon_state
+        .get_beacon_committee(current_slot, 0)
+        .expect("should get committee")
+        .committee[0];
+    let sign_attestation = |mut attestation: Attestation<E>| {
+        attestation
+            .sign(
+                &harness.keypairs[attester].sk,
+                0,
+                &fork,
+                chain.genesis_validators_root,
+                &chain.spec,
+            )
+            .expect("should sign attestation");
+        attestation
+    };
+
+    let attestation_1 = sign_attestation(
+        chain
+            .produce_unaggregated_attestation(current_slot, 0)
+            .expect("should produce attestation"),
+    );
+    let mut attestation_2 = chain
+        .produce_unaggregated_attestation(current_slot, 0)
+        .expect("should produce attestation");
+    attestation_2.data.beacon_block_root = head.beacon_block.parent_root();
+    let attestation_2 = sign_attestation(attestation_2);
+
+    chain
+        .verify_unaggregated_attestation_for_gossip(attestation_1)
+        .expect("should verify first vote");
+    // The conflicting vote is not propagated, but it is still sent to the slasher.
+    match chain.verify_unaggregated_attestation_for_gossip(attestation_2) {
+        Err(AttnError::PriorAttestationKnown { .. }) => (),
+        other => panic!(
+            "expected the second vote to be rejected, got {:?}",
+            other.map(|_| ())
+        ),
+    }
+
+    /*
+     * Double proposal: the proposer signs two different blocks for the same slot.
+     */
+
+    let proposer = chain
+        .block_proposer(current_slot)
+        .expect("should get proposer");
+    let proposer_sk = &harness.keypairs[proposer].sk;
+    let randao_reveal = {
+        let epoch = current_slot.epoch(E::slots_per_epoch());
+        let domain =
+            chain
+                .spec
+                .get_domain(epoch, Domain::Randao, &fork, chain.genesis_validators_root);
+        Signature::new(epoch.signing_root(domain).as_bytes(), proposer_sk)
+    };
+    let (block, _state) = chain
+        .produce_block(randao_reveal, current_slot)
+        .expect("should produce block");
+    let sign_block = |block: BeaconBlock<E>| -> SignedBeaconBlock<E> {
+        block.sign(
+            proposer_sk,
+            &fork,
+            chain.genesis_validators_root,
+            &chain.spec,
+        )
+    };
+
+    let block_1 = sign_block(block.clone());
+    let mut block_2 = block;
+    block_2.body.graffiti = [42; 32];
+    let block_2 = sign_block(block_2);
+
+    chain
+        .verify_block_for_gossip(block_1)
+        .expect("should verify first proposal");
+    // The conflicting proposal is not propagated, but it is still sent to the slasher.
+    match chain.verify_block_for_gossip(block_2) {
+        Err(BlockError::RepeatProposal { .. }) => (),
+        other => panic!(
+            "expected the second proposal to be rejected, got {:?}",
+            other.map(|_| ())
+        ),
+    }
+
+    /*
+     * The slasher finds both slashings, which are valid and can be added to the op pool.
+     */
+
+    slasher
+        .process_queued(current_slot.epoch(E::slots_per_epoch()))
+        .expect("should process slasher queue");
+
+    let proposer_slashings = slasher.get_proposer_slashings();
+    assert_eq!(proposer_slashings.len(), 1);
+    assert_eq!(
+        proposer_slashings[0].signed_header_1.message.proposer_index,
+        proposer as u64
+    );
+    let attester_slashings = slasher.get_attester_slashings();
+    assert_eq!(attester_slashings.len(), 1);
+    assert_eq!(
+        attester_slashings[0]
+            .attestation_1
+            .attesting_indices
+            .to_vec(),
+        vec![attester as u64]
+    );
+
+    match chain
+        .verify_proposer_slashing_for_gossip(proposer_slashings[0].clone())
+        .expect("proposer slashing should be valid")
+    {
+        ObservationOutcome::New(slashing) => chain.import_proposer_slashing(slashing),
+        ObservationOutcome::AlreadyKnown => panic!("proposer slashing should be new"),
+    }
+    match chain
+        .verify_attester_slashing_for_gossip(attester_slashings[0].clone())
+        .expect("attester slashing should be valid")
+    {
+        ObservationOutcome::New(slashing) => chain
+            .import_attester_slashing(slashing)
+            .expect("should import attester slashing"),
+        ObservationOutcome::AlreadyKnown => panic!("attester slashing should be new"),
+    }
+
+    let state = chain.head().expect("should get head").beacon_state;
+    let (pooled_proposer_slashings, pooled_attester_slashings) =
+        chain.op_pool.get_slashings(&state, &chain.spec);
+    assert_eq!(pooled_proposer_slashings, proposer_slashings);
+    assert_eq!(pooled_attester_slashings, attester_slashings);
+}
@@ -4,19 +4,25 @@
 extern crate lazy_static;
 
 use beacon_chain::attestation_verification::Error as AttnError;
+use beacon_chain::builder::BeaconChainBuilder;
+use beacon_chain::migrate::NullMigrator;
 use beacon_chain::test_utils::{
     AttestationStrategy, BeaconChainHarness, BlockStrategy, DiskHarnessType,
 };
 use beacon_chain::BeaconSnapshot;
+use beacon_chain::HistoricalBlockError;
 use beacon_chain::StateSkipConfig;
 use rand::Rng;
 use sloggers::{null::NullLoggerBuilder, Build};
+use state_processing::per_slot_processing;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::sync::Arc;
 use store::{
+    chunked_iter::ChunkedVectorIter,
+    chunked_vector::BlockRoots,
     iter::{BlockRootsIterator, StateRootsIterator},
-    HotColdDB, Store, StoreConfig,
+    DBColumn, HotColdDB, KeyValueStore, Store, StoreConfig,
 };
 use tempfile::{tempdir, TempDir};
 use tree_hash::TreeHash;
@@ -36,10 +42,13 @@ type E = MinimalEthSpec;
 type TestHarness = BeaconChainHarness<DiskHarnessType<E>>;
 
 fn get_store(db_path: &TempDir) -> Arc<HotColdDB<E>> {
+    get_store_with_config(db_path, StoreConfig::default())
+}
+
+fn get_store_with_config(db_path: &TempDir, config: StoreConfig) -> Arc<HotColdDB<E>> {
     let spec = MinimalEthSpec::default_spec();
     let hot_path = db_path.path().join("hot_db");
     let cold_path = db_path.path().join("cold_db");
-    let config = StoreConfig::default();
     let log = NullLoggerBuilder.build().expect("logger should build");
     Arc::new(
         HotColdDB::open(&hot_path, &cold_path, config, spec, log)
@@ -76,6 +85,369 @@ fn full_participation_no_skips() {
     check_iterators(&harness);
 }
 
+#[test]
+fn database_inspection() {
+    let num_blocks_produced = E::slots_per_epoch() * 5;
+    let db_path = tempdir().unwrap();
+    let store = get_store(&db_path);
+    let harness = get_harness(store.clone(), LOW_VALIDATOR_COUNT);
+
+    harness.extend_chain(
+        num_blocks_produced as usize,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::AllValidators,
+    );
+
+    let genesis_state_root = harness.chain.chain_dump().unwrap()[0].beacon_state_root;
+    assert_eq!(
+        store.get_restore_points().unwrap(),
+        vec![(Slot::new(0), genesis_state_root)]
+    );
+    assert_eq!(store.find_unreachable_hot_states().unwrap(), vec![]);
+
+    // Deleting the head state makes the head unreachable, since it is on an epoch boundary.
+    let head = harness.chain.head().unwrap();
+    assert_eq!(head.beacon_state.slot % E::slots_per_epoch(), 0);
+    store
+        .hot_db
+        .key_delete(
+            DBColumn::BeaconState.into(),
+            head.beacon_state_root.as_bytes(),
+        )
+        .unwrap();
+
+    let unreachable = store.find_unreachable_hot_states().unwrap();
+    assert_eq!(unreachable.len(), 1);
+    assert_eq!(unreachable[0].state_root, head.beacon_state_root);
+    assert_eq!(unreachable[0].slot, head.beacon_state.slot);
+    assert_eq!(
+        unreachable[0].epoch_boundary_state_root,
+        head.beacon_state_root
+    );
+
+    // Compaction must not lose any data.
+    store.compact();
+    assert_eq!(store.find_unreachable_hot_states().unwrap(), unreachable);
+}
+
+#[test]
+fn no_history_prunes_states() {
+    let num_blocks_produced = E::slots_per_epoch() * 5;
+    let db_path = tempdir().unwrap();
+    let config = StoreConfig {
+        max_restore_points: Some(0),
+        ..StoreConfig::default()
+    };
+    let store = get_store_with_config(&db_path, config);
+    let harness = get_harness(store.clone(), LOW_VALIDATOR_COUNT);
+
+    harness.extend_chain(
+        num_blocks_produced as usize,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::AllValidators,
+    );
+
+    check_finalization(&harness, num_blocks_produced);
+    check_split_slot(&harness, store.clone());
+
+    assert_eq!(store.get_restore_points().unwrap(), vec![]);
+    match store.load_cold_state_by_slot(Slot::new(1)) {
+        Err(store::Error::StatePruned { slot }) => assert_eq!(slot, 1),
+        other => panic!("expected StatePruned, got {:?}", other.map(|_| ())),
+    }
+
+    // The block roots of the freezer must still be available.
+    let block_roots = harness
+        .chain
+        .forwards_iter_block_roots(Slot::new(0))
+        .unwrap()
+        .map(Result::unwrap)
+        .collect::<Vec<_>>();
+    assert_eq!(block_roots.len() as u64, num_blocks_produced + 1);
+    assert_eq!(
+        block_roots[0],
+        (harness.chain.genesis_block_root, Slot::new(0))
+    );
+    for (block_root, slot) in block_roots {
+        let block = store.get_block(&block_root).unwrap().expect("block exists");
+        assert_eq!(block.slot(), slot);
+    }
+}
+
+#[test]
+fn prune_restore_points() {
+    let num_blocks_produced = E::slots_per_epoch() * 5;
+    let db_path = tempdir().unwrap();
+    let config = StoreConfig {
+        slots_per_restore_point: E::slots_per_epoch(),
+        ..StoreConfig::default()
+    };
+    let store = get_store_with_config(&db_path, config);
+    let harness = get_harness(store.clone(), LOW_VALIDATOR_COUNT);
+
+    harness.extend_chain(
+        num_blocks_produced as usize,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::AllValidators,
+    );
+
+    let restore_points = store.get_restore_points().unwrap();
+    assert!(restore_points.len() > 1);
+    let split_slot = store.get_split_slot();
+    let latest_restore_point_slot = restore_points.last().unwrap().0;
+
+    store.prune_restore_points(1).unwrap();
+
+    assert_eq!(
+        store.get_restore_points().unwrap(),
+        vec![*restore_points.last().unwrap()]
+    );
+    match store.load_cold_state_by_slot(latest_restore_point_slot - 1) {
+        Err(store::Error::StatePruned { .. }) => {}
+        other => panic!("expected StatePruned, got {:?}", other.map(|_| ())),
+    }
+
+    // States from the remaining restore point up to the split are still available.
+    for slot in latest_restore_point_slot.as_u64()..split_slot.as_u64() {
+        let state = store
+            .load_cold_state_by_slot(Slot::new(slot))
+            .expect("state should be available");
+        assert_eq!(state.slot, slot);
+    }
+}
+
+#[test]
+fn weak_subjectivity_backfill() {
+    let num_blocks_produced = E::slots_per_epoch() * 5;
+    let db_path = tempdir().unwrap();
+    let store = get_store(&db_path);
+    let harness = get_harness(store.clone(), LOW_VALIDATOR_COUNT);
+
+    harness.extend_chain(
+        num_blocks_produced as usize,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::AllValidators,
+    );
+
+    // Start a second chain from a finalized checkpoint of the first.
+    let chain_dump = harness.chain.chain_dump().unwrap();
+    let anchor_slot = Slot::new(E::slots_per_epoch() * 2);
+    let anchor = chain_dump
+        .iter()
+        .find(|snapshot| snapshot.beacon_block.slot() == anchor_slot)
+        .expect("should have a block at the anchor slot");
+
+    let ws_db_path = tempdir().unwrap();
+    let ws_store = get_store(&ws_db_path);
+    let ws_harness = BeaconChainHarness::new_from_checkpoint(
+        MinimalEthSpec,
+        ws_store.clone(),
+        KEYPAIRS[0..LOW_VALIDATOR_COUNT].to_vec(),
+        anchor.beacon_state.clone(),
+        anchor.beacon_block.clone(),
+    );
+
+    let anchor_info = ws_store.get_anchor_info().expect("should have anchor info");
+    assert_eq!(anchor_info.anchor_slot, anchor_slot);
+    assert_eq!(anchor_info.oldest_block_slot, anchor_slot);
+    assert!(!anchor_info.block_backfill_complete());
+
+    let historical_blocks = chain_dump
+        .iter()
+        .filter(|snapshot| snapshot.beacon_block.slot() < anchor_slot)
+        .map(|snapshot| snapshot.beacon_block.clone())
+        .collect::<Vec<_>>();
+    let mut batches = historical_blocks
+        .chunks(E::slots_per_epoch() as usize)
+        .rev()
+        .map(|batch| batch.to_vec())
+        .collect::<Vec<_>>();
+
+    // A batch which does not end at the parent of the oldest block is rejected.
+    match ws_harness.chain.import_historical_block_batch(&batches[1]) {
+        Err(HistoricalBlockError::MismatchedBlockRoot { .. }) => (),
+        other => panic!("expected mismatched block root, got {:?}", other),
+    }
+
+    // A batch with an invalid proposer signature is rejected.
+    let mut invalid_batch = batches[0].clone();
+    let last = invalid_batch.len() - 1;
+    invalid_batch[last].signature = invalid_batch[0].signature.clone();
+    match ws_harness
+        .chain
+        .import_historical_block_batch(&invalid_batch)
+    {
+        Err(HistoricalBlockError::InvalidSignature) => (),
+        other => panic!("expected invalid signature, got {:?}", other),
+    }
+    assert_eq!(ws_store.get_anchor_info(), Some(anchor_info));
+
+    // Importing the batches newest-first fills in the chain back to genesis.
+    for batch in batches.drain(..) {
+        let num_imported = ws_harness
+            .chain
+            .import_historical_block_batch(&batch)
+            .expect("should import historical batch");
+        assert_eq!(num_imported, batch.len());
+        assert_eq!(
+            ws_store.get_anchor_info().unwrap().oldest_block_slot,
+            batch[0].slot()
+        );
+    }
+
+    assert!(ws_store
+        .get_anchor_info()
+        .unwrap()
+        .block_backfill_complete());
+
+    for block in &historical_blocks {
+        assert_eq!(
+            ws_store
+                .get_block(&block.canonical_root())
+                .unwrap()
+                .as_ref(),
+            Some(block)
+        );
+    }
+
+    // The block roots of the back-filled range are available from the freezer.
+    let frozen_block_roots =
+        ChunkedVectorIter::<BlockRoots, E>::new(ws_store.clone(), 0, anchor_slot, &harness.spec)
+            .map(|(_, block_root)| block_root)
+            .collect::<Vec<_>>();
+    let expected_block_roots = historical_blocks
+        .iter()
+        .map(|block| block.canonical_root())
+        .collect::<Vec<_>>();
+    assert_eq!(frozen_block_roots, expected_block_roots);
+}
+
+#[test]
+fn weak_subjectivity_anchor_with_skipped_boundary_slot() {
+    let num_slots = E::slots_per_epoch() * 5;
+    let anchor_slot = Slot::new(E::slots_per_epoch() * 2);
+    let db_path = tempdir().unwrap();
+    let store = get_store(&db_path);
+    let harness = get_harness(store.clone(), LOW_VALIDATOR_COUNT);
+
+    // Build a chain on which the first slot of the anchor epoch is skipped.
+    let mut head_slot = 0;
+    for slot in 1..=num_slots {
+        if slot != anchor_slot {
+            harness.extend_chain(
+                1,
+                BlockStrategy::ForkCanonicalChainAt {
+                    previous_slot: Slot::new(head_slot),
+                    first_slot: Slot::new(slot),
+                },
+                AttestationStrategy::AllValidators,
+            );
+            head_slot = slot;
+        }
+        harness.advance_slot();
+    }
+
+    // The checkpoint block is the last block before the anchor slot, and the checkpoint state is
+    // its post-state advanced to the anchor slot.
+    let chain_dump = harness.chain.chain_dump().unwrap();
+    let anchor = chain_dump
+        .iter()
+        .find(|snapshot| snapshot.beacon_block.slot() == anchor_slot - 1)
+        .expect("should have a block before the anchor slot");
+    let mut anchor_state = anchor.beacon_state.clone();
+    per_slot_processing(
+        &mut anchor_state,
+        Some(anchor.beacon_state_root),
+        &harness.spec,
+    )
+    .unwrap();
+    assert_eq!(anchor_state.slot, anchor_slot);
+
+    // The anchor state must be a post-state of the anchor block.
+    let ws_db_path = tempdir().unwrap();
+    let ws_store = get_store(&ws_db_path);
+    let earlier_block = chain_dump
+        .iter()
+        .find(|snapshot| snapshot.beacon_block.slot() == anchor_slot - 2)
+        .expect("should have an earlier block")
+        .beacon_block
+        .clone();
+    assert!(BeaconChainBuilder::new(MinimalEthSpec)
+        .logger(NullLoggerBuilder.build().expect("logger should build"))
+        .store(ws_store.clone())
+        .store_migrator(NullMigrator)
+        .data_dir(ws_db_path.path().to_path_buf())
+        .weak_subjectivity_state(
+            anchor_state.clone(),
+            earlier_block,
+            chain_dump[0].beacon_state.clone(),
+        )
+        .is_err());
+
+    let ws_harness = BeaconChainHarness::new_from_checkpoint(
+        MinimalEthSpec,
+        ws_store.clone(),
+        KEYPAIRS[0..LOW_VALIDATOR_COUNT].to_vec(),
+        anchor_state,
+        anchor.beacon_block.clone(),
+    );
+
+    let anchor_info = ws_store.get_anchor_info().expect("should have anchor info");
+    assert_eq!(anchor_info.anchor_slot, anchor_slot);
+    assert_eq!(anchor_info.oldest_block_slot, anchor_slot - 1);
+    assert_eq!(
+        ws_harness.chain.head().unwrap().beacon_block_root,
+        anchor.beacon_block_root
+    );
+
+    // The chain can be extended from the anchor and finalized beyond it.
+    ws_harness.extend_chain(
+        1,
+        BlockStrategy::ForkCanonicalChainAt {
+            previous_slot: anchor_slot,
+            first_slot: anchor_slot + 1,
+        },
+        AttestationStrategy::AllValidators,
+    );
+    ws_harness.advance_slot();
+    ws_harness.extend_chain(
+        E::slots_per_epoch() as usize * 4,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::AllValidators,
+    );
+    assert!(ws_store.get_split_slot() > anchor_slot);
+
+    // Back-filling fills in the block roots of every slot before the anchor.
+    let historical_blocks = chain_dump
+        .iter()
+        .filter(|snapshot| snapshot.beacon_block.slot() < anchor_slot - 1)
+        .map(|snapshot| snapshot.beacon_block.clone())
+        .collect::<Vec<_>>();
+    ws_harness
+        .chain
+        .import_historical_block_batch(&historical_blocks)
+        .expect("should import historical blocks");
+    assert!(ws_store
+        .get_anchor_info()
+        .unwrap()
+        .block_backfill_complete());
+
+    let frozen_block_roots =
+        ChunkedVectorIter::<BlockRoots, E>::new(ws_store.clone(), 0, anchor_slot, &harness.spec)
+            .map(|(_, block_root)| block_root)
+            .collect::<Vec<_>>();
+    let mut expected_block_roots = harness
+        .chain
+        .rev_iter_block_roots()
+        .unwrap()
+        .map(Result::unwrap)
+        .filter(|(_, slot)| *slot < anchor_slot)
+        .map(|(block_root, _)| block_root)
+        .collect::<Vec<_>>();
+    expected_block_roots.reverse();
+    assert_eq!(frozen_block_roots, expected_block_roots);
+}
+
 #[test]
 fn randomised_skips() {
     let num_slots = E::slots_per_epoch() * 5;
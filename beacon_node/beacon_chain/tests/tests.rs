@@ -5,13 +5,21 @@ extern crate lazy_static;
 
 use beacon_chain::{
     attestation_verification::Error as AttnError,
+    events::{EventKind, EventType},
     test_utils::{
         AttestationStrategy, BeaconChainHarness, BlockStrategy, HarnessType, OP_POOL_DB_KEY,
     },
 };
 use operation_pool::PersistedOperationPool;
 use state_processing::{
-    per_slot_processing, per_slot_processing::Error as SlotProcessingError, EpochProcessingError,
+    common::get_base_reward,
+    per_epoch_processing::{
+        apply_rewards::{get_attestation_deltas, process_rewards_and_penalties},
+        validator_statuses::ValidatorStatuses,
+    },
+    per_slot_processing,
+    per_slot_processing::Error as SlotProcessingError,
+    EpochProcessingError,
 };
 use store::Store;
 use types::{BeaconStateError, EthSpec, Hash256, Keypair, MinimalEthSpec, RelativeEpoch, Slot};
@@ -182,6 +190,95 @@ fn chooses_fork() {
     );
 }
 
+#[test]
+fn reorg_event() {
+    let harness = get_harness(VALIDATOR_COUNT);
+
+    let two_thirds = (VALIDATOR_COUNT / 3) * 2;
+    let delay = MinimalEthSpec::default_spec().min_attestation_inclusion_delay as usize;
+
+    let minority_validators: Vec<usize> = (two_thirds..VALIDATOR_COUNT).collect();
+    let majority_validators: Vec<usize> = (0..two_thirds).collect();
+
+    harness.extend_chain(
+        delay + 1,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::AllValidators,
+    );
+    let common_ancestor_slot = harness
+        .chain
+        .head()
+        .expect("should get head")
+        .beacon_block
+        .slot();
+
+    let mut receiver = harness
+        .chain
+        .event_handler
+        .subscribers()
+        .subscribe(vec![EventType::BeaconChainReorg])
+        .expect("should subscribe");
+
+    // The chain first follows the fork attested to by the minority, then reorgs to the fork
+    // attested to by the majority.
+    let (minority_head, majority_head) = harness.generate_two_forks_by_skipping_a_block(
+        &minority_validators,
+        &majority_validators,
+        delay + 1,
+        delay + 2,
+    );
+
+    assert_eq!(
+        harness
+            .chain
+            .head()
+            .expect("should get head")
+            .beacon_block_root,
+        majority_head,
+        "the majority fork should be the canonical chain"
+    );
+
+    let minority_head_block = harness
+        .chain
+        .get_block(&minority_head)
+        .expect("should read block")
+        .expect("block should exist");
+
+    match receiver.try_next() {
+        Ok(Some(EventKind::BeaconChainReorg {
+            slot,
+            depth,
+            old_head_block,
+            new_head_block,
+            old_head_state,
+            new_head_state,
+            epoch,
+        })) => {
+            assert_eq!(old_head_block, minority_head);
+            assert_eq!(old_head_state, minority_head_block.state_root());
+            assert_eq!(
+                depth,
+                (minority_head_block.slot() - common_ancestor_slot).as_u64()
+            );
+
+            let new_head_block = harness
+                .chain
+                .get_block(&new_head_block)
+                .expect("should read block")
+                .expect("new head block should exist");
+            assert_eq!(new_head_state, new_head_block.state_root());
+            assert_eq!(slot, new_head_block.slot());
+            assert_eq!(epoch, slot.epoch(MinimalEthSpec::slots_per_epoch()));
+            assert!(
+                slot > common_ancestor_slot + 1,
+                "new head is on the majority fork"
+            );
+        }
+        other => panic!("expected a reorg event, got {:?}", other),
+    }
+    assert!(receiver.try_next().is_err(), "there should be one reorg");
+}
+
 #[test]
 fn finalizes_with_full_participation() {
     let num_blocks_produced = MinimalEthSpec::slots_per_epoch() * 5;
@@ -330,6 +427,142 @@ fn does_not_finalize_without_attestation() {
     );
 }
 
+#[test]
+fn attestation_deltas_with_two_thirds_participation() {
+    // Stop one slot short of an epoch boundary so the previous epoch has not yet been processed.
+    let num_blocks_produced = MinimalEthSpec::slots_per_epoch() * 3 - 1;
+
+    let harness = get_harness(VALIDATOR_COUNT);
+
+    let two_thirds = (VALIDATOR_COUNT / 3) * 2;
+    let attesters = (0..two_thirds).collect();
+
+    harness.extend_chain(
+        num_blocks_produced as usize,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::SomeValidators(attesters),
+    );
+
+    let spec = &harness.spec;
+    let mut state = harness.chain.head().expect("should get head").beacon_state;
+    state
+        .build_all_committee_caches(spec)
+        .expect("should build caches");
+
+    let mut validator_statuses =
+        ValidatorStatuses::new(&state, spec).expect("should get validator statuses");
+    validator_statuses
+        .process_attestations(&state, spec)
+        .expect("should process attestations");
+
+    let deltas =
+        get_attestation_deltas(&state, &validator_statuses, spec).expect("should get deltas");
+    assert_eq!(deltas.len(), VALIDATOR_COUNT);
+
+    let total_balances = &validator_statuses.total_balances;
+    let total_balance_ebi = total_balances.current_epoch() / spec.effective_balance_increment;
+
+    assert!(
+        total_balances.previous_epoch_head_attesters() > 0,
+        "some attestations should be included in the previous epoch"
+    );
+
+    let mut inclusion_delay_rewards = vec![0; VALIDATOR_COUNT];
+
+    for (index, (status, delta)) in validator_statuses.statuses.iter().zip(&deltas).enumerate() {
+        let base_reward = get_base_reward(&state, index, total_balances.current_epoch(), spec)
+            .expect("should get base reward");
+        let reward_for = |attesting_balance: u64| {
+            base_reward * (attesting_balance / spec.effective_balance_increment) / total_balance_ebi
+        };
+        let expected_delta = |is_attester: bool, attesting_balance: u64| {
+            if is_attester {
+                (reward_for(attesting_balance), 0)
+            } else {
+                (0, base_reward)
+            }
+        };
+
+        if status.is_previous_epoch_attester {
+            assert!(
+                index < two_thirds,
+                "validator {} did not attest but was counted as an attester",
+                index
+            );
+
+            let inclusion = status
+                .inclusion_info
+                .expect("attesters should have inclusion info");
+            let proposer_reward = base_reward / spec.proposer_reward_quotient;
+            inclusion_delay_rewards[index] += (base_reward - proposer_reward) / inclusion.delay;
+            inclusion_delay_rewards[inclusion.proposer_index] += proposer_reward;
+        }
+
+        assert_eq!(
+            (delta.source_delta.rewards, delta.source_delta.penalties),
+            expected_delta(
+                status.is_previous_epoch_attester,
+                total_balances.previous_epoch_attesters()
+            ),
+            "source delta for validator {}",
+            index
+        );
+        assert_eq!(
+            (delta.target_delta.rewards, delta.target_delta.penalties),
+            expected_delta(
+                status.is_previous_epoch_target_attester,
+                total_balances.previous_epoch_target_attesters()
+            ),
+            "target delta for validator {}",
+            index
+        );
+        assert_eq!(
+            (delta.head_delta.rewards, delta.head_delta.penalties),
+            expected_delta(
+                status.is_previous_epoch_head_attester,
+                total_balances.previous_epoch_head_attesters()
+            ),
+            "head delta for validator {}",
+            index
+        );
+        assert_eq!(
+            (
+                delta.inactivity_penalty_delta.rewards,
+                delta.inactivity_penalty_delta.penalties
+            ),
+            (0, 0),
+            "no inactivity penalty should apply whilst finality is recent"
+        );
+    }
+
+    for (index, delta) in deltas.iter().enumerate() {
+        assert_eq!(
+            (
+                delta.inclusion_delay_delta.rewards,
+                delta.inclusion_delay_delta.penalties
+            ),
+            (inclusion_delay_rewards[index], 0),
+            "inclusion delay delta for validator {}",
+            index
+        );
+    }
+
+    // The flattened deltas should be exactly what is applied to the balances.
+    let mut new_state = state.clone();
+    process_rewards_and_penalties(&mut new_state, &mut validator_statuses, spec)
+        .expect("should process rewards and penalties");
+
+    for (index, delta) in deltas.into_iter().enumerate() {
+        let delta = delta.flatten().expect("should flatten delta");
+        assert_eq!(
+            new_state.balances[index],
+            (state.balances[index] + delta.rewards).saturating_sub(delta.penalties),
+            "balance of validator {}",
+            index
+        );
+    }
+}
+
 #[test]
 fn roundtrip_operation_pool() {
     let num_blocks_produced = MinimalEthSpec::slots_per_epoch() * 5;
@@ -1,25 +1,28 @@
 use crate::config::{ClientGenesis, Config as ClientConfig};
 use crate::notifier::spawn_notifier;
+use crate::slasher_service::spawn_slasher_service;
 use crate::Client;
-use beacon_chain::events::TeeEventHandler;
+use beacon_chain::events::{EventSubscribers, TeeEventHandler};
 use beacon_chain::{
     builder::{BeaconChainBuilder, Witness},
     eth1_chain::{CachingEth1Backend, Eth1Chain},
     migrate::{BackgroundMigrator, Migrate, NullMigrator},
+    schema_change::migrate_to_current_schema,
     slot_clock::{SlotClock, SystemTimeSlotClock},
-    store::{HotColdDB, MemoryStore, Store, StoreConfig},
+    store::{Error as StoreError, HotColdDB, MemoryStore, Store, StoreConfig},
     BeaconChain, BeaconChainTypes, Eth1ChainBackend, EventHandler,
 };
-use bus::Bus;
 use environment::RuntimeContext;
 use eth1::{Config as Eth1Config, Service as Eth1Service};
 use eth2_config::Eth2Config;
 use eth2_libp2p::NetworkGlobals;
 use genesis::{interop_genesis_state, Eth1GenesisService};
 use network::{NetworkConfig, NetworkMessage, NetworkService};
-use parking_lot::Mutex;
+use remote_beacon_node::RemoteBeaconNode;
+use slasher::Slasher;
 use slog::info;
 use ssz::Decode;
+use state_processing::per_slot_processing;
 use std::net::SocketAddr;
 use std::path::Path;
 use std::sync::Arc;
@@ -27,14 +30,18 @@ use std::time::Duration;
 use timer::spawn_timer;
 use tokio::sync::mpsc::UnboundedSender;
 use types::{
-    test_utils::generate_deterministic_keypairs, BeaconState, ChainSpec, EthSpec,
-    SignedBeaconBlockHash,
+    test_utils::generate_deterministic_keypairs, BeaconState, ChainSpec, EthSpec, SignedBeaconBlock,
 };
 use websocket_server::{Config as WebSocketConfig, WebSocketSender};
 
 /// Interval between polling the eth1 node for genesis information.
 pub const ETH1_GENESIS_UPDATE_INTERVAL_MILLIS: u64 = 7_000;
 
+/// Timeout for HTTP requests made when fetching a checkpoint from a remote beacon node.
+///
+/// Generous, since `BeaconState` responses are large.
+pub const CHECKPOINT_SYNC_TIMEOUT_SECONDS: u64 = 180;
+
 /// Builds a `Client` instance.
 ///
 /// ## Notes
@@ -144,6 +151,14 @@ where
             .custom_spec(spec.clone())
             .disabled_forks(disabled_forks);
 
+        let builder = if let Some(slasher_config) = config.slasher.clone() {
+            let slasher = Slasher::open(slasher_config, context.log().clone())
+                .map_err(|e| format!("Unable to open slasher: {:?}", e))?;
+            builder.slasher(Arc::new(slasher))
+        } else {
+            builder
+        };
+
         let chain_exists = builder
             .store_contains_beacon_chain()
             .unwrap_or_else(|_| false);
@@ -186,6 +201,83 @@ where
 
                 builder.genesis_state(genesis_state).map(|v| (v, None))?
             }
+            ClientGenesis::WeakSubjSszBytes {
+                anchor_state_bytes,
+                anchor_block_bytes,
+                genesis_state_bytes,
+            } => {
+                info!(
+                    context.log(),
+                    "Starting from weak subjectivity checkpoint";
+                );
+
+                let anchor_state = BeaconState::from_ssz_bytes(&anchor_state_bytes)
+                    .map_err(|e| format!("Unable to parse checkpoint state SSZ: {:?}", e))?;
+                let anchor_block = SignedBeaconBlock::from_ssz_bytes(&anchor_block_bytes)
+                    .map_err(|e| format!("Unable to parse checkpoint block SSZ: {:?}", e))?;
+                let genesis_state = BeaconState::from_ssz_bytes(&genesis_state_bytes)
+                    .map_err(|e| format!("Unable to parse genesis state SSZ: {:?}", e))?;
+
+                builder
+                    .weak_subjectivity_state(anchor_state, anchor_block, genesis_state)
+                    .map(|v| (v, None))?
+            }
+            ClientGenesis::CheckpointSyncUrl {
+                url,
+                genesis_state_bytes,
+            } => {
+                info!(
+                    context.log(),
+                    "Fetching weak subjectivity checkpoint";
+                    "remote_url" => &url,
+                );
+
+                let remote = RemoteBeaconNode::<TEthSpec>::new_with_timeout(
+                    url,
+                    Duration::from_secs(CHECKPOINT_SYNC_TIMEOUT_SECONDS),
+                )?;
+
+                let head = remote
+                    .http
+                    .beacon()
+                    .get_head()
+                    .await
+                    .map_err(|e| format!("Unable to fetch remote head: {:?}", e))?;
+                let (anchor_block, _) = remote
+                    .http
+                    .beacon()
+                    .get_block_by_root(head.finalized_block_root)
+                    .await
+                    .map_err(|e| format!("Unable to fetch remote finalized block: {:?}", e))?;
+                let (mut anchor_state, _) = remote
+                    .http
+                    .beacon()
+                    .get_state_by_root(anchor_block.state_root())
+                    .await
+                    .map_err(|e| format!("Unable to fetch remote finalized state: {:?}", e))?;
+
+                // If the first slot of the finalized epoch was skipped, the finalized block
+                // precedes it and its post-state must be advanced to the epoch boundary.
+                let mut state_root = Some(anchor_block.state_root());
+                while anchor_state.slot < head.finalized_slot {
+                    per_slot_processing(&mut anchor_state, state_root.take(), &spec)
+                        .map_err(|e| format!("Unable to advance finalized state: {:?}", e))?;
+                }
+
+                info!(
+                    context.log(),
+                    "Starting from weak subjectivity checkpoint";
+                    "slot" => anchor_block.slot(),
+                    "block_root" => format!("{:?}", head.finalized_block_root),
+                );
+
+                let genesis_state = BeaconState::from_ssz_bytes(&genesis_state_bytes)
+                    .map_err(|e| format!("Unable to parse genesis state SSZ: {:?}", e))?;
+
+                builder
+                    .weak_subjectivity_state(anchor_state, anchor_block, genesis_state)
+                    .map(|v| (v, None))?
+            }
             ClientGenesis::DepositContract => {
                 info!(
                     context.log(),
@@ -266,7 +358,7 @@ where
         mut self,
         client_config: &ClientConfig,
         eth2_config: &Eth2Config,
-        events: Arc<Mutex<Bus<SignedBeaconBlockHash>>>,
+        events: Arc<EventSubscribers<TEthSpec>>,
     ) -> Result<Self, String> {
         let beacon_chain = self
             .beacon_chain
@@ -344,6 +436,34 @@ where
         Ok(self)
     }
 
+    /// Immediately starts the service that periodically processes the slasher's queued messages,
+    /// if the beacon chain has a slasher.
+    pub fn slasher_service(self) -> Result<Self, String> {
+        let beacon_chain = self
+            .beacon_chain
+            .clone()
+            .ok_or_else(|| "slasher_service requires a beacon chain")?;
+
+        if beacon_chain.slasher.is_none() {
+            return Ok(self);
+        }
+
+        let context = self
+            .runtime_context
+            .as_ref()
+            .ok_or_else(|| "slasher_service requires a runtime_context")?
+            .service_context("slasher".into());
+        let network_send = self
+            .network_send
+            .clone()
+            .ok_or_else(|| "slasher_service requires a libp2p network sender")?;
+
+        spawn_slasher_service(context.executor, beacon_chain, network_send)
+            .map_err(|e| format!("Unable to start slasher service: {}", e))?;
+
+        Ok(self)
+    }
+
     /// Consumers the builder, returning a `Client` if all necessary components have been
     /// specified.
     ///
@@ -464,14 +584,13 @@ where
     pub fn tee_event_handler(
         mut self,
         config: WebSocketConfig,
-    ) -> Result<(Self, Arc<Mutex<Bus<SignedBeaconBlockHash>>>), String> {
+    ) -> Result<(Self, Arc<EventSubscribers<TEthSpec>>), String> {
         let context = self
             .runtime_context
             .as_ref()
             .ok_or_else(|| "websocket_event_handler requires a runtime_context")?
             .service_context("ws".into());
 
-        let log = context.log().clone();
         let (sender, listening_addr): (WebSocketSender<TEthSpec>, Option<_>) = if config.enabled {
             let (sender, listening_addr) =
                 websocket_server::start_server(context.executor, &config)?;
@@ -481,9 +600,9 @@ where
         };
 
         self.websocket_listen_addr = listening_addr;
-        let (tee_event_handler, bus) = TeeEventHandler::new(log, sender)?;
+        let (tee_event_handler, subscribers) = TeeEventHandler::new(sender)?;
         self.event_handler = Some(tee_event_handler);
-        Ok((self, bus))
+        Ok((self, subscribers))
     }
 }
 
@@ -524,6 +643,17 @@ where
 
         let store = HotColdDB::open(hot_path, cold_path, config, spec, context.log().clone())
             .map_err(|e| format!("Unable to open database: {:?}", e))?;
+
+        migrate_to_current_schema(&store, context.log()).map_err(|e| match e {
+            StoreError::SchemaVersionTooNew { on_disk, supported } => format!(
+                "Database schema version {} is newer than the latest version supported by this \
+                 version of Lighthouse ({}), downgrading is not supported",
+                on_disk.as_u64(),
+                supported.as_u64()
+            ),
+            e => format!("Unable to migrate database schema: {:?}", e),
+        })?;
+
         self.store = Some(Arc::new(store));
         Ok(self)
     }
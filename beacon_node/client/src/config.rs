@@ -29,6 +29,21 @@ pub enum ClientGenesis {
     /// We include the bytes instead of the `BeaconState<E>` because the `EthSpec` type
     /// parameter would be very annoying.
     SszBytes { genesis_state_bytes: Vec<u8> },
+    /// Starts from a trusted, finalized `BeaconState` and `SignedBeaconBlock` (a weak subjectivity
+    /// checkpoint) rather than from genesis, without downloading the blocks prior to it.
+    ///
+    /// The genesis state is still required in order to determine the genesis block root.
+    WeakSubjSszBytes {
+        anchor_state_bytes: Vec<u8>,
+        anchor_block_bytes: Vec<u8>,
+        genesis_state_bytes: Vec<u8>,
+    },
+    /// As per `WeakSubjSszBytes`, except the checkpoint is the latest finalized block (and its
+    /// state) of the beacon node HTTP API at `url`.
+    CheckpointSyncUrl {
+        url: String,
+        genesis_state_bytes: Vec<u8>,
+    },
 }
 
 impl Default for ClientGenesis {
@@ -64,6 +79,8 @@ pub struct Config {
     pub rest_api: rest_api::Config,
     pub websocket_server: websocket_server::Config,
     pub eth1: eth1::Config,
+    /// Configuration for the slasher, which is disabled if `None`.
+    pub slasher: Option<slasher::Config>,
 }
 
 impl Default for Config {
@@ -84,6 +101,7 @@ impl Default for Config {
             sync_eth1_chain: false,
             eth1: <_>::default(),
             disabled_forks: Vec::new(),
+            slasher: None,
         }
     }
 }
@@ -1,6 +1,6 @@
 use crate::metrics;
 use beacon_chain::{BeaconChain, BeaconChainTypes};
-use eth2_libp2p::NetworkGlobals;
+use eth2_libp2p::{types::SyncState, NetworkGlobals};
 use futures::prelude::*;
 use parking_lot::Mutex;
 use slog::{debug, error, info, warn};
@@ -132,6 +132,20 @@ pub fn spawn_notifier<T: BeaconChainTypes>(
                     "est_time" => estimated_time_pretty(speedo.estimated_time_till_slot(current_slot)),
                 );
             } else {
+                if let SyncState::BackFillSyncing {
+                    completed,
+                    remaining,
+                } = sync_state
+                {
+                    info!(
+                        log,
+                        "Downloading historical blocks";
+                        "peers" => peer_count_pretty(connected_peer_count),
+                        "completed_slots" => completed,
+                        "remaining_slots" => remaining,
+                    );
+                }
+
                 if sync_state.is_synced() {
                     let block_info = if current_slot > head_slot {
                         format!("   …  empty")
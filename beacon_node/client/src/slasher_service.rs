@@ -0,0 +1,152 @@
+use beacon_chain::{observed_operations::ObservationOutcome, BeaconChain, BeaconChainTypes};
+use eth2_libp2p::PubsubMessage;
+use futures::prelude::*;
+use network::NetworkMessage;
+use slog::{debug, error, info, Logger};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc::UnboundedSender;
+
+/// Spawns a service which periodically processes the attestations and blocks queued in the
+/// slasher, adding any resulting slashings to the op pool and publishing them on gossip.
+pub fn spawn_slasher_service<T: BeaconChainTypes>(
+    executor: environment::TaskExecutor,
+    beacon_chain: Arc<BeaconChain<T>>,
+    network_send: UnboundedSender<NetworkMessage<T::EthSpec>>,
+) -> Result<(), String> {
+    let update_period = beacon_chain
+        .slasher
+        .as_ref()
+        .ok_or_else(|| "slasher_service requires the beacon chain to have a slasher")?
+        .config()
+        .update_period;
+
+    let log = executor.log().clone();
+    let inner_executor = executor.clone();
+    let mut interval = tokio::time::interval(Duration::from_secs(update_period));
+
+    let interval_future = async move {
+        while let Some(_) = interval.next().await {
+            let beacon_chain = beacon_chain.clone();
+            let network_send = network_send.clone();
+            let log = log.clone();
+
+            // Processing a batch reads and writes the slasher database, so it must not block the
+            // executor.
+            inner_executor.spawn_blocking(
+                move || process_slasher_batch(&beacon_chain, &network_send, &log),
+                "slasher_batch",
+            );
+        }
+    };
+
+    info!(
+        executor.log(),
+        "Slasher started";
+        "update_period_seconds" => update_period,
+    );
+
+    executor.spawn(interval_future, "slasher_service");
+
+    Ok(())
+}
+
+fn process_slasher_batch<T: BeaconChainTypes>(
+    beacon_chain: &BeaconChain<T>,
+    network_send: &UnboundedSender<NetworkMessage<T::EthSpec>>,
+    log: &Logger,
+) {
+    let slasher = match beacon_chain.slasher.as_ref() {
+        Some(slasher) => slasher,
+        None => return,
+    };
+
+    let current_epoch = match beacon_chain.epoch() {
+        Ok(epoch) => epoch,
+        Err(e) => {
+            error!(log, "Slasher unable to read current epoch"; "error" => format!("{:?}", e));
+            return;
+        }
+    };
+
+    if let Err(e) = slasher.process_queued(current_epoch) {
+        error!(log, "Error processing slasher batch"; "error" => format!("{:?}", e));
+        return;
+    }
+
+    for slashing in slasher.get_proposer_slashings() {
+        let proposer_index = slashing.signed_header_1.message.proposer_index;
+
+        match beacon_chain.verify_proposer_slashing_for_gossip(slashing.clone()) {
+            Ok(ObservationOutcome::New(verified_slashing)) => {
+                info!(
+                    log,
+                    "Found proposer slashing";
+                    "proposer_index" => proposer_index,
+                    "slot" => slashing.signed_header_1.message.slot.as_u64(),
+                );
+                beacon_chain.import_proposer_slashing(verified_slashing);
+                publish(
+                    network_send,
+                    PubsubMessage::ProposerSlashing(Box::new(slashing)),
+                    log,
+                );
+            }
+            Ok(ObservationOutcome::AlreadyKnown) => {}
+            Err(e) => debug!(
+                log,
+                "Slasher produced an invalid proposer slashing";
+                "proposer_index" => proposer_index,
+                "error" => format!("{:?}", e),
+            ),
+        }
+    }
+
+    for slashing in slasher.get_attester_slashings() {
+        match beacon_chain.verify_attester_slashing_for_gossip(slashing.clone()) {
+            Ok(ObservationOutcome::New(verified_slashing)) => {
+                info!(
+                    log,
+                    "Found attester slashing";
+                    "target_epoch" => slashing.attestation_2.data.target.epoch.as_u64(),
+                );
+                if let Err(e) = beacon_chain.import_attester_slashing(verified_slashing) {
+                    error!(
+                        log,
+                        "Unable to import attester slashing";
+                        "error" => format!("{:?}", e),
+                    );
+                }
+                publish(
+                    network_send,
+                    PubsubMessage::AttesterSlashing(Box::new(slashing)),
+                    log,
+                );
+            }
+            Ok(ObservationOutcome::AlreadyKnown) => {}
+            // An attester slashing is invalid if all of the validators involved are already
+            // slashed, which is common when the same offence is seen again.
+            Err(e) => debug!(
+                log,
+                "Slasher produced an invalid attester slashing";
+                "error" => format!("{:?}", e),
+            ),
+        }
+    }
+}
+
+fn publish<E: types::EthSpec>(
+    network_send: &UnboundedSender<NetworkMessage<E>>,
+    message: PubsubMessage<E>,
+    log: &Logger,
+) {
+    if let Err(e) = network_send.send(NetworkMessage::Publish {
+        messages: vec![message],
+    }) {
+        error!(
+            log,
+            "Unable to publish slashing";
+            "error" => format!("{:?}", e),
+        );
+    }
+}
@@ -1,5 +1,5 @@
 use crate::discovery::{enr::Eth2Enr, Discovery};
-use crate::peer_manager::{PeerManager, PeerManagerEvent};
+use crate::peer_manager::{GossipsubScoreParams, MessageAcceptance, PeerManager, PeerManagerEvent};
 use crate::rpc::*;
 use crate::types::{GossipEncoding, GossipKind, GossipTopic};
 use crate::{error, Enr, NetworkConfig, NetworkGlobals, PubsubMessage, TopicHash};
@@ -27,7 +27,7 @@ use std::{
     task::{Context, Poll},
     time::Instant,
 };
-use types::{EnrForkId, EthSpec, SignedBeaconBlock, SubnetId};
+use types::{ChainSpec, EnrForkId, EthSpec, SignedBeaconBlock, SubnetId};
 
 mod handler;
 
@@ -250,6 +250,7 @@ impl<TSpec: EthSpec> Behaviour<TSpec> {
         local_key: &Keypair,
         net_conf: &NetworkConfig,
         network_globals: Arc<NetworkGlobals<TSpec>>,
+        chain_spec: &ChainSpec,
         log: &slog::Logger,
     ) -> error::Result<Self> {
         let local_peer_id = local_key.public().into_peer_id();
@@ -283,7 +284,11 @@ impl<TSpec: EthSpec> Behaviour<TSpec> {
             gossipsub: Gossipsub::new(local_peer_id, net_conf.gs_config.clone()),
             discovery: Discovery::new(local_key, net_conf, network_globals.clone(), log)?,
             identify,
-            peer_manager: PeerManager::new(network_globals.clone(), log),
+            peer_manager: PeerManager::new(
+                network_globals.clone(),
+                GossipsubScoreParams::new::<TSpec>(chain_spec),
+                log,
+            ),
             events: Vec::new(),
             seen_gossip_messages: LruCache::new(100_000),
             meta_data,
@@ -392,6 +397,18 @@ impl<TSpec: EthSpec> Behaviour<TSpec> {
             .propagate_message(&message_id, propagation_source);
     }
 
+    /// Informs the peer manager of the result of validating a message received from
+    /// `propagation_source` on the topic of `kind`, which updates the peer's gossipsub score.
+    pub fn report_message_validation_result(
+        &mut self,
+        propagation_source: &PeerId,
+        kind: &GossipKind,
+        acceptance: MessageAcceptance,
+    ) {
+        self.peer_manager
+            .validation_result(propagation_source, kind, acceptance);
+    }
+
     /* Eth2 RPC behaviour functions */
 
     /// Send a request to a peer over RPC.
@@ -528,6 +545,14 @@ impl<TSpec: EthSpec> Behaviour<TSpec> {
         self.send_rpc(peer_id, event);
     }
 
+    /// Sends a Goodbye request to the peer.
+    fn goodbye_peer(&mut self, peer_id: PeerId, reason: GoodbyeReason) {
+        debug!(self.log, "Sending Goodbye"; "peer_id" => peer_id.to_string(), "reason" => reason.to_string());
+        let event = RPCSend::Request(RequestId::Behaviour, RPCRequest::Goodbye(reason));
+
+        self.send_rpc(peer_id, event);
+    }
+
     /// Sends a METADATA request to a peer.
     fn send_meta_data_request(&mut self, peer_id: PeerId) {
         let metadata_request =
@@ -756,11 +781,23 @@ impl<TSpec: EthSpec> Behaviour<TSpec> {
                     PeerManagerEvent::MetaData(peer_id) => {
                         self.send_meta_data_request(peer_id);
                     }
-                    PeerManagerEvent::_DisconnectPeer(_peer_id) => {
-                        //TODO: Implement
+                    PeerManagerEvent::DisconnectPeer(peer_id) => {
+                        // say goodbye, the service closes the connection once it has been sent
+                        self.goodbye_peer(peer_id.clone(), GoodbyeReason::Fault);
+                        return Poll::Ready(NBAction::GenerateEvent(
+                            BehaviourEvent::DisconnectPeer(peer_id),
+                        ));
+                    }
+                    PeerManagerEvent::BanPeer(peer_id) => {
+                        self.goodbye_peer(peer_id.clone(), GoodbyeReason::Fault);
+                        return Poll::Ready(NBAction::GenerateEvent(BehaviourEvent::BanPeer(
+                            peer_id,
+                        )));
                     }
-                    PeerManagerEvent::_BanPeer(_peer_id) => {
-                        //TODO: Implement
+                    PeerManagerEvent::UnBanPeer(peer_id) => {
+                        return Poll::Ready(NBAction::GenerateEvent(BehaviourEvent::UnBanPeer(
+                            peer_id,
+                        )));
                     }
                 },
                 Poll::Pending => break,
@@ -914,4 +951,13 @@ pub enum BehaviourEvent<TSpec: EthSpec> {
     PeerSubscribed(PeerId, TopicHash),
     /// Inform the network to send a Status to this peer.
     StatusPeer(PeerId),
+    /// The peer manager has requested that this peer be disconnected. This is handled by the
+    /// libp2p `Service`.
+    DisconnectPeer(PeerId),
+    /// The peer manager has requested that this peer be banned. This is handled by the libp2p
+    /// `Service`.
+    BanPeer(PeerId),
+    /// The peer manager has requested that this peer be unbanned. This is handled by the libp2p
+    /// `Service`.
+    UnBanPeer(PeerId),
 }
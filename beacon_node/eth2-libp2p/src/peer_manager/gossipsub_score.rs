@@ -0,0 +1,359 @@
+//! Gossipsub v1.1-style topic scoring for the eth2 gossipsub topics.
+//!
+//! Each peer keeps a set of counters per topic: the number of messages it was the first to
+//! deliver to us and the number of invalid messages it has sent us. The counters decay
+//! exponentially every `decay_interval` (one slot) and are combined into a single score using
+//! the per-topic parameters in `GossipsubScoreParams`, which are derived from the `ChainSpec`.
+//!
+//! The resulting score forms the gossipsub topic component of a peer's `Score` and therefore
+//! contributes to the `PeerManager`'s decisions to disconnect or ban the peer.
+use crate::types::GossipKind;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use types::{ChainSpec, EthSpec};
+
+/// The weight of the beacon block topic.
+const BEACON_BLOCK_WEIGHT: f64 = 0.5;
+/// The weight of the aggregate and proof topic.
+const BEACON_AGGREGATE_PROOF_WEIGHT: f64 = 0.5;
+/// The combined weight of all attestation subnet topics. It is shared equally between subnets.
+const ATTESTATION_SUBNETS_WEIGHT: f64 = 1.0;
+/// The weight of the voluntary exit topic.
+const VOLUNTARY_EXIT_WEIGHT: f64 = 0.05;
+/// The weight of the proposer slashing topic.
+const PROPOSER_SLASHING_WEIGHT: f64 = 0.05;
+/// The weight of the attester slashing topic.
+const ATTESTER_SLASHING_WEIGHT: f64 = 0.05;
+
+/// The maximum score a peer can obtain by being the first to deliver messages, across all topics.
+const MAX_POSITIVE_SCORE: f64 = 10.0;
+/// The score a peer obtains for its first invalid message on any topic. Subsequent invalid
+/// messages are penalised quadratically.
+const INVALID_MESSAGE_PENALTY: f64 = -5.0;
+/// The number of epochs it takes for the first message delivery counters to decay to zero.
+const FIRST_MESSAGE_DECAY_EPOCHS: u64 = 10;
+/// The number of epochs it takes for the invalid message delivery counters to decay to zero.
+const INVALID_MESSAGE_DECAY_EPOCHS: u64 = 50;
+/// The value below which a counter is considered to have decayed to zero.
+const DECAY_TO_ZERO: f64 = 0.01;
+
+/// The result of validating a gossipsub message, as reported by the application.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MessageAcceptance {
+    /// The message is valid and should be propagated.
+    Accept,
+    /// The message should not be propagated, but the peer that sent it is not at fault (e.g.,
+    /// the message is a duplicate or we are unable to verify it yet).
+    Ignore,
+    /// The message is invalid and the peer that sent it should be penalised.
+    Reject,
+}
+
+/// The scoring parameters of a single topic.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TopicScoreParams {
+    /// The weight of the topic's score in the peer's total gossipsub score.
+    pub topic_weight: f64,
+    /// The score obtained for each message the peer was first to deliver.
+    pub first_message_deliveries_weight: f64,
+    /// The factor the first message delivery counter is multiplied by every decay interval.
+    pub first_message_deliveries_decay: f64,
+    /// The maximum value of the first message delivery counter.
+    pub first_message_deliveries_cap: f64,
+    /// The score obtained for the square of the invalid message delivery counter.
+    pub invalid_message_deliveries_weight: f64,
+    /// The factor the invalid message delivery counter is multiplied by every decay interval.
+    pub invalid_message_deliveries_decay: f64,
+}
+
+impl TopicScoreParams {
+    /// Builds the parameters for a topic of weight `topic_weight` on which we expect to receive
+    /// `expected_messages_per_slot` messages.
+    fn new(
+        topic_weight: f64,
+        expected_messages_per_slot: f64,
+        slots_per_epoch: u64,
+        total_topic_weight: f64,
+    ) -> Self {
+        let first_message_deliveries_decay =
+            decay_factor(FIRST_MESSAGE_DECAY_EPOCHS * slots_per_epoch);
+        // The value the counter settles at if the peer delivers every message first.
+        let first_message_deliveries_cap =
+            expected_messages_per_slot / (1.0 - first_message_deliveries_decay);
+
+        TopicScoreParams {
+            topic_weight,
+            first_message_deliveries_weight: MAX_POSITIVE_SCORE
+                / (total_topic_weight * first_message_deliveries_cap),
+            first_message_deliveries_decay,
+            first_message_deliveries_cap,
+            invalid_message_deliveries_weight: INVALID_MESSAGE_PENALTY / topic_weight,
+            invalid_message_deliveries_decay: decay_factor(
+                INVALID_MESSAGE_DECAY_EPOCHS * slots_per_epoch,
+            ),
+        }
+    }
+}
+
+/// The gossipsub scoring parameters for each of the eth2 topics.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GossipsubScoreParams {
+    /// The interval at which the topic counters are decayed.
+    pub decay_interval: Duration,
+    pub beacon_block: TopicScoreParams,
+    pub beacon_aggregate_and_proof: TopicScoreParams,
+    /// The parameters shared by each attestation subnet topic.
+    pub attestation_subnet: TopicScoreParams,
+    pub voluntary_exit: TopicScoreParams,
+    pub proposer_slashing: TopicScoreParams,
+    pub attester_slashing: TopicScoreParams,
+}
+
+impl GossipsubScoreParams {
+    /// Derives the scoring parameters from the slot duration and committee parameters of `spec`.
+    pub fn new<E: EthSpec>(spec: &ChainSpec) -> Self {
+        let slots_per_epoch = E::slots_per_epoch();
+        let subnet_count = spec.attestation_subnet_count.max(1);
+        let total_topic_weight = BEACON_BLOCK_WEIGHT
+            + BEACON_AGGREGATE_PROOF_WEIGHT
+            + ATTESTATION_SUBNETS_WEIGHT
+            + VOLUNTARY_EXIT_WEIGHT
+            + PROPOSER_SLASHING_WEIGHT
+            + ATTESTER_SLASHING_WEIGHT;
+        let topic = |weight, messages_per_slot| -> TopicScoreParams {
+            TopicScoreParams::new(
+                weight,
+                messages_per_slot,
+                slots_per_epoch,
+                total_topic_weight,
+            )
+        };
+
+        // Operations are rare, we expect at most one of each per epoch.
+        let operations_per_slot = 1.0 / slots_per_epoch as f64;
+        // Every committee has aggregators and each subnet carries the attestations of at most
+        // one committee per slot.
+        let aggregates_per_slot =
+            (spec.target_aggregators_per_committee * spec.max_committees_per_slot as u64) as f64;
+        let attestations_per_subnet_per_slot = spec.target_committee_size as f64;
+
+        GossipsubScoreParams {
+            decay_interval: Duration::from_millis(spec.milliseconds_per_slot),
+            beacon_block: topic(BEACON_BLOCK_WEIGHT, 1.0),
+            beacon_aggregate_and_proof: topic(BEACON_AGGREGATE_PROOF_WEIGHT, aggregates_per_slot),
+            attestation_subnet: topic(
+                ATTESTATION_SUBNETS_WEIGHT / subnet_count as f64,
+                attestations_per_subnet_per_slot,
+            ),
+            voluntary_exit: topic(VOLUNTARY_EXIT_WEIGHT, operations_per_slot),
+            proposer_slashing: topic(PROPOSER_SLASHING_WEIGHT, operations_per_slot),
+            attester_slashing: topic(ATTESTER_SLASHING_WEIGHT, operations_per_slot),
+        }
+    }
+
+    /// Returns the scoring parameters of the topic of `kind`.
+    pub fn topic_params(&self, kind: &GossipKind) -> &TopicScoreParams {
+        match kind {
+            GossipKind::BeaconBlock => &self.beacon_block,
+            GossipKind::BeaconAggregateAndProof => &self.beacon_aggregate_and_proof,
+            GossipKind::CommitteeIndex(_) => &self.attestation_subnet,
+            GossipKind::VoluntaryExit => &self.voluntary_exit,
+            GossipKind::ProposerSlashing => &self.proposer_slashing,
+            GossipKind::AttesterSlashing => &self.attester_slashing,
+        }
+    }
+}
+
+/// The counters kept for a single topic of a peer.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct TopicStats {
+    /// The number of messages the peer was the first to deliver.
+    first_message_deliveries: f64,
+    /// The number of invalid messages the peer delivered.
+    invalid_message_deliveries: f64,
+}
+
+impl TopicStats {
+    /// The score of the topic, before being weighted by the topic's weight.
+    fn score(&self, params: &TopicScoreParams) -> f64 {
+        let first_message_deliveries = self
+            .first_message_deliveries
+            .min(params.first_message_deliveries_cap);
+        params.first_message_deliveries_weight * first_message_deliveries
+            + params.invalid_message_deliveries_weight * self.invalid_message_deliveries.powi(2)
+    }
+}
+
+/// The gossipsub topic counters of a peer.
+#[derive(Debug, Clone)]
+pub struct GossipsubStats {
+    /// The counters for each topic the peer has delivered messages on.
+    topics: HashMap<GossipKind, TopicStats>,
+    /// The last time the counters were decayed.
+    last_decayed: Instant,
+}
+
+impl Default for GossipsubStats {
+    fn default() -> Self {
+        GossipsubStats {
+            topics: HashMap::new(),
+            last_decayed: Instant::now(),
+        }
+    }
+}
+
+impl GossipsubStats {
+    /// Records the result of validating a message the peer was the first to deliver on the topic
+    /// of `kind`.
+    pub fn on_validation_result(
+        &mut self,
+        kind: &GossipKind,
+        acceptance: MessageAcceptance,
+        params: &GossipsubScoreParams,
+    ) {
+        let stats = self.topics.entry(kind.clone()).or_default();
+        match acceptance {
+            MessageAcceptance::Accept => {
+                let cap = params.topic_params(kind).first_message_deliveries_cap;
+                stats.first_message_deliveries = (stats.first_message_deliveries + 1.0).min(cap);
+            }
+            MessageAcceptance::Ignore => {}
+            MessageAcceptance::Reject => stats.invalid_message_deliveries += 1.0,
+        }
+    }
+
+    /// The weighted sum of the scores of all topics.
+    pub fn score(&self, params: &GossipsubScoreParams) -> f64 {
+        self.topics
+            .iter()
+            .map(|(kind, stats)| {
+                let topic_params = params.topic_params(kind);
+                topic_params.topic_weight * stats.score(topic_params)
+            })
+            .sum()
+    }
+
+    /// Decays the counters according to the number of decay intervals since they were last
+    /// decayed.
+    pub fn decay(&mut self, params: &GossipsubScoreParams) {
+        self.decay_at(params, Instant::now())
+    }
+
+    /// Decays the counters as if the current time were `now`.
+    fn decay_at(&mut self, params: &GossipsubScoreParams, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.last_decayed);
+        let intervals = elapsed.as_secs_f64() / params.decay_interval.as_secs_f64().max(1e-3);
+        self.last_decayed = now;
+
+        self.topics.retain(|kind, stats| {
+            let topic_params = params.topic_params(kind);
+            stats.first_message_deliveries *=
+                topic_params.first_message_deliveries_decay.powf(intervals);
+            stats.invalid_message_deliveries *= topic_params
+                .invalid_message_deliveries_decay
+                .powf(intervals);
+            // forget the topic once both counters have decayed away
+            stats.first_message_deliveries >= DECAY_TO_ZERO
+                || stats.invalid_message_deliveries >= DECAY_TO_ZERO
+        });
+    }
+}
+
+/// The factor a counter must be multiplied by every interval to decay to `DECAY_TO_ZERO` after
+/// `intervals` intervals.
+fn decay_factor(intervals: u64) -> f64 {
+    DECAY_TO_ZERO.powf(1.0 / intervals.max(1) as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::{MainnetEthSpec, SubnetId};
+
+    type E = MainnetEthSpec;
+
+    fn params() -> GossipsubScoreParams {
+        GossipsubScoreParams::new::<E>(&E::default_spec())
+    }
+
+    #[test]
+    fn test_params_from_spec() {
+        let spec = E::default_spec();
+        let params = params();
+
+        assert_eq!(
+            params.decay_interval,
+            Duration::from_millis(spec.milliseconds_per_slot)
+        );
+        assert_eq!(
+            params.attestation_subnet.topic_weight,
+            ATTESTATION_SUBNETS_WEIGHT / spec.attestation_subnet_count as f64
+        );
+        assert_eq!(
+            params.topic_params(&GossipKind::CommitteeIndex(SubnetId::new(3))),
+            &params.attestation_subnet
+        );
+    }
+
+    #[test]
+    fn test_max_positive_score() {
+        let params = params();
+        let mut stats = GossipsubStats::default();
+        let mut kinds = vec![
+            GossipKind::BeaconBlock,
+            GossipKind::BeaconAggregateAndProof,
+            GossipKind::VoluntaryExit,
+            GossipKind::ProposerSlashing,
+            GossipKind::AttesterSlashing,
+        ];
+        let spec = E::default_spec();
+        kinds.extend(
+            (0..spec.attestation_subnet_count)
+                .map(|i| GossipKind::CommitteeIndex(SubnetId::new(i))),
+        );
+
+        for kind in &kinds {
+            let cap = params
+                .topic_params(kind)
+                .first_message_deliveries_cap
+                .ceil() as usize;
+            for _ in 0..=cap {
+                stats.on_validation_result(kind, MessageAcceptance::Accept, &params);
+            }
+        }
+        assert!((stats.score(&params) - MAX_POSITIVE_SCORE).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_invalid_messages_are_penalised_quadratically() {
+        let params = params();
+        let mut stats = GossipsubStats::default();
+        let kind = GossipKind::CommitteeIndex(SubnetId::new(0));
+
+        stats.on_validation_result(&kind, MessageAcceptance::Ignore, &params);
+        assert_eq!(stats.score(&params), 0.0);
+
+        stats.on_validation_result(&kind, MessageAcceptance::Reject, &params);
+        assert!((stats.score(&params) - INVALID_MESSAGE_PENALTY).abs() < 1e-9);
+
+        stats.on_validation_result(&kind, MessageAcceptance::Reject, &params);
+        assert!((stats.score(&params) - 4.0 * INVALID_MESSAGE_PENALTY).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_counters_decay() {
+        let params = params();
+        let mut stats = GossipsubStats::default();
+        stats.on_validation_result(&GossipKind::BeaconBlock, MessageAcceptance::Reject, &params);
+
+        let slots = INVALID_MESSAGE_DECAY_EPOCHS * E::slots_per_epoch();
+        let now = stats.last_decayed + params.decay_interval * (slots as u32 / 2);
+        stats.decay_at(&params, now);
+        assert!(stats.score(&params) < 0.0);
+        assert!(stats.score(&params) > INVALID_MESSAGE_PENALTY);
+
+        stats.decay_at(&params, now + params.decay_interval * (slots as u32));
+        assert!(stats.topics.is_empty());
+        assert_eq!(stats.score(&params), 0.0);
+    }
+}
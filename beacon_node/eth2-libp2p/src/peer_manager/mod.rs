@@ -3,37 +3,41 @@
 pub use self::peerdb::*;
 use crate::metrics;
 use crate::rpc::{MetaData, Protocol, RPCError, RPCResponseErrorCode};
+use crate::types::GossipKind;
 use crate::{NetworkGlobals, PeerId};
 use futures::prelude::*;
 use futures::Stream;
 use hashset_delay::HashSetDelay;
 use libp2p::identify::IdentifyInfo;
-use slog::{crit, debug, error, warn};
+use slog::{crit, debug, error, info, warn};
 use smallvec::SmallVec;
-use std::convert::TryInto;
+use std::net::IpAddr;
 use std::pin::Pin;
 use std::sync::Arc;
 use std::task::{Context, Poll};
-use std::time::{Duration, Instant};
+use std::time::Duration;
 use types::EthSpec;
 
 pub mod client;
+mod gossipsub_score;
 mod peer_info;
 mod peer_sync_status;
 mod peerdb;
+mod score;
 
+pub use gossipsub_score::{GossipsubScoreParams, MessageAcceptance, TopicScoreParams};
 pub use peer_info::{PeerConnectionStatus::*, PeerInfo};
 pub use peer_sync_status::{PeerSyncStatus, SyncInfo};
-/// The minimum reputation before a peer is disconnected.
-// Most likely this needs tweaking.
-const _MIN_REP_BEFORE_BAN: Rep = 10;
+pub use score::{ReportSource, Score, ScoreState};
+/// The time in seconds between decaying the scores of all known peers.
+const HEARTBEAT_INTERVAL: u64 = 30;
 /// The time in seconds between re-status's peers.
 const STATUS_INTERVAL: u64 = 300;
 /// The time in seconds between PING events. We do not send a ping if the other peer as PING'd us within
 /// this time frame (Seconds)
 const PING_INTERVAL: u64 = 30;
 
-/// The main struct that handles peer's reputation and connection status.
+/// The main struct that handles peer's score and connection status.
 pub struct PeerManager<TSpec: EthSpec> {
     /// Storage of network globals to access the `PeerDB`.
     network_globals: Arc<NetworkGlobals<TSpec>>,
@@ -43,16 +47,19 @@ pub struct PeerManager<TSpec: EthSpec> {
     ping_peers: HashSetDelay<PeerId>,
     /// A collection of peers awaiting to be Status'd.
     status_peers: HashSetDelay<PeerId>,
-    /// Last updated moment.
-    _last_updated: Instant,
+    /// The heartbeat interval at which peer scores are decayed and bans expire.
+    heartbeat: tokio::time::Interval,
+    /// The parameters used to score peers on the gossipsub topics.
+    gossipsub_params: GossipsubScoreParams,
     /// The logger associated with the `PeerManager`.
     log: slog::Logger,
 }
 
-/// A collection of actions a peer can perform which will adjust its reputation.
-/// Each variant has an associated reputation change.
-// To easily assess the behaviour of reputation changes the number of variants should stay low, and
+/// A collection of actions a peer can perform which will adjust its score.
+/// Each variant has an associated score change.
+// To easily assess the behaviour of score changes the number of variants should stay low, and
 // somewhat generic.
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum PeerAction {
     /// We should not communicate more with this peer.
     /// This action will cause the peer to get banned.
@@ -75,18 +82,6 @@ pub enum PeerAction {
     _ValidMessage,
 }
 
-impl PeerAction {
-    fn rep_change(&self) -> RepChange {
-        match self {
-            PeerAction::Fatal => RepChange::worst(),
-            PeerAction::LowToleranceError => RepChange::bad(60),
-            PeerAction::MidToleranceError => RepChange::bad(25),
-            PeerAction::HighToleranceError => RepChange::bad(15),
-            PeerAction::_ValidMessage => RepChange::good(20),
-        }
-    }
-}
-
 /// The events that the `PeerManager` outputs (requests).
 pub enum PeerManagerEvent {
     /// Sends a STATUS to a peer.
@@ -96,17 +91,24 @@ pub enum PeerManagerEvent {
     /// Request METADATA from a peer.
     MetaData(PeerId),
     /// The peer should be disconnected.
-    _DisconnectPeer(PeerId),
+    DisconnectPeer(PeerId),
     /// The peer should be disconnected and banned.
-    _BanPeer(PeerId),
+    BanPeer(PeerId),
+    /// The peer's ban has expired and it should be unbanned.
+    UnBanPeer(PeerId),
 }
 
 impl<TSpec: EthSpec> PeerManager<TSpec> {
-    pub fn new(network_globals: Arc<NetworkGlobals<TSpec>>, log: &slog::Logger) -> Self {
+    pub fn new(
+        network_globals: Arc<NetworkGlobals<TSpec>>,
+        gossipsub_params: GossipsubScoreParams,
+        log: &slog::Logger,
+    ) -> Self {
         PeerManager {
             network_globals,
             events: SmallVec::new(),
-            _last_updated: Instant::now(),
+            heartbeat: tokio::time::interval(Duration::from_secs(HEARTBEAT_INTERVAL)),
+            gossipsub_params,
             ping_peers: HashSetDelay::new(Duration::from_secs(PING_INTERVAL)),
             status_peers: HashSetDelay::new(Duration::from_secs(STATUS_INTERVAL)),
             log: log.clone(),
@@ -204,7 +206,6 @@ impl<TSpec: EthSpec> PeerManager<TSpec> {
 
     /// Updates the state of the peer as disconnected.
     pub fn notify_disconnect(&mut self, peer_id: &PeerId) {
-        //self.update_reputations();
         self.network_globals.peers.write().disconnect(peer_id);
 
         // remove the ping and status timer for the peer
@@ -217,13 +218,13 @@ impl<TSpec: EthSpec> PeerManager<TSpec> {
         );
     }
 
-    /// Sets a peer as connected as long as their reputation allows it
+    /// Sets a peer as connected as long as their score allows it
     /// Informs if the peer was accepted
     pub fn connect_ingoing(&mut self, peer_id: &PeerId) -> bool {
         self.connect_peer(peer_id, ConnectingType::IngoingConnected)
     }
 
-    /// Sets a peer as connected as long as their reputation allows it
+    /// Sets a peer as connected as long as their score allows it
     /// Informs if the peer was accepted
     pub fn connect_outgoing(&mut self, peer_id: &PeerId) -> bool {
         self.connect_peer(peer_id, ConnectingType::OutgoingConnected)
@@ -234,17 +235,91 @@ impl<TSpec: EthSpec> PeerManager<TSpec> {
         self.connect_peer(peer_id, ConnectingType::Dialing)
     }
 
-    /// Reports a peer for some action.
+    /// Reports a peer for some action, adjusting the component of its score given by `source`.
     ///
-    /// If the peer doesn't exist, log a warning and insert defaults.
-    pub fn report_peer(&mut self, peer_id: &PeerId, action: PeerAction) {
-        //TODO: Check these. There are double disconnects for example
-        // self.update_reputations();
-        self.network_globals
-            .peers
-            .write()
-            .add_reputation(peer_id, action.rep_change());
-        // self.update_reputations();
+    /// If the score drops low enough, the peer is disconnected or banned immediately. If the peer
+    /// doesn't exist, log a warning and make no change.
+    pub fn report_peer(&mut self, peer_id: &PeerId, action: PeerAction, source: ReportSource) {
+        let network_globals = self.network_globals.clone();
+        let mut peerdb = network_globals.peers.write();
+        let transition = match peerdb.peer_info_mut(peer_id) {
+            Some(info) => {
+                info.score.apply_peer_action(action, source);
+                debug!(self.log, "Peer score adjusted"; "peer_id" => peer_id.to_string(),
+                    "action" => format!("{:?}", action), "source" => format!("{:?}", source),
+                    "score" => info.score.score());
+                PeerDB::score_transition(info)
+            }
+            None => {
+                warn!(self.log, "Peer is unknown, no score change made";
+                    "peer_id" => peer_id.to_string());
+                return;
+            }
+        };
+
+        if let Some(state) = transition {
+            self.handle_score_transition(&mut peerdb, peer_id.clone(), state);
+        }
+    }
+
+    /// Records the result of validating a gossipsub message the peer was the first to deliver to
+    /// us on the topic of `kind`.
+    ///
+    /// Rejected messages update the peer's score immediately, so peers sending invalid messages
+    /// are disconnected or banned without waiting for the next heartbeat.
+    pub fn validation_result(
+        &mut self,
+        peer_id: &PeerId,
+        kind: &GossipKind,
+        acceptance: MessageAcceptance,
+    ) {
+        let network_globals = self.network_globals.clone();
+        let mut peerdb = network_globals.peers.write();
+        let transition = match peerdb.peer_info_mut(peer_id) {
+            Some(info) => {
+                info.gossipsub_stats
+                    .on_validation_result(kind, acceptance, &self.gossipsub_params);
+                if acceptance != MessageAcceptance::Reject {
+                    return;
+                }
+                info.score
+                    .set_gossipsub_score(info.gossipsub_stats.score(&self.gossipsub_params));
+                debug!(self.log, "Peer delivered an invalid gossipsub message";
+                    "peer_id" => peer_id.to_string(), "topic" => kind.to_string(),
+                    "score" => info.score.score());
+                PeerDB::score_transition(info)
+            }
+            None => {
+                debug!(self.log, "Gossipsub message from an unknown peer";
+                    "peer_id" => peer_id.to_string());
+                return;
+            }
+        };
+
+        if let Some(state) = transition {
+            self.handle_score_transition(&mut peerdb, peer_id.clone(), state);
+        }
+    }
+
+    /// Bans a peer that was banned before the node was restarted, restoring the IP addresses it
+    /// was seen on. The ban expires in the same way as any other ban.
+    pub fn restore_banned_peer(&mut self, peer_id: &PeerId, ips: Vec<IpAddr>) {
+        let mut peerdb = self.network_globals.peers.write();
+        for ip in ips {
+            peerdb.add_seen_ip(peer_id, ip);
+        }
+        peerdb.ban(peer_id);
+        if let Some(info) = peerdb.peer_info_mut(peer_id) {
+            info.score = Score::banned();
+        }
+    }
+
+    /// Records the IP address a peer has connected from or has been dialed on. Returns true if
+    /// the IP address is banned, in which case the peer should be disconnected.
+    pub fn seen_ip(&mut self, peer_id: &PeerId, ip: IpAddr) -> bool {
+        let mut peerdb = self.network_globals.peers.write();
+        peerdb.add_seen_ip(peer_id, ip);
+        peerdb.is_ip_banned(&ip)
     }
 
     /// Updates `PeerInfo` with `identify` information.
@@ -313,7 +388,7 @@ impl<TSpec: EthSpec> PeerManager<TSpec> {
             RPCError::NegotiationTimeout => PeerAction::HighToleranceError,
         };
 
-        self.report_peer(peer_id, peer_action);
+        self.report_peer(peer_id, peer_action, ReportSource::RPC);
     }
 
     /* Internal functions */
@@ -326,9 +401,6 @@ impl<TSpec: EthSpec> PeerManager<TSpec> {
     /// This informs if the peer was accepted in to the db or not.
     // TODO: Drop peers if over max_peer limit
     fn connect_peer(&mut self, peer_id: &PeerId, connection: ConnectingType) -> bool {
-        // TODO: remove after timed updates
-        //self.update_reputations();
-
         {
             let mut peerdb = self.network_globals.peers.write();
             if peerdb.connection_status(peer_id).map(|c| c.is_banned()) == Some(true) {
@@ -364,94 +436,47 @@ impl<TSpec: EthSpec> PeerManager<TSpec> {
         self.network_globals.peers.write().dialing_peer(peer_id);
     }
 
-    /// Updates the reputation of known peers according to their connection
-    /// status and the time that has passed.
+    /// Decays the scores and gossipsub topic counters of all known peers and disconnects, bans or
+    /// unbans the peers whose score has crossed a threshold.
     ///
-    /// **Disconnected peers** get a 1rep hit every hour they stay disconnected.
-    /// **Banned peers** get a 1rep gain for every hour to slowly allow them back again.
-    ///
-    /// A banned(disconnected) peer that gets its rep above(below) MIN_REP_BEFORE_BAN is
-    /// now considered a disconnected(banned) peer.
-    fn _update_reputations(&mut self) {
-        // avoid locking the peerdb too often
-        // TODO: call this on a timer
-        if self._last_updated.elapsed().as_secs() < 30 {
-            return;
+    /// This is called on every heartbeat.
+    fn update_peer_scores(&mut self) {
+        let network_globals = self.network_globals.clone();
+        let mut peerdb = network_globals.peers.write();
+        for (peer_id, state) in peerdb.update_scores(&self.gossipsub_params) {
+            self.handle_score_transition(&mut peerdb, peer_id, state);
         }
+    }
 
-        let now = Instant::now();
-
-        // Check for peers that get banned, unbanned and that should be disconnected
-        let mut ban_queue = Vec::new();
-        let mut unban_queue = Vec::new();
-
-        /* Check how long have peers been in this state and update their reputations if needed */
-        let mut pdb = self.network_globals.peers.write();
-
-        for (id, info) in pdb._peers_mut() {
-            // Update reputations
-            match info.connection_status {
-                Connected { .. } => {
-                    // Connected peers gain reputation by sending useful messages
-                }
-                Disconnected { since } | Banned { since } => {
-                    // For disconnected peers, lower their reputation by 1 for every hour they
-                    // stay disconnected. This helps us slowly forget disconnected peers.
-                    // In the same way, slowly allow banned peers back again.
-                    let dc_hours = now
-                        .checked_duration_since(since)
-                        .unwrap_or_else(|| Duration::from_secs(0))
-                        .as_secs()
-                        / 3600;
-                    let last_dc_hours = self
-                        ._last_updated
-                        .checked_duration_since(since)
-                        .unwrap_or_else(|| Duration::from_secs(0))
-                        .as_secs()
-                        / 3600;
-                    if dc_hours > last_dc_hours {
-                        // this should be 1 most of the time
-                        let rep_dif = (dc_hours - last_dc_hours)
-                            .try_into()
-                            .unwrap_or(Rep::max_value());
-
-                        info.reputation = if info.connection_status.is_banned() {
-                            info.reputation.saturating_add(rep_dif)
-                        } else {
-                            info.reputation.saturating_sub(rep_dif)
-                        };
-                    }
-                }
-                Dialing { since } => {
-                    // A peer shouldn't be dialing for more than 2 minutes
-                    if since.elapsed().as_secs() > 120 {
-                        warn!(self.log,"Peer has been dialing for too long"; "peer_id" => id.to_string());
-                        // TODO: decide how to handle this
-                    }
-                }
-                Unknown => {} //TODO: Handle this case
+    /// Updates the `PeerDB` and queues the events required for a peer whose score now implies
+    /// `state`, as returned by `PeerDB::score_transition`.
+    fn handle_score_transition(
+        &mut self,
+        peerdb: &mut PeerDB<TSpec>,
+        peer_id: PeerId,
+        state: ScoreState,
+    ) {
+        match state {
+            ScoreState::Banned => {
+                info!(self.log, "Banning peer"; "peer_id" => peer_id.to_string(),
+                    "score" => peerdb.score(&peer_id));
+                peerdb.ban(&peer_id);
+                self.ping_peers.remove(&peer_id);
+                self.status_peers.remove(&peer_id);
+                self.events.push(PeerManagerEvent::BanPeer(peer_id));
             }
-            // Check if the peer gets banned or unbanned and if it should be disconnected
-            if info.reputation < _MIN_REP_BEFORE_BAN && !info.connection_status.is_banned() {
-                // This peer gets banned. Check if we should request disconnection
-                ban_queue.push(id.clone());
-            } else if info.reputation >= _MIN_REP_BEFORE_BAN && info.connection_status.is_banned() {
-                // This peer gets unbanned
-                unban_queue.push(id.clone());
+            ScoreState::Disconnected => {
+                debug!(self.log, "Disconnecting peer with low score";
+                    "peer_id" => peer_id.to_string(), "score" => peerdb.score(&peer_id));
+                self.events.push(PeerManagerEvent::DisconnectPeer(peer_id));
+            }
+            ScoreState::Healthy => {
+                debug!(self.log, "Peer ban has expired"; "peer_id" => peer_id.to_string(),
+                    "score" => peerdb.score(&peer_id));
+                peerdb.unban(&peer_id);
+                self.events.push(PeerManagerEvent::UnBanPeer(peer_id));
             }
         }
-
-        for id in ban_queue {
-            pdb.ban(&id);
-
-            self.events.push(PeerManagerEvent::_BanPeer(id.clone()));
-        }
-
-        for id in unban_queue {
-            pdb.disconnect(&id);
-        }
-
-        self._last_updated = Instant::now();
     }
 }
 
@@ -459,6 +484,11 @@ impl<TSpec: EthSpec> Stream for PeerManager<TSpec> {
     type Item = PeerManagerEvent;
 
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        // decay the peer scores on every heartbeat
+        while let Poll::Ready(_) = self.heartbeat.poll_tick(cx) {
+            self.update_peer_scores();
+        }
+
         // poll the timeouts for pings and status'
         loop {
             match self.ping_peers.poll_next_unpin(cx) {
@@ -1,5 +1,6 @@
 use super::client::Client;
-use super::peerdb::{Rep, DEFAULT_REPUTATION};
+use super::gossipsub_score::GossipsubStats;
+use super::score::Score;
 use super::PeerSyncStatus;
 use crate::rpc::MetaData;
 use crate::Multiaddr;
@@ -7,6 +8,8 @@ use serde::{
     ser::{SerializeStructVariant, Serializer},
     Serialize,
 };
+use std::collections::HashSet;
+use std::net::IpAddr;
 use std::time::Instant;
 use types::{EthSpec, SubnetId};
 use PeerConnectionStatus::*;
@@ -17,14 +20,21 @@ use PeerConnectionStatus::*;
 pub struct PeerInfo<T: EthSpec> {
     /// The connection status of the peer
     _status: PeerStatus,
-    /// The peers reputation
-    pub reputation: Rep,
+    /// The peer's score, which determines if the peer should be disconnected or banned.
+    pub score: Score,
+    /// The peer's gossipsub topic counters, from which the gossipsub component of its score is
+    /// computed.
+    #[serde(skip)]
+    pub gossipsub_stats: GossipsubStats,
     /// Client managing this peer
     pub client: Client,
     /// Connection status of this peer
     pub connection_status: PeerConnectionStatus,
     /// The known listening addresses of this peer.
     pub listening_addresses: Vec<Multiaddr>,
+    /// The IP addresses we have seen this peer connect from or have dialed it on.
+    #[serde(skip)]
+    pub seen_ips: HashSet<IpAddr>,
     /// The current syncing state of the peer. The state may be determined after it's initial
     /// connection.
     pub sync_status: PeerSyncStatus,
@@ -41,10 +51,12 @@ impl<TSpec: EthSpec> Default for PeerInfo<TSpec> {
     fn default() -> PeerInfo<TSpec> {
         PeerInfo {
             _status: Default::default(),
-            reputation: DEFAULT_REPUTATION,
+            score: Score::default(),
+            gossipsub_stats: GossipsubStats::default(),
             client: Client::default(),
             connection_status: Default::default(),
             listening_addresses: vec![],
+            seen_ips: HashSet::new(),
             sync_status: PeerSyncStatus::Unknown,
             meta_data: None,
             min_ttl: None,
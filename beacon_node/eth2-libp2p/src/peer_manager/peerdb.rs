@@ -1,30 +1,25 @@
+use super::gossipsub_score::GossipsubScoreParams;
 use super::peer_info::{PeerConnectionStatus, PeerInfo};
 use super::peer_sync_status::PeerSyncStatus;
+use super::score::{Score, ScoreState};
 use crate::rpc::methods::MetaData;
 use crate::PeerId;
 use slog::{crit, debug, trace, warn};
-use std::collections::{hash_map::Entry, HashMap};
+use std::collections::{HashMap, HashSet};
+use std::net::IpAddr;
 use std::time::Instant;
 use types::{EthSpec, SubnetId};
 
-/// A peer's reputation (perceived potential usefulness)
-pub type Rep = u8;
-
-/// Reputation change (positive or negative)
-pub struct RepChange {
-    is_good: bool,
-    diff: Rep,
-}
-
 /// Max number of disconnected nodes to remember
 const MAX_DC_PEERS: usize = 30;
 
-/// The default starting reputation for an unknown peer.
-pub const DEFAULT_REPUTATION: Rep = 50;
+/// The number of banned peers that must share an IP address before the IP address itself is
+/// considered banned.
+const BANNED_PEERS_PER_IP_THRESHOLD: usize = 5;
 
-/// Storage of known peers, their reputation and information
+/// Storage of known peers, their score and information
 pub struct PeerDB<TSpec: EthSpec> {
-    /// The collection of known connected peers, their status and reputation
+    /// The collection of known connected peers, their status and score
     peers: HashMap<PeerId, PeerInfo<TSpec>>,
     /// Tracking of number of disconnected nodes
     n_dc: usize,
@@ -32,27 +27,6 @@ pub struct PeerDB<TSpec: EthSpec> {
     log: slog::Logger,
 }
 
-impl RepChange {
-    pub fn good(diff: Rep) -> Self {
-        RepChange {
-            is_good: true,
-            diff,
-        }
-    }
-    pub fn bad(diff: Rep) -> Self {
-        RepChange {
-            is_good: false,
-            diff,
-        }
-    }
-    pub const fn worst() -> Self {
-        RepChange {
-            is_good: false,
-            diff: Rep::max_value(),
-        }
-    }
-}
-
 impl<TSpec: EthSpec> PeerDB<TSpec> {
     pub fn new(log: &slog::Logger) -> Self {
         Self {
@@ -64,11 +38,11 @@ impl<TSpec: EthSpec> PeerDB<TSpec> {
 
     /* Getters */
 
-    /// Gives the reputation of a peer, or DEFAULT_REPUTATION if it is unknown.
-    pub fn reputation(&self, peer_id: &PeerId) -> Rep {
+    /// Gives the score of a peer, or the default score if it is unknown.
+    pub fn score(&self, peer_id: &PeerId) -> f64 {
         self.peers
             .get(peer_id)
-            .map_or(DEFAULT_REPUTATION, |info| info.reputation)
+            .map_or_else(|| Score::default().score(), |info| info.score.score())
     }
 
     /// Returns an iterator over all peers in the db.
@@ -170,8 +144,26 @@ impl<TSpec: EthSpec> PeerDB<TSpec> {
             .map(|(peer_id, _)| peer_id)
     }
 
+    /// Gives the ids of all known banned peers along with the IP addresses they were seen on.
+    pub fn banned_peers_with_ips(&self) -> impl Iterator<Item = (&PeerId, &HashSet<IpAddr>)> {
+        self.peers
+            .iter()
+            .filter(|(_, info)| info.connection_status.is_banned())
+            .map(|(peer_id, info)| (peer_id, &info.seen_ips))
+    }
+
+    /// Returns true if enough banned peers have been seen on `ip` for the IP address itself to be
+    /// considered banned.
+    pub fn is_ip_banned(&self, ip: &IpAddr) -> bool {
+        self.peers
+            .values()
+            .filter(|info| info.connection_status.is_banned() && info.seen_ips.contains(ip))
+            .count()
+            >= BANNED_PEERS_PER_IP_THRESHOLD
+    }
+
     /// Returns a vector containing peers (their ids and info), sorted by
-    /// reputation from highest to lowest, and filtered using `is_status`
+    /// score from highest to lowest, and filtered using `is_status`
     pub fn best_peers_by_status<F>(&self, is_status: F) -> Vec<(&PeerId, &PeerInfo<TSpec>)>
     where
         F: Fn(&PeerConnectionStatus) -> bool,
@@ -181,11 +173,16 @@ impl<TSpec: EthSpec> PeerDB<TSpec> {
             .iter()
             .filter(|(_, info)| is_status(&info.connection_status))
             .collect::<Vec<_>>();
-        by_status.sort_by_key(|(_, info)| Rep::max_value() - info.reputation);
+        by_status.sort_by(|(_, a), (_, b)| {
+            b.score
+                .score()
+                .partial_cmp(&a.score.score())
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
         by_status
     }
 
-    /// Returns the peer with highest reputation that satisfies `is_status`
+    /// Returns the peer with highest score that satisfies `is_status`
     pub fn best_by_status<F>(&self, is_status: F) -> Option<&PeerId>
     where
         F: Fn(&PeerConnectionStatus) -> bool,
@@ -193,7 +190,12 @@ impl<TSpec: EthSpec> PeerDB<TSpec> {
         self.peers
             .iter()
             .filter(|(_, info)| is_status(&info.connection_status))
-            .max_by_key(|(_, info)| info.reputation)
+            .max_by(|(_, a), (_, b)| {
+                a.score
+                    .score()
+                    .partial_cmp(&b.score.score())
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
             .map(|(id, _)| id)
     }
 
@@ -306,7 +308,7 @@ impl<TSpec: EthSpec> PeerDB<TSpec> {
         self.shrink_to_fit();
     }
 
-    /// Drops the peers with the lowest reputation so that the number of
+    /// Drops the peers with the lowest score so that the number of
     /// disconnected peers is less than MAX_DC_PEERS
     pub fn shrink_to_fit(&mut self) {
         // for caution, but the difference should never be > 1
@@ -315,7 +317,12 @@ impl<TSpec: EthSpec> PeerDB<TSpec> {
                 .peers
                 .iter()
                 .filter(|(_, info)| info.connection_status.is_disconnected())
-                .min_by_key(|(_, info)| info.reputation)
+                .min_by(|(_, a), (_, b)| {
+                    a.score
+                        .score()
+                        .partial_cmp(&b.score.score())
+                        .unwrap_or(std::cmp::Ordering::Equal)
+                })
                 .map(|(id, _)| id.clone())
                 .unwrap(); // should be safe since n_dc > MAX_DC_PEERS > 0
             self.peers.remove(&to_drop);
@@ -337,6 +344,30 @@ impl<TSpec: EthSpec> PeerDB<TSpec> {
         info.connection_status.ban();
     }
 
+    /// Unbans a peer, leaving it as disconnected.
+    pub fn unban(&mut self, peer_id: &PeerId) {
+        let log_ref = &self.log;
+        let info = self.peers.entry(peer_id.clone()).or_insert_with(|| {
+            warn!(log_ref, "Unbanning unknown peer";
+                    "peer_id" => peer_id.to_string());
+            PeerInfo::default()
+        });
+        if info.connection_status.is_banned() {
+            info.connection_status.disconnect();
+            self.n_dc += 1;
+        }
+        self.shrink_to_fit();
+    }
+
+    /// Records an IP address the peer has been seen on.
+    pub fn add_seen_ip(&mut self, peer_id: &PeerId, ip: IpAddr) {
+        self.peers
+            .entry(peer_id.clone())
+            .or_default()
+            .seen_ips
+            .insert(ip);
+    }
+
     /// Add the meta data of a peer.
     pub fn add_metadata(&mut self, peer_id: &PeerId, meta_data: MetaData<TSpec>) {
         if let Some(peer_info) = self.peers.get_mut(peer_id) {
@@ -346,13 +377,13 @@ impl<TSpec: EthSpec> PeerDB<TSpec> {
         }
     }
 
-    /// Sets the reputation of peer.
+    /// Sets the score of peer.
     #[allow(dead_code)]
-    pub(super) fn set_reputation(&mut self, peer_id: &PeerId, rep: Rep) {
+    pub(super) fn set_score(&mut self, peer_id: &PeerId, score: Score) {
         if let Some(peer_info) = self.peers.get_mut(peer_id) {
-            peer_info.reputation = rep;
+            peer_info.score = score;
         } else {
-            crit!(self.log, "Tried to modify reputation for an unknown peer"; "peer_id" => peer_id.to_string());
+            crit!(self.log, "Tried to modify score for an unknown peer"; "peer_id" => peer_id.to_string());
         }
     }
 
@@ -365,24 +396,39 @@ impl<TSpec: EthSpec> PeerDB<TSpec> {
         }
     }
 
-    /// Adds to a peer's reputation by `change`. If the reputation exceeds Rep's
-    /// upper (lower) bounds, it stays at the maximum (minimum) value.
-    pub(super) fn add_reputation(&mut self, peer_id: &PeerId, change: RepChange) {
-        let log_ref = &self.log;
-        let info = match self.peers.entry(peer_id.clone()) {
-            Entry::Vacant(_) => {
-                warn!(log_ref, "Peer is unknown, no reputation change made";
-                        "peer_id" => peer_id.to_string());
-                return;
-            }
-            Entry::Occupied(e) => e.into_mut(),
-        };
+    /// Decays the score and gossipsub topic counters of every known peer and returns the peers
+    /// whose connection status no longer matches their score, along with the state implied by
+    /// their score.
+    pub(super) fn update_scores(
+        &mut self,
+        gossipsub_params: &GossipsubScoreParams,
+    ) -> Vec<(PeerId, ScoreState)> {
+        self.peers
+            .iter_mut()
+            .filter_map(|(peer_id, info)| {
+                info.score.update();
+                info.gossipsub_stats.decay(gossipsub_params);
+                info.score
+                    .set_gossipsub_score(info.gossipsub_stats.score(gossipsub_params));
+                Self::score_transition(info).map(|state| (peer_id.clone(), state))
+            })
+            .collect()
+    }
 
-        info.reputation = if change.is_good {
-            info.reputation.saturating_add(change.diff)
-        } else {
-            info.reputation.saturating_sub(change.diff)
-        };
+    /// Returns the state implied by the peer's score if it does not match the peer's
+    /// connection status.
+    pub(super) fn score_transition(info: &PeerInfo<TSpec>) -> Option<ScoreState> {
+        let status = &info.connection_status;
+        match info.score.state() {
+            ScoreState::Banned if !status.is_banned() => Some(ScoreState::Banned),
+            ScoreState::Disconnected if status.is_connected() || status.is_dialing() => {
+                Some(ScoreState::Disconnected)
+            }
+            ScoreState::Disconnected | ScoreState::Healthy if status.is_banned() => {
+                Some(ScoreState::Healthy)
+            }
+            _ => None,
+        }
     }
 }
 
@@ -428,8 +474,8 @@ mod tests {
         assert!(peer_info.is_some());
         // this is the only peer
         assert_eq!(pdb.peers().count(), 1);
-        // the peer has the default reputation
-        assert_eq!(pdb.reputation(&random_peer), DEFAULT_REPUTATION);
+        // the peer has the default score
+        assert_eq!(pdb.score(&random_peer), Score::default().score());
         // it should be connected, and therefore not counted as disconnected
         assert_eq!(pdb.n_dc, 0);
         assert!(peer_info.unwrap().connection_status.is_connected());
@@ -440,47 +486,78 @@ mod tests {
     }
 
     #[test]
-    fn test_set_reputation() {
+    fn test_set_score() {
         let mut pdb = get_db();
         let random_peer = PeerId::random();
         pdb.connect_ingoing(&random_peer);
 
-        let mut rep = Rep::min_value();
-        pdb.set_reputation(&random_peer, rep);
-        assert_eq!(pdb.reputation(&random_peer), rep);
+        let mut score = -100.0;
+        pdb.set_score(&random_peer, Score::from(score));
+        assert_eq!(pdb.score(&random_peer), score);
 
-        rep = Rep::max_value();
-        pdb.set_reputation(&random_peer, rep);
-        assert_eq!(pdb.reputation(&random_peer), rep);
+        score = 100.0;
+        pdb.set_score(&random_peer, Score::from(score));
+        assert_eq!(pdb.score(&random_peer), score);
 
-        rep = Rep::max_value() / 100;
-        pdb.set_reputation(&random_peer, rep);
-        assert_eq!(pdb.reputation(&random_peer), rep);
+        // scores are capped
+        pdb.set_score(&random_peer, Score::from(1000.0));
+        assert_eq!(pdb.score(&random_peer), score);
     }
 
     #[test]
-    fn test_reputation_change() {
+    fn test_score_transitions() {
         let mut pdb = get_db();
 
-        // 0 change does not change de reputation
-        let random_peer = PeerId::random();
-        let change = RepChange::good(0);
-        pdb.connect_ingoing(&random_peer);
-        pdb.add_reputation(&random_peer, change);
-        assert_eq!(pdb.reputation(&random_peer), DEFAULT_REPUTATION);
+        let healthy = PeerId::random();
+        let to_disconnect = PeerId::random();
+        let to_ban = PeerId::random();
+        let to_unban = PeerId::random();
+        for peer in &[&healthy, &to_disconnect, &to_ban] {
+            pdb.connect_ingoing(peer);
+        }
+        pdb.ban(&to_unban);
 
-        // overflowing change is capped
-        let random_peer = PeerId::random();
-        let change = RepChange::worst();
-        pdb.connect_ingoing(&random_peer);
-        pdb.add_reputation(&random_peer, change);
-        assert_eq!(pdb.reputation(&random_peer), Rep::min_value());
+        pdb.set_score(&healthy, Score::from(-10.0));
+        pdb.set_score(&to_disconnect, Score::from(-30.0));
+        pdb.set_score(&to_ban, Score::from(-60.0));
+        pdb.set_score(&to_unban, Score::from(-30.0));
 
-        let random_peer = PeerId::random();
-        let change = RepChange::good(Rep::max_value());
-        pdb.connect_ingoing(&random_peer);
-        pdb.add_reputation(&random_peer, change);
-        assert_eq!(pdb.reputation(&random_peer), Rep::max_value());
+        let params = GossipsubScoreParams::new::<M>(&M::default_spec());
+        let mut transitions = pdb.update_scores(&params);
+        transitions.sort_by_key(|(_, state)| state.to_string());
+        assert_eq!(
+            transitions,
+            vec![
+                (to_ban, ScoreState::Banned),
+                (to_disconnect, ScoreState::Disconnected),
+                (to_unban.clone(), ScoreState::Healthy),
+            ]
+        );
+
+        pdb.unban(&to_unban);
+        assert!(pdb
+            .connection_status(&to_unban)
+            .map_or(false, |status| status.is_disconnected()));
+        assert_eq!(pdb.n_dc, pdb.disconnected_peers().count());
+    }
+
+    #[test]
+    fn test_ip_ban_threshold() {
+        let mut pdb = get_db();
+        let ip: IpAddr = "1.2.3.4".parse().unwrap();
+
+        for _ in 0..BANNED_PEERS_PER_IP_THRESHOLD {
+            assert!(!pdb.is_ip_banned(&ip));
+            let peer = PeerId::random();
+            pdb.connect_ingoing(&peer);
+            pdb.add_seen_ip(&peer, ip);
+            pdb.ban(&peer);
+        }
+        assert!(pdb.is_ip_banned(&ip));
+
+        let peer = pdb.banned_peers().next().cloned().unwrap();
+        pdb.unban(&peer);
+        assert!(!pdb.is_ip_banned(&ip));
     }
 
     #[test]
@@ -510,9 +587,9 @@ mod tests {
         pdb.connect_ingoing(&p0);
         pdb.connect_ingoing(&p1);
         pdb.connect_ingoing(&p2);
-        pdb.set_reputation(&p0, 70);
-        pdb.set_reputation(&p1, 100);
-        pdb.set_reputation(&p2, 50);
+        pdb.set_score(&p0, Score::from(70.0));
+        pdb.set_score(&p1, Score::from(100.0));
+        pdb.set_score(&p2, Score::from(50.0));
 
         let best_peers = pdb.best_peers_by_status(PeerConnectionStatus::is_connected);
         assert!(vec![&p1, &p0, &p2]
@@ -530,9 +607,9 @@ mod tests {
         pdb.connect_ingoing(&p0);
         pdb.connect_ingoing(&p1);
         pdb.connect_ingoing(&p2);
-        pdb.set_reputation(&p0, 70);
-        pdb.set_reputation(&p1, 100);
-        pdb.set_reputation(&p2, 50);
+        pdb.set_score(&p0, Score::from(70.0));
+        pdb.set_score(&p1, Score::from(100.0));
+        pdb.set_score(&p2, Score::from(50.0));
 
         let the_best = pdb.best_by_status(PeerConnectionStatus::is_connected);
         assert!(the_best.is_some());
@@ -0,0 +1,309 @@
+//! This contains the scoring logic for peers.
+//!
+//! A peer's score is a rational number in the range [-100, 100]. It is the sum of three
+//! components which are tracked separately: one for the peer's behaviour on the RPC, one for
+//! actions reported against the peer on gossipsub and one computed by the gossipsub topic scoring
+//! (see `gossipsub_score`).
+//!
+//! The first two components decay exponentially towards zero over time, with a half-life of
+//! `SCORE_HALFLIFE` seconds. A peer that falls below `MIN_SCORE_BEFORE_DISCONNECT` is
+//! disconnected and a peer that falls below `MIN_SCORE_BEFORE_BAN` is banned. Banned peers do not
+//! decay for `BANNED_BEFORE_DECAY`, after which their score recovers and the ban expires once the
+//! score rises above `MIN_SCORE_BEFORE_BAN`.
+use super::PeerAction;
+use serde::{
+    ser::{SerializeStruct, Serializer},
+    Serialize,
+};
+use std::time::{Duration, Instant};
+
+/// The default score for new peers.
+pub(crate) const DEFAULT_SCORE: f64 = 0.0;
+/// The minimum score before a peer is disconnected.
+const MIN_SCORE_BEFORE_DISCONNECT: f64 = -20.0;
+/// The minimum score before a peer is banned.
+const MIN_SCORE_BEFORE_BAN: f64 = -50.0;
+/// The maximum score a peer can obtain.
+const MAX_SCORE: f64 = 100.0;
+/// The minimum score a peer can obtain.
+const MIN_SCORE: f64 = -100.0;
+/// The time (in seconds) it takes for a score to halve.
+const SCORE_HALFLIFE: f64 = 600.0;
+/// The time a peer remains banned before its score starts to decay.
+const BANNED_BEFORE_DECAY: Duration = Duration::from_secs(1800);
+
+/// The source of a `PeerAction`, which determines which component of the score it affects.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReportSource {
+    /// The action was observed on the RPC.
+    RPC,
+    /// The action was observed on gossipsub (e.g., an invalid gossip message).
+    Gossipsub,
+}
+
+/// The state of a peer, as determined by its score.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub enum ScoreState {
+    /// The peer is performing within the tolerable range.
+    Healthy,
+    /// The score is low enough that the peer should be disconnected, but not banned.
+    Disconnected,
+    /// The score is low enough that the peer should be banned.
+    Banned,
+}
+
+impl std::fmt::Display for ScoreState {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ScoreState::Healthy => write!(f, "Healthy"),
+            ScoreState::Disconnected => write!(f, "Disconnected"),
+            ScoreState::Banned => write!(f, "Banned"),
+        }
+    }
+}
+
+/// A peer's score, split into its RPC and gossipsub components.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Score {
+    /// The component of the score obtained from RPC behaviour.
+    rpc: f64,
+    /// The component of the score obtained from gossipsub behaviour.
+    gossip: f64,
+    /// The component of the score computed by the gossipsub topic scoring. This decays
+    /// separately, along with the topic counters it is computed from.
+    gossipsub: f64,
+    /// The last time the score was decayed. This is set into the future when a peer is banned,
+    /// which prevents the score from decaying until `BANNED_BEFORE_DECAY` has passed.
+    last_updated: Instant,
+}
+
+impl Default for Score {
+    fn default() -> Self {
+        Score {
+            rpc: DEFAULT_SCORE,
+            gossip: DEFAULT_SCORE,
+            gossipsub: DEFAULT_SCORE,
+            last_updated: Instant::now(),
+        }
+    }
+}
+
+/// Builds a score with the given RPC component.
+impl From<f64> for Score {
+    fn from(rpc: f64) -> Self {
+        let mut score = Score::default();
+        score.rpc = clamp(rpc);
+        score
+    }
+}
+
+/// Serialization for http requests.
+impl Serialize for Score {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("Score", 5)?;
+        s.serialize_field("score", &self.score())?;
+        s.serialize_field("rpc", &self.rpc)?;
+        s.serialize_field("gossip", &self.gossip)?;
+        s.serialize_field("gossipsub", &self.gossipsub)?;
+        s.serialize_field("state", &self.state())?;
+        s.end()
+    }
+}
+
+impl PeerAction {
+    /// The change in score this action causes.
+    fn score_change(&self) -> f64 {
+        match self {
+            PeerAction::Fatal => MIN_SCORE,
+            PeerAction::LowToleranceError => -10.0,
+            PeerAction::MidToleranceError => -5.0,
+            PeerAction::HighToleranceError => -3.5,
+            PeerAction::_ValidMessage => 1.0,
+        }
+    }
+}
+
+impl Score {
+    /// Returns a score for a peer that is banned, such as one that was banned before a restart.
+    pub fn banned() -> Self {
+        let mut score = Score::from(MIN_SCORE);
+        score.last_updated += BANNED_BEFORE_DECAY;
+        score
+    }
+
+    /// The total score of the peer.
+    pub fn score(&self) -> f64 {
+        clamp(self.rpc + self.gossip + self.gossipsub)
+    }
+
+    /// The component of the score obtained from RPC behaviour.
+    pub fn rpc_score(&self) -> f64 {
+        self.rpc
+    }
+
+    /// The component of the score obtained from gossipsub behaviour.
+    pub fn gossip_score(&self) -> f64 {
+        self.gossip
+    }
+
+    /// The component of the score computed by the gossipsub topic scoring.
+    pub fn gossipsub_score(&self) -> f64 {
+        self.gossipsub
+    }
+
+    /// Returns the state of the peer implied by its score.
+    pub fn state(&self) -> ScoreState {
+        let score = self.score();
+        if score <= MIN_SCORE_BEFORE_BAN {
+            ScoreState::Banned
+        } else if score <= MIN_SCORE_BEFORE_DISCONNECT {
+            ScoreState::Disconnected
+        } else {
+            ScoreState::Healthy
+        }
+    }
+
+    /// Applies the change in score of `action` to the component of the score given by `source`.
+    pub fn apply_peer_action(&mut self, action: PeerAction, source: ReportSource) {
+        let previous_state = self.state();
+
+        let (component, other) = match source {
+            ReportSource::RPC => (&mut self.rpc, &mut self.gossip),
+            ReportSource::Gossipsub => (&mut self.gossip, &mut self.rpc),
+        };
+        match action {
+            PeerAction::Fatal => {
+                // A fatal action bans the peer regardless of how well it behaved elsewhere.
+                *component = MIN_SCORE;
+                *other = other.min(DEFAULT_SCORE);
+            }
+            action => *component = clamp(*component + action.score_change()),
+        }
+
+        self.on_state_change(previous_state);
+    }
+
+    /// Sets the component of the score computed by the gossipsub topic scoring.
+    pub fn set_gossipsub_score(&mut self, gossipsub: f64) {
+        let previous_state = self.state();
+        self.gossipsub = clamp(gossipsub);
+        self.on_state_change(previous_state);
+    }
+
+    /// Prevents the score from decaying for `BANNED_BEFORE_DECAY` if the peer has just become
+    /// banned.
+    fn on_state_change(&mut self, previous_state: ScoreState) {
+        if self.state() == ScoreState::Banned && previous_state != ScoreState::Banned {
+            self.last_updated = Instant::now() + BANNED_BEFORE_DECAY;
+        }
+    }
+
+    /// Decays the score according to the time that has passed since it was last updated.
+    pub fn update(&mut self) {
+        self.update_at(Instant::now())
+    }
+
+    /// Decays the score as if the current time were `now`.
+    fn update_at(&mut self, now: Instant) {
+        if let Some(elapsed) = now.checked_duration_since(self.last_updated) {
+            let decay_factor =
+                (-std::f64::consts::LN_2 * elapsed.as_secs_f64() / SCORE_HALFLIFE).exp();
+            self.rpc *= decay_factor;
+            self.gossip *= decay_factor;
+            self.last_updated = now;
+        }
+    }
+}
+
+/// Restricts `score` to the range [`MIN_SCORE`, `MAX_SCORE`].
+fn clamp(score: f64) -> f64 {
+    score.max(MIN_SCORE).min(MAX_SCORE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_score_is_bounded() {
+        let mut score = Score::default();
+        for _ in 0..200 {
+            score.apply_peer_action(PeerAction::_ValidMessage, ReportSource::RPC);
+        }
+        assert_eq!(score.score(), MAX_SCORE);
+
+        for _ in 0..200 {
+            score.apply_peer_action(PeerAction::LowToleranceError, ReportSource::RPC);
+        }
+        assert_eq!(score.score(), MIN_SCORE);
+    }
+
+    #[test]
+    fn test_components_are_separate() {
+        let mut score = Score::default();
+        score.apply_peer_action(PeerAction::LowToleranceError, ReportSource::Gossipsub);
+        score.apply_peer_action(PeerAction::MidToleranceError, ReportSource::RPC);
+
+        assert_eq!(score.gossip_score(), -10.0);
+        assert_eq!(score.rpc_score(), -5.0);
+        assert_eq!(score.score(), -15.0);
+        assert_eq!(score.state(), ScoreState::Healthy);
+    }
+
+    #[test]
+    fn test_fatal_bans_well_behaved_peer() {
+        let mut score = Score::default();
+        for _ in 0..200 {
+            score.apply_peer_action(PeerAction::_ValidMessage, ReportSource::RPC);
+        }
+        score.apply_peer_action(PeerAction::Fatal, ReportSource::Gossipsub);
+
+        assert_eq!(score.score(), MIN_SCORE);
+        assert_eq!(score.state(), ScoreState::Banned);
+    }
+
+    #[test]
+    fn test_gossipsub_score_can_ban() {
+        let mut score = Score::default();
+        score.set_gossipsub_score(-60.0);
+        assert_eq!(score.gossipsub_score(), -60.0);
+        assert_eq!(score.state(), ScoreState::Banned);
+
+        // the gossipsub component is not decayed with the others
+        let now = Instant::now() + BANNED_BEFORE_DECAY * 2;
+        score.update_at(now);
+        assert_eq!(score.score(), -60.0);
+
+        score.set_gossipsub_score(0.0);
+        assert_eq!(score.state(), ScoreState::Healthy);
+    }
+
+    #[test]
+    fn test_score_decay() {
+        let mut score = Score::from(-40.0);
+        assert_eq!(score.state(), ScoreState::Disconnected);
+
+        let now = score.last_updated + Duration::from_secs(SCORE_HALFLIFE as u64);
+        score.update_at(now);
+        assert!((score.score() - -20.0).abs() < 1e-9);
+
+        // decaying again at the same instant changes nothing
+        score.update_at(now);
+        assert!((score.score() - -20.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_banned_peers_do_not_decay_until_ban_is_served() {
+        let mut score = Score::default();
+        score.apply_peer_action(PeerAction::Fatal, ReportSource::RPC);
+        assert_eq!(score.state(), ScoreState::Banned);
+
+        score.update_at(Instant::now() + BANNED_BEFORE_DECAY / 2);
+        assert_eq!(score.score(), MIN_SCORE);
+
+        // after the ban is served, the score recovers until the peer is no longer banned
+        let unban_time = score.last_updated + Duration::from_secs(SCORE_HALFLIFE as u64 + 1);
+        score.update_at(unban_time);
+        assert_ne!(score.state(), ScoreState::Banned);
+    }
+}
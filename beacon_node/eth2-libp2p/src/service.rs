@@ -23,11 +23,12 @@ use slog::{crit, debug, info, o, trace, warn};
 use std::fs::File;
 use std::io::prelude::*;
 use std::io::{Error, ErrorKind};
+use std::net::IpAddr;
 use std::pin::Pin;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::time::DelayQueue;
-use types::{EnrForkId, EthSpec};
+use types::{ChainSpec, EnrForkId, EthSpec};
 
 pub const NETWORK_KEY_FILENAME: &str = "key";
 /// The time in milliseconds to wait before banning a peer. This allows for any Goodbye messages to be
@@ -88,6 +89,7 @@ impl<TSpec: EthSpec> Service<TSpec> {
         executor: environment::TaskExecutor,
         config: &NetworkConfig,
         enr_fork_id: EnrForkId,
+        chain_spec: &ChainSpec,
         log: &slog::Logger,
     ) -> error::Result<(Arc<NetworkGlobals<TSpec>>, Self)> {
         let log = log.new(o!("service"=> "libp2p"));
@@ -121,7 +123,13 @@ impl<TSpec: EthSpec> Service<TSpec> {
             let transport = build_transport(local_keypair.clone())
                 .map_err(|e| format!("Failed to build transport: {:?}", e))?;
             // Lighthouse network behaviour
-            let behaviour = Behaviour::new(&local_keypair, config, network_globals.clone(), &log)?;
+            let behaviour = Behaviour::new(
+                &local_keypair,
+                config,
+                network_globals.clone(),
+                chain_spec,
+                &log,
+            )?;
 
             // use the executor for libp2p
             struct Executor(environment::TaskExecutor);
@@ -230,6 +238,41 @@ impl<TSpec: EthSpec> Service<TSpec> {
         self.peer_ban_timeout.insert(peer_id, timeout);
     }
 
+    /// Bans a peer that was banned before the node was restarted, along with the IP addresses
+    /// it was seen on. The ban expires with the peer's score.
+    pub fn restore_banned_peer(&mut self, peer_id: PeerId, ips: Vec<IpAddr>) {
+        self.swarm.peer_manager().restore_banned_peer(&peer_id, ips);
+        Swarm::ban_peer_id(&mut self.swarm, peer_id.clone());
+        self.swarm.peer_banned(peer_id);
+    }
+
+    /// Disconnects a peer without a lasting ban. The peer is banned at the swarm level, which
+    /// closes its connections, and is unbanned shortly afterwards.
+    fn disconnect_peer(&mut self, peer_id: PeerId) {
+        debug!(self.log, "Disconnecting peer"; "peer_id" => peer_id.to_string());
+        self.peers_to_ban.insert(
+            peer_id.clone(),
+            Duration::from_millis(BAN_PEER_WAIT_TIMEOUT),
+        );
+        self.peer_ban_timeout
+            .insert(peer_id, Duration::from_millis(2 * BAN_PEER_WAIT_TIMEOUT));
+    }
+
+    /// Bans a peer whose score has dropped below the ban threshold. The ban is lifted by the
+    /// peer manager once the peer's score has recovered.
+    fn ban_peer(&mut self, peer_id: PeerId) {
+        warn!(self.log, "Disconnecting and banning peer"; "peer_id" => peer_id.to_string());
+        self.peers_to_ban
+            .insert(peer_id, Duration::from_millis(BAN_PEER_WAIT_TIMEOUT));
+    }
+
+    /// Lifts the ban of a peer whose score has recovered.
+    fn unban_peer(&mut self, peer_id: PeerId) {
+        debug!(self.log, "Peer has been unbanned"; "peer" => peer_id.to_string());
+        self.swarm.peer_unbanned(&peer_id);
+        Swarm::unban_peer_id(&mut self.swarm, peer_id);
+    }
+
     /// Sends a request to a peer, with a given Id.
     pub fn send_request(&mut self, peer_id: PeerId, request_id: RequestId, request: Request) {
         self.swarm.send_request(peer_id, request_id, request);
@@ -263,6 +306,17 @@ impl<TSpec: EthSpec> Service<TSpec> {
             tokio::select! {
                 event = self.swarm.next_event() => {
                     match event {
+                        // requests from the peer manager are handled here, as they require
+                        // access to the swarm
+                        SwarmEvent::Behaviour(BehaviourEvent::DisconnectPeer(peer_id)) => {
+                            self.disconnect_peer(peer_id);
+                        }
+                        SwarmEvent::Behaviour(BehaviourEvent::BanPeer(peer_id)) => {
+                            self.ban_peer(peer_id);
+                        }
+                        SwarmEvent::Behaviour(BehaviourEvent::UnBanPeer(peer_id)) => {
+                            self.unban_peer(peer_id);
+                        }
                         SwarmEvent::Behaviour(behaviour) => {
                             return Libp2pEvent::Behaviour(behaviour)
                         }
@@ -272,6 +326,13 @@ impl<TSpec: EthSpec> Service<TSpec> {
                             num_established,
                         } => {
                             debug!(self.log, "Connection established"; "peer_id" => peer_id.to_string(), "connections" => num_established.get());
+                            // refuse peers connecting from, or dialed on, a banned IP address
+                            if let Some(ip) = multiaddr_to_ip(endpoint.get_remote_address()) {
+                                if self.swarm.peer_manager().seen_ip(&peer_id, ip) {
+                                    debug!(self.log, "Peer is on a banned IP address"; "peer_id" => peer_id.to_string(), "ip" => ip.to_string());
+                                    self.disconnect_peer(peer_id.clone());
+                                }
+                            }
                             // if this is the first connection inform the network layer a new connection
                             // has been established and update the db
                             if num_established.get() == 1 {
@@ -366,16 +427,32 @@ impl<TSpec: EthSpec> Service<TSpec> {
                     self.swarm.peer_banned(peer_id);
                 }
                 Some(Ok(peer_to_unban)) = self.peer_ban_timeout.next() => {
-                    debug!(self.log, "Peer has been unbanned"; "peer" => format!("{:?}", peer_to_unban));
                     let unban_peer = peer_to_unban.into_inner();
-                    self.swarm.peer_unbanned(&unban_peer);
-                    Swarm::unban_peer_id(&mut self.swarm, unban_peer);
+                    // peers banned by the peer manager remain banned until their score recovers
+                    let banned_by_score = self
+                        .network_globals
+                        .peers
+                        .read()
+                        .connection_status(&unban_peer)
+                        .map_or(false, |status| status.is_banned());
+                    if !banned_by_score {
+                        self.unban_peer(unban_peer);
+                    }
                 }
             }
         }
     }
 }
 
+/// Returns the IP address of a `Multiaddr`, if it has one.
+fn multiaddr_to_ip(addr: &Multiaddr) -> Option<IpAddr> {
+    addr.iter().find_map(|protocol| match protocol {
+        Protocol::Ip4(ip) => Some(ip.into()),
+        Protocol::Ip6(ip) => Some(ip.into()),
+        _ => None,
+    })
+}
+
 /// The implementation supports TCP/IP, WebSockets over TCP/IP, noise/secio as the encryption
 /// layer, and mplex or yamux as the multiplexing layer.
 fn build_transport(
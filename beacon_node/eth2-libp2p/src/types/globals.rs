@@ -109,6 +109,21 @@ impl<TSpec: EthSpec> NetworkGlobals<TSpec> {
             .unwrap_or_default()
     }
 
+    /// Returns the sync state of the node when it is not performing a range sync, which depends
+    /// only on whether any synced peers are connected.
+    ///
+    /// A synced node that is back-filling historical blocks continues to report its progress.
+    pub fn peer_sync_state(&self) -> SyncState {
+        if self.peers.read().synced_peers().next().is_none() {
+            return SyncState::Stalled;
+        }
+
+        match &*self.sync_state.read() {
+            backfill @ SyncState::BackFillSyncing { .. } => backfill.clone(),
+            _ => SyncState::Synced,
+        }
+    }
+
     /// Updates the syncing state of the node.
     ///
     /// If there is a new state, the old state and the new states are returned.
@@ -116,13 +131,7 @@ impl<TSpec: EthSpec> NetworkGlobals<TSpec> {
         let mut result = None;
         // if we are in a range sync, nothing changes. Range sync will update this.
         if !self.is_syncing() {
-            let new_state = self
-                .peers
-                .read()
-                .synced_peers()
-                .next()
-                .map(|_| SyncState::Synced)
-                .unwrap_or_else(|| SyncState::Stalled);
+            let new_state = self.peer_sync_state();
 
             let mut peer_state = self.sync_state.write();
             if new_state != *peer_state {
@@ -14,6 +14,9 @@ pub enum SyncState {
     /// The node is performing a long-range (batch) sync over one or many head chains.
     /// In this state parent lookups are disabled.
     SyncingHead { start_slot: Slot, head_slot: Slot },
+    /// The node is synced but is still downloading the historical blocks that precede the
+    /// checkpoint it was started from. The `completed` and `remaining` fields are in slots.
+    BackFillSyncing { completed: usize, remaining: usize },
     /// The node is up to date with all known peers and is connected to at least one
     /// fully synced peer. In this state, parent lookups are enabled.
     Synced,
@@ -27,6 +30,7 @@ impl PartialEq for SyncState {
         match (self, other) {
             (SyncState::SyncingFinalized { .. }, SyncState::SyncingFinalized { .. }) => true,
             (SyncState::SyncingHead { .. }, SyncState::SyncingHead { .. }) => true,
+            (SyncState::BackFillSyncing { .. }, SyncState::BackFillSyncing { .. }) => true,
             (SyncState::Synced, SyncState::Synced) => true,
             (SyncState::Stalled, SyncState::Stalled) => true,
             _ => false,
@@ -40,15 +44,18 @@ impl SyncState {
         match self {
             SyncState::SyncingFinalized { .. } => true,
             SyncState::SyncingHead { .. } => true,
+            SyncState::BackFillSyncing { .. } => false,
             SyncState::Synced => false,
             SyncState::Stalled => false,
         }
     }
 
     /// Returns true if the node is synced.
+    ///
+    /// A node which is back-filling historical blocks is synced with respect to its head.
     pub fn is_synced(&self) -> bool {
         match self {
-            SyncState::Synced => true,
+            SyncState::Synced | SyncState::BackFillSyncing { .. } => true,
             _ => false,
         }
     }
@@ -59,6 +66,7 @@ impl std::fmt::Display for SyncState {
         match self {
             SyncState::SyncingFinalized { .. } => write!(f, "Syncing Finalized Chain"),
             SyncState::SyncingHead { .. } => write!(f, "Syncing Head Chain"),
+            SyncState::BackFillSyncing { .. } => write!(f, "Syncing Historical Blocks"),
             SyncState::Synced { .. } => write!(f, "Synced"),
             SyncState::Stalled { .. } => write!(f, "Stalled"),
         }
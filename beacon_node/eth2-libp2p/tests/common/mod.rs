@@ -7,7 +7,7 @@ use eth2_libp2p::{Libp2pEvent, NetworkConfig};
 use slog::{debug, error, o, Drain};
 use std::net::{TcpListener, UdpSocket};
 use std::time::Duration;
-use types::{EnrForkId, MinimalEthSpec};
+use types::{EnrForkId, EthSpec, MinimalEthSpec};
 
 type E = MinimalEthSpec;
 use tempdir::TempDir;
@@ -103,12 +103,23 @@ pub fn build_libp2p_instance(
     // launch libp2p service
 
     let (signal, exit) = exit_future::signal();
-    let executor =
-        environment::TaskExecutor::new(tokio::runtime::Handle::current(), exit, log.clone());
+    let (shutdown_tx, _) = futures::channel::mpsc::channel(1);
+    let executor = environment::TaskExecutor::new(
+        tokio::runtime::Handle::current(),
+        exit,
+        log.clone(),
+        shutdown_tx,
+    );
     Libp2pInstance(
-        LibP2PService::new(executor, &config, EnrForkId::default(), &log)
-            .expect("should build libp2p instance")
-            .1,
+        LibP2PService::new(
+            executor,
+            &config,
+            EnrForkId::default(),
+            &E::default_spec(),
+            &log,
+        )
+        .expect("should build libp2p instance")
+        .1,
         signal,
     )
 }
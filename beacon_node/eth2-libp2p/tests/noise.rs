@@ -1,7 +1,7 @@
 #![cfg(test)]
 use crate::behaviour::Behaviour;
 use crate::multiaddr::Protocol;
-use ::types::{EnrForkId, MinimalEthSpec};
+use ::types::{EnrForkId, EthSpec, MinimalEthSpec};
 use eth2_libp2p::discovery::{build_enr, CombinedKey, CombinedKeyExt};
 use eth2_libp2p::*;
 use futures::prelude::*;
@@ -46,7 +46,13 @@ fn build_secio_swarm(
         // Set up the transport - tcp/ws with secio and mplex/yamux
         let transport = build_secio_transport(local_keypair.clone());
         // Lighthouse network behaviour
-        let behaviour = Behaviour::new(&local_keypair, config, network_globals.clone(), &log)?;
+        let behaviour = Behaviour::new(
+            &local_keypair,
+            config,
+            network_globals.clone(),
+            &TSpec::default_spec(),
+            &log,
+        )?;
         // requires a tokio runtime
         struct Executor(tokio::runtime::Handle);
         impl libp2p::core::Executor for Executor {
@@ -137,11 +143,22 @@ async fn test_secio_noise_fallback() {
     let port = common::unused_port("tcp").unwrap();
     let noisy_config = common::build_config(port, vec![], None);
     let (_signal, exit) = exit_future::signal();
-    let executor =
-        environment::TaskExecutor::new(tokio::runtime::Handle::current(), exit, log.clone());
-    let mut noisy_node = Service::new(executor, &noisy_config, EnrForkId::default(), &log)
-        .expect("should build a libp2p instance")
-        .1;
+    let (shutdown_tx, _) = futures::channel::mpsc::channel(1);
+    let executor = environment::TaskExecutor::new(
+        tokio::runtime::Handle::current(),
+        exit,
+        log.clone(),
+        shutdown_tx,
+    );
+    let mut noisy_node = Service::new(
+        executor,
+        &noisy_config,
+        EnrForkId::default(),
+        &TSpec::default_spec(),
+        &log,
+    )
+    .expect("should build a libp2p instance")
+    .1;
 
     let port = common::unused_port("tcp").unwrap();
     let secio_config = common::build_config(port, vec![common::get_enr(&noisy_node)], None);
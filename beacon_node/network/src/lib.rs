@@ -7,6 +7,7 @@ pub mod service;
 
 mod attestation_service;
 mod metrics;
+mod persisted_banned_peers;
 mod persisted_dht;
 mod router;
 mod sync;
@@ -0,0 +1,133 @@
+use eth2_libp2p::PeerId;
+use rlp::{DecoderError, Rlp, RlpStream};
+use std::net::IpAddr;
+use std::sync::Arc;
+use store::{DBColumn, Error as StoreError, Store, StoreItem};
+use types::{EthSpec, Hash256};
+
+/// 32-byte key for accessing the `BannedPeers`.
+pub const BANNED_PEERS_DB_KEY: &str = "PERSISTEDBANNEDPEERSPERSISTEDBAN";
+
+/// Loads the peers that were banned when the node last shut down, along with the IP addresses
+/// they were seen on.
+pub fn load_banned_peers<T: Store<E>, E: EthSpec>(store: Arc<T>) -> Vec<(PeerId, Vec<IpAddr>)> {
+    let key = Hash256::from_slice(&BANNED_PEERS_DB_KEY.as_bytes());
+    match store.get_item(&key) {
+        Ok(Some(p)) => {
+            let p: PersistedBannedPeers = p;
+            p.peers
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Attempt to persist the banned peers to `self.store`.
+pub fn persist_banned_peers<T: Store<E>, E: EthSpec>(
+    store: Arc<T>,
+    peers: Vec<(PeerId, Vec<IpAddr>)>,
+) -> Result<(), store::Error> {
+    let key = Hash256::from_slice(&BANNED_PEERS_DB_KEY.as_bytes());
+    store.put_item(&key, &PersistedBannedPeers { peers })?;
+    Ok(())
+}
+
+/// Wrapper around the banned peers for persistence to disk.
+pub struct PersistedBannedPeers {
+    pub peers: Vec<(PeerId, Vec<IpAddr>)>,
+}
+
+impl StoreItem for PersistedBannedPeers {
+    fn db_column() -> DBColumn {
+        DBColumn::BannedPeers
+    }
+
+    fn as_store_bytes(&self) -> Vec<u8> {
+        let mut stream = RlpStream::new_list(self.peers.len());
+        for (peer_id, ips) in &self.peers {
+            stream.begin_list(2);
+            stream.append(&peer_id.as_bytes().to_vec());
+            stream.begin_list(ips.len());
+            for ip in ips {
+                stream.append(&ip_to_bytes(ip));
+            }
+        }
+        stream.out()
+    }
+
+    fn from_store_bytes(bytes: &[u8]) -> Result<Self, StoreError> {
+        let decode = |item: Rlp| -> Result<(PeerId, Vec<IpAddr>), DecoderError> {
+            let peer_id = PeerId::from_bytes(item.at(0)?.as_val()?)
+                .map_err(|_| DecoderError::Custom("Invalid peer id"))?;
+            let ips = item
+                .at(1)?
+                .as_list::<Vec<u8>>()?
+                .into_iter()
+                .map(|bytes| ip_from_bytes(&bytes))
+                .collect::<Result<_, _>>()?;
+            Ok((peer_id, ips))
+        };
+
+        let peers = Rlp::new(bytes)
+            .iter()
+            .map(decode)
+            .collect::<Result<_, _>>()
+            .map_err(|e| StoreError::RlpError(format!("{}", e)))?;
+        Ok(PersistedBannedPeers { peers })
+    }
+}
+
+/// Returns the octets of an IP address.
+fn ip_to_bytes(ip: &IpAddr) -> Vec<u8> {
+    match ip {
+        IpAddr::V4(ip) => ip.octets().to_vec(),
+        IpAddr::V6(ip) => ip.octets().to_vec(),
+    }
+}
+
+/// Builds an IP address from its octets.
+fn ip_from_bytes(bytes: &[u8]) -> Result<IpAddr, DecoderError> {
+    match bytes.len() {
+        4 => {
+            let mut octets = [0; 4];
+            octets.copy_from_slice(bytes);
+            Ok(IpAddr::from(octets))
+        }
+        16 => {
+            let mut octets = [0; 16];
+            octets.copy_from_slice(bytes);
+            Ok(IpAddr::from(octets))
+        }
+        _ => Err(DecoderError::Custom("Invalid IP address length")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use store::{MemoryStore, Store};
+    use types::Hash256;
+    use types::MinimalEthSpec;
+    #[test]
+    fn test_persisted_banned_peers() {
+        let store = Arc::new(MemoryStore::<MinimalEthSpec>::open());
+        let peers = vec![
+            (PeerId::random(), vec![]),
+            (
+                PeerId::random(),
+                vec!["1.2.3.4".parse().unwrap(), "::1".parse().unwrap()],
+            ),
+        ];
+        let key = Hash256::from_slice(&BANNED_PEERS_DB_KEY.as_bytes());
+        store
+            .put_item(
+                &key,
+                &PersistedBannedPeers {
+                    peers: peers.clone(),
+                },
+            )
+            .unwrap();
+        let banned: PersistedBannedPeers = store.get_item(&key).unwrap().unwrap();
+        assert_eq!(banned.peers, peers);
+    }
+}
@@ -11,7 +11,8 @@ use crate::service::NetworkMessage;
 use beacon_chain::{BeaconChain, BeaconChainTypes, BlockError};
 use eth2_libp2p::{
     rpc::{RPCError, RequestId, SubstreamId},
-    MessageId, NetworkGlobals, PeerId, PubsubMessage, Request, Response,
+    types::GossipKind,
+    MessageAcceptance, MessageId, NetworkGlobals, PeerId, PubsubMessage, Request, Response,
 };
 use futures::prelude::*;
 use processor::Processor;
@@ -215,80 +216,182 @@ impl<T: BeaconChainTypes> Router<T> {
         peer_id: PeerId,
         gossip_message: PubsubMessage<T::EthSpec>,
     ) {
-        match gossip_message {
+        let kind = gossip_message.kind();
+        let acceptance = match gossip_message {
             // Attestations should never reach the router.
             PubsubMessage::AggregateAndProofAttestation(aggregate_and_proof) => {
-                if let Some(gossip_verified) =
-                    self.processor.verify_aggregated_attestation_for_gossip(
-                        peer_id.clone(),
-                        *aggregate_and_proof.clone(),
-                    )
+                match self
+                    .processor
+                    .verify_aggregated_attestation_for_gossip(peer_id.clone(), *aggregate_and_proof)
                 {
-                    self.propagate_message(id, peer_id.clone());
-                    self.processor
-                        .import_aggregated_attestation(peer_id, gossip_verified);
+                    Ok(gossip_verified) => {
+                        self.propagate_validation_result(
+                            id,
+                            peer_id.clone(),
+                            kind,
+                            MessageAcceptance::Accept,
+                        );
+                        self.processor
+                            .import_aggregated_attestation(peer_id, gossip_verified);
+                        return;
+                    }
+                    Err(acceptance) => acceptance,
                 }
             }
             PubsubMessage::Attestation(subnet_attestation) => {
-                if let Some(gossip_verified) =
-                    self.processor.verify_unaggregated_attestation_for_gossip(
-                        peer_id.clone(),
-                        subnet_attestation.1.clone(),
-                    )
-                {
-                    self.propagate_message(id, peer_id.clone());
-                    self.processor
-                        .import_unaggregated_attestation(peer_id, gossip_verified);
+                match self.processor.verify_unaggregated_attestation_for_gossip(
+                    peer_id.clone(),
+                    subnet_attestation.1,
+                ) {
+                    Ok(gossip_verified) => {
+                        self.propagate_validation_result(
+                            id,
+                            peer_id.clone(),
+                            kind,
+                            MessageAcceptance::Accept,
+                        );
+                        self.processor
+                            .import_unaggregated_attestation(peer_id, gossip_verified);
+                        return;
+                    }
+                    Err(acceptance) => acceptance,
                 }
             }
             PubsubMessage::BeaconBlock(block) => {
                 match self.processor.should_forward_block(&peer_id, block) {
                     Ok(verified_block) => {
                         info!(self.log, "New block received"; "slot" => verified_block.block.slot(), "hash" => verified_block.block_root.to_string());
-                        self.propagate_message(id, peer_id.clone());
+                        self.propagate_validation_result(
+                            id,
+                            peer_id.clone(),
+                            kind,
+                            MessageAcceptance::Accept,
+                        );
                         self.processor.on_block_gossip(peer_id, verified_block);
+                        return;
                     }
-                    Err(BlockError::ParentUnknown { .. }) => {} // performing a parent lookup
-                    Err(e) => {
+                    Err(BlockError::ParentUnknown { .. }) => {
                         // performing a parent lookup
+                        MessageAcceptance::Ignore
+                    }
+                    Err(e) => {
                         warn!(self.log, "Could not verify block for gossip";
                             "error" => format!("{:?}", e));
+                        block_error_acceptance(&e)
                     }
                 }
             }
-            PubsubMessage::VoluntaryExit(_exit) => {
-                // TODO: Apply more sophisticated validation
-                self.propagate_message(id, peer_id.clone());
-                // TODO: Handle exits
-                debug!(self.log, "Received a voluntary exit"; "peer_id" => format!("{}", peer_id) );
+            PubsubMessage::VoluntaryExit(exit) => {
+                debug!(self.log, "Received a voluntary exit"; "peer_id" => format!("{}", peer_id));
+                match self
+                    .processor
+                    .verify_voluntary_exit_for_gossip(&peer_id, *exit)
+                {
+                    Ok(verified_exit) => {
+                        self.propagate_validation_result(
+                            id,
+                            peer_id,
+                            kind,
+                            MessageAcceptance::Accept,
+                        );
+                        self.processor.import_verified_voluntary_exit(verified_exit);
+                        return;
+                    }
+                    Err(acceptance) => acceptance,
+                }
             }
-            PubsubMessage::ProposerSlashing(_proposer_slashing) => {
-                // TODO: Apply more sophisticated validation
-                self.propagate_message(id, peer_id.clone());
-                // TODO: Handle proposer slashings
-                debug!(self.log, "Received a proposer slashing"; "peer_id" => format!("{}", peer_id) );
+            PubsubMessage::ProposerSlashing(proposer_slashing) => {
+                debug!(
+                    self.log,
+                    "Received a proposer slashing";
+                    "peer_id" => format!("{}", peer_id)
+                );
+                match self
+                    .processor
+                    .verify_proposer_slashing_for_gossip(&peer_id, *proposer_slashing)
+                {
+                    Ok(verified_proposer_slashing) => {
+                        self.propagate_validation_result(
+                            id,
+                            peer_id,
+                            kind,
+                            MessageAcceptance::Accept,
+                        );
+                        self.processor
+                            .import_verified_proposer_slashing(verified_proposer_slashing);
+                        return;
+                    }
+                    Err(acceptance) => acceptance,
+                }
             }
-            PubsubMessage::AttesterSlashing(_attester_slashing) => {
-                // TODO: Apply more sophisticated validation
-                self.propagate_message(id, peer_id.clone());
-                // TODO: Handle attester slashings
-                debug!(self.log, "Received an attester slashing"; "peer_id" => format!("{}", peer_id) );
+            PubsubMessage::AttesterSlashing(attester_slashing) => {
+                debug!(
+                    self.log,
+                    "Received an attester slashing";
+                    "peer_id" => format!("{}", peer_id)
+                );
+                match self
+                    .processor
+                    .verify_attester_slashing_for_gossip(&peer_id, *attester_slashing)
+                {
+                    Ok(verified_attester_slashing) => {
+                        self.propagate_validation_result(
+                            id,
+                            peer_id,
+                            kind,
+                            MessageAcceptance::Accept,
+                        );
+                        self.processor
+                            .import_verified_attester_slashing(verified_attester_slashing);
+                        return;
+                    }
+                    Err(acceptance) => acceptance,
+                }
             }
-        }
+        };
+
+        // the message was not accepted, inform gossipsub so the peer can be scored
+        self.propagate_validation_result(id, peer_id, kind, acceptance);
     }
 
-    /// Informs the network service that the message should be forwarded to other peers.
-    fn propagate_message(&mut self, message_id: MessageId, propagation_source: PeerId) {
+    /// Informs the network service of the result of validating a gossipsub message. Accepted
+    /// messages are forwarded to other peers and the result is used to score the peer that sent
+    /// the message.
+    fn propagate_validation_result(
+        &mut self,
+        message_id: MessageId,
+        propagation_source: PeerId,
+        kind: GossipKind,
+        acceptance: MessageAcceptance,
+    ) {
         self.network_send
-            .send(NetworkMessage::Propagate {
+            .send(NetworkMessage::ValidationResult {
                 propagation_source,
                 message_id,
+                kind,
+                acceptance,
             })
             .unwrap_or_else(|_| {
                 warn!(
                     self.log,
-                    "Could not send propagation request to the network service"
+                    "Could not send validation result to the network service"
                 )
             });
     }
 }
+
+/// Determines whether the peer that sent a block which failed gossip verification is at fault.
+fn block_error_acceptance(error: &BlockError) -> MessageAcceptance {
+    match error {
+        // These errors can be caused by a mismatch between our clock, head or database and the
+        // peer's, the peer is not necessarily faulty.
+        BlockError::ParentUnknown(_)
+        | BlockError::FutureSlot { .. }
+        | BlockError::WouldRevertFinalizedSlot { .. }
+        | BlockError::BlockIsAlreadyKnown
+        | BlockError::RepeatProposal { .. }
+        | BlockError::BeaconChainError(_) => MessageAcceptance::Ignore,
+        // The peer has published an invalid consensus message.
+        _ => MessageAcceptance::Reject,
+    }
+}
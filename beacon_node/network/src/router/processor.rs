@@ -5,19 +5,23 @@ use beacon_chain::{
         Error as AttnError, IntoForkChoiceVerifiedAttestation, VerifiedAggregatedAttestation,
         VerifiedUnaggregatedAttestation,
     },
-    BeaconChain, BeaconChainTypes, BlockError, BlockProcessingOutcome, GossipVerifiedBlock,
+    observed_operations::ObservationOutcome,
+    AttesterSlashingValidationError, BeaconChain, BeaconChainError, BeaconChainTypes, BlockError,
+    BlockProcessingOutcome, ExitValidationError, GossipVerifiedBlock,
+    ProposerSlashingValidationError,
 };
 use eth2_libp2p::rpc::*;
-use eth2_libp2p::{NetworkGlobals, PeerId, Request, Response};
+use eth2_libp2p::{MessageAcceptance, NetworkGlobals, PeerId, Request, Response};
 use itertools::process_results;
 use slog::{debug, error, o, trace, warn};
 use ssz::Encode;
+use state_processing::SigVerifiedOp;
 use std::sync::Arc;
 use store::Store;
 use tokio::sync::mpsc;
 use types::{
-    Attestation, ChainSpec, Epoch, EthSpec, Hash256, SignedAggregateAndProof, SignedBeaconBlock,
-    Slot,
+    Attestation, AttesterSlashing, ChainSpec, Epoch, EthSpec, Hash256, ProposerSlashing,
+    SignedAggregateAndProof, SignedBeaconBlock, SignedVoluntaryExit, Slot,
 };
 
 //TODO: Rate limit requests
@@ -342,6 +346,27 @@ impl<T: BeaconChainTypes> Processor<T> {
             return;
         }
 
+        // Blocks prior to the oldest block in the database are unavailable until back-filling is
+        // complete. Serving such a range would make it appear as though its slots were skipped.
+        if let Some(anchor_info) = self.chain.store.get_anchor_info() {
+            if req.start_slot < anchor_info.oldest_block_slot.as_u64() {
+                debug!(
+                    self.log,
+                    "Unable to serve BlocksByRange request";
+                    "peer" => format!("{:?}", peer_id),
+                    "start_slot" => req.start_slot,
+                    "oldest_block_slot" => anchor_info.oldest_block_slot,
+                );
+                self.network.send_error_response(
+                    peer_id,
+                    request_id,
+                    RPCResponseErrorCode::ServerError,
+                    "Historical blocks are not yet available".to_string(),
+                );
+                return;
+            }
+        }
+
         let forwards_block_root_iter = match self
             .chain
             .forwards_iter_block_roots(Slot::from(req.start_slot))
@@ -584,13 +609,16 @@ impl<T: BeaconChainTypes> Processor<T> {
 
     /// Handle an error whilst verifying an `Attestation` or `SignedAggregateAndProof` from the
     /// network.
+    ///
+    /// Returns `MessageAcceptance::Reject` if the peer has published an invalid consensus
+    /// message, otherwise `MessageAcceptance::Ignore`.
     pub fn handle_attestation_verification_failure(
         &mut self,
         peer_id: PeerId,
         beacon_block_root: Hash256,
         attestation_type: &str,
         error: AttnError,
-    ) {
+    ) -> MessageAcceptance {
         debug!(
             self.log,
             "Invalid attestation from network";
@@ -610,6 +638,7 @@ impl<T: BeaconChainTypes> Processor<T> {
                  *
                  * The peer has published an invalid consensus message, _only_ if we trust our own clock.
                  */
+                MessageAcceptance::Ignore
             }
             AttnError::InvalidSelectionProof { .. } | AttnError::InvalidSignature => {
                 /*
@@ -617,6 +646,7 @@ impl<T: BeaconChainTypes> Processor<T> {
                  *
                  * The peer has published an invalid consensus message.
                  */
+                MessageAcceptance::Reject
             }
             AttnError::EmptyAggregationBitfield => {
                 /*
@@ -629,6 +659,7 @@ impl<T: BeaconChainTypes> Processor<T> {
                  *
                  * https://github.com/ethereum/eth2.0-specs/pull/1732
                  */
+                MessageAcceptance::Ignore
             }
             AttnError::AggregatorPubkeyUnknown(_) => {
                 /*
@@ -644,6 +675,7 @@ impl<T: BeaconChainTypes> Processor<T> {
                  *
                  * The peer has published an invalid consensus message.
                  */
+                MessageAcceptance::Reject
             }
             AttnError::AggregatorNotInCommittee { .. } => {
                 /*
@@ -659,6 +691,7 @@ impl<T: BeaconChainTypes> Processor<T> {
                  *
                  * The peer has published an invalid consensus message.
                  */
+                MessageAcceptance::Reject
             }
             AttnError::AttestationAlreadyKnown { .. } => {
                 /*
@@ -667,6 +700,7 @@ impl<T: BeaconChainTypes> Processor<T> {
                  *
                  * The peer is not necessarily faulty.
                  */
+                MessageAcceptance::Ignore
             }
             AttnError::AggregatorAlreadyKnown(_) => {
                 /*
@@ -675,6 +709,7 @@ impl<T: BeaconChainTypes> Processor<T> {
                  *
                  * The peer is not necessarily faulty.
                  */
+                MessageAcceptance::Ignore
             }
             AttnError::PriorAttestationKnown { .. } => {
                 /*
@@ -682,6 +717,7 @@ impl<T: BeaconChainTypes> Processor<T> {
                  *
                  * The peer is not necessarily faulty.
                  */
+                MessageAcceptance::Ignore
             }
             AttnError::ValidatorIndexTooHigh(_) => {
                 /*
@@ -690,6 +726,7 @@ impl<T: BeaconChainTypes> Processor<T> {
                  *
                  * The peer has published an invalid consensus message.
                  */
+                MessageAcceptance::Reject
             }
             AttnError::UnknownHeadBlock { beacon_block_root } => {
                 // Note: its a little bit unclear as to whether or not this block is unknown or
@@ -706,6 +743,7 @@ impl<T: BeaconChainTypes> Processor<T> {
                 );
                 // we don't know the block, get the sync manager to handle the block lookup
                 self.send_to_sync(SyncMessage::UnknownBlockHash(peer_id, beacon_block_root));
+                MessageAcceptance::Ignore
             }
             AttnError::UnknownTargetRoot(_) => {
                 /*
@@ -724,6 +762,7 @@ impl<T: BeaconChainTypes> Processor<T> {
                  *
                  * The peer has published an invalid consensus message.
                  */
+                MessageAcceptance::Reject
             }
             AttnError::BadTargetEpoch => {
                 /*
@@ -732,6 +771,7 @@ impl<T: BeaconChainTypes> Processor<T> {
                  *
                  * The peer has published an invalid consensus message.
                  */
+                MessageAcceptance::Reject
             }
             AttnError::NoCommitteeForSlotAndIndex { .. } => {
                 /*
@@ -739,6 +779,7 @@ impl<T: BeaconChainTypes> Processor<T> {
                  *
                  * The peer has published an invalid consensus message.
                  */
+                MessageAcceptance::Reject
             }
             AttnError::NotExactlyOneAggregationBitSet(_) => {
                 /*
@@ -746,6 +787,7 @@ impl<T: BeaconChainTypes> Processor<T> {
                  *
                  * The peer has published an invalid consensus message.
                  */
+                MessageAcceptance::Reject
             }
             AttnError::AttestsToFutureBlock { .. } => {
                 /*
@@ -753,6 +795,7 @@ impl<T: BeaconChainTypes> Processor<T> {
                  *
                  * The peer has published an invalid consensus message.
                  */
+                MessageAcceptance::Reject
             }
             AttnError::Invalid(_) => {
                 /*
@@ -760,6 +803,7 @@ impl<T: BeaconChainTypes> Processor<T> {
                  *
                  * The peer has published an invalid consensus message.
                  */
+                MessageAcceptance::Reject
             }
             AttnError::BeaconChainError(e) => {
                 /*
@@ -775,6 +819,7 @@ impl<T: BeaconChainTypes> Processor<T> {
                     "peer_id" => format!("{:?}", peer_id),
                     "error" => format!("{:?}", e),
                 );
+                MessageAcceptance::Ignore
             }
         }
     }
@@ -783,7 +828,7 @@ impl<T: BeaconChainTypes> Processor<T> {
         &mut self,
         peer_id: PeerId,
         aggregate_and_proof: SignedAggregateAndProof<T::EthSpec>,
-    ) -> Option<VerifiedAggregatedAttestation<T>> {
+    ) -> Result<VerifiedAggregatedAttestation<T>, MessageAcceptance> {
         // This is provided to the error handling function to assist with debugging.
         let beacon_block_root = aggregate_and_proof.message.aggregate.data.beacon_block_root;
 
@@ -797,7 +842,6 @@ impl<T: BeaconChainTypes> Processor<T> {
                     e,
                 )
             })
-            .ok()
     }
 
     pub fn import_aggregated_attestation(
@@ -829,7 +873,7 @@ impl<T: BeaconChainTypes> Processor<T> {
         &mut self,
         peer_id: PeerId,
         unaggregated_attestation: Attestation<T::EthSpec>,
-    ) -> Option<VerifiedUnaggregatedAttestation<T>> {
+    ) -> Result<VerifiedUnaggregatedAttestation<T>, MessageAcceptance> {
         // This is provided to the error handling function to assist with debugging.
         let beacon_block_root = unaggregated_attestation.data.beacon_block_root;
 
@@ -843,7 +887,6 @@ impl<T: BeaconChainTypes> Processor<T> {
                     e,
                 )
             })
-            .ok()
     }
 
     pub fn import_unaggregated_attestation(
@@ -874,6 +917,178 @@ impl<T: BeaconChainTypes> Processor<T> {
         }
     }
 
+    /// Verify a voluntary exit received on gossip before it is propagated or imported.
+    ///
+    /// Returns an error if the exit is invalid or concerns a validator for which we have already
+    /// seen an exit, indicating whether the peer that sent it should be penalised.
+    pub fn verify_voluntary_exit_for_gossip(
+        &mut self,
+        peer_id: &PeerId,
+        voluntary_exit: SignedVoluntaryExit,
+    ) -> Result<SigVerifiedOp<SignedVoluntaryExit>, MessageAcceptance> {
+        let validator_index = voluntary_exit.message.validator_index;
+
+        match self.chain.verify_voluntary_exit_for_gossip(voluntary_exit) {
+            Ok(ObservationOutcome::New(verified_exit)) => Ok(verified_exit),
+            Ok(ObservationOutcome::AlreadyKnown) => {
+                debug!(
+                    self.log,
+                    "Dropping exit for already exiting validator";
+                    "validator_index" => validator_index,
+                    "peer" => peer_id.to_string()
+                );
+                Err(MessageAcceptance::Ignore)
+            }
+            Err(e) => {
+                debug!(
+                    self.log,
+                    "Dropping invalid exit";
+                    "validator_index" => validator_index,
+                    "peer" => peer_id.to_string(),
+                    "error" => format!("{:?}", e)
+                );
+                Err(self.handle_operation_verification_failure(&e))
+            }
+        }
+    }
+
+    /// Import a gossip-verified voluntary exit into the operation pool.
+    pub fn import_verified_voluntary_exit(
+        &self,
+        verified_exit: SigVerifiedOp<SignedVoluntaryExit>,
+    ) {
+        self.chain.import_voluntary_exit(verified_exit);
+        debug!(self.log, "Successfully imported voluntary exit");
+    }
+
+    /// Verify a proposer slashing received on gossip before it is propagated or imported.
+    ///
+    /// Returns an error if the slashing is invalid or concerns a proposer that we have already
+    /// seen slashed, indicating whether the peer that sent it should be penalised.
+    pub fn verify_proposer_slashing_for_gossip(
+        &mut self,
+        peer_id: &PeerId,
+        proposer_slashing: ProposerSlashing,
+    ) -> Result<SigVerifiedOp<ProposerSlashing>, MessageAcceptance> {
+        let validator_index = proposer_slashing.signed_header_1.message.proposer_index;
+
+        match self
+            .chain
+            .verify_proposer_slashing_for_gossip(proposer_slashing)
+        {
+            Ok(ObservationOutcome::New(verified_slashing)) => Ok(verified_slashing),
+            Ok(ObservationOutcome::AlreadyKnown) => {
+                debug!(
+                    self.log,
+                    "Dropping proposer slashing";
+                    "reason" => "Already seen a proposer slashing for that validator",
+                    "validator_index" => validator_index,
+                    "peer" => peer_id.to_string()
+                );
+                Err(MessageAcceptance::Ignore)
+            }
+            Err(e) => {
+                debug!(
+                    self.log,
+                    "Dropping invalid proposer slashing";
+                    "validator_index" => validator_index,
+                    "peer" => peer_id.to_string(),
+                    "error" => format!("{:?}", e)
+                );
+                Err(self.handle_operation_verification_failure(&e))
+            }
+        }
+    }
+
+    /// Import a gossip-verified proposer slashing into the operation pool.
+    pub fn import_verified_proposer_slashing(
+        &self,
+        verified_slashing: SigVerifiedOp<ProposerSlashing>,
+    ) {
+        self.chain.import_proposer_slashing(verified_slashing);
+        debug!(self.log, "Successfully imported proposer slashing");
+    }
+
+    /// Verify an attester slashing received on gossip before it is propagated or imported.
+    ///
+    /// Returns an error if the slashing is invalid or only concerns validators that have already
+    /// been seen in a prior attester slashing, indicating whether the peer that sent it should be
+    /// penalised.
+    pub fn verify_attester_slashing_for_gossip(
+        &mut self,
+        peer_id: &PeerId,
+        attester_slashing: AttesterSlashing<T::EthSpec>,
+    ) -> Result<SigVerifiedOp<AttesterSlashing<T::EthSpec>>, MessageAcceptance> {
+        match self
+            .chain
+            .verify_attester_slashing_for_gossip(attester_slashing)
+        {
+            Ok(ObservationOutcome::New(verified_slashing)) => Ok(verified_slashing),
+            Ok(ObservationOutcome::AlreadyKnown) => {
+                debug!(
+                    self.log,
+                    "Dropping attester slashing";
+                    "reason" => "Slashings already known for all slashed validators",
+                    "peer" => peer_id.to_string()
+                );
+                Err(MessageAcceptance::Ignore)
+            }
+            Err(e) => {
+                debug!(
+                    self.log,
+                    "Dropping invalid attester slashing";
+                    "peer" => peer_id.to_string(),
+                    "error" => format!("{:?}", e)
+                );
+                Err(self.handle_operation_verification_failure(&e))
+            }
+        }
+    }
+
+    /// Import a gossip-verified attester slashing into the operation pool.
+    pub fn import_verified_attester_slashing(
+        &self,
+        verified_slashing: SigVerifiedOp<AttesterSlashing<T::EthSpec>>,
+    ) {
+        if let Err(e) = self.chain.import_attester_slashing(verified_slashing) {
+            debug!(self.log, "Error importing attester slashing"; "error" => format!("{:?}", e));
+        } else {
+            debug!(self.log, "Successfully imported attester slashing");
+        }
+    }
+
+    /// Determines whether the peer that sent an exit or slashing which failed verification should
+    /// be penalised.
+    ///
+    /// Only errors which show the operation itself to be invalid are attributed to the peer.
+    /// Internal errors (e.g., failing to load the wall-clock state) are not the peer's fault.
+    fn handle_operation_verification_failure(&self, error: &BeaconChainError) -> MessageAcceptance {
+        match error {
+            BeaconChainError::ExitValidationError(ExitValidationError::Invalid(_))
+            | BeaconChainError::ProposerSlashingValidationError(
+                ProposerSlashingValidationError::Invalid(_),
+            )
+            | BeaconChainError::AttesterSlashingValidationError(
+                AttesterSlashingValidationError::Invalid(_),
+            ) => {
+                /*
+                 * The operation failed the state_processing verification.
+                 *
+                 * The peer has published an invalid consensus message.
+                 */
+                MessageAcceptance::Reject
+            }
+            _ => {
+                /*
+                 * Lighthouse hit an unexpected error whilst verifying the operation.
+                 *
+                 * It's not clear if the message is invalid/malicious.
+                 */
+                MessageAcceptance::Ignore
+            }
+        }
+    }
+
     /// Apply the attestation to fork choice, suppressing errors.
     ///
     /// We suppress the errors when adding an attestation to fork choice since the spec
@@ -911,10 +1126,21 @@ pub(crate) fn status_message<T: BeaconChainTypes>(
     let fork_digest =
         ChainSpec::compute_fork_digest(head_info.fork.current_version, genesis_validators_root);
 
+    // A node started from a weak subjectivity checkpoint holds no blocks prior to its anchor.
+    // Advertise the anchor as finalized (until the head finalizes beyond it) so that range sync
+    // never attempts to download blocks which precede it.
+    let head_finalized = head_info.finalized_checkpoint;
+    let fork_choice_finalized = beacon_chain.fork_choice.finalized_checkpoint();
+    let finalized_checkpoint = if fork_choice_finalized.epoch > head_finalized.epoch {
+        fork_choice_finalized
+    } else {
+        head_finalized
+    };
+
     Some(StatusMessage {
         fork_digest,
-        finalized_root: head_info.finalized_checkpoint.root,
-        finalized_epoch: head_info.finalized_checkpoint.epoch,
+        finalized_root: finalized_checkpoint.root,
+        finalized_epoch: finalized_checkpoint.epoch,
         head_root: head_info.block_root,
         head_slot: head_info.slot,
     })
@@ -971,7 +1197,7 @@ impl<T: EthSpec> HandlerNetworkContext<T> {
             response,
         })
     }
-    pub fn _send_error_response(
+    pub fn send_error_response(
         &mut self,
         peer_id: PeerId,
         substream_id: SubstreamId,
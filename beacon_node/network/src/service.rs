@@ -1,3 +1,4 @@
+use crate::persisted_banned_peers::{load_banned_peers, persist_banned_peers};
 use crate::persisted_dht::{load_dht, persist_dht};
 use crate::router::{Router, RouterMessage};
 use crate::{
@@ -11,10 +12,14 @@ use eth2_libp2p::{
     rpc::{RPCResponseErrorCode, RequestId, SubstreamId},
     Libp2pEvent, PubsubMessage, Request, Response,
 };
-use eth2_libp2p::{BehaviourEvent, Enr, MessageId, NetworkGlobals, PeerId};
+use eth2_libp2p::{
+    types::GossipKind, BehaviourEvent, Enr, MessageAcceptance, MessageId, NetworkGlobals,
+    PeerAction, PeerId, ReportSource,
+};
 use futures::prelude::*;
 use rest_types::ValidatorSubscription;
 use slog::{debug, error, info, o, trace};
+use std::net::IpAddr;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::mpsc;
@@ -75,13 +80,22 @@ impl<T: BeaconChainTypes> NetworkService<T> {
         let next_fork_update = next_fork_delay(&beacon_chain);
 
         // launch libp2p service
-        let (network_globals, mut libp2p) =
-            LibP2PService::new(executor.clone(), config, enr_fork_id, &network_log)?;
+        let (network_globals, mut libp2p) = LibP2PService::new(
+            executor.clone(),
+            config,
+            enr_fork_id,
+            &beacon_chain.spec,
+            &network_log,
+        )?;
 
         for enr in load_dht::<T::Store, T::EthSpec>(store.clone()) {
             libp2p.swarm.add_enr(enr);
         }
 
+        for (peer_id, ips) in load_banned_peers::<T::Store, T::EthSpec>(store.clone()) {
+            libp2p.restore_banned_peer(peer_id, ips);
+        }
+
         // launch derived network services
 
         // router task
@@ -154,6 +168,31 @@ fn spawn_service<T: BeaconChainTypes>(
                         ),
                     }
 
+                    let banned_peers: Vec<(PeerId, Vec<IpAddr>)> = service
+                        .network_globals
+                        .peers
+                        .read()
+                        .banned_peers_with_ips()
+                        .map(|(peer_id, ips)| (peer_id.clone(), ips.iter().cloned().collect()))
+                        .collect();
+                    debug!(
+                        service.log,
+                        "Persisting banned peers to store";
+                        "Number of peers" => format!("{}", banned_peers.len()),
+                    );
+
+                    match persist_banned_peers::<T::Store, T::EthSpec>(service.store.clone(), banned_peers) {
+                        Err(e) => error!(
+                            service.log,
+                            "Failed to persist banned peers on drop";
+                            "error" => format!("{:?}", e)
+                        ),
+                        Ok(_) => info!(
+                            service.log,
+                            "Saved banned peers";
+                        ),
+                    }
+
                     info!(service.log, "Network service shutdown");
                     return;
                 }
@@ -169,10 +208,18 @@ fn spawn_service<T: BeaconChainTypes>(
                         NetworkMessage::SendError{ peer_id, error, substream_id, reason } => {
                             service.libp2p.respond_with_error(peer_id, substream_id, error, reason);
                         }
-                        NetworkMessage::Propagate {
+                        NetworkMessage::ValidationResult {
                             propagation_source,
                             message_id,
+                            kind,
+                            acceptance,
                         } => {
+                            // inform gossipsub of the result so that the peer is scored
+                            service
+                                .libp2p
+                                .swarm
+                                .report_message_validation_result(&propagation_source, &kind, acceptance);
+
                             // TODO: Remove this for mainnet
                             // randomly prevents propagation
                             let mut should_send = true;
@@ -184,7 +231,12 @@ fn spawn_service<T: BeaconChainTypes>(
                                     should_send = false;
                                 }
                             }
-                            if !should_send {
+                            if acceptance != MessageAcceptance::Accept {
+                                trace!(service.log, "Not propagating gossipsub message";
+                                    "acceptance" => format!("{:?}", acceptance),
+                                    "message_id" => message_id.to_string(),
+                                );
+                            } else if !should_send {
                                 info!(service.log, "Random filter did not propagate message");
                             } else {
                                 trace!(service.log, "Propagating gossipsub message";
@@ -234,6 +286,9 @@ fn spawn_service<T: BeaconChainTypes>(
                                 std::time::Duration::from_secs(BAN_PEER_TIMEOUT),
                             );
                         }
+                        NetworkMessage::ReportPeer { peer_id, action, source } => {
+                            service.libp2p.swarm.peer_manager().report_peer(&peer_id, action, source);
+                        }
                         NetworkMessage::Subscribe { subscriptions } => {
                             // the result is dropped as it used solely for ergonomics
                             let _ = service
@@ -353,6 +408,10 @@ fn spawn_service<T: BeaconChainTypes>(
                                 }
                             }
                             BehaviourEvent::PeerSubscribed(_, _) => {},
+                            // peer manager requests are handled by the libp2p service
+                            BehaviourEvent::DisconnectPeer(_)
+                            | BehaviourEvent::BanPeer(_)
+                            | BehaviourEvent::UnBanPeer(_) => {},
                         }
                         Libp2pEvent::NewListenAddr(multiaddr) => {
                             service.network_globals.listen_multiaddrs.write().push(multiaddr);
@@ -437,13 +496,22 @@ pub enum NetworkMessage<T: EthSpec> {
     },
     /// Publish a list of messages to the gossipsub protocol.
     Publish { messages: Vec<PubsubMessage<T>> },
-    /// Propagate a received gossipsub message.
-    Propagate {
+    /// Reports the result of validating a received gossipsub message. The message is propagated
+    /// if it was accepted and the result is used to score the peer that sent it.
+    ValidationResult {
         propagation_source: PeerId,
         message_id: MessageId,
+        kind: GossipKind,
+        acceptance: MessageAcceptance,
     },
     /// Disconnect and bans a peer id.
     Disconnect { peer_id: PeerId },
+    /// Reports a peer to the peer manager for performing an action.
+    ReportPeer {
+        peer_id: PeerId,
+        action: PeerAction,
+        source: ReportSource,
+    },
 }
 
 /// Inspects the `messages` that were being sent to the network and updates Prometheus metrics.
@@ -0,0 +1,529 @@
+//! Downloads the historical blocks which precede the weak subjectivity checkpoint that the node
+//! was started from.
+//!
+//! Blocks are requested with `BlocksByRange`, one batch at a time, working backwards from the
+//! oldest block in the database towards genesis. Each batch is verified by the block processor
+//! (hash chain linkage and proposer signatures) and stored without re-executing any states: the
+//! blocks go to the hot database and their roots to the freezer database.
+//!
+//! Back-filling only takes place whilst the node is synced, so that it does not compete with a
+//! range sync. Progress is published in the global `SyncState`.
+//!
+//! A batch need not contain a block at each of its slots, so a peer can withhold the oldest
+//! blocks of a batch (or all of them) by claiming that their slots were skipped. Such claims
+//! cannot be checked until a later batch connects to the chain, at which point the peer whose
+//! claimed range contains the connecting block is penalized.
+
+use super::block_processor::{spawn_block_processor, BatchProcessResult, ProcessId};
+use super::manager::SyncMessage;
+use super::network_context::SyncNetworkContext;
+use super::RequestId;
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use eth2_libp2p::rpc::methods::BlocksByRangeRequest;
+use eth2_libp2p::{types::SyncState, NetworkGlobals, PeerId};
+use slog::{debug, info, warn};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use store::{AnchorInfo, Store};
+use tokio::sync::mpsc;
+use types::{EthSpec, SignedBeaconBlock, Slot};
+
+/// The number of slots of historical blocks requested in a single batch.
+const BACKFILL_BATCH_SLOTS: u64 = 64;
+
+/// The time for which a peer that failed to serve a batch is not used by the back-fill.
+const FAILED_PEER_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// The state of the batch of historical blocks currently being back-filled.
+enum BatchState {
+    /// The blocks are being downloaded from a peer.
+    Downloading { request_id: RequestId },
+    /// The blocks have been sent to the block processor.
+    Processing,
+}
+
+/// A batch of historical blocks.
+struct Batch<E: EthSpec> {
+    /// The requested start slot of the batch, inclusive.
+    start_slot: Slot,
+    /// The requested end slot of the batch, exclusive.
+    end_slot: Slot,
+    /// The peer which the blocks were requested from.
+    peer_id: PeerId,
+    state: BatchState,
+    /// The blocks that have been downloaded.
+    downloaded_blocks: Vec<SignedBeaconBlock<E>>,
+    /// The slots of the oldest and newest downloaded blocks, set once the batch is sent for
+    /// processing.
+    served_slots: Option<(Slot, Slot)>,
+}
+
+/// A peer's claim that there are no blocks in the slots `start_slot..end_slot`.
+struct SkippedSlotsClaim {
+    peer_id: PeerId,
+    start_slot: Slot,
+    end_slot: Slot,
+}
+
+/// Tracks which peers may be used by the back-fill, and which peers are yet to be proven honest.
+#[derive(Default)]
+struct BackFillPeers {
+    /// Peers which failed to serve a batch, with the time of the failure.
+    failed: HashMap<PeerId, Instant>,
+    /// Claims of skipped slots made by batches since the last batch that connected to the chain,
+    /// newest first.
+    unverified_claims: Vec<SkippedSlotsClaim>,
+}
+
+impl BackFillPeers {
+    /// Excludes `peer_id` from the back-fill for `FAILED_PEER_TIMEOUT`.
+    fn mark_failed(&mut self, peer_id: PeerId, now: Instant) {
+        self.failed.insert(peer_id, now);
+    }
+
+    /// Returns `true` if `peer_id` is currently excluded from the back-fill.
+    fn is_failed(&self, peer_id: &PeerId) -> bool {
+        self.failed.contains_key(peer_id)
+    }
+
+    /// Allows peers to be used again once `FAILED_PEER_TIMEOUT` has passed since they failed.
+    fn expire_failed(&mut self, now: Instant) {
+        self.failed
+            .retain(|_, failed_at| now.saturating_duration_since(*failed_at) < FAILED_PEER_TIMEOUT);
+    }
+
+    /// Returns `true` if a batch failure may have been caused by a peer that withheld blocks.
+    fn has_unverified_claims(&self) -> bool {
+        !self.unverified_claims.is_empty()
+    }
+
+    /// A batch served by `peer_id` for `start_slot..end_slot` has been imported.
+    ///
+    /// `served_slots` are the slots of the oldest and newest blocks of the batch, if it was not
+    /// empty. The newest block is the parent of the previously oldest block, so it verifies any
+    /// outstanding claims of skipped slots.
+    ///
+    /// Returns the peers whose claims were disproven by the batch.
+    fn batch_imported(
+        &mut self,
+        peer_id: PeerId,
+        start_slot: Slot,
+        end_slot: Slot,
+        served_slots: Option<(Slot, Slot)>,
+    ) -> Vec<PeerId> {
+        let (claim_end_slot, disproven) = match served_slots {
+            Some((oldest_slot, newest_slot)) => {
+                let disproven = std::mem::replace(&mut self.unverified_claims, vec![])
+                    .into_iter()
+                    .filter(|claim| claim.start_slot <= newest_slot && newest_slot < claim.end_slot)
+                    .map(|claim| claim.peer_id)
+                    .collect();
+                (oldest_slot, disproven)
+            }
+            None => (end_slot, vec![]),
+        };
+
+        if start_slot < claim_end_slot {
+            self.unverified_claims.push(SkippedSlotsClaim {
+                peer_id,
+                start_slot,
+                end_slot: claim_end_slot,
+            });
+        }
+
+        disproven
+    }
+}
+
+/// Drives the back-fill of historical blocks for a node started from a checkpoint.
+pub struct BackFillSync<T: BeaconChainTypes> {
+    /// A reference to the underlying beacon chain.
+    chain: Arc<BeaconChain<T>>,
+    /// A reference to the network globals and peer-db.
+    network_globals: Arc<NetworkGlobals<T::EthSpec>>,
+    /// The batch currently being downloaded or processed, if any.
+    current_batch: Option<Batch<T::EthSpec>>,
+    /// The exclusive end slot of the next batch to request.
+    ///
+    /// This is usually the slot of the oldest block in the database, but may be earlier if a
+    /// batch contained no new blocks (i.e. it only covered skipped slots).
+    next_end_slot: Option<Slot>,
+    /// Peers which may not currently be used, and claims of skipped slots yet to be verified.
+    peers: BackFillPeers,
+    /// A channel to the sync manager, used by the block processor.
+    sync_send: mpsc::UnboundedSender<SyncMessage<T::EthSpec>>,
+    log: slog::Logger,
+}
+
+impl<T: BeaconChainTypes> BackFillSync<T> {
+    pub fn new(
+        chain: Arc<BeaconChain<T>>,
+        network_globals: Arc<NetworkGlobals<T::EthSpec>>,
+        sync_send: mpsc::UnboundedSender<SyncMessage<T::EthSpec>>,
+        log: slog::Logger,
+    ) -> Self {
+        Self {
+            chain,
+            network_globals,
+            current_batch: None,
+            next_end_slot: None,
+            peers: BackFillPeers::default(),
+            sync_send,
+            log,
+        }
+    }
+
+    /// Returns the anchor of the database, if there are still blocks to back-fill.
+    fn incomplete_anchor(&self) -> Option<AnchorInfo> {
+        self.chain
+            .store
+            .get_anchor_info()
+            .filter(|anchor_info| !anchor_info.block_backfill_complete())
+    }
+
+    /// Returns `true` if `request_id` belongs to the batch currently being downloaded.
+    pub fn is_backfill_request(&self, request_id: RequestId) -> bool {
+        match self.current_batch {
+            Some(Batch {
+                state: BatchState::Downloading { request_id: id },
+                ..
+            }) => id == request_id,
+            _ => false,
+        }
+    }
+
+    /// Requests the next batch of historical blocks, if back-filling is incomplete and no batch
+    /// is already in progress.
+    ///
+    /// This also keeps the back-fill progress in the global sync state up to date.
+    pub fn resume(&mut self, network: &mut SyncNetworkContext<T::EthSpec>) {
+        let anchor_info = match self.incomplete_anchor() {
+            Some(anchor_info) => anchor_info,
+            None => {
+                self.set_complete();
+                return;
+            }
+        };
+
+        // Only back-fill whilst the node is synced, range sync takes priority.
+        if !self.network_globals.sync_state.read().is_synced() {
+            return;
+        }
+
+        self.update_progress(&anchor_info);
+
+        if self.current_batch.is_some() {
+            return;
+        }
+
+        self.peers.expire_failed(Instant::now());
+
+        let peer_id = match self
+            .network_globals
+            .peers
+            .read()
+            .synced_peers()
+            .find(|peer_id| !self.peers.is_failed(peer_id))
+            .cloned()
+        {
+            Some(peer_id) => peer_id,
+            None => return,
+        };
+
+        let end_slot = match self.next_end_slot {
+            Some(slot) if slot > 0 && slot <= anchor_info.oldest_block_slot => slot,
+            _ => anchor_info.oldest_block_slot,
+        };
+        let start_slot = end_slot.saturating_sub(BACKFILL_BATCH_SLOTS);
+
+        let request = BlocksByRangeRequest {
+            start_slot: start_slot.as_u64(),
+            count: (end_slot - start_slot).as_u64(),
+            step: 1,
+        };
+
+        if let Ok(request_id) = network.blocks_by_range_request(peer_id.clone(), request) {
+            debug!(
+                self.log,
+                "Requesting historical blocks";
+                "peer" => format!("{}", peer_id),
+                "start_slot" => start_slot,
+                "end_slot" => end_slot,
+            );
+            self.current_batch = Some(Batch {
+                start_slot,
+                end_slot,
+                peer_id,
+                state: BatchState::Downloading { request_id },
+                downloaded_blocks: vec![],
+                served_slots: None,
+            });
+        }
+    }
+
+    /// A `BlocksByRange` response has been received for the current batch. A `None` block
+    /// terminates the stream, at which point the batch is sent for processing.
+    pub fn blocks_by_range_response(
+        &mut self,
+        network: &mut SyncNetworkContext<T::EthSpec>,
+        peer_id: PeerId,
+        request_id: RequestId,
+        beacon_block: Option<SignedBeaconBlock<T::EthSpec>>,
+    ) {
+        if !self.is_backfill_request(request_id) {
+            return;
+        }
+
+        let batch = match self.current_batch.as_mut() {
+            Some(batch) => batch,
+            None => return,
+        };
+
+        match beacon_block {
+            Some(block) => {
+                if block.slot() < batch.start_slot || block.slot() >= batch.end_slot {
+                    warn!(
+                        self.log,
+                        "Peer returned historical block outside of requested range";
+                        "peer" => format!("{}", peer_id),
+                        "block_slot" => block.slot(),
+                    );
+                    self.peers.mark_failed(peer_id.clone(), Instant::now());
+                    self.current_batch = None;
+                    network.downvote_peer(peer_id);
+                    return;
+                }
+                batch.downloaded_blocks.push(block);
+            }
+            None => {
+                batch.state = BatchState::Processing;
+                let downloaded_blocks = std::mem::replace(&mut batch.downloaded_blocks, vec![]);
+                batch.served_slots = match (downloaded_blocks.first(), downloaded_blocks.last()) {
+                    (Some(oldest), Some(newest)) => Some((oldest.slot(), newest.slot())),
+                    _ => None,
+                };
+                spawn_block_processor(
+                    Arc::downgrade(&self.chain),
+                    ProcessId::BackFillBatch,
+                    downloaded_blocks,
+                    self.sync_send.clone(),
+                    self.log.clone(),
+                );
+            }
+        }
+    }
+
+    /// The block processor has finished processing the current batch. The next batch is
+    /// requested by `resume`.
+    pub fn handle_block_process_result(
+        &mut self,
+        network: &mut SyncNetworkContext<T::EthSpec>,
+        result: BatchProcessResult,
+    ) {
+        let batch = match self.current_batch.take() {
+            Some(batch) => batch,
+            None => return,
+        };
+
+        match result {
+            BatchProcessResult::Success | BatchProcessResult::Partial => {
+                // The batch may have only covered skipped slots, in which case the next batch
+                // must start further back than the oldest block.
+                self.next_end_slot = Some(batch.start_slot);
+
+                for peer_id in self.peers.batch_imported(
+                    batch.peer_id,
+                    batch.start_slot,
+                    batch.end_slot,
+                    batch.served_slots,
+                ) {
+                    warn!(
+                        self.log,
+                        "Peer withheld historical blocks";
+                        "peer" => format!("{}", peer_id),
+                    );
+                    self.peers.mark_failed(peer_id.clone(), Instant::now());
+                    network.downvote_peer(peer_id);
+                }
+            }
+            BatchProcessResult::Failed => {
+                // A peer may have omitted the blocks of an earlier batch, so start again from
+                // the oldest block that we know of.
+                self.next_end_slot = None;
+                self.peers
+                    .mark_failed(batch.peer_id.clone(), Instant::now());
+
+                // If an earlier peer withheld blocks, this peer may have served a valid batch
+                // which does not connect to the oldest block. The withholding peer is penalized
+                // once the gap is filled.
+                if !self.peers.has_unverified_claims() {
+                    network.downvote_peer(batch.peer_id);
+                }
+            }
+        }
+    }
+
+    /// A request for the current batch has failed, it will be re-requested from another peer.
+    pub fn inject_error(&mut self, peer_id: PeerId, request_id: RequestId) {
+        if self.is_backfill_request(request_id) {
+            debug!(
+                self.log,
+                "Historical block request failed";
+                "peer" => format!("{}", peer_id),
+            );
+            self.peers.mark_failed(peer_id, Instant::now());
+            self.current_batch = None;
+        }
+    }
+
+    /// A peer has disconnected. If it was serving the current batch, the batch is abandoned.
+    pub fn peer_disconnect(&mut self, peer_id: &PeerId) {
+        if let Some(Batch {
+            peer_id: batch_peer,
+            state: BatchState::Downloading { .. },
+            ..
+        }) = &self.current_batch
+        {
+            if batch_peer == peer_id {
+                self.current_batch = None;
+            }
+        }
+    }
+
+    /// Publish the progress of the back-fill in the global sync state.
+    fn update_progress(&self, anchor_info: &AnchorInfo) {
+        let mut sync_state = self.network_globals.sync_state.write();
+
+        if !sync_state.is_synced() {
+            return;
+        }
+
+        let completed = (anchor_info.anchor_slot - anchor_info.oldest_block_slot).as_usize();
+        let remaining = anchor_info.oldest_block_slot.as_usize();
+
+        let new_state = SyncState::BackFillSyncing {
+            completed,
+            remaining,
+        };
+
+        if *sync_state != new_state {
+            info!(
+                self.log,
+                "Sync state updated";
+                "old_state" => format!("{}", sync_state),
+                "new_state" => format!("{}", new_state),
+            );
+        }
+        *sync_state = new_state;
+    }
+
+    /// Marks the back-fill as complete in the global sync state.
+    fn set_complete(&mut self) {
+        let mut sync_state = self.network_globals.sync_state.write();
+
+        if let SyncState::BackFillSyncing { .. } = *sync_state {
+            info!(self.log, "Historical block download complete");
+            *sync_state = SyncState::Synced;
+        }
+
+        self.current_batch = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn failed_peers_expire() {
+        let mut peers = BackFillPeers::default();
+        let peer_id = PeerId::random();
+        let now = Instant::now();
+
+        peers.mark_failed(peer_id.clone(), now);
+        peers.expire_failed(now + FAILED_PEER_TIMEOUT / 2);
+        assert!(peers.is_failed(&peer_id));
+
+        peers.expire_failed(now + FAILED_PEER_TIMEOUT);
+        assert!(!peers.is_failed(&peer_id));
+    }
+
+    #[test]
+    fn empty_batch_disproven() {
+        let mut peers = BackFillPeers::default();
+        let honest = PeerId::random();
+        let withholding = PeerId::random();
+
+        // An empty batch claims that slots 64..128 were skipped.
+        assert!(peers
+            .batch_imported(withholding.clone(), Slot::new(64), Slot::new(128), None)
+            .is_empty());
+        assert!(peers.has_unverified_claims());
+
+        // The next batch connects with a block at slot 40, verifying the claim.
+        assert!(peers
+            .batch_imported(
+                honest.clone(),
+                Slot::new(0),
+                Slot::new(64),
+                Some((Slot::new(1), Slot::new(40)))
+            )
+            .is_empty());
+
+        // The slot 0 block is missing from the honest batch, so that is also a claim. After
+        // restarting from the oldest block, the connecting block is at slot 100, which the
+        // withholding peer claimed was skipped.
+        let mut peers = BackFillPeers::default();
+        peers.batch_imported(withholding.clone(), Slot::new(64), Slot::new(128), None);
+        assert_eq!(
+            peers.batch_imported(
+                honest,
+                Slot::new(64),
+                Slot::new(128),
+                Some((Slot::new(65), Slot::new(100)))
+            ),
+            vec![withholding]
+        );
+    }
+
+    #[test]
+    fn short_batch_disproven() {
+        let mut peers = BackFillPeers::default();
+        let honest = PeerId::random();
+        let withholding = PeerId::random();
+
+        // The batch omits the blocks in slots 64..120.
+        assert!(peers
+            .batch_imported(
+                withholding.clone(),
+                Slot::new(64),
+                Slot::new(128),
+                Some((Slot::new(120), Slot::new(127)))
+            )
+            .is_empty());
+
+        // A block at slot 90 connects to the oldest block at slot 120.
+        assert_eq!(
+            peers.batch_imported(
+                honest.clone(),
+                Slot::new(64),
+                Slot::new(120),
+                Some((Slot::new(64), Slot::new(90)))
+            ),
+            vec![withholding]
+        );
+        assert!(!peers.has_unverified_claims());
+
+        // A batch starting at its first slot makes no claim.
+        assert!(peers
+            .batch_imported(
+                honest,
+                Slot::new(0),
+                Slot::new(64),
+                Some((Slot::new(0), Slot::new(63)))
+            )
+            .is_empty());
+        assert!(!peers.has_unverified_claims());
+    }
+}
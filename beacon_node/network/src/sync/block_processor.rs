@@ -15,6 +15,8 @@ pub enum ProcessId {
     RangeBatchId(ChainId, BatchId),
     /// Processing Id of the parent lookup of a block
     ParentLookup(PeerId),
+    /// Processing Id of a batch of historical blocks being back-filled.
+    BackFillBatch,
 }
 
 /// The result of a block processing request.
@@ -29,7 +31,8 @@ pub enum BatchProcessResult {
     Partial,
 }
 
-/// Spawns a thread handling the block processing of a request: range syncing or parent lookup.
+/// Spawns a thread handling the block processing of a request: range syncing, parent lookup or
+/// historical block back-filling.
 pub fn spawn_block_processor<T: BeaconChainTypes>(
     chain: Weak<BeaconChain<T>>,
     process_id: ProcessId,
@@ -110,6 +113,19 @@ pub fn spawn_block_processor<T: BeaconChainTypes>(
                     }
                 }
             }
+            // this is a batch of historical blocks from the back-fill sync
+            ProcessId::BackFillBatch => {
+                let result = process_historical_blocks(chain, &downloaded_blocks, &log);
+
+                sync_send
+                    .send(SyncMessage::BackFillBatchProcessed { result })
+                    .unwrap_or_else(|_| {
+                        debug!(
+                            log,
+                            "Block processor could not inform back-fill sync result. Likely shutting down."
+                        );
+                    });
+            }
         }
     });
 }
@@ -159,6 +175,36 @@ fn process_blocks<
     (0, Ok(()))
 }
 
+/// Helper function to import a batch of historical blocks, which are verified and stored without
+/// being applied to any state.
+fn process_historical_blocks<T: BeaconChainTypes>(
+    chain: Weak<BeaconChain<T>>,
+    downloaded_blocks: &[SignedBeaconBlock<T::EthSpec>],
+    log: &slog::Logger,
+) -> BatchProcessResult {
+    let chain = match chain.upgrade() {
+        Some(chain) => chain,
+        None => return BatchProcessResult::Failed,
+    };
+
+    match chain.import_historical_block_batch(downloaded_blocks) {
+        Ok(imported_blocks) => {
+            debug!(
+                log, "Imported historical blocks";
+                "count" => imported_blocks,
+            );
+            BatchProcessResult::Success
+        }
+        Err(e) => {
+            warn!(
+                log, "Historical block batch failed";
+                "error" => format!("{:?}", e),
+            );
+            BatchProcessResult::Failed
+        }
+    }
+}
+
 /// Runs fork-choice on a given chain. This is used during block processing after one successful
 /// block import.
 fn run_fork_choice<T: BeaconChainTypes>(chain: Arc<BeaconChain<T>>, log: &slog::Logger) {
@@ -27,12 +27,18 @@
 //! fully sync'd peers. If `PARENT_FAIL_TOLERANCE` attempts at requesting the block fails, we
 //! drop the propagated block and downvote the peer that sent it to us.
 //!
+//! ## Back-fill Sync
+//!
+//! A node started from a weak subjectivity checkpoint downloads the blocks prior to the checkpoint
+//! once it is synced. See `BackFillSync` for further details.
+//!
 //! Block Lookup
 //!
 //! To keep the logic maintained to the syncing thread (and manage the request_ids), when a block needs to be searched for (i.e
 //! if an attestation references an unknown block) this manager can search for the block and
 //! subsequently search for parents if needed.
 
+use super::backfill_sync::BackFillSync;
 use super::block_processor::{spawn_block_processor, BatchProcessResult, ProcessId};
 use super::network_context::SyncNetworkContext;
 use super::peer_sync_info::{PeerSyncInfo, PeerSyncType};
@@ -106,6 +112,9 @@ pub enum SyncMessage<T: EthSpec> {
 
     /// A parent lookup has failed for a block given by this `peer_id`.
     ParentLookupFailed(PeerId),
+
+    /// A batch of historical blocks has been processed by the block processor thread.
+    BackFillBatchProcessed { result: BatchProcessResult },
 }
 
 /// Maintains a sequential list of parents to lookup and the lookup's current state.
@@ -145,6 +154,9 @@ pub struct SyncManager<T: BeaconChainTypes> {
     /// The object handling long-range batch load-balanced syncing.
     range_sync: RangeSync<T>,
 
+    /// The object handling the download of blocks prior to a weak subjectivity checkpoint.
+    backfill_sync: BackFillSync<T>,
+
     /// A collection of parent block lookups.
     parent_queue: SmallVec<[ParentRequests<T::EthSpec>; 3]>,
 
@@ -199,6 +211,12 @@ pub fn spawn<T: BeaconChainTypes>(
             sync_send.clone(),
             log.clone(),
         ),
+        backfill_sync: BackFillSync::new(
+            beacon_chain.clone(),
+            network_globals.clone(),
+            sync_send.clone(),
+            log.clone(),
+        ),
         network: SyncNetworkContext::new(network_send, network_globals.clone(), log.clone()),
         chain: beacon_chain,
         network_globals,
@@ -527,6 +545,11 @@ impl<T: BeaconChainTypes> SyncManager<T> {
             return;
         }
 
+        if self.backfill_sync.is_backfill_request(request_id) {
+            self.backfill_sync.inject_error(peer_id, request_id);
+            return;
+        }
+
         // otherwise, this is a range sync issue, notify the range sync
         self.range_sync
             .inject_error(&mut self.network, peer_id.clone(), request_id);
@@ -534,6 +557,7 @@ impl<T: BeaconChainTypes> SyncManager<T> {
 
     fn peer_disconnect(&mut self, peer_id: &PeerId) {
         self.range_sync.peer_disconnect(&mut self.network, peer_id);
+        self.backfill_sync.peer_disconnect(peer_id);
         self.update_sync_state();
     }
 
@@ -743,12 +767,21 @@ impl<T: BeaconChainTypes> SyncManager<T> {
                         request_id,
                         beacon_block,
                     } => {
-                        self.range_sync.blocks_by_range_response(
-                            &mut self.network,
-                            peer_id,
-                            request_id,
-                            beacon_block.map(|b| *b),
-                        );
+                        if self.backfill_sync.is_backfill_request(request_id) {
+                            self.backfill_sync.blocks_by_range_response(
+                                &mut self.network,
+                                peer_id,
+                                request_id,
+                                beacon_block.map(|b| *b),
+                            );
+                        } else {
+                            self.range_sync.blocks_by_range_response(
+                                &mut self.network,
+                                peer_id,
+                                request_id,
+                                beacon_block.map(|b| *b),
+                            );
+                        }
                     }
                     SyncMessage::BlocksByRootResponse {
                         peer_id,
@@ -786,7 +819,14 @@ impl<T: BeaconChainTypes> SyncManager<T> {
                     SyncMessage::ParentLookupFailed(peer_id) => {
                         self.network.downvote_peer(peer_id);
                     }
+                    SyncMessage::BackFillBatchProcessed { result } => {
+                        self.backfill_sync
+                            .handle_block_process_result(&mut self.network, result);
+                    }
                 }
+
+                // Back-filling proceeds whenever the node is synced and has no batch in progress.
+                self.backfill_sync.resume(&mut self.network);
             }
         }
     }
@@ -1,6 +1,7 @@
 //! Syncing for lighthouse.
 //!
 //! Stores the various syncing methods for the beacon chain.
+mod backfill_sync;
 mod block_processor;
 pub mod manager;
 mod network_context;
@@ -116,14 +116,7 @@ impl<T: BeaconChainTypes> ChainCollection<T> {
 
         if self.state == RangeSyncState::Idle {
             // there is no range sync, let the state of peers determine the global node sync state
-            let new_state = self
-                .network_globals
-                .peers
-                .read()
-                .synced_peers()
-                .next()
-                .map(|_| SyncState::Synced)
-                .unwrap_or_else(|| SyncState::Stalled);
+            let new_state = self.network_globals.peer_sync_state();
             let mut peer_state = self.network_globals.sync_state.write();
             if new_state != *peer_state {
                 info!(self.log, "Sync state updated"; "old_state" => format!("{}",peer_state), "new_state" => format!("{}",new_state));
@@ -9,15 +9,12 @@ use attestation::AttMaxCover;
 use attestation_id::AttestationId;
 use max_cover::maximum_cover;
 use parking_lot::RwLock;
-use state_processing::per_block_processing::errors::{
-    AttestationValidationError, AttesterSlashingValidationError, ExitValidationError,
-    ProposerSlashingValidationError,
-};
+use state_processing::per_block_processing::errors::AttestationValidationError;
 use state_processing::per_block_processing::{
-    get_slashable_indices_modular, verify_attestation_for_block_inclusion,
-    verify_attester_slashing, verify_exit, verify_exit_time_independent_only,
-    verify_proposer_slashing, VerifySignatures,
+    get_slashable_indices_modular, verify_attestation_for_block_inclusion, verify_exit,
+    VerifySignatures,
 };
+use state_processing::SigVerifiedOp;
 use std::collections::{hash_map, HashMap, HashSet};
 use std::marker::PhantomData;
 use std::ptr;
@@ -175,17 +172,12 @@ impl<T: EthSpec> OperationPool<T> {
     /// Insert a proposer slashing into the pool.
     pub fn insert_proposer_slashing(
         &self,
-        slashing: ProposerSlashing,
-        state: &BeaconState<T>,
-        spec: &ChainSpec,
-    ) -> Result<(), ProposerSlashingValidationError> {
-        // TODO: should maybe insert anyway if the proposer is unknown in the validator index,
-        // because they could *become* known later
-        verify_proposer_slashing(&slashing, state, VerifySignatures::True, spec)?;
+        verified_proposer_slashing: SigVerifiedOp<ProposerSlashing>,
+    ) {
+        let slashing = verified_proposer_slashing.into_inner();
         self.proposer_slashings
             .write()
             .insert(slashing.signed_header_1.message.proposer_index, slashing);
-        Ok(())
     }
 
     /// Compute the tuple ID that is used to identify an attester slashing.
@@ -193,20 +185,21 @@ impl<T: EthSpec> OperationPool<T> {
     /// Depends on the fork field of the state, but not on the state's epoch.
     fn attester_slashing_id(
         slashing: &AttesterSlashing<T>,
-        state: &BeaconState<T>,
+        fork: &Fork,
+        genesis_validators_root: Hash256,
         spec: &ChainSpec,
     ) -> (AttestationId, AttestationId) {
         (
             AttestationId::from_data(
                 &slashing.attestation_1.data,
-                &state.fork,
-                state.genesis_validators_root,
+                fork,
+                genesis_validators_root,
                 spec,
             ),
             AttestationId::from_data(
                 &slashing.attestation_2.data,
-                &state.fork,
-                state.genesis_validators_root,
+                fork,
+                genesis_validators_root,
                 spec,
             ),
         )
@@ -215,14 +208,14 @@ impl<T: EthSpec> OperationPool<T> {
     /// Insert an attester slashing into the pool.
     pub fn insert_attester_slashing(
         &self,
-        slashing: AttesterSlashing<T>,
-        state: &BeaconState<T>,
+        verified_slashing: SigVerifiedOp<AttesterSlashing<T>>,
+        fork: &Fork,
+        genesis_validators_root: Hash256,
         spec: &ChainSpec,
-    ) -> Result<(), AttesterSlashingValidationError> {
-        verify_attester_slashing(state, &slashing, VerifySignatures::True, spec)?;
-        let id = Self::attester_slashing_id(&slashing, state, spec);
+    ) {
+        let slashing = verified_slashing.into_inner();
+        let id = Self::attester_slashing_id(&slashing, fork, genesis_validators_root, spec);
         self.attester_slashings.write().insert(id, slashing);
-        Ok(())
     }
 
     /// Get proposer and attester slashings for inclusion in a block.
@@ -260,7 +253,12 @@ impl<T: EthSpec> OperationPool<T> {
             .iter()
             .filter(|(id, slashing)| {
                 // Check the fork.
-                Self::attester_slashing_id(slashing, state, spec) == **id
+                Self::attester_slashing_id(
+                    slashing,
+                    &state.fork,
+                    state.genesis_validators_root,
+                    spec,
+                ) == **id
             })
             .filter(|(_, slashing)| {
                 // Take all slashings that will slash 1 or more validators.
@@ -300,7 +298,12 @@ impl<T: EthSpec> OperationPool<T> {
     /// fork.
     pub fn prune_attester_slashings(&self, finalized_state: &BeaconState<T>, spec: &ChainSpec) {
         self.attester_slashings.write().retain(|id, slashing| {
-            let fork_ok = &Self::attester_slashing_id(slashing, finalized_state, spec) == id;
+            let fork_ok = &Self::attester_slashing_id(
+                slashing,
+                &finalized_state.fork,
+                finalized_state.genesis_validators_root,
+                spec,
+            ) == id;
             let curr_epoch = finalized_state.current_epoch();
             let slashing_ok =
                 get_slashable_indices_modular(finalized_state, slashing, |_, validator| {
@@ -321,18 +324,12 @@ impl<T: EthSpec> OperationPool<T> {
         self.proposer_slashings.read().len()
     }
 
-    /// Insert a voluntary exit, validating it almost-entirely (future exits are permitted).
-    pub fn insert_voluntary_exit(
-        &self,
-        exit: SignedVoluntaryExit,
-        state: &BeaconState<T>,
-        spec: &ChainSpec,
-    ) -> Result<(), ExitValidationError> {
-        verify_exit_time_independent_only(state, &exit, VerifySignatures::True, spec)?;
+    /// Insert a voluntary exit that has previously been checked elsewhere.
+    pub fn insert_voluntary_exit(&self, verified_exit: SigVerifiedOp<SignedVoluntaryExit>) {
+        let exit = verified_exit.into_inner();
         self.voluntary_exits
             .write()
             .insert(exit.message.validator_index, exit);
-        Ok(())
     }
 
     /// Get a list of voluntary exits for inclusion in a block.
@@ -369,6 +366,30 @@ impl<T: EthSpec> OperationPool<T> {
     pub fn num_voluntary_exits(&self) -> usize {
         self.voluntary_exits.read().len()
     }
+
+    /// Returns all known `Attestation` objects, without any filtering.
+    pub fn get_all_attestations(&self) -> Vec<Attestation<T>> {
+        self.attestations
+            .read()
+            .values()
+            .flat_map(|attestations| attestations.iter().cloned())
+            .collect()
+    }
+
+    /// Returns all known `AttesterSlashing` objects, without any filtering.
+    pub fn get_all_attester_slashings(&self) -> Vec<AttesterSlashing<T>> {
+        self.attester_slashings.read().values().cloned().collect()
+    }
+
+    /// Returns all known `ProposerSlashing` objects, without any filtering.
+    pub fn get_all_proposer_slashings(&self) -> Vec<ProposerSlashing> {
+        self.proposer_slashings.read().values().cloned().collect()
+    }
+
+    /// Returns all known `SignedVoluntaryExit` objects, without any filtering.
+    pub fn get_all_voluntary_exits(&self) -> Vec<SignedVoluntaryExit> {
+        self.voluntary_exits.read().values().cloned().collect()
+    }
 }
 
 /// Filter up to a maximum number of operations out of an iterator.
@@ -425,6 +446,7 @@ mod release_tests {
     use super::attestation::earliest_attestation_validators;
     use super::*;
     use state_processing::common::{get_attesting_indices, get_base_reward};
+    use state_processing::VerifyOperation;
     use std::collections::BTreeSet;
     use std::iter::FromIterator;
     use types::test_utils::*;
@@ -923,13 +945,9 @@ mod release_tests {
             signed_header_2: slashing1.signed_header_1.clone(),
         };
 
-        // Both slashings should be accepted by the pool.
-        op_pool
-            .insert_proposer_slashing(slashing1.clone(), &state, &spec)
-            .unwrap();
-        op_pool
-            .insert_proposer_slashing(slashing2.clone(), &state, &spec)
-            .unwrap();
+        // Both slashings should be valid and accepted by the pool.
+        op_pool.insert_proposer_slashing(slashing1.clone().validate(&state, &spec).unwrap());
+        op_pool.insert_proposer_slashing(slashing2.clone().validate(&state, &spec).unwrap());
 
         // Should only get the second slashing back.
         assert_eq!(op_pool.get_slashings(&state, &spec).0, vec![slashing2]);
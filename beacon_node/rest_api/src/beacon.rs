@@ -1,12 +1,15 @@
 use crate::helpers::*;
 use crate::response_builder::ResponseBuilder;
 use crate::validator::get_state_for_epoch;
-use crate::{ApiError, ApiResult, UrlQuery};
-use beacon_chain::{BeaconChain, BeaconChainTypes, StateSkipConfig};
-use bus::BusReader;
-use futures::executor::block_on;
+use crate::{events, ApiError, ApiResult, NetworkChannel, UrlQuery};
+use beacon_chain::{
+    events::{EventKind, EventSubscribers, EventType},
+    observed_operations::ObservationOutcome,
+    BeaconChain, BeaconChainTypes, StateSkipConfig,
+};
+use environment::TaskExecutor;
 use hyper::body::Bytes;
-use hyper::{Body, Request, Response};
+use hyper::{Body, Request};
 use rest_types::{
     BlockResponse, CanonicalHeadResponse, Committee, HeadBeaconBlock, StateResponse,
     ValidatorRequest, ValidatorResponse,
@@ -15,10 +18,9 @@ use std::io::Write;
 use std::sync::Arc;
 use store::Store;
 
-use slog::{error, Logger};
 use types::{
     AttesterSlashing, BeaconState, EthSpec, Hash256, ProposerSlashing, PublicKeyBytes,
-    RelativeEpoch, SignedBeaconBlockHash, Slot,
+    RelativeEpoch, SignedVoluntaryExit, Slot,
 };
 
 /// HTTP handler to return a `BeaconBlock` at a given `root` or `slot`.
@@ -128,46 +130,30 @@ pub fn get_block_root<T: BeaconChainTypes>(
     ResponseBuilder::new(&req)?.body(&root)
 }
 
-fn make_sse_response_chunk(new_head_hash: SignedBeaconBlockHash) -> std::io::Result<Bytes> {
+fn make_sse_response_chunk(new_head_hash: Hash256) -> std::io::Result<Bytes> {
     let mut buffer = Vec::new();
     {
         let mut sse_message = uhttp_sse::SseMessage::new(&mut buffer);
-        let untyped_hash: Hash256 = new_head_hash.into();
-        write!(sse_message.data()?, "{:?}", untyped_hash)?;
+        write!(sse_message.data()?, "{:?}", new_head_hash)?;
     }
     let bytes: Bytes = buffer.into();
     Ok(bytes)
 }
 
+/// HTTP handler to stream the block root of each new head as a server-sent event.
 pub fn stream_forks<T: BeaconChainTypes>(
-    log: Logger,
-    mut events: BusReader<SignedBeaconBlockHash>,
+    executor: TaskExecutor,
+    subscribers: Arc<EventSubscribers<T::EthSpec>>,
 ) -> ApiResult {
-    let (mut sender, body) = Body::channel();
-    std::thread::spawn(move || {
-        while let Ok(new_head_hash) = events.recv() {
-            let chunk = match make_sse_response_chunk(new_head_hash) {
-                Ok(chunk) => chunk,
-                Err(e) => {
-                    error!(log, "Failed to make SSE chunk"; "error" => e.to_string());
-                    sender.abort();
-                    break;
-                }
-            };
-            if let Err(bytes) = block_on(sender.send_data(chunk)) {
-                error!(log, "Couldn't stream piece {:?}", bytes);
-            }
-        }
-    });
-    let response = Response::builder()
-        .status(200)
-        .header("Content-Type", "text/event-stream")
-        .header("Connection", "Keep-Alive")
-        .header("Cache-Control", "no-cache")
-        .header("Access-Control-Allow-Origin", "*")
-        .body(body)
-        .map_err(|e| ApiError::ServerError(format!("Failed to build response: {:?}", e)))?;
-    Ok(response)
+    let receiver = events::subscribe(&subscribers, vec![EventType::BeaconHeadChanged])?;
+
+    events::stream_sse(executor, receiver, |kind| match kind {
+        EventKind::BeaconHeadChanged {
+            current_head_beacon_block_root,
+            ..
+        } => make_sse_response_chunk(current_head_beacon_block_root).map(Some),
+        _ => Ok(None),
+    })
 }
 
 /// HTTP handler to return the `Fork` of the current head.
@@ -297,14 +283,9 @@ fn get_state_from_root_opt<T: BeaconChainTypes>(
     state_root_opt: Option<Hash256>,
 ) -> Result<BeaconState<T::EthSpec>, ApiError> {
     if let Some(state_root) = state_root_opt {
+        // A pruned state is reported as `NotFound` by the `BeaconChainError` conversion.
         beacon_chain
-            .get_state(&state_root, None)
-            .map_err(|e| {
-                ApiError::ServerError(format!(
-                    "Database error when reading state root {}: {:?}",
-                    state_root, e
-                ))
-            })?
+            .get_state(&state_root, None)?
             .ok_or_else(|| ApiError::NotFound(format!("No state exists with root: {}", state_root)))
     } else {
         Ok(beacon_chain.head()?.beacon_state)
@@ -468,6 +449,8 @@ pub fn get_state_root<T: BeaconChainTypes>(
 ///
 /// This is an undocumented convenience method used during testing. For production, simply do a
 /// state request at slot 0.
+///
+/// Returns `NotFound` if the genesis state has been pruned, e.g. under `--no-history`.
 pub fn get_genesis_state<T: BeaconChainTypes>(
     req: Request<Body>,
     beacon_chain: Arc<BeaconChain<T>>,
@@ -512,22 +495,28 @@ pub async fn proposer_slashing<T: BeaconChainTypes>(
             ))
         })
         .and_then(move |proposer_slashing| {
-            let spec = &beacon_chain.spec;
-            let state = &beacon_chain.head().unwrap().beacon_state;
             if beacon_chain.eth1_chain.is_some() {
-                beacon_chain
-                    .op_pool
-                    .insert_proposer_slashing(proposer_slashing, state, spec)
+                let obs_outcome = beacon_chain
+                    .verify_proposer_slashing_for_gossip(proposer_slashing)
                     .map_err(|e| {
                         ApiError::BadRequest(format!(
-                            "Error while inserting proposer slashing: {:?}",
+                            "Error while verifying proposer slashing: {:?}",
                             e
                         ))
-                    })
+                    })?;
+                match obs_outcome {
+                    ObservationOutcome::New(verified_proposer_slashing) => {
+                        beacon_chain.import_proposer_slashing(verified_proposer_slashing);
+                        Ok(())
+                    }
+                    ObservationOutcome::AlreadyKnown => Err(ApiError::BadRequest(
+                        "Proposer slashing for this validator is already known".to_string(),
+                    )),
+                }
             } else {
-                return Err(ApiError::BadRequest(
+                Err(ApiError::BadRequest(
                     "Cannot insert proposer slashing on node without Eth1 connection.".to_string(),
-                ));
+                ))
             }
         })
         .and_then(|_| response_builder?.body(&true))
@@ -552,18 +541,28 @@ pub async fn attester_slashing<T: BeaconChainTypes>(
             ))
         })
         .and_then(move |attester_slashing| {
-            let spec = &beacon_chain.spec;
-            let state = &beacon_chain.head().unwrap().beacon_state;
             if beacon_chain.eth1_chain.is_some() {
-                beacon_chain
-                    .op_pool
-                    .insert_attester_slashing(attester_slashing, state, spec)
+                let obs_outcome = beacon_chain
+                    .verify_attester_slashing_for_gossip(attester_slashing)
                     .map_err(|e| {
                         ApiError::BadRequest(format!(
-                            "Error while inserting attester slashing: {:?}",
+                            "Error while verifying attester slashing: {:?}",
                             e
                         ))
-                    })
+                    })?;
+                match obs_outcome {
+                    ObservationOutcome::New(verified_attester_slashing) => beacon_chain
+                        .import_attester_slashing(verified_attester_slashing)
+                        .map_err(|e| {
+                            ApiError::ServerError(format!(
+                                "Error while importing attester slashing: {:?}",
+                                e
+                            ))
+                        }),
+                    ObservationOutcome::AlreadyKnown => Err(ApiError::BadRequest(
+                        "Attester slashing for these validators is already known".to_string(),
+                    )),
+                }
             } else {
                 Err(ApiError::BadRequest(
                     "Cannot insert attester slashing on node without Eth1 connection.".to_string(),
@@ -572,3 +571,44 @@ pub async fn attester_slashing<T: BeaconChainTypes>(
         })
         .and_then(|_| response_builder?.body(&true))
 }
+
+/// HTTP handler to verify a `SignedVoluntaryExit`, add it to the operation pool and publish it
+/// to the network.
+pub async fn voluntary_exit<T: BeaconChainTypes>(
+    req: Request<Body>,
+    beacon_chain: Arc<BeaconChain<T>>,
+    network_chan: NetworkChannel<T::EthSpec>,
+) -> ApiResult {
+    let response_builder = ResponseBuilder::new(&req);
+
+    let body = req.into_body();
+    let chunks = hyper::body::to_bytes(body)
+        .await
+        .map_err(|e| ApiError::ServerError(format!("Unable to get request body: {:?}", e)))?;
+
+    serde_json::from_slice::<SignedVoluntaryExit>(&chunks)
+        .map_err(|e| {
+            ApiError::BadRequest(format!(
+                "Unable to parse JSON into SignedVoluntaryExit: {:?}",
+                e
+            ))
+        })
+        .and_then(move |exit| {
+            let obs_outcome = beacon_chain
+                .verify_voluntary_exit_for_gossip(exit.clone())
+                .map_err(|e| {
+                    ApiError::BadRequest(format!("Error while verifying voluntary exit: {:?}", e))
+                })?;
+            match obs_outcome {
+                ObservationOutcome::New(verified_exit) => {
+                    publish_voluntary_exit_to_network::<T>(network_chan, exit)?;
+                    beacon_chain.import_voluntary_exit(verified_exit);
+                    Ok(())
+                }
+                ObservationOutcome::AlreadyKnown => Err(ApiError::BadRequest(
+                    "Voluntary exit for this validator is already known".to_string(),
+                )),
+            }
+        })
+        .and_then(|_| response_builder?.body(&true))
+}
@@ -1,4 +1,5 @@
 use hyper::{Body, Response, StatusCode};
+use rest_types::eth_v1::ErrorMessage;
 use std::error::Error as StdError;
 
 #[derive(PartialEq, Debug, Clone)]
@@ -8,6 +9,7 @@ pub enum ApiError {
     NotImplemented(String),
     BadRequest(String),
     NotFound(String),
+    ServiceUnavailable(String),
     UnsupportedType(String),
     ImATeapot(String),       // Just in case.
     ProcessingError(String), // A 202 error, for when a block/attestation cannot be processed, but still transmitted.
@@ -23,6 +25,7 @@ impl ApiError {
             ApiError::NotImplemented(desc) => (StatusCode::NOT_IMPLEMENTED, desc),
             ApiError::BadRequest(desc) => (StatusCode::BAD_REQUEST, desc),
             ApiError::NotFound(desc) => (StatusCode::NOT_FOUND, desc),
+            ApiError::ServiceUnavailable(desc) => (StatusCode::SERVICE_UNAVAILABLE, desc),
             ApiError::UnsupportedType(desc) => (StatusCode::UNSUPPORTED_MEDIA_TYPE, desc),
             ApiError::ImATeapot(desc) => (StatusCode::IM_A_TEAPOT, desc),
             ApiError::ProcessingError(desc) => (StatusCode::ACCEPTED, desc),
@@ -41,9 +44,34 @@ impl Into<Response<Body>> for ApiError {
     }
 }
 
+impl ApiError {
+    /// Converts `self` into a response with a JSON `ErrorMessage` body, as used by the standard
+    /// `/eth/v1` API.
+    pub fn into_error_message_response(self) -> Response<Body> {
+        let (status_code, message) = self.status_code();
+        let body = serde_json::to_string(&ErrorMessage {
+            code: status_code.as_u16(),
+            message,
+        })
+        .expect("ErrorMessage should always serialize");
+
+        Response::builder()
+            .status(status_code)
+            .header("content-type", "application/json")
+            .body(Body::from(body))
+            .expect("Response should always be created.")
+    }
+}
+
 impl From<store::Error> for ApiError {
     fn from(e: store::Error) -> ApiError {
-        ApiError::ServerError(format!("Database error: {:?}", e))
+        match e {
+            store::Error::StatePruned { slot } => ApiError::NotFound(format!(
+                "State pruned: the state at slot {} is not retained by this node",
+                slot
+            )),
+            e => ApiError::ServerError(format!("Database error: {:?}", e)),
+        }
     }
 }
 
@@ -55,7 +83,12 @@ impl From<types::BeaconStateError> for ApiError {
 
 impl From<beacon_chain::BeaconChainError> for ApiError {
     fn from(e: beacon_chain::BeaconChainError) -> ApiError {
-        ApiError::ServerError(format!("BeaconChainError error: {:?}", e))
+        match e {
+            beacon_chain::BeaconChainError::DBError(e @ store::Error::StatePruned { .. }) => {
+                e.into()
+            }
+            e => ApiError::ServerError(format!("BeaconChainError error: {:?}", e)),
+        }
     }
 }
 
@@ -0,0 +1,496 @@
+use super::block_id::{self, parse_block_id};
+use super::state_id::{self, parse_state_id};
+use super::{invalid_object_to_bad_request, parse_json_body, respond};
+use crate::helpers::{
+    block_root_at_slot, parse_committee_index, parse_epoch, parse_slot,
+    publish_attester_slashing_to_network, publish_proposer_slashing_to_network,
+    publish_voluntary_exit_to_network,
+};
+use crate::response_builder::ResponseBuilder;
+use crate::validator::{process_and_publish_block, process_unaggregated_attestation};
+use crate::{ApiError, ApiResult, NetworkChannel, UrlQuery};
+use beacon_chain::{observed_operations::ObservationOutcome, BeaconChain, BeaconChainTypes};
+use hyper::{Body, Request};
+use rest_types::eth_v1::{
+    BlockHeaderAndSignature, BlockHeaderData, CommitteeData, FinalityCheckpointsData, GenesisData,
+    RootData, ValidatorData, ValidatorId, ValidatorStatus,
+};
+use slog::Logger;
+use std::sync::Arc;
+use types::{
+    Attestation, AttesterSlashing, BeaconState, ChainSpec, EthSpec, Hash256, ProposerSlashing,
+    RelativeEpoch, SignedBeaconBlock, SignedVoluntaryExit,
+};
+
+/// HTTP handler to return the genesis time, validators root and fork version.
+pub fn get_genesis<T: BeaconChainTypes>(
+    req: Request<Body>,
+    beacon_chain: Arc<BeaconChain<T>>,
+) -> ApiResult {
+    let head_info = beacon_chain.head_info()?;
+
+    respond(
+        &req,
+        GenesisData {
+            genesis_time: head_info.genesis_time,
+            genesis_validators_root: head_info.genesis_validators_root,
+            genesis_fork_version: beacon_chain.spec.genesis_fork_version,
+        },
+    )
+}
+
+/// HTTP handler to return the root of the state identified by `state_id`.
+pub fn get_state_root<T: BeaconChainTypes>(
+    req: Request<Body>,
+    beacon_chain: Arc<BeaconChain<T>>,
+    state_id: &str,
+) -> ApiResult {
+    let root = state_id::state_root(&beacon_chain, parse_state_id(state_id)?)?;
+
+    respond(&req, RootData::from(root))
+}
+
+/// HTTP handler to return the `Fork` of the state identified by `state_id`.
+pub fn get_state_fork<T: BeaconChainTypes>(
+    req: Request<Body>,
+    beacon_chain: Arc<BeaconChain<T>>,
+    state_id: &str,
+) -> ApiResult {
+    let state = state_id::state(&beacon_chain, parse_state_id(state_id)?)?;
+
+    respond(&req, state.fork)
+}
+
+/// HTTP handler to return the justified and finalized checkpoints of the state identified by
+/// `state_id`.
+pub fn get_state_finality_checkpoints<T: BeaconChainTypes>(
+    req: Request<Body>,
+    beacon_chain: Arc<BeaconChain<T>>,
+    state_id: &str,
+) -> ApiResult {
+    let state = state_id::state(&beacon_chain, parse_state_id(state_id)?)?;
+
+    respond(
+        &req,
+        FinalityCheckpointsData {
+            previous_justified: state.previous_justified_checkpoint.into(),
+            current_justified: state.current_justified_checkpoint.into(),
+            finalized: state.finalized_checkpoint.into(),
+        },
+    )
+}
+
+/// HTTP handler to return the validators of the state identified by `state_id`.
+///
+/// The validators may be filtered by supplying one or more `id` query parameters, each of which
+/// is a validator index or public key. Unknown validators are omitted from the response.
+pub fn get_state_validators<T: BeaconChainTypes>(
+    req: Request<Body>,
+    beacon_chain: Arc<BeaconChain<T>>,
+    state_id: &str,
+) -> ApiResult {
+    let validator_ids = UrlQuery::from_request(&req)?
+        .all_of("id")?
+        .iter()
+        .flat_map(|ids| ids.split(','))
+        .map(parse_validator_id)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut state = state_id::state(&beacon_chain, parse_state_id(state_id)?)?;
+
+    let validator_indices: Vec<usize> = if validator_ids.is_empty() {
+        (0..state.validators.len()).collect()
+    } else {
+        state.update_pubkey_cache()?;
+        validator_ids
+            .iter()
+            .filter_map(|validator_id| validator_index(&state, validator_id).transpose())
+            .collect::<Result<Vec<_>, _>>()?
+    };
+
+    let validators = validator_indices
+        .into_iter()
+        .map(|index| validator_data(&state, index, &beacon_chain.spec))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    respond(&req, validators)
+}
+
+/// HTTP handler to return a single validator of the state identified by `state_id`.
+pub fn get_state_validator<T: BeaconChainTypes>(
+    req: Request<Body>,
+    beacon_chain: Arc<BeaconChain<T>>,
+    state_id: &str,
+    validator_id: &str,
+) -> ApiResult {
+    let validator_id = parse_validator_id(validator_id)?;
+
+    let mut state = state_id::state(&beacon_chain, parse_state_id(state_id)?)?;
+    state.update_pubkey_cache()?;
+
+    let index = validator_index(&state, &validator_id)?
+        .ok_or_else(|| ApiError::NotFound(format!("Unknown validator: {}", validator_id)))?;
+
+    respond(&req, validator_data(&state, index, &beacon_chain.spec)?)
+}
+
+/// HTTP handler to return the committees of the state identified by `state_id`.
+///
+/// The `epoch` query parameter defaults to the epoch of the state, the committees may be further
+/// filtered with the `index` and `slot` query parameters.
+pub fn get_state_committees<T: BeaconChainTypes>(
+    req: Request<Body>,
+    beacon_chain: Arc<BeaconChain<T>>,
+    state_id: &str,
+) -> ApiResult {
+    let query = UrlQuery::from_request(&req)?;
+    let epoch_opt = query
+        .first_of_opt(&["epoch"])
+        .map(|(_key, value)| parse_epoch(&value))
+        .transpose()?;
+    let index_opt = query
+        .first_of_opt(&["index"])
+        .map(|(_key, value)| parse_committee_index(&value))
+        .transpose()?;
+    let slot_opt = query
+        .first_of_opt(&["slot"])
+        .map(|(_key, value)| parse_slot(&value))
+        .transpose()?;
+
+    let mut state = state_id::state(&beacon_chain, parse_state_id(state_id)?)?;
+    let epoch = epoch_opt.unwrap_or_else(|| state.current_epoch());
+
+    let relative_epoch = RelativeEpoch::from_epoch(state.current_epoch(), epoch).map_err(|_| {
+        ApiError::BadRequest(format!(
+            "Epoch {} is not within one epoch of the state at slot {}",
+            epoch, state.slot
+        ))
+    })?;
+
+    state.build_committee_cache(relative_epoch, &beacon_chain.spec)?;
+
+    let committees = state
+        .get_beacon_committees_at_epoch(relative_epoch)?
+        .into_iter()
+        .filter(|committee| index_opt.map_or(true, |index| committee.index == index))
+        .filter(|committee| slot_opt.map_or(true, |slot| committee.slot == slot))
+        .map(|committee| CommitteeData {
+            index: committee.index,
+            slot: committee.slot,
+            validators: committee.committee.iter().map(|i| *i as u64).collect(),
+        })
+        .collect::<Vec<_>>();
+
+    respond(&req, committees)
+}
+
+/// HTTP handler to return the header of the canonical block at the `slot` query parameter, or
+/// the head block if no slot is supplied.
+pub fn get_headers<T: BeaconChainTypes>(
+    req: Request<Body>,
+    beacon_chain: Arc<BeaconChain<T>>,
+) -> ApiResult {
+    let slot_opt = UrlQuery::from_request(&req)?
+        .first_of_opt(&["slot"])
+        .map(|(_key, value)| parse_slot(&value))
+        .transpose()?;
+
+    let block_root = match slot_opt {
+        Some(slot) => block_root_at_slot(&beacon_chain, slot)?,
+        None => Some(beacon_chain.head_info()?.block_root),
+    };
+
+    let headers = block_root
+        .map(|root| block_header_data(&beacon_chain, root))
+        .transpose()?
+        .into_iter()
+        .collect::<Vec<_>>();
+
+    respond(&req, headers)
+}
+
+/// HTTP handler to return the header of the block identified by `block_id`.
+pub fn get_header<T: BeaconChainTypes>(
+    req: Request<Body>,
+    beacon_chain: Arc<BeaconChain<T>>,
+    block_id: &str,
+) -> ApiResult {
+    let root = block_id::block_root(&beacon_chain, parse_block_id(block_id)?)?;
+
+    respond(&req, block_header_data(&beacon_chain, root)?)
+}
+
+/// HTTP handler to import a `SignedBeaconBlock` and publish it to the network.
+pub async fn post_block<T: BeaconChainTypes>(
+    req: Request<Body>,
+    beacon_chain: Arc<BeaconChain<T>>,
+    network_chan: NetworkChannel<T::EthSpec>,
+    log: Logger,
+) -> ApiResult {
+    let response_builder = ResponseBuilder::new(&req);
+
+    let block: SignedBeaconBlock<T::EthSpec> = parse_json_body(req).await?;
+    process_and_publish_block(&beacon_chain, network_chan, block, &log)
+        .map_err(invalid_object_to_bad_request)?;
+
+    response_builder?.body_no_ssz(&())
+}
+
+/// HTTP handler to return the block identified by `block_id`.
+pub fn get_block<T: BeaconChainTypes>(
+    req: Request<Body>,
+    beacon_chain: Arc<BeaconChain<T>>,
+    block_id: &str,
+) -> ApiResult {
+    let block = block_id::block(&beacon_chain, parse_block_id(block_id)?)?;
+
+    respond(&req, block)
+}
+
+/// HTTP handler to return the root of the block identified by `block_id`.
+pub fn get_block_root<T: BeaconChainTypes>(
+    req: Request<Body>,
+    beacon_chain: Arc<BeaconChain<T>>,
+    block_id: &str,
+) -> ApiResult {
+    let root = block_id::block_root(&beacon_chain, parse_block_id(block_id)?)?;
+
+    respond(&req, RootData::from(root))
+}
+
+/// HTTP handler to return the attestations included in the block identified by `block_id`.
+pub fn get_block_attestations<T: BeaconChainTypes>(
+    req: Request<Body>,
+    beacon_chain: Arc<BeaconChain<T>>,
+    block_id: &str,
+) -> ApiResult {
+    let block = block_id::block(&beacon_chain, parse_block_id(block_id)?)?;
+
+    respond(&req, block.message.body.attestations.to_vec())
+}
+
+/// HTTP handler to return the attestations in the operation pool, optionally filtered by the
+/// `slot` and `committee_index` query parameters.
+pub fn get_pool_attestations<T: BeaconChainTypes>(
+    req: Request<Body>,
+    beacon_chain: Arc<BeaconChain<T>>,
+) -> ApiResult {
+    let query = UrlQuery::from_request(&req)?;
+    let slot_opt = query
+        .first_of_opt(&["slot"])
+        .map(|(_key, value)| parse_slot(&value))
+        .transpose()?;
+    let index_opt = query
+        .first_of_opt(&["committee_index"])
+        .map(|(_key, value)| parse_committee_index(&value))
+        .transpose()?;
+
+    let attestations = beacon_chain
+        .op_pool
+        .get_all_attestations()
+        .into_iter()
+        .filter(|attestation| slot_opt.map_or(true, |slot| attestation.data.slot == slot))
+        .filter(|attestation| index_opt.map_or(true, |index| attestation.data.index == index))
+        .collect::<Vec<_>>();
+
+    respond(&req, attestations)
+}
+
+/// HTTP handler to verify a list of unaggregated `Attestation`, import them and publish them to
+/// the network.
+pub async fn post_pool_attestations<T: BeaconChainTypes>(
+    req: Request<Body>,
+    beacon_chain: Arc<BeaconChain<T>>,
+    network_chan: NetworkChannel<T::EthSpec>,
+    log: Logger,
+) -> ApiResult {
+    let response_builder = ResponseBuilder::new(&req);
+
+    let attestations: Vec<Attestation<T::EthSpec>> = parse_json_body(req).await?;
+    attestations
+        .into_iter()
+        .enumerate()
+        .try_for_each(|(i, attestation)| {
+            process_unaggregated_attestation(
+                &beacon_chain,
+                network_chan.clone(),
+                attestation,
+                i,
+                &log,
+            )
+        })
+        .map_err(invalid_object_to_bad_request)?;
+
+    response_builder?.body_no_ssz(&())
+}
+
+/// HTTP handler to return the attester slashings in the operation pool.
+pub fn get_pool_attester_slashings<T: BeaconChainTypes>(
+    req: Request<Body>,
+    beacon_chain: Arc<BeaconChain<T>>,
+) -> ApiResult {
+    respond(&req, beacon_chain.op_pool.get_all_attester_slashings())
+}
+
+/// HTTP handler to verify an `AttesterSlashing`, add it to the operation pool and publish it to
+/// the network.
+pub async fn post_pool_attester_slashings<T: BeaconChainTypes>(
+    req: Request<Body>,
+    beacon_chain: Arc<BeaconChain<T>>,
+    network_chan: NetworkChannel<T::EthSpec>,
+) -> ApiResult {
+    let response_builder = ResponseBuilder::new(&req);
+
+    let slashing: AttesterSlashing<T::EthSpec> = parse_json_body(req).await?;
+    let outcome = beacon_chain
+        .verify_attester_slashing_for_gossip(slashing.clone())
+        .map_err(|e| {
+            ApiError::BadRequest(format!("Error while verifying attester slashing: {:?}", e))
+        })?;
+
+    if let ObservationOutcome::New(verified_slashing) = outcome {
+        publish_attester_slashing_to_network::<T>(network_chan, slashing)?;
+        beacon_chain
+            .import_attester_slashing(verified_slashing)
+            .map_err(|e| {
+                ApiError::ServerError(format!("Error while importing attester slashing: {:?}", e))
+            })?;
+    }
+
+    response_builder?.body_no_ssz(&())
+}
+
+/// HTTP handler to return the proposer slashings in the operation pool.
+pub fn get_pool_proposer_slashings<T: BeaconChainTypes>(
+    req: Request<Body>,
+    beacon_chain: Arc<BeaconChain<T>>,
+) -> ApiResult {
+    respond(&req, beacon_chain.op_pool.get_all_proposer_slashings())
+}
+
+/// HTTP handler to verify a `ProposerSlashing`, add it to the operation pool and publish it to
+/// the network.
+pub async fn post_pool_proposer_slashings<T: BeaconChainTypes>(
+    req: Request<Body>,
+    beacon_chain: Arc<BeaconChain<T>>,
+    network_chan: NetworkChannel<T::EthSpec>,
+) -> ApiResult {
+    let response_builder = ResponseBuilder::new(&req);
+
+    let slashing: ProposerSlashing = parse_json_body(req).await?;
+    let outcome = beacon_chain
+        .verify_proposer_slashing_for_gossip(slashing.clone())
+        .map_err(|e| {
+            ApiError::BadRequest(format!("Error while verifying proposer slashing: {:?}", e))
+        })?;
+
+    if let ObservationOutcome::New(verified_slashing) = outcome {
+        publish_proposer_slashing_to_network::<T>(network_chan, slashing)?;
+        beacon_chain.import_proposer_slashing(verified_slashing);
+    }
+
+    response_builder?.body_no_ssz(&())
+}
+
+/// HTTP handler to return the voluntary exits in the operation pool.
+pub fn get_pool_voluntary_exits<T: BeaconChainTypes>(
+    req: Request<Body>,
+    beacon_chain: Arc<BeaconChain<T>>,
+) -> ApiResult {
+    respond(&req, beacon_chain.op_pool.get_all_voluntary_exits())
+}
+
+/// HTTP handler to verify a `SignedVoluntaryExit`, add it to the operation pool and publish it
+/// to the network.
+pub async fn post_pool_voluntary_exits<T: BeaconChainTypes>(
+    req: Request<Body>,
+    beacon_chain: Arc<BeaconChain<T>>,
+    network_chan: NetworkChannel<T::EthSpec>,
+) -> ApiResult {
+    let response_builder = ResponseBuilder::new(&req);
+
+    let exit: SignedVoluntaryExit = parse_json_body(req).await?;
+    let outcome = beacon_chain
+        .verify_voluntary_exit_for_gossip(exit.clone())
+        .map_err(|e| {
+            ApiError::BadRequest(format!("Error while verifying voluntary exit: {:?}", e))
+        })?;
+
+    if let ObservationOutcome::New(verified_exit) = outcome {
+        publish_voluntary_exit_to_network::<T>(network_chan, exit)?;
+        beacon_chain.import_voluntary_exit(verified_exit);
+    }
+
+    response_builder?.body_no_ssz(&())
+}
+
+/// Parse a `ValidatorId`.
+///
+/// E.g., `"42"` or a `0x` prefixed public key.
+fn parse_validator_id(string: &str) -> Result<ValidatorId, ApiError> {
+    string.parse().map_err(ApiError::BadRequest)
+}
+
+/// Returns the index of the validator identified by `validator_id`, if it is in `state`.
+///
+/// The provided `state` must have a fully up-to-date pubkey cache.
+fn validator_index<E: EthSpec>(
+    state: &BeaconState<E>,
+    validator_id: &ValidatorId,
+) -> Result<Option<usize>, ApiError> {
+    match validator_id {
+        ValidatorId::PublicKey(pubkey) => Ok(state.get_validator_index(pubkey)?),
+        ValidatorId::Index(index) => {
+            Ok(Some(*index as usize).filter(|index| *index < state.validators.len()))
+        }
+    }
+}
+
+/// Returns the `ValidatorData` for the validator at `index` in `state`.
+fn validator_data<E: EthSpec>(
+    state: &BeaconState<E>,
+    index: usize,
+    spec: &ChainSpec,
+) -> Result<ValidatorData, ApiError> {
+    let validator = state
+        .validators
+        .get(index)
+        .ok_or_else(|| ApiError::ServerError(format!("Invalid validator index: {}", index)))?;
+    let balance = *state
+        .balances
+        .get(index)
+        .ok_or_else(|| ApiError::ServerError(format!("Invalid balances index: {}", index)))?;
+
+    Ok(ValidatorData {
+        index: index as u64,
+        balance,
+        status: ValidatorStatus::from_validator(
+            validator,
+            balance,
+            state.current_epoch(),
+            spec.far_future_epoch,
+        ),
+        validator: validator.clone().into(),
+    })
+}
+
+/// Returns the `BlockHeaderData` for the block with the given `root`.
+fn block_header_data<T: BeaconChainTypes>(
+    beacon_chain: &BeaconChain<T>,
+    root: Hash256,
+) -> Result<BlockHeaderData, ApiError> {
+    let block = beacon_chain
+        .get_block(&root)?
+        .ok_or_else(|| ApiError::NotFound(format!("Unable to find block with root {:?}", root)))?;
+
+    let canonical = block_root_at_slot(beacon_chain, block.slot())? == Some(root);
+
+    Ok(BlockHeaderData {
+        root,
+        canonical,
+        header: BlockHeaderAndSignature {
+            message: block.message.block_header().into(),
+            signature: block.signature.into(),
+        },
+    })
+}
@@ -0,0 +1,55 @@
+//! Resolves a `BlockId` to a `SignedBeaconBlock` (or its root).
+
+use crate::helpers::block_root_at_slot;
+use crate::ApiError;
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use rest_types::eth_v1::BlockId;
+use types::{Hash256, SignedBeaconBlock};
+
+/// Parse a `BlockId`.
+///
+/// E.g., `"head"`, `"1234"` or `"0x0000000000000000000000000000000000000000000000000000000000000000"`
+pub fn parse_block_id(string: &str) -> Result<BlockId, ApiError> {
+    string.parse().map_err(ApiError::BadRequest)
+}
+
+/// Returns the root of the block identified by `block_id`.
+pub fn block_root<T: BeaconChainTypes>(
+    beacon_chain: &BeaconChain<T>,
+    block_id: BlockId,
+) -> Result<Hash256, ApiError> {
+    // Checkpoints in the genesis epoch have a zero root, which refers to the genesis block.
+    let checkpoint_root = |root: Hash256| {
+        if root == Hash256::zero() {
+            beacon_chain.genesis_block_root
+        } else {
+            root
+        }
+    };
+
+    match block_id {
+        BlockId::Head => Ok(beacon_chain.head_info()?.block_root),
+        BlockId::Genesis => Ok(beacon_chain.genesis_block_root),
+        BlockId::Finalized => Ok(checkpoint_root(
+            beacon_chain.head_info()?.finalized_checkpoint.root,
+        )),
+        BlockId::Justified => Ok(checkpoint_root(
+            beacon_chain.head_info()?.current_justified_checkpoint.root,
+        )),
+        BlockId::Slot(slot) => block_root_at_slot(beacon_chain, slot)?
+            .ok_or_else(|| ApiError::NotFound(format!("Unable to find block at slot {}", slot))),
+        BlockId::Root(root) => Ok(root),
+    }
+}
+
+/// Returns the block identified by `block_id`.
+pub fn block<T: BeaconChainTypes>(
+    beacon_chain: &BeaconChain<T>,
+    block_id: BlockId,
+) -> Result<SignedBeaconBlock<T::EthSpec>, ApiError> {
+    let root = block_root(beacon_chain, block_id)?;
+
+    beacon_chain
+        .get_block(&root)?
+        .ok_or_else(|| ApiError::NotFound(format!("Unable to find block with root {:?}", root)))
+}
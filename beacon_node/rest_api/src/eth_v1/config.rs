@@ -0,0 +1,33 @@
+use super::respond;
+use crate::helpers::implementation_pending_response;
+use crate::ApiResult;
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use hyper::{Body, Request};
+use std::sync::Arc;
+use types::YamlConfig;
+
+/// HTTP handler to return the forks known to the node.
+///
+/// Presently, only the fork of the head state is known.
+pub fn get_fork_schedule<T: BeaconChainTypes>(
+    req: Request<Body>,
+    beacon_chain: Arc<BeaconChain<T>>,
+) -> ApiResult {
+    respond(&req, vec![beacon_chain.head_info()?.fork])
+}
+
+/// HTTP handler to return the specification constants used by the node.
+pub fn get_spec<T: BeaconChainTypes>(
+    req: Request<Body>,
+    beacon_chain: Arc<BeaconChain<T>>,
+) -> ApiResult {
+    respond(
+        &req,
+        YamlConfig::from_spec::<T::EthSpec>(&beacon_chain.spec),
+    )
+}
+
+/// HTTP handler to return the deposit contract used by the node.
+pub fn get_deposit_contract(req: Request<Body>) -> ApiResult {
+    implementation_pending_response(req)
+}
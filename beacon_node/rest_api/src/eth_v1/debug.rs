@@ -0,0 +1,33 @@
+use super::respond;
+use super::state_id::{self, parse_state_id};
+use crate::ApiResult;
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use hyper::{Body, Request};
+use rest_types::eth_v1::ChainHeadData;
+use std::sync::Arc;
+
+/// HTTP handler to return the full `BeaconState` identified by `state_id`.
+pub fn get_state<T: BeaconChainTypes>(
+    req: Request<Body>,
+    beacon_chain: Arc<BeaconChain<T>>,
+    state_id: &str,
+) -> ApiResult {
+    let state = state_id::state(&beacon_chain, parse_state_id(state_id)?)?;
+
+    respond(&req, state)
+}
+
+/// HTTP handler to return all the heads known to fork choice. One of these will be the canonical
+/// head.
+pub fn get_heads<T: BeaconChainTypes>(
+    req: Request<Body>,
+    beacon_chain: Arc<BeaconChain<T>>,
+) -> ApiResult {
+    let heads = beacon_chain
+        .heads()
+        .into_iter()
+        .map(|(root, slot)| ChainHeadData { slot, root })
+        .collect::<Vec<_>>();
+
+    respond(&req, heads)
+}
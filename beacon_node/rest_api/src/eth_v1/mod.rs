@@ -0,0 +1,177 @@
+//! Implements the standard Ethereum Beacon Node API (`/eth/v1/...`).
+//!
+//! These endpoints are served alongside the Lighthouse-specific endpoints. Successful responses
+//! are wrapped in a `GenericResponse` (i.e., `{"data": ...}`) and errors are returned as a JSON
+//! `ErrorMessage` (see `ApiError::into_error_message_response`).
+
+mod beacon;
+mod block_id;
+mod config;
+mod debug;
+mod node;
+mod state_id;
+mod validator;
+
+use crate::response_builder::ResponseBuilder;
+use crate::{ApiError, ApiResult, NetworkChannel};
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use eth2_libp2p::NetworkGlobals;
+use hyper::{Body, Method, Request};
+use rest_types::eth_v1::GenericResponse;
+use serde::{de::DeserializeOwned, Serialize};
+use std::sync::Arc;
+
+pub use rest_types::eth_v1::ETH_V1_PATH_PREFIX;
+
+/// Routes a request with a path starting with `ETH_V1_PATH_PREFIX` to its handler.
+pub async fn route<T: BeaconChainTypes>(
+    req: Request<Body>,
+    beacon_chain: Arc<BeaconChain<T>>,
+    network_globals: Arc<NetworkGlobals<T::EthSpec>>,
+    network_channel: NetworkChannel<T::EthSpec>,
+    log: slog::Logger,
+) -> ApiResult {
+    let path = req.uri().path().to_string();
+    let parts = path
+        .trim_start_matches(ETH_V1_PATH_PREFIX)
+        .split('/')
+        .collect::<Vec<_>>();
+
+    match (req.method(), parts.as_slice()) {
+        // Methods for the beacon chain
+        (&Method::GET, ["beacon", "genesis"]) => beacon::get_genesis::<T>(req, beacon_chain),
+        (&Method::GET, ["beacon", "states", state_id, "root"]) => {
+            beacon::get_state_root::<T>(req, beacon_chain, state_id)
+        }
+        (&Method::GET, ["beacon", "states", state_id, "fork"]) => {
+            beacon::get_state_fork::<T>(req, beacon_chain, state_id)
+        }
+        (&Method::GET, ["beacon", "states", state_id, "finality_checkpoints"]) => {
+            beacon::get_state_finality_checkpoints::<T>(req, beacon_chain, state_id)
+        }
+        (&Method::GET, ["beacon", "states", state_id, "validators"]) => {
+            beacon::get_state_validators::<T>(req, beacon_chain, state_id)
+        }
+        (&Method::GET, ["beacon", "states", state_id, "validators", validator_id]) => {
+            beacon::get_state_validator::<T>(req, beacon_chain, state_id, validator_id)
+        }
+        (&Method::GET, ["beacon", "states", state_id, "committees"]) => {
+            beacon::get_state_committees::<T>(req, beacon_chain, state_id)
+        }
+        (&Method::GET, ["beacon", "headers"]) => beacon::get_headers::<T>(req, beacon_chain),
+        (&Method::GET, ["beacon", "headers", block_id]) => {
+            beacon::get_header::<T>(req, beacon_chain, block_id)
+        }
+        (&Method::POST, ["beacon", "blocks"]) => {
+            beacon::post_block::<T>(req, beacon_chain, network_channel, log).await
+        }
+        (&Method::GET, ["beacon", "blocks", block_id]) => {
+            beacon::get_block::<T>(req, beacon_chain, block_id)
+        }
+        (&Method::GET, ["beacon", "blocks", block_id, "root"]) => {
+            beacon::get_block_root::<T>(req, beacon_chain, block_id)
+        }
+        (&Method::GET, ["beacon", "blocks", block_id, "attestations"]) => {
+            beacon::get_block_attestations::<T>(req, beacon_chain, block_id)
+        }
+        (&Method::GET, ["beacon", "pool", "attestations"]) => {
+            beacon::get_pool_attestations::<T>(req, beacon_chain)
+        }
+        (&Method::POST, ["beacon", "pool", "attestations"]) => {
+            beacon::post_pool_attestations::<T>(req, beacon_chain, network_channel, log).await
+        }
+        (&Method::GET, ["beacon", "pool", "attester_slashings"]) => {
+            beacon::get_pool_attester_slashings::<T>(req, beacon_chain)
+        }
+        (&Method::POST, ["beacon", "pool", "attester_slashings"]) => {
+            beacon::post_pool_attester_slashings::<T>(req, beacon_chain, network_channel).await
+        }
+        (&Method::GET, ["beacon", "pool", "proposer_slashings"]) => {
+            beacon::get_pool_proposer_slashings::<T>(req, beacon_chain)
+        }
+        (&Method::POST, ["beacon", "pool", "proposer_slashings"]) => {
+            beacon::post_pool_proposer_slashings::<T>(req, beacon_chain, network_channel).await
+        }
+        (&Method::GET, ["beacon", "pool", "voluntary_exits"]) => {
+            beacon::get_pool_voluntary_exits::<T>(req, beacon_chain)
+        }
+        (&Method::POST, ["beacon", "pool", "voluntary_exits"]) => {
+            beacon::post_pool_voluntary_exits::<T>(req, beacon_chain, network_channel).await
+        }
+
+        // Methods for configuration
+        (&Method::GET, ["config", "fork_schedule"]) => {
+            config::get_fork_schedule::<T>(req, beacon_chain)
+        }
+        (&Method::GET, ["config", "spec"]) => config::get_spec::<T>(req, beacon_chain),
+        (&Method::GET, ["config", "deposit_contract"]) => config::get_deposit_contract(req),
+
+        // Methods for debugging
+        (&Method::GET, ["debug", "beacon", "states", state_id]) => {
+            debug::get_state::<T>(req, beacon_chain, state_id)
+        }
+        (&Method::GET, ["debug", "beacon", "heads"]) => debug::get_heads::<T>(req, beacon_chain),
+
+        // Methods for the node
+        (&Method::GET, ["node", "identity"]) => {
+            node::get_identity::<T::EthSpec>(req, network_globals)
+        }
+        (&Method::GET, ["node", "peers"]) => node::get_peers::<T::EthSpec>(req, network_globals),
+        (&Method::GET, ["node", "version"]) => node::get_version(req),
+        (&Method::GET, ["node", "syncing"]) => {
+            node::get_syncing::<T>(req, beacon_chain, network_globals)
+        }
+        (&Method::GET, ["node", "health"]) => node::get_health::<T::EthSpec>(network_globals),
+
+        // Methods for validators
+        (&Method::POST, ["validator", "duties", "attester", epoch]) => {
+            validator::post_attester_duties::<T>(req, beacon_chain, epoch).await
+        }
+        (&Method::GET, ["validator", "duties", "proposer", epoch]) => {
+            validator::get_proposer_duties::<T>(req, beacon_chain, epoch)
+        }
+        (&Method::GET, ["validator", "blocks", slot]) => {
+            validator::get_block::<T>(req, beacon_chain, slot, log)
+        }
+        (&Method::GET, ["validator", "attestation_data"]) => {
+            validator::get_attestation_data::<T>(req, beacon_chain)
+        }
+        (&Method::GET, ["validator", "aggregate_attestation"]) => {
+            validator::get_aggregate_attestation::<T>(req, beacon_chain)
+        }
+        (&Method::POST, ["validator", "aggregate_and_proofs"]) => {
+            validator::post_aggregate_and_proofs::<T>(req, beacon_chain, network_channel, log).await
+        }
+        (&Method::POST, ["validator", "beacon_committee_subscriptions"]) => {
+            validator::post_beacon_committee_subscriptions::<T>(req, network_channel).await
+        }
+
+        _ => Err(ApiError::NotFound(
+            "Request path and/or method not found.".to_owned(),
+        )),
+    }
+}
+
+/// Builds a response with `data` wrapped in a `GenericResponse`.
+fn respond<S: Serialize>(req: &Request<Body>, data: S) -> ApiResult {
+    ResponseBuilder::new(req)?.body_no_ssz(&GenericResponse::from(data))
+}
+
+/// Reads the body of `req` and parses it as JSON.
+async fn parse_json_body<T: DeserializeOwned>(req: Request<Body>) -> Result<T, ApiError> {
+    let chunks = hyper::body::to_bytes(req.into_body())
+        .await
+        .map_err(|e| ApiError::ServerError(format!("Unable to get request body: {:?}", e)))?;
+
+    serde_json::from_slice(&chunks)
+        .map_err(|e| ApiError::BadRequest(format!("Unable to parse JSON request body: {:?}", e)))
+}
+
+/// The Lighthouse API uses a `202` response for objects that were invalid, whilst the standard
+/// API expects a `400`.
+fn invalid_object_to_bad_request(e: ApiError) -> ApiError {
+    match e {
+        ApiError::ProcessingError(desc) => ApiError::BadRequest(desc),
+        other => other,
+    }
+}
@@ -0,0 +1,101 @@
+use super::respond;
+use crate::{ApiError, ApiResult};
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use eth2_libp2p::NetworkGlobals;
+use hyper::{Body, Request, Response, StatusCode};
+use rest_types::eth_v1::{IdentityData, PeerData, SyncingData, VersionData};
+use std::sync::Arc;
+use types::EthSpec;
+
+/// HTTP handler to return the network identity of the node.
+pub fn get_identity<T: EthSpec>(
+    req: Request<Body>,
+    network_globals: Arc<NetworkGlobals<T>>,
+) -> ApiResult {
+    respond(
+        &req,
+        IdentityData {
+            peer_id: network_globals.local_peer_id().to_base58(),
+            enr: network_globals.local_enr().to_base64(),
+            p2p_addresses: network_globals
+                .listen_multiaddrs()
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+        },
+    )
+}
+
+/// HTTP handler to return all the peers known to the node.
+pub fn get_peers<T: EthSpec>(
+    req: Request<Body>,
+    network_globals: Arc<NetworkGlobals<T>>,
+) -> ApiResult {
+    let peer_db = network_globals.peers.read();
+    let peers = peer_db
+        .peer_ids()
+        .map(|peer_id| {
+            let state = if peer_db.is_connected(peer_id) {
+                "connected"
+            } else if peer_db.is_connected_or_dialing(peer_id) {
+                "connecting"
+            } else {
+                "disconnected"
+            };
+
+            PeerData {
+                peer_id: peer_id.to_string(),
+                state: state.to_string(),
+            }
+        })
+        .collect::<Vec<_>>();
+
+    respond(&req, peers)
+}
+
+/// HTTP handler to return the version string of the current Lighthouse build.
+pub fn get_version(req: Request<Body>) -> ApiResult {
+    respond(
+        &req,
+        VersionData {
+            version: version::version(),
+        },
+    )
+}
+
+/// HTTP handler to return the sync status of the node.
+pub fn get_syncing<T: BeaconChainTypes>(
+    req: Request<Body>,
+    beacon_chain: Arc<BeaconChain<T>>,
+    network_globals: Arc<NetworkGlobals<T::EthSpec>>,
+) -> ApiResult {
+    let head_slot = beacon_chain.head_info()?.slot;
+    let current_slot = beacon_chain
+        .slot()
+        .map_err(|_| ApiError::ServerError("Unable to read slot clock".to_string()))?;
+
+    respond(
+        &req,
+        SyncingData {
+            is_syncing: network_globals.is_syncing(),
+            head_slot,
+            sync_distance: current_slot.saturating_sub(head_slot),
+        },
+    )
+}
+
+/// HTTP handler to return the health of the node as a status code, without a body.
+///
+/// Returns a `200` if the node is synced or a `206` if it is still syncing.
+pub fn get_health<T: EthSpec>(network_globals: Arc<NetworkGlobals<T>>) -> ApiResult {
+    let status = if network_globals.is_syncing() {
+        StatusCode::PARTIAL_CONTENT
+    } else {
+        StatusCode::OK
+    };
+
+    Response::builder()
+        .status(status)
+        .body(Body::empty())
+        .map_err(|e| ApiError::ServerError(format!("Failed to build response: {:?}", e)))
+}
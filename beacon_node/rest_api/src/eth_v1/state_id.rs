@@ -0,0 +1,82 @@
+//! Resolves a `StateId` to a `BeaconState` (or its root) in the canonical chain.
+
+use crate::helpers::{state_at_slot, state_root_at_slot};
+use crate::ApiError;
+use beacon_chain::{BeaconChain, BeaconChainTypes, StateSkipConfig};
+use rest_types::eth_v1::StateId;
+use types::{BeaconState, Checkpoint, EthSpec, Hash256, Slot};
+
+/// Parse a `StateId`.
+///
+/// E.g., `"head"`, `"1234"` or `"0x0000000000000000000000000000000000000000000000000000000000000000"`
+pub fn parse_state_id(string: &str) -> Result<StateId, ApiError> {
+    string.parse().map_err(ApiError::BadRequest)
+}
+
+/// Returns the root of the state identified by `state_id`.
+pub fn state_root<T: BeaconChainTypes>(
+    beacon_chain: &BeaconChain<T>,
+    state_id: StateId,
+) -> Result<Hash256, ApiError> {
+    match state_id {
+        StateId::Head => Ok(beacon_chain.head_info()?.state_root),
+        StateId::Genesis => {
+            let genesis_block = beacon_chain
+                .get_block(&beacon_chain.genesis_block_root)?
+                .ok_or_else(|| ApiError::ServerError("Unable to find genesis block".into()))?;
+            Ok(genesis_block.state_root())
+        }
+        StateId::Finalized => {
+            let checkpoint = beacon_chain.head_info()?.finalized_checkpoint;
+            state_root_at_slot(
+                beacon_chain,
+                checkpoint_slot::<T::EthSpec>(&checkpoint),
+                StateSkipConfig::WithStateRoots,
+            )
+        }
+        StateId::Justified => {
+            let checkpoint = beacon_chain.head_info()?.current_justified_checkpoint;
+            state_root_at_slot(
+                beacon_chain,
+                checkpoint_slot::<T::EthSpec>(&checkpoint),
+                StateSkipConfig::WithStateRoots,
+            )
+        }
+        StateId::Slot(slot) => {
+            state_root_at_slot(beacon_chain, slot, StateSkipConfig::WithStateRoots)
+        }
+        // Unlike the other identifiers, a root may not refer to any known state.
+        StateId::Root(root) => state_by_root(beacon_chain, root).map(|_| root),
+    }
+}
+
+/// Returns the state identified by `state_id`.
+pub fn state<T: BeaconChainTypes>(
+    beacon_chain: &BeaconChain<T>,
+    state_id: StateId,
+) -> Result<BeaconState<T::EthSpec>, ApiError> {
+    match state_id {
+        StateId::Head => Ok(beacon_chain.head()?.beacon_state),
+        StateId::Slot(slot) => state_at_slot(beacon_chain, slot).map(|(_root, state)| state),
+        StateId::Root(root) => state_by_root(beacon_chain, root),
+        other => {
+            let root = state_root(beacon_chain, other)?;
+            state_by_root(beacon_chain, root)
+        }
+    }
+}
+
+/// Returns the state with the given `root`, or `NotFound` if it is not in the store.
+fn state_by_root<T: BeaconChainTypes>(
+    beacon_chain: &BeaconChain<T>,
+    root: Hash256,
+) -> Result<BeaconState<T::EthSpec>, ApiError> {
+    beacon_chain
+        .get_state(&root, None)?
+        .ok_or_else(|| ApiError::NotFound(format!("Unable to find state at root {:?}", root)))
+}
+
+/// Returns the slot of the state for a finalized or justified `checkpoint`.
+fn checkpoint_slot<E: EthSpec>(checkpoint: &Checkpoint) -> Slot {
+    checkpoint.epoch.start_slot(E::slots_per_epoch())
+}
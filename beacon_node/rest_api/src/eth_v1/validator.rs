@@ -0,0 +1,220 @@
+use super::{invalid_object_to_bad_request, parse_json_body, respond};
+use crate::helpers::{parse_epoch, parse_root, parse_slot};
+use crate::response_builder::ResponseBuilder;
+use crate::validator::{get_state_for_epoch, process_aggregated_attestation};
+use crate::{ApiError, ApiResult, NetworkChannel, UrlQuery};
+use beacon_chain::{BeaconChain, BeaconChainTypes, StateSkipConfig};
+use hyper::{Body, Request};
+use network::NetworkMessage;
+use rest_types::eth_v1::{
+    AttesterData, BeaconCommitteeSubscription, GenericResponse, ProposerData,
+};
+use rest_types::ValidatorSubscription;
+use slog::{error, Logger};
+use std::sync::Arc;
+use types::{EthSpec, RelativeEpoch, SignedAggregateAndProof};
+
+/// HTTP handler to return the attestation duties of the validators with the indices given in the
+/// request body, during `epoch`.
+///
+/// Validators without duties in `epoch` (e.g., unknown or inactive validators) are omitted.
+pub async fn post_attester_duties<T: BeaconChainTypes>(
+    req: Request<Body>,
+    beacon_chain: Arc<BeaconChain<T>>,
+    epoch: &str,
+) -> ApiResult {
+    let response_builder = ResponseBuilder::new(&req);
+    let epoch = parse_epoch(epoch)?;
+
+    let validator_indices: Vec<u64> = parse_json_body(req).await?;
+
+    let mut state = get_state_for_epoch(&beacon_chain, epoch, StateSkipConfig::WithoutStateRoots)?;
+    let relative_epoch = RelativeEpoch::from_epoch(state.current_epoch(), epoch)
+        .map_err(|_| ApiError::ServerError(String::from("Loaded state is in the wrong epoch")))?;
+    state.build_committee_cache(relative_epoch, &beacon_chain.spec)?;
+
+    let mut duties = vec![];
+    for validator_index in validator_indices {
+        let validator = match state.validators.get(validator_index as usize) {
+            Some(validator) => validator,
+            None => continue,
+        };
+
+        if let Some(duty) =
+            state.get_attestation_duties(validator_index as usize, relative_epoch)?
+        {
+            duties.push(AttesterData {
+                pubkey: validator.pubkey.clone(),
+                validator_index,
+                committee_index: duty.index,
+                committee_length: duty.committee_len as u64,
+                committees_at_slot: state.get_committee_count_at_slot(duty.slot)?,
+                validator_committee_index: duty.committee_position as u64,
+                slot: duty.slot,
+            });
+        }
+    }
+
+    response_builder?.body_no_ssz(&GenericResponse::from(duties))
+}
+
+/// HTTP handler to return the block proposers for each slot of `epoch`.
+pub fn get_proposer_duties<T: BeaconChainTypes>(
+    req: Request<Body>,
+    beacon_chain: Arc<BeaconChain<T>>,
+    epoch: &str,
+) -> ApiResult {
+    let epoch = parse_epoch(epoch)?;
+
+    let mut state = get_state_for_epoch(&beacon_chain, epoch, StateSkipConfig::WithoutStateRoots)?;
+    let relative_epoch = RelativeEpoch::from_epoch(state.current_epoch(), epoch)
+        .map_err(|_| ApiError::ServerError(String::from("Loaded state is in the wrong epoch")))?;
+    state.build_committee_cache(relative_epoch, &beacon_chain.spec)?;
+
+    let duties = epoch
+        .slot_iter(T::EthSpec::slots_per_epoch())
+        .map(|slot| {
+            let validator_index = state.get_beacon_proposer_index(slot, &beacon_chain.spec)?;
+            let validator = state.validators.get(validator_index).ok_or_else(|| {
+                ApiError::ServerError(format!("Invalid validator index: {}", validator_index))
+            })?;
+
+            Ok(ProposerData {
+                pubkey: validator.pubkey.clone(),
+                validator_index: validator_index as u64,
+                slot,
+            })
+        })
+        .collect::<Result<Vec<_>, ApiError>>()?;
+
+    respond(&req, duties)
+}
+
+/// HTTP handler to produce a new `BeaconBlock` at `slot`, ready to be signed by a validator.
+///
+/// Requires the `randao_reveal` query parameter.
+pub fn get_block<T: BeaconChainTypes>(
+    req: Request<Body>,
+    beacon_chain: Arc<BeaconChain<T>>,
+    slot: &str,
+    log: Logger,
+) -> ApiResult {
+    let slot = parse_slot(slot)?;
+    let randao_reveal = UrlQuery::from_request(&req)?.randao_reveal()?;
+
+    let (block, _state) = beacon_chain
+        .produce_block(randao_reveal, slot)
+        .map_err(|e| {
+            error!(
+                log,
+                "Error whilst producing block";
+                "error" => format!("{:?}", e)
+            );
+
+            ApiError::ServerError(format!(
+                "Beacon node is not able to produce a block: {:?}",
+                e
+            ))
+        })?;
+
+    respond(&req, block)
+}
+
+/// HTTP handler to produce the `AttestationData` for the `slot` and `committee_index` query
+/// parameters, ready to be signed by a validator.
+pub fn get_attestation_data<T: BeaconChainTypes>(
+    req: Request<Body>,
+    beacon_chain: Arc<BeaconChain<T>>,
+) -> ApiResult {
+    let query = UrlQuery::from_request(&req)?;
+    let slot = query.slot()?;
+    let index = query.committee_index()?;
+
+    let attestation = beacon_chain
+        .produce_unaggregated_attestation(slot, index)
+        .map_err(|e| ApiError::BadRequest(format!("Unable to produce attestation: {:?}", e)))?;
+
+    respond(&req, attestation.data)
+}
+
+/// HTTP handler to return the aggregate attestation for the `slot` and `attestation_data_root`
+/// query parameters.
+pub fn get_aggregate_attestation<T: BeaconChainTypes>(
+    req: Request<Body>,
+    beacon_chain: Arc<BeaconChain<T>>,
+) -> ApiResult {
+    let query = UrlQuery::from_request(&req)?;
+    let slot = query.slot()?;
+    let (_key, value) = query.first_of(&["attestation_data_root"])?;
+    let data_root = parse_root(&value)?;
+
+    let attestation = beacon_chain
+        .get_aggregated_attestation_by_slot_and_root(slot, &data_root)
+        .ok_or_else(|| {
+            ApiError::NotFound(format!(
+                "No matching aggregate attestation for slot {} and root {:?}",
+                slot, data_root
+            ))
+        })?;
+
+    respond(&req, attestation)
+}
+
+/// HTTP handler to verify a list of `SignedAggregateAndProof`, import them and publish them to
+/// the network.
+pub async fn post_aggregate_and_proofs<T: BeaconChainTypes>(
+    req: Request<Body>,
+    beacon_chain: Arc<BeaconChain<T>>,
+    network_chan: NetworkChannel<T::EthSpec>,
+    log: Logger,
+) -> ApiResult {
+    let response_builder = ResponseBuilder::new(&req);
+
+    let signed_aggregates: Vec<SignedAggregateAndProof<T::EthSpec>> = parse_json_body(req).await?;
+    signed_aggregates
+        .into_iter()
+        .enumerate()
+        .try_for_each(|(i, signed_aggregate)| {
+            process_aggregated_attestation(
+                &beacon_chain,
+                network_chan.clone(),
+                signed_aggregate,
+                i,
+                &log,
+            )
+        })
+        .map_err(invalid_object_to_bad_request)?;
+
+    response_builder?.body_no_ssz(&())
+}
+
+/// HTTP handler to subscribe the node to the attestation subnets of the given committees, so
+/// that it may aggregate attestations for them.
+pub async fn post_beacon_committee_subscriptions<T: BeaconChainTypes>(
+    req: Request<Body>,
+    network_chan: NetworkChannel<T::EthSpec>,
+) -> ApiResult {
+    let response_builder = ResponseBuilder::new(&req);
+
+    let committee_subscriptions: Vec<BeaconCommitteeSubscription> = parse_json_body(req).await?;
+    let subscriptions = committee_subscriptions
+        .into_iter()
+        .map(|subscription| ValidatorSubscription {
+            validator_index: subscription.validator_index,
+            attestation_committee_index: subscription.committee_index,
+            slot: subscription.slot,
+            is_aggregator: subscription.is_aggregator,
+        })
+        .collect();
+
+    network_chan
+        .send(NetworkMessage::Subscribe { subscriptions })
+        .map_err(|e| {
+            ApiError::ServerError(format!(
+                "Unable to send subscriptions to the network: {:?}",
+                e
+            ))
+        })?;
+
+    response_builder?.body_no_ssz(&())
+}
@@ -0,0 +1,213 @@
+use crate::{ApiError, ApiResult, UrlQuery};
+use beacon_chain::events::{EventKind, EventSubscribers, EventType};
+use beacon_chain::BeaconChainTypes;
+use environment::TaskExecutor;
+use futures::channel::mpsc::Receiver;
+use futures::future::{self, Either};
+use futures::StreamExt;
+use hyper::body::Bytes;
+use hyper::{Body, Request, Response};
+use rest_types::events::{
+    BlockEvent, ChainReorgEvent, Event, EventTopic, FinalizedCheckpointEvent, HeadEvent,
+};
+use serde::Serialize;
+use slog::error;
+use std::io::Write;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+use types::EthSpec;
+
+/// The interval at which a comment is sent on an event stream, so that a client which has
+/// disconnected is detected even if no events occur.
+const KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(15);
+
+/// HTTP handler to stream the events for the topics listed in the `topics` query parameter as
+/// server-sent events.
+///
+/// Topics may be comma-separated (`?topics=head,block`) or repeated
+/// (`?topics=head&topics=block`).
+pub fn stream_events<T: BeaconChainTypes>(
+    req: Request<Body>,
+    executor: TaskExecutor,
+    subscribers: Arc<EventSubscribers<T::EthSpec>>,
+) -> ApiResult {
+    let event_types = UrlQuery::from_request(&req)?
+        .all_of("topics")?
+        .iter()
+        .flat_map(|topics| topics.split(','))
+        .filter(|topic| !topic.is_empty())
+        .map(|topic| {
+            EventTopic::from_str(topic)
+                .map(event_type)
+                .map_err(ApiError::BadRequest)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if event_types.is_empty() {
+        return Err(ApiError::BadRequest(
+            "At least one event topic must be supplied".to_string(),
+        ));
+    }
+
+    let receiver = subscribe(&subscribers, event_types)?;
+
+    stream_sse(executor, receiver, |kind| match into_event(kind) {
+        Some(event) => make_event_chunk(&event).map(Some),
+        None => Ok(None),
+    })
+}
+
+/// Subscribes to events of the given `event_types`, returning an error if the maximum number of
+/// subscribers has been reached.
+pub fn subscribe<E: EthSpec>(
+    subscribers: &EventSubscribers<E>,
+    event_types: Vec<EventType>,
+) -> Result<Receiver<EventKind<E>>, ApiError> {
+    subscribers.subscribe(event_types).ok_or_else(|| {
+        ApiError::ServiceUnavailable("Maximum number of event subscribers reached".to_string())
+    })
+}
+
+/// Returns a response which streams a server-sent event for each chunk returned by `make_chunk`.
+///
+/// The stream ends, and the subscription is cancelled, once the client disconnects.
+pub fn stream_sse<E, F>(
+    executor: TaskExecutor,
+    mut receiver: Receiver<EventKind<E>>,
+    make_chunk: F,
+) -> ApiResult
+where
+    E: EthSpec,
+    F: Fn(EventKind<E>) -> std::io::Result<Option<Bytes>> + Send + 'static,
+{
+    let log = executor.log().clone();
+    let (mut sender, body) = Body::channel();
+
+    let stream_future = async move {
+        let mut keep_alive = tokio::time::interval(KEEP_ALIVE_INTERVAL);
+
+        loop {
+            let chunk = match future::select(receiver.next(), Box::pin(keep_alive.tick())).await {
+                Either::Left((Some(kind), _)) => match make_chunk(kind) {
+                    Ok(Some(chunk)) => chunk,
+                    Ok(None) => continue,
+                    Err(e) => {
+                        error!(log, "Failed to make SSE chunk"; "error" => e.to_string());
+                        sender.abort();
+                        break;
+                    }
+                },
+                // The subscribers have been dropped, so no more events will arrive.
+                Either::Left((None, _)) => break,
+                // An SSE comment, which is ignored by clients.
+                Either::Right(_) => Bytes::from_static(b":\n\n"),
+            };
+
+            // An error indicates the client has gone away. Returning drops `receiver`, which
+            // removes this subscriber.
+            if sender.send_data(chunk).await.is_err() {
+                break;
+            }
+        }
+    };
+    executor.spawn(stream_future, "sse");
+
+    let response = Response::builder()
+        .status(200)
+        .header("Content-Type", "text/event-stream")
+        .header("Connection", "Keep-Alive")
+        .header("Cache-Control", "no-cache")
+        .header("Access-Control-Allow-Origin", "*")
+        .body(body)
+        .map_err(|e| ApiError::ServerError(format!("Failed to build response: {:?}", e)))?;
+    Ok(response)
+}
+
+/// Returns the type of the events published under `topic`.
+fn event_type(topic: EventTopic) -> EventType {
+    match topic {
+        EventTopic::Head => EventType::BeaconHeadChanged,
+        EventTopic::Block => EventType::BeaconBlockImported,
+        EventTopic::Attestation => EventType::BeaconAttestationImported,
+        EventTopic::FinalizedCheckpoint => EventType::BeaconFinalization,
+        EventTopic::ChainReorg => EventType::BeaconChainReorg,
+        EventTopic::VoluntaryExit => EventType::BeaconVoluntaryExitImported,
+    }
+}
+
+/// Converts an internal `EventKind` into the `Event` sent to clients, if it has a topic.
+fn into_event<E: EthSpec>(kind: EventKind<E>) -> Option<Event<E>> {
+    let event = match kind {
+        EventKind::BeaconHeadChanged {
+            reorg,
+            slot,
+            current_head_beacon_block_root,
+            current_head_beacon_state_root,
+            previous_head_beacon_block_root,
+        } => Event::Head(HeadEvent {
+            slot,
+            block: current_head_beacon_block_root,
+            state: current_head_beacon_state_root,
+            previous_block: previous_head_beacon_block_root,
+            reorg,
+        }),
+        EventKind::BeaconBlockImported { block_root, block } => Event::Block(BlockEvent {
+            slot: block.slot(),
+            block: block_root,
+        }),
+        EventKind::BeaconAttestationImported { attestation } => Event::Attestation(attestation),
+        EventKind::BeaconFinalization { epoch, root } => {
+            Event::FinalizedCheckpoint(FinalizedCheckpointEvent { block: root, epoch })
+        }
+        EventKind::BeaconChainReorg {
+            slot,
+            depth,
+            old_head_block,
+            new_head_block,
+            old_head_state,
+            new_head_state,
+            epoch,
+        } => Event::ChainReorg(ChainReorgEvent {
+            slot,
+            depth,
+            old_head_block,
+            new_head_block,
+            old_head_state,
+            new_head_state,
+            epoch,
+        }),
+        EventKind::BeaconVoluntaryExitImported { voluntary_exit } => {
+            Event::VoluntaryExit(voluntary_exit)
+        }
+        EventKind::BeaconBlockRejected { .. } | EventKind::BeaconAttestationRejected { .. } => {
+            return None
+        }
+    };
+    Some(event)
+}
+
+/// Encodes `event` as a server-sent event with the topic as the event type and the JSON-encoded
+/// payload as the data.
+fn make_event_chunk<E: EthSpec>(event: &Event<E>) -> std::io::Result<Bytes> {
+    let data = match event {
+        Event::Head(head) => to_json(head)?,
+        Event::Block(block) => to_json(block)?,
+        Event::Attestation(attestation) => to_json(attestation)?,
+        Event::FinalizedCheckpoint(checkpoint) => to_json(checkpoint)?,
+        Event::ChainReorg(reorg) => to_json(reorg)?,
+        Event::VoluntaryExit(exit) => to_json(exit)?,
+    };
+
+    let mut buffer = Vec::new();
+    {
+        let mut sse_message = uhttp_sse::SseMessage::new(&mut buffer);
+        write!(sse_message.event()?, "{}", event.topic())?;
+        write!(sse_message.data()?, "{}", data)?;
+    }
+    Ok(buffer.into())
+}
+
+fn to_json<T: Serialize>(item: &T) -> std::io::Result<String> {
+    serde_json::to_string(item).map_err(Into::into)
+}
@@ -10,7 +10,8 @@ use network::NetworkMessage;
 use ssz::Decode;
 use store::{iter::AncestorIter, Store};
 use types::{
-    BeaconState, CommitteeIndex, Epoch, EthSpec, Hash256, RelativeEpoch, SignedBeaconBlock, Slot,
+    AttesterSlashing, BeaconState, CommitteeIndex, Epoch, EthSpec, Hash256, ProposerSlashing,
+    RelativeEpoch, SignedBeaconBlock, SignedVoluntaryExit, Slot,
 };
 
 /// Parse a slot.
@@ -253,6 +254,57 @@ pub fn publish_beacon_block_to_network<T: BeaconChainTypes + 'static>(
     Ok(())
 }
 
+pub fn publish_voluntary_exit_to_network<T: BeaconChainTypes + 'static>(
+    chan: NetworkChannel<T::EthSpec>,
+    exit: SignedVoluntaryExit,
+) -> Result<(), ApiError> {
+    let messages = vec![PubsubMessage::VoluntaryExit(Box::new(exit))];
+
+    // Publish the exit to the p2p network via gossipsub.
+    if let Err(e) = chan.send(NetworkMessage::Publish { messages }) {
+        return Err(ApiError::ServerError(format!(
+            "Unable to send voluntary exit to network: {:?}",
+            e
+        )));
+    }
+
+    Ok(())
+}
+
+pub fn publish_proposer_slashing_to_network<T: BeaconChainTypes + 'static>(
+    chan: NetworkChannel<T::EthSpec>,
+    slashing: ProposerSlashing,
+) -> Result<(), ApiError> {
+    let messages = vec![PubsubMessage::ProposerSlashing(Box::new(slashing))];
+
+    // Publish the slashing to the p2p network via gossipsub.
+    if let Err(e) = chan.send(NetworkMessage::Publish { messages }) {
+        return Err(ApiError::ServerError(format!(
+            "Unable to send proposer slashing to network: {:?}",
+            e
+        )));
+    }
+
+    Ok(())
+}
+
+pub fn publish_attester_slashing_to_network<T: BeaconChainTypes + 'static>(
+    chan: NetworkChannel<T::EthSpec>,
+    slashing: AttesterSlashing<T::EthSpec>,
+) -> Result<(), ApiError> {
+    let messages = vec![PubsubMessage::AttesterSlashing(Box::new(slashing))];
+
+    // Publish the slashing to the p2p network via gossipsub.
+    if let Err(e) = chan.send(NetworkMessage::Publish { messages }) {
+        return Err(ApiError::ServerError(format!(
+            "Unable to send attester slashing to network: {:?}",
+            e
+        )));
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -9,6 +9,8 @@ mod beacon;
 pub mod config;
 mod consensus;
 mod error;
+mod eth_v1;
+mod events;
 mod helpers;
 mod lighthouse;
 mod metrics;
@@ -20,8 +22,7 @@ mod spec;
 mod url_query;
 mod validator;
 
-use beacon_chain::{BeaconChain, BeaconChainTypes};
-use bus::Bus;
+use beacon_chain::{events::EventSubscribers, BeaconChain, BeaconChainTypes};
 use client_network::NetworkMessage;
 pub use config::ApiEncodingFormat;
 use error::{ApiError, ApiResult};
@@ -31,13 +32,11 @@ use futures::future::TryFutureExt;
 use hyper::server::conn::AddrStream;
 use hyper::service::{make_service_fn, service_fn};
 use hyper::{Body, Request, Server};
-use parking_lot::Mutex;
 use slog::{info, warn};
 use std::net::SocketAddr;
 use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::sync::mpsc;
-use types::SignedBeaconBlockHash;
 use url_query::UrlQuery;
 
 pub use crate::helpers::parse_pubkey_bytes;
@@ -60,10 +59,11 @@ pub fn start_server<T: BeaconChainTypes>(
     db_path: PathBuf,
     freezer_db_path: PathBuf,
     eth2_config: Eth2Config,
-    events: Arc<Mutex<Bus<SignedBeaconBlockHash>>>,
+    events: Arc<EventSubscribers<T::EthSpec>>,
 ) -> Result<SocketAddr, hyper::Error> {
     let log = executor.log();
     let inner_log = log.clone();
+    let inner_executor = executor.clone();
     let eth2_config = Arc::new(eth2_config);
 
     // Define the function that will build the request handler.
@@ -76,6 +76,7 @@ pub fn start_server<T: BeaconChainTypes>(
         let db_path = db_path.clone();
         let freezer_db_path = freezer_db_path.clone();
         let events = events.clone();
+        let executor = inner_executor.clone();
 
         async move {
             Ok::<_, hyper::Error>(service_fn(move |req: Request<Body>| {
@@ -89,6 +90,7 @@ pub fn start_server<T: BeaconChainTypes>(
                     db_path.clone(),
                     freezer_db_path.clone(),
                     events.clone(),
+                    executor.clone(),
                 )
             }))
         }
@@ -1,18 +1,17 @@
 use crate::{
-    advanced, beacon, consensus, error::ApiError, helpers, lighthouse, metrics, network, node,
-    spec, validator, NetworkChannel,
+    advanced, beacon, consensus, error::ApiError, eth_v1, events, helpers, lighthouse, metrics,
+    network, node, spec, validator, NetworkChannel,
 };
-use beacon_chain::{BeaconChain, BeaconChainTypes};
-use bus::Bus;
+use beacon_chain::{events::EventSubscribers, BeaconChain, BeaconChainTypes};
+use environment::TaskExecutor;
 use eth2_config::Eth2Config;
 use eth2_libp2p::NetworkGlobals;
 use hyper::{Body, Error, Method, Request, Response};
-use parking_lot::Mutex;
 use slog::debug;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Instant;
-use types::{SignedBeaconBlockHash, Slot};
+use types::Slot;
 
 // Allowing more than 7 arguments.
 #[allow(clippy::too_many_arguments)]
@@ -25,7 +24,8 @@ pub async fn route<T: BeaconChainTypes>(
     local_log: slog::Logger,
     db_path: PathBuf,
     freezer_db_path: PathBuf,
-    events: Arc<Mutex<Bus<SignedBeaconBlockHash>>>,
+    events: Arc<EventSubscribers<T::EthSpec>>,
+    executor: TaskExecutor,
 ) -> Result<Response<Body>, Error> {
     metrics::inc_counter(&metrics::REQUEST_COUNT);
     let timer = metrics::start_timer(&metrics::REQUEST_RESPONSE_TIME);
@@ -66,10 +66,7 @@ pub async fn route<T: BeaconChainTypes>(
         (&Method::GET, "/beacon/block") => beacon::get_block::<T>(req, beacon_chain),
         (&Method::GET, "/beacon/block_root") => beacon::get_block_root::<T>(req, beacon_chain),
         (&Method::GET, "/beacon/fork") => beacon::get_fork::<T>(req, beacon_chain),
-        (&Method::GET, "/beacon/fork/stream") => {
-            let reader = events.lock().add_rx();
-            beacon::stream_forks::<T>(log, reader)
-        }
+        (&Method::GET, "/beacon/fork/stream") => beacon::stream_forks::<T>(executor, events),
         (&Method::GET, "/beacon/genesis_time") => beacon::get_genesis_time::<T>(req, beacon_chain),
         (&Method::GET, "/beacon/genesis_validators_root") => {
             beacon::get_genesis_validators_root::<T>(req, beacon_chain)
@@ -96,6 +93,9 @@ pub async fn route<T: BeaconChainTypes>(
         (&Method::POST, "/beacon/attester_slashing") => {
             beacon::attester_slashing::<T>(req, beacon_chain).await
         }
+        (&Method::POST, "/beacon/voluntary_exit") => {
+            beacon::voluntary_exit::<T>(req, beacon_chain, network_channel).await
+        }
 
         // Methods for Validator
         (&Method::POST, "/validator/duties") => {
@@ -166,6 +166,8 @@ pub async fn route<T: BeaconChainTypes>(
             metrics::get_prometheus::<T>(req, beacon_chain, db_path, freezer_db_path)
         }
 
+        (&Method::GET, "/events") => events::stream_events::<T>(req, executor, events),
+
         // Lighthouse specific
         (&Method::GET, "/lighthouse/syncing") => {
             lighthouse::syncing::<T::EthSpec>(req, network_globals)
@@ -178,6 +180,12 @@ pub async fn route<T: BeaconChainTypes>(
         (&Method::GET, "/lighthouse/connected_peers") => {
             lighthouse::connected_peers::<T::EthSpec>(req, network_globals)
         }
+
+        // Methods for the standard Ethereum API
+        (_, p) if p.starts_with(eth_v1::ETH_V1_PATH_PREFIX) => {
+            eth_v1::route::<T>(req, beacon_chain, network_globals, network_channel, log).await
+        }
+
         _ => Err(ApiError::NotFound(
             "Request path and/or method not found.".to_owned(),
         )),
@@ -201,7 +209,12 @@ pub async fn route<T: BeaconChainTypes>(
             Ok(response)
         }
         Err(e) => {
-            let error_response = e.into();
+            // The standard API has its own JSON error format.
+            let error_response = if path.starts_with(eth_v1::ETH_V1_PATH_PREFIX) {
+                e.into_error_message_response()
+            } else {
+                e.into()
+            };
 
             debug!(
                 local_log,
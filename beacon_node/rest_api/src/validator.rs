@@ -154,9 +154,8 @@ pub fn get_state_for_epoch<T: BeaconChainTypes>(
             (epoch + 2).start_slot(slots_per_epoch) - 1
         };
 
-        beacon_chain.state_at_slot(slot, config).map_err(|e| {
-            ApiError::ServerError(format!("Unable to load state for epoch {}: {:?}", epoch, e))
-        })
+        // A pruned state is reported as `NotFound` by the `BeaconChainError` conversion.
+        Ok(beacon_chain.state_at_slot(slot, config)?)
     }
 }
 
@@ -301,84 +300,98 @@ pub async fn publish_beacon_block<T: BeaconChainTypes>(
         .await
         .map_err(|e| ApiError::ServerError(format!("Unable to get request body: {:?}", e)))?;
 
-    serde_json::from_slice(&chunks).map_err(|e| {
-                    ApiError::BadRequest(format!("Unable to parse JSON into SignedBeaconBlock: {:?}", e))
-                })
-            .and_then(move |block: SignedBeaconBlock<T::EthSpec>| {
-                let slot = block.slot();
-                match beacon_chain.process_block(block.clone()) {
-                    Ok(block_root) => {
-                        // Block was processed, publish via gossipsub
-                        info!(
-                            log,
-                            "Block from local validator";
-                            "block_root" => format!("{}", block_root),
-                            "block_slot" => slot,
-                        );
-
-                        publish_beacon_block_to_network::<T>(network_chan, block)?;
-
-                        // Run the fork choice algorithm and enshrine a new canonical head, if
-                        // found.
-                        //
-                        // The new head may or may not be the block we just received.
-                        if let Err(e) = beacon_chain.fork_choice() {
-                            error!(
-                                log,
-                                "Failed to find beacon chain head";
-                                "error" => format!("{:?}", e)
-                            );
-                        } else {
-                            // In the best case, validators should produce blocks that become the
-                            // head.
-                            //
-                            // Potential reasons this may not be the case:
-                            //
-                            // - A quick re-org between block produce and publish.
-                            // - Excessive time between block produce and publish.
-                            // - A validator is using another beacon node to produce blocks and
-                            // submitting them here.
-                            if beacon_chain.head()?.beacon_block_root != block_root {
-                                warn!(
-                                    log,
-                                    "Block from validator is not head";
-                                    "desc" => "potential re-org",
-                                );
-
-                            }
-                        }
-
-                        Ok(())
-                    }
-                    Err(BlockError::BeaconChainError(e)) => {
-                        error!(
-                            log,
-                            "Error whilst processing block";
-                            "error" => format!("{:?}", e)
-                        );
-
-                        Err(ApiError::ServerError(format!(
-                            "Error while processing block: {:?}",
-                            e
-                        )))
-                    }
-                    Err(other) => {
-                        warn!(
-                            log,
-                            "Invalid block from local validator";
-                            "outcome" => format!("{:?}", other)
-                        );
-
-                        Err(ApiError::ProcessingError(format!(
-                            "The SignedBeaconBlock could not be processed and has not been published: {:?}",
-                            other
-                        )))
-                    }
-                }
+    serde_json::from_slice(&chunks)
+        .map_err(|e| {
+            ApiError::BadRequest(format!(
+                "Unable to parse JSON into SignedBeaconBlock: {:?}",
+                e
+            ))
+        })
+        .and_then(move |block: SignedBeaconBlock<T::EthSpec>| {
+            process_and_publish_block(&beacon_chain, network_chan, block, &log)
         })
         .and_then(|_| response_builder?.body_no_ssz(&()))
 }
 
+/// Processes a `SignedBeaconBlock` from a local validator, publishing it to the network if it is
+/// valid.
+pub fn process_and_publish_block<T: BeaconChainTypes>(
+    beacon_chain: &BeaconChain<T>,
+    network_chan: NetworkChannel<T::EthSpec>,
+    block: SignedBeaconBlock<T::EthSpec>,
+    log: &Logger,
+) -> Result<(), ApiError> {
+    let slot = block.slot();
+    match beacon_chain.process_block(block.clone()) {
+        Ok(block_root) => {
+            // Block was processed, publish via gossipsub
+            info!(
+                log,
+                "Block from local validator";
+                "block_root" => format!("{}", block_root),
+                "block_slot" => slot,
+            );
+
+            publish_beacon_block_to_network::<T>(network_chan, block)?;
+
+            // Run the fork choice algorithm and enshrine a new canonical head, if
+            // found.
+            //
+            // The new head may or may not be the block we just received.
+            if let Err(e) = beacon_chain.fork_choice() {
+                error!(
+                    log,
+                    "Failed to find beacon chain head";
+                    "error" => format!("{:?}", e)
+                );
+            } else {
+                // In the best case, validators should produce blocks that become the
+                // head.
+                //
+                // Potential reasons this may not be the case:
+                //
+                // - A quick re-org between block produce and publish.
+                // - Excessive time between block produce and publish.
+                // - A validator is using another beacon node to produce blocks and
+                // submitting them here.
+                if beacon_chain.head()?.beacon_block_root != block_root {
+                    warn!(
+                        log,
+                        "Block from validator is not head";
+                        "desc" => "potential re-org",
+                    );
+                }
+            }
+
+            Ok(())
+        }
+        Err(BlockError::BeaconChainError(e)) => {
+            error!(
+                log,
+                "Error whilst processing block";
+                "error" => format!("{:?}", e)
+            );
+
+            Err(ApiError::ServerError(format!(
+                "Error while processing block: {:?}",
+                e
+            )))
+        }
+        Err(other) => {
+            warn!(
+                log,
+                "Invalid block from local validator";
+                "outcome" => format!("{:?}", other)
+            );
+
+            Err(ApiError::ProcessingError(format!(
+                "The SignedBeaconBlock could not be processed and has not been published: {:?}",
+                other
+            )))
+        }
+    }
+}
+
 /// HTTP Handler to produce a new Attestation from the current state, ready to be signed by a validator.
 pub fn get_new_attestation<T: BeaconChainTypes>(
     req: Request<Body>,
@@ -467,7 +480,7 @@ pub async fn publish_attestations<T: BeaconChainTypes>(
 
 /// Processes an unaggregrated attestation that was included in a list of attestations with the
 /// index `i`.
-fn process_unaggregated_attestation<T: BeaconChainTypes>(
+pub fn process_unaggregated_attestation<T: BeaconChainTypes>(
     beacon_chain: &BeaconChain<T>,
     network_chan: NetworkChannel<T::EthSpec>,
     attestation: Attestation<T::EthSpec>,
@@ -582,7 +595,7 @@ pub async fn publish_aggregate_and_proofs<T: BeaconChainTypes>(
 
 /// Processes an aggregrated attestation that was included in a list of attestations with the index
 /// `i`.
-fn process_aggregated_attestation<T: BeaconChainTypes>(
+pub fn process_aggregated_attestation<T: BeaconChainTypes>(
     beacon_chain: &BeaconChain<T>,
     network_chan: NetworkChannel<T::EthSpec>,
     signed_aggregate: SignedAggregateAndProof<T::EthSpec>,
@@ -3,26 +3,47 @@
 #[macro_use]
 extern crate assert_matches;
 
-use beacon_chain::{BeaconChain, BeaconChainTypes, StateSkipConfig};
+use beacon_chain::{
+    events::EventSubscribers,
+    test_utils::{AttestationStrategy, BeaconChainHarness, BlockStrategy},
+    BeaconChain, BeaconChainTypes, StateSkipConfig,
+};
+use eth2_libp2p::{
+    discovery::{build_enr, CombinedKey, Keypair},
+    CombinedKeyExt, NetworkConfig, NetworkGlobals, PubsubMessage,
+};
+use futures::StreamExt;
+use http::StatusCode;
+use network::NetworkMessage;
 use node_test_rig::{
     environment::{Environment, EnvironmentBuilder},
     testing_client_config, ClientConfig, ClientGenesis, LocalBeaconNode,
 };
 use remote_beacon_node::{
-    Committee, HeadBeaconBlock, PersistedOperationPool, PublishStatus, ValidatorResponse,
+    events::{Event, EventTopic},
+    Committee, HeadBeaconBlock, HttpClient, PersistedOperationPool, PublishStatus,
+    ValidatorResponse,
 };
+use rest_api::NetworkInfo;
+use rest_types::events::{BlockEvent, HeadEvent};
 use rest_types::ValidatorDutyBytes;
 use std::convert::TryInto;
+use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
+use store::{HotColdDB, StoreConfig};
+use tempfile::tempdir;
 use types::{
     test_utils::{
         build_double_vote_attester_slashing, build_proposer_slashing,
-        generate_deterministic_keypair, AttesterSlashingTestTask, ProposerSlashingTestTask,
+        generate_deterministic_keypair, generate_deterministic_keypairs, AttesterSlashingTestTask,
+        ProposerSlashingTestTask,
     },
-    BeaconBlock, BeaconState, ChainSpec, Domain, Epoch, EthSpec, MinimalEthSpec, PublicKey,
-    RelativeEpoch, Signature, SignedAggregateAndProof, SignedBeaconBlock, SignedRoot, Slot,
-    Validator,
+    BeaconBlock, BeaconState, ChainSpec, Domain, EnrForkId, Epoch, EthSpec, MinimalEthSpec,
+    PublicKey, RelativeEpoch, Signature, SignedAggregateAndProof, SignedBeaconBlock, SignedRoot,
+    Slot, Validator, VoluntaryExit,
 };
+use url::Url;
 use version;
 
 type E = MinimalEthSpec;
@@ -1141,6 +1162,108 @@ fn attester_slashing() {
     assert_eq!(attester_slashing, attester_slashings[0]);
 }
 
+#[test]
+fn voluntary_exit() {
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let remote_node = node.remote_node().expect("should produce remote node");
+    let chain = node
+        .client
+        .beacon_chain()
+        .expect("node should have beacon chain");
+
+    let state = chain
+        .head()
+        .expect("should have retrieved state")
+        .beacon_state;
+    let spec = &chain.spec;
+
+    let validator_index = 0;
+    let keypair = generate_deterministic_keypair(validator_index);
+
+    // At genesis no validator has been active for `persistent_committee_period`, so the exit
+    // must be rejected.
+    let exit = VoluntaryExit {
+        epoch: state.current_epoch(),
+        validator_index: validator_index as u64,
+    }
+    .sign(
+        &keypair.sk,
+        &state.fork,
+        state.genesis_validators_root,
+        spec,
+    );
+
+    let result = env
+        .runtime()
+        .block_on(remote_node.http.beacon().voluntary_exit(exit));
+    assert!(result.is_err());
+
+    assert!(chain.op_pool.get_voluntary_exits(&state, spec).is_empty());
+}
+
+#[test]
+fn valid_voluntary_exit_is_published_and_pooled() {
+    let mut env = build_env();
+    let db_path = tempdir().expect("should create temporary db dir");
+
+    let mut harness = BeaconChainHarness::new(MinimalEthSpec, generate_deterministic_keypairs(8));
+    // Allow validators to exit as soon as they are active.
+    harness.chain.spec.persistent_committee_period = 0;
+    harness.advance_slot();
+    harness.extend_chain(
+        E::slots_per_epoch() as usize,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::AllValidators,
+    );
+    let beacon_chain = Arc::new(harness.chain);
+
+    let (_server_url, client, mut network_recv) = start_harness_server(
+        &mut env,
+        beacon_chain.clone(),
+        db_path.path().join("hot_db"),
+        db_path.path().join("cold_db"),
+    );
+
+    let state = beacon_chain
+        .head()
+        .expect("should have retrieved state")
+        .beacon_state;
+    let spec = &beacon_chain.spec;
+
+    let validator_index = 0;
+    let keypair = generate_deterministic_keypair(validator_index);
+
+    let exit = VoluntaryExit {
+        epoch: state.current_epoch(),
+        validator_index: validator_index as u64,
+    }
+    .sign(
+        &keypair.sk,
+        &state.fork,
+        state.genesis_validators_root,
+        spec,
+    );
+
+    env.runtime()
+        .block_on(client.beacon().voluntary_exit(exit.clone()))
+        .expect("should accept valid exit");
+
+    match env.runtime().block_on(network_recv.recv()) {
+        Some(NetworkMessage::Publish { messages }) => assert_eq!(
+            messages,
+            vec![PubsubMessage::VoluntaryExit(Box::new(exit.clone()))]
+        ),
+        other => panic!("exit should be published to the network, got {:?}", other),
+    }
+
+    assert_eq!(
+        beacon_chain.op_pool.get_voluntary_exits(&state, spec),
+        vec![exit]
+    );
+}
+
 mod validator_attestation {
     use super::*;
     use http::StatusCode;
@@ -1255,6 +1378,112 @@ mod validator_attestation {
     }
 }
 
+#[test]
+fn events_stream() {
+    let mut env = build_env();
+
+    let spec = &E::default_spec();
+
+    let mut config = testing_client_config();
+    config.genesis = ClientGenesis::Interop {
+        validator_count: 8,
+        genesis_time: 13_371_337,
+    };
+
+    let node = build_node(&mut env, config);
+    let remote_node = node.remote_node().expect("should produce remote node");
+
+    let beacon_chain = node
+        .client
+        .beacon_chain()
+        .expect("client should have beacon chain");
+
+    let events = env
+        .runtime()
+        .block_on(
+            remote_node
+                .http
+                .events()
+                .stream(&[EventTopic::Block, EventTopic::Head]),
+        )
+        .expect("should subscribe to events");
+    let mut events = Box::pin(events);
+
+    let slot = Slot::new(1);
+    let randao_reveal = get_randao_reveal(beacon_chain.clone(), slot, spec);
+    let block = env
+        .runtime()
+        .block_on(
+            remote_node
+                .http
+                .validator()
+                .produce_block(slot, randao_reveal),
+        )
+        .expect("should fetch block from http api");
+    let signed_block = sign_block(beacon_chain.clone(), block, spec);
+    let block_root = signed_block.canonical_root();
+
+    env.runtime()
+        .block_on(remote_node.http.validator().publish_block(signed_block))
+        .expect("should publish block");
+
+    let received = env.runtime().block_on(async {
+        let mut received = vec![];
+        for _ in 0..2 {
+            let event = events
+                .next()
+                .await
+                .expect("stream should not end")
+                .expect("event should be valid");
+            received.push(event);
+        }
+        received
+    });
+
+    let head = beacon_chain.head_info().expect("should get head");
+    assert_eq!(head.block_root, block_root);
+
+    assert!(received.contains(&Event::Block(BlockEvent {
+        slot,
+        block: block_root
+    })));
+    assert!(received.contains(&Event::Head(HeadEvent {
+        slot,
+        block: block_root,
+        state: head.state_root,
+        previous_block: beacon_chain.genesis_block_root,
+        reorg: false,
+    })));
+}
+
+#[test]
+fn events_unknown_topic() {
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let remote_node = node.remote_node().expect("should produce remote node");
+
+    let socket_addr = node
+        .client
+        .http_listen_addr()
+        .expect("node should have a http server");
+    let url = Url::parse(&format!("http://{}/events", socket_addr)).expect("should parse url");
+
+    let result = env.runtime().block_on(
+        remote_node
+            .http
+            .json_get::<()>(url, vec![("topics".into(), "head,unknown".into())]),
+    );
+
+    assert_matches!(
+        result.expect_err("should not succeed"),
+        remote_beacon_node::Error::DidNotSucceed { status, body } => {
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+            assert_eq!(body, "Unknown event topic: unknown".to_owned());
+        }
+    );
+}
+
 #[cfg(target_os = "linux")]
 #[test]
 fn get_health() {
@@ -1267,3 +1496,284 @@ fn get_health() {
         .block_on(remote_node.http.node().get_health())
         .unwrap();
 }
+
+mod eth_v1 {
+    use super::*;
+    use remote_beacon_node::eth_v1::{BlockId, StateId};
+    use remote_beacon_node::Error::ServerMessage;
+    use types::Hash256;
+
+    #[test]
+    fn get_genesis() {
+        let mut env = build_env();
+
+        let node = build_node(&mut env, testing_client_config());
+        let remote_node = node.remote_node().expect("should produce remote node");
+        let chain = node
+            .client
+            .beacon_chain()
+            .expect("node should have beacon chain");
+        let head_info = chain.head_info().expect("should get head info");
+
+        let genesis = env
+            .runtime()
+            .block_on(remote_node.http.eth_v1().get_genesis())
+            .expect("should fetch genesis from http api");
+
+        assert_eq!(genesis.genesis_time, head_info.genesis_time);
+        assert_eq!(
+            genesis.genesis_validators_root,
+            head_info.genesis_validators_root
+        );
+        assert_eq!(
+            genesis.genesis_fork_version,
+            chain.spec.genesis_fork_version
+        );
+    }
+
+    #[test]
+    fn get_state_root() {
+        let mut env = build_env();
+
+        let node = build_node(&mut env, testing_client_config());
+        let remote_node = node.remote_node().expect("should produce remote node");
+        let chain = node
+            .client
+            .beacon_chain()
+            .expect("node should have beacon chain");
+        let head_state_root = chain.head_info().expect("should get head info").state_root;
+
+        for state_id in &[
+            StateId::Head,
+            StateId::Genesis,
+            StateId::Slot(Slot::new(0)),
+            StateId::Root(head_state_root),
+        ] {
+            let root = env
+                .runtime()
+                .block_on(remote_node.http.eth_v1().get_state_root(*state_id))
+                .expect("should fetch state root from http api");
+
+            assert_eq!(root, head_state_root, "state root for {}", state_id);
+        }
+    }
+
+    #[test]
+    fn get_block() {
+        let mut env = build_env();
+
+        let node = build_node(&mut env, testing_client_config());
+        let remote_node = node.remote_node().expect("should produce remote node");
+        let chain = node
+            .client
+            .beacon_chain()
+            .expect("node should have beacon chain");
+        let head_block_root = chain.head_info().expect("should get head info").block_root;
+        let head_block = chain
+            .get_block(&head_block_root)
+            .expect("should read block")
+            .expect("head block should exist");
+
+        for block_id in &[
+            BlockId::Head,
+            BlockId::Genesis,
+            BlockId::Slot(Slot::new(0)),
+            BlockId::Root(head_block_root),
+        ] {
+            let block = env
+                .runtime()
+                .block_on(remote_node.http.eth_v1().get_block(*block_id))
+                .expect("should fetch block from http api");
+            assert_eq!(block, head_block, "block for {}", block_id);
+
+            let root = env
+                .runtime()
+                .block_on(remote_node.http.eth_v1().get_block_root(*block_id))
+                .expect("should fetch block root from http api");
+            assert_eq!(root, head_block_root, "block root for {}", block_id);
+        }
+    }
+
+    #[test]
+    fn unknown_state_is_error_message() {
+        let mut env = build_env();
+
+        let node = build_node(&mut env, testing_client_config());
+        let remote_node = node.remote_node().expect("should produce remote node");
+
+        let result = env.runtime().block_on(
+            remote_node
+                .http
+                .eth_v1()
+                .get_state_fork(StateId::Root(Hash256::repeat_byte(42))),
+        );
+
+        assert_matches!(
+            result.expect_err("should not find state"),
+            ServerMessage(message) => {
+                assert_eq!(message.code, 404);
+            }
+        );
+    }
+
+    #[test]
+    fn unknown_state_root_is_not_found() {
+        let mut env = build_env();
+
+        let node = build_node(&mut env, testing_client_config());
+        let remote_node = node.remote_node().expect("should produce remote node");
+
+        let result = env.runtime().block_on(
+            remote_node
+                .http
+                .eth_v1()
+                .get_state_root(StateId::Root(Hash256::repeat_byte(42))),
+        );
+
+        assert_matches!(
+            result.expect_err("should not find state root"),
+            ServerMessage(message) => {
+                assert_eq!(message.code, 404);
+            }
+        );
+    }
+}
+
+/// Serves the HTTP API for `beacon_chain`, returning the server URL, a client for it and the
+/// receiving end of the network channel.
+fn start_harness_server<T: BeaconChainTypes<EthSpec = E>>(
+    env: &mut Environment<E>,
+    beacon_chain: Arc<BeaconChain<T>>,
+    db_path: PathBuf,
+    freezer_db_path: PathBuf,
+) -> (
+    String,
+    HttpClient<E>,
+    tokio::sync::mpsc::UnboundedReceiver<NetworkMessage<E>>,
+) {
+    let context = env.core_context();
+    let log = context.log().clone();
+
+    let network_config = NetworkConfig::default();
+    let enr_key = CombinedKey::from_libp2p(&Keypair::generate_secp256k1()).unwrap();
+    let enr = build_enr::<E>(&enr_key, &network_config, EnrForkId::default()).unwrap();
+    let (network_chan, network_recv) = tokio::sync::mpsc::unbounded_channel();
+    let network_info = NetworkInfo {
+        network_globals: Arc::new(NetworkGlobals::new(enr, 0, 0, &log)),
+        network_chan,
+    };
+
+    let mut api_config = rest_api::Config::default();
+    api_config.port = 0;
+
+    let listen_addr = env
+        .runtime()
+        .enter(|| {
+            rest_api::start_server(
+                context.executor.clone(),
+                &api_config,
+                beacon_chain,
+                network_info,
+                db_path,
+                freezer_db_path,
+                context.eth2_config.clone(),
+                Arc::new(EventSubscribers::new()),
+            )
+        })
+        .expect("should start http server");
+
+    let server_url = format!("http://{}:{}", listen_addr.ip(), listen_addr.port());
+    let client = HttpClient::new(server_url.clone(), Duration::from_secs(30))
+        .expect("should build http client");
+
+    (server_url, client, network_recv)
+}
+
+#[test]
+fn pruned_state_returns_not_found() {
+    let mut env = build_env();
+    let context = env.core_context();
+    let log = context.log().clone();
+
+    // Build a finalized chain in a database which does not retain historic states.
+    let db_path = tempdir().expect("should create temporary db dir");
+    let hot_path = db_path.path().join("hot_db");
+    let cold_path = db_path.path().join("cold_db");
+    let store_config = StoreConfig {
+        max_restore_points: Some(0),
+        ..StoreConfig::default()
+    };
+    let store = Arc::new(
+        HotColdDB::open(
+            &hot_path,
+            &cold_path,
+            store_config,
+            E::default_spec(),
+            log.clone(),
+        )
+        .expect("disk store should initialize"),
+    );
+
+    let harness = BeaconChainHarness::new_with_disk_store(
+        MinimalEthSpec,
+        store.clone(),
+        generate_deterministic_keypairs(24),
+    );
+    harness.advance_slot();
+    harness.extend_chain(
+        E::slots_per_epoch() as usize * 5,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::AllValidators,
+    );
+    assert!(store.get_split_slot() > E::slots_per_epoch() * 2);
+    let beacon_chain = Arc::new(harness.chain);
+
+    let genesis_state_root = beacon_chain
+        .get_block(&beacon_chain.genesis_block_root)
+        .expect("should read genesis block")
+        .expect("genesis block should exist")
+        .state_root();
+
+    let (server_url, client, _network_recv) =
+        start_harness_server(&mut env, beacon_chain, hot_path, cold_path);
+
+    let assert_not_found = |result: Result<(), remote_beacon_node::Error>| {
+        assert_matches!(
+            result.expect_err("should not find a pruned state"),
+            remote_beacon_node::Error::DidNotSucceed { status, .. } => {
+                assert_eq!(status, StatusCode::NOT_FOUND);
+            }
+        );
+    };
+
+    assert_not_found(
+        env.runtime()
+            .block_on(
+                client.json_get::<BeaconState<E>>(
+                    Url::parse(&format!("{}/beacon/state/genesis", server_url))
+                        .expect("should be valid endpoint"),
+                    vec![],
+                ),
+            )
+            .map(|_| ()),
+    );
+    assert_not_found(
+        env.runtime()
+            .block_on(client.beacon().get_state_by_slot(Slot::new(1)))
+            .map(|_| ()),
+    );
+    assert_not_found(
+        env.runtime()
+            .block_on(client.beacon().get_all_validators(Some(genesis_state_root)))
+            .map(|_| ()),
+    );
+    assert_not_found(
+        env.runtime()
+            .block_on(
+                client
+                    .validator()
+                    .get_duties(Epoch::new(0), &[generate_deterministic_keypair(0).pk]),
+            )
+            .map(|_| ()),
+    );
+}
@@ -222,6 +222,23 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                        DO NOT DECREASE AFTER INITIALIZATION. [default: 2048 (mainnet) or 64 (minimal)]")
                 .takes_value(true)
         )
+        .arg(
+            Arg::with_name("max-restore-points")
+                .long("max-restore-points")
+                .value_name("COUNT")
+                .help("Only retain this many of the most recent restore points in the freezer DB, \
+                       pruning older historic states. States prior to the oldest restore point \
+                       cannot be loaded. [default: retain all restore points]")
+                .takes_value(true)
+        )
+        .arg(
+            Arg::with_name("no-history")
+                .long("no-history")
+                .help("Do not store any historic states in the freezer DB, only blocks and block \
+                       roots. Equivalent to --max-restore-points 0.")
+                .takes_value(false)
+                .conflicts_with("max-restore-points")
+        )
         .arg(
             Arg::with_name("block-cache-size")
                 .long("block-cache-size")
@@ -230,6 +247,78 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 .takes_value(true)
         )
 
+        /*
+         * Weak subjectivity checkpoint sync.
+         */
+        .arg(
+            Arg::with_name("checkpoint-state")
+                .long("checkpoint-state")
+                .value_name("STATE_SSZ")
+                .help("Path to a trusted, finalized BeaconState (SSZ) from which to start the node \
+                       instead of genesis. Must lie on an epoch boundary and be the post-state of \
+                       --checkpoint-block, advanced through any skipped slots. Ignored if the \
+                       database already contains a chain.")
+                .requires("checkpoint-block")
+                .takes_value(true)
+        )
+        .arg(
+            Arg::with_name("checkpoint-block")
+                .long("checkpoint-block")
+                .value_name("BLOCK_SSZ")
+                .help("Path to the trusted, finalized SignedBeaconBlock (SSZ) which corresponds \
+                       to --checkpoint-state.")
+                .requires("checkpoint-state")
+                .takes_value(true)
+        )
+        .arg(
+            Arg::with_name("checkpoint-sync-url")
+                .long("checkpoint-sync-url")
+                .value_name("HTTP-ENDPOINT")
+                .help("The HTTP API of a trusted beacon node from which to fetch the latest \
+                       finalized block and state, starting the node from that checkpoint instead \
+                       of genesis. Ignored if the database already contains a chain.")
+                .conflicts_with("checkpoint-state")
+                .takes_value(true)
+        )
+
+        /*
+         * Slasher.
+         */
+        .arg(
+            Arg::with_name("slasher")
+                .long("slasher")
+                .help("If present, the node will record every attestation and block it verifies \
+                       and search them for slashable behaviour. Any slashings found are added to \
+                       the op pool and published on gossip.")
+                .takes_value(false)
+        )
+        .arg(
+            Arg::with_name("slasher-dir")
+                .long("slasher-dir")
+                .value_name("DIR")
+                .help("Data directory for the slasher database. [default: <datadir>/slasher_db]")
+                .requires("slasher")
+                .takes_value(true)
+        )
+        .arg(
+            Arg::with_name("slasher-history-length")
+                .long("slasher-history-length")
+                .value_name("EPOCHS")
+                .help("The number of epochs of attestations and blocks kept by the slasher. \
+                       [default: 4096]")
+                .requires("slasher")
+                .takes_value(true)
+        )
+        .arg(
+            Arg::with_name("slasher-update-period")
+                .long("slasher-update-period")
+                .value_name("SECONDS")
+                .help("The number of seconds between processing batches of attestations and \
+                       blocks in the slasher. [default: 12]")
+                .requires("slasher")
+                .takes_value(true)
+        )
+
         /*
          * Purge.
          */
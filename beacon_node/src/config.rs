@@ -1,6 +1,6 @@
 use beacon_chain::builder::PUBKEY_CACHE_FILENAME;
 use clap::ArgMatches;
-use clap_utils::BAD_TESTNET_DIR_MESSAGE;
+use clap_utils::{parse_optional, BAD_TESTNET_DIR_MESSAGE};
 use client::{config::DEFAULT_DATADIR, ClientConfig, ClientGenesis};
 use eth2_libp2p::{Enr, Multiaddr};
 use eth2_testnet_config::Eth2TestnetConfig;
@@ -17,6 +17,11 @@ use types::{ChainSpec, EthSpec};
 pub const CLIENT_CONFIG_FILENAME: &str = "beacon-node.toml";
 pub const BEACON_NODE_DIR: &str = "beacon";
 pub const NETWORK_DIR: &str = "network";
+/// Default directory name for the slasher database under the beacon node data dir.
+pub const DEFAULT_SLASHER_DB_DIR: &str = "slasher_db";
+
+const CHECKPOINT_SYNC_REQUIRES_GENESIS: &str =
+    "Checkpoint sync requires a testnet directory which contains a genesis state";
 
 /// Gets the fully-initialized global client.
 ///
@@ -305,12 +310,48 @@ pub fn get_config<E: EthSpec>(
         );
     }
 
+    if cli_args.is_present("no-history") {
+        client_config.store.max_restore_points = Some(0);
+    } else if let Some(max_restore_points) = parse_optional(cli_args, "max-restore-points")? {
+        client_config.store.max_restore_points = Some(max_restore_points);
+    }
+
     if let Some(block_cache_size) = cli_args.value_of("block-cache-size") {
         client_config.store.block_cache_size = block_cache_size
             .parse()
             .map_err(|_| "block-cache-size is not a valid integer".to_string())?;
     }
 
+    /*
+     * Slasher
+     */
+    if cli_args.is_present("slasher") {
+        let slasher_dir = if let Some(slasher_dir) = cli_args.value_of("slasher-dir") {
+            PathBuf::from(slasher_dir)
+        } else {
+            client_config
+                .get_data_dir()
+                .ok_or_else(|| "Unable to locate user home directory")?
+                .join(DEFAULT_SLASHER_DB_DIR)
+        };
+
+        let mut slasher_config = slasher::Config::new(slasher_dir);
+
+        if let Some(history_length) = parse_optional(cli_args, "slasher-history-length")? {
+            slasher_config.history_length = history_length;
+        }
+
+        if let Some(update_period) = parse_optional(cli_args, "slasher-update-period")? {
+            slasher_config.update_period = update_period;
+        }
+
+        slasher_config
+            .validate()
+            .map_err(|e| format!("Invalid slasher configuration: {:?}", e))?;
+
+        client_config.slasher = Some(slasher_config);
+    }
+
     if spec_constants != client_config.spec_constants {
         crit!(log, "Specification constants do not match.";
               "client_config" => client_config.spec_constants.to_string(),
@@ -358,12 +399,40 @@ pub fn get_config<E: EthSpec>(
         client_config.network.boot_nodes.append(&mut boot_nodes)
     }
 
-    if let Some(genesis_state) = eth2_testnet_config.genesis_state {
-        // Note: re-serializing the genesis state is not so efficient, however it avoids adding
-        // trait bounds to the `ClientGenesis` enum. This would have significant flow-on
-        // effects.
+    // Note: re-serializing the genesis state is not so efficient, however it avoids adding
+    // trait bounds to the `ClientGenesis` enum. This would have significant flow-on
+    // effects.
+    let genesis_state_bytes = eth2_testnet_config
+        .genesis_state
+        .map(|genesis_state| genesis_state.as_ssz_bytes());
+
+    /*
+     * Weak subjectivity checkpoint sync.
+     */
+    if let (Some(state_path), Some(block_path)) = (
+        cli_args.value_of("checkpoint-state"),
+        cli_args.value_of("checkpoint-block"),
+    ) {
+        let anchor_state_bytes = fs::read(state_path)
+            .map_err(|e| format!("Unable to read checkpoint state {}: {:?}", state_path, e))?;
+        let anchor_block_bytes = fs::read(block_path)
+            .map_err(|e| format!("Unable to read checkpoint block {}: {:?}", block_path, e))?;
+
+        client_config.genesis = ClientGenesis::WeakSubjSszBytes {
+            anchor_state_bytes,
+            anchor_block_bytes,
+            genesis_state_bytes: genesis_state_bytes
+                .ok_or_else(|| CHECKPOINT_SYNC_REQUIRES_GENESIS.to_string())?,
+        };
+    } else if let Some(url) = cli_args.value_of("checkpoint-sync-url") {
+        client_config.genesis = ClientGenesis::CheckpointSyncUrl {
+            url: url.to_string(),
+            genesis_state_bytes: genesis_state_bytes
+                .ok_or_else(|| CHECKPOINT_SYNC_REQUIRES_GENESIS.to_string())?,
+        };
+    } else if let Some(genesis_state_bytes) = genesis_state_bytes {
         client_config.genesis = ClientGenesis::SszBytes {
-            genesis_state_bytes: genesis_state.as_ssz_bytes(),
+            genesis_state_bytes,
         };
     } else {
         client_config.genesis = ClientGenesis::DepositContract;
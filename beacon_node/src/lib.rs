@@ -120,7 +120,8 @@ impl<E: EthSpec> ProductionBeaconNode<E> {
         let builder = builder
             .build_beacon_chain()?
             .network(&mut client_config.network)?
-            .notifier()?;
+            .notifier()?
+            .slasher_service()?;
 
         let builder = if client_config.rest_api.enabled {
             builder.http_server(&client_config, &http_eth2_config, events)?
@@ -375,6 +375,63 @@ pub fn store_updated_vector<F: Field<E>, E: EthSpec, S: KeyValueStore<E>>(
     Ok(())
 }
 
+/// Write `values` to the vector for `field`, beginning at vector index `start_vindex`.
+///
+/// Useful for filling in values that are not known from any stored state, e.g. the block roots of
+/// blocks back-filled after starting from a checkpoint. Values already present in the database
+/// must agree with those being written.
+pub fn store_vector_values<F: Field<E>, E: EthSpec, S: KeyValueStore<E>>(
+    _: F,
+    store: &S,
+    start_vindex: usize,
+    values: &[F::Value],
+) -> Result<(), Error> {
+    if values.is_empty() {
+        return Ok(());
+    }
+
+    let chunk_size = F::chunk_size();
+    let end_vindex = start_vindex + values.len();
+    let start_cindex = start_vindex / chunk_size;
+    let end_cindex = (end_vindex - 1) / chunk_size;
+
+    for chunk_index in start_cindex..=end_cindex {
+        let chunk_key = &chunk_key(chunk_index as u64)[..];
+
+        let existing_chunk =
+            Chunk::<F::Value>::load(store, F::column(), chunk_key)?.unwrap_or_else(Chunk::default);
+
+        let mut new_chunk = existing_chunk.clone();
+        new_chunk.values.resize(chunk_size, F::Value::default());
+
+        for (i, new_value) in new_chunk.values.iter_mut().enumerate() {
+            let vindex = chunk_index * chunk_size + i;
+            if vindex < start_vindex || vindex >= end_vindex {
+                continue;
+            }
+
+            let vector_value = &values[vindex - start_vindex];
+            if *new_value != *vector_value && *new_value != F::Value::default() {
+                return Err(ChunkError::Inconsistent {
+                    field: F::column(),
+                    chunk_index,
+                    existing_value: format!("{:?}", new_value),
+                    new_value: format!("{:?}", vector_value),
+                }
+                .into());
+            }
+
+            *new_value = vector_value.clone();
+        }
+
+        if new_chunk != existing_chunk {
+            new_chunk.store(store, F::column(), chunk_key)?;
+        }
+    }
+
+    Ok(())
+}
+
 fn store_range<F, E, S, I>(
     _: F,
     range: I,
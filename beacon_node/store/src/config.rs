@@ -11,6 +11,11 @@ pub struct StoreConfig {
     pub slots_per_restore_point: u64,
     /// Maximum number of blocks to store in the in-memory block cache.
     pub block_cache_size: usize,
+    /// If set, only this many of the most recent restore points are retained in the freezer
+    /// database, and older historic states are pruned.
+    ///
+    /// With `Some(0)` the freezer stores no states at all, only block roots.
+    pub max_restore_points: Option<u64>,
 }
 
 impl Default for StoreConfig {
@@ -19,6 +24,7 @@ impl Default for StoreConfig {
             // Safe default for tests, shouldn't ever be read by a CLI node.
             slots_per_restore_point: MinimalEthSpec::slots_per_historical_root() as u64,
             block_cache_size: DEFAULT_BLOCK_CACHE_SIZE,
+            max_restore_points: None,
         }
     }
 }
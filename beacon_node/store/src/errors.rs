@@ -1,7 +1,8 @@
 use crate::chunked_vector::ChunkError;
 use crate::hot_cold_store::HotColdDBError;
+use crate::metadata::SchemaVersion;
 use ssz::DecodeError;
-use types::{BeaconStateError, Hash256};
+use types::{BeaconStateError, Hash256, Slot};
 
 pub type Result<T> = std::result::Result<T, Error>;
 
@@ -12,10 +13,33 @@ pub enum Error {
     BeaconStateError(BeaconStateError),
     PartialBeaconStateError,
     HotColdDBError(HotColdDBError),
-    DBError { message: String },
+    DBError {
+        message: String,
+    },
     RlpError(String),
     BlockNotFound(Hash256),
     NoContinuationData,
+    /// A historical block was stored on a database that was not initialized from a checkpoint.
+    MissingAnchorInfo,
+    /// A historical block does not form a chain with the oldest block in the database.
+    HistoricalBlockMismatch {
+        expected_root: Hash256,
+        block_root: Hash256,
+    },
+    /// The database was written by a newer version of Lighthouse.
+    SchemaVersionTooNew {
+        on_disk: SchemaVersion,
+        supported: SchemaVersion,
+    },
+    /// There is no migration between two schema versions.
+    UnsupportedSchemaMigration {
+        from: SchemaVersion,
+        to: SchemaVersion,
+    },
+    /// The historic state at `slot` has been pruned from the freezer database.
+    StatePruned {
+        slot: Slot,
+    },
 }
 
 impl From<DecodeError> for Error {
@@ -1,10 +1,13 @@
 use crate::chunked_vector::{
-    store_updated_vector, BlockRoots, HistoricalRoots, RandaoMixes, StateRoots,
+    store_updated_vector, store_vector_values, BlockRoots, HistoricalRoots, RandaoMixes, StateRoots,
 };
 use crate::config::StoreConfig;
 use crate::forwards_iter::HybridForwardsBlockRootsIterator;
 use crate::impls::beacon_state::{get_full_state, store_full_state};
 use crate::iter::{ParentRootBlockIterator, StateRootsIterator};
+use crate::metadata::{
+    AnchorInfo, SchemaVersion, ANCHOR_INFO_KEY, LEGACY_SCHEMA_VERSION, SCHEMA_VERSION_KEY,
+};
 use crate::metrics;
 use crate::{
     leveldb_store::LevelDB, DBColumn, Error, ItemStore, KeyValueStore, PartialBeaconState, Store,
@@ -38,13 +41,16 @@ pub struct HotColdDB<E: EthSpec> {
     /// States with slots less than `split.slot` are in the cold DB, while states with slots
     /// greater than or equal are in the hot DB.
     split: RwLock<Split>,
+    /// The anchor of a database initialized from a weak subjectivity checkpoint, tracking the
+    /// progress of historical block back-filling.
+    anchor_info: RwLock<Option<AnchorInfo>>,
     config: StoreConfig,
     /// Cold database containing compact historical data.
-    pub(crate) cold_db: LevelDB<E>,
+    pub cold_db: LevelDB<E>,
     /// Hot database containing duplicated but quick-to-access recent data.
     ///
     /// The hot database also contains all blocks.
-    pub(crate) hot_db: LevelDB<E>,
+    pub hot_db: LevelDB<E>,
     /// LRU cache of deserialized blocks. Updated whenever a block is loaded.
     block_cache: Mutex<LruCache<Hash256, SignedBeaconBlock<E>>>,
     /// Chain spec.
@@ -252,6 +258,87 @@ impl<E: EthSpec> Store<E> for HotColdDB<E> {
         }
         Ok(())
     }
+
+    /// Move the split point to a weak subjectivity anchor.
+    ///
+    /// The freezer starts out empty: states prior to the anchor are simply unavailable, and blocks
+    /// prior to the anchor are added by `put_historical_blocks`. If the anchor block precedes the
+    /// anchor slot, its root is recorded for the skipped slots in between.
+    fn init_anchor(
+        &self,
+        anchor_state_root: Hash256,
+        anchor_block_root: Hash256,
+        anchor_info: AnchorInfo,
+    ) -> Result<(), Error> {
+        let current_split_slot = self.get_split_slot();
+
+        if current_split_slot != 0 {
+            return Err(HotColdDBError::FreezeSlotError {
+                current_split_slot,
+                proposed_split_slot: anchor_info.anchor_slot,
+            }
+            .into());
+        }
+
+        *self.split.write() = Split {
+            slot: anchor_info.anchor_slot,
+            state_root: anchor_state_root,
+        };
+        self.store_split()?;
+
+        let num_skipped_slots =
+            (anchor_info.anchor_slot - anchor_info.oldest_block_slot).as_usize();
+        store_vector_values(
+            BlockRoots,
+            &self.cold_db,
+            anchor_info.oldest_block_slot.as_usize(),
+            &vec![anchor_block_root; num_skipped_slots],
+        )?;
+
+        self.store_anchor_info(anchor_info)
+    }
+
+    fn get_anchor_info(&self) -> Option<AnchorInfo> {
+        *self.anchor_info.read()
+    }
+
+    /// Store historical blocks in the hot database and their roots in the freezer, so that the
+    /// frozen portion of the chain can be iterated and served to peers.
+    fn put_historical_blocks(
+        &self,
+        blocks: Vec<(Hash256, SignedBeaconBlock<E>)>,
+    ) -> Result<(), Error> {
+        let anchor_info = self
+            .get_anchor_info()
+            .ok_or_else(|| Error::MissingAnchorInfo)?;
+        let new_anchor_info = anchor_info.extend_with_historical_blocks(&blocks)?;
+
+        // Each block's root is the block root for every slot from its own up until the slot of
+        // the next (newer) block.
+        let mut block_roots = vec![];
+        let mut newer_block_slot = anchor_info.oldest_block_slot;
+        for (block_root, block) in &blocks {
+            let num_slots = newer_block_slot
+                .as_usize()
+                .saturating_sub(block.slot().as_usize());
+            block_roots.extend(std::iter::repeat(*block_root).take(num_slots));
+            newer_block_slot = block.slot();
+        }
+        block_roots.reverse();
+
+        for (block_root, block) in blocks {
+            self.hot_db.put(&block_root, &block)?;
+        }
+
+        store_vector_values(
+            BlockRoots,
+            &self.cold_db,
+            new_anchor_info.oldest_block_slot.as_usize(),
+            &block_roots,
+        )?;
+
+        self.store_anchor_info(new_anchor_info)
+    }
 }
 
 impl<E: EthSpec> HotColdDB<E> {
@@ -269,6 +356,7 @@ impl<E: EthSpec> HotColdDB<E> {
 
         let db = HotColdDB {
             split: RwLock::new(Split::default()),
+            anchor_info: RwLock::new(None),
             cold_db: LevelDB::open(cold_path)?,
             hot_db: LevelDB::open(hot_path)?,
             block_cache: Mutex::new(LruCache::new(config.block_cache_size)),
@@ -283,6 +371,7 @@ impl<E: EthSpec> HotColdDB<E> {
         if let Some(split) = db.load_split()? {
             *db.split.write() = split;
         }
+        *db.anchor_info.write() = db.load_anchor_info()?;
         Ok(db)
     }
 
@@ -366,6 +455,16 @@ impl<E: EthSpec> HotColdDB<E> {
             return Ok(());
         }
 
+        // Without any restore points only the block roots are required, which are always stored
+        // so that blocks can be looked up by slot.
+        let db = &self.cold_db;
+        store_updated_vector(BlockRoots, db, state, &self.spec)?;
+        store_updated_vector(HistoricalRoots, db, state, &self.spec)?;
+
+        if self.config.max_restore_points == Some(0) {
+            return Ok(());
+        }
+
         trace!(
             self.log,
             "Creating restore point";
@@ -377,17 +476,63 @@ impl<E: EthSpec> HotColdDB<E> {
         let partial_state = PartialBeaconState::from_state_forgetful(state);
         self.cold_db.put(state_root, &partial_state)?;
 
-        // 2. Store updated vector entries.
-        let db = &self.cold_db;
-        store_updated_vector(BlockRoots, db, state, &self.spec)?;
+        // 2. Store the remaining vector entries.
         store_updated_vector(StateRoots, db, state, &self.spec)?;
-        store_updated_vector(HistoricalRoots, db, state, &self.spec)?;
         store_updated_vector(RandaoMixes, db, state, &self.spec)?;
 
         // 3. Store restore point.
         let restore_point_index = state.slot.as_u64() / self.config.slots_per_restore_point;
         self.store_restore_point_hash(restore_point_index, *state_root)?;
 
+        // 4. Prune the restore points which are no longer required.
+        if let Some(max_restore_points) = self.config.max_restore_points {
+            self.prune_restore_points(max_restore_points)?;
+        }
+
+        Ok(())
+    }
+
+    /// Delete all but the `keep` most recent restore points from the freezer database.
+    ///
+    /// States prior to the oldest remaining restore point can no longer be loaded. If no restore
+    /// points are kept, the state roots and randao mixes used to reconstruct them are also
+    /// deleted, leaving only the block roots.
+    pub fn prune_restore_points(&self, keep: u64) -> Result<(), Error> {
+        let mut restore_points = vec![];
+        self.cold_db
+            .for_each_in_column(DBColumn::BeaconRestorePoint, |key, value| {
+                let state_root = RestorePointHash::from_store_bytes(value)?.state_root;
+                restore_points.push((Hash256::from_slice(key), state_root));
+                Ok(true)
+            })?;
+
+        // Restore point keys are big-endian indices, so they are iterated in ascending order.
+        let num_to_prune = restore_points.len().saturating_sub(keep as usize);
+
+        for (key, state_root) in restore_points.into_iter().take(num_to_prune) {
+            debug!(
+                self.log,
+                "Pruning restore point";
+                "state_root" => format!("{:?}", state_root)
+            );
+            // Delete the pointer first, so that a restore point is never partially present.
+            self.cold_db.delete::<RestorePointHash>(&key)?;
+            self.cold_db.delete::<PartialBeaconState<E>>(&state_root)?;
+        }
+
+        if keep == 0 {
+            for column in &[DBColumn::BeaconStateRoots, DBColumn::BeaconRandaoMixes] {
+                let mut keys = vec![];
+                self.cold_db.for_each_in_column(*column, |key, _| {
+                    keys.push(key.to_vec());
+                    Ok(true)
+                })?;
+                for key in keys {
+                    self.cold_db.key_delete((*column).into(), &key)?;
+                }
+            }
+        }
+
         Ok(())
     }
 
@@ -404,7 +549,18 @@ impl<E: EthSpec> HotColdDB<E> {
     /// Load a pre-finalization state from the freezer database.
     ///
     /// Will reconstruct the state if it lies between restore points.
+    ///
+    /// Returns `Error::StatePruned` if the restore point required to load the state has been
+    /// pruned.
     pub fn load_cold_state_by_slot(&self, slot: Slot) -> Result<BeaconState<E>, Error> {
+        let low_restore_point_idx = slot.as_u64() / self.config.slots_per_restore_point;
+        if !self.cold_db.key_exists(
+            DBColumn::BeaconRestorePoint.into(),
+            Self::restore_point_key(low_restore_point_idx).as_bytes(),
+        )? {
+            return Err(Error::StatePruned { slot });
+        }
+
         if slot % self.config.slots_per_restore_point == 0 {
             let restore_point_idx = slot.as_u64() / self.config.slots_per_restore_point;
             self.load_restore_point_by_index(restore_point_idx)
@@ -580,6 +736,60 @@ impl<E: EthSpec> HotColdDB<E> {
             * self.config.slots_per_restore_point
     }
 
+    /// Fetch a copy of the current split point from memory.
+    pub fn get_split_info(&self) -> Split {
+        *self.split.read()
+    }
+
+    /// Load the slot and state root of every restore point in the freezer database.
+    pub fn get_restore_points(&self) -> Result<Vec<(Slot, Hash256)>, Error> {
+        let mut restore_points = vec![];
+
+        self.cold_db
+            .for_each_in_column(DBColumn::BeaconRestorePoint, |_, value| {
+                let state_root = RestorePointHash::from_store_bytes(value)?.state_root;
+                let slot = self
+                    .load_cold_state_slot(&state_root)?
+                    .ok_or_else(|| HotColdDBError::MissingColdStateSummary(state_root))?;
+                restore_points.push((slot, state_root));
+                Ok(true)
+            })?;
+
+        Ok(restore_points)
+    }
+
+    /// Find the hot state summaries whose epoch boundary state is missing from the hot database,
+    /// and which therefore cannot be loaded.
+    pub fn find_unreachable_hot_states(&self) -> Result<Vec<UnreachableHotState>, Error> {
+        let mut unreachable = vec![];
+
+        self.hot_db
+            .for_each_in_column(DBColumn::BeaconStateSummary, |key, value| {
+                let summary = HotStateSummary::from_store_bytes(value)?;
+                let boundary_root = summary.epoch_boundary_state_root;
+
+                if !self
+                    .hot_db
+                    .key_exists(DBColumn::BeaconState.into(), boundary_root.as_bytes())?
+                {
+                    unreachable.push(UnreachableHotState {
+                        state_root: Hash256::from_slice(key),
+                        slot: summary.slot,
+                        epoch_boundary_state_root: boundary_root,
+                    });
+                }
+                Ok(true)
+            })?;
+
+        Ok(unreachable)
+    }
+
+    /// Compact the hot and cold databases.
+    pub fn compact(&self) {
+        self.hot_db.compact();
+        self.cold_db.compact();
+    }
+
     /// Load the split point from disk.
     fn load_split(&self) -> Result<Option<Split>, Error> {
         let key = Hash256::from_slice(SPLIT_DB_KEY.as_bytes());
@@ -594,6 +804,40 @@ impl<E: EthSpec> HotColdDB<E> {
         Ok(())
     }
 
+    /// Load the schema version from disk.
+    ///
+    /// A database without a stored schema version has the `LEGACY_SCHEMA_VERSION`.
+    pub fn load_schema_version(&self) -> Result<SchemaVersion, Error> {
+        let key = Hash256::from_slice(SCHEMA_VERSION_KEY.as_bytes());
+        Ok(self.hot_db.get(&key)?.unwrap_or(LEGACY_SCHEMA_VERSION))
+    }
+
+    /// Store the schema version on disk.
+    ///
+    /// This should only be called by a schema migration.
+    pub fn store_schema_version(&self, schema_version: SchemaVersion) -> Result<(), Error> {
+        let key = Hash256::from_slice(SCHEMA_VERSION_KEY.as_bytes());
+        if schema_version == LEGACY_SCHEMA_VERSION {
+            self.hot_db.delete::<SchemaVersion>(&key)
+        } else {
+            self.hot_db.put(&key, &schema_version)
+        }
+    }
+
+    /// Load the anchor info from disk.
+    fn load_anchor_info(&self) -> Result<Option<AnchorInfo>, Error> {
+        let key = Hash256::from_slice(ANCHOR_INFO_KEY.as_bytes());
+        self.hot_db.get(&key)
+    }
+
+    /// Store the anchor info on disk and update the in-memory copy.
+    fn store_anchor_info(&self, anchor_info: AnchorInfo) -> Result<(), Error> {
+        let key = Hash256::from_slice(ANCHOR_INFO_KEY.as_bytes());
+        self.hot_db.put(&key, &anchor_info)?;
+        *self.anchor_info.write() = Some(anchor_info);
+        Ok(())
+    }
+
     /// Load the state root of a restore point.
     fn load_restore_point_hash(&self, restore_point_index: u64) -> Result<Hash256, Error> {
         let key = Self::restore_point_key(restore_point_index);
@@ -751,9 +995,9 @@ pub fn process_finalization<E: EthSpec>(
 
 /// Struct for storing the split slot and state root in the database.
 #[derive(Debug, Clone, Copy, Default, Encode, Decode)]
-struct Split {
-    slot: Slot,
-    state_root: Hash256,
+pub struct Split {
+    pub slot: Slot,
+    pub state_root: Hash256,
 }
 
 impl StoreItem for Split {
@@ -817,6 +1061,14 @@ impl HotStateSummary {
     }
 }
 
+/// A hot state which cannot be loaded because its epoch boundary state is missing.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UnreachableHotState {
+    pub state_root: Hash256,
+    pub slot: Slot,
+    pub epoch_boundary_state_root: Hash256,
+}
+
 /// Struct for summarising a state in the freezer database.
 #[derive(Debug, Clone, Copy, Default, Encode, Decode)]
 struct ColdStateSummary {
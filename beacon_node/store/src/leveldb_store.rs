@@ -1,10 +1,12 @@
 use super::*;
 use crate::metrics;
 use db_key::Key;
+use leveldb::compaction::Compaction;
 use leveldb::database::batch::{Batch, Writebatch};
 use leveldb::database::kv::KV;
 use leveldb::database::Database;
 use leveldb::error::Error as LevelDBError;
+use leveldb::iterator::{Iterable, LevelDBIterator};
 use leveldb::options::{Options, ReadOptions, WriteOptions};
 use std::marker::PhantomData;
 use std::path::Path;
@@ -30,6 +32,37 @@ impl<E: EthSpec> LevelDB<E> {
         })
     }
 
+    /// Call `f` with the key (excluding the column prefix) and value of every entry in `column`,
+    /// in key order.
+    ///
+    /// Iteration stops early if `f` returns an error or `Ok(false)`.
+    pub fn for_each_in_column<F>(&self, column: DBColumn, mut f: F) -> Result<(), Error>
+    where
+        F: FnMut(&[u8], &[u8]) -> Result<bool, Error>,
+    {
+        let prefix: &str = column.into();
+        let start_key = get_key_for_col(prefix, &[]);
+
+        for (key, value) in self.db.iter(self.read_options()).from(&start_key) {
+            if !key.key.starts_with(prefix.as_bytes()) {
+                break;
+            }
+            if !f(&key.key[prefix.len()..], &value)? {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Compact the entire database, reclaiming the space used by deleted and overwritten entries.
+    pub fn compact(&self) {
+        let start_key = get_key_for_col("", &[]);
+        // All column prefixes are ASCII, so all keys sort before this one.
+        let end_key = get_key_for_col("", &[0xff; 64]);
+        self.db.compact(&start_key, &end_key);
+    }
+
     fn read_options(&self) -> ReadOptions<BytesKey> {
         ReadOptions::new()
     }
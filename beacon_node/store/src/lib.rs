@@ -19,6 +19,7 @@ pub mod hot_cold_store;
 mod impls;
 mod leveldb_store;
 mod memory_store;
+pub mod metadata;
 mod metrics;
 mod partial_beacon_state;
 mod state_batch;
@@ -31,6 +32,7 @@ pub use self::config::StoreConfig;
 pub use self::hot_cold_store::{HotColdDB, HotStateSummary};
 pub use self::leveldb_store::LevelDB;
 pub use self::memory_store::MemoryStore;
+pub use self::metadata::{AnchorInfo, SchemaVersion, CURRENT_SCHEMA_VERSION};
 pub use self::partial_beacon_state::PartialBeaconState;
 pub use errors::Error;
 pub use impls::beacon_state::StorageContainer as BeaconStateStorageContainer;
@@ -160,6 +162,31 @@ pub trait Store<E: EthSpec>: Sync + Send + Sized + 'static {
     fn item_exists<I: StoreItem>(&self, key: &Hash256) -> Result<bool, Error>;
 
     fn do_atomically(&self, batch: &[StoreOp]) -> Result<(), Error>;
+
+    /// Initialize a database which is being started from a weak subjectivity checkpoint rather
+    /// than genesis, recording the `anchor_info` and setting the oldest slot for which the store
+    /// holds states.
+    ///
+    /// Stores which do not distinguish between finalized and unfinalized states need only record
+    /// the `anchor_info`.
+    fn init_anchor(
+        &self,
+        anchor_state_root: Hash256,
+        anchor_block_root: Hash256,
+        anchor_info: AnchorInfo,
+    ) -> Result<(), Error>;
+
+    /// Return the anchor info of a database started from a checkpoint, if any.
+    fn get_anchor_info(&self) -> Option<AnchorInfo>;
+
+    /// Store a batch of historical blocks which precede the oldest block in the store.
+    ///
+    /// The `blocks` must be in slot-descending order, with the first block being the parent of the
+    /// current oldest block. Signatures are not checked, that is the responsibility of the caller.
+    fn put_historical_blocks(
+        &self,
+        blocks: Vec<(Hash256, SignedBeaconBlock<E>)>,
+    ) -> Result<(), Error>;
 }
 
 /// Reified key-value storage operation.  Helps in modifying the storage atomically.
@@ -190,6 +217,20 @@ pub enum DBColumn {
     BeaconHistoricalRoots,
     BeaconRandaoMixes,
     DhtEnrs,
+    /// For the peers (and their IP addresses) that were banned when the node shut down.
+    BannedPeers,
+    /// For the slasher's record of the attestation each validator made for each target epoch.
+    SlasherAttesterRecord,
+    /// For the indexed attestations referenced by the slasher's attester records.
+    SlasherIndexedAttestation,
+    /// For chunks of the slasher's minimum target span arrays.
+    SlasherMinTargets,
+    /// For chunks of the slasher's maximum target span arrays.
+    SlasherMaxTargets,
+    /// For the block headers seen by the slasher, indexed by slot.
+    SlasherProposals,
+    /// For data related to the slasher database itself.
+    SlasherMetadata,
 }
 
 impl Into<&'static str> for DBColumn {
@@ -210,10 +251,62 @@ impl Into<&'static str> for DBColumn {
             DBColumn::BeaconHistoricalRoots => "bhr",
             DBColumn::BeaconRandaoMixes => "brm",
             DBColumn::DhtEnrs => "dht",
+            DBColumn::BannedPeers => "bnp",
+            DBColumn::SlasherAttesterRecord => "sar",
+            DBColumn::SlasherIndexedAttestation => "sia",
+            DBColumn::SlasherMinTargets => "smn",
+            DBColumn::SlasherMaxTargets => "smx",
+            DBColumn::SlasherProposals => "spr",
+            DBColumn::SlasherMetadata => "sme",
         }
     }
 }
 
+impl DBColumn {
+    /// All columns, in the order they are reported by database tooling.
+    pub fn all() -> &'static [DBColumn] {
+        &[
+            DBColumn::BeaconMeta,
+            DBColumn::BeaconBlock,
+            DBColumn::BeaconState,
+            DBColumn::BeaconChain,
+            DBColumn::OpPool,
+            DBColumn::Eth1Cache,
+            DBColumn::ForkChoice,
+            DBColumn::BeaconRestorePoint,
+            DBColumn::BeaconStateSummary,
+            DBColumn::BeaconBlockRoots,
+            DBColumn::BeaconStateRoots,
+            DBColumn::BeaconHistoricalRoots,
+            DBColumn::BeaconRandaoMixes,
+            DBColumn::DhtEnrs,
+            DBColumn::BannedPeers,
+            DBColumn::SlasherAttesterRecord,
+            DBColumn::SlasherIndexedAttestation,
+            DBColumn::SlasherMinTargets,
+            DBColumn::SlasherMaxTargets,
+            DBColumn::SlasherProposals,
+            DBColumn::SlasherMetadata,
+        ]
+    }
+}
+
+impl std::str::FromStr for DBColumn {
+    type Err = String;
+
+    /// Parses a column from the `&str` used to key it in the database (e.g., `"blk"`).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        DBColumn::all()
+            .iter()
+            .copied()
+            .find(|column| {
+                let key: &str = (*column).into();
+                key == s
+            })
+            .ok_or_else(|| format!("Unknown database column: {}", s))
+    }
+}
+
 /// An item that may stored in a `Store` by serializing and deserializing from bytes.
 pub trait StoreItem: Sized {
     /// Identifies which column this item should be placed in.
@@ -275,6 +368,15 @@ mod tests {
         assert_eq!(store.get::<StorableThing>(&key).unwrap(), None);
     }
 
+    #[test]
+    fn db_column_round_trip() {
+        for column in DBColumn::all() {
+            let key: &str = (*column).into();
+            assert_eq!(key.parse::<DBColumn>(), Ok(*column));
+        }
+        assert!("foo".parse::<DBColumn>().is_err());
+    }
+
     #[test]
     fn simplediskdb() {
         let dir = tempdir().unwrap();
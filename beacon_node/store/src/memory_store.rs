@@ -2,6 +2,7 @@ use super::{DBColumn, Error, ItemStore, KeyValueStore, Store, StoreOp};
 use crate::forwards_iter::SimpleForwardsBlockRootsIterator;
 use crate::hot_cold_store::HotStateSummary;
 use crate::impls::beacon_state::{get_full_state, store_full_state};
+use crate::metadata::{AnchorInfo, ANCHOR_INFO_KEY};
 use crate::StoreItem;
 use parking_lot::RwLock;
 use std::collections::HashMap;
@@ -40,6 +41,10 @@ impl<E: EthSpec> MemoryStore<E> {
         col.append(&mut key.to_vec());
         col
     }
+
+    fn anchor_info_key() -> Hash256 {
+        Hash256::from_slice(ANCHOR_INFO_KEY.as_bytes())
+    }
 }
 
 impl<E: EthSpec> KeyValueStore<E> for MemoryStore<E> {
@@ -191,4 +196,29 @@ impl<E: EthSpec> Store<E> for MemoryStore<E> {
     fn do_atomically(&self, batch: &[StoreOp]) -> Result<(), Error> {
         KeyValueStore::do_atomically(self, batch)
     }
+
+    /// The `MemoryStore` has no freezer, so there is no split to initialize.
+    fn init_anchor(&self, _: Hash256, _: Hash256, anchor_info: AnchorInfo) -> Result<(), Error> {
+        self.put(&Self::anchor_info_key(), &anchor_info)
+    }
+
+    fn get_anchor_info(&self) -> Option<AnchorInfo> {
+        self.get(&Self::anchor_info_key()).ok().flatten()
+    }
+
+    fn put_historical_blocks(
+        &self,
+        blocks: Vec<(Hash256, SignedBeaconBlock<E>)>,
+    ) -> Result<(), Error> {
+        let anchor_info = self
+            .get_anchor_info()
+            .ok_or_else(|| Error::MissingAnchorInfo)?
+            .extend_with_historical_blocks(&blocks)?;
+
+        for (block_root, block) in blocks {
+            self.put(&block_root, &block)?;
+        }
+
+        self.put(&Self::anchor_info_key(), &anchor_info)
+    }
 }
@@ -0,0 +1,104 @@
+use crate::{DBColumn, Error, StoreItem};
+use ssz::{Decode, Encode};
+use ssz_derive::{Decode, Encode};
+use types::{EthSpec, Hash256, SignedBeaconBlock, Slot};
+
+/// The version of the database schema written by this version of Lighthouse.
+pub const CURRENT_SCHEMA_VERSION: SchemaVersion = SchemaVersion(1);
+
+/// The schema version of a database with no stored version, i.e. one created before schema
+/// versioning was introduced.
+pub const LEGACY_SCHEMA_VERSION: SchemaVersion = SchemaVersion(0);
+
+/// 32-byte key for accessing the `SchemaVersion` of the database.
+pub const SCHEMA_VERSION_KEY: &str = "SCHEMAVERSIONSCHEMAVERSIONSCHEMA";
+
+/// 32-byte key for accessing the `AnchorInfo` of a database initialized from a checkpoint.
+pub const ANCHOR_INFO_KEY: &str = "ANCHORINFOANCHORINFOANCHORINFOAN";
+
+/// The version of the on-disk layout of the items in the database.
+///
+/// Must be incremented whenever the encoding of a stored item changes, along with a migration in
+/// `beacon_chain::schema_change`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SchemaVersion(pub u64);
+
+impl SchemaVersion {
+    pub fn as_u64(self) -> u64 {
+        self.0
+    }
+}
+
+impl StoreItem for SchemaVersion {
+    fn db_column() -> DBColumn {
+        DBColumn::BeaconMeta
+    }
+
+    fn as_store_bytes(&self) -> Vec<u8> {
+        self.0.as_ssz_bytes()
+    }
+
+    fn from_store_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        Ok(SchemaVersion(u64::from_ssz_bytes(bytes)?))
+    }
+}
+
+/// Information about the block history of a database initialized from a weak subjectivity
+/// checkpoint.
+///
+/// The database holds every block with a slot greater than or equal to `oldest_block_slot`.
+/// Older blocks are back-filled from peers, moving `oldest_block_slot` towards genesis.
+#[derive(Debug, Clone, Copy, PartialEq, Encode, Decode)]
+pub struct AnchorInfo {
+    /// The slot of the checkpoint that the database was initialized from.
+    pub anchor_slot: Slot,
+    /// The slot of the oldest block held by the database.
+    pub oldest_block_slot: Slot,
+    /// The parent root of the oldest block held by the database, i.e. the root of the next block
+    /// to back-fill.
+    pub oldest_block_parent: Hash256,
+}
+
+impl AnchorInfo {
+    /// Returns `true` once every block back to genesis is held by the database.
+    pub fn block_backfill_complete(&self) -> bool {
+        self.oldest_block_slot == 0
+    }
+
+    /// Check that `blocks` (in slot-descending order) extend the block history back from the
+    /// oldest block, returning the anchor info updated to account for them.
+    pub fn extend_with_historical_blocks<E: EthSpec>(
+        &self,
+        blocks: &[(Hash256, SignedBeaconBlock<E>)],
+    ) -> Result<Self, Error> {
+        let mut anchor_info = *self;
+
+        for (block_root, block) in blocks {
+            if *block_root != anchor_info.oldest_block_parent {
+                return Err(Error::HistoricalBlockMismatch {
+                    expected_root: anchor_info.oldest_block_parent,
+                    block_root: *block_root,
+                });
+            }
+
+            anchor_info.oldest_block_slot = block.slot();
+            anchor_info.oldest_block_parent = block.parent_root();
+        }
+
+        Ok(anchor_info)
+    }
+}
+
+impl StoreItem for AnchorInfo {
+    fn db_column() -> DBColumn {
+        DBColumn::BeaconMeta
+    }
+
+    fn as_store_bytes(&self) -> Vec<u8> {
+        self.as_ssz_bytes()
+    }
+
+    fn from_store_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        Ok(Self::from_ssz_bytes(bytes)?)
+    }
+}
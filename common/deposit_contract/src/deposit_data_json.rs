@@ -0,0 +1,115 @@
+//! Provides the `deposit_data-*.json` format produced by the
+//! [eth2.0-deposit-cli](https://github.com/ethereum/eth2.0-deposit-cli), which is widely used to
+//! submit batches of deposits (e.g., via a launchpad web page).
+
+use serde_derive::{Deserialize, Serialize};
+use tree_hash::TreeHash;
+use types::{ChainSpec, DepositData, Hash256, PublicKeyBytes, SignatureBytes};
+
+/// A single deposit in a `deposit_data-*.json` file, which contains a JSON list of them.
+///
+/// All byte fields are hex-encoded without a `0x` prefix, although a prefix is tolerated when
+/// decoding.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StandardDepositDataJson {
+    #[serde(with = "hex_ssz")]
+    pub pubkey: PublicKeyBytes,
+    #[serde(with = "hex_ssz")]
+    pub withdrawal_credentials: Hash256,
+    pub amount: u64,
+    #[serde(with = "hex_ssz")]
+    pub signature: SignatureBytes,
+    #[serde(with = "hex_ssz")]
+    pub deposit_message_root: Hash256,
+    #[serde(with = "hex_ssz")]
+    pub deposit_data_root: Hash256,
+    #[serde(with = "hex_ssz")]
+    pub fork_version: [u8; 4],
+}
+
+impl StandardDepositDataJson {
+    /// Returns the JSON representation of `deposit_data`, for the genesis fork of `spec`.
+    ///
+    /// The roots are computed from `deposit_data`; the signature is not verified.
+    pub fn new(deposit_data: &DepositData, spec: &ChainSpec) -> Self {
+        Self {
+            pubkey: deposit_data.pubkey.clone(),
+            withdrawal_credentials: deposit_data.withdrawal_credentials,
+            amount: deposit_data.amount,
+            signature: deposit_data.signature.clone(),
+            deposit_message_root: deposit_data.as_deposit_message().tree_hash_root(),
+            deposit_data_root: deposit_data.tree_hash_root(),
+            fork_version: spec.genesis_fork_version,
+        }
+    }
+
+    /// Returns the `DepositData` described by `self`.
+    ///
+    /// The roots and fork version of `self` are ignored.
+    pub fn deposit_data(&self) -> DepositData {
+        DepositData {
+            pubkey: self.pubkey.clone(),
+            withdrawal_credentials: self.withdrawal_credentials,
+            amount: self.amount,
+            signature: self.signature.clone(),
+        }
+    }
+}
+
+/// Serializes SSZ-encodable types as hex strings without a `0x` prefix.
+mod hex_ssz {
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serializer};
+    use ssz::{Decode, Encode};
+
+    pub fn serialize<T: Encode, S: Serializer>(
+        value: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&hex::encode(value.as_ssz_bytes()))
+    }
+
+    pub fn deserialize<'de, T: Decode, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<T, D::Error> {
+        let string = String::deserialize(deserializer)?;
+        let hex = string.trim_start_matches("0x");
+        let bytes =
+            hex::decode(hex).map_err(|e| D::Error::custom(format!("invalid hex: {:?}", e)))?;
+
+        T::from_ssz_bytes(&bytes).map_err(|e| D::Error::custom(format!("invalid bytes: {:?}", e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::{test_utils::generate_deterministic_keypair, EthSpec, MainnetEthSpec, Signature};
+
+    #[test]
+    fn json_round_trip() {
+        let spec = &MainnetEthSpec::default_spec();
+        let keypair = generate_deterministic_keypair(42);
+
+        let mut deposit_data = DepositData {
+            pubkey: keypair.pk.into(),
+            withdrawal_credentials: Hash256::from_low_u64_be(42),
+            amount: spec.max_effective_balance,
+            signature: Signature::empty_signature().into(),
+        };
+        deposit_data.signature = deposit_data.create_signature(&keypair.sk, spec);
+
+        let json = StandardDepositDataJson::new(&deposit_data, spec);
+        let string = serde_json::to_string(&vec![json.clone()]).expect("should serialize");
+
+        assert!(!string.contains("0x"), "hex should not be prefixed");
+
+        let decoded: Vec<StandardDepositDataJson> =
+            serde_json::from_str(&string).expect("should deserialize");
+
+        assert_eq!(decoded, vec![json.clone()]);
+        assert_eq!(decoded[0].deposit_data(), deposit_data);
+        assert_eq!(decoded[0].fork_version, spec.genesis_fork_version);
+        assert_eq!(decoded[0].deposit_data_root, deposit_data.tree_hash_root());
+    }
+}
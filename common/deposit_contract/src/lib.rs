@@ -1,8 +1,11 @@
+mod deposit_data_json;
+
 use ethabi::{Contract, Token};
 use ssz::{Decode, DecodeError as SszDecodeError, Encode};
 use tree_hash::TreeHash;
 use types::{DepositData, Hash256, PublicKeyBytes, SignatureBytes};
 
+pub use deposit_data_json::StandardDepositDataJson;
 pub use ethabi::Error;
 
 #[derive(Debug)]
@@ -2,7 +2,7 @@ use crate::{
     filesystem::{read, update},
     Error,
 };
-use eth2_wallet::{Uuid, ValidatorKeystores, Wallet};
+use eth2_wallet::{KdfParams, Uuid, ValidatorKeystores, Wallet};
 use std::fs::{remove_file, OpenOptions};
 use std::path::{Path, PathBuf};
 
@@ -88,10 +88,34 @@ impl LockedWallet {
         voting_keystore_password: &[u8],
         withdrawal_keystore_password: &[u8],
     ) -> Result<ValidatorKeystores, Error> {
-        let keystores = self.wallet.next_validator(
+        self.next_validator_with_kdf(
             wallet_password,
             voting_keystore_password,
             withdrawal_keystore_password,
+            &KdfParams::default(),
+        )
+    }
+
+    /// Calls `Wallet::next_validator_with_kdf` on the underlying `wallet`.
+    ///
+    /// Ensures that the wallet JSON file is updated after each call.
+    ///
+    /// ## Errors
+    ///
+    /// - If there is an error generating the validator keys.
+    /// - If there is a file-system error.
+    pub fn next_validator_with_kdf(
+        &mut self,
+        wallet_password: &[u8],
+        voting_keystore_password: &[u8],
+        withdrawal_keystore_password: &[u8],
+        kdf_params: &KdfParams,
+    ) -> Result<ValidatorKeystores, Error> {
+        let keystores = self.wallet.next_validator_with_kdf(
+            wallet_password,
+            voting_keystore_password,
+            withdrawal_keystore_password,
+            kdf_params,
         )?;
 
         update(&self.wallet_dir, &self.wallet)?;
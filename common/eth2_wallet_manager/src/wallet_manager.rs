@@ -2,7 +2,7 @@ use crate::{
     filesystem::{create, Error as FilesystemError},
     LockedWallet,
 };
-use eth2_wallet::{bip39::Mnemonic, Error as WalletError, Uuid, Wallet, WalletBuilder};
+use eth2_wallet::{bip39::Mnemonic, Error as WalletError, KdfParams, Uuid, Wallet, WalletBuilder};
 use std::collections::HashMap;
 use std::ffi::OsString;
 use std::fs::{create_dir_all, read_dir, OpenOptions};
@@ -112,17 +112,32 @@ impl WalletManager {
     /// - If a wallet with this name already exists.
     /// - If there is a file-system or parsing error.
     pub fn create_wallet(
+        &self,
+        name: String,
+        wallet_type: WalletType,
+        mnemonic: &Mnemonic,
+        password: &[u8],
+    ) -> Result<LockedWallet, Error> {
+        self.create_wallet_with_kdf(name, wallet_type, mnemonic, password, &KdfParams::default())
+    }
+
+    /// As per `Self::create_wallet`, but the wallet is encrypted via a KDF with the given
+    /// `kdf_params`.
+    pub fn create_wallet_with_kdf(
         &self,
         name: String,
         _wallet_type: WalletType,
         mnemonic: &Mnemonic,
         password: &[u8],
+        kdf_params: &KdfParams,
     ) -> Result<LockedWallet, Error> {
         if self.wallets()?.contains_key(&name) {
             return Err(Error::NameAlreadyTaken(name));
         }
 
-        let wallet = WalletBuilder::from_mnemonic(mnemonic, password, name)?.build()?;
+        let wallet = WalletBuilder::from_mnemonic(mnemonic, password, name)?
+            .kdf_params(kdf_params)
+            .build()?;
         let uuid = wallet.uuid().clone();
 
         let wallet_dir = self.dir.join(format!("{}", uuid));
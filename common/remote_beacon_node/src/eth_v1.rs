@@ -0,0 +1,447 @@
+//! Provides the functions on the standard Ethereum API (`/eth/v1/...`) of the node.
+
+use super::{as_ssz_hex_string, Error, HttpClient};
+use reqwest::{Response, StatusCode};
+pub use rest_types::eth_v1::{
+    AttesterData, BeaconCommitteeSubscription, BlockHeaderAndSignature, BlockHeaderData,
+    BlockHeaderMessage, BlockId, ChainHeadData, CheckpointData, CommitteeData, ErrorMessage,
+    FinalityCheckpointsData, GenericResponse, GenesisData, IdentityData, PeerData, ProposerData,
+    RootData, StateId, SyncingData, ValidatorData, ValidatorId, ValidatorInfo, VersionData,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use types::{
+    Attestation, AttestationData, AttesterSlashing, BeaconBlock, BeaconState, CommitteeIndex,
+    Epoch, EthSpec, Fork, Hash256, ProposerSlashing, Signature, SignedAggregateAndProof,
+    SignedBeaconBlock, SignedVoluntaryExit, Slot, YamlConfig,
+};
+use url::Url;
+
+/// Provides the functions on the `/eth/v1` endpoint of the node.
+#[derive(Clone)]
+pub struct EthV1<E>(pub(crate) HttpClient<E>);
+
+impl<E: EthSpec> EthV1<E> {
+    fn url(&self, path: &str) -> Result<Url, Error> {
+        self.0
+            .url("eth/v1/")
+            .and_then(move |url| url.join(path).map_err(Error::from))
+    }
+
+    /// Performs a `GET` request and returns the `data` of the `GenericResponse`.
+    async fn get<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        query_pairs: Vec<(String, String)>,
+    ) -> Result<T, Error> {
+        let mut url = self.url(path)?;
+        query_pairs.into_iter().for_each(|(key, param)| {
+            url.query_pairs_mut().append_pair(&key, &param);
+        });
+
+        let response = self
+            .0
+            .client
+            .get(&url.to_string())
+            .send()
+            .await
+            .map_err(Error::from)?;
+
+        let success = ok_or_error(response).await?;
+        success
+            .json::<GenericResponse<T>>()
+            .await
+            .map(|response| response.data)
+            .map_err(Error::from)
+    }
+
+    /// Performs a `POST` request with a JSON `body`, returning the raw response.
+    async fn post_raw<T: Serialize>(&self, path: &str, body: &T) -> Result<Response, Error> {
+        let url = self.url(path)?;
+        let response = self.0.json_post(url, body).await?;
+        ok_or_error(response).await
+    }
+
+    /// Performs a `POST` request with a JSON `body`, ignoring the body of the response.
+    async fn post<T: Serialize>(&self, path: &str, body: &T) -> Result<(), Error> {
+        self.post_raw(path, body).await.map(|_| ())
+    }
+
+    /// Performs a `POST` request with a JSON `body` and returns the `data` of the
+    /// `GenericResponse`.
+    async fn post_with_response<T: Serialize, U: DeserializeOwned>(
+        &self,
+        path: &str,
+        body: &T,
+    ) -> Result<U, Error> {
+        self.post_raw(path, body)
+            .await?
+            .json::<GenericResponse<U>>()
+            .await
+            .map(|response| response.data)
+            .map_err(Error::from)
+    }
+
+    /*
+     * Beacon
+     */
+
+    /// `GET beacon/genesis`
+    pub async fn get_genesis(&self) -> Result<GenesisData, Error> {
+        self.get("beacon/genesis", vec![]).await
+    }
+
+    /// `GET beacon/states/{state_id}/root`
+    pub async fn get_state_root(&self, state_id: StateId) -> Result<Hash256, Error> {
+        self.get::<RootData>(&format!("beacon/states/{}/root", state_id), vec![])
+            .await
+            .map(|data| data.root)
+    }
+
+    /// `GET beacon/states/{state_id}/fork`
+    pub async fn get_state_fork(&self, state_id: StateId) -> Result<Fork, Error> {
+        self.get(&format!("beacon/states/{}/fork", state_id), vec![])
+            .await
+    }
+
+    /// `GET beacon/states/{state_id}/finality_checkpoints`
+    pub async fn get_state_finality_checkpoints(
+        &self,
+        state_id: StateId,
+    ) -> Result<FinalityCheckpointsData, Error> {
+        self.get(
+            &format!("beacon/states/{}/finality_checkpoints", state_id),
+            vec![],
+        )
+        .await
+    }
+
+    /// `GET beacon/states/{state_id}/validators`
+    ///
+    /// Returns all the validators in the state if `ids` is empty.
+    pub async fn get_state_validators(
+        &self,
+        state_id: StateId,
+        ids: &[ValidatorId],
+    ) -> Result<Vec<ValidatorData>, Error> {
+        let query_pairs = ids
+            .iter()
+            .map(|id| ("id".to_string(), id.to_string()))
+            .collect();
+
+        self.get(
+            &format!("beacon/states/{}/validators", state_id),
+            query_pairs,
+        )
+        .await
+    }
+
+    /// `GET beacon/states/{state_id}/validators/{validator_id}`
+    pub async fn get_state_validator(
+        &self,
+        state_id: StateId,
+        validator_id: &ValidatorId,
+    ) -> Result<ValidatorData, Error> {
+        self.get(
+            &format!("beacon/states/{}/validators/{}", state_id, validator_id),
+            vec![],
+        )
+        .await
+    }
+
+    /// `GET beacon/states/{state_id}/committees`
+    ///
+    /// Returns the committees for the epoch of the state if `epoch` is `None`.
+    pub async fn get_state_committees(
+        &self,
+        state_id: StateId,
+        epoch: Option<Epoch>,
+    ) -> Result<Vec<CommitteeData>, Error> {
+        let query_pairs = epoch
+            .into_iter()
+            .map(|epoch| ("epoch".to_string(), epoch.to_string()))
+            .collect();
+
+        self.get(
+            &format!("beacon/states/{}/committees", state_id),
+            query_pairs,
+        )
+        .await
+    }
+
+    /// `GET beacon/headers`
+    ///
+    /// Returns the header of the head block if `slot` is `None`.
+    pub async fn get_headers(&self, slot: Option<Slot>) -> Result<Vec<BlockHeaderData>, Error> {
+        let query_pairs = slot
+            .into_iter()
+            .map(|slot| ("slot".to_string(), slot.to_string()))
+            .collect();
+
+        self.get("beacon/headers", query_pairs).await
+    }
+
+    /// `GET beacon/headers/{block_id}`
+    pub async fn get_header(&self, block_id: BlockId) -> Result<BlockHeaderData, Error> {
+        self.get(&format!("beacon/headers/{}", block_id), vec![])
+            .await
+    }
+
+    /// `POST beacon/blocks`
+    pub async fn post_block(&self, block: &SignedBeaconBlock<E>) -> Result<(), Error> {
+        self.post("beacon/blocks", block).await
+    }
+
+    /// `GET beacon/blocks/{block_id}`
+    pub async fn get_block(&self, block_id: BlockId) -> Result<SignedBeaconBlock<E>, Error> {
+        self.get(&format!("beacon/blocks/{}", block_id), vec![])
+            .await
+    }
+
+    /// `GET beacon/blocks/{block_id}/root`
+    pub async fn get_block_root(&self, block_id: BlockId) -> Result<Hash256, Error> {
+        self.get::<RootData>(&format!("beacon/blocks/{}/root", block_id), vec![])
+            .await
+            .map(|data| data.root)
+    }
+
+    /// `GET beacon/blocks/{block_id}/attestations`
+    pub async fn get_block_attestations(
+        &self,
+        block_id: BlockId,
+    ) -> Result<Vec<Attestation<E>>, Error> {
+        self.get(&format!("beacon/blocks/{}/attestations", block_id), vec![])
+            .await
+    }
+
+    /// `GET beacon/pool/attestations`
+    pub async fn get_pool_attestations(&self) -> Result<Vec<Attestation<E>>, Error> {
+        self.get("beacon/pool/attestations", vec![]).await
+    }
+
+    /// `POST beacon/pool/attestations`
+    pub async fn post_pool_attestations(
+        &self,
+        attestations: &[Attestation<E>],
+    ) -> Result<(), Error> {
+        self.post("beacon/pool/attestations", &attestations).await
+    }
+
+    /// `GET beacon/pool/attester_slashings`
+    pub async fn get_pool_attester_slashings(&self) -> Result<Vec<AttesterSlashing<E>>, Error> {
+        self.get("beacon/pool/attester_slashings", vec![]).await
+    }
+
+    /// `POST beacon/pool/attester_slashings`
+    pub async fn post_pool_attester_slashings(
+        &self,
+        slashing: &AttesterSlashing<E>,
+    ) -> Result<(), Error> {
+        self.post("beacon/pool/attester_slashings", slashing).await
+    }
+
+    /// `GET beacon/pool/proposer_slashings`
+    pub async fn get_pool_proposer_slashings(&self) -> Result<Vec<ProposerSlashing>, Error> {
+        self.get("beacon/pool/proposer_slashings", vec![]).await
+    }
+
+    /// `POST beacon/pool/proposer_slashings`
+    pub async fn post_pool_proposer_slashings(
+        &self,
+        slashing: &ProposerSlashing,
+    ) -> Result<(), Error> {
+        self.post("beacon/pool/proposer_slashings", slashing).await
+    }
+
+    /// `GET beacon/pool/voluntary_exits`
+    pub async fn get_pool_voluntary_exits(&self) -> Result<Vec<SignedVoluntaryExit>, Error> {
+        self.get("beacon/pool/voluntary_exits", vec![]).await
+    }
+
+    /// `POST beacon/pool/voluntary_exits`
+    pub async fn post_pool_voluntary_exits(&self, exit: &SignedVoluntaryExit) -> Result<(), Error> {
+        self.post("beacon/pool/voluntary_exits", exit).await
+    }
+
+    /*
+     * Config
+     */
+
+    /// `GET config/fork_schedule`
+    pub async fn get_fork_schedule(&self) -> Result<Vec<Fork>, Error> {
+        self.get("config/fork_schedule", vec![]).await
+    }
+
+    /// `GET config/spec`
+    pub async fn get_spec(&self) -> Result<YamlConfig, Error> {
+        self.get("config/spec", vec![]).await
+    }
+
+    /*
+     * Debug
+     */
+
+    /// `GET debug/beacon/states/{state_id}`
+    pub async fn get_debug_state(&self, state_id: StateId) -> Result<BeaconState<E>, Error> {
+        self.get(&format!("debug/beacon/states/{}", state_id), vec![])
+            .await
+    }
+
+    /// `GET debug/beacon/heads`
+    pub async fn get_debug_heads(&self) -> Result<Vec<ChainHeadData>, Error> {
+        self.get("debug/beacon/heads", vec![]).await
+    }
+
+    /*
+     * Node
+     */
+
+    /// `GET node/identity`
+    pub async fn get_node_identity(&self) -> Result<IdentityData, Error> {
+        self.get("node/identity", vec![]).await
+    }
+
+    /// `GET node/peers`
+    pub async fn get_node_peers(&self) -> Result<Vec<PeerData>, Error> {
+        self.get("node/peers", vec![]).await
+    }
+
+    /// `GET node/version`
+    pub async fn get_node_version(&self) -> Result<String, Error> {
+        self.get::<VersionData>("node/version", vec![])
+            .await
+            .map(|data| data.version)
+    }
+
+    /// `GET node/syncing`
+    pub async fn get_node_syncing(&self) -> Result<SyncingData, Error> {
+        self.get("node/syncing", vec![]).await
+    }
+
+    /// `GET node/health`
+    ///
+    /// Returns `200` if the node is synced or `206` if it is syncing.
+    pub async fn get_node_health(&self) -> Result<StatusCode, Error> {
+        let url = self.url("node/health")?;
+        let response = self
+            .0
+            .client
+            .get(&url.to_string())
+            .send()
+            .await
+            .map_err(Error::from)?;
+
+        ok_or_error(response)
+            .await
+            .map(|response| response.status())
+    }
+
+    /*
+     * Validator
+     */
+
+    /// `POST validator/duties/attester/{epoch}`
+    pub async fn post_attester_duties(
+        &self,
+        epoch: Epoch,
+        validator_indices: &[u64],
+    ) -> Result<Vec<AttesterData>, Error> {
+        self.post_with_response(
+            &format!("validator/duties/attester/{}", epoch),
+            &validator_indices,
+        )
+        .await
+    }
+
+    /// `GET validator/duties/proposer/{epoch}`
+    pub async fn get_proposer_duties(&self, epoch: Epoch) -> Result<Vec<ProposerData>, Error> {
+        self.get(&format!("validator/duties/proposer/{}", epoch), vec![])
+            .await
+    }
+
+    /// `GET validator/blocks/{slot}`
+    pub async fn get_validator_block(
+        &self,
+        slot: Slot,
+        randao_reveal: &Signature,
+    ) -> Result<BeaconBlock<E>, Error> {
+        self.get(
+            &format!("validator/blocks/{}", slot),
+            vec![(
+                "randao_reveal".to_string(),
+                as_ssz_hex_string(randao_reveal),
+            )],
+        )
+        .await
+    }
+
+    /// `GET validator/attestation_data`
+    pub async fn get_attestation_data(
+        &self,
+        slot: Slot,
+        committee_index: CommitteeIndex,
+    ) -> Result<AttestationData, Error> {
+        self.get(
+            "validator/attestation_data",
+            vec![
+                ("slot".to_string(), slot.to_string()),
+                ("committee_index".to_string(), committee_index.to_string()),
+            ],
+        )
+        .await
+    }
+
+    /// `GET validator/aggregate_attestation`
+    pub async fn get_aggregate_attestation(
+        &self,
+        slot: Slot,
+        attestation_data_root: Hash256,
+    ) -> Result<Attestation<E>, Error> {
+        self.get(
+            "validator/aggregate_attestation",
+            vec![
+                ("slot".to_string(), slot.to_string()),
+                (
+                    "attestation_data_root".to_string(),
+                    format!("{:?}", attestation_data_root),
+                ),
+            ],
+        )
+        .await
+    }
+
+    /// `POST validator/aggregate_and_proofs`
+    pub async fn post_aggregate_and_proofs(
+        &self,
+        aggregates: &[SignedAggregateAndProof<E>],
+    ) -> Result<(), Error> {
+        self.post("validator/aggregate_and_proofs", &aggregates)
+            .await
+    }
+
+    /// `POST validator/beacon_committee_subscriptions`
+    pub async fn post_beacon_committee_subscriptions(
+        &self,
+        subscriptions: &[BeaconCommitteeSubscription],
+    ) -> Result<(), Error> {
+        self.post("validator/beacon_committee_subscriptions", &subscriptions)
+            .await
+    }
+}
+
+/// Returns the `response` if it was a 200-type success response, otherwise returns the
+/// `ErrorMessage` in its body.
+///
+/// Falls back to `Error::DidNotSucceed` if the body is not an `ErrorMessage`.
+async fn ok_or_error(response: Response) -> Result<Response, Error> {
+    let status = response.status();
+
+    if status.is_success() {
+        Ok(response)
+    } else {
+        let body = response.text().await.map_err(Error::from)?;
+        match serde_json::from_str::<ErrorMessage>(&body) {
+            Ok(message) => Err(Error::ServerMessage(message)),
+            Err(_) => Err(Error::DidNotSucceed { status, body }),
+        }
+    }
+}
@@ -0,0 +1,144 @@
+//! Provides a client for the server-sent events of the `/events` endpoint of the node.
+
+use super::{error_for_status, Error, HttpClient};
+use futures::stream::{self, Stream};
+use reqwest::{Client, Response};
+use rest_types::events::{BlockEvent, ChainReorgEvent, FinalizedCheckpointEvent, HeadEvent};
+pub use rest_types::events::{Event, EventTopic};
+use serde::de::DeserializeOwned;
+use std::str::FromStr;
+use types::EthSpec;
+
+/// Provides the functions on the `/events` endpoint of the node.
+#[derive(Clone)]
+pub struct Events<E>(pub(crate) HttpClient<E>);
+
+impl<E: EthSpec> Events<E> {
+    /// Subscribes to the given `topics`, returning a stream which yields each event as it is
+    /// received from the node.
+    ///
+    /// The stream ends when the node closes the connection.
+    pub async fn stream(
+        &self,
+        topics: &[EventTopic],
+    ) -> Result<impl Stream<Item = Result<Event<E>, Error>>, Error> {
+        let mut url = self.0.url("events")?;
+        let topics = topics
+            .iter()
+            .map(|topic| topic.as_str())
+            .collect::<Vec<_>>()
+            .join(",");
+        url.query_pairs_mut().append_pair("topics", &topics);
+
+        // The request timeout of `self.0.client` would apply to the entire, unbounded, stream so
+        // a client without a timeout is used instead.
+        let response = Client::new()
+            .get(&url.to_string())
+            .send()
+            .await
+            .map_err(Error::from)?;
+        let response = error_for_status(response).await?;
+
+        Ok(stream::unfold(
+            Some((response, vec![])),
+            |state| async move {
+                let (mut response, mut buffer) = state?;
+                match next_event(&mut response, &mut buffer).await {
+                    Ok(Some(event)) => Some((Ok(event), Some((response, buffer)))),
+                    Ok(None) => None,
+                    // End the stream after the first error.
+                    Err(e) => Some((Err(e), None)),
+                }
+            },
+        ))
+    }
+}
+
+/// Reads from `response` until a complete event has been received, returning `None` if the
+/// response ends first.
+///
+/// `buffer` holds any bytes received after the end of the returned event.
+async fn next_event<E: EthSpec>(
+    response: &mut Response,
+    buffer: &mut Vec<u8>,
+) -> Result<Option<Event<E>>, Error> {
+    loop {
+        // Events are terminated by a blank line.
+        if let Some(end) = buffer.windows(2).position(|window| window == b"\n\n") {
+            let message = buffer.drain(..end + 2).collect::<Vec<_>>();
+            if let Some(event) = parse_event(&message)? {
+                return Ok(Some(event));
+            }
+            continue;
+        }
+
+        match response.chunk().await.map_err(Error::from)? {
+            Some(chunk) => buffer.extend_from_slice(&chunk),
+            None => return Ok(None),
+        }
+    }
+}
+
+/// Parses a single server-sent event, returning `None` if it contains no data (e.g., it is a
+/// comment).
+fn parse_event<E: EthSpec>(message: &[u8]) -> Result<Option<Event<E>>, Error> {
+    let message = std::str::from_utf8(message)
+        .map_err(|e| Error::InvalidEvent(format!("Invalid UTF-8: {}", e)))?;
+
+    let mut topic = None;
+    let mut data: Option<String> = None;
+
+    for line in message.lines() {
+        let (field, value) = match line.find(':') {
+            // Lines starting with a colon are comments.
+            Some(0) => continue,
+            Some(i) => {
+                let value = &line[i + 1..];
+                // A single space may follow the colon.
+                if value.starts_with(' ') {
+                    (&line[..i], &value[1..])
+                } else {
+                    (&line[..i], value)
+                }
+            }
+            None => (line, ""),
+        };
+
+        match field {
+            "event" => topic = Some(value),
+            "data" => match data.as_mut() {
+                Some(data) => {
+                    data.push('\n');
+                    data.push_str(value);
+                }
+                None => data = Some(value.to_string()),
+            },
+            _ => (),
+        }
+    }
+
+    let data = match data {
+        Some(data) => data,
+        None => return Ok(None),
+    };
+    let topic = topic
+        .ok_or_else(|| Error::InvalidEvent("Event has no type".to_string()))
+        .and_then(|topic| EventTopic::from_str(topic).map_err(Error::InvalidEvent))?;
+
+    let event = match topic {
+        EventTopic::Head => Event::Head(from_json::<HeadEvent>(&data)?),
+        EventTopic::Block => Event::Block(from_json::<BlockEvent>(&data)?),
+        EventTopic::Attestation => Event::Attestation(Box::new(from_json(&data)?)),
+        EventTopic::FinalizedCheckpoint => {
+            Event::FinalizedCheckpoint(from_json::<FinalizedCheckpointEvent>(&data)?)
+        }
+        EventTopic::ChainReorg => Event::ChainReorg(from_json::<ChainReorgEvent>(&data)?),
+        EventTopic::VoluntaryExit => Event::VoluntaryExit(Box::new(from_json(&data)?)),
+    };
+
+    Ok(Some(event))
+}
+
+fn from_json<T: DeserializeOwned>(data: &str) -> Result<T, Error> {
+    serde_json::from_str(data).map_err(Error::from)
+}
@@ -3,6 +3,9 @@
 //!
 //! Presently, this is only used for testing but it _could_ become a user-facing library.
 
+pub mod eth_v1;
+pub mod events;
+
 use eth2_config::Eth2Config;
 use reqwest::{Client, ClientBuilder, Response, StatusCode};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
@@ -12,10 +15,12 @@ use std::time::Duration;
 use types::{
     Attestation, AttestationData, AttesterSlashing, BeaconBlock, BeaconState, CommitteeIndex,
     Epoch, EthSpec, Fork, Hash256, ProposerSlashing, PublicKey, PublicKeyBytes, Signature,
-    SignedAggregateAndProof, SignedBeaconBlock, Slot,
+    SignedAggregateAndProof, SignedBeaconBlock, SignedVoluntaryExit, Slot,
 };
 use url::Url;
 
+pub use eth_v1::EthV1;
+pub use events::Events;
 pub use operation_pool::PersistedOperationPool;
 pub use proto_array_fork_choice::core::ProtoArray;
 pub use rest_types::{
@@ -61,8 +66,12 @@ pub enum Error {
     SerdeJsonError(serde_json::Error),
     /// The server responded to the request, however it did not return a 200-type success code.
     DidNotSucceed { status: StatusCode, body: String },
+    /// The server responded with an error message from the standard API.
+    ServerMessage(rest_types::eth_v1::ErrorMessage),
     /// The request input was invalid.
     InvalidInput,
+    /// A server-sent event could not be parsed.
+    InvalidEvent(String),
 }
 
 #[derive(Clone)]
@@ -111,6 +120,14 @@ impl<E: EthSpec> HttpClient<E> {
         Consensus(self.clone())
     }
 
+    pub fn eth_v1(&self) -> EthV1<E> {
+        EthV1(self.clone())
+    }
+
+    pub fn events(&self) -> Events<E> {
+        Events(self.clone())
+    }
+
     fn url(&self, path: &str) -> Result<Url, Error> {
         self.url.join(path).map_err(|e| e.into())
     }
@@ -573,6 +590,15 @@ impl<E: EthSpec> Beacon<E> {
         let success = error_for_status(response).await.map_err(Error::from)?;
         success.json().await.map_err(Error::from)
     }
+
+    pub async fn voluntary_exit(&self, voluntary_exit: SignedVoluntaryExit) -> Result<bool, Error> {
+        let client = self.0.clone();
+
+        let url = self.url("voluntary_exit")?;
+        let response = client.json_post::<_>(url, voluntary_exit).await?;
+        let success = error_for_status(response).await.map_err(Error::from)?;
+        success.json().await.map_err(Error::from)
+    }
 }
 
 /// Provides the functions on the `/spec` endpoint of the node.
@@ -669,7 +695,7 @@ impl<E: EthSpec> Consensus<E> {
         &self,
         epoch: Epoch,
         pubkeys: Vec<PublicKeyBytes>,
-    ) -> Result<IndividualVotesResponse, Error> {
+    ) -> Result<Vec<IndividualVotesResponse>, Error> {
         let client = self.0.clone();
         let req_body = IndividualVotesRequest { epoch, pubkeys };
 
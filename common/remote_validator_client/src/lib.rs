@@ -0,0 +1,178 @@
+//! Provides a `ValidatorClientHttpClient` which interacts with the HTTP API of a Lighthouse
+//! validator client.
+//!
+//! All requests are authorized with the API token which the validator client stores in
+//! `API_TOKEN_FILENAME` in its data directory.
+
+pub mod types;
+
+use crate::types::{
+    KeystoreValidatorsPostRequest, PublicKeyBytes, PublishResult, ValidatorData, ValidatorDutyData,
+    ValidatorPatchRequest,
+};
+use reqwest::{header::AUTHORIZATION, Client, RequestBuilder, Response, StatusCode};
+use serde::{de::DeserializeOwned, Serialize};
+use std::fs;
+use std::path::Path;
+use url::Url;
+
+/// The name of the file in the validator client data directory which contains the API token.
+pub const API_TOKEN_FILENAME: &str = "api-token.txt";
+
+#[derive(Debug)]
+pub enum Error {
+    /// Unable to parse a URL. Check the server URL.
+    UrlParseError(url::ParseError),
+    /// The `reqwest` library returned an error.
+    ReqwestError(reqwest::Error),
+    /// The server responded to the request, however it did not return a 200-type success code.
+    DidNotSucceed { status: StatusCode, body: String },
+}
+
+impl From<url::ParseError> for Error {
+    fn from(e: url::ParseError) -> Error {
+        Error::UrlParseError(e)
+    }
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(e: reqwest::Error) -> Error {
+        Error::ReqwestError(e)
+    }
+}
+
+/// Reads the API token from the `API_TOKEN_FILENAME` in the validator client `data_dir`.
+pub fn read_api_token<P: AsRef<Path>>(data_dir: P) -> Result<String, String> {
+    let path = data_dir.as_ref().join(API_TOKEN_FILENAME);
+    fs::read_to_string(&path)
+        .map(|token| token.trim().to_string())
+        .map_err(|e| format!("Unable to read API token from {:?}: {:?}", path, e))
+}
+
+/// Connects to the HTTP API of a validator client.
+#[derive(Clone)]
+pub struct ValidatorClientHttpClient {
+    client: Client,
+    server: Url,
+    api_token: String,
+}
+
+impl ValidatorClientHttpClient {
+    /// Creates a new instance (without connecting to the validator client).
+    pub fn new(server: &str, api_token: String) -> Result<Self, Error> {
+        Ok(Self {
+            client: Client::new(),
+            server: Url::parse(server)?,
+            api_token,
+        })
+    }
+
+    fn url(&self, path: &str) -> Result<Url, Error> {
+        self.server.join(path).map_err(Error::from)
+    }
+
+    fn validator_url(&self, pubkey: &PublicKeyBytes, suffix: &str) -> Result<Url, Error> {
+        self.url(&format!(
+            "lighthouse/validators/0x{}{}",
+            hex::encode(pubkey.as_slice()),
+            suffix
+        ))
+    }
+
+    /// Sends the request with the API token.
+    async fn send(&self, builder: RequestBuilder) -> Result<Response, Error> {
+        let response = builder
+            .header(AUTHORIZATION, format!("Bearer {}", self.api_token))
+            .send()
+            .await?;
+        error_for_status(response).await
+    }
+
+    async fn get<T: DeserializeOwned>(&self, url: Url) -> Result<T, Error> {
+        let response = self.send(self.client.get(url)).await?;
+        response.json().await.map_err(Error::from)
+    }
+
+    async fn json_request<T: Serialize, U: DeserializeOwned>(
+        &self,
+        builder: RequestBuilder,
+        body: &T,
+    ) -> Result<U, Error> {
+        let response = self.send(builder.json(body)).await?;
+        response.json().await.map_err(Error::from)
+    }
+
+    /// `GET lighthouse/validators`
+    pub async fn get_validators(&self) -> Result<Vec<ValidatorData>, Error> {
+        self.get(self.url("lighthouse/validators")?).await
+    }
+
+    /// `GET lighthouse/validators/{pubkey}`
+    ///
+    /// Returns `None` if the validator is unknown to the validator client.
+    pub async fn get_validator(
+        &self,
+        pubkey: &PublicKeyBytes,
+    ) -> Result<Option<ValidatorData>, Error> {
+        match self.get(self.validator_url(pubkey, "")?).await {
+            Ok(data) => Ok(Some(data)),
+            Err(Error::DidNotSucceed {
+                status: StatusCode::NOT_FOUND,
+                ..
+            }) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// `PATCH lighthouse/validators/{pubkey}`
+    pub async fn patch_validator(
+        &self,
+        pubkey: &PublicKeyBytes,
+        enabled: bool,
+    ) -> Result<ValidatorData, Error> {
+        let url = self.validator_url(pubkey, "")?;
+        self.json_request(self.client.patch(url), &ValidatorPatchRequest { enabled })
+            .await
+    }
+
+    /// `POST lighthouse/validators/keystore`
+    pub async fn post_validators_keystore(
+        &self,
+        request: &KeystoreValidatorsPostRequest,
+    ) -> Result<ValidatorData, Error> {
+        let url = self.url("lighthouse/validators/keystore")?;
+        self.json_request(self.client.post(url), request).await
+    }
+
+    /// `GET lighthouse/validators/{pubkey}/duties`
+    pub async fn get_validator_duties(
+        &self,
+        pubkey: &PublicKeyBytes,
+    ) -> Result<Vec<ValidatorDutyData>, Error> {
+        self.get(self.validator_url(pubkey, "/duties")?).await
+    }
+
+    /// `GET lighthouse/validators/{pubkey}/publish_results`
+    pub async fn get_validator_publish_results(
+        &self,
+        pubkey: &PublicKeyBytes,
+    ) -> Result<Vec<PublishResult>, Error> {
+        self.get(self.validator_url(pubkey, "/publish_results")?)
+            .await
+    }
+}
+
+/// Returns an `Error` (with a description) if the `response` was not a 200-type success response.
+///
+/// Distinct from `Response::error_for_status` because it includes the body of the response as
+/// text. This ensures the error message from the server is not discarded.
+async fn error_for_status(response: Response) -> Result<Response, Error> {
+    let status = response.status();
+
+    if status.is_success() {
+        Ok(response)
+    } else {
+        let body = response.text().await?;
+        Err(Error::DidNotSucceed { status, body })
+    }
+}
@@ -0,0 +1,75 @@
+//! The types sent to and from the validator client HTTP API.
+
+use serde::{Deserialize, Serialize};
+
+pub use ::types::{CommitteeIndex, Epoch, PublicKeyBytes, Slot};
+pub use eth2_keystore::Keystore;
+
+/// A validator known to the validator client.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ValidatorData {
+    /// If `false`, the validator client will not sign any messages with this validator.
+    pub enabled: bool,
+    pub voting_pubkey: PublicKeyBytes,
+}
+
+/// The body of a request to enable or disable a validator.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ValidatorPatchRequest {
+    pub enabled: bool,
+}
+
+/// The body of a request to import an EIP-2335 keystore as a new validator.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KeystoreValidatorsPostRequest {
+    /// The password which decrypts `keystore`. It is stored in the validator client's secrets
+    /// directory.
+    pub password: String,
+    /// If `false`, the validator is imported in the disabled state.
+    pub enable: bool,
+    pub keystore: Keystore,
+}
+
+/// The duties of a validator for a single epoch, as known to the validator client.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ValidatorDutyData {
+    pub epoch: Epoch,
+    /// `None` if the validator is not yet known to the beacon chain.
+    pub validator_index: Option<u64>,
+    pub attestation_slot: Option<Slot>,
+    pub attestation_committee_index: Option<CommitteeIndex>,
+    pub attestation_committee_position: Option<usize>,
+    pub block_proposal_slots: Vec<Slot>,
+    pub is_aggregator: bool,
+}
+
+/// The type of a message published by the validator client.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PublishKind {
+    Block,
+    Attestation,
+    AggregateAndProof,
+}
+
+/// The response of the beacon nodes to a published message.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PublishOutcome {
+    /// The message was valid and has been published to the network.
+    Valid,
+    /// The message was not valid and may or may not have been published to the network.
+    Invalid(String),
+    /// The beacon nodes responded with an unknown status code.
+    Unknown,
+    /// The message could not be sent to any beacon node.
+    Failed(String),
+}
+
+/// The result of publishing a message signed by a validator.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PublishResult {
+    pub kind: PublishKind,
+    pub slot: Slot,
+    pub outcome: PublishOutcome,
+}
@@ -0,0 +1,694 @@
+//! Types for the standard Ethereum Beacon Node API (`/eth/v1/...`).
+//!
+//! These types are shared by the beacon node HTTP server and the `remote_beacon_node` client.
+//!
+//! As required by the standard API, integers are encoded as quoted decimal strings.
+
+use bls::{PublicKeyBytes, SignatureBytes};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use types::utils::{fork_from_hex_str, fork_to_hex_str, quoted_u64, quoted_u64_vec};
+use types::{BeaconBlockHeader, Checkpoint, CommitteeIndex, Epoch, Hash256, Slot, Validator};
+
+/// The prefix of every path in the standard API.
+pub const ETH_V1_PATH_PREFIX: &str = "/eth/v1/";
+
+/// Identifies a `BeaconState` in the standard API.
+///
+/// E.g., `head`, `genesis`, `finalized`, `justified`, `1234` or `0x3b6a...`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StateId {
+    Head,
+    Genesis,
+    Finalized,
+    Justified,
+    Slot(Slot),
+    Root(Hash256),
+}
+
+impl FromStr for StateId {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "head" => Ok(StateId::Head),
+            "genesis" => Ok(StateId::Genesis),
+            "finalized" => Ok(StateId::Finalized),
+            "justified" => Ok(StateId::Justified),
+            other => parse_slot_or_root(other)
+                .map(|slot_or_root| match slot_or_root {
+                    SlotOrRoot::Slot(slot) => StateId::Slot(slot),
+                    SlotOrRoot::Root(root) => StateId::Root(root),
+                })
+                .map_err(|e| format!("Invalid state id: {}", e)),
+        }
+    }
+}
+
+impl fmt::Display for StateId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StateId::Head => write!(f, "head"),
+            StateId::Genesis => write!(f, "genesis"),
+            StateId::Finalized => write!(f, "finalized"),
+            StateId::Justified => write!(f, "justified"),
+            StateId::Slot(slot) => write!(f, "{}", slot),
+            StateId::Root(root) => write!(f, "{:?}", root),
+        }
+    }
+}
+
+/// Identifies a `SignedBeaconBlock` in the standard API.
+///
+/// E.g., `head`, `genesis`, `finalized`, `justified`, `1234` or `0x3b6a...`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BlockId {
+    Head,
+    Genesis,
+    Finalized,
+    Justified,
+    Slot(Slot),
+    Root(Hash256),
+}
+
+impl FromStr for BlockId {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "head" => Ok(BlockId::Head),
+            "genesis" => Ok(BlockId::Genesis),
+            "finalized" => Ok(BlockId::Finalized),
+            "justified" => Ok(BlockId::Justified),
+            other => parse_slot_or_root(other)
+                .map(|slot_or_root| match slot_or_root {
+                    SlotOrRoot::Slot(slot) => BlockId::Slot(slot),
+                    SlotOrRoot::Root(root) => BlockId::Root(root),
+                })
+                .map_err(|e| format!("Invalid block id: {}", e)),
+        }
+    }
+}
+
+impl fmt::Display for BlockId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BlockId::Head => write!(f, "head"),
+            BlockId::Genesis => write!(f, "genesis"),
+            BlockId::Finalized => write!(f, "finalized"),
+            BlockId::Justified => write!(f, "justified"),
+            BlockId::Slot(slot) => write!(f, "{}", slot),
+            BlockId::Root(root) => write!(f, "{:?}", root),
+        }
+    }
+}
+
+enum SlotOrRoot {
+    Slot(Slot),
+    Root(Hash256),
+}
+
+/// Parses either a decimal slot or a `0x` prefixed root.
+fn parse_slot_or_root(s: &str) -> Result<SlotOrRoot, String> {
+    if s.starts_with("0x") {
+        s[2..]
+            .parse()
+            .map(SlotOrRoot::Root)
+            .map_err(|e| format!("unable to parse root {}: {:?}", s, e))
+    } else {
+        s.parse::<u64>()
+            .map(|slot| SlotOrRoot::Slot(Slot::new(slot)))
+            .map_err(|e| format!("unable to parse slot {}: {:?}", s, e))
+    }
+}
+
+/// Identifies a validator in the standard API, either by its public key or its index.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValidatorId {
+    PublicKey(PublicKeyBytes),
+    Index(u64),
+}
+
+impl FromStr for ValidatorId {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.starts_with("0x") {
+            let bytes = hex::decode(&s[2..])
+                .map_err(|e| format!("Invalid validator pubkey hex {}: {:?}", s, e))?;
+            PublicKeyBytes::from_bytes(&bytes)
+                .map(ValidatorId::PublicKey)
+                .map_err(|e| format!("Invalid validator pubkey {}: {:?}", s, e))
+        } else {
+            s.parse()
+                .map(ValidatorId::Index)
+                .map_err(|e| format!("Invalid validator index {}: {:?}", s, e))
+        }
+    }
+}
+
+impl fmt::Display for ValidatorId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ValidatorId::PublicKey(pubkey) => write!(f, "0x{}", hex::encode(pubkey.as_slice())),
+            ValidatorId::Index(index) => write!(f, "{}", index),
+        }
+    }
+}
+
+/// The envelope of every successful response from the standard API.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GenericResponse<T> {
+    pub data: T,
+}
+
+impl<T> From<T> for GenericResponse<T> {
+    fn from(data: T) -> Self {
+        Self { data }
+    }
+}
+
+/// The body of every error response from the standard API.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ErrorMessage {
+    /// The HTTP status code of the response.
+    pub code: u16,
+    /// A human-readable description of the error.
+    pub message: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GenesisData {
+    #[serde(with = "quoted_u64")]
+    pub genesis_time: u64,
+    pub genesis_validators_root: Hash256,
+    #[serde(
+        serialize_with = "fork_to_hex_str",
+        deserialize_with = "fork_from_hex_str"
+    )]
+    pub genesis_fork_version: [u8; 4],
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RootData {
+    pub root: Hash256,
+}
+
+impl From<Hash256> for RootData {
+    fn from(root: Hash256) -> Self {
+        Self { root }
+    }
+}
+
+/// A `Checkpoint` with a quoted epoch.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CheckpointData {
+    #[serde(with = "quoted_u64")]
+    pub epoch: Epoch,
+    pub root: Hash256,
+}
+
+impl From<Checkpoint> for CheckpointData {
+    fn from(checkpoint: Checkpoint) -> Self {
+        Self {
+            epoch: checkpoint.epoch,
+            root: checkpoint.root,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FinalityCheckpointsData {
+    pub previous_justified: CheckpointData,
+    pub current_justified: CheckpointData,
+    pub finalized: CheckpointData,
+}
+
+/// The status of a validator, as defined by the standard API.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ValidatorStatus {
+    PendingInitialized,
+    PendingQueued,
+    ActiveOngoing,
+    ActiveExiting,
+    ActiveSlashed,
+    ExitedUnslashed,
+    ExitedSlashed,
+    WithdrawalPossible,
+    WithdrawalDone,
+}
+
+impl ValidatorStatus {
+    /// Determine the status of `validator` at `epoch`, given its `balance`.
+    pub fn from_validator(
+        validator: &Validator,
+        balance: u64,
+        epoch: Epoch,
+        far_future_epoch: Epoch,
+    ) -> Self {
+        if validator.activation_epoch > epoch {
+            if validator.activation_eligibility_epoch == far_future_epoch {
+                ValidatorStatus::PendingInitialized
+            } else {
+                ValidatorStatus::PendingQueued
+            }
+        } else if validator.is_active_at(epoch) {
+            if validator.slashed {
+                ValidatorStatus::ActiveSlashed
+            } else if validator.exit_epoch == far_future_epoch {
+                ValidatorStatus::ActiveOngoing
+            } else {
+                ValidatorStatus::ActiveExiting
+            }
+        } else if !validator.is_withdrawable_at(epoch) {
+            if validator.slashed {
+                ValidatorStatus::ExitedSlashed
+            } else {
+                ValidatorStatus::ExitedUnslashed
+            }
+        } else if balance > 0 {
+            ValidatorStatus::WithdrawalPossible
+        } else {
+            ValidatorStatus::WithdrawalDone
+        }
+    }
+}
+
+/// A `Validator` with quoted integers.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ValidatorInfo {
+    pub pubkey: PublicKeyBytes,
+    pub withdrawal_credentials: Hash256,
+    #[serde(with = "quoted_u64")]
+    pub effective_balance: u64,
+    pub slashed: bool,
+    #[serde(with = "quoted_u64")]
+    pub activation_eligibility_epoch: Epoch,
+    #[serde(with = "quoted_u64")]
+    pub activation_epoch: Epoch,
+    #[serde(with = "quoted_u64")]
+    pub exit_epoch: Epoch,
+    #[serde(with = "quoted_u64")]
+    pub withdrawable_epoch: Epoch,
+}
+
+impl From<Validator> for ValidatorInfo {
+    fn from(validator: Validator) -> Self {
+        Self {
+            pubkey: validator.pubkey,
+            withdrawal_credentials: validator.withdrawal_credentials,
+            effective_balance: validator.effective_balance,
+            slashed: validator.slashed,
+            activation_eligibility_epoch: validator.activation_eligibility_epoch,
+            activation_epoch: validator.activation_epoch,
+            exit_epoch: validator.exit_epoch,
+            withdrawable_epoch: validator.withdrawable_epoch,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ValidatorData {
+    #[serde(with = "quoted_u64")]
+    pub index: u64,
+    #[serde(with = "quoted_u64")]
+    pub balance: u64,
+    pub status: ValidatorStatus,
+    pub validator: ValidatorInfo,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CommitteeData {
+    #[serde(with = "quoted_u64")]
+    pub index: CommitteeIndex,
+    #[serde(with = "quoted_u64")]
+    pub slot: Slot,
+    #[serde(with = "quoted_u64_vec")]
+    pub validators: Vec<u64>,
+}
+
+/// A `BeaconBlockHeader` with quoted integers.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BlockHeaderMessage {
+    #[serde(with = "quoted_u64")]
+    pub slot: Slot,
+    #[serde(with = "quoted_u64")]
+    pub proposer_index: u64,
+    pub parent_root: Hash256,
+    pub state_root: Hash256,
+    pub body_root: Hash256,
+}
+
+impl From<BeaconBlockHeader> for BlockHeaderMessage {
+    fn from(header: BeaconBlockHeader) -> Self {
+        Self {
+            slot: header.slot,
+            proposer_index: header.proposer_index,
+            parent_root: header.parent_root,
+            state_root: header.state_root,
+            body_root: header.body_root,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BlockHeaderAndSignature {
+    pub message: BlockHeaderMessage,
+    pub signature: SignatureBytes,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BlockHeaderData {
+    pub root: Hash256,
+    /// `true` if the block is in the canonical chain of the head.
+    pub canonical: bool,
+    pub header: BlockHeaderAndSignature,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChainHeadData {
+    #[serde(with = "quoted_u64")]
+    pub slot: Slot,
+    pub root: Hash256,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IdentityData {
+    pub peer_id: String,
+    /// The ENR of the node, encoded as base64.
+    pub enr: String,
+    pub p2p_addresses: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PeerData {
+    pub peer_id: String,
+    pub state: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VersionData {
+    pub version: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SyncingData {
+    pub is_syncing: bool,
+    #[serde(with = "quoted_u64")]
+    pub head_slot: Slot,
+    /// The number of slots between the head and the current slot.
+    #[serde(with = "quoted_u64")]
+    pub sync_distance: Slot,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AttesterData {
+    pub pubkey: PublicKeyBytes,
+    #[serde(with = "quoted_u64")]
+    pub validator_index: u64,
+    #[serde(with = "quoted_u64")]
+    pub committee_index: CommitteeIndex,
+    #[serde(with = "quoted_u64")]
+    pub committee_length: u64,
+    #[serde(with = "quoted_u64")]
+    pub committees_at_slot: u64,
+    #[serde(with = "quoted_u64")]
+    pub validator_committee_index: u64,
+    #[serde(with = "quoted_u64")]
+    pub slot: Slot,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProposerData {
+    pub pubkey: PublicKeyBytes,
+    #[serde(with = "quoted_u64")]
+    pub validator_index: u64,
+    #[serde(with = "quoted_u64")]
+    pub slot: Slot,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BeaconCommitteeSubscription {
+    #[serde(with = "quoted_u64")]
+    pub validator_index: u64,
+    #[serde(with = "quoted_u64")]
+    pub committee_index: CommitteeIndex,
+    #[serde(with = "quoted_u64")]
+    pub committees_at_slot: u64,
+    #[serde(with = "quoted_u64")]
+    pub slot: Slot,
+    pub is_aggregator: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn state_id_round_trip() {
+        let root = Hash256::from_low_u64_be(42);
+
+        for state_id in &[
+            StateId::Head,
+            StateId::Genesis,
+            StateId::Finalized,
+            StateId::Justified,
+            StateId::Slot(Slot::new(1234)),
+            StateId::Root(root),
+        ] {
+            assert_eq!(state_id.to_string().parse::<StateId>(), Ok(*state_id));
+        }
+
+        assert!("cats".parse::<StateId>().is_err());
+        assert!("0x".parse::<StateId>().is_err());
+        assert!("0x00".parse::<StateId>().is_err());
+    }
+
+    #[test]
+    fn block_id_round_trip() {
+        let root = Hash256::from_low_u64_be(42);
+
+        for block_id in &[
+            BlockId::Head,
+            BlockId::Genesis,
+            BlockId::Finalized,
+            BlockId::Justified,
+            BlockId::Slot(Slot::new(0)),
+            BlockId::Root(root),
+        ] {
+            assert_eq!(block_id.to_string().parse::<BlockId>(), Ok(*block_id));
+        }
+
+        assert!("-1".parse::<BlockId>().is_err());
+    }
+
+    #[test]
+    fn validator_id_round_trip() {
+        let pubkey = PublicKeyBytes::from_bytes(&[1; 48]).unwrap();
+
+        for validator_id in &[ValidatorId::Index(7), ValidatorId::PublicKey(pubkey)] {
+            assert_eq!(
+                validator_id.to_string().parse::<ValidatorId>(),
+                Ok(validator_id.clone())
+            );
+        }
+
+        assert!("0x0102".parse::<ValidatorId>().is_err());
+    }
+
+    #[test]
+    fn integers_are_quoted() {
+        let genesis = GenesisData {
+            genesis_time: 1_606_824_023,
+            genesis_validators_root: Hash256::zero(),
+            genesis_fork_version: [0; 4],
+        };
+        let json = serde_json::to_value(&genesis).unwrap();
+        assert_eq!(json["genesis_time"], "1606824023");
+        assert_eq!(
+            serde_json::from_value::<GenesisData>(json).unwrap(),
+            genesis
+        );
+
+        let committee = CommitteeData {
+            index: 1,
+            slot: Slot::new(2),
+            validators: vec![3, 4],
+        };
+        let json = serde_json::to_string(&committee).unwrap();
+        assert_eq!(json, r#"{"index":"1","slot":"2","validators":["3","4"]}"#);
+        assert_eq!(
+            serde_json::from_str::<CommitteeData>(&json).unwrap(),
+            committee
+        );
+
+        let attester = AttesterData {
+            pubkey: PublicKeyBytes::empty(),
+            validator_index: 5,
+            committee_index: 6,
+            committee_length: 7,
+            committees_at_slot: 8,
+            validator_committee_index: 9,
+            slot: Slot::new(10),
+        };
+        let json = serde_json::to_value(&attester).unwrap();
+        assert_eq!(json["validator_index"], "5");
+        assert_eq!(json["committee_index"], "6");
+        assert_eq!(json["committee_length"], "7");
+        assert_eq!(json["committees_at_slot"], "8");
+        assert_eq!(json["validator_committee_index"], "9");
+        assert_eq!(json["slot"], "10");
+        assert_eq!(
+            serde_json::from_value::<AttesterData>(json).unwrap(),
+            attester
+        );
+
+        let proposer = ProposerData {
+            pubkey: PublicKeyBytes::empty(),
+            validator_index: 11,
+            slot: Slot::new(12),
+        };
+        let json = serde_json::to_value(&proposer).unwrap();
+        assert_eq!(json["validator_index"], "11");
+        assert_eq!(json["slot"], "12");
+        assert_eq!(
+            serde_json::from_value::<ProposerData>(json).unwrap(),
+            proposer
+        );
+
+        let checkpoints = FinalityCheckpointsData {
+            previous_justified: Checkpoint {
+                epoch: Epoch::new(17),
+                root: Hash256::zero(),
+            }
+            .into(),
+            current_justified: Checkpoint {
+                epoch: Epoch::new(18),
+                root: Hash256::zero(),
+            }
+            .into(),
+            finalized: Checkpoint {
+                epoch: Epoch::new(19),
+                root: Hash256::zero(),
+            }
+            .into(),
+        };
+        let json = serde_json::to_value(&checkpoints).unwrap();
+        assert_eq!(json["previous_justified"]["epoch"], "17");
+        assert_eq!(json["current_justified"]["epoch"], "18");
+        assert_eq!(json["finalized"]["epoch"], "19");
+        assert_eq!(
+            serde_json::from_value::<FinalityCheckpointsData>(json).unwrap(),
+            checkpoints
+        );
+
+        let validator = ValidatorData {
+            index: 20,
+            balance: 21,
+            status: ValidatorStatus::ActiveOngoing,
+            validator: Validator {
+                pubkey: PublicKeyBytes::empty(),
+                withdrawal_credentials: Hash256::zero(),
+                effective_balance: 22,
+                slashed: false,
+                activation_eligibility_epoch: Epoch::new(23),
+                activation_epoch: Epoch::new(24),
+                exit_epoch: Epoch::new(25),
+                withdrawable_epoch: Epoch::new(26),
+            }
+            .into(),
+        };
+        let json = serde_json::to_value(&validator).unwrap();
+        assert_eq!(json["index"], "20");
+        assert_eq!(json["balance"], "21");
+        assert_eq!(json["validator"]["effective_balance"], "22");
+        assert_eq!(json["validator"]["activation_eligibility_epoch"], "23");
+        assert_eq!(json["validator"]["activation_epoch"], "24");
+        assert_eq!(json["validator"]["exit_epoch"], "25");
+        assert_eq!(json["validator"]["withdrawable_epoch"], "26");
+        assert_eq!(
+            serde_json::from_value::<ValidatorData>(json).unwrap(),
+            validator
+        );
+
+        let header = BlockHeaderAndSignature {
+            message: BeaconBlockHeader {
+                slot: Slot::new(27),
+                proposer_index: 28,
+                parent_root: Hash256::zero(),
+                state_root: Hash256::zero(),
+                body_root: Hash256::zero(),
+            }
+            .into(),
+            signature: SignatureBytes::empty(),
+        };
+        let json = serde_json::to_value(&header).unwrap();
+        assert_eq!(json["message"]["slot"], "27");
+        assert_eq!(json["message"]["proposer_index"], "28");
+        assert_eq!(
+            serde_json::from_value::<BlockHeaderAndSignature>(json).unwrap(),
+            header
+        );
+
+        let subscription = BeaconCommitteeSubscription {
+            validator_index: 13,
+            committee_index: 14,
+            committees_at_slot: 15,
+            slot: Slot::new(16),
+            is_aggregator: true,
+        };
+        let json = serde_json::to_string(&subscription).unwrap();
+        assert_eq!(
+            json,
+            r#"{"validator_index":"13","committee_index":"14","committees_at_slot":"15","slot":"16","is_aggregator":true}"#
+        );
+        assert_eq!(
+            serde_json::from_str::<BeaconCommitteeSubscription>(&json).unwrap(),
+            subscription
+        );
+    }
+
+    #[test]
+    fn validator_status() {
+        let far_future_epoch = Epoch::new(u64::max_value());
+        let mut validator = Validator {
+            pubkey: PublicKeyBytes::empty(),
+            withdrawal_credentials: Hash256::zero(),
+            effective_balance: 32,
+            slashed: false,
+            activation_eligibility_epoch: far_future_epoch,
+            activation_epoch: far_future_epoch,
+            exit_epoch: far_future_epoch,
+            withdrawable_epoch: far_future_epoch,
+        };
+        let status = |validator: &Validator, balance, epoch| {
+            ValidatorStatus::from_validator(validator, balance, Epoch::new(epoch), far_future_epoch)
+        };
+
+        assert_eq!(
+            status(&validator, 32, 0),
+            ValidatorStatus::PendingInitialized
+        );
+
+        validator.activation_eligibility_epoch = Epoch::new(1);
+        validator.activation_epoch = Epoch::new(4);
+        assert_eq!(status(&validator, 32, 2), ValidatorStatus::PendingQueued);
+        assert_eq!(status(&validator, 32, 4), ValidatorStatus::ActiveOngoing);
+
+        validator.exit_epoch = Epoch::new(10);
+        validator.withdrawable_epoch = Epoch::new(20);
+        assert_eq!(status(&validator, 32, 5), ValidatorStatus::ActiveExiting);
+        assert_eq!(status(&validator, 32, 10), ValidatorStatus::ExitedUnslashed);
+        assert_eq!(
+            status(&validator, 32, 20),
+            ValidatorStatus::WithdrawalPossible
+        );
+        assert_eq!(status(&validator, 0, 20), ValidatorStatus::WithdrawalDone);
+
+        validator.slashed = true;
+        assert_eq!(status(&validator, 32, 5), ValidatorStatus::ActiveSlashed);
+        assert_eq!(status(&validator, 32, 10), ValidatorStatus::ExitedSlashed);
+    }
+}
@@ -0,0 +1,119 @@
+//! Types for the server-sent events streamed by the `/events` endpoint of the beacon node.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use types::{Attestation, Epoch, EthSpec, Hash256, SignedVoluntaryExit, Slot};
+
+/// A topic which may be subscribed to via the `topics` query parameter of `/events`.
+///
+/// The topic is also sent as the `event` field of each server-sent event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EventTopic {
+    Head,
+    Block,
+    Attestation,
+    FinalizedCheckpoint,
+    ChainReorg,
+    VoluntaryExit,
+}
+
+impl EventTopic {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            EventTopic::Head => "head",
+            EventTopic::Block => "block",
+            EventTopic::Attestation => "attestation",
+            EventTopic::FinalizedCheckpoint => "finalized_checkpoint",
+            EventTopic::ChainReorg => "chain_reorg",
+            EventTopic::VoluntaryExit => "voluntary_exit",
+        }
+    }
+}
+
+impl FromStr for EventTopic {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "head" => Ok(EventTopic::Head),
+            "block" => Ok(EventTopic::Block),
+            "attestation" => Ok(EventTopic::Attestation),
+            "finalized_checkpoint" => Ok(EventTopic::FinalizedCheckpoint),
+            "chain_reorg" => Ok(EventTopic::ChainReorg),
+            "voluntary_exit" => Ok(EventTopic::VoluntaryExit),
+            other => Err(format!("Unknown event topic: {}", other)),
+        }
+    }
+}
+
+impl fmt::Display for EventTopic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// The canonical head of the chain has changed.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct HeadEvent {
+    pub slot: Slot,
+    pub block: Hash256,
+    pub state: Hash256,
+    pub previous_block: Hash256,
+    /// `true` if the new head does not descend from the previous head.
+    pub reorg: bool,
+}
+
+/// A block has been imported.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BlockEvent {
+    pub slot: Slot,
+    pub block: Hash256,
+}
+
+/// The finalized checkpoint has changed.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FinalizedCheckpointEvent {
+    pub block: Hash256,
+    pub epoch: Epoch,
+}
+
+/// The canonical head has moved to a chain which does not descend from the previous head.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ChainReorgEvent {
+    /// The slot of the new head.
+    pub slot: Slot,
+    /// The number of blocks of the previous head's chain which are no longer canonical, counted in
+    /// slots from the common ancestor to the previous head.
+    pub depth: u64,
+    pub old_head_block: Hash256,
+    pub new_head_block: Hash256,
+    pub old_head_state: Hash256,
+    pub new_head_state: Hash256,
+    /// The epoch of the new head.
+    pub epoch: Epoch,
+}
+
+/// An event received from the `/events` endpoint.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Event<T: EthSpec> {
+    Head(HeadEvent),
+    Block(BlockEvent),
+    Attestation(Box<Attestation<T>>),
+    FinalizedCheckpoint(FinalizedCheckpointEvent),
+    ChainReorg(ChainReorgEvent),
+    VoluntaryExit(Box<SignedVoluntaryExit>),
+}
+
+impl<T: EthSpec> Event<T> {
+    pub fn topic(&self) -> EventTopic {
+        match self {
+            Event::Head(_) => EventTopic::Head,
+            Event::Block(_) => EventTopic::Block,
+            Event::Attestation(_) => EventTopic::Attestation,
+            Event::FinalizedCheckpoint(_) => EventTopic::FinalizedCheckpoint,
+            Event::ChainReorg(_) => EventTopic::ChainReorg,
+            Event::VoluntaryExit(_) => EventTopic::VoluntaryExit,
+        }
+    }
+}
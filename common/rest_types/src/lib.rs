@@ -4,6 +4,8 @@
 
 mod beacon;
 mod consensus;
+pub mod eth_v1;
+pub mod events;
 mod node;
 mod validator;
 
@@ -37,6 +37,7 @@ pub enum Error {
     KeystoreError(KeystoreError),
     UnableToOpenDir(DirError),
     UninitializedVotingKeystore,
+    VotingKeypairMismatch,
     UninitializedWithdrawalKeystore,
     #[cfg(feature = "insecure_keys")]
     InsecureKeysError(String),
@@ -53,6 +54,7 @@ pub struct Builder<'a> {
     base_validators_dir: PathBuf,
     password_dir: PathBuf,
     pub(crate) voting_keystore: Option<(Keystore, PlainText)>,
+    voting_keypair: Option<Keypair>,
     pub(crate) withdrawal_keystore: Option<(Keystore, PlainText)>,
     store_withdrawal_keystore: bool,
     deposit_info: Option<(u64, &'a ChainSpec)>,
@@ -65,6 +67,7 @@ impl<'a> Builder<'a> {
             base_validators_dir,
             password_dir,
             voting_keystore: None,
+            voting_keypair: None,
             withdrawal_keystore: None,
             store_withdrawal_keystore: true,
             deposit_info: None,
@@ -73,12 +76,27 @@ impl<'a> Builder<'a> {
 
     /// Build the `ValidatorDir` use the given `keystore` which can be unlocked with `password`.
     ///
-    /// The builder will not necessarily check that `password` can unlock `keystore`.
+    /// `Self::build` will fail if `password` cannot unlock `keystore`.
     pub fn voting_keystore(mut self, keystore: Keystore, password: &[u8]) -> Self {
         self.voting_keystore = Some((keystore, password.to_vec().into()));
         self
     }
 
+    /// Build the `ValidatorDir` use the given `keystore`, which the caller has already unlocked
+    /// with `password` to obtain `keypair`.
+    ///
+    /// `Self::build` will not decrypt `keystore` again, but will fail if `keypair` does not match
+    /// the public key of `keystore`.
+    pub fn decrypted_voting_keystore(
+        mut self,
+        keystore: Keystore,
+        password: &[u8],
+        keypair: Keypair,
+    ) -> Self {
+        self.voting_keypair = Some(keypair);
+        self.voting_keystore(keystore, password)
+    }
+
     /// Build the `ValidatorDir` use the given `keystore` which can be unlocked with `password`.
     ///
     /// The builder will not necessarily check that `password` can unlock `keystore`.
@@ -133,6 +151,26 @@ impl<'a> Builder<'a> {
             .voting_keystore
             .ok_or_else(|| Error::UninitializedVotingKeystore)?;
 
+        // The withdrawal keystore must be initialized in order to store it or create an eth1
+        // deposit.
+        if (self.store_withdrawal_keystore || self.deposit_info.is_some())
+            && self.withdrawal_keystore.is_none()
+        {
+            return Err(Error::UninitializedWithdrawalKeystore);
+        };
+
+        // Attempt to decrypt the voting keypair before creating any files, ensuring that the
+        // password is correct and the keystore pubkey matches its secret key.
+        let voting_keypair = match self.voting_keypair {
+            Some(keypair) => {
+                if keypair.pk.as_hex_string() != format!("0x{}", voting_keystore.pubkey()) {
+                    return Err(Error::VotingKeypairMismatch);
+                }
+                keypair
+            }
+            None => voting_keystore.decrypt_keypair(voting_password.as_bytes())?,
+        };
+
         let dir = self
             .base_validators_dir
             .join(format!("0x{}", voting_keystore.pubkey()));
@@ -143,18 +181,7 @@ impl<'a> Builder<'a> {
             create_dir_all(&dir).map_err(Error::UnableToCreateDir)?;
         }
 
-        // The withdrawal keystore must be initialized in order to store it or create an eth1
-        // deposit.
-        if (self.store_withdrawal_keystore || self.deposit_info.is_some())
-            && self.withdrawal_keystore.is_none()
-        {
-            return Err(Error::UninitializedWithdrawalKeystore);
-        };
-
         if let Some((withdrawal_keystore, withdrawal_password)) = self.withdrawal_keystore {
-            // Attempt to decrypt the voting keypair.
-            let voting_keypair = voting_keystore.decrypt_keypair(voting_password.as_bytes())?;
-
             // Attempt to decrypt the withdrawal keypair.
             let withdrawal_keypair =
                 withdrawal_keystore.decrypt_keypair(withdrawal_password.as_bytes())?;
@@ -13,7 +13,9 @@ mod manager;
 pub mod unencrypted_keys;
 mod validator_dir;
 
-pub use crate::validator_dir::{Error, Eth1DepositData, ValidatorDir, ETH1_DEPOSIT_TX_HASH_FILE};
+pub use crate::validator_dir::{
+    Error, Eth1DepositData, ValidatorDir, DISABLED_FILE, ETH1_DEPOSIT_TX_HASH_FILE,
+};
 pub use builder::{
     Builder, Error as BuilderError, ETH1_DEPOSIT_DATA_FILE, VOTING_KEYSTORE_FILE,
     WITHDRAWAL_KEYSTORE_FILE,
@@ -1,4 +1,5 @@
-use crate::{Error as ValidatorDirError, ValidatorDir};
+use crate::validator_dir::read_keystore;
+use crate::{Error as ValidatorDirError, ValidatorDir, VOTING_KEYSTORE_FILE};
 use bls::Keypair;
 use rayon::prelude::*;
 use slog::{info, Logger};
@@ -126,4 +127,25 @@ impl Manager {
                 .map(|path| (format!("{:?}", path), path)),
         ))
     }
+
+    /// Returns a map of `0x`-prefixed voting public key to validator directory, for each
+    /// directory in `self.dir` that contains a voting keystore.
+    ///
+    /// The keystores are not decrypted and the directories are not locked, so this may be used
+    /// whilst the validators are in use by another process.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if a directory is unable to be read or contains an invalid keystore.
+    pub fn voting_pubkeys(&self) -> Result<HashMap<String, PathBuf>, Error> {
+        self.iter_dir()?
+            .into_iter()
+            .filter(|path| path.join(VOTING_KEYSTORE_FILE).exists())
+            .map(|path| {
+                read_keystore(&path, VOTING_KEYSTORE_FILE)
+                    .map(|keystore| (format!("0x{}", keystore.pubkey()), path))
+                    .map_err(Error::ValidatorDirError)
+            })
+            .collect()
+    }
 }
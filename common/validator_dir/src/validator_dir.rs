@@ -8,7 +8,7 @@ use std::fs::{read, remove_file, write, OpenOptions};
 use std::io;
 use std::path::{Path, PathBuf};
 use tree_hash::TreeHash;
-use types::{DepositData, Hash256, Keypair};
+use types::{DepositData, Hash256, Keypair, PublicKey};
 
 /// The file used for indicating if a directory is in-use by another process.
 const LOCK_FILE: &str = ".lock";
@@ -16,6 +16,9 @@ const LOCK_FILE: &str = ".lock";
 /// The file used to save the Eth1 transaction hash from a deposit.
 pub const ETH1_DEPOSIT_TX_HASH_FILE: &str = "eth1-deposit-tx-hash.txt";
 
+/// The file used for indicating that the validator client should not sign with this validator.
+pub const DISABLED_FILE: &str = "disabled";
+
 #[derive(Debug)]
 pub enum Error {
     DirectoryDoesNotExist(PathBuf),
@@ -26,6 +29,7 @@ pub enum Error {
     UnableToOpenPassword(io::Error),
     UnableToReadPassword(PathBuf),
     UnableToDecryptKeypair(KeystoreError),
+    InvalidKeystorePubkey(String),
     UnableToReadDepositData(io::Error),
     DepositDataMissing0xPrefix,
     DepositDataNotUtf8,
@@ -37,6 +41,8 @@ pub enum Error {
     UnableToParseDepositData(deposit_contract::DecodeError),
     Eth1TxHashExists(PathBuf),
     UnableToWriteEth1TxHash(io::Error),
+    UnableToCreateDisabledFile(io::Error),
+    UnableToRemoveDisabledFile(io::Error),
     /// The deposit root in the deposit data file does not match the one generated locally. This is
     /// generally caused by supplying an `amount` at deposit-time that is different to the one used
     /// at generation-time.
@@ -110,6 +116,21 @@ impl ValidatorDir {
         unlock_keypair(&self.dir.clone(), VOTING_KEYSTORE_FILE, password_dir)
     }
 
+    /// Reads the public key from the voting keystore in `self.dir`, without decrypting it.
+    ///
+    /// This is useful when the secret key is held elsewhere, such as by a remote signer.
+    ///
+    /// ## Errors
+    ///
+    /// If there is a filesystem error or the keystore contains an invalid public key.
+    pub fn voting_public_key(&self) -> Result<PublicKey, Error> {
+        let keystore = read_keystore(&self.dir, VOTING_KEYSTORE_FILE)?;
+        hex::decode(keystore.pubkey())
+            .map_err(|e| format!("{:?}", e))
+            .and_then(|bytes| PublicKey::from_bytes(&bytes).map_err(|e| format!("{:?}", e)))
+            .map_err(Error::InvalidKeystorePubkey)
+    }
+
     /// Attempts to read the keystore in `self.dir` and decrypt the keypair using a password file
     /// in `password_dir`.
     ///
@@ -150,6 +171,30 @@ impl ValidatorDir {
         write(path, tx_hash.as_bytes()).map_err(Error::UnableToWriteEth1TxHash)
     }
 
+    /// Indicates if the validator has been disabled, in which case the validator client should not
+    /// sign any messages with it.
+    pub fn is_disabled(&self) -> bool {
+        self.dir.join(DISABLED_FILE).exists()
+    }
+
+    /// Disables or re-enables the validator by creating or removing a file in `self.dir`.
+    /// Artificially requires `mut self` to prevent concurrent calls.
+    ///
+    /// ## Errors
+    ///
+    /// If there is a file-system error.
+    pub fn set_disabled(&mut self, disabled: bool) -> Result<(), Error> {
+        let path = self.dir.join(DISABLED_FILE);
+
+        if disabled && !path.exists() {
+            write(path, &[]).map_err(Error::UnableToCreateDisabledFile)
+        } else if !disabled && path.exists() {
+            remove_file(path).map_err(Error::UnableToRemoveDisabledFile)
+        } else {
+            Ok(())
+        }
+    }
+
     /// Attempts to read files in `self.dir` and return an `Eth1DepositData` that can be used for
     /// submitting an Eth1 deposit.
     ///
@@ -214,20 +259,25 @@ impl Drop for ValidatorDir {
     }
 }
 
-/// Attempts to load and decrypt a keystore.
-fn unlock_keypair<P: AsRef<Path>>(
-    keystore_dir: &PathBuf,
-    filename: &str,
-    password_dir: P,
-) -> Result<Keypair, Error> {
-    let keystore = Keystore::from_json_reader(
+/// Attempts to load a keystore, without decrypting it.
+pub(crate) fn read_keystore(keystore_dir: &PathBuf, filename: &str) -> Result<Keystore, Error> {
+    Keystore::from_json_reader(
         &mut OpenOptions::new()
             .read(true)
             .create(false)
             .open(keystore_dir.clone().join(filename))
             .map_err(Error::UnableToOpenKeystore)?,
     )
-    .map_err(Error::UnableToReadKeystore)?;
+    .map_err(Error::UnableToReadKeystore)
+}
+
+/// Attempts to load and decrypt a keystore.
+fn unlock_keypair<P: AsRef<Path>>(
+    keystore_dir: &PathBuf,
+    filename: &str,
+    password_dir: P,
+) -> Result<Keypair, Error> {
+    let keystore = read_keystore(keystore_dir, filename)?;
 
     let password_path = password_dir
         .as_ref()
@@ -6,8 +6,8 @@ use std::path::Path;
 use tempfile::{tempdir, TempDir};
 use types::{test_utils::generate_deterministic_keypair, EthSpec, Keypair, MainnetEthSpec};
 use validator_dir::{
-    Builder, BuilderError, ValidatorDir, ETH1_DEPOSIT_DATA_FILE, ETH1_DEPOSIT_TX_HASH_FILE,
-    VOTING_KEYSTORE_FILE, WITHDRAWAL_KEYSTORE_FILE,
+    Builder, BuilderError, Manager, ValidatorDir, DISABLED_FILE, ETH1_DEPOSIT_DATA_FILE,
+    ETH1_DEPOSIT_TX_HASH_FILE, VOTING_KEYSTORE_FILE, WITHDRAWAL_KEYSTORE_FILE,
 };
 
 /// A very weak password with which to encrypt the keystores.
@@ -123,6 +123,9 @@ impl Harness {
             assert_eq!(voting_keypair.pk, generate_deterministic_keypair(0).pk)
         }
 
+        // The voting public key should be readable without decrypting the keystore.
+        assert_eq!(validator.voting_public_key().unwrap(), voting_keypair.pk);
+
         // Use OR here instead of AND so we *always* check for the withdrawal keystores if random
         // keystores were generated.
         if config.random_withdrawal_keystore || config.store_withdrawal_keystore {
@@ -203,6 +206,38 @@ fn concurrency() {
     ValidatorDir::open(&path).unwrap();
 }
 
+#[test]
+fn disabled() {
+    let harness = Harness::new();
+
+    let mut val_dir = harness.create_and_test(&BuildConfig::default());
+    let path = val_dir.dir().clone();
+
+    assert!(!val_dir.is_disabled(), "should be enabled after build");
+
+    val_dir.set_disabled(true).unwrap();
+    assert!(val_dir.is_disabled(), "should be disabled");
+    assert!(
+        path.join(DISABLED_FILE).exists(),
+        "should create disabled file"
+    );
+
+    // Disabling twice should be a no-op.
+    val_dir.set_disabled(true).unwrap();
+
+    // Should remain disabled after re-opening.
+    drop(val_dir);
+    let mut val_dir = ValidatorDir::open(&path).unwrap();
+    assert!(val_dir.is_disabled(), "should still be disabled");
+
+    val_dir.set_disabled(false).unwrap();
+    assert!(!val_dir.is_disabled(), "should be enabled");
+    assert!(
+        !path.join(DISABLED_FILE).exists(),
+        "should remove disabled file"
+    );
+}
+
 #[test]
 fn without_voting_keystore() {
     let harness = Harness::new();
@@ -385,3 +420,97 @@ fn store_withdrawal_keystore_with_eth1_data() {
 
     harness.create_and_test(&config);
 }
+
+#[test]
+fn wrong_voting_password() {
+    let harness = Harness::new();
+    let (keystore, _password) = generate_deterministic_keystore(0).unwrap();
+
+    assert!(matches!(
+        Builder::new(
+            harness.validators_dir.path().into(),
+            harness.password_dir.path().into(),
+        )
+        .voting_keystore(keystore, &[42; 42])
+        .store_withdrawal_keystore(false)
+        .build(),
+        Err(BuilderError::KeystoreError(_))
+    ));
+
+    // No files should be created for a keystore that cannot be decrypted.
+    assert_eq!(
+        fs::read_dir(harness.validators_dir.path()).unwrap().count(),
+        0
+    );
+    assert_eq!(
+        fs::read_dir(harness.password_dir.path()).unwrap().count(),
+        0
+    );
+}
+
+#[test]
+fn decrypted_voting_keystore() {
+    let harness = Harness::new();
+    let (keystore, password) = generate_deterministic_keystore(0).unwrap();
+
+    // A keypair that does not belong to the keystore should be rejected.
+    assert!(matches!(
+        Builder::new(
+            harness.validators_dir.path().into(),
+            harness.password_dir.path().into(),
+        )
+        .decrypted_voting_keystore(
+            keystore.clone(),
+            password.as_bytes(),
+            generate_deterministic_keypair(1)
+        )
+        .store_withdrawal_keystore(false)
+        .build(),
+        Err(BuilderError::VotingKeypairMismatch)
+    ));
+    assert_eq!(
+        fs::read_dir(harness.validators_dir.path()).unwrap().count(),
+        0
+    );
+
+    let keypair = generate_deterministic_keypair(0);
+    let validator = Builder::new(
+        harness.validators_dir.path().into(),
+        harness.password_dir.path().into(),
+    )
+    .decrypted_voting_keystore(keystore, password.as_bytes(), keypair.clone())
+    .store_withdrawal_keystore(false)
+    .build()
+    .unwrap();
+
+    let password_dir = harness.password_dir.path().into();
+    let voting_keypair = check_keystore(&validator.dir().join(VOTING_KEYSTORE_FILE), &password_dir);
+    assert_eq!(voting_keypair.pk, keypair.pk);
+}
+
+#[test]
+fn manager_voting_pubkeys() {
+    let harness = Harness::new();
+
+    let manager = Manager::open(harness.validators_dir.path()).unwrap();
+    assert!(manager.voting_pubkeys().unwrap().is_empty());
+
+    let validators = (0..2)
+        .map(|_| harness.create_and_test(&BuildConfig::default()))
+        .collect::<Vec<_>>();
+
+    // A directory without a voting keystore should be ignored.
+    fs::create_dir(harness.validators_dir.path().join("not_a_validator")).unwrap();
+
+    let pubkeys = manager.voting_pubkeys().unwrap();
+    assert_eq!(pubkeys.len(), validators.len());
+
+    for validator in &validators {
+        let pubkey = validator.voting_public_key().unwrap();
+        assert_eq!(
+            pubkeys.get(&pubkey.as_hex_string()),
+            Some(validator.dir()),
+            "should find pubkey whilst validator dir is locked"
+        );
+    }
+}
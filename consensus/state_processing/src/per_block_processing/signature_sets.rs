@@ -108,6 +108,51 @@ where
     ))
 }
 
+/// A signature set that is valid if a block was signed by the validator at its `proposer_index`.
+///
+/// Unlike `block_proposal_signature_set`, the proposer index is not checked against the shuffling,
+/// so no state from the block's epoch is required. Useful for historical blocks whose ancestry
+/// has already been established by some other means.
+pub fn block_proposal_signature_set_from_parts<'a, T, F>(
+    signed_block: &'a SignedBeaconBlock<T>,
+    block_root: Option<Hash256>,
+    get_pubkey: F,
+    fork: &Fork,
+    genesis_validators_root: Hash256,
+    spec: &'a ChainSpec,
+) -> Result<SignatureSet>
+where
+    T: EthSpec,
+    F: Fn(usize) -> Option<Cow<'a, PublicKey>>,
+{
+    let block = &signed_block.message;
+    let proposer_index = block.proposer_index;
+
+    let domain = spec.get_domain(
+        block.slot.epoch(T::slots_per_epoch()),
+        Domain::BeaconProposer,
+        fork,
+        genesis_validators_root,
+    );
+
+    let message = if let Some(root) = block_root {
+        SigningRoot {
+            object_root: root,
+            domain,
+        }
+        .tree_hash_root()
+    } else {
+        block.signing_root(domain)
+    };
+
+    Ok(SignatureSet::single(
+        &signed_block.signature,
+        get_pubkey(proposer_index as usize)
+            .ok_or_else(|| Error::ValidatorUnknown(proposer_index))?,
+        message.as_bytes().to_vec(),
+    ))
+}
+
 /// A signature set that is valid if the block proposers randao reveal signature is correct.
 pub fn randao_signature_set<'a, T, F>(
     state: &'a BeaconState<T>,
@@ -8,8 +8,8 @@ use types::*;
 /// Use to track the changes to a validators balance.
 #[derive(Default, Clone)]
 pub struct Delta {
-    rewards: u64,
-    penalties: u64,
+    pub rewards: u64,
+    pub penalties: u64,
 }
 
 impl Delta {
@@ -32,6 +32,31 @@ impl Delta {
     }
 }
 
+/// The deltas for each component of a validator's attestation rewards and penalties.
+///
+/// Kept separate so that each component can be checked against the EF rewards tests.
+#[derive(Default, Clone)]
+pub struct AttestationDelta {
+    pub source_delta: Delta,
+    pub target_delta: Delta,
+    pub head_delta: Delta,
+    pub inclusion_delay_delta: Delta,
+    pub inactivity_penalty_delta: Delta,
+}
+
+impl AttestationDelta {
+    /// Flatten into a single delta.
+    pub fn flatten(self) -> Result<Delta, Error> {
+        let mut result = Delta::default();
+        result.combine(self.source_delta)?;
+        result.combine(self.target_delta)?;
+        result.combine(self.head_delta)?;
+        result.combine(self.inclusion_delay_delta)?;
+        result.combine(self.inactivity_penalty_delta)?;
+        Ok(result)
+    }
+}
+
 /// Apply attester and proposer rewards.
 ///
 /// Spec v0.11.1
@@ -51,15 +76,12 @@ pub fn process_rewards_and_penalties<T: EthSpec>(
         return Err(Error::ValidatorStatusesInconsistent);
     }
 
-    let mut deltas = vec![Delta::default(); state.balances.len()];
-
-    get_attestation_deltas(&mut deltas, state, &validator_statuses, spec)?;
-
-    get_proposer_deltas(&mut deltas, state, validator_statuses, spec)?;
+    let deltas = get_attestation_deltas(state, &validator_statuses, spec)?;
 
     // Apply the deltas, erroring on overflow above but not on overflow below (saturating at 0
     // instead).
-    for (i, delta) in deltas.iter().enumerate() {
+    for (i, delta) in deltas.into_iter().enumerate() {
+        let delta = delta.flatten()?;
         state.balances[i] = state.balances[i].safe_add(delta.rewards)?;
         state.balances[i] = state.balances[i].saturating_sub(delta.penalties);
     }
@@ -69,11 +91,13 @@ pub fn process_rewards_and_penalties<T: EthSpec>(
 
 /// For each attesting validator, reward the proposer who was first to include their attestation.
 ///
+/// The reward is added to the proposer's inclusion delay delta.
+///
 /// Spec v0.11.1
 fn get_proposer_deltas<T: EthSpec>(
-    deltas: &mut Vec<Delta>,
+    deltas: &mut Vec<AttestationDelta>,
     state: &BeaconState<T>,
-    validator_statuses: &mut ValidatorStatuses,
+    validator_statuses: &ValidatorStatuses,
     spec: &ChainSpec,
 ) -> Result<(), Error> {
     for (index, validator) in validator_statuses.statuses.iter().enumerate() {
@@ -94,6 +118,7 @@ fn get_proposer_deltas<T: EthSpec>(
             }
 
             deltas[inclusion.proposer_index]
+                .inclusion_delay_delta
                 .reward(base_reward.safe_div(spec.proposer_reward_quotient)?)?;
         }
     }
@@ -101,15 +126,19 @@ fn get_proposer_deltas<T: EthSpec>(
     Ok(())
 }
 
-/// Apply rewards for participation in attestations during the previous epoch.
+/// Compute the rewards and penalties for participation in attestations during the previous epoch,
+/// including the rewards of the proposers who included those attestations.
+///
+/// Returns one `AttestationDelta` per validator, in validator index order.
 ///
 /// Spec v0.11.1
-fn get_attestation_deltas<T: EthSpec>(
-    deltas: &mut Vec<Delta>,
+pub fn get_attestation_deltas<T: EthSpec>(
     state: &BeaconState<T>,
     validator_statuses: &ValidatorStatuses,
     spec: &ChainSpec,
-) -> Result<(), Error> {
+) -> Result<Vec<AttestationDelta>, Error> {
+    let mut deltas = vec![AttestationDelta::default(); validator_statuses.statuses.len()];
+
     let finality_delay = (state.previous_epoch() - state.finalized_checkpoint.epoch).as_u64();
 
     for (index, validator) in validator_statuses.statuses.iter().enumerate() {
@@ -128,10 +157,12 @@ fn get_attestation_deltas<T: EthSpec>(
             spec,
         )?;
 
-        deltas[index].combine(delta)?;
+        deltas[index] = delta;
     }
 
-    Ok(())
+    get_proposer_deltas(&mut deltas, state, validator_statuses, spec)?;
+
+    Ok(deltas)
 }
 
 /// Determine the delta for a single validator, sans proposer rewards.
@@ -143,8 +174,8 @@ fn get_attestation_delta<T: EthSpec>(
     base_reward: u64,
     finality_delay: u64,
     spec: &ChainSpec,
-) -> Result<Delta, Error> {
-    let mut delta = Delta::default();
+) -> Result<AttestationDelta, Error> {
+    let mut delta = AttestationDelta::default();
 
     // Is this validator eligible to be rewarded or penalized?
     // Spec: validator index in `eligible_validator_indices`
@@ -177,7 +208,7 @@ fn get_attestation_delta<T: EthSpec>(
     // Spec:
     // - validator index in `get_unslashed_attesting_indices(state, matching_source_attestations)`
     if validator.is_previous_epoch_attester && !validator.is_slashed {
-        delta.reward(
+        delta.source_delta.reward(
             base_reward
                 .safe_mul(total_attesting_balance_ebi)?
                 .safe_div(total_balance_ebi)?,
@@ -188,47 +219,51 @@ fn get_attestation_delta<T: EthSpec>(
         let inclusion = validator
             .inclusion_info
             .expect("It is a logic error for an attester not to have an inclusion delay.");
-        delta.reward(max_attester_reward.safe_div(inclusion.delay)?)?;
+        delta
+            .inclusion_delay_delta
+            .reward(max_attester_reward.safe_div(inclusion.delay)?)?;
     } else {
-        delta.penalize(base_reward)?;
+        delta.source_delta.penalize(base_reward)?;
     }
 
     // Expected FFG target.
     // Spec:
     // - validator index in `get_unslashed_attesting_indices(state, matching_target_attestations)`
     if validator.is_previous_epoch_target_attester && !validator.is_slashed {
-        delta.reward(
+        delta.target_delta.reward(
             base_reward
                 .safe_mul(matching_target_balance_ebi)?
                 .safe_div(total_balance_ebi)?,
         )?;
     } else {
-        delta.penalize(base_reward)?;
+        delta.target_delta.penalize(base_reward)?;
     }
 
     // Expected head.
     // Spec:
     // - validator index in `get_unslashed_attesting_indices(state, matching_head_attestations)`
     if validator.is_previous_epoch_head_attester && !validator.is_slashed {
-        delta.reward(
+        delta.head_delta.reward(
             base_reward
                 .safe_mul(matching_head_balance_ebi)?
                 .safe_div(total_balance_ebi)?,
         )?;
     } else {
-        delta.penalize(base_reward)?;
+        delta.head_delta.penalize(base_reward)?;
     }
 
     // Inactivity penalty
     if finality_delay > spec.min_epochs_to_inactivity_penalty {
         // All eligible validators are penalized
-        delta.penalize(spec.base_rewards_per_epoch.safe_mul(base_reward)?)?;
+        delta
+            .inactivity_penalty_delta
+            .penalize(spec.base_rewards_per_epoch.safe_mul(base_reward)?)?;
 
         // Additionally, all validators whose FFG target didn't match are penalized extra
         // This condition is equivalent to this condition from the spec:
         // `index not in get_unslashed_attesting_indices(state, matching_target_attestations)`
         if validator.is_slashed || !validator.is_previous_epoch_target_attester {
-            delta.penalize(
+            delta.inactivity_penalty_delta.penalize(
                 validator
                     .current_epoch_effective_balance
                     .safe_mul(finality_delay)?
@@ -58,6 +58,29 @@ impl<T: EthSpec> Attestation<T> {
         fork: &Fork,
         genesis_validators_root: Hash256,
         spec: &ChainSpec,
+    ) -> Result<(), Error> {
+        let domain = spec.get_domain(
+            self.data.target.epoch,
+            Domain::BeaconAttester,
+            fork,
+            genesis_validators_root,
+        );
+        let message = self.data.signing_root(domain);
+
+        self.add_signature(
+            &Signature::new(message.as_bytes(), secret_key),
+            committee_position,
+        )
+    }
+
+    /// Adds `signature` to `self` and sets the `committee_position`'th bit of `aggregation_bits`
+    /// to `true`.
+    ///
+    /// The signature must already be over `self.data` (e.g., produced by a remote signer).
+    pub fn add_signature(
+        &mut self,
+        signature: &Signature,
+        committee_position: usize,
     ) -> Result<(), Error> {
         if self
             .aggregation_bits
@@ -70,16 +93,7 @@ impl<T: EthSpec> Attestation<T> {
                 .set(committee_position, true)
                 .map_err(Error::SszTypesError)?;
 
-            let domain = spec.get_domain(
-                self.data.target.epoch,
-                Domain::BeaconAttester,
-                fork,
-                genesis_validators_root,
-            );
-            let message = self.data.signing_root(domain);
-
-            self.signature
-                .add(&Signature::new(message.as_bytes(), secret_key));
+            self.signature.add(signature);
 
             Ok(())
         }
@@ -1,6 +1,6 @@
 use crate::{
     test_utils::TestRandom, BeaconBlock, ChainSpec, Domain, EthSpec, Fork, Hash256, PublicKey,
-    SignedRoot, SigningRoot, Slot,
+    SignedBeaconBlockHeader, SignedRoot, SigningRoot, Slot,
 };
 use bls::Signature;
 use serde_derive::{Deserialize, Serialize};
@@ -96,6 +96,16 @@ impl<E: EthSpec> SignedBeaconBlock<E> {
         self.message.state_root
     }
 
+    /// Returns the block's header, signed with the block's signature.
+    ///
+    /// Note: performs a full tree-hash of `self.message.body`.
+    pub fn signed_block_header(&self) -> SignedBeaconBlockHeader {
+        SignedBeaconBlockHeader {
+            message: self.message.block_header(),
+            signature: self.signature.clone(),
+        }
+    }
+
     /// Returns the `tree_hash_root` of the block.
     ///
     /// Spec v0.11.1
@@ -1,3 +1,5 @@
+mod quoted_int;
 mod serde_utils;
 
+pub use quoted_int::{quoted_u64, quoted_u64_opt, quoted_u64_vec};
 pub use serde_utils::*;
@@ -0,0 +1,177 @@
+//! Serde helpers for integers which are encoded as quoted decimal strings (e.g., `"42"`), as
+//! required by the standard Beacon API and EIP-3076.
+//!
+//! Deserialization accepts both quoted and unquoted integers.
+//!
+//! These are intended for use with `#[serde(with = "...")]` on fields of type `u64` or any type
+//! convertible to and from a `u64` (e.g., `Slot` and `Epoch`).
+
+use serde::de::{Error, Visitor};
+use serde::{Deserializer, Serializer};
+use std::fmt;
+
+struct QuotedU64Visitor;
+
+impl<'de> Visitor<'de> for QuotedU64Visitor {
+    type Value = u64;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "a quoted or unquoted unsigned integer")
+    }
+
+    fn visit_str<E: Error>(self, s: &str) -> Result<Self::Value, E> {
+        s.parse()
+            .map_err(|e| E::custom(format!("invalid integer {:?}: {}", s, e)))
+    }
+
+    fn visit_u64<E: Error>(self, v: u64) -> Result<Self::Value, E> {
+        Ok(v)
+    }
+}
+
+/// Serde helper for a single quoted integer.
+pub mod quoted_u64 {
+    use super::*;
+
+    pub fn serialize<S, T>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        T: Copy + Into<u64>,
+    {
+        let int: u64 = (*value).into();
+        serializer.serialize_str(&int.to_string())
+    }
+
+    pub fn deserialize<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: From<u64>,
+    {
+        deserializer.deserialize_any(QuotedU64Visitor).map(T::from)
+    }
+}
+
+/// Serde helper for an optional quoted integer.
+///
+/// Fields using this helper should also be marked `#[serde(default)]` so that a missing field
+/// deserializes as `None`.
+pub mod quoted_u64_opt {
+    use super::*;
+    use serde_derive::Deserialize;
+
+    #[derive(Deserialize)]
+    struct Wrapper(#[serde(with = "super::quoted_u64")] u64);
+
+    pub fn serialize<S, T>(value: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        T: Copy + Into<u64>,
+    {
+        match value {
+            Some(value) => {
+                let int: u64 = (*value).into();
+                serializer.serialize_some(&int.to_string())
+            }
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: From<u64>,
+    {
+        <Option<Wrapper> as serde::Deserialize>::deserialize(deserializer)
+            .map(|opt| opt.map(|Wrapper(int)| T::from(int)))
+    }
+}
+
+/// Serde helper for a list of quoted integers.
+pub mod quoted_u64_vec {
+    use super::*;
+    use serde::ser::SerializeSeq;
+    use serde_derive::Deserialize;
+
+    #[derive(Deserialize)]
+    struct Wrapper(#[serde(with = "super::quoted_u64")] u64);
+
+    pub fn serialize<S, T>(values: &[T], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        T: Copy + Into<u64>,
+    {
+        let mut seq = serializer.serialize_seq(Some(values.len()))?;
+        for value in values {
+            let int: u64 = (*value).into();
+            seq.serialize_element(&int.to_string())?;
+        }
+        seq.end()
+    }
+
+    pub fn deserialize<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: From<u64>,
+    {
+        <Vec<Wrapper> as serde::Deserialize>::deserialize(deserializer).map(|values| {
+            values
+                .into_iter()
+                .map(|Wrapper(int)| T::from(int))
+                .collect()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Epoch, Slot};
+    use serde_derive::{Deserialize, Serialize};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Quoted {
+        #[serde(with = "super::quoted_u64")]
+        int: u64,
+        #[serde(with = "super::quoted_u64")]
+        slot: Slot,
+        #[serde(default, with = "super::quoted_u64_opt")]
+        epoch: Option<Epoch>,
+        #[serde(with = "super::quoted_u64_vec")]
+        list: Vec<u64>,
+    }
+
+    #[test]
+    fn round_trip() {
+        let value = Quoted {
+            int: u64::max_value(),
+            slot: Slot::new(1),
+            epoch: Some(Epoch::new(2)),
+            list: vec![3, 4],
+        };
+        let json = serde_json::to_string(&value).unwrap();
+        assert_eq!(
+            json,
+            r#"{"int":"18446744073709551615","slot":"1","epoch":"2","list":["3","4"]}"#
+        );
+        assert_eq!(serde_json::from_str::<Quoted>(&json).unwrap(), value);
+    }
+
+    #[test]
+    fn accepts_unquoted_and_missing() {
+        let value: Quoted = serde_json::from_str(r#"{"int":1,"slot":2,"list":[3]}"#).unwrap();
+        assert_eq!(
+            value,
+            Quoted {
+                int: 1,
+                slot: Slot::new(2),
+                epoch: None,
+                list: vec![3],
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_invalid() {
+        assert!(serde_json::from_str::<Quoted>(r#"{"int":"-1","slot":"2","list":[]}"#).is_err());
+        assert!(serde_json::from_str::<Quoted>(r#"{"int":"0x1","slot":"2","list":[]}"#).is_err());
+    }
+}
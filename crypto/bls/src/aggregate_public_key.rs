@@ -1,71 +1,85 @@
-use super::{PublicKey, BLS_PUBLIC_KEY_BYTE_SIZE};
-use milagro_bls::AggregatePublicKey as RawAggregatePublicKey;
+use super::{BlsBackend, GenericPublicKey, BLS_PUBLIC_KEY_BYTE_SIZE};
 use serde::de::{Deserialize, Deserializer};
 use serde::ser::{Serialize, Serializer};
 use serde_hex::{encode as hex_encode, PrefixedHexVisitor};
 use ssz::{Decode, DecodeError, Encode};
+use std::fmt;
 
 /// A BLS aggregate public key.
 ///
-/// This struct is a wrapper upon a base type and provides helper functions (e.g., SSZ
+/// This struct is a wrapper upon a `BlsBackend` type and provides helper functions (e.g., SSZ
 /// serialization).
-#[derive(Debug, Clone, Default)]
-pub struct AggregatePublicKey(RawAggregatePublicKey);
+pub struct GenericAggregatePublicKey<B: BlsBackend> {
+    point: B::AggregatePublicKey,
+}
 
-impl AggregatePublicKey {
+impl<B: BlsBackend> GenericAggregatePublicKey<B> {
     pub fn new() -> Self {
-        AggregatePublicKey(RawAggregatePublicKey::new())
+        Self {
+            point: B::aggregate_public_key_infinity(),
+        }
     }
 
     pub fn from_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
-        let pubkey = RawAggregatePublicKey::from_bytes(&bytes).map_err(|_| {
+        let point = B::aggregate_public_key_from_bytes(bytes).map_err(|_| {
             DecodeError::BytesInvalid(format!("Invalid AggregatePublicKey bytes: {:?}", bytes))
         })?;
 
-        Ok(AggregatePublicKey(pubkey))
-    }
-
-    pub fn add_without_affine(&mut self, public_key: &PublicKey) {
-        self.0.point.add(&public_key.as_raw().point)
+        Ok(Self { point })
     }
 
-    pub fn affine(&mut self) {
-        self.0.point.affine()
-    }
-
-    pub fn add(&mut self, public_key: &PublicKey) {
-        self.0.add(public_key.as_raw())
+    pub fn add(&mut self, public_key: &GenericPublicKey<B>) {
+        B::aggregate_public_key_add(&mut self.point, public_key.as_raw())
     }
 
     /// Returns the underlying public key.
-    pub fn as_raw(&self) -> &RawAggregatePublicKey {
-        &self.0
+    pub fn as_raw(&self) -> &B::AggregatePublicKey {
+        &self.point
     }
 
     /// Returns the underlying point as compressed bytes.
     pub fn as_bytes(&self) -> Vec<u8> {
-        self.as_raw().as_bytes()
+        B::aggregate_public_key_to_bytes(&self.point)
     }
 
-    pub fn into_raw(self) -> RawAggregatePublicKey {
-        self.0
+    pub fn into_raw(self) -> B::AggregatePublicKey {
+        self.point
     }
 
     /// Return a hex string representation of this key's bytes.
-    #[cfg(test)]
     pub fn as_hex_string(&self) -> String {
         serde_hex::encode(self.as_bytes())
     }
 }
 
+impl<B: BlsBackend> Default for GenericAggregatePublicKey<B> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<B: BlsBackend> Clone for GenericAggregatePublicKey<B> {
+    fn clone(&self) -> Self {
+        Self {
+            point: self.point.clone(),
+        }
+    }
+}
+
+impl<B: BlsBackend> fmt::Debug for GenericAggregatePublicKey<B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_hex_string())
+    }
+}
+
 impl_ssz!(
-    AggregatePublicKey,
+    GenericAggregatePublicKey,
     BLS_PUBLIC_KEY_BYTE_SIZE,
     "AggregatePublicKey"
 );
-impl_tree_hash!(AggregatePublicKey, BLS_PUBLIC_KEY_BYTE_SIZE);
+impl_tree_hash!(GenericAggregatePublicKey, BLS_PUBLIC_KEY_BYTE_SIZE);
 
-impl Serialize for AggregatePublicKey {
+impl<B: BlsBackend> Serialize for GenericAggregatePublicKey<B> {
     /// Serde serialization is compliant the Ethereum YAML test format.
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -75,14 +89,14 @@ impl Serialize for AggregatePublicKey {
     }
 }
 
-impl<'de> Deserialize<'de> for AggregatePublicKey {
+impl<'de, B: BlsBackend> Deserialize<'de> for GenericAggregatePublicKey<B> {
     /// Serde serialization is compliant the Ethereum YAML test format.
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
         let bytes = deserializer.deserialize_str(PrefixedHexVisitor)?;
-        let agg_sig = AggregatePublicKey::from_ssz_bytes(&bytes)
+        let agg_sig = Self::from_ssz_bytes(&bytes)
             .map_err(|e| serde::de::Error::custom(format!("invalid ssz ({:?})", e)))?;
 
         Ok(agg_sig)
@@ -90,7 +104,7 @@ impl<'de> Deserialize<'de> for AggregatePublicKey {
 }
 
 #[cfg(feature = "arbitrary")]
-impl arbitrary::Arbitrary for AggregatePublicKey {
+impl<B: BlsBackend> arbitrary::Arbitrary for GenericAggregatePublicKey<B> {
     fn arbitrary(u: &mut arbitrary::Unstructured<'_>) -> arbitrary::Result<Self> {
         let mut bytes = [0u8; BLS_PUBLIC_KEY_BYTE_SIZE];
         u.fill_buffer(&mut bytes)?;
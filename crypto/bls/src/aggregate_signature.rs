@@ -1,132 +1,93 @@
 use super::*;
-use milagro_bls::{AggregateSignature as RawAggregateSignature, G2Point};
 use serde::de::{Deserialize, Deserializer};
 use serde::ser::{Serialize, Serializer};
 use serde_hex::{encode as hex_encode, PrefixedHexVisitor};
 use ssz::{Decode, DecodeError, Encode};
+use std::fmt;
 
 /// A BLS aggregate signature.
 ///
-/// This struct is a wrapper upon a base type and provides helper functions (e.g., SSZ
+/// This struct is a wrapper upon a `BlsBackend` type and provides helper functions (e.g., SSZ
 /// serialization).
-#[derive(Debug, PartialEq, Clone, Default, Eq)]
-pub struct AggregateSignature {
-    aggregate_signature: RawAggregateSignature,
-    is_empty: bool,
+pub struct GenericAggregateSignature<B: BlsBackend> {
+    point: B::AggregateSignature,
 }
 
-impl AggregateSignature {
+impl<B: BlsBackend> GenericAggregateSignature<B> {
     /// Instantiate a new AggregateSignature.
     ///
     /// is_empty is false
     /// AggregateSignature is point at infinity
     pub fn new() -> Self {
         Self {
-            aggregate_signature: RawAggregateSignature::new(),
-            is_empty: false,
+            point: B::aggregate_signature_infinity(),
         }
     }
 
     /// Add (aggregate) a signature to the `AggregateSignature`.
-    pub fn add(&mut self, signature: &Signature) {
-        // Only empty if both are empty
-        self.is_empty = self.is_empty && signature.is_empty();
-
-        // Note: empty signatures will have point at infinity which is equivalent of adding 0.
-        self.aggregate_signature.add(signature.as_raw())
+    pub fn add(&mut self, signature: &GenericSignature<B>) {
+        B::aggregate_signature_add(&mut self.point, signature.as_raw())
     }
 
     /// Add (aggregate) another `AggregateSignature`.
-    pub fn add_aggregate(&mut self, agg_signature: &AggregateSignature) {
-        // Only empty if both are empty
-        self.is_empty = self.is_empty && agg_signature.is_empty();
-
-        // Note: empty signatures will have point at infinity which is equivalent of adding 0.
-        self.aggregate_signature
-            .add_aggregate(&agg_signature.aggregate_signature)
+    pub fn add_aggregate(&mut self, agg_signature: &Self) {
+        B::aggregate_signature_add_aggregate(&mut self.point, &agg_signature.point)
     }
 
     /// Verify the `AggregateSignature` against an `AggregatePublicKey`.
     ///
     /// Only returns `true` if the set of keys in the `AggregatePublicKey` match the set of keys
     /// that signed the `AggregateSignature`.
-    pub fn verify(&self, msg: &[u8], aggregate_public_key: &AggregatePublicKey) -> bool {
-        if self.is_empty {
-            return false;
-        }
-        self.aggregate_signature
-            .fast_aggregate_verify_pre_aggregated(msg, aggregate_public_key.as_raw())
+    pub fn verify(&self, msg: &[u8], aggregate_public_key: &GenericAggregatePublicKey<B>) -> bool {
+        B::aggregate_signature_fast_aggregate_verify_pre_aggregated(
+            &self.point,
+            msg,
+            aggregate_public_key.as_raw(),
+        )
     }
 
     /// Verify the `AggregateSignature` against an `AggregatePublicKey`.
     ///
     /// Only returns `true` if the set of keys in the `AggregatePublicKey` match the set of keys
     /// that signed the `AggregateSignature`.
-    pub fn verify_unaggregated(&self, msg: &[u8], public_keys: &[&PublicKey]) -> bool {
-        if self.is_empty {
-            return false;
-        }
+    pub fn verify_unaggregated(&self, msg: &[u8], public_keys: &[&GenericPublicKey<B>]) -> bool {
         let public_key_refs: Vec<_> = public_keys.iter().map(|pk| pk.as_raw()).collect();
-        self.aggregate_signature
-            .fast_aggregate_verify(msg, &public_key_refs)
+        B::aggregate_signature_fast_aggregate_verify(&self.point, msg, &public_key_refs)
     }
 
     /// Verify this AggregateSignature against multiple AggregatePublickeys and Messages.
     ///
     /// Each AggregatePublicKey has a 1:1 ratio with a 32 byte Message.
-    pub fn verify_multiple(&self, messages: &[&[u8]], public_keys: &[&PublicKey]) -> bool {
-        if self.is_empty {
-            return false;
-        }
+    pub fn verify_multiple(
+        &self,
+        messages: &[&[u8]],
+        public_keys: &[&GenericPublicKey<B>],
+    ) -> bool {
         let public_keys_refs: Vec<_> = public_keys.iter().map(|pk| pk.as_raw()).collect();
-        self.aggregate_signature
-            .aggregate_verify(&messages, &public_keys_refs)
+        B::aggregate_signature_aggregate_verify(&self.point, messages, &public_keys_refs)
     }
 
     /// Return AggregateSignature as bytes
     pub fn as_bytes(&self) -> Vec<u8> {
-        if self.is_empty {
-            return vec![0; BLS_AGG_SIG_BYTE_SIZE];
-        }
-        self.aggregate_signature.as_bytes()
+        B::aggregate_signature_to_bytes(&self.point)
     }
 
     /// Convert bytes to AggregateSignature
     pub fn from_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
-        for byte in bytes {
-            if *byte != 0 {
-                let sig = RawAggregateSignature::from_bytes(&bytes).map_err(|_| {
-                    DecodeError::BytesInvalid(format!(
-                        "Invalid AggregateSignature bytes: {:?}",
-                        bytes
-                    ))
-                })?;
-
-                return Ok(Self {
-                    aggregate_signature: sig,
-                    is_empty: false,
-                });
-            }
-        }
-        Ok(Self::empty_signature())
+        let point = B::aggregate_signature_from_bytes(bytes).map_err(|_| {
+            DecodeError::BytesInvalid(format!("Invalid AggregateSignature bytes: {:?}", bytes))
+        })?;
+        Ok(Self { point })
     }
 
     /// Returns the underlying signature.
-    pub fn as_raw(&self) -> &RawAggregateSignature {
-        &self.aggregate_signature
-    }
-
-    /// Returns the underlying signature.
-    pub fn from_point(point: G2Point) -> Self {
-        Self {
-            aggregate_signature: RawAggregateSignature { point },
-            is_empty: false,
-        }
+    pub fn as_raw(&self) -> &B::AggregateSignature {
+        &self.point
     }
 
     /// Returns if the AggregateSignature `is_empty`
     pub fn is_empty(&self) -> bool {
-        self.is_empty
+        B::aggregate_signature_is_empty(&self.point)
     }
 
     /// Creates a new AggregateSignature
@@ -135,27 +96,53 @@ impl AggregateSignature {
     /// is_empty set to true
     pub fn empty_signature() -> Self {
         Self {
-            aggregate_signature: RawAggregateSignature::new(),
-            is_empty: true,
+            point: B::aggregate_signature_empty(),
         }
     }
 
     /// Return a hex string representation of the bytes of this signature.
-    #[cfg(test)]
     pub fn as_hex_string(&self) -> String {
         hex_encode(self.as_bytes())
     }
 }
 
+impl<B: BlsBackend> Default for GenericAggregateSignature<B> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<B: BlsBackend> Clone for GenericAggregateSignature<B> {
+    fn clone(&self) -> Self {
+        Self {
+            point: self.point.clone(),
+        }
+    }
+}
+
+impl<B: BlsBackend> PartialEq for GenericAggregateSignature<B> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_bytes() == other.as_bytes()
+    }
+}
+
+impl<B: BlsBackend> Eq for GenericAggregateSignature<B> {}
+
+impl<B: BlsBackend> fmt::Debug for GenericAggregateSignature<B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_hex_string())
+    }
+}
+
 impl_ssz!(
-    AggregateSignature,
+    GenericAggregateSignature,
     BLS_AGG_SIG_BYTE_SIZE,
     "AggregateSignature"
 );
 
-impl_tree_hash!(AggregateSignature, BLS_AGG_SIG_BYTE_SIZE);
+impl_tree_hash!(GenericAggregateSignature, BLS_AGG_SIG_BYTE_SIZE);
 
-impl Serialize for AggregateSignature {
+impl<B: BlsBackend> Serialize for GenericAggregateSignature<B> {
     /// Serde serialization is compliant the Ethereum YAML test format.
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -165,14 +152,14 @@ impl Serialize for AggregateSignature {
     }
 }
 
-impl<'de> Deserialize<'de> for AggregateSignature {
+impl<'de, B: BlsBackend> Deserialize<'de> for GenericAggregateSignature<B> {
     /// Serde serialization is compliant the Ethereum YAML test format.
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
         let bytes = deserializer.deserialize_str(PrefixedHexVisitor)?;
-        let agg_sig = AggregateSignature::from_ssz_bytes(&bytes)
+        let agg_sig = Self::from_ssz_bytes(&bytes)
             .map_err(|e| serde::de::Error::custom(format!("invalid ssz ({:?})", e)))?;
 
         Ok(agg_sig)
@@ -180,7 +167,7 @@ impl<'de> Deserialize<'de> for AggregateSignature {
 }
 
 #[cfg(feature = "arbitrary")]
-impl arbitrary::Arbitrary for AggregateSignature {
+impl<B: BlsBackend> arbitrary::Arbitrary for GenericAggregateSignature<B> {
     fn arbitrary(u: &mut arbitrary::Unstructured<'_>) -> arbitrary::Result<Self> {
         let mut bytes = [0u8; BLS_AGG_SIG_BYTE_SIZE];
         u.fill_buffer(&mut bytes)?;
@@ -190,9 +177,8 @@ impl arbitrary::Arbitrary for AggregateSignature {
 
 #[cfg(test)]
 mod tests {
-    use super::super::{Keypair, Signature};
-    use super::*;
-    use ssz::Encode;
+    use crate::{AggregateSignature, Keypair, Signature};
+    use ssz::{Decode, Encode};
 
     #[test]
     pub fn test_ssz_round_trip() {
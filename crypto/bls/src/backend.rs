@@ -0,0 +1,176 @@
+//! Defines the interface that a BLS12-381 library must provide in order to back the types exposed
+//! by this crate (e.g., `PublicKey`, `Signature`, etc).
+//!
+//! The types in this crate (see `GenericPublicKey`, `GenericSignature`, etc.) are generic across
+//! a `BlsBackend` and provide SSZ, tree hash and serde implementations, whilst the backend only
+//! needs to provide the raw cryptographic operations.
+
+/// Errors are returned as a human-readable `String` and converted to `ssz::DecodeError` by the
+/// generic wrapper types.
+pub type Error = String;
+
+/// A BLS12-381 library which can be used to back the types in this crate.
+///
+/// ## Empty signatures
+///
+/// The eth2 specification represents a "missing" signature as 96 zero bytes. These bytes are not
+/// a valid point, so the backend is responsible for tracking whether or not a signature is
+/// "empty". An empty signature must:
+///
+/// - Serialize as all-zeros.
+/// - Be produced when deserializing all-zeros.
+/// - Be treated as the point at infinity when added to an aggregate.
+pub trait BlsBackend: Sized + Send + Sync + 'static {
+    type SecretKey: Clone + Send + Sync;
+    type PublicKey: Clone + Send + Sync;
+    type AggregatePublicKey: Clone + Send + Sync;
+    type Signature: Clone + Send + Sync;
+    type AggregateSignature: Clone + Send + Sync;
+
+    /*
+     * Secret keys
+     */
+
+    /// Generate a new secret key using `rand::thread_rng`.
+    fn secret_key_random() -> Self::SecretKey;
+
+    fn secret_key_from_bytes(bytes: &[u8]) -> Result<Self::SecretKey, Error>;
+
+    fn secret_key_to_bytes(secret_key: &Self::SecretKey) -> Vec<u8>;
+
+    /*
+     * Public keys
+     */
+
+    fn public_key_from_secret_key(secret_key: &Self::SecretKey) -> Self::PublicKey;
+
+    /// Decode a public key from its 48 byte compressed representation.
+    fn public_key_from_bytes(bytes: &[u8]) -> Result<Self::PublicKey, Error>;
+
+    /// Encode a public key to its 48 byte compressed representation.
+    fn public_key_to_bytes(public_key: &Self::PublicKey) -> Vec<u8>;
+
+    /// Decode a public key from its 96 byte uncompressed representation.
+    fn public_key_from_uncompressed_bytes(bytes: &[u8]) -> Result<Self::PublicKey, Error>;
+
+    /// Encode a public key to its 96 byte uncompressed representation.
+    ///
+    /// This should be significantly faster than `Self::public_key_to_bytes`.
+    fn public_key_to_uncompressed_bytes(public_key: &Self::PublicKey) -> Vec<u8>;
+
+    /*
+     * Aggregate public keys
+     */
+
+    /// Returns the point at infinity.
+    fn aggregate_public_key_infinity() -> Self::AggregatePublicKey;
+
+    fn aggregate_public_key_add(
+        aggregate: &mut Self::AggregatePublicKey,
+        public_key: &Self::PublicKey,
+    );
+
+    fn aggregate_public_key_from_bytes(bytes: &[u8]) -> Result<Self::AggregatePublicKey, Error>;
+
+    fn aggregate_public_key_to_bytes(aggregate: &Self::AggregatePublicKey) -> Vec<u8>;
+
+    /// Aggregate all of the `public_keys` into a single key.
+    fn aggregate_public_key_from_public_keys(
+        public_keys: &[&Self::PublicKey],
+    ) -> Self::AggregatePublicKey {
+        let mut aggregate = Self::aggregate_public_key_infinity();
+        for public_key in public_keys {
+            Self::aggregate_public_key_add(&mut aggregate, public_key);
+        }
+        aggregate
+    }
+
+    /*
+     * Signatures
+     */
+
+    fn signature_sign(msg: &[u8], secret_key: &Self::SecretKey) -> Self::Signature;
+
+    /// Returns an "empty" signature (see trait-level docs).
+    fn signature_empty() -> Self::Signature;
+
+    fn signature_is_empty(signature: &Self::Signature) -> bool;
+
+    /// Decode a signature from its 96 byte compressed representation, returning an empty
+    /// signature if all bytes are zero.
+    fn signature_from_bytes(bytes: &[u8]) -> Result<Self::Signature, Error>;
+
+    /// Encode a signature to its 96 byte compressed representation, returning all zeros if the
+    /// signature is empty.
+    fn signature_to_bytes(signature: &Self::Signature) -> Vec<u8>;
+
+    fn signature_verify(
+        signature: &Self::Signature,
+        msg: &[u8],
+        public_key: &Self::PublicKey,
+    ) -> bool;
+
+    /*
+     * Aggregate signatures
+     */
+
+    /// Returns the point at infinity. This is _not_ an empty signature.
+    fn aggregate_signature_infinity() -> Self::AggregateSignature;
+
+    /// Returns an "empty" aggregate signature (see trait-level docs).
+    fn aggregate_signature_empty() -> Self::AggregateSignature;
+
+    fn aggregate_signature_is_empty(aggregate: &Self::AggregateSignature) -> bool;
+
+    fn aggregate_signature_from_signature(signature: &Self::Signature) -> Self::AggregateSignature;
+
+    fn aggregate_signature_add(
+        aggregate: &mut Self::AggregateSignature,
+        signature: &Self::Signature,
+    );
+
+    fn aggregate_signature_add_aggregate(
+        aggregate: &mut Self::AggregateSignature,
+        other: &Self::AggregateSignature,
+    );
+
+    fn aggregate_signature_from_bytes(bytes: &[u8]) -> Result<Self::AggregateSignature, Error>;
+
+    fn aggregate_signature_to_bytes(aggregate: &Self::AggregateSignature) -> Vec<u8>;
+
+    /// Verify that `aggregate` is the signature of all `public_keys` across the same `msg`.
+    fn aggregate_signature_fast_aggregate_verify(
+        aggregate: &Self::AggregateSignature,
+        msg: &[u8],
+        public_keys: &[&Self::PublicKey],
+    ) -> bool;
+
+    /// Verify that `aggregate` is the signature of an already-aggregated public key across `msg`.
+    fn aggregate_signature_fast_aggregate_verify_pre_aggregated(
+        aggregate: &Self::AggregateSignature,
+        msg: &[u8],
+        aggregate_public_key: &Self::AggregatePublicKey,
+    ) -> bool;
+
+    /// Verify that `aggregate` is the signature of each `public_keys[i]` across `msgs[i]`.
+    fn aggregate_signature_aggregate_verify(
+        aggregate: &Self::AggregateSignature,
+        msgs: &[&[u8]],
+        public_keys: &[&Self::PublicKey],
+    ) -> bool;
+
+    /*
+     * Batch verification
+     */
+
+    /// Verify all of the given `(signature, signing_keys, message)` sets at once, returning
+    /// `true` only if _all_ sets are valid.
+    ///
+    /// An empty `sets` is invalid: implementations must return `false`, regardless of how the
+    /// native batch verification treats an empty batch.
+    ///
+    /// Backends should map this onto their native multi-pairing batch verification.
+    fn verify_signature_sets(
+        sets: &[(&Self::AggregateSignature, &Self::AggregatePublicKey, &[u8])],
+    ) -> bool;
+}
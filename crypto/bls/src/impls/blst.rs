@@ -0,0 +1,351 @@
+use crate::backend::{BlsBackend, Error};
+use crate::{
+    BLS_AGG_SIG_BYTE_SIZE, BLS_PUBLIC_KEY_BYTE_SIZE, BLS_SECRET_KEY_BYTE_SIZE, BLS_SIG_BYTE_SIZE,
+};
+use blst::min_pk as blst_core;
+use blst::{blst_scalar, BLST_ERROR};
+use rand::Rng;
+use zeroize::Zeroize;
+
+/// The domain separation tag for the eth2 proof-of-possession ciphersuite.
+pub const DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+/// The number of random bits used to weight each signature during batch verification.
+pub const RAND_BITS: usize = 64;
+
+/// The byte size of an uncompressed public key.
+const BLS_PUBLIC_KEY_UNCOMPRESSED_BYTE_SIZE: usize = 96;
+
+/// A signature which tracks if it is "empty" (i.e., represented as all-zeros).
+#[derive(Clone)]
+pub struct BlstSignature {
+    point: blst_core::Signature,
+    is_empty: bool,
+}
+
+/// An aggregate signature which tracks if it is "empty" (i.e., represented as all-zeros).
+#[derive(Clone)]
+pub struct BlstAggregateSignature {
+    point: blst_core::AggregateSignature,
+    is_empty: bool,
+}
+
+/// The `blst` implementation of `BlsBackend`.
+pub struct Blst;
+
+impl Blst {
+    /// Returns the point at infinity in G2.
+    fn infinity_signature() -> blst_core::Signature {
+        let mut infinity = [0; BLS_SIG_BYTE_SIZE];
+        infinity[0] = 0xc0;
+        blst_core::Signature::from_bytes(&infinity).expect("should decode the point at infinity")
+    }
+}
+
+fn is_success(result: BLST_ERROR) -> bool {
+    result == BLST_ERROR::BLST_SUCCESS
+}
+
+fn check_length(bytes: &[u8], expected: usize) -> Result<(), Error> {
+    if bytes.len() == expected {
+        Ok(())
+    } else {
+        Err(format!(
+            "invalid byte length {}, expected {}",
+            bytes.len(),
+            expected
+        ))
+    }
+}
+
+impl BlsBackend for Blst {
+    type SecretKey = blst_core::SecretKey;
+    type PublicKey = blst_core::PublicKey;
+    type AggregatePublicKey = blst_core::AggregatePublicKey;
+    type Signature = BlstSignature;
+    type AggregateSignature = BlstAggregateSignature;
+
+    fn secret_key_random() -> Self::SecretKey {
+        let mut ikm = [0u8; 32];
+        rand::thread_rng().fill(&mut ikm[..]);
+        let secret_key = blst_core::SecretKey::key_gen(&ikm, &[])
+            .expect("should generate a secret key from 32 bytes of ikm");
+        ikm.zeroize();
+        secret_key
+    }
+
+    fn secret_key_from_bytes(bytes: &[u8]) -> Result<Self::SecretKey, Error> {
+        check_length(bytes, BLS_SECRET_KEY_BYTE_SIZE)?;
+        blst_core::SecretKey::from_bytes(bytes).map_err(|e| format!("{:?}", e))
+    }
+
+    fn secret_key_to_bytes(secret_key: &Self::SecretKey) -> Vec<u8> {
+        secret_key.to_bytes().to_vec()
+    }
+
+    fn public_key_from_secret_key(secret_key: &Self::SecretKey) -> Self::PublicKey {
+        secret_key.sk_to_pk()
+    }
+
+    fn public_key_from_bytes(bytes: &[u8]) -> Result<Self::PublicKey, Error> {
+        check_length(bytes, BLS_PUBLIC_KEY_BYTE_SIZE)?;
+        blst_core::PublicKey::key_validate(bytes).map_err(|e| format!("{:?}", e))
+    }
+
+    fn public_key_to_bytes(public_key: &Self::PublicKey) -> Vec<u8> {
+        public_key.compress().to_vec()
+    }
+
+    fn public_key_from_uncompressed_bytes(bytes: &[u8]) -> Result<Self::PublicKey, Error> {
+        check_length(bytes, BLS_PUBLIC_KEY_UNCOMPRESSED_BYTE_SIZE)?;
+        blst_core::PublicKey::key_validate(bytes).map_err(|e| format!("{:?}", e))
+    }
+
+    fn public_key_to_uncompressed_bytes(public_key: &Self::PublicKey) -> Vec<u8> {
+        public_key.serialize().to_vec()
+    }
+
+    fn aggregate_public_key_infinity() -> Self::AggregatePublicKey {
+        blst_core::AggregatePublicKey::from_public_key(&blst_core::PublicKey::default())
+    }
+
+    fn aggregate_public_key_add(
+        aggregate: &mut Self::AggregatePublicKey,
+        public_key: &Self::PublicKey,
+    ) {
+        // Public keys are validated during decoding, there is no need to re-validate.
+        aggregate
+            .add_public_key(public_key, false)
+            .expect("adding an unvalidated public key cannot fail")
+    }
+
+    fn aggregate_public_key_from_bytes(bytes: &[u8]) -> Result<Self::AggregatePublicKey, Error> {
+        Self::public_key_from_bytes(bytes)
+            .map(|public_key| blst_core::AggregatePublicKey::from_public_key(&public_key))
+    }
+
+    fn aggregate_public_key_to_bytes(aggregate: &Self::AggregatePublicKey) -> Vec<u8> {
+        aggregate.to_public_key().compress().to_vec()
+    }
+
+    fn signature_sign(msg: &[u8], secret_key: &Self::SecretKey) -> Self::Signature {
+        BlstSignature {
+            point: secret_key.sign(msg, DST, &[]),
+            is_empty: false,
+        }
+    }
+
+    fn signature_empty() -> Self::Signature {
+        BlstSignature {
+            point: Self::infinity_signature(),
+            is_empty: true,
+        }
+    }
+
+    fn signature_is_empty(signature: &Self::Signature) -> bool {
+        signature.is_empty
+    }
+
+    fn signature_from_bytes(bytes: &[u8]) -> Result<Self::Signature, Error> {
+        check_length(bytes, BLS_SIG_BYTE_SIZE)?;
+        if bytes.iter().all(|byte| *byte == 0) {
+            Ok(Self::signature_empty())
+        } else {
+            Ok(BlstSignature {
+                point: blst_core::Signature::sig_validate(bytes, false)
+                    .map_err(|e| format!("{:?}", e))?,
+                is_empty: false,
+            })
+        }
+    }
+
+    fn signature_to_bytes(signature: &Self::Signature) -> Vec<u8> {
+        if signature.is_empty {
+            vec![0; BLS_SIG_BYTE_SIZE]
+        } else {
+            signature.point.compress().to_vec()
+        }
+    }
+
+    fn signature_verify(
+        signature: &Self::Signature,
+        msg: &[u8],
+        public_key: &Self::PublicKey,
+    ) -> bool {
+        // Signatures and public keys are group-checked during decoding.
+        !signature.is_empty
+            && is_success(
+                signature
+                    .point
+                    .verify(false, msg, DST, &[], public_key, false),
+            )
+    }
+
+    fn aggregate_signature_infinity() -> Self::AggregateSignature {
+        BlstAggregateSignature {
+            point: blst_core::AggregateSignature::from_signature(&Self::infinity_signature()),
+            is_empty: false,
+        }
+    }
+
+    fn aggregate_signature_empty() -> Self::AggregateSignature {
+        BlstAggregateSignature {
+            point: blst_core::AggregateSignature::from_signature(&Self::infinity_signature()),
+            is_empty: true,
+        }
+    }
+
+    fn aggregate_signature_is_empty(aggregate: &Self::AggregateSignature) -> bool {
+        aggregate.is_empty
+    }
+
+    fn aggregate_signature_from_signature(signature: &Self::Signature) -> Self::AggregateSignature {
+        BlstAggregateSignature {
+            point: blst_core::AggregateSignature::from_signature(&signature.point),
+            is_empty: signature.is_empty,
+        }
+    }
+
+    fn aggregate_signature_add(
+        aggregate: &mut Self::AggregateSignature,
+        signature: &Self::Signature,
+    ) {
+        // Only empty if both are empty.
+        aggregate.is_empty = aggregate.is_empty && signature.is_empty;
+
+        // Note: empty signatures have a point at infinity which is equivalent to adding 0.
+        aggregate
+            .point
+            .add_signature(&signature.point, false)
+            .expect("adding an unvalidated signature cannot fail")
+    }
+
+    fn aggregate_signature_add_aggregate(
+        aggregate: &mut Self::AggregateSignature,
+        other: &Self::AggregateSignature,
+    ) {
+        // Only empty if both are empty.
+        aggregate.is_empty = aggregate.is_empty && other.is_empty;
+
+        // Note: empty signatures have a point at infinity which is equivalent to adding 0.
+        aggregate.point.add_aggregate(&other.point)
+    }
+
+    fn aggregate_signature_from_bytes(bytes: &[u8]) -> Result<Self::AggregateSignature, Error> {
+        check_length(bytes, BLS_AGG_SIG_BYTE_SIZE)?;
+        if bytes.iter().all(|byte| *byte == 0) {
+            Ok(Self::aggregate_signature_empty())
+        } else {
+            let signature =
+                blst_core::Signature::sig_validate(bytes, false).map_err(|e| format!("{:?}", e))?;
+            Ok(BlstAggregateSignature {
+                point: blst_core::AggregateSignature::from_signature(&signature),
+                is_empty: false,
+            })
+        }
+    }
+
+    fn aggregate_signature_to_bytes(aggregate: &Self::AggregateSignature) -> Vec<u8> {
+        if aggregate.is_empty {
+            vec![0; BLS_AGG_SIG_BYTE_SIZE]
+        } else {
+            aggregate.point.to_signature().compress().to_vec()
+        }
+    }
+
+    fn aggregate_signature_fast_aggregate_verify(
+        aggregate: &Self::AggregateSignature,
+        msg: &[u8],
+        public_keys: &[&Self::PublicKey],
+    ) -> bool {
+        !aggregate.is_empty
+            && !public_keys.is_empty()
+            && is_success(aggregate.point.to_signature().fast_aggregate_verify(
+                false,
+                msg,
+                DST,
+                public_keys,
+            ))
+    }
+
+    fn aggregate_signature_fast_aggregate_verify_pre_aggregated(
+        aggregate: &Self::AggregateSignature,
+        msg: &[u8],
+        aggregate_public_key: &Self::AggregatePublicKey,
+    ) -> bool {
+        !aggregate.is_empty
+            && is_success(
+                aggregate
+                    .point
+                    .to_signature()
+                    .fast_aggregate_verify_pre_aggregated(
+                        false,
+                        msg,
+                        DST,
+                        &aggregate_public_key.to_public_key(),
+                    ),
+            )
+    }
+
+    fn aggregate_signature_aggregate_verify(
+        aggregate: &Self::AggregateSignature,
+        msgs: &[&[u8]],
+        public_keys: &[&Self::PublicKey],
+    ) -> bool {
+        !aggregate.is_empty
+            && is_success(aggregate.point.to_signature().aggregate_verify(
+                false,
+                msgs,
+                DST,
+                public_keys,
+                false,
+            ))
+    }
+
+    fn verify_signature_sets(
+        sets: &[(&Self::AggregateSignature, &Self::AggregatePublicKey, &[u8])],
+    ) -> bool {
+        if sets.is_empty() || sets.iter().any(|(signature, _, _)| signature.is_empty) {
+            return false;
+        }
+
+        let rng = &mut rand::thread_rng();
+        let rands: Vec<blst_scalar> = sets
+            .iter()
+            .map(|_| {
+                let mut scalar = blst_scalar::default();
+                // Only the first `RAND_BITS` bits are used, ensure they are non-zero.
+                let mut rand = 0u64;
+                while rand == 0 {
+                    rand = rng.gen();
+                }
+                scalar.b[..8].copy_from_slice(&rand.to_le_bytes());
+                scalar
+            })
+            .collect();
+
+        let msgs: Vec<&[u8]> = sets.iter().map(|(_, _, message)| *message).collect();
+        let signing_keys: Vec<blst_core::PublicKey> = sets
+            .iter()
+            .map(|(_, signing_keys, _)| signing_keys.to_public_key())
+            .collect();
+        let signatures: Vec<blst_core::Signature> = sets
+            .iter()
+            .map(|(signature, _, _)| signature.point.to_signature())
+            .collect();
+
+        let signing_key_refs: Vec<&blst_core::PublicKey> = signing_keys.iter().collect();
+        let signature_refs: Vec<&blst_core::Signature> = signatures.iter().collect();
+
+        is_success(blst_core::Signature::verify_multiple_aggregate_signatures(
+            &msgs,
+            DST,
+            &signing_key_refs,
+            false,
+            &signature_refs,
+            false,
+            &rands,
+            RAND_BITS,
+        ))
+    }
+}
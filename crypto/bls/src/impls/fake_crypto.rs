@@ -0,0 +1,214 @@
+//! A `BlsBackend` which performs no real cryptography, useful for quickly running tests which do
+//! not care about signature validity.
+//!
+//! Public keys are derived from secret keys using `milagro_bls` so that keypairs remain unique,
+//! however signing produces an all-zero signature and _all_ verification functions return `true`.
+
+use crate::backend::{BlsBackend, Error};
+use crate::{BLS_AGG_SIG_BYTE_SIZE, BLS_PUBLIC_KEY_BYTE_SIZE, BLS_SIG_BYTE_SIZE};
+use milagro_bls::{PublicKey as RawPublicKey, SecretKey as RawSecretKey};
+
+/// A public key which is never checked to be a valid point.
+#[derive(Clone)]
+pub struct FakePublicKey {
+    bytes: Vec<u8>,
+}
+
+/// An aggregate public key which is never checked to be a valid point.
+#[derive(Clone)]
+pub struct FakeAggregatePublicKey {
+    bytes: Vec<u8>,
+}
+
+/// A signature which is never checked to be a valid point.
+#[derive(Clone)]
+pub struct FakeSignature {
+    bytes: Vec<u8>,
+}
+
+/// An aggregate signature which is never checked to be a valid point.
+#[derive(Clone)]
+pub struct FakeAggregateSignature {
+    bytes: Vec<u8>,
+}
+
+/// The "fake crypto" implementation of `BlsBackend`.
+pub struct FakeCrypto;
+
+impl BlsBackend for FakeCrypto {
+    type SecretKey = RawSecretKey;
+    type PublicKey = FakePublicKey;
+    type AggregatePublicKey = FakeAggregatePublicKey;
+    type Signature = FakeSignature;
+    type AggregateSignature = FakeAggregateSignature;
+
+    fn secret_key_random() -> Self::SecretKey {
+        RawSecretKey::random(&mut rand::thread_rng())
+    }
+
+    fn secret_key_from_bytes(bytes: &[u8]) -> Result<Self::SecretKey, Error> {
+        RawSecretKey::from_bytes(bytes).map_err(|e| format!("{:?}", e))
+    }
+
+    fn secret_key_to_bytes(secret_key: &Self::SecretKey) -> Vec<u8> {
+        secret_key.as_bytes()
+    }
+
+    fn public_key_from_secret_key(secret_key: &Self::SecretKey) -> Self::PublicKey {
+        FakePublicKey {
+            bytes: RawPublicKey::from_secret_key(secret_key).as_bytes(),
+        }
+    }
+
+    fn public_key_from_bytes(bytes: &[u8]) -> Result<Self::PublicKey, Error> {
+        Ok(FakePublicKey {
+            bytes: bytes.to_vec(),
+        })
+    }
+
+    fn public_key_to_bytes(public_key: &Self::PublicKey) -> Vec<u8> {
+        public_key.bytes.clone()
+    }
+
+    fn public_key_from_uncompressed_bytes(bytes: &[u8]) -> Result<Self::PublicKey, Error> {
+        Self::public_key_from_bytes(bytes)
+    }
+
+    fn public_key_to_uncompressed_bytes(public_key: &Self::PublicKey) -> Vec<u8> {
+        Self::public_key_to_bytes(public_key)
+    }
+
+    fn aggregate_public_key_infinity() -> Self::AggregatePublicKey {
+        FakeAggregatePublicKey {
+            bytes: vec![0; BLS_PUBLIC_KEY_BYTE_SIZE],
+        }
+    }
+
+    fn aggregate_public_key_add(
+        _aggregate: &mut Self::AggregatePublicKey,
+        _public_key: &Self::PublicKey,
+    ) {
+        // Do nothing.
+    }
+
+    fn aggregate_public_key_from_bytes(bytes: &[u8]) -> Result<Self::AggregatePublicKey, Error> {
+        Ok(FakeAggregatePublicKey {
+            bytes: bytes.to_vec(),
+        })
+    }
+
+    fn aggregate_public_key_to_bytes(aggregate: &Self::AggregatePublicKey) -> Vec<u8> {
+        aggregate.bytes.clone()
+    }
+
+    fn signature_sign(_msg: &[u8], _secret_key: &Self::SecretKey) -> Self::Signature {
+        Self::signature_empty()
+    }
+
+    fn signature_empty() -> Self::Signature {
+        FakeSignature {
+            bytes: vec![0; BLS_SIG_BYTE_SIZE],
+        }
+    }
+
+    fn signature_is_empty(signature: &Self::Signature) -> bool {
+        signature.bytes.iter().all(|byte| *byte == 0)
+    }
+
+    fn signature_from_bytes(bytes: &[u8]) -> Result<Self::Signature, Error> {
+        Ok(FakeSignature {
+            bytes: bytes.to_vec(),
+        })
+    }
+
+    fn signature_to_bytes(signature: &Self::Signature) -> Vec<u8> {
+        signature.bytes.clone()
+    }
+
+    /// _Always_ returns `true`.
+    fn signature_verify(
+        _signature: &Self::Signature,
+        _msg: &[u8],
+        _public_key: &Self::PublicKey,
+    ) -> bool {
+        true
+    }
+
+    fn aggregate_signature_infinity() -> Self::AggregateSignature {
+        Self::aggregate_signature_empty()
+    }
+
+    fn aggregate_signature_empty() -> Self::AggregateSignature {
+        FakeAggregateSignature {
+            bytes: vec![0; BLS_AGG_SIG_BYTE_SIZE],
+        }
+    }
+
+    fn aggregate_signature_is_empty(aggregate: &Self::AggregateSignature) -> bool {
+        aggregate.bytes.iter().all(|byte| *byte == 0)
+    }
+
+    fn aggregate_signature_from_signature(signature: &Self::Signature) -> Self::AggregateSignature {
+        FakeAggregateSignature {
+            bytes: signature.bytes.clone(),
+        }
+    }
+
+    fn aggregate_signature_add(
+        _aggregate: &mut Self::AggregateSignature,
+        _signature: &Self::Signature,
+    ) {
+        // Do nothing.
+    }
+
+    fn aggregate_signature_add_aggregate(
+        _aggregate: &mut Self::AggregateSignature,
+        _other: &Self::AggregateSignature,
+    ) {
+        // Do nothing.
+    }
+
+    fn aggregate_signature_from_bytes(bytes: &[u8]) -> Result<Self::AggregateSignature, Error> {
+        Ok(FakeAggregateSignature {
+            bytes: bytes.to_vec(),
+        })
+    }
+
+    fn aggregate_signature_to_bytes(aggregate: &Self::AggregateSignature) -> Vec<u8> {
+        aggregate.bytes.clone()
+    }
+
+    /// _Always_ returns `true`.
+    fn aggregate_signature_fast_aggregate_verify(
+        _aggregate: &Self::AggregateSignature,
+        _msg: &[u8],
+        _public_keys: &[&Self::PublicKey],
+    ) -> bool {
+        true
+    }
+
+    /// _Always_ returns `true`.
+    fn aggregate_signature_fast_aggregate_verify_pre_aggregated(
+        _aggregate: &Self::AggregateSignature,
+        _msg: &[u8],
+        _aggregate_public_key: &Self::AggregatePublicKey,
+    ) -> bool {
+        true
+    }
+
+    /// _Always_ returns `true`.
+    fn aggregate_signature_aggregate_verify(
+        _aggregate: &Self::AggregateSignature,
+        _msgs: &[&[u8]],
+        _public_keys: &[&Self::PublicKey],
+    ) -> bool {
+        true
+    }
+
+    /// Returns `true` for any non-empty `sets`.
+    fn verify_signature_sets(
+        sets: &[(&Self::AggregateSignature, &Self::AggregatePublicKey, &[u8])],
+    ) -> bool {
+        !sets.is_empty()
+    }
+}
@@ -0,0 +1,246 @@
+use crate::backend::{BlsBackend, Error};
+use crate::{BLS_AGG_SIG_BYTE_SIZE, BLS_SIG_BYTE_SIZE};
+use milagro_bls::{
+    AggregatePublicKey as RawAggregatePublicKey, AggregateSignature as RawAggregateSignature,
+    PublicKey as RawPublicKey, SecretKey as RawSecretKey, Signature as RawSignature,
+};
+
+/// A signature which tracks if it is "empty" (i.e., represented as all-zeros).
+#[derive(Clone)]
+pub struct MilagroSignature {
+    point: RawSignature,
+    is_empty: bool,
+}
+
+/// An aggregate signature which tracks if it is "empty" (i.e., represented as all-zeros).
+#[derive(Clone)]
+pub struct MilagroAggregateSignature {
+    point: RawAggregateSignature,
+    is_empty: bool,
+}
+
+/// The `milagro_bls` implementation of `BlsBackend`.
+pub struct Milagro;
+
+impl BlsBackend for Milagro {
+    type SecretKey = RawSecretKey;
+    type PublicKey = RawPublicKey;
+    type AggregatePublicKey = RawAggregatePublicKey;
+    type Signature = MilagroSignature;
+    type AggregateSignature = MilagroAggregateSignature;
+
+    fn secret_key_random() -> Self::SecretKey {
+        RawSecretKey::random(&mut rand::thread_rng())
+    }
+
+    fn secret_key_from_bytes(bytes: &[u8]) -> Result<Self::SecretKey, Error> {
+        RawSecretKey::from_bytes(bytes).map_err(|e| format!("{:?}", e))
+    }
+
+    fn secret_key_to_bytes(secret_key: &Self::SecretKey) -> Vec<u8> {
+        secret_key.as_bytes()
+    }
+
+    fn public_key_from_secret_key(secret_key: &Self::SecretKey) -> Self::PublicKey {
+        RawPublicKey::from_secret_key(secret_key)
+    }
+
+    fn public_key_from_bytes(bytes: &[u8]) -> Result<Self::PublicKey, Error> {
+        RawPublicKey::from_bytes(bytes).map_err(|e| format!("{:?}", e))
+    }
+
+    fn public_key_to_bytes(public_key: &Self::PublicKey) -> Vec<u8> {
+        public_key.as_bytes()
+    }
+
+    fn public_key_from_uncompressed_bytes(bytes: &[u8]) -> Result<Self::PublicKey, Error> {
+        RawPublicKey::from_uncompressed_bytes(bytes).map_err(|e| format!("{:?}", e))
+    }
+
+    fn public_key_to_uncompressed_bytes(public_key: &Self::PublicKey) -> Vec<u8> {
+        RawPublicKey::as_uncompressed_bytes(&mut public_key.clone())
+    }
+
+    fn aggregate_public_key_infinity() -> Self::AggregatePublicKey {
+        RawAggregatePublicKey::new()
+    }
+
+    fn aggregate_public_key_add(
+        aggregate: &mut Self::AggregatePublicKey,
+        public_key: &Self::PublicKey,
+    ) {
+        aggregate.add(public_key)
+    }
+
+    fn aggregate_public_key_from_bytes(bytes: &[u8]) -> Result<Self::AggregatePublicKey, Error> {
+        RawAggregatePublicKey::from_bytes(bytes).map_err(|e| format!("{:?}", e))
+    }
+
+    fn aggregate_public_key_to_bytes(aggregate: &Self::AggregatePublicKey) -> Vec<u8> {
+        aggregate.as_bytes()
+    }
+
+    fn aggregate_public_key_from_public_keys(
+        public_keys: &[&Self::PublicKey],
+    ) -> Self::AggregatePublicKey {
+        RawAggregatePublicKey::aggregate(public_keys)
+    }
+
+    fn signature_sign(msg: &[u8], secret_key: &Self::SecretKey) -> Self::Signature {
+        MilagroSignature {
+            point: RawSignature::new(msg, secret_key),
+            is_empty: false,
+        }
+    }
+
+    fn signature_empty() -> Self::Signature {
+        // Set the underlying point to infinity.
+        let mut infinity = vec![0; BLS_SIG_BYTE_SIZE];
+        infinity[0] += u8::pow(2, 6) + u8::pow(2, 7);
+        MilagroSignature {
+            point: RawSignature::from_bytes(&infinity)
+                .expect("should decode the point at infinity"),
+            is_empty: true,
+        }
+    }
+
+    fn signature_is_empty(signature: &Self::Signature) -> bool {
+        signature.is_empty
+    }
+
+    fn signature_from_bytes(bytes: &[u8]) -> Result<Self::Signature, Error> {
+        if bytes.iter().all(|byte| *byte == 0) {
+            Ok(Self::signature_empty())
+        } else {
+            Ok(MilagroSignature {
+                point: RawSignature::from_bytes(bytes).map_err(|e| format!("{:?}", e))?,
+                is_empty: false,
+            })
+        }
+    }
+
+    fn signature_to_bytes(signature: &Self::Signature) -> Vec<u8> {
+        if signature.is_empty {
+            vec![0; BLS_SIG_BYTE_SIZE]
+        } else {
+            signature.point.as_bytes()
+        }
+    }
+
+    fn signature_verify(
+        signature: &Self::Signature,
+        msg: &[u8],
+        public_key: &Self::PublicKey,
+    ) -> bool {
+        !signature.is_empty && signature.point.verify(msg, public_key)
+    }
+
+    fn aggregate_signature_infinity() -> Self::AggregateSignature {
+        MilagroAggregateSignature {
+            point: RawAggregateSignature::new(),
+            is_empty: false,
+        }
+    }
+
+    fn aggregate_signature_empty() -> Self::AggregateSignature {
+        MilagroAggregateSignature {
+            point: RawAggregateSignature::new(),
+            is_empty: true,
+        }
+    }
+
+    fn aggregate_signature_is_empty(aggregate: &Self::AggregateSignature) -> bool {
+        aggregate.is_empty
+    }
+
+    fn aggregate_signature_from_signature(signature: &Self::Signature) -> Self::AggregateSignature {
+        MilagroAggregateSignature {
+            point: RawAggregateSignature::from_signature(&signature.point),
+            is_empty: signature.is_empty,
+        }
+    }
+
+    fn aggregate_signature_add(
+        aggregate: &mut Self::AggregateSignature,
+        signature: &Self::Signature,
+    ) {
+        // Only empty if both are empty.
+        aggregate.is_empty = aggregate.is_empty && signature.is_empty;
+
+        // Note: empty signatures have a point at infinity which is equivalent to adding 0.
+        aggregate.point.add(&signature.point)
+    }
+
+    fn aggregate_signature_add_aggregate(
+        aggregate: &mut Self::AggregateSignature,
+        other: &Self::AggregateSignature,
+    ) {
+        // Only empty if both are empty.
+        aggregate.is_empty = aggregate.is_empty && other.is_empty;
+
+        // Note: empty signatures have a point at infinity which is equivalent to adding 0.
+        aggregate.point.add_aggregate(&other.point)
+    }
+
+    fn aggregate_signature_from_bytes(bytes: &[u8]) -> Result<Self::AggregateSignature, Error> {
+        if bytes.iter().all(|byte| *byte == 0) {
+            Ok(Self::aggregate_signature_empty())
+        } else {
+            Ok(MilagroAggregateSignature {
+                point: RawAggregateSignature::from_bytes(bytes).map_err(|e| format!("{:?}", e))?,
+                is_empty: false,
+            })
+        }
+    }
+
+    fn aggregate_signature_to_bytes(aggregate: &Self::AggregateSignature) -> Vec<u8> {
+        if aggregate.is_empty {
+            vec![0; BLS_AGG_SIG_BYTE_SIZE]
+        } else {
+            aggregate.point.as_bytes()
+        }
+    }
+
+    fn aggregate_signature_fast_aggregate_verify(
+        aggregate: &Self::AggregateSignature,
+        msg: &[u8],
+        public_keys: &[&Self::PublicKey],
+    ) -> bool {
+        !aggregate.is_empty && aggregate.point.fast_aggregate_verify(msg, public_keys)
+    }
+
+    fn aggregate_signature_fast_aggregate_verify_pre_aggregated(
+        aggregate: &Self::AggregateSignature,
+        msg: &[u8],
+        aggregate_public_key: &Self::AggregatePublicKey,
+    ) -> bool {
+        !aggregate.is_empty
+            && aggregate
+                .point
+                .fast_aggregate_verify_pre_aggregated(msg, aggregate_public_key)
+    }
+
+    fn aggregate_signature_aggregate_verify(
+        aggregate: &Self::AggregateSignature,
+        msgs: &[&[u8]],
+        public_keys: &[&Self::PublicKey],
+    ) -> bool {
+        !aggregate.is_empty && aggregate.point.aggregate_verify(msgs, public_keys)
+    }
+
+    fn verify_signature_sets(
+        sets: &[(&Self::AggregateSignature, &Self::AggregatePublicKey, &[u8])],
+    ) -> bool {
+        if sets.is_empty() || sets.iter().any(|(signature, _, _)| signature.is_empty) {
+            return false;
+        }
+
+        let rng = &mut rand::thread_rng();
+        RawAggregateSignature::verify_multiple_aggregate_signatures(
+            rng,
+            sets.iter().map(|(signature, signing_keys, message)| {
+                (&signature.point, *signing_keys, *message)
+            }),
+        )
+    }
+}
@@ -0,0 +1,6 @@
+//! Implementations of `BlsBackend` for each supported BLS12-381 library.
+
+#[cfg(feature = "supranational")]
+pub mod blst;
+pub mod fake_crypto;
+pub mod milagro;
@@ -1,19 +1,18 @@
-use super::{PublicKey, SecretKey};
+use super::{BlsBackend, GenericPublicKey, GenericSecretKey};
 use std::fmt;
 use std::hash::{Hash, Hasher};
 
-#[derive(Clone)]
-pub struct Keypair {
-    pub sk: SecretKey,
-    pub pk: PublicKey,
+pub struct GenericKeypair<B: BlsBackend> {
+    pub sk: GenericSecretKey<B>,
+    pub pk: GenericPublicKey<B>,
 }
 
-impl Keypair {
+impl<B: BlsBackend> GenericKeypair<B> {
     /// Instantiate a Keypair using SecretKey::random().
     pub fn random() -> Self {
-        let sk = SecretKey::random();
-        let pk = PublicKey::from_secret_key(&sk);
-        Keypair { sk, pk }
+        let sk = GenericSecretKey::random();
+        let pk = GenericPublicKey::from_secret_key(&sk);
+        Self { sk, pk }
     }
 
     pub fn identifier(&self) -> String {
@@ -21,8 +20,17 @@ impl Keypair {
     }
 }
 
+impl<B: BlsBackend> Clone for GenericKeypair<B> {
+    fn clone(&self) -> Self {
+        Self {
+            sk: self.sk.clone(),
+            pk: self.pk.clone(),
+        }
+    }
+}
+
 #[allow(clippy::derive_hash_xor_eq)]
-impl Hash for Keypair {
+impl<B: BlsBackend> Hash for GenericKeypair<B> {
     /// Note: this is distinct from consensus serialization, it will produce a different hash.
     ///
     /// This method uses the uncompressed bytes, which are much faster to obtain than the
@@ -34,7 +42,7 @@ impl Hash for Keypair {
     }
 }
 
-impl fmt::Display for Keypair {
+impl<B: BlsBackend> fmt::Display for GenericKeypair<B> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{}", self.pk)
     }
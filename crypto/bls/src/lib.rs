@@ -1,63 +1,85 @@
+//! Provides BLS12-381 types (e.g., `PublicKey`, `Signature`) which are generic across a
+//! `BlsBackend`.
+//!
+//! The following backends are available:
+//!
+//! - `milagro`: the default, always available.
+//! - `blst`: the `supranational` feature enables the much faster `blst` library.
+//! - `fake_crypto`: performs no real cryptography, all signatures are valid.
+//!
+//! The types at the root of this crate use the backend selected by cargo features, preferring
+//! `fake_crypto`, then `supranational`, then `milagro`. Each backend is also accessible directly
+//! via its own module (e.g., `milagro_implementations`), which is useful for testing.
+
 extern crate milagro_bls;
 extern crate ssz;
 
 #[macro_use]
 mod macros;
+mod aggregate_public_key;
+mod aggregate_signature;
+mod backend;
+pub mod impls;
 mod keypair;
 mod plain_text;
+mod public_key;
 mod public_key_bytes;
 mod secret_key;
+mod signature;
 mod signature_bytes;
 mod signature_set;
 
-pub use crate::keypair::Keypair;
+pub use crate::aggregate_public_key::GenericAggregatePublicKey;
+pub use crate::aggregate_signature::GenericAggregateSignature;
+pub use crate::backend::BlsBackend;
+pub use crate::keypair::GenericKeypair;
+pub use crate::public_key::GenericPublicKey;
 pub use crate::public_key_bytes::PublicKeyBytes;
-pub use crate::secret_key::SecretKey;
+pub use crate::secret_key::GenericSecretKey;
+pub use crate::signature::GenericSignature;
 pub use crate::signature_bytes::SignatureBytes;
-pub use milagro_bls::{compress_g2, hash_to_curve_g2};
+pub use crate::signature_set::GenericSignatureSet;
 pub use plain_text::PlainText;
-pub use signature_set::{verify_signature_sets, SignatureSet};
 
 #[cfg(feature = "arbitrary")]
 pub use arbitrary;
 
-#[cfg(feature = "fake_crypto")]
-mod fake_aggregate_public_key;
-#[cfg(feature = "fake_crypto")]
-mod fake_aggregate_signature;
-#[cfg(feature = "fake_crypto")]
-mod fake_public_key;
-#[cfg(feature = "fake_crypto")]
-mod fake_signature;
+/// Defines a module containing the concrete types for the given `BlsBackend`.
+macro_rules! define_mod {
+    ($name: ident, $backend: ty) => {
+        pub mod $name {
+            use super::*;
 
-#[cfg(not(feature = "fake_crypto"))]
-mod aggregate_public_key;
-#[cfg(not(feature = "fake_crypto"))]
-mod aggregate_signature;
-#[cfg(not(feature = "fake_crypto"))]
-mod public_key;
-#[cfg(not(feature = "fake_crypto"))]
-mod signature;
+            pub type AggregatePublicKey = GenericAggregatePublicKey<$backend>;
+            pub type AggregateSignature = GenericAggregateSignature<$backend>;
+            pub type Keypair = GenericKeypair<$backend>;
+            pub type PublicKey = GenericPublicKey<$backend>;
+            pub type SecretKey = GenericSecretKey<$backend>;
+            pub type Signature = GenericSignature<$backend>;
+            pub type SignatureSet = GenericSignatureSet<$backend>;
 
-#[cfg(feature = "fake_crypto")]
-pub use fakes::*;
-#[cfg(feature = "fake_crypto")]
-mod fakes {
-    pub use crate::fake_aggregate_public_key::FakeAggregatePublicKey as AggregatePublicKey;
-    pub use crate::fake_aggregate_signature::FakeAggregateSignature as AggregateSignature;
-    pub use crate::fake_public_key::FakePublicKey as PublicKey;
-    pub use crate::fake_signature::FakeSignature as Signature;
+            /// Verify all of the `sets`, returning `true` only if _all_ sets are valid.
+            pub fn verify_signature_sets(sets: Vec<SignatureSet>) -> bool {
+                crate::signature_set::verify_signature_sets(sets)
+            }
+        }
+    };
 }
 
-#[cfg(not(feature = "fake_crypto"))]
-pub use reals::*;
-#[cfg(not(feature = "fake_crypto"))]
-mod reals {
-    pub use crate::aggregate_public_key::AggregatePublicKey;
-    pub use crate::aggregate_signature::AggregateSignature;
-    pub use crate::public_key::PublicKey;
-    pub use crate::signature::Signature;
-}
+define_mod!(milagro_implementations, crate::impls::milagro::Milagro);
+#[cfg(feature = "supranational")]
+define_mod!(blst_implementations, crate::impls::blst::Blst);
+define_mod!(
+    fake_crypto_implementations,
+    crate::impls::fake_crypto::FakeCrypto
+);
+
+#[cfg(all(feature = "supranational", not(feature = "fake_crypto")))]
+pub use blst_implementations::*;
+#[cfg(feature = "fake_crypto")]
+pub use fake_crypto_implementations::*;
+#[cfg(all(not(feature = "supranational"), not(feature = "fake_crypto")))]
+pub use milagro_implementations::*;
 
 pub const BLS_AGG_SIG_BYTE_SIZE: usize = 96;
 pub const BLS_SIG_BYTE_SIZE: usize = 96;
@@ -1,6 +1,7 @@
+/// Implements `ssz::Encode` and `ssz::Decode` for a type which is generic across a `BlsBackend`.
 macro_rules! impl_ssz {
     ($type: ident, $byte_size: expr, $item_str: expr) => {
-        impl ssz::Encode for $type {
+        impl<B: BlsBackend> ssz::Encode for $type<B> {
             fn is_ssz_fixed_len() -> bool {
                 true
             }
@@ -18,7 +19,7 @@ macro_rules! impl_ssz {
             }
         }
 
-        impl ssz::Decode for $type {
+        impl<B: BlsBackend> ssz::Decode for $type<B> {
             fn is_ssz_fixed_len() -> bool {
                 true
             }
@@ -34,16 +35,17 @@ macro_rules! impl_ssz {
                 if len != expected {
                     Err(ssz::DecodeError::InvalidByteLength { len, expected })
                 } else {
-                    $type::from_bytes(bytes)
+                    Self::from_bytes(bytes)
                 }
             }
         }
     };
 }
 
+/// Implements `tree_hash::TreeHash` for a type which is generic across a `BlsBackend`.
 macro_rules! impl_tree_hash {
-    ($type: ty, $byte_size: expr) => {
-        impl tree_hash::TreeHash for $type {
+    ($type: ident, $byte_size: expr) => {
+        impl<B: BlsBackend> tree_hash::TreeHash for $type<B> {
             fn tree_hash_type() -> tree_hash::TreeHashType {
                 tree_hash::TreeHashType::Vector
             }
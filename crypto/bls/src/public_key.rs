@@ -1,5 +1,4 @@
-use super::{SecretKey, BLS_PUBLIC_KEY_BYTE_SIZE};
-use milagro_bls::{G1Point, PublicKey as RawPublicKey};
+use super::{BlsBackend, GenericSecretKey, BLS_PUBLIC_KEY_BYTE_SIZE};
 use serde::de::{Deserialize, Deserializer};
 use serde::ser::{Serialize, Serializer};
 use serde_hex::{encode as hex_encode, PrefixedHexVisitor};
@@ -8,62 +7,51 @@ use std::default;
 use std::fmt;
 use std::hash::{Hash, Hasher};
 
-/// A single BLS signature.
+/// A single BLS public key.
 ///
-/// This struct is a wrapper upon a base type and provides helper functions (e.g., SSZ
+/// This struct is a wrapper upon a `BlsBackend` type and provides helper functions (e.g., SSZ
 /// serialization).
-#[derive(Clone, Eq)]
-pub struct PublicKey(RawPublicKey);
-
-impl PublicKey {
-    pub fn from_secret_key(secret_key: &SecretKey) -> Self {
-        PublicKey(RawPublicKey::from_secret_key(secret_key.as_raw()))
-    }
-
-    pub fn from_raw(raw: RawPublicKey) -> Self {
-        Self(raw)
-    }
-
-    /// Returns a reference to the underlying signature.
-    pub fn as_raw(&self) -> &RawPublicKey {
-        &self.0
-    }
+pub struct GenericPublicKey<B: BlsBackend> {
+    point: B::PublicKey,
+}
 
-    /// Consumes self and returns the underlying signature.
-    pub fn as_point(&self) -> &G1Point {
-        &self.0.point
+impl<B: BlsBackend> GenericPublicKey<B> {
+    pub fn from_secret_key(secret_key: &GenericSecretKey<B>) -> Self {
+        Self {
+            point: B::public_key_from_secret_key(secret_key.as_raw()),
+        }
     }
 
-    /// Consumes self and returns the underlying signature.
-    pub fn into_point(self) -> G1Point {
-        self.0.point
+    /// Returns a reference to the underlying public key.
+    pub fn as_raw(&self) -> &B::PublicKey {
+        &self.point
     }
 
     /// Returns the underlying point as compressed bytes.
     pub fn as_bytes(&self) -> Vec<u8> {
-        self.as_raw().as_bytes()
+        B::public_key_to_bytes(&self.point)
     }
 
     /// Converts compressed bytes to PublicKey
     pub fn from_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
-        let pubkey = RawPublicKey::from_bytes(&bytes).map_err(|_| {
+        let point = B::public_key_from_bytes(bytes).map_err(|_| {
             DecodeError::BytesInvalid(format!("Invalid PublicKey bytes: {:?}", bytes))
         })?;
 
-        Ok(PublicKey(pubkey))
+        Ok(Self { point })
     }
 
     /// Returns the PublicKey as (x, y) bytes
     pub fn as_uncompressed_bytes(&self) -> Vec<u8> {
-        RawPublicKey::as_uncompressed_bytes(&mut self.0.clone())
+        B::public_key_to_uncompressed_bytes(&self.point)
     }
 
     /// Converts (x, y) bytes to PublicKey
     pub fn from_uncompressed_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
-        let pubkey = RawPublicKey::from_uncompressed_bytes(&bytes).map_err(|_| {
+        let point = B::public_key_from_uncompressed_bytes(bytes).map_err(|_| {
             DecodeError::BytesInvalid("Invalid PublicKey uncompressed bytes.".to_string())
         })?;
-        Ok(PublicKey(pubkey))
+        Ok(Self { point })
     }
 
     /// Returns the last 6 bytes of the SSZ encoding of the public key, as a hex string.
@@ -81,39 +69,47 @@ impl PublicKey {
     }
 }
 
-impl fmt::Display for PublicKey {
+impl<B: BlsBackend> Clone for GenericPublicKey<B> {
+    fn clone(&self) -> Self {
+        Self {
+            point: self.point.clone(),
+        }
+    }
+}
+
+impl<B: BlsBackend> fmt::Display for GenericPublicKey<B> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{}", self.concatenated_hex_id())
     }
 }
 
-impl fmt::Debug for PublicKey {
+impl<B: BlsBackend> fmt::Debug for GenericPublicKey<B> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{}", self.as_hex_string())
     }
 }
 
-impl default::Default for PublicKey {
+impl<B: BlsBackend> default::Default for GenericPublicKey<B> {
     fn default() -> Self {
-        let secret_key = SecretKey::random();
-        PublicKey::from_secret_key(&secret_key)
+        let secret_key = GenericSecretKey::random();
+        Self::from_secret_key(&secret_key)
     }
 }
 
-impl_ssz!(PublicKey, BLS_PUBLIC_KEY_BYTE_SIZE, "PublicKey");
+impl_ssz!(GenericPublicKey, BLS_PUBLIC_KEY_BYTE_SIZE, "PublicKey");
 
-impl_tree_hash!(PublicKey, BLS_PUBLIC_KEY_BYTE_SIZE);
+impl_tree_hash!(GenericPublicKey, BLS_PUBLIC_KEY_BYTE_SIZE);
 
-impl Serialize for PublicKey {
+impl<B: BlsBackend> Serialize for GenericPublicKey<B> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        serializer.serialize_str(&hex_encode(self.as_raw().as_bytes()))
+        serializer.serialize_str(&hex_encode(self.as_bytes()))
     }
 }
 
-impl<'de> Deserialize<'de> for PublicKey {
+impl<'de, B: BlsBackend> Deserialize<'de> for GenericPublicKey<B> {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: Deserializer<'de>,
@@ -125,13 +121,15 @@ impl<'de> Deserialize<'de> for PublicKey {
     }
 }
 
-impl PartialEq for PublicKey {
-    fn eq(&self, other: &PublicKey) -> bool {
+impl<B: BlsBackend> PartialEq for GenericPublicKey<B> {
+    fn eq(&self, other: &Self) -> bool {
         self.as_ssz_bytes() == other.as_ssz_bytes()
     }
 }
 
-impl Hash for PublicKey {
+impl<B: BlsBackend> Eq for GenericPublicKey<B> {}
+
+impl<B: BlsBackend> Hash for GenericPublicKey<B> {
     /// Note: this is distinct from consensus serialization, it will produce a different hash.
     ///
     /// This method uses the uncompressed bytes, which are much faster to obtain than the
@@ -144,7 +142,7 @@ impl Hash for PublicKey {
 }
 
 #[cfg(feature = "arbitrary")]
-impl arbitrary::Arbitrary for PublicKey {
+impl<B: BlsBackend> arbitrary::Arbitrary for GenericPublicKey<B> {
     fn arbitrary(u: &mut arbitrary::Unstructured<'_>) -> arbitrary::Result<Self> {
         let mut bytes = [0u8; BLS_PUBLIC_KEY_BYTE_SIZE];
         u.fill_buffer(&mut bytes)?;
@@ -154,8 +152,8 @@ impl arbitrary::Arbitrary for PublicKey {
 
 #[cfg(test)]
 mod tests {
-    use super::*;
-    use ssz::ssz_encode;
+    use crate::{PublicKey, SecretKey, BLS_PUBLIC_KEY_BYTE_SIZE};
+    use ssz::{ssz_encode, Decode};
 
     #[test]
     pub fn test_ssz_round_trip() {
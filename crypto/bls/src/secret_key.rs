@@ -1,24 +1,20 @@
-extern crate rand;
-
-use crate::PlainText;
-use milagro_bls::SecretKey as RawSecretKey;
+use crate::{BlsBackend, PlainText};
 use ssz::DecodeError;
 
-/// A single BLS signature.
+/// A single BLS secret key.
 ///
-/// This struct is a wrapper upon a base type and provides helper functions (e.g., SSZ
-/// serialization).
-#[derive(Clone)]
-pub struct SecretKey(RawSecretKey);
+/// This struct is a wrapper upon a `BlsBackend` type and provides helper functions (e.g.,
+/// conversion to bytes).
+pub struct GenericSecretKey<B: BlsBackend> {
+    point: B::SecretKey,
+}
 
-impl SecretKey {
+impl<B: BlsBackend> GenericSecretKey<B> {
     /// Generate a new `Self` using `rand::thread_rng`.
     pub fn random() -> Self {
-        SecretKey(RawSecretKey::random(&mut rand::thread_rng()))
-    }
-
-    pub fn from_raw(raw: RawSecretKey) -> Self {
-        Self(raw)
+        Self {
+            point: B::secret_key_random(),
+        }
     }
 
     /// Returns the secret key as a byte array (wrapped in `PlainText` wrapper so it is zeroized on
@@ -27,30 +23,39 @@ impl SecretKey {
     /// Extreme care should be taken not to leak these bytes as they are the unencrypted secret
     /// key.
     pub fn as_bytes(&self) -> PlainText {
-        self.as_raw().as_bytes().into()
+        B::secret_key_to_bytes(&self.point).into()
     }
 
     /// Instantiate a SecretKey from existing bytes.
     ///
     /// Note: this is _not_ SSZ decoding.
-    pub fn from_bytes(bytes: &[u8]) -> Result<SecretKey, DecodeError> {
-        Ok(SecretKey(RawSecretKey::from_bytes(bytes).map_err(|e| {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        let point = B::secret_key_from_bytes(bytes).map_err(|e| {
             DecodeError::BytesInvalid(format!(
                 "Invalid SecretKey bytes: {:?} Error: {:?}",
                 bytes, e
             ))
-        })?))
+        })?;
+        Ok(Self { point })
     }
 
     /// Returns the underlying secret key.
-    pub(crate) fn as_raw(&self) -> &RawSecretKey {
-        &self.0
+    pub(crate) fn as_raw(&self) -> &B::SecretKey {
+        &self.point
+    }
+}
+
+impl<B: BlsBackend> Clone for GenericSecretKey<B> {
+    fn clone(&self) -> Self {
+        Self {
+            point: self.point.clone(),
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use crate::SecretKey;
 
     #[test]
     pub fn test_ssz_round_trip() {
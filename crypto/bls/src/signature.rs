@@ -1,94 +1,94 @@
-use super::{PublicKey, SecretKey, BLS_SIG_BYTE_SIZE};
-use milagro_bls::Signature as RawSignature;
+use super::{BlsBackend, GenericPublicKey, GenericSecretKey, BLS_SIG_BYTE_SIZE};
 use serde::de::{Deserialize, Deserializer};
 use serde::ser::{Serialize, Serializer};
 use serde_hex::{encode as hex_encode, PrefixedHexVisitor};
 use ssz::{ssz_encode, Decode, DecodeError, Encode};
+use std::fmt;
 
 /// A single BLS signature.
 ///
-/// This struct is a wrapper upon a base type and provides helper functions (e.g., SSZ
+/// This struct is a wrapper upon a `BlsBackend` type and provides helper functions (e.g., SSZ
 /// serialization).
-#[derive(Debug, PartialEq, Clone, Eq)]
-pub struct Signature {
-    signature: RawSignature,
-    is_empty: bool,
+pub struct GenericSignature<B: BlsBackend> {
+    point: B::Signature,
 }
 
-impl Signature {
+impl<B: BlsBackend> GenericSignature<B> {
     /// Instantiate a new Signature from a message and a SecretKey.
-    pub fn new(msg: &[u8], sk: &SecretKey) -> Self {
-        Signature {
-            signature: RawSignature::new(msg, sk.as_raw()),
-            is_empty: false,
+    pub fn new(msg: &[u8], sk: &GenericSecretKey<B>) -> Self {
+        Self {
+            point: B::signature_sign(msg, sk.as_raw()),
         }
     }
 
     /// Verify the Signature against a PublicKey.
-    pub fn verify(&self, msg: &[u8], pk: &PublicKey) -> bool {
-        if self.is_empty {
-            return false;
-        }
-        self.signature.verify(msg, pk.as_raw())
+    pub fn verify(&self, msg: &[u8], pk: &GenericPublicKey<B>) -> bool {
+        B::signature_verify(&self.point, msg, pk.as_raw())
     }
 
     /// Returns the underlying signature.
-    pub fn as_raw(&self) -> &RawSignature {
-        &self.signature
+    pub fn as_raw(&self) -> &B::Signature {
+        &self.point
     }
 
     /// Returns a new empty signature.
     pub fn empty_signature() -> Self {
-        // Set RawSignature = infinity
-        let mut empty: Vec<u8> = vec![0; BLS_SIG_BYTE_SIZE];
-        empty[0] += u8::pow(2, 6) + u8::pow(2, 7);
-        Signature {
-            signature: RawSignature::from_bytes(&empty).unwrap(),
-            is_empty: true,
+        Self {
+            point: B::signature_empty(),
         }
     }
 
     // Converts a BLS Signature to bytes
     pub fn as_bytes(&self) -> Vec<u8> {
-        if self.is_empty {
-            return vec![0; 96];
-        }
-        self.signature.as_bytes()
+        B::signature_to_bytes(&self.point)
     }
 
     // Convert bytes to BLS Signature
     pub fn from_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
-        for byte in bytes {
-            if *byte != 0 {
-                let raw_signature = RawSignature::from_bytes(&bytes).map_err(|_| {
-                    DecodeError::BytesInvalid(format!("Invalid Signature bytes: {:?}", bytes))
-                })?;
-                return Ok(Signature {
-                    signature: raw_signature,
-                    is_empty: false,
-                });
-            }
-        }
-        Ok(Signature::empty_signature())
+        let point = B::signature_from_bytes(bytes).map_err(|_| {
+            DecodeError::BytesInvalid(format!("Invalid Signature bytes: {:?}", bytes))
+        })?;
+        Ok(Self { point })
     }
 
     // Check for empty Signature
     pub fn is_empty(&self) -> bool {
-        self.is_empty
+        B::signature_is_empty(&self.point)
     }
 
     /// Display a signature as a hex string of its bytes.
-    #[cfg(test)]
     pub fn as_hex_string(&self) -> String {
         hex_encode(self.as_bytes())
     }
 }
 
-impl_ssz!(Signature, BLS_SIG_BYTE_SIZE, "Signature");
+impl<B: BlsBackend> Clone for GenericSignature<B> {
+    fn clone(&self) -> Self {
+        Self {
+            point: self.point.clone(),
+        }
+    }
+}
+
+impl<B: BlsBackend> PartialEq for GenericSignature<B> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_bytes() == other.as_bytes()
+    }
+}
+
+impl<B: BlsBackend> Eq for GenericSignature<B> {}
+
+impl<B: BlsBackend> fmt::Debug for GenericSignature<B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_hex_string())
+    }
+}
+
+impl_ssz!(GenericSignature, BLS_SIG_BYTE_SIZE, "Signature");
 
-impl_tree_hash!(Signature, BLS_SIG_BYTE_SIZE);
+impl_tree_hash!(GenericSignature, BLS_SIG_BYTE_SIZE);
 
-impl Serialize for Signature {
+impl<B: BlsBackend> Serialize for GenericSignature<B> {
     /// Serde serialization is compliant the Ethereum YAML test format.
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -98,7 +98,7 @@ impl Serialize for Signature {
     }
 }
 
-impl<'de> Deserialize<'de> for Signature {
+impl<'de, B: BlsBackend> Deserialize<'de> for GenericSignature<B> {
     /// Serde serialization is compliant the Ethereum YAML test format.
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -112,7 +112,7 @@ impl<'de> Deserialize<'de> for Signature {
 }
 
 #[cfg(feature = "arbitrary")]
-impl arbitrary::Arbitrary for Signature {
+impl<B: BlsBackend> arbitrary::Arbitrary for GenericSignature<B> {
     fn arbitrary(u: &mut arbitrary::Unstructured<'_>) -> arbitrary::Result<Self> {
         let mut bytes = [0u8; BLS_SIG_BYTE_SIZE];
         u.fill_buffer(&mut bytes)?;
@@ -122,9 +122,8 @@ impl arbitrary::Arbitrary for Signature {
 
 #[cfg(test)]
 mod tests {
-    use super::super::Keypair;
-    use super::*;
-    use ssz::ssz_encode;
+    use crate::{Keypair, Signature, BLS_SIG_BYTE_SIZE};
+    use ssz::{ssz_encode, Decode};
 
     #[test]
     pub fn test_ssz_round_trip() {
@@ -151,15 +150,12 @@ mod tests {
     pub fn test_empty_signature() {
         let sig = Signature::empty_signature();
 
-        let sig_as_bytes: Vec<u8> = sig.as_raw().as_bytes();
+        let sig_as_bytes = sig.as_bytes();
 
-        assert_eq!(sig_as_bytes.len(), BLS_SIG_BYTE_SIZE);
-        for (i, one_byte) in sig_as_bytes.iter().enumerate() {
-            if i == 0 {
-                assert_eq!(*one_byte, u8::pow(2, 6) + u8::pow(2, 7));
-            } else {
-                assert_eq!(*one_byte, 0);
-            }
-        }
+        assert_eq!(sig_as_bytes, vec![0; BLS_SIG_BYTE_SIZE]);
+        assert!(sig.is_empty());
+
+        let decoded = Signature::from_ssz_bytes(&sig_as_bytes).unwrap();
+        assert!(decoded.is_empty());
     }
 }
@@ -1,75 +1,102 @@
-use crate::{AggregateSignature, PublicKey, Signature};
+use crate::{BlsBackend, GenericAggregateSignature, GenericPublicKey, GenericSignature};
 use std::borrow::Cow;
-
-#[cfg(not(feature = "fake_crypto"))]
-use milagro_bls::{
-    AggregatePublicKey as RawAggregatePublicKey, AggregateSignature as RawAggregateSignature,
-    PublicKey as RawPublicKey,
-};
-
-#[cfg(feature = "fake_crypto")]
-use crate::fakes::{
-    AggregatePublicKey as RawAggregatePublicKey, AggregateSignature as RawAggregateSignature,
-    PublicKey as RawPublicKey,
-};
+use std::fmt;
 
 type Message = Vec<u8>;
 
-#[derive(Clone, Debug)]
-pub struct SignatureSet {
-    pub signature: RawAggregateSignature,
-    signing_keys: RawAggregatePublicKey,
+pub struct GenericSignatureSet<B: BlsBackend> {
+    pub signature: B::AggregateSignature,
+    signing_keys: B::AggregatePublicKey,
     message: Message,
 }
 
-impl SignatureSet {
-    pub fn single(signature: &Signature, signing_key: Cow<PublicKey>, message: Message) -> Self {
+impl<B: BlsBackend> GenericSignatureSet<B> {
+    pub fn single(
+        signature: &GenericSignature<B>,
+        signing_key: Cow<GenericPublicKey<B>>,
+        message: Message,
+    ) -> Self {
         Self {
-            signature: RawAggregateSignature::from_signature(signature.as_raw()),
-            signing_keys: RawAggregatePublicKey::from_public_key(signing_key.as_raw()),
+            signature: B::aggregate_signature_from_signature(signature.as_raw()),
+            signing_keys: B::aggregate_public_key_from_public_keys(&[signing_key.as_raw()]),
             message,
         }
     }
 
     pub fn new(
-        signature: &AggregateSignature,
-        signing_keys: Vec<Cow<PublicKey>>,
+        signature: &GenericAggregateSignature<B>,
+        signing_keys: Vec<Cow<GenericPublicKey<B>>>,
         message: Message,
-    ) -> Self
-where {
-        let signing_keys_refs: Vec<&RawPublicKey> =
+    ) -> Self {
+        let signing_keys_refs: Vec<&B::PublicKey> =
             signing_keys.iter().map(|pk| pk.as_raw()).collect();
         Self {
             signature: signature.as_raw().clone(),
-            signing_keys: RawAggregatePublicKey::aggregate(&signing_keys_refs),
+            signing_keys: B::aggregate_public_key_from_public_keys(&signing_keys_refs),
             message,
         }
     }
 
     pub fn is_valid(&self) -> bool {
-        self.signature
-            .fast_aggregate_verify_pre_aggregated(&self.message, &self.signing_keys)
+        B::aggregate_signature_fast_aggregate_verify_pre_aggregated(
+            &self.signature,
+            &self.message,
+            &self.signing_keys,
+        )
+    }
+}
+
+impl<B: BlsBackend> Clone for GenericSignatureSet<B> {
+    fn clone(&self) -> Self {
+        Self {
+            signature: self.signature.clone(),
+            signing_keys: self.signing_keys.clone(),
+            message: self.message.clone(),
+        }
     }
 }
 
-#[cfg(not(feature = "fake_crypto"))]
-type VerifySet<'a> = (
-    &'a RawAggregateSignature,
-    &'a RawAggregatePublicKey,
-    &'a [u8],
-);
+impl<B: BlsBackend> fmt::Debug for GenericSignatureSet<B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SignatureSet")
+            .field(
+                "signature",
+                &hex::encode(B::aggregate_signature_to_bytes(&self.signature)),
+            )
+            .field(
+                "signing_keys",
+                &hex::encode(B::aggregate_public_key_to_bytes(&self.signing_keys)),
+            )
+            .field("message", &hex::encode(&self.message))
+            .finish()
+    }
+}
 
-#[cfg(not(feature = "fake_crypto"))]
-pub fn verify_signature_sets<'a>(sets: Vec<SignatureSet>) -> bool {
-    let rng = &mut rand::thread_rng();
-    let verify_set: Vec<VerifySet> = sets
+/// Verify all of the `sets` using the backend's batch verification, returning `true` only if
+/// _all_ sets are valid.
+///
+/// Returns `false` if `sets` is empty.
+pub fn verify_signature_sets<B: BlsBackend>(sets: Vec<GenericSignatureSet<B>>) -> bool {
+    let verify_sets: Vec<_> = sets
         .iter()
         .map(|ss| (&ss.signature, &ss.signing_keys, ss.message.as_slice()))
         .collect();
-    RawAggregateSignature::verify_multiple_aggregate_signatures(rng, verify_set.into_iter())
+    B::verify_signature_sets(&verify_sets)
 }
 
-#[cfg(feature = "fake_crypto")]
-pub fn verify_signature_sets<'a>(_: Vec<SignatureSet>) -> bool {
-    true
+#[cfg(test)]
+mod tests {
+    macro_rules! test_empty_sets {
+        ($name: ident, $backend: ident) => {
+            #[test]
+            fn $name() {
+                assert!(!crate::$backend::verify_signature_sets(vec![]));
+            }
+        };
+    }
+
+    test_empty_sets!(milagro_empty_sets, milagro_implementations);
+    #[cfg(feature = "supranational")]
+    test_empty_sets!(blst_empty_sets, blst_implementations);
+    test_empty_sets!(fake_crypto_empty_sets, fake_crypto_implementations);
 }
@@ -4,7 +4,7 @@
 use crate::derived_key::DerivedKey;
 use crate::json_keystore::{
     Aes128Ctr, ChecksumModule, Cipher, CipherModule, Crypto, EmptyMap, EmptyString, JsonKeystore,
-    Kdf, KdfModule, Scrypt, Sha256Checksum, Version,
+    Kdf, KdfModule, Pbkdf2, Prf, Scrypt, Sha256Checksum, Version,
 };
 use crate::PlainText;
 use crate::Uuid;
@@ -44,6 +44,14 @@ pub const DKLEN: u32 = 32;
 pub const IV_SIZE: usize = 16;
 /// The byte size of a SHA256 hash.
 pub const HASH_SIZE: usize = 32;
+/// The default scrypt cost parameter (`N`).
+pub const DEFAULT_SCRYPT_N: u32 = 262_144;
+/// The default scrypt block size (`r`).
+pub const DEFAULT_SCRYPT_R: u32 = 8;
+/// The default scrypt parallelization parameter (`p`).
+pub const DEFAULT_SCRYPT_P: u32 = 1;
+/// The default number of pbkdf2 iterations (`c`).
+pub const DEFAULT_PBKDF2_C: u32 = 262_144;
 
 #[derive(Debug, PartialEq)]
 pub enum Error {
@@ -61,6 +69,78 @@ pub enum Error {
     IncorrectIvSize { expected: usize, len: usize },
 }
 
+/// The parameters of a key derivation function, without the `salt`.
+///
+/// Allows a KDF to be chosen once and then applied to many keystores, each with its own random
+/// salt.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum KdfParams {
+    Scrypt { n: u32, r: u32, p: u32 },
+    Pbkdf2 { c: u32 },
+}
+
+impl KdfParams {
+    /// Returns the default scrypt parameters, as used by `crate::default_kdf`.
+    pub fn scrypt() -> Self {
+        KdfParams::Scrypt {
+            n: DEFAULT_SCRYPT_N,
+            r: DEFAULT_SCRYPT_R,
+            p: DEFAULT_SCRYPT_P,
+        }
+    }
+
+    /// Returns the default pbkdf2 parameters.
+    pub fn pbkdf2() -> Self {
+        KdfParams::Pbkdf2 {
+            c: DEFAULT_PBKDF2_C,
+        }
+    }
+
+    /// Returns a `Kdf` with these parameters and the given `salt`.
+    pub fn to_kdf(&self, salt: Vec<u8>) -> Kdf {
+        match *self {
+            KdfParams::Scrypt { n, r, p } => Kdf::Scrypt(Scrypt {
+                dklen: DKLEN,
+                n,
+                r,
+                p,
+                salt: salt.into(),
+            }),
+            KdfParams::Pbkdf2 { c } => Kdf::Pbkdf2(Pbkdf2 {
+                dklen: DKLEN,
+                c,
+                prf: Prf::default(),
+                salt: salt.into(),
+            }),
+        }
+    }
+
+    /// Returns a `Kdf` with these parameters and a random salt from `rand::thread_rng()`.
+    pub fn to_random_salt_kdf(&self) -> Kdf {
+        self.to_kdf(rand::thread_rng().gen::<[u8; SALT_SIZE]>().to_vec())
+    }
+}
+
+impl Default for KdfParams {
+    fn default() -> Self {
+        Self::scrypt()
+    }
+}
+
+/// Returns the parameters of an existing `Kdf`, discarding its salt.
+impl From<&Kdf> for KdfParams {
+    fn from(kdf: &Kdf) -> Self {
+        match kdf {
+            Kdf::Scrypt(scrypt) => KdfParams::Scrypt {
+                n: scrypt.n,
+                r: scrypt.r,
+                p: scrypt.p,
+            },
+            Kdf::Pbkdf2(pbkdf2) => KdfParams::Pbkdf2 { c: pbkdf2.c },
+        }
+    }
+}
+
 /// Constructs a `Keystore`.
 pub struct KeystoreBuilder<'a> {
     keypair: &'a Keypair,
@@ -103,6 +183,17 @@ impl<'a> KeystoreBuilder<'a> {
         self
     }
 
+    /// Build the keystore using a KDF with the supplied `params` and a random salt.
+    pub fn kdf_params(self, params: &KdfParams) -> Self {
+        self.kdf(params.to_random_salt_kdf())
+    }
+
+    /// Build the keystore using the supplied `uuid` instead of a random one.
+    pub fn uuid(mut self, uuid: Uuid) -> Self {
+        self.uuid = uuid;
+        self
+    }
+
     /// Consumes `self`, returning a `Keystore`.
     pub fn build(self) -> Result<Keystore, Error> {
         Keystore::encrypt(
@@ -197,6 +288,29 @@ impl Keystore {
         Ok(keypair)
     }
 
+    /// Decrypts `self` with `password` and returns a new keystore for the same keypair, encrypted
+    /// with `new_password` via a KDF with the given `kdf_params`.
+    ///
+    /// The UUID and path of `self` are preserved, whilst the salt and IV are regenerated.
+    ///
+    /// ## Errors
+    ///
+    /// - If `password` is unable to decrypt `self`.
+    /// - If `new_password.is_empty()`.
+    pub fn reencrypt(
+        &self,
+        password: &[u8],
+        new_password: &[u8],
+        kdf_params: &KdfParams,
+    ) -> Result<Self, Error> {
+        let keypair = self.decrypt_keypair(password)?;
+
+        KeystoreBuilder::new(&keypair, new_password, self.json.path.clone())?
+            .kdf_params(kdf_params)
+            .uuid(self.json.uuid)
+            .build()
+    }
+
     /// Returns the UUID for the keystore.
     pub fn uuid(&self) -> &Uuid {
         &self.json.uuid
@@ -256,13 +370,7 @@ pub fn keypair_from_secret(secret: &[u8]) -> Result<Keypair, Error> {
 ///
 /// Currently this is set to scrypt due to its memory hardness properties.
 pub fn default_kdf(salt: Vec<u8>) -> Kdf {
-    Kdf::Scrypt(Scrypt {
-        dklen: DKLEN,
-        n: 262144,
-        p: 1,
-        r: 8,
-        salt: salt.into(),
-    })
+    KdfParams::default().to_kdf(salt)
 }
 
 /// Returns `(cipher_text, checksum)` for the given `plain_text` encrypted with `Cipher` using a
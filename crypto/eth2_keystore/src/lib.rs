@@ -8,7 +8,8 @@ pub mod json_keystore;
 
 pub use bls::PlainText;
 pub use keystore::{
-    decrypt, default_kdf, encrypt, keypair_from_secret, Error, Keystore, KeystoreBuilder, DKLEN,
+    decrypt, default_kdf, encrypt, keypair_from_secret, Error, KdfParams, Keystore,
+    KeystoreBuilder, DEFAULT_PBKDF2_C, DEFAULT_SCRYPT_N, DEFAULT_SCRYPT_P, DEFAULT_SCRYPT_R, DKLEN,
     HASH_SIZE, IV_SIZE, SALT_SIZE,
 };
 pub use uuid::Uuid;
@@ -5,7 +5,7 @@ use bls::Keypair;
 use eth2_keystore::{
     default_kdf,
     json_keystore::{Kdf, Pbkdf2, Prf, Scrypt},
-    Error, Keystore, KeystoreBuilder, DKLEN,
+    Error, KdfParams, Keystore, KeystoreBuilder, DKLEN,
 };
 use std::fs::OpenOptions;
 use tempfile::tempdir;
@@ -160,3 +160,89 @@ fn custom_pbkdf2_kdf() {
 
     assert_eq!(keystore.kdf(), &my_kdf);
 }
+
+#[test]
+fn kdf_params() {
+    let keypair = Keypair::random();
+
+    for params in &[
+        KdfParams::Scrypt { n: 2, r: 8, p: 1 },
+        KdfParams::Pbkdf2 { c: 2 },
+    ] {
+        let keystore = KeystoreBuilder::new(&keypair, GOOD_PASSWORD, "".into())
+            .unwrap()
+            .kdf_params(params)
+            .build()
+            .unwrap();
+
+        match (params, keystore.kdf()) {
+            (KdfParams::Scrypt { n, r, p }, Kdf::Scrypt(scrypt)) => {
+                assert_eq!((scrypt.n, scrypt.r, scrypt.p), (*n, *r, *p))
+            }
+            (KdfParams::Pbkdf2 { c }, Kdf::Pbkdf2(pbkdf2)) => assert_eq!(pbkdf2.c, *c),
+            (params, kdf) => panic!("{:?} produced {:?}", params, kdf),
+        }
+
+        assert_eq!(
+            keystore.decrypt_keypair(GOOD_PASSWORD).unwrap().pk,
+            keypair.pk
+        );
+    }
+}
+
+#[test]
+fn kdf_params_use_fresh_salts() {
+    let params = KdfParams::Pbkdf2 { c: 2 };
+
+    assert!(params.to_random_salt_kdf() != params.to_random_salt_kdf());
+    assert_eq!(params.to_kdf(vec![42]), params.to_kdf(vec![42]));
+}
+
+#[test]
+fn reencrypt() {
+    let keypair = Keypair::random();
+
+    let keystore = KeystoreBuilder::new(&keypair, GOOD_PASSWORD, "m/12381/3600/0/0/0".into())
+        .unwrap()
+        .kdf_params(&KdfParams::Scrypt { n: 2, r: 8, p: 1 })
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        keystore
+            .reencrypt(BAD_PASSWORD, BAD_PASSWORD, &KdfParams::Pbkdf2 { c: 2 })
+            .err()
+            .unwrap(),
+        Error::InvalidPassword,
+        "should not reencrypt with bad password"
+    );
+
+    assert_eq!(
+        keystore
+            .reencrypt(GOOD_PASSWORD, &[], &KdfParams::Pbkdf2 { c: 2 })
+            .err()
+            .unwrap(),
+        Error::EmptyPassword,
+        "should not reencrypt with empty password"
+    );
+
+    let reencrypted = keystore
+        .reencrypt(GOOD_PASSWORD, BAD_PASSWORD, &KdfParams::Pbkdf2 { c: 2 })
+        .unwrap();
+
+    assert_eq!(reencrypted.uuid(), keystore.uuid());
+    assert_eq!(reencrypted.path(), keystore.path());
+    assert_eq!(reencrypted.pubkey(), keystore.pubkey());
+    assert!(matches!(reencrypted.kdf(), Kdf::Pbkdf2(_)));
+
+    assert_eq!(
+        reencrypted.decrypt_keypair(GOOD_PASSWORD).err().unwrap(),
+        Error::InvalidPassword,
+        "should not decrypt with old password"
+    );
+    assert_eq!(
+        reencrypted.decrypt_keypair(BAD_PASSWORD).unwrap().pk,
+        keypair.pk,
+        "should decrypt with new password"
+    );
+}
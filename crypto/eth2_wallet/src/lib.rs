@@ -4,8 +4,9 @@ mod wallet;
 pub mod json_wallet;
 
 pub use bip39;
+pub use eth2_keystore::KdfParams;
 pub use validator_path::{KeyType, ValidatorPath, COIN_TYPE, PURPOSE};
 pub use wallet::{
-    recover_validator_secret, DerivedKey, Error, KeystoreError, PlainText, Uuid,
-    ValidatorKeystores, Wallet, WalletBuilder,
+    recover_validator_secret, recover_validator_secret_from_seed, DerivedKey, Error, KeystoreError,
+    PlainText, Uuid, ValidatorKeystores, Wallet, WalletBuilder,
 };
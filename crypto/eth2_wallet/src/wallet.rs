@@ -6,8 +6,8 @@ use crate::{
     KeyType, ValidatorPath,
 };
 use eth2_keystore::{
-    decrypt, default_kdf, encrypt, keypair_from_secret, Keystore, KeystoreBuilder, IV_SIZE,
-    SALT_SIZE,
+    decrypt, default_kdf, encrypt, keypair_from_secret, KdfParams, Keystore, KeystoreBuilder,
+    IV_SIZE, SALT_SIZE,
 };
 use rand::prelude::*;
 use serde::{Deserialize, Serialize};
@@ -98,6 +98,13 @@ impl<'a> WalletBuilder<'a> {
         }
     }
 
+    /// Encrypt the wallet using a KDF with the supplied `params` (and a random salt) instead of
+    /// `eth2_keystore::default_kdf`.
+    pub fn kdf_params(mut self, params: &KdfParams) -> Self {
+        self.kdf = params.to_random_salt_kdf();
+        self
+    }
+
     /// Consumes `self`, returning an encrypted `Wallet`.
     pub fn build(self) -> Result<Wallet, Error> {
         Wallet::encrypt(
@@ -178,6 +185,23 @@ impl Wallet {
         wallet_password: &[u8],
         voting_keystore_password: &[u8],
         withdrawal_keystore_password: &[u8],
+    ) -> Result<ValidatorKeystores, Error> {
+        self.next_validator_with_kdf(
+            wallet_password,
+            voting_keystore_password,
+            withdrawal_keystore_password,
+            &KdfParams::default(),
+        )
+    }
+
+    /// As per `Self::next_validator`, but encrypts both keystores using a KDF with the given
+    /// `kdf_params`.
+    pub fn next_validator_with_kdf(
+        &mut self,
+        wallet_password: &[u8],
+        voting_keystore_password: &[u8],
+        withdrawal_keystore_password: &[u8],
+        kdf_params: &KdfParams,
     ) -> Result<ValidatorKeystores, Error> {
         // Helper closure to reduce code duplication when generating keys.
         //
@@ -190,6 +214,7 @@ impl Wallet {
             let keypair = keypair_from_secret(secret.as_bytes())?;
 
             KeystoreBuilder::new(&keypair, password, format!("{}", path))?
+                .kdf_params(kdf_params)
                 .build()
                 .map_err(Into::into)
         };
@@ -277,9 +302,23 @@ pub fn recover_validator_secret(
     index: u32,
     key_type: KeyType,
 ) -> Result<(PlainText, ValidatorPath), Error> {
-    let path = ValidatorPath::new(index, key_type);
     let secret = wallet.decrypt_seed(wallet_password)?;
-    let master = DerivedKey::from_seed(secret.as_bytes()).map_err(|()| Error::EmptyPassword)?;
+
+    recover_validator_secret_from_seed(secret.as_bytes(), index, key_type)
+}
+
+/// Returns `(secret, path)` for the `key_type` for the validator at `index`, derived directly from
+/// the master `seed` (e.g., the seed of a BIP-39 mnemonic).
+///
+/// The same caveats as `recover_validator_secret` apply: this function should only be used for
+/// recovering lost keys, not creating new ones.
+pub fn recover_validator_secret_from_seed(
+    seed: &[u8],
+    index: u32,
+    key_type: KeyType,
+) -> Result<(PlainText, ValidatorPath), Error> {
+    let path = ValidatorPath::new(index, key_type);
+    let master = DerivedKey::from_seed(seed).map_err(|()| Error::EmptySeed)?;
 
     let destination = path.iter_nodes().fold(master, |dk, i| dk.child(*i));
 
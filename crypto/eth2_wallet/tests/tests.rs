@@ -1,8 +1,10 @@
 #![cfg(not(debug_assertions))]
 
+use eth2_keystore::json_keystore::Kdf;
 use eth2_wallet::{
     bip39::{Language, Mnemonic, Seed},
-    recover_validator_secret, DerivedKey, Error, KeyType, KeystoreError, Wallet, WalletBuilder,
+    recover_validator_secret, recover_validator_secret_from_seed, DerivedKey, Error, KdfParams,
+    KeyType, KeystoreError, Wallet, WalletBuilder,
 };
 use std::fs::OpenOptions;
 use tempfile::tempdir;
@@ -279,3 +281,82 @@ fn key_derivation_from_seed() {
         assert_eq!(wallet.nextaccount(), i + 1, "updated nextaccount");
     }
 }
+
+#[test]
+fn key_recovery_from_seed() {
+    for i in 0..4 {
+        let (voting_secret, voting_path) =
+            recover_validator_secret_from_seed(SEED, i, KeyType::Voting)
+                .expect("should recover voting secret");
+
+        assert_eq!(
+            format!("{}", voting_path),
+            format!("m/12381/3600/{}/0/0", i),
+            "voting path should match"
+        );
+        assert_eq!(
+            voting_secret.as_bytes(),
+            &manually_derived_voting_key(i)[..],
+            "voting secret should match manually derived"
+        );
+
+        let (withdrawal_secret, withdrawal_path) =
+            recover_validator_secret_from_seed(SEED, i, KeyType::Withdrawal)
+                .expect("should recover withdrawal secret");
+
+        assert_eq!(
+            format!("{}", withdrawal_path),
+            format!("m/12381/3600/{}/0", i),
+            "withdrawal path should match"
+        );
+        assert_eq!(
+            withdrawal_secret.as_bytes(),
+            &manually_derived_withdrawal_key(i)[..],
+            "withdrawal secret should match manually derived"
+        );
+    }
+}
+
+#[test]
+fn empty_seed_recovery() {
+    assert_eq!(
+        recover_validator_secret_from_seed(&[], 0, KeyType::Voting).err(),
+        Some(Error::EmptySeed),
+        "should fail with empty seed"
+    );
+}
+
+#[test]
+fn next_validator_with_kdf() {
+    let mut wallet = wallet_from_seed();
+
+    let keystores = wallet
+        .next_validator_with_kdf(
+            WALLET_PASSWORD,
+            VOTING_KEYSTORE_PASSWORD,
+            WITHDRAWAL_KEYSTORE_PASSWORD,
+            &KdfParams::Pbkdf2 { c: 2 },
+        )
+        .expect("should generate keystores");
+
+    assert_eq!(wallet.nextaccount(), 1, "should increment nextaccount");
+
+    for keystore in &[&keystores.voting, &keystores.withdrawal] {
+        match keystore.kdf() {
+            Kdf::Pbkdf2(params) => assert_eq!(params.c, 2, "should use the given cost"),
+            other => panic!("should use pbkdf2, not {:?}", other),
+        }
+    }
+
+    assert_eq!(
+        keystores
+            .voting
+            .decrypt_keypair(VOTING_KEYSTORE_PASSWORD)
+            .expect("should decrypt voting keystore")
+            .sk
+            .as_bytes()
+            .as_ref(),
+        &recovered_voting_key(&wallet, 0)[..],
+        "voting secret should match recovered"
+    );
+}
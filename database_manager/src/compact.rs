@@ -0,0 +1,18 @@
+use clap::App;
+use store::HotColdDB;
+use types::EthSpec;
+
+pub const CMD: &str = "compact";
+
+pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
+    App::new(CMD).about(
+        "Compacts the hot and freezer databases, reclaiming the space used by deleted data. \
+         This may take a long time for large databases.",
+    )
+}
+
+pub fn cli_run<T: EthSpec>(db: &HotColdDB<T>) {
+    eprintln!("Compacting database, this may take some time");
+    db.compact();
+    eprintln!("Compaction complete");
+}
@@ -0,0 +1,61 @@
+use crate::{column_arg, for_each_entry, freezer_arg, limit_arg, selected_column};
+use clap::{App, ArgMatches};
+use ssz::Decode;
+use std::convert::TryInto;
+use store::{
+    BeaconStateStorageContainer, DBColumn, HotColdDB, HotStateSummary, PartialBeaconState,
+    StoreItem,
+};
+use types::{BeaconState, EthSpec, SignedBeaconBlock};
+
+pub const CMD: &str = "dump";
+
+pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
+    App::new(CMD)
+        .about(
+            "Prints the keys and values of a column, in key order. Values of blocks, states and \
+             state summaries are SSZ-decoded, all other values are printed as hex.",
+        )
+        .arg(column_arg())
+        .arg(freezer_arg())
+        .arg(limit_arg())
+}
+
+pub fn cli_run<T: EthSpec>(matches: &ArgMatches, db: &HotColdDB<T>) -> Result<(), String> {
+    let (column, kv) = selected_column(matches, db)?;
+    let freezer = matches.is_present(crate::FREEZER_FLAG);
+
+    for_each_entry(matches, kv, column, |key, value| {
+        let value = decode_value::<T>(column, freezer, value)
+            .unwrap_or_else(|e| format!("<{}> 0x{}", e, hex::encode(value)));
+        println!("0x{}: {}", hex::encode(key), value);
+    })
+}
+
+/// Decode a value of `column`, which is stored differently in the hot and freezer databases.
+fn decode_value<T: EthSpec>(
+    column: DBColumn,
+    freezer: bool,
+    bytes: &[u8],
+) -> Result<String, String> {
+    match (column, freezer) {
+        (DBColumn::BeaconBlock, _) => SignedBeaconBlock::<T>::from_ssz_bytes(bytes)
+            .map(|block| format!("{:?}", block))
+            .map_err(|e| format!("invalid block: {:?}", e)),
+        (DBColumn::BeaconState, false) => BeaconStateStorageContainer::<T>::from_ssz_bytes(bytes)
+            .map_err(|e| format!("{:?}", e))
+            .and_then(|container| {
+                let state: Result<BeaconState<T>, _> = container.try_into();
+                state.map_err(|e| format!("{:?}", e))
+            })
+            .map(|state| format!("{:?}", state))
+            .map_err(|e| format!("invalid state: {}", e)),
+        (DBColumn::BeaconState, true) => PartialBeaconState::<T>::from_ssz_bytes(bytes)
+            .map(|state| format!("{:?}", state))
+            .map_err(|e| format!("invalid restore point: {:?}", e)),
+        (DBColumn::BeaconStateSummary, false) => HotStateSummary::from_store_bytes(bytes)
+            .map(|summary| format!("{:?}", summary))
+            .map_err(|e| format!("invalid state summary: {:?}", e)),
+        _ => Ok(format!("0x{}", hex::encode(bytes))),
+    }
+}
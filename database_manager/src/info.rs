@@ -0,0 +1,39 @@
+use clap::App;
+use store::{HotColdDB, Store};
+use types::EthSpec;
+
+pub const CMD: &str = "info";
+
+pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
+    App::new(CMD).about(
+        "Displays the schema version of the database, the split between the hot and freezer \
+         databases, and the restore points held by the freezer.",
+    )
+}
+
+pub fn cli_run<T: EthSpec>(db: &HotColdDB<T>) -> Result<(), String> {
+    let schema_version = db
+        .load_schema_version()
+        .map_err(|e| format!("Unable to read schema version: {:?}", e))?;
+    let split = db.get_split_info();
+
+    println!("Schema version: {}", schema_version.as_u64());
+    println!("Split slot: {}", split.slot);
+    println!("Split state root: {:?}", split.state_root);
+
+    if let Some(anchor) = db.get_anchor_info() {
+        println!("Anchor slot: {}", anchor.anchor_slot);
+        println!("Oldest block slot: {}", anchor.oldest_block_slot);
+    }
+
+    let restore_points = db
+        .get_restore_points()
+        .map_err(|e| format!("Unable to read restore points: {:?}", e))?;
+
+    println!("Restore points: {}", restore_points.len());
+    for (slot, state_root) in restore_points {
+        println!("  slot {}: {:?}", slot, state_root);
+    }
+
+    Ok(())
+}
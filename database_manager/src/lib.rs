@@ -0,0 +1,221 @@
+pub mod compact;
+pub mod dump;
+pub mod info;
+pub mod list;
+pub mod prune_states;
+pub mod size;
+pub mod verify;
+
+use beacon_node::{get_data_dir, ClientConfig};
+use clap::{App, Arg, ArgMatches};
+use environment::Environment;
+use slog::Logger;
+use std::path::PathBuf;
+use store::{
+    config::DEFAULT_SLOTS_PER_RESTORE_POINT, DBColumn, HotColdDB, LevelDB, CURRENT_SCHEMA_VERSION,
+};
+use types::{ChainSpec, EthSpec};
+
+pub const CMD: &str = "database_manager";
+pub const COLUMN_FLAG: &str = "column";
+pub const FREEZER_FLAG: &str = "freezer";
+pub const LIMIT_FLAG: &str = "limit";
+pub const SLOTS_PER_RESTORE_POINT_FLAG: &str = "slots-per-restore-point";
+
+pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
+    App::new(CMD)
+        .visible_aliases(&["db", CMD])
+        .about(
+            "Utilities for inspecting and maintaining the beacon node database. The beacon node \
+             must be stopped before running these commands.",
+        )
+        .arg(
+            Arg::with_name("freezer-dir")
+                .long("freezer-dir")
+                .value_name("DIR")
+                .help("Data directory for the freezer database, if not within the datadir.")
+                .takes_value(true)
+                .global(true),
+        )
+        .arg(
+            Arg::with_name(SLOTS_PER_RESTORE_POINT_FLAG)
+                .long(SLOTS_PER_RESTORE_POINT_FLAG)
+                .value_name("SLOT_COUNT")
+                .help(
+                    "The --slots-per-restore-point the beacon node was run with. \
+                     [default: 2048 (mainnet) or 64 (minimal)]",
+                )
+                .takes_value(true)
+                .global(true),
+        )
+        .subcommand(size::cli_app())
+        .subcommand(list::cli_app())
+        .subcommand(dump::cli_app())
+        .subcommand(info::cli_app())
+        .subcommand(verify::cli_app())
+        .subcommand(compact::cli_app())
+        .subcommand(prune_states::cli_app())
+}
+
+/// Run the database manager, returning an error if the operation did not succeed.
+pub fn run<T: EthSpec>(matches: &ArgMatches<'_>, mut env: Environment<T>) -> Result<(), String> {
+    let spec = env.eth2_config.spec.clone();
+    let log = env.core_context().log().clone();
+    let db = open_db(matches, spec, log)?;
+
+    match matches.subcommand() {
+        (size::CMD, Some(_)) => size::cli_run(&db)?,
+        (list::CMD, Some(matches)) => list::cli_run(matches, &db)?,
+        (dump::CMD, Some(matches)) => dump::cli_run(matches, &db)?,
+        (info::CMD, Some(_)) => info::cli_run(&db)?,
+        (verify::CMD, Some(_)) => verify::cli_run(&db)?,
+        (compact::CMD, Some(_)) => {
+            check_schema_version(&db)?;
+            compact::cli_run(&db)
+        }
+        (prune_states::CMD, Some(matches)) => {
+            check_schema_version(&db)?;
+            prune_states::cli_run(matches, &db)?
+        }
+        (unknown, _) => {
+            return Err(format!(
+                "{} is not a valid {} command. See --help.",
+                unknown, CMD
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Open the hot and cold databases of a stopped beacon node.
+fn open_db<T: EthSpec>(
+    matches: &ArgMatches<'_>,
+    spec: ChainSpec,
+    log: Logger,
+) -> Result<HotColdDB<T>, String> {
+    let mut client_config = ClientConfig::default();
+    // The global `--datadir` is joined with the beacon node directory, as for the beacon node.
+    client_config.data_dir = get_data_dir(matches);
+    client_config.freezer_db_path = matches.value_of("freezer-dir").map(PathBuf::from);
+    client_config.store.slots_per_restore_point =
+        clap_utils::parse_optional(matches, SLOTS_PER_RESTORE_POINT_FLAG)?.unwrap_or_else(|| {
+            std::cmp::min(
+                T::slots_per_historical_root() as u64,
+                DEFAULT_SLOTS_PER_RESTORE_POINT,
+            )
+        });
+
+    let hot_path = client_config
+        .get_db_path()
+        .ok_or_else(|| "Unable to locate the database".to_string())?;
+    let cold_path = client_config
+        .get_freezer_db_path()
+        .ok_or_else(|| "Unable to locate the freezer database".to_string())?;
+
+    // Opening a database creates it if it does not exist, which is never what the user wants here.
+    for path in &[&hot_path, &cold_path] {
+        if !path.exists() {
+            return Err(format!("No database found at {:?}", path));
+        }
+    }
+
+    HotColdDB::open(&hot_path, &cold_path, client_config.store, spec, log).map_err(|e| {
+        format!(
+            "Unable to open database, ensure the beacon node is not running: {:?}",
+            e
+        )
+    })
+}
+
+/// Returns an error unless the database has the schema version written by this version of
+/// Lighthouse, which must be the case before the database is modified.
+fn check_schema_version<T: EthSpec>(db: &HotColdDB<T>) -> Result<(), String> {
+    let on_disk = db
+        .load_schema_version()
+        .map_err(|e| format!("Unable to read schema version: {:?}", e))?;
+
+    if on_disk > CURRENT_SCHEMA_VERSION {
+        Err(format!(
+            "Database schema version {} is newer than the latest version supported by this \
+             version of Lighthouse ({})",
+            on_disk.as_u64(),
+            CURRENT_SCHEMA_VERSION.as_u64()
+        ))
+    } else if on_disk < CURRENT_SCHEMA_VERSION {
+        Err(format!(
+            "Database schema version {} must be migrated to version {}, start the beacon node \
+             once to migrate it",
+            on_disk.as_u64(),
+            CURRENT_SCHEMA_VERSION.as_u64()
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Returns the `--column` argument shared by commands which read a single column.
+fn column_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name(COLUMN_FLAG)
+        .long(COLUMN_FLAG)
+        .value_name("COLUMN")
+        .help("The key of the column to read, e.g. \"blk\" for blocks.")
+        .takes_value(true)
+        .required(true)
+}
+
+/// Returns the `--freezer` flag shared by commands which read a single column.
+fn freezer_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name(FREEZER_FLAG)
+        .long(FREEZER_FLAG)
+        .help("Read the column from the freezer database rather than the hot database.")
+        .takes_value(false)
+}
+
+/// Returns the `--limit` argument shared by commands which read a single column.
+fn limit_arg<'a, 'b>() -> Arg<'a, 'b> {
+    Arg::with_name(LIMIT_FLAG)
+        .long(LIMIT_FLAG)
+        .value_name("N")
+        .help("The maximum number of entries to read.")
+        .takes_value(true)
+}
+
+/// Returns the column and sub-database selected by the `--column` and `--freezer` arguments.
+fn selected_column<'a, T: EthSpec>(
+    matches: &ArgMatches<'_>,
+    db: &'a HotColdDB<T>,
+) -> Result<(DBColumn, &'a LevelDB<T>), String> {
+    let column = clap_utils::parse_required(matches, COLUMN_FLAG)?;
+
+    if matches.is_present(FREEZER_FLAG) {
+        Ok((column, &db.cold_db))
+    } else {
+        Ok((column, &db.hot_db))
+    }
+}
+
+/// Calls `f` for each entry in `column` of `kv`, stopping after `--limit` entries.
+fn for_each_entry<T, F>(
+    matches: &ArgMatches<'_>,
+    kv: &LevelDB<T>,
+    column: DBColumn,
+    mut f: F,
+) -> Result<(), String>
+where
+    T: EthSpec,
+    F: FnMut(&[u8], &[u8]),
+{
+    let limit: Option<usize> = clap_utils::parse_optional(matches, LIMIT_FLAG)?;
+    let mut count = 0;
+
+    kv.for_each_in_column(column, |key, value| {
+        if limit.map_or(false, |limit| count >= limit) {
+            return Ok(false);
+        }
+        f(key, value);
+        count += 1;
+        Ok(true)
+    })
+    .map_err(|e| format!("Unable to read database: {:?}", e))
+}
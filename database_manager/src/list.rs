@@ -0,0 +1,22 @@
+use crate::{column_arg, for_each_entry, freezer_arg, limit_arg, selected_column};
+use clap::{App, ArgMatches};
+use store::HotColdDB;
+use types::EthSpec;
+
+pub const CMD: &str = "list";
+
+pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
+    App::new(CMD)
+        .about("Lists the keys of a column, in key order, along with the size of each value.")
+        .arg(column_arg())
+        .arg(freezer_arg())
+        .arg(limit_arg())
+}
+
+pub fn cli_run<T: EthSpec>(matches: &ArgMatches, db: &HotColdDB<T>) -> Result<(), String> {
+    let (column, kv) = selected_column(matches, db)?;
+
+    for_each_entry(matches, kv, column, |key, value| {
+        println!("0x{} {} bytes", hex::encode(key), value.len());
+    })
+}
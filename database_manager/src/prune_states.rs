@@ -0,0 +1,45 @@
+use clap::{App, Arg, ArgMatches};
+use store::HotColdDB;
+use types::EthSpec;
+
+pub const CMD: &str = "prune-states";
+pub const KEEP_FLAG: &str = "keep-restore-points";
+
+pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
+    App::new(CMD)
+        .about(
+            "Prunes historic states from the freezer database, converting an archive database \
+             into one which only stores blocks, block roots and the most recent restore points. \
+             This cannot be undone.",
+        )
+        .arg(
+            Arg::with_name(KEEP_FLAG)
+                .long(KEEP_FLAG)
+                .value_name("COUNT")
+                .help("The number of most recent restore points to keep.")
+                .default_value("0")
+                .takes_value(true),
+        )
+}
+
+pub fn cli_run<T: EthSpec>(matches: &ArgMatches, db: &HotColdDB<T>) -> Result<(), String> {
+    let keep: u64 = clap_utils::parse_required(matches, KEEP_FLAG)?;
+
+    eprintln!("Pruning historic states, keeping {} restore points", keep);
+    db.prune_restore_points(keep)
+        .map_err(|e| format!("Unable to prune states: {:?}", e))?;
+
+    eprintln!("Compacting database, this may take some time");
+    db.compact();
+
+    if keep == 0 {
+        eprintln!("Pruning complete, start the beacon node with --no-history to stay pruned");
+    } else {
+        eprintln!(
+            "Pruning complete, start the beacon node with --max-restore-points {} to stay pruned",
+            keep
+        );
+    }
+
+    Ok(())
+}
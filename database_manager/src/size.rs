@@ -0,0 +1,54 @@
+use clap::App;
+use store::{DBColumn, HotColdDB, LevelDB};
+use types::EthSpec;
+
+pub const CMD: &str = "size";
+
+pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
+    App::new(CMD).about(
+        "Reports the number of entries and bytes used by each column of the hot and freezer \
+         databases. Sizes are of the uncompressed keys and values.",
+    )
+}
+
+pub fn cli_run<T: EthSpec>(db: &HotColdDB<T>) -> Result<(), String> {
+    println!(
+        "{:<8}{:<28}{:>12}{:>16}{:>12}{:>16}",
+        "Column", "Name", "Hot entries", "Hot bytes", "Cold entries", "Cold bytes"
+    );
+
+    for column in DBColumn::all() {
+        let key: &str = (*column).into();
+        let (hot_entries, hot_bytes) = column_size(&db.hot_db, *column)?;
+        let (cold_entries, cold_bytes) = column_size(&db.cold_db, *column)?;
+
+        if hot_entries + cold_entries > 0 {
+            println!(
+                "{:<8}{:<28}{:>12}{:>16}{:>12}{:>16}",
+                key,
+                format!("{:?}", column),
+                hot_entries,
+                hot_bytes,
+                cold_entries,
+                cold_bytes
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the number of entries in `column` and their total size in bytes.
+fn column_size<T: EthSpec>(kv: &LevelDB<T>, column: DBColumn) -> Result<(u64, u64), String> {
+    let mut entries = 0;
+    let mut bytes = 0;
+
+    kv.for_each_in_column(column, |key, value| {
+        entries += 1;
+        bytes += (key.len() + value.len()) as u64;
+        Ok(true)
+    })
+    .map_err(|e| format!("Unable to read {:?} column: {:?}", column, e))?;
+
+    Ok((entries, bytes))
+}
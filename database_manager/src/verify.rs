@@ -0,0 +1,35 @@
+use clap::App;
+use store::HotColdDB;
+use types::EthSpec;
+
+pub const CMD: &str = "verify";
+
+pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
+    App::new(CMD).about(
+        "Checks that every state summary in the hot database refers to an epoch boundary state \
+         which is present in the hot database.",
+    )
+}
+
+pub fn cli_run<T: EthSpec>(db: &HotColdDB<T>) -> Result<(), String> {
+    let unreachable = db
+        .find_unreachable_hot_states()
+        .map_err(|e| format!("Unable to verify database: {:?}", e))?;
+
+    if unreachable.is_empty() {
+        println!("Database is consistent");
+        return Ok(());
+    }
+
+    for state in &unreachable {
+        println!(
+            "State {:?} at slot {} is missing its epoch boundary state {:?}",
+            state.state_root, state.slot, state.epoch_boundary_state_root
+        );
+    }
+
+    Err(format!(
+        "Found {} hot states which cannot be loaded",
+        unreachable.len()
+    ))
+}
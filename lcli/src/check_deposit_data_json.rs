@@ -0,0 +1,135 @@
+use clap::ArgMatches;
+use clap_utils::parse_required;
+use deposit_contract::{decode_eth1_tx_data, encode_eth1_tx_data, StandardDepositDataJson};
+use environment::Environment;
+use eth2_testnet_config::Eth2TestnetConfig;
+use log::{error, info};
+use state_processing::per_block_processing::verify_deposit_signature;
+use std::collections::HashSet;
+use std::fs::File;
+use std::path::PathBuf;
+use tree_hash::TreeHash;
+use types::{ChainSpec, EthSpec};
+
+pub fn run<T: EthSpec>(mut env: Environment<T>, matches: &ArgMatches) -> Result<(), String> {
+    let path: PathBuf = parse_required(matches, "deposit-data-json")?;
+    let spec = chain_spec(&mut env, matches)?;
+
+    let deposits: Vec<StandardDepositDataJson> = File::open(&path)
+        .map_err(|e| format!("Unable to open {:?}: {:?}", path, e))
+        .and_then(|file| {
+            serde_json::from_reader(file)
+                .map_err(|e| format!("Unable to parse {:?}: {:?}", path, e))
+        })?;
+
+    let mut seen_pubkeys = HashSet::new();
+    let mut invalid_count = 0;
+
+    for (i, deposit) in deposits.iter().enumerate() {
+        let pubkey = format!("0x{}", hex::encode(deposit.pubkey.as_slice()));
+
+        let result = if !seen_pubkeys.insert(deposit.pubkey.clone()) {
+            Err("Duplicate pubkey in file".to_string())
+        } else {
+            check_deposit(deposit, &spec)
+        };
+
+        match result {
+            Ok(()) => info!("Deposit {} is valid: {}", i, pubkey),
+            Err(e) => {
+                invalid_count += 1;
+                error!("Deposit {} is invalid: {}: {}", i, pubkey, e);
+            }
+        }
+    }
+
+    if invalid_count == 0 {
+        info!("All {} deposits are valid", deposits.len());
+        Ok(())
+    } else {
+        Err(format!(
+            "{} of {} deposits are invalid",
+            invalid_count,
+            deposits.len()
+        ))
+    }
+}
+
+/// Returns the spec for the `--spec` flag, with the config from the `--testnet-dir` applied (if
+/// supplied).
+fn chain_spec<T: EthSpec>(
+    env: &mut Environment<T>,
+    matches: &ArgMatches,
+) -> Result<ChainSpec, String> {
+    let eth2_config = env.core_context().eth2_config;
+    let spec = eth2_config.spec;
+
+    match matches.value_of("testnet-dir") {
+        Some(dir) => Eth2TestnetConfig::<T>::load(PathBuf::from(dir))?
+            .yaml_config
+            .as_ref()
+            .ok_or_else(|| "The testnet directory must contain a spec config".to_string())?
+            .apply_to_chain_spec::<T>(&spec)
+            .ok_or_else(|| {
+                format!(
+                    "The loaded config is not compatible with the {} spec",
+                    eth2_config.spec_constants
+                )
+            }),
+        None => Ok(spec),
+    }
+}
+
+/// Checks that `deposit` is consistent with its roots, was created for `spec` and is signed by
+/// its pubkey.
+fn check_deposit(deposit: &StandardDepositDataJson, spec: &ChainSpec) -> Result<(), String> {
+    let deposit_data = deposit.deposit_data();
+
+    if deposit.fork_version != spec.genesis_fork_version {
+        return Err(format!(
+            "Fork version is 0x{}, expected 0x{}",
+            hex::encode(deposit.fork_version),
+            hex::encode(spec.genesis_fork_version)
+        ));
+    }
+
+    let deposit_message_root = deposit_data.as_deposit_message().tree_hash_root();
+    if deposit.deposit_message_root != deposit_message_root {
+        return Err(format!(
+            "Deposit message root is {:?}, expected {:?}",
+            deposit.deposit_message_root, deposit_message_root
+        ));
+    }
+
+    let deposit_data_root = deposit_data.tree_hash_root();
+    if deposit.deposit_data_root != deposit_data_root {
+        return Err(format!(
+            "Deposit data root is {:?}, expected {:?}",
+            deposit.deposit_data_root, deposit_data_root
+        ));
+    }
+
+    if deposit_data.amount < spec.min_deposit_amount {
+        return Err(format!(
+            "Amount {} is less than the minimum deposit amount {}",
+            deposit_data.amount, spec.min_deposit_amount
+        ));
+    }
+
+    verify_deposit_signature(&deposit_data, spec)
+        .map_err(|e| format!("Invalid signature: {:?}", e))?;
+
+    // Ensure that the deposit contract transaction for this deposit commits to the same root.
+    let tx_data = encode_eth1_tx_data(&deposit_data)
+        .map_err(|e| format!("Unable to encode deposit transaction: {:?}", e))?;
+    let (_, tx_root) = decode_eth1_tx_data(&tx_data, deposit_data.amount)
+        .map_err(|e| format!("Unable to decode deposit transaction: {:?}", e))?;
+    if tx_root != deposit_data_root {
+        return Err(format!(
+            "Deposit transaction root is {:?}, expected {:?}",
+            tx_root, deposit_data_root
+        ));
+    }
+
+    Ok(())
+}
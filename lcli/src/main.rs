@@ -3,6 +3,7 @@ extern crate log;
 
 mod change_genesis_time;
 mod check_deposit_data;
+mod check_deposit_data_json;
 mod deploy_deposit_contract;
 mod eth1_genesis;
 mod generate_bootnode_enr;
@@ -403,6 +404,22 @@ fn main() {
                             function signature."),
                 )
         )
+        .subcommand(
+            SubCommand::with_name("check-deposit-data-json")
+                .about(
+                    "Verifies a deposit_data-*.json file (as produced by the eth2.0-deposit-cli or
+                    `lighthouse account validator export-deposit-data`) against the --spec and
+                    --testnet-dir. Checks the fork version, roots and signature of each deposit.",
+                )
+                .arg(
+                    Arg::with_name("deposit-data-json")
+                        .index(1)
+                        .value_name("PATH")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Path to the JSON file of deposits."),
+                )
+        )
         .subcommand(
             SubCommand::with_name("generate-bootnode-enr")
                 .about(
@@ -570,6 +587,8 @@ fn run<T: EthSpec>(
             .map_err(|e| format!("Failed to run new_testnet command: {}", e)),
         ("check-deposit-data", Some(matches)) => check_deposit_data::run::<T>(matches)
             .map_err(|e| format!("Failed to run check-deposit-data command: {}", e)),
+        ("check-deposit-data-json", Some(matches)) => check_deposit_data_json::run::<T>(env, matches)
+            .map_err(|e| format!("Failed to run check-deposit-data-json command: {}", e)),
         ("generate-bootnode-enr", Some(matches)) => generate_bootnode_enr::run::<T>(matches)
             .map_err(|e| format!("Failed to run generate-bootnode-enr command: {}", e)),
         ("insecure-validators", Some(matches)) => insecure_validators::run(matches)
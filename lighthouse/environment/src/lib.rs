@@ -9,7 +9,11 @@
 
 use eth2_config::Eth2Config;
 use eth2_testnet_config::Eth2TestnetConfig;
-use futures::channel::oneshot;
+use futures::channel::{
+    mpsc::{channel, Receiver, Sender},
+    oneshot,
+};
+use futures::{future, StreamExt};
 
 pub use executor::TaskExecutor;
 use slog::{info, o, Drain, Level, Logger};
@@ -26,6 +30,23 @@ mod metrics;
 
 pub const ETH2_CONFIG_FILENAME: &str = "eth2-spec.toml";
 
+/// The reason for shutting down the process.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ShutdownReason {
+    /// The process should exit successfully (e.g., Ctrl+C was pressed).
+    Success(&'static str),
+    /// The process should exit with an error, because a service cannot safely continue.
+    Failure(&'static str),
+}
+
+impl ShutdownReason {
+    pub fn message(&self) -> &'static str {
+        match *self {
+            ShutdownReason::Success(msg) | ShutdownReason::Failure(msg) => msg,
+        }
+    }
+}
+
 /// Builds an `Environment`.
 pub struct EnvironmentBuilder<E: EthSpec> {
     runtime: Option<Runtime>,
@@ -177,12 +198,15 @@ impl<E: EthSpec> EnvironmentBuilder<E> {
     /// Consumes the builder, returning an `Environment`.
     pub fn build(self) -> Result<Environment<E>, String> {
         let (signal, exit) = exit_future::signal();
+        let (signal_tx, signal_rx) = channel(1);
         Ok(Environment {
             runtime: self
                 .runtime
                 .ok_or_else(|| "Cannot build environment without runtime".to_string())?,
             signal: Some(signal),
             exit,
+            signal_rx: Some(signal_rx),
+            signal_tx,
             log: self
                 .log
                 .ok_or_else(|| "Cannot build environment without log".to_string())?,
@@ -213,6 +237,7 @@ impl<E: EthSpec> RuntimeContext<E> {
             executor: TaskExecutor {
                 handle: self.executor.handle.clone(),
                 exit: self.executor.exit.clone(),
+                signal_tx: self.executor.signal_tx.clone(),
                 log: self.executor.log.new(o!("service" => service_name)),
             },
             eth_spec_instance: self.eth_spec_instance.clone(),
@@ -237,6 +262,9 @@ pub struct Environment<E: EthSpec> {
     runtime: Runtime,
     signal: Option<exit_future::Signal>,
     exit: exit_future::Exit,
+    /// Receives requests from services to shut down the process.
+    signal_rx: Option<Receiver<ShutdownReason>>,
+    signal_tx: Sender<ShutdownReason>,
     log: Logger,
     eth_spec_instance: E,
     pub eth2_config: Eth2Config,
@@ -258,6 +286,7 @@ impl<E: EthSpec> Environment<E> {
             executor: TaskExecutor {
                 exit: self.exit.clone(),
                 handle: self.runtime().handle().clone(),
+                signal_tx: self.signal_tx.clone(),
                 log: self.log.clone(),
             },
             eth_spec_instance: self.eth_spec_instance.clone(),
@@ -271,6 +300,7 @@ impl<E: EthSpec> Environment<E> {
             executor: TaskExecutor {
                 exit: self.exit.clone(),
                 handle: self.runtime().handle().clone(),
+                signal_tx: self.signal_tx.clone(),
                 log: self.log.new(o!("service" => service_name.clone())),
             },
             eth_spec_instance: self.eth_spec_instance.clone(),
@@ -278,8 +308,9 @@ impl<E: EthSpec> Environment<E> {
         }
     }
 
-    /// Block the current thread until Ctrl+C is received.
-    pub fn block_until_ctrl_c(&mut self) -> Result<(), String> {
+    /// Block the current thread until Ctrl+C is received or a service sends a `ShutdownReason` on
+    /// the `TaskExecutor::shutdown_sender`.
+    pub fn block_until_shutdown_requested(&mut self) -> Result<ShutdownReason, String> {
         let (ctrlc_send, ctrlc_oneshot) = oneshot::channel();
         let ctrlc_send_c = RefCell::new(Some(ctrlc_send));
         ctrlc::set_handler(move || {
@@ -289,10 +320,31 @@ impl<E: EthSpec> Environment<E> {
         })
         .map_err(|e| format!("Could not set ctrlc handler: {:?}", e))?;
 
-        // Block this thread until Crtl+C is pressed.
-        self.runtime()
-            .block_on(ctrlc_oneshot)
-            .map_err(|e| format!("Ctrlc oneshot failed: {:?}", e))
+        let mut signal_rx = self
+            .signal_rx
+            .take()
+            .ok_or_else(|| "Shutdown has already been requested".to_string())?;
+
+        let ctrlc = async move {
+            ctrlc_oneshot
+                .await
+                .map(|()| ShutdownReason::Success("Received Ctrl+C"))
+                .map_err(|e| format!("Ctrlc oneshot failed: {:?}", e))
+        };
+        let shutdown_request = async move {
+            signal_rx
+                .next()
+                .await
+                .ok_or_else(|| "All shutdown senders were dropped".to_string())
+        };
+
+        // Block this thread until Crtl+C is pressed or a service requests a shutdown.
+        match self
+            .runtime()
+            .block_on(future::select(Box::pin(ctrlc), Box::pin(shutdown_request)))
+        {
+            future::Either::Left((result, _)) | future::Either::Right((result, _)) => result,
+        }
     }
 
     /// Shutdown the `tokio` runtime when all tasks are idle.
@@ -5,7 +5,7 @@ use beacon_node::ProductionBeaconNode;
 use clap::{App, Arg, ArgMatches};
 use clap_utils;
 use env_logger::{Builder, Env};
-use environment::EnvironmentBuilder;
+use environment::{EnvironmentBuilder, ShutdownReason};
 use eth2_testnet_config::HARDCODED_TESTNET;
 use slog::{crit, info, warn};
 use std::path::PathBuf;
@@ -91,6 +91,7 @@ fn main() {
         .subcommand(beacon_node::cli_app())
         .subcommand(validator_client::cli_app())
         .subcommand(account_manager::cli_app())
+        .subcommand(database_manager::cli_app())
         .get_matches();
 
     macro_rules! run_with_spec {
@@ -175,6 +176,13 @@ fn run<E: EthSpec>(
         return Ok(());
     };
 
+    if let Some(sub_matches) = matches.subcommand_matches(database_manager::CMD) {
+        database_manager::run(sub_matches, environment)?;
+
+        // Exit as soon as database manager returns control.
+        return Ok(());
+    };
+
     warn!(
         log,
         "Ethereum 2.0 is pre-release. This software is experimental."
@@ -236,14 +244,19 @@ fn run<E: EthSpec>(
         return Err("No subcommand supplied.".into());
     }
 
-    // Block this thread until Crtl+C is pressed.
-    environment.block_until_ctrl_c()?;
-    info!(log, "Shutting down..");
+    // Block this thread until Crtl+C is pressed or a service requests a shutdown.
+    let shutdown_reason = environment.block_until_shutdown_requested()?;
+    info!(log, "Shutting down.."; "reason" => shutdown_reason.message());
 
     environment.fire_signal();
     drop(beacon_node);
     drop(validator_client);
 
     // Shutdown the environment once all tasks have completed.
-    Ok(environment.shutdown_on_idle())
+    environment.shutdown_on_idle();
+
+    match shutdown_reason {
+        ShutdownReason::Success(_) => Ok(()),
+        ShutdownReason::Failure(msg) => Err(msg.to_string()),
+    }
 }
@@ -1,15 +1,21 @@
 #![cfg(not(debug_assertions))]
 
 use account_manager::{
+    keystore::{reencrypt, CMD as KEYSTORE_CMD},
     upgrade_legacy_keypairs::{CMD as UPGRADE_CMD, *},
-    validator::{create::*, CMD as VALIDATOR_CMD},
+    validator::{
+        create::*, deposit, export_deposit_data, import as validator_import,
+        recover as validator_recover, CMD as VALIDATOR_CMD,
+    },
     wallet::{
         create::{CMD as CREATE_CMD, *},
         list::CMD as LIST_CMD,
-        CMD as WALLET_CMD,
+        recover as wallet_recover, CMD as WALLET_CMD,
     },
     BASE_DIR_FLAG, CMD as ACCOUNT_CMD, *,
 };
+use deposit_contract::StandardDepositDataJson;
+use eth2_keystore::{json_keystore::Kdf, Keystore, KeystoreBuilder};
 use std::env;
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -366,6 +372,373 @@ fn validator_create() {
     assert_eq!(dir_child_count(validator_dir.path()), 6);
 }
 
+#[test]
+fn wallet_recover() {
+    let base_dir = tempdir().unwrap();
+    let recovered_base_dir = tempdir().unwrap();
+    let password_dir = tempdir().unwrap();
+
+    let wallet = TestWallet::new(base_dir.path(), "wally");
+    wallet.create_expect_success();
+
+    let recover = |name: &str| {
+        output_result(
+            wallet_cmd()
+                .arg(format!("--{}", BASE_DIR_FLAG))
+                .arg(recovered_base_dir.path().as_os_str())
+                .arg(wallet_recover::CMD)
+                .arg(format!("--{}", NAME_FLAG))
+                .arg(name)
+                .arg(format!("--{}", PASSPHRASE_FLAG))
+                .arg(password_dir.path().join("password.pass").as_os_str())
+                .arg(format!("--{}", wallet_recover::MNEMONIC_FLAG))
+                .arg(wallet.mnemonic_path().as_os_str()),
+        )
+    };
+
+    recover("recovered_wally").unwrap();
+    assert_eq!(
+        list_wallets(recovered_base_dir.path()),
+        vec!["recovered_wally".to_string()]
+    );
+
+    // Should not recover a wallet with a duplicate name.
+    recover("recovered_wally").unwrap_err();
+    assert_eq!(list_wallets(recovered_base_dir.path()).len(), 1);
+}
+
+#[test]
+fn wallet_recover_with_kdf() {
+    let base_dir = tempdir().unwrap();
+    let recovered_base_dir = tempdir().unwrap();
+    let password_dir = tempdir().unwrap();
+
+    let wallet = TestWallet::new(base_dir.path(), "wally");
+    wallet.create_expect_success();
+
+    output_result(
+        wallet_cmd()
+            .arg(format!("--{}", BASE_DIR_FLAG))
+            .arg(recovered_base_dir.path().as_os_str())
+            .arg(wallet_recover::CMD)
+            .arg(format!("--{}", NAME_FLAG))
+            .arg("recovered_wally")
+            .arg(format!("--{}", PASSPHRASE_FLAG))
+            .arg(password_dir.path().join("password.pass").as_os_str())
+            .arg(format!("--{}", wallet_recover::MNEMONIC_FLAG))
+            .arg(wallet.mnemonic_path().as_os_str())
+            .arg(format!("--{}", KDF_FLAG))
+            .arg(PBKDF2_KDF)
+            .arg(format!("--{}", KDF_COST_FLAG))
+            .arg("2"),
+    )
+    .unwrap();
+
+    // The wallet is stored at `{base_dir}/{uuid}/{uuid}`.
+    let wallet_dir = fs::read_dir(recovered_base_dir.path())
+        .unwrap()
+        .next()
+        .unwrap()
+        .unwrap()
+        .path();
+    let wallet_json: serde_json::Value = serde_json::from_reader(
+        fs::File::open(wallet_dir.join(wallet_dir.file_name().unwrap())).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(wallet_json["crypto"]["kdf"]["function"], "pbkdf2");
+    assert_eq!(wallet_json["crypto"]["kdf"]["params"]["c"], 2);
+}
+
+#[test]
+fn validator_recover() {
+    let base_dir = tempdir().unwrap();
+    let validator_dir = tempdir().unwrap();
+    let secrets_dir = tempdir().unwrap();
+    let recovered_validator_dir = tempdir().unwrap();
+    let recovered_secrets_dir = tempdir().unwrap();
+
+    let wallet = TestWallet::new(base_dir.path(), "wally");
+    wallet.create_expect_success();
+    let mnemonic_path = wallet.mnemonic_path();
+
+    let validator = TestValidator::new(validator_dir.path(), secrets_dir.path(), wallet);
+    let created = validator.create_expect_success(COUNT_FLAG, 3, false);
+
+    // Recover the second and third validators.
+    output_result(
+        validator_cmd()
+            .arg(validator_recover::CMD)
+            .arg(format!("--{}", validator_recover::FIRST_INDEX_FLAG))
+            .arg("1")
+            .arg(format!("--{}", COUNT_FLAG))
+            .arg("2")
+            .arg(format!("--{}", validator_recover::MNEMONIC_FLAG))
+            .arg(mnemonic_path.as_os_str())
+            .arg(format!("--{}", VALIDATOR_DIR_FLAG))
+            .arg(recovered_validator_dir.path().as_os_str())
+            .arg(format!("--{}", SECRETS_DIR_FLAG))
+            .arg(recovered_secrets_dir.path().as_os_str())
+            .arg(format!("--{}", STORE_WITHDRAW_FLAG)),
+    )
+    .unwrap();
+
+    assert_eq!(dir_child_count(recovered_validator_dir.path()), 2);
+
+    for dir in &created[1..] {
+        let name = dir.dir().file_name().unwrap();
+        let recovered = ValidatorDir::open(recovered_validator_dir.path().join(name))
+            .expect("should open recovered validator dir");
+
+        assert_eq!(
+            recovered
+                .voting_keypair(recovered_secrets_dir.path())
+                .unwrap()
+                .pk,
+            dir.voting_keypair(secrets_dir.path()).unwrap().pk
+        );
+        recovered
+            .withdrawal_keypair(recovered_secrets_dir.path())
+            .unwrap();
+        recovered.eth1_deposit_data().unwrap().unwrap();
+    }
+}
+
+#[test]
+fn validator_export_deposit_data() {
+    let base_dir = tempdir().unwrap();
+    let validator_dir = tempdir().unwrap();
+    let secrets_dir = tempdir().unwrap();
+    let output_dir = tempdir().unwrap();
+    let output_path = output_dir.path().join("deposit_data.json");
+
+    let wallet = TestWallet::new(base_dir.path(), "wally");
+    wallet.create_expect_success();
+
+    let validator = TestValidator::new(validator_dir.path(), secrets_dir.path(), wallet);
+    let deposit_datas = validator
+        .create_expect_success(COUNT_FLAG, 2, false)
+        .into_iter()
+        .map(|dir| dir.eth1_deposit_data().unwrap().unwrap().deposit_data)
+        .collect::<Vec<_>>();
+
+    let export = || {
+        output_result(
+            validator_cmd()
+                .arg(export_deposit_data::CMD)
+                .arg(format!("--{}", VALIDATOR_DIR_FLAG))
+                .arg(validator_dir.path().as_os_str())
+                .arg(format!("--{}", deposit::VALIDATOR_FLAG))
+                .arg("all")
+                .arg(format!("--{}", export_deposit_data::OUTPUT_FLAG))
+                .arg(output_path.as_os_str()),
+        )
+    };
+
+    export().unwrap();
+
+    let exported: Vec<StandardDepositDataJson> =
+        serde_json::from_reader(fs::File::open(&output_path).unwrap()).unwrap();
+
+    assert_eq!(exported.len(), deposit_datas.len());
+    for deposit_data in &deposit_datas {
+        assert!(
+            exported
+                .iter()
+                .any(|deposit| &deposit.deposit_data() == deposit_data),
+            "should export every deposit"
+        );
+    }
+
+    // An existing file is not overwritten.
+    export().unwrap_err();
+}
+
+/// Writes a keystore for a random keypair, encrypted with `password`, to `dir/name`.
+fn write_keystore<P: AsRef<Path>>(name: &str, dir: P, password: &[u8]) -> Keypair {
+    let keypair = Keypair::random();
+    let keystore = KeystoreBuilder::new(&keypair, password, "".into())
+        .unwrap()
+        .build()
+        .unwrap();
+
+    let file = fs::File::create(dir.as_ref().join(name)).unwrap();
+    keystore.to_json_writer(file).unwrap();
+
+    keypair
+}
+
+#[test]
+fn validator_import() {
+    let keystores_dir = tempdir().unwrap();
+    let validator_dir = tempdir().unwrap();
+    let secrets_dir = tempdir().unwrap();
+
+    let password = "cats_and_dogs";
+    let password_path = keystores_dir.path().join("password.pass");
+    fs::write(&password_path, format!("{}\n", password)).unwrap();
+
+    let keypairs = vec![
+        write_keystore(
+            "keystore-m_12381_3600_0_0_0.json",
+            keystores_dir.path(),
+            password.as_bytes(),
+        ),
+        write_keystore(
+            "keystore-m_12381_3600_1_0_0.json",
+            keystores_dir.path(),
+            password.as_bytes(),
+        ),
+    ];
+    // Files which do not look like keystores are ignored.
+    write_keystore(
+        "not-a-keystore.json",
+        keystores_dir.path(),
+        password.as_bytes(),
+    );
+
+    let import = |keystore_flag: &str, path: &Path, password_path: &Path| {
+        output_result(
+            validator_cmd()
+                .arg(validator_import::CMD)
+                .arg(format!("--{}", keystore_flag))
+                .arg(path.as_os_str())
+                .arg(format!("--{}", validator_import::PASSWORD_FLAG))
+                .arg(password_path.as_os_str())
+                .arg(format!("--{}", VALIDATOR_DIR_FLAG))
+                .arg(validator_dir.path().as_os_str())
+                .arg(format!("--{}", SECRETS_DIR_FLAG))
+                .arg(secrets_dir.path().as_os_str()),
+        )
+    };
+
+    import(
+        validator_import::DIR_FLAG,
+        keystores_dir.path(),
+        &password_path,
+    )
+    .unwrap();
+
+    assert_eq!(dir_child_count(validator_dir.path()), 2);
+    assert_eq!(dir_child_count(secrets_dir.path()), 2);
+
+    for keypair in &keypairs {
+        let dir = ValidatorDir::open(validator_dir.path().join(keypair.pk.as_hex_string()))
+            .expect("should open imported validator dir");
+        assert_eq!(
+            dir.voting_keypair(secrets_dir.path()).unwrap().pk,
+            keypair.pk
+        );
+    }
+
+    // Importing a validator which already exists is refused.
+    import(
+        validator_import::KEYSTORE_FLAG,
+        &keystores_dir
+            .path()
+            .join("keystore-m_12381_3600_0_0_0.json"),
+        &password_path,
+    )
+    .unwrap_err();
+
+    // Importing with the wrong password fails, without creating a validator.
+    let new_keystore_dir = tempdir().unwrap();
+    write_keystore(
+        "keystore.json",
+        new_keystore_dir.path(),
+        password.as_bytes(),
+    );
+    let wrong_password_path = new_keystore_dir.path().join("wrong.pass");
+    fs::write(&wrong_password_path, "dogs_and_cats").unwrap();
+
+    import(
+        validator_import::KEYSTORE_FLAG,
+        &new_keystore_dir.path().join("keystore.json"),
+        &wrong_password_path,
+    )
+    .unwrap_err();
+
+    assert_eq!(dir_child_count(validator_dir.path()), 2);
+    assert_eq!(dir_child_count(secrets_dir.path()), 2);
+}
+
+/// Returns the `lighthouse account keystore` command.
+fn keystore_cmd() -> Command {
+    let mut cmd = account_cmd();
+    cmd.arg(KEYSTORE_CMD);
+    cmd
+}
+
+fn read_keystore_file<P: AsRef<Path>>(path: P) -> Keystore {
+    Keystore::from_json_reader(fs::File::open(path).unwrap()).unwrap()
+}
+
+#[test]
+fn keystore_reencrypt() {
+    let dir = tempdir().unwrap();
+    let keystore_path = dir.path().join("keystore.json");
+    let password_path = dir.path().join("password.pass");
+    let new_password_path = dir.path().join("new_password.pass");
+
+    fs::write(&password_path, "cats_and_dogs\n").unwrap();
+    fs::write(&new_password_path, "dogs_and_cats").unwrap();
+
+    let keypair = write_keystore("keystore.json", dir.path(), b"cats_and_dogs");
+    let original = read_keystore_file(&keystore_path);
+
+    let reencrypt_cmd = |password_path: &Path| {
+        output_result(
+            keystore_cmd()
+                .arg(reencrypt::CMD)
+                .arg(format!("--{}", reencrypt::KEYSTORE_FLAG))
+                .arg(keystore_path.as_os_str())
+                .arg(format!("--{}", reencrypt::PASSWORD_FLAG))
+                .arg(password_path.as_os_str())
+                .arg(format!("--{}", reencrypt::NEW_PASSWORD_FLAG))
+                .arg(new_password_path.as_os_str())
+                .arg(format!("--{}", KDF_FLAG))
+                .arg(PBKDF2_KDF)
+                .arg(format!("--{}", KDF_COST_FLAG))
+                .arg("2"),
+        )
+    };
+
+    // A wrong password leaves the keystore untouched.
+    reencrypt_cmd(&new_password_path).unwrap_err();
+    assert_eq!(read_keystore_file(&keystore_path), original);
+
+    reencrypt_cmd(&password_path).unwrap();
+
+    let reencrypted = read_keystore_file(&keystore_path);
+    assert_eq!(reencrypted.uuid(), original.uuid());
+    assert_eq!(reencrypted.pubkey(), original.pubkey());
+    match reencrypted.kdf() {
+        Kdf::Pbkdf2(params) => assert_eq!(params.c, 2),
+        other => panic!("should use pbkdf2, not {:?}", other),
+    }
+    assert_eq!(
+        reencrypted.decrypt_keypair(b"dogs_and_cats").unwrap().pk,
+        keypair.pk
+    );
+
+    // Without --kdf, the key derivation function of the keystore is kept.
+    output_result(
+        keystore_cmd()
+            .arg(reencrypt::CMD)
+            .arg(format!("--{}", reencrypt::KEYSTORE_FLAG))
+            .arg(keystore_path.as_os_str())
+            .arg(format!("--{}", reencrypt::PASSWORD_FLAG))
+            .arg(new_password_path.as_os_str()),
+    )
+    .unwrap();
+    match read_keystore_file(&keystore_path).kdf() {
+        Kdf::Pbkdf2(params) => assert_eq!(params.c, 2),
+        other => panic!("should keep pbkdf2, not {:?}", other),
+    }
+
+    // No temporary files are left behind.
+    assert_eq!(dir_child_count(dir.path()), 3);
+}
+
 fn write_legacy_keypair<P: AsRef<Path>>(name: &str, dir: P) -> Keypair {
     let keypair = Keypair::random();
 
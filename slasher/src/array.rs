@@ -0,0 +1,217 @@
+//! The min-max surround vote detection scheme.
+//!
+//! For each validator we maintain two arrays indexed by epoch:
+//!
+//! - `min_targets[e]`: the minimum target epoch of the validator's attestations with a source epoch
+//!   greater than `e`. A new attestation `(s, t)` surrounds an existing one iff
+//!   `min_targets[s] < t`.
+//! - `max_targets[e]`: the maximum target epoch of the validator's attestations with a source epoch
+//!   less than `e`. A new attestation `(s, t)` is surrounded by an existing one iff
+//!   `max_targets[s] > t`.
+//!
+//! Both arrays are non-decreasing in `e`, so an update can stop as soon as it reaches an entry
+//! which does not change. The arrays are stored on disk in chunks of `CHUNK_SIZE` epochs, with each
+//! entry stored as the `u16` distance from its epoch to the target epoch.
+
+use crate::{database::SlasherDB, Error};
+use std::collections::HashMap;
+use store::DBColumn;
+use types::{Epoch, EthSpec};
+
+/// The number of epochs in each chunk of a span array.
+pub const CHUNK_SIZE: u64 = 16;
+
+/// One of the two span arrays kept for each validator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TargetArray {
+    MinTargets,
+    MaxTargets,
+}
+
+impl TargetArray {
+    fn column(self) -> DBColumn {
+        match self {
+            TargetArray::MinTargets => DBColumn::SlasherMinTargets,
+            TargetArray::MaxTargets => DBColumn::SlasherMaxTargets,
+        }
+    }
+
+    /// The distance stored for epochs which have no relevant attestation.
+    fn empty_distance(self) -> u16 {
+        match self {
+            TargetArray::MinTargets => u16::max_value(),
+            TargetArray::MaxTargets => 0,
+        }
+    }
+
+    /// Returns `true` if the entry `existing` should be replaced by `new_target`.
+    fn should_replace(self, existing: Option<Epoch>, new_target: Epoch) -> bool {
+        match (self, existing) {
+            (_, None) => true,
+            (TargetArray::MinTargets, Some(existing)) => new_target < existing,
+            (TargetArray::MaxTargets, Some(existing)) => new_target > existing,
+        }
+    }
+}
+
+struct Chunk {
+    distances: Vec<u16>,
+    dirty: bool,
+}
+
+/// Caches the span array chunks read and written while processing a batch of attestations.
+///
+/// Modified chunks are only written to the database by `SpanCache::write`.
+pub struct SpanCache<'a, E: EthSpec> {
+    db: &'a SlasherDB<E>,
+    chunks: HashMap<(TargetArray, u64, u64), Chunk>,
+}
+
+impl<'a, E: EthSpec> SpanCache<'a, E> {
+    pub fn new(db: &'a SlasherDB<E>) -> Self {
+        Self {
+            db,
+            chunks: HashMap::new(),
+        }
+    }
+
+    /// Returns the target epoch stored in `array` for `validator_index` at `epoch`.
+    pub fn get(
+        &mut self,
+        array: TargetArray,
+        validator_index: u64,
+        epoch: Epoch,
+    ) -> Result<Option<Epoch>, Error> {
+        let chunk = self.chunk(array, validator_index, epoch)?;
+        let distance = chunk.distances[chunk_offset(epoch)];
+
+        if distance == array.empty_distance() {
+            Ok(None)
+        } else {
+            Ok(Some(epoch + u64::from(distance)))
+        }
+    }
+
+    /// Stores `target` (which must be greater than `epoch`) in `array` for `validator_index` at
+    /// `epoch`.
+    fn set(
+        &mut self,
+        array: TargetArray,
+        validator_index: u64,
+        epoch: Epoch,
+        target: Epoch,
+    ) -> Result<(), Error> {
+        let distance = (target - epoch).as_u64() as u16;
+        let chunk = self.chunk(array, validator_index, epoch)?;
+        chunk.distances[chunk_offset(epoch)] = distance;
+        chunk.dirty = true;
+        Ok(())
+    }
+
+    /// Updates both span arrays of `validator_index` with an attestation from `source` to
+    /// `target`.
+    ///
+    /// Entries of `min_targets` below `window_start` are not updated, since they have been (or are
+    /// about to be) pruned.
+    pub fn update(
+        &mut self,
+        validator_index: u64,
+        source: Epoch,
+        target: Epoch,
+        window_start: Epoch,
+    ) -> Result<(), Error> {
+        let array = TargetArray::MinTargets;
+        let mut epoch = source;
+        while epoch > window_start {
+            epoch -= 1;
+            let existing = self.get(array, validator_index, epoch)?;
+            if !array.should_replace(existing, target) {
+                break;
+            }
+            self.set(array, validator_index, epoch, target)?;
+        }
+
+        // Entries at or beyond `target` are irrelevant: any attestation with a source there also
+        // has a target beyond `target`, so it cannot be surrounded by this attestation.
+        let array = TargetArray::MaxTargets;
+        let mut epoch = source + 1;
+        while epoch < target {
+            let existing = self.get(array, validator_index, epoch)?;
+            if !array.should_replace(existing, target) {
+                break;
+            }
+            self.set(array, validator_index, epoch, target)?;
+            epoch += 1;
+        }
+
+        Ok(())
+    }
+
+    /// Writes all modified chunks to the database.
+    pub fn write(self) -> Result<(), Error> {
+        for ((array, validator_index, chunk_index), chunk) in self.chunks {
+            if chunk.dirty {
+                self.db.put_chunk(
+                    array.column(),
+                    validator_index,
+                    chunk_index,
+                    &chunk.distances,
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    fn chunk(
+        &mut self,
+        array: TargetArray,
+        validator_index: u64,
+        epoch: Epoch,
+    ) -> Result<&mut Chunk, Error> {
+        let chunk_index = epoch.as_u64() / CHUNK_SIZE;
+        let key = (array, validator_index, chunk_index);
+
+        if !self.chunks.contains_key(&key) {
+            let distances = match self
+                .db
+                .get_chunk(array.column(), validator_index, chunk_index)?
+            {
+                Some(distances) if distances.len() == CHUNK_SIZE as usize => distances,
+                Some(distances) => return Err(Error::InvalidChunkLength(distances.len())),
+                None => vec![array.empty_distance(); CHUNK_SIZE as usize],
+            };
+            self.chunks.insert(
+                key,
+                Chunk {
+                    distances,
+                    dirty: false,
+                },
+            );
+        }
+
+        Ok(self.chunks.get_mut(&key).expect("chunk was inserted above"))
+    }
+}
+
+/// Deletes the chunks of both span arrays of `validator_index` which only contain epochs in
+/// `old_window_start..new_window_start`.
+pub fn prune_chunks<E: EthSpec>(
+    db: &SlasherDB<E>,
+    validator_index: u64,
+    old_window_start: Epoch,
+    new_window_start: Epoch,
+) -> Result<(), Error> {
+    let first_chunk = old_window_start.as_u64() / CHUNK_SIZE;
+    let end_chunk = new_window_start.as_u64() / CHUNK_SIZE;
+
+    for array in &[TargetArray::MinTargets, TargetArray::MaxTargets] {
+        for chunk_index in first_chunk..end_chunk {
+            db.delete_chunk(array.column(), validator_index, chunk_index)?;
+        }
+    }
+    Ok(())
+}
+
+fn chunk_offset(epoch: Epoch) -> usize {
+    (epoch.as_u64() % CHUNK_SIZE) as usize
+}
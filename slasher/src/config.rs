@@ -0,0 +1,46 @@
+use crate::Error;
+use serde_derive::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// The default number of epochs of attestations and blocks kept by the slasher.
+pub const DEFAULT_HISTORY_LENGTH: u64 = 4096;
+/// The default number of seconds between processing batches of attestations and blocks.
+pub const DEFAULT_UPDATE_PERIOD: u64 = 12;
+/// The largest supported history length.
+///
+/// The span arrays store distances between epochs as `u16`, with `u16::max_value()` reserved to
+/// mark an empty entry.
+pub const MAX_HISTORY_LENGTH: u64 = u16::max_value() as u64 - 1;
+
+/// Configuration for the slasher.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Config {
+    /// The directory containing the slasher database.
+    pub database_path: PathBuf,
+    /// The number of epochs of history to check for slashable messages.
+    pub history_length: u64,
+    /// The number of seconds between processing batches of queued attestations and blocks.
+    pub update_period: u64,
+}
+
+impl Config {
+    pub fn new(database_path: PathBuf) -> Self {
+        Self {
+            database_path,
+            history_length: DEFAULT_HISTORY_LENGTH,
+            update_period: DEFAULT_UPDATE_PERIOD,
+        }
+    }
+
+    /// Returns an error if the config cannot be used by the slasher.
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.history_length == 0 || self.history_length > MAX_HISTORY_LENGTH {
+            Err(Error::InvalidHistoryLength {
+                history_length: self.history_length,
+                max_history_length: MAX_HISTORY_LENGTH,
+            })
+        } else {
+            Ok(())
+        }
+    }
+}
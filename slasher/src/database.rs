@@ -0,0 +1,226 @@
+use crate::Error;
+use ssz::{Decode, Encode};
+use ssz_derive::{Decode, Encode};
+use std::path::Path;
+use store::{DBColumn, KeyValueStore, LevelDB};
+use types::{Epoch, EthSpec, Hash256, IndexedAttestation, SignedBeaconBlockHeader, Slot};
+
+/// The key of the `Metadata` in the `SlasherMetadata` column.
+const METADATA_KEY: &[u8] = b"metadata";
+
+/// The attestation made by a validator for some target epoch.
+#[derive(Debug, Clone, Copy, PartialEq, Encode, Decode)]
+pub struct AttesterRecord {
+    /// The tree hash root of the `AttestationData`.
+    pub attestation_data_hash: Hash256,
+    /// The tree hash root of the `IndexedAttestation` the validator was first seen in.
+    pub indexed_attestation_hash: Hash256,
+}
+
+/// Information about the contents of the database.
+#[derive(Debug, Clone, Copy, PartialEq, Encode, Decode)]
+pub struct Metadata {
+    /// One more than the highest validator index seen.
+    pub num_validators: u64,
+    /// The lowest epoch which has not been pruned.
+    pub window_start: Epoch,
+}
+
+/// The on-disk database of the slasher, held separately from the beacon node database.
+pub struct SlasherDB<E: EthSpec> {
+    db: LevelDB<E>,
+}
+
+impl<E: EthSpec> SlasherDB<E> {
+    /// Open the database at `path`, creating it if it does not exist.
+    pub fn open(path: &Path) -> Result<Self, Error> {
+        std::fs::create_dir_all(path).map_err(|e| store::Error::DBError {
+            message: format!("Unable to create slasher database directory: {:?}", e),
+        })?;
+
+        Ok(Self {
+            db: LevelDB::open(path)?,
+        })
+    }
+
+    pub fn get_metadata(&self) -> Result<Option<Metadata>, Error> {
+        self.get_ssz(DBColumn::SlasherMetadata, METADATA_KEY)
+    }
+
+    pub fn put_metadata(&self, metadata: &Metadata) -> Result<(), Error> {
+        self.put_ssz(DBColumn::SlasherMetadata, METADATA_KEY, metadata)
+    }
+
+    pub fn get_attester_record(
+        &self,
+        validator_index: u64,
+        target_epoch: Epoch,
+    ) -> Result<Option<AttesterRecord>, Error> {
+        self.get_ssz(
+            DBColumn::SlasherAttesterRecord,
+            &attester_record_key(validator_index, target_epoch),
+        )
+    }
+
+    pub fn put_attester_record(
+        &self,
+        validator_index: u64,
+        target_epoch: Epoch,
+        record: &AttesterRecord,
+    ) -> Result<(), Error> {
+        self.put_ssz(
+            DBColumn::SlasherAttesterRecord,
+            &attester_record_key(validator_index, target_epoch),
+            record,
+        )
+    }
+
+    pub fn delete_attester_record(
+        &self,
+        validator_index: u64,
+        target_epoch: Epoch,
+    ) -> Result<(), Error> {
+        self.delete(
+            DBColumn::SlasherAttesterRecord,
+            &attester_record_key(validator_index, target_epoch),
+        )
+    }
+
+    pub fn get_indexed_attestation(
+        &self,
+        indexed_attestation_hash: Hash256,
+    ) -> Result<Option<IndexedAttestation<E>>, Error> {
+        self.get_ssz(
+            DBColumn::SlasherIndexedAttestation,
+            indexed_attestation_hash.as_bytes(),
+        )
+    }
+
+    /// Store `indexed_attestation`, unless it is already known.
+    pub fn put_indexed_attestation(
+        &self,
+        indexed_attestation_hash: Hash256,
+        indexed_attestation: &IndexedAttestation<E>,
+    ) -> Result<(), Error> {
+        let column = DBColumn::SlasherIndexedAttestation;
+        let key = indexed_attestation_hash.as_bytes();
+
+        if !self.db.key_exists(column.into(), key)? {
+            self.put_ssz(column, key, indexed_attestation)?;
+        }
+        Ok(())
+    }
+
+    pub fn delete_indexed_attestation(
+        &self,
+        indexed_attestation_hash: Hash256,
+    ) -> Result<(), Error> {
+        self.delete(
+            DBColumn::SlasherIndexedAttestation,
+            indexed_attestation_hash.as_bytes(),
+        )
+    }
+
+    /// Returns the chunk of a span array in `column` for `validator_index`, if it exists.
+    pub fn get_chunk(
+        &self,
+        column: DBColumn,
+        validator_index: u64,
+        chunk_index: u64,
+    ) -> Result<Option<Vec<u16>>, Error> {
+        self.db
+            .get_bytes(column.into(), &chunk_key(validator_index, chunk_index))?
+            .map(|bytes| {
+                if bytes.len() % 2 != 0 {
+                    return Err(Error::InvalidChunkLength(bytes.len()));
+                }
+                Ok(bytes
+                    .chunks_exact(2)
+                    .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+                    .collect())
+            })
+            .transpose()
+    }
+
+    pub fn put_chunk(
+        &self,
+        column: DBColumn,
+        validator_index: u64,
+        chunk_index: u64,
+        chunk: &[u16],
+    ) -> Result<(), Error> {
+        let bytes = chunk
+            .iter()
+            .flat_map(|distance| distance.to_le_bytes().to_vec())
+            .collect::<Vec<_>>();
+        self.db.put_bytes(
+            column.into(),
+            &chunk_key(validator_index, chunk_index),
+            &bytes,
+        )?;
+        Ok(())
+    }
+
+    pub fn delete_chunk(
+        &self,
+        column: DBColumn,
+        validator_index: u64,
+        chunk_index: u64,
+    ) -> Result<(), Error> {
+        self.delete(column, &chunk_key(validator_index, chunk_index))
+    }
+
+    /// Returns the block headers seen at `slot`, at most one per proposer.
+    pub fn get_block_headers(&self, slot: Slot) -> Result<Vec<SignedBeaconBlockHeader>, Error> {
+        Ok(self
+            .get_ssz(DBColumn::SlasherProposals, &slot_key(slot))?
+            .unwrap_or_default())
+    }
+
+    pub fn put_block_headers(
+        &self,
+        slot: Slot,
+        headers: Vec<SignedBeaconBlockHeader>,
+    ) -> Result<(), Error> {
+        self.put_ssz(DBColumn::SlasherProposals, &slot_key(slot), &headers)
+    }
+
+    pub fn delete_block_headers(&self, slot: Slot) -> Result<(), Error> {
+        self.delete(DBColumn::SlasherProposals, &slot_key(slot))
+    }
+
+    fn get_ssz<T: Decode>(&self, column: DBColumn, key: &[u8]) -> Result<Option<T>, Error> {
+        self.db
+            .get_bytes(column.into(), key)?
+            .map(|bytes| T::from_ssz_bytes(&bytes))
+            .transpose()
+            .map_err(Into::into)
+    }
+
+    fn put_ssz<T: Encode>(&self, column: DBColumn, key: &[u8], item: &T) -> Result<(), Error> {
+        self.db
+            .put_bytes(column.into(), key, &item.as_ssz_bytes())
+            .map_err(Into::into)
+    }
+
+    fn delete(&self, column: DBColumn, key: &[u8]) -> Result<(), Error> {
+        self.db.key_delete(column.into(), key).map_err(Into::into)
+    }
+}
+
+/// Big-endian keys keep the entries of each validator adjacent and in epoch order on disk.
+fn attester_record_key(validator_index: u64, target_epoch: Epoch) -> Vec<u8> {
+    let mut key = validator_index.to_be_bytes().to_vec();
+    key.extend_from_slice(&target_epoch.as_u64().to_be_bytes());
+    key
+}
+
+fn chunk_key(validator_index: u64, chunk_index: u64) -> Vec<u8> {
+    let mut key = validator_index.to_be_bytes().to_vec();
+    key.extend_from_slice(&chunk_index.to_be_bytes());
+    key
+}
+
+fn slot_key(slot: Slot) -> Vec<u8> {
+    slot.as_u64().to_be_bytes().to_vec()
+}
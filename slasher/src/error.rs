@@ -0,0 +1,27 @@
+use types::Hash256;
+
+#[derive(Debug)]
+pub enum Error {
+    DatabaseError(store::Error),
+    SszDecodeError(ssz::DecodeError),
+    InvalidHistoryLength {
+        history_length: u64,
+        max_history_length: u64,
+    },
+    /// A span array chunk had the wrong number of entries.
+    InvalidChunkLength(usize),
+    /// An attester record refers to an indexed attestation which is not in the database.
+    MissingIndexedAttestation(Hash256),
+}
+
+impl From<store::Error> for Error {
+    fn from(e: store::Error) -> Self {
+        Error::DatabaseError(e)
+    }
+}
+
+impl From<ssz::DecodeError> for Error {
+    fn from(e: ssz::DecodeError) -> Self {
+        Error::SszDecodeError(e)
+    }
+}
@@ -0,0 +1,562 @@
+//! Detects slashable behaviour by recording every attestation and block header seen by the beacon
+//! node.
+//!
+//! Attestations and block headers are queued by the `BeaconChain` as they are verified and
+//! processed in batches by `Slasher::process_queued`. Double proposals and double votes are found
+//! by looking up the message previously signed by the same validator at the same slot or target
+//! epoch. Surround votes are found using the min-max span arrays described in the `array` module.
+//!
+//! Messages older than `Config::history_length` epochs are pruned from the database.
+
+#[macro_use]
+extern crate lazy_static;
+
+mod array;
+mod config;
+mod database;
+mod error;
+mod metrics;
+
+pub use crate::config::{
+    Config, DEFAULT_HISTORY_LENGTH, DEFAULT_UPDATE_PERIOD, MAX_HISTORY_LENGTH,
+};
+pub use crate::error::Error;
+
+use crate::array::{prune_chunks, SpanCache, TargetArray};
+use crate::database::{AttesterRecord, Metadata, SlasherDB};
+use parking_lot::Mutex;
+use slog::{debug, Logger};
+use std::collections::HashSet;
+use tree_hash::TreeHash;
+use types::{
+    AttesterSlashing, Epoch, EthSpec, Hash256, IndexedAttestation, ProposerSlashing,
+    SignedBeaconBlockHeader,
+};
+
+/// The maximum number of attestations waiting to be processed. Attestations received whilst the
+/// queue is full are dropped.
+pub const MAX_QUEUED_ATTESTATIONS: usize = 1 << 16;
+
+/// Records attestations and block headers and produces slashings for any conflicts between them.
+pub struct Slasher<E: EthSpec> {
+    db: SlasherDB<E>,
+    config: Config,
+    attestation_queue: Mutex<Vec<IndexedAttestation<E>>>,
+    block_queue: Mutex<Vec<SignedBeaconBlockHeader>>,
+    attester_slashings: Mutex<Vec<AttesterSlashing<E>>>,
+    proposer_slashings: Mutex<Vec<ProposerSlashing>>,
+    /// Ensures only one batch is processed at a time.
+    process_lock: Mutex<()>,
+    log: Logger,
+}
+
+impl<E: EthSpec> Slasher<E> {
+    /// Open the slasher database described by `config`, creating it if it does not exist.
+    pub fn open(config: Config, log: Logger) -> Result<Self, Error> {
+        config.validate()?;
+        let db = SlasherDB::open(&config.database_path)?;
+
+        Ok(Self {
+            db,
+            config,
+            attestation_queue: Mutex::new(vec![]),
+            block_queue: Mutex::new(vec![]),
+            attester_slashings: Mutex::new(vec![]),
+            proposer_slashings: Mutex::new(vec![]),
+            process_lock: Mutex::new(()),
+            log,
+        })
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Queue an attestation with a valid signature for processing.
+    ///
+    /// The attestation is dropped if `MAX_QUEUED_ATTESTATIONS` are already queued.
+    pub fn accept_attestation(&self, indexed_attestation: IndexedAttestation<E>) {
+        let mut queue = self.attestation_queue.lock();
+        if queue.len() < MAX_QUEUED_ATTESTATIONS {
+            queue.push(indexed_attestation);
+        } else {
+            metrics::inc_counter(&metrics::SLASHER_DROPPED_ATTESTATIONS);
+        }
+    }
+
+    /// Queue a block header with a valid signature for processing.
+    pub fn accept_block_header(&self, block_header: SignedBeaconBlockHeader) {
+        self.block_queue.lock().push(block_header);
+    }
+
+    /// Removes and returns the attester slashings found since the last call.
+    pub fn get_attester_slashings(&self) -> Vec<AttesterSlashing<E>> {
+        std::mem::replace(&mut self.attester_slashings.lock(), vec![])
+    }
+
+    /// Removes and returns the proposer slashings found since the last call.
+    pub fn get_proposer_slashings(&self) -> Vec<ProposerSlashing> {
+        std::mem::replace(&mut self.proposer_slashings.lock(), vec![])
+    }
+
+    /// Prune the database and check all queued attestations and block headers for slashable
+    /// behaviour. Slashings are made available via `get_attester_slashings` and
+    /// `get_proposer_slashings`.
+    pub fn process_queued(&self, current_epoch: Epoch) -> Result<(), Error> {
+        let _process_lock = self.process_lock.lock();
+
+        let window_start = (current_epoch + 1).saturating_sub(self.config.history_length);
+        let mut metadata = self.prune(window_start)?;
+
+        let block_headers = std::mem::replace(&mut *self.block_queue.lock(), vec![]);
+        let num_blocks = block_headers.len();
+        let proposer_slashings = self.process_block_headers(block_headers, window_start)?;
+
+        let attestations = std::mem::replace(&mut *self.attestation_queue.lock(), vec![]);
+        let num_attestations = attestations.len();
+        let attester_slashings =
+            self.process_attestations(attestations, current_epoch, &mut metadata)?;
+        self.db.put_metadata(&metadata)?;
+
+        debug!(
+            self.log,
+            "Slasher batch processed";
+            "epoch" => current_epoch.as_u64(),
+            "blocks" => num_blocks,
+            "attestations" => num_attestations,
+            "proposer_slashings" => proposer_slashings.len(),
+            "attester_slashings" => attester_slashings.len(),
+        );
+
+        self.proposer_slashings.lock().extend(proposer_slashings);
+        self.attester_slashings.lock().extend(attester_slashings);
+
+        Ok(())
+    }
+
+    fn process_block_headers(
+        &self,
+        block_headers: Vec<SignedBeaconBlockHeader>,
+        window_start: Epoch,
+    ) -> Result<Vec<ProposerSlashing>, Error> {
+        let mut slashings = vec![];
+
+        for header in block_headers {
+            let slot = header.message.slot;
+            if slot.epoch(E::slots_per_epoch()) < window_start {
+                continue;
+            }
+
+            let mut headers = self.db.get_block_headers(slot)?;
+            let existing = headers
+                .iter()
+                .find(|existing| existing.message.proposer_index == header.message.proposer_index);
+
+            match existing {
+                Some(existing) if existing.message == header.message => {}
+                Some(existing) => {
+                    if !slashings.iter().any(|slashing: &ProposerSlashing| {
+                        slashing.signed_header_2.message == header.message
+                    }) {
+                        slashings.push(ProposerSlashing {
+                            signed_header_1: existing.clone(),
+                            signed_header_2: header,
+                        });
+                    }
+                }
+                None => {
+                    headers.push(header);
+                    self.db.put_block_headers(slot, headers)?;
+                }
+            }
+        }
+
+        Ok(slashings)
+    }
+
+    fn process_attestations(
+        &self,
+        attestations: Vec<IndexedAttestation<E>>,
+        current_epoch: Epoch,
+        metadata: &mut Metadata,
+    ) -> Result<Vec<AttesterSlashing<E>>, Error> {
+        let window_start = metadata.window_start;
+        let mut spans = SpanCache::new(&self.db);
+        let mut seen_attestations = HashSet::new();
+        let mut slashings = vec![];
+        let mut slashing_roots = HashSet::new();
+
+        for attestation in attestations {
+            let source = attestation.data.source.epoch;
+            let target = attestation.data.target.epoch;
+
+            // Attestations with a source outside the window may have span distances which do not
+            // fit in the span arrays. Honest validators do not produce them during normal
+            // operation.
+            if source < window_start || source > target || target > current_epoch + 1 {
+                debug!(
+                    self.log,
+                    "Slasher ignoring attestation";
+                    "source" => source.as_u64(),
+                    "target" => target.as_u64(),
+                    "window_start" => window_start.as_u64(),
+                );
+                continue;
+            }
+
+            let indexed_attestation_hash = attestation.tree_hash_root();
+            if !seen_attestations.insert(indexed_attestation_hash) {
+                continue;
+            }
+
+            let record = AttesterRecord {
+                attestation_data_hash: attestation.data.tree_hash_root(),
+                indexed_attestation_hash,
+            };
+            self.db
+                .put_indexed_attestation(indexed_attestation_hash, &attestation)?;
+
+            for &validator_index in attestation.attesting_indices.iter() {
+                metadata.num_validators =
+                    std::cmp::max(metadata.num_validators, validator_index + 1);
+
+                let slashing = self.check_attestation(
+                    &mut spans,
+                    validator_index,
+                    &attestation,
+                    &record,
+                    window_start,
+                )?;
+
+                if let Some(slashing) = slashing {
+                    if slashing_roots.insert(slashing.tree_hash_root()) {
+                        slashings.push(slashing);
+                    }
+                }
+            }
+        }
+
+        spans.write()?;
+
+        Ok(slashings)
+    }
+
+    /// Checks `attestation` against the previous attestations of `validator_index`, recording it if
+    /// it is not a double vote.
+    fn check_attestation(
+        &self,
+        spans: &mut SpanCache<E>,
+        validator_index: u64,
+        attestation: &IndexedAttestation<E>,
+        record: &AttesterRecord,
+        window_start: Epoch,
+    ) -> Result<Option<AttesterSlashing<E>>, Error> {
+        let source = attestation.data.source.epoch;
+        let target = attestation.data.target.epoch;
+
+        if let Some(existing) = self.db.get_attester_record(validator_index, target)? {
+            if existing.attestation_data_hash == record.attestation_data_hash {
+                return Ok(None);
+            }
+
+            return Ok(Some(AttesterSlashing {
+                attestation_1: self.get_indexed_attestation(existing.indexed_attestation_hash)?,
+                attestation_2: attestation.clone(),
+            }));
+        }
+
+        let mut slashing = None;
+
+        if let Some(min_target) = spans.get(TargetArray::MinTargets, validator_index, source)? {
+            if min_target < target {
+                slashing = self
+                    .get_recorded_attestation(validator_index, min_target)?
+                    .map(|existing| AttesterSlashing {
+                        attestation_1: attestation.clone(),
+                        attestation_2: existing,
+                    });
+            }
+        }
+
+        if let Some(max_target) = spans.get(TargetArray::MaxTargets, validator_index, source)? {
+            if slashing.is_none() && max_target > target {
+                slashing = self
+                    .get_recorded_attestation(validator_index, max_target)?
+                    .map(|existing| AttesterSlashing {
+                        attestation_1: existing,
+                        attestation_2: attestation.clone(),
+                    });
+            }
+        }
+
+        self.db
+            .put_attester_record(validator_index, target, record)?;
+        spans.update(validator_index, source, target, window_start)?;
+
+        Ok(slashing)
+    }
+
+    /// Returns the attestation recorded for `validator_index` at `target_epoch`, if any.
+    fn get_recorded_attestation(
+        &self,
+        validator_index: u64,
+        target_epoch: Epoch,
+    ) -> Result<Option<IndexedAttestation<E>>, Error> {
+        self.db
+            .get_attester_record(validator_index, target_epoch)?
+            .map(|record| self.get_indexed_attestation(record.indexed_attestation_hash))
+            .transpose()
+    }
+
+    fn get_indexed_attestation(
+        &self,
+        indexed_attestation_hash: Hash256,
+    ) -> Result<IndexedAttestation<E>, Error> {
+        self.db
+            .get_indexed_attestation(indexed_attestation_hash)?
+            .ok_or_else(|| Error::MissingIndexedAttestation(indexed_attestation_hash))
+    }
+
+    /// Delete all attestations and block headers from before `window_start`, returning the updated
+    /// metadata.
+    fn prune(&self, window_start: Epoch) -> Result<Metadata, Error> {
+        let metadata = match self.db.get_metadata()? {
+            Some(metadata) => metadata,
+            // Nothing has been stored yet, so there is nothing to prune.
+            None => Metadata {
+                num_validators: 0,
+                window_start,
+            },
+        };
+
+        if window_start <= metadata.window_start {
+            return Ok(metadata);
+        }
+
+        let old_window_start = metadata.window_start;
+        let mut indexed_attestation_hashes = HashSet::new();
+
+        for validator_index in 0..metadata.num_validators {
+            for epoch in old_window_start.as_u64()..window_start.as_u64() {
+                let epoch = Epoch::new(epoch);
+                if let Some(record) = self.db.get_attester_record(validator_index, epoch)? {
+                    indexed_attestation_hashes.insert(record.indexed_attestation_hash);
+                    self.db.delete_attester_record(validator_index, epoch)?;
+                }
+            }
+            prune_chunks(&self.db, validator_index, old_window_start, window_start)?;
+        }
+
+        for indexed_attestation_hash in indexed_attestation_hashes {
+            self.db
+                .delete_indexed_attestation(indexed_attestation_hash)?;
+        }
+
+        let start_slot = old_window_start.start_slot(E::slots_per_epoch());
+        let end_slot = window_start.start_slot(E::slots_per_epoch());
+        for slot in start_slot.as_u64()..end_slot.as_u64() {
+            self.db.delete_block_headers(slot.into())?;
+        }
+
+        let metadata = Metadata {
+            num_validators: metadata.num_validators,
+            window_start,
+        };
+        self.db.put_metadata(&metadata)?;
+
+        debug!(
+            self.log,
+            "Slasher database pruned";
+            "old_window_start" => old_window_start.as_u64(),
+            "window_start" => window_start.as_u64(),
+        );
+
+        Ok(metadata)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sloggers::{null::NullLoggerBuilder, Build};
+    use tempfile::{tempdir, TempDir};
+    use types::{
+        AggregateSignature, AttestationData, BeaconBlockHeader, Checkpoint, MainnetEthSpec,
+        Signature, Slot, VariableList,
+    };
+
+    type E = MainnetEthSpec;
+
+    fn slasher() -> (Slasher<E>, TempDir) {
+        let dir = tempdir().unwrap();
+        let config = Config::new(dir.path().into());
+        let log = NullLoggerBuilder.build().unwrap();
+        (Slasher::open(config, log).unwrap(), dir)
+    }
+
+    fn attestation(
+        indices: &[u64],
+        source: u64,
+        target: u64,
+        block_root: u64,
+    ) -> IndexedAttestation<E> {
+        IndexedAttestation {
+            attesting_indices: VariableList::new(indices.to_vec()).unwrap(),
+            data: AttestationData {
+                slot: Epoch::new(target).start_slot(E::slots_per_epoch()),
+                index: 0,
+                beacon_block_root: Hash256::from_low_u64_be(block_root),
+                source: Checkpoint {
+                    epoch: Epoch::new(source),
+                    root: Hash256::from_low_u64_be(source),
+                },
+                target: Checkpoint {
+                    epoch: Epoch::new(target),
+                    root: Hash256::from_low_u64_be(target),
+                },
+            },
+            signature: AggregateSignature::new(),
+        }
+    }
+
+    fn block_header(slot: u64, proposer_index: u64, state_root: u64) -> SignedBeaconBlockHeader {
+        SignedBeaconBlockHeader {
+            message: BeaconBlockHeader {
+                slot: Slot::new(slot),
+                proposer_index,
+                parent_root: Hash256::zero(),
+                state_root: Hash256::from_low_u64_be(state_root),
+                body_root: Hash256::zero(),
+            },
+            signature: Signature::empty_signature(),
+        }
+    }
+
+    /// Processes `attestations` in a single batch, returning the resulting slashings.
+    fn process(
+        slasher: &Slasher<E>,
+        attestations: Vec<IndexedAttestation<E>>,
+        current_epoch: u64,
+    ) -> Vec<AttesterSlashing<E>> {
+        for attestation in attestations {
+            slasher.accept_attestation(attestation);
+        }
+        slasher.process_queued(Epoch::new(current_epoch)).unwrap();
+        slasher.get_attester_slashings()
+    }
+
+    #[test]
+    fn no_slashings() {
+        let (slasher, _dir) = slasher();
+        let attestations = (1..10)
+            .map(|epoch| attestation(&[0, 1], epoch - 1, epoch, epoch))
+            .collect::<Vec<_>>();
+
+        assert!(process(&slasher, attestations.clone(), 10).is_empty());
+        // Repeating the same attestations is not slashable.
+        assert!(process(&slasher, attestations, 10).is_empty());
+    }
+
+    #[test]
+    fn attestation_queue_is_bounded() {
+        let (slasher, _dir) = slasher();
+        let att = attestation(&[0], 0, 1, 1);
+
+        for _ in 0..MAX_QUEUED_ATTESTATIONS + 1 {
+            slasher.accept_attestation(att.clone());
+        }
+        assert_eq!(
+            slasher.attestation_queue.lock().len(),
+            MAX_QUEUED_ATTESTATIONS
+        );
+    }
+
+    #[test]
+    fn double_vote() {
+        let (slasher, _dir) = slasher();
+        let att_1 = attestation(&[0, 1], 2, 3, 1);
+        let att_2 = attestation(&[1, 2], 2, 3, 2);
+
+        assert!(process(&slasher, vec![att_1.clone()], 4).is_empty());
+        assert_eq!(
+            process(&slasher, vec![att_2.clone()], 4),
+            vec![AttesterSlashing {
+                attestation_1: att_1,
+                attestation_2: att_2,
+            }]
+        );
+    }
+
+    #[test]
+    fn surrounding_vote() {
+        let (slasher, _dir) = slasher();
+        let surrounded = attestation(&[0], 3, 4, 0);
+        let surrounding = attestation(&[0], 2, 5, 0);
+
+        assert!(process(&slasher, vec![surrounded.clone()], 5).is_empty());
+        assert_eq!(
+            process(&slasher, vec![surrounding.clone()], 5),
+            vec![AttesterSlashing {
+                attestation_1: surrounding,
+                attestation_2: surrounded,
+            }]
+        );
+    }
+
+    #[test]
+    fn surrounded_vote() {
+        let (slasher, _dir) = slasher();
+        let surrounding = attestation(&[0], 1, 8, 0);
+        let surrounded = attestation(&[0], 3, 4, 0);
+
+        // The surrounded vote is detected within the same batch.
+        assert_eq!(
+            process(&slasher, vec![surrounding.clone(), surrounded.clone()], 8),
+            vec![AttesterSlashing {
+                attestation_1: surrounding,
+                attestation_2: surrounded,
+            }]
+        );
+    }
+
+    #[test]
+    fn double_proposal() {
+        let (slasher, _dir) = slasher();
+        let header_1 = block_header(10, 3, 1);
+        let header_2 = block_header(10, 3, 2);
+
+        slasher.accept_block_header(header_1.clone());
+        slasher.accept_block_header(header_1.clone());
+        slasher.accept_block_header(block_header(10, 4, 2));
+        slasher.process_queued(Epoch::new(1)).unwrap();
+        assert!(slasher.get_proposer_slashings().is_empty());
+
+        slasher.accept_block_header(header_2.clone());
+        slasher.process_queued(Epoch::new(1)).unwrap();
+        assert_eq!(
+            slasher.get_proposer_slashings(),
+            vec![ProposerSlashing {
+                signed_header_1: header_1,
+                signed_header_2: header_2,
+            }]
+        );
+    }
+
+    #[test]
+    fn prune_old_attestations() {
+        let dir = tempdir().unwrap();
+        let mut config = Config::new(dir.path().into());
+        config.history_length = 4;
+        let log = NullLoggerBuilder.build().unwrap();
+        let slasher = Slasher::<E>::open(config, log).unwrap();
+
+        assert!(process(&slasher, vec![attestation(&[0], 0, 1, 1)], 1).is_empty());
+        // The first attestation has been pruned and the conflicting one is too old to be checked.
+        assert!(process(&slasher, vec![attestation(&[0], 0, 1, 2)], 8).is_empty());
+        assert!(slasher
+            .db
+            .get_attester_record(0, Epoch::new(1))
+            .unwrap()
+            .is_none());
+    }
+}
@@ -0,0 +1,8 @@
+pub use lighthouse_metrics::*;
+
+lazy_static! {
+    pub static ref SLASHER_DROPPED_ATTESTATIONS: Result<IntCounter> = try_create_int_counter(
+        "slasher_dropped_attestations_total",
+        "Number of attestations dropped because the slasher queue was full"
+    );
+}
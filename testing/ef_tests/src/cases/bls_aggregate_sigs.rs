@@ -1,7 +1,7 @@
 use super::*;
 use crate::case_result::compare_result;
 use crate::cases::common::BlsCase;
-use bls::{AggregateSignature, Signature};
+use bls::{BlsBackend, GenericAggregateSignature, GenericSignature};
 use serde_derive::Deserialize;
 
 #[derive(Debug, Clone, Deserialize)]
@@ -10,16 +10,14 @@ pub struct BlsAggregateSigs {
     pub output: String,
 }
 
-impl BlsCase for BlsAggregateSigs {}
-
-impl Case for BlsAggregateSigs {
-    fn result(&self, _case_index: usize) -> Result<(), Error> {
-        let mut aggregate_signature = AggregateSignature::new();
+impl BlsCase for BlsAggregateSigs {
+    fn run<B: BlsBackend>(&self) -> Result<(), Error> {
+        let mut aggregate_signature = GenericAggregateSignature::<B>::new();
 
         for key_str in &self.input {
             let sig = hex::decode(&key_str[2..])
                 .map_err(|e| Error::FailedToParseTest(format!("{:?}", e)))?;
-            let sig = Signature::from_bytes(&sig)
+            let sig = GenericSignature::<B>::from_bytes(&sig)
                 .map_err(|e| Error::FailedToParseTest(format!("{:?}", e)))?;
 
             aggregate_signature.add(&sig);
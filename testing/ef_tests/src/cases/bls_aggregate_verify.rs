@@ -1,12 +1,12 @@
 use super::*;
 use crate::case_result::compare_result;
 use crate::cases::common::BlsCase;
-use bls::{AggregateSignature, PublicKey};
+use bls::{BlsBackend, GenericAggregateSignature, GenericPublicKey, PublicKeyBytes};
 use serde_derive::Deserialize;
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct BlsAggregatePair {
-    pub pubkey: PublicKey,
+    pub pubkey: PublicKeyBytes,
     pub message: String,
 }
 
@@ -22,10 +22,8 @@ pub struct BlsAggregateVerify {
     pub output: bool,
 }
 
-impl BlsCase for BlsAggregateVerify {}
-
-impl Case for BlsAggregateVerify {
-    fn result(&self, _case_index: usize) -> Result<(), Error> {
+impl BlsCase for BlsAggregateVerify {
+    fn run<B: BlsBackend>(&self) -> Result<(), Error> {
         let messages = self
             .input
             .pairs
@@ -41,17 +39,22 @@ impl Case for BlsAggregateVerify {
             .map(|x| x.as_slice())
             .collect::<Vec<&[u8]>>();
 
-        let pubkey_refs = self
+        let signature_ok = self
             .input
             .pairs
             .iter()
-            .map(|p| &p.pubkey)
-            .collect::<Vec<_>>();
-
-        let signature_ok = hex::decode(&self.input.signature[2..])
-            .ok()
-            .and_then(|bytes: Vec<u8>| AggregateSignature::from_bytes(&bytes).ok())
-            .map(|signature| signature.verify_multiple(&message_refs, &pubkey_refs))
+            .map(|p| GenericPublicKey::<B>::from_bytes(p.pubkey.as_slice()).ok())
+            .collect::<Option<Vec<_>>>()
+            .and_then(|pubkeys| {
+                let pubkey_refs = pubkeys.iter().collect::<Vec<_>>();
+
+                hex::decode(&self.input.signature[2..])
+                    .ok()
+                    .and_then(|bytes: Vec<u8>| {
+                        GenericAggregateSignature::<B>::from_bytes(&bytes).ok()
+                    })
+                    .map(|signature| signature.verify_multiple(&message_refs, &pubkey_refs))
+            })
             .unwrap_or(false);
 
         compare_result::<bool, ()>(&Ok(signature_ok), &Some(self.output))
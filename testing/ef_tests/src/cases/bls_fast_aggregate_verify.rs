@@ -1,9 +1,11 @@
 use super::*;
 use crate::case_result::compare_result;
 use crate::cases::common::BlsCase;
-use bls::{AggregatePublicKey, AggregateSignature, PublicKey, PublicKeyBytes};
+use bls::{
+    BlsBackend, GenericAggregatePublicKey, GenericAggregateSignature, GenericPublicKey,
+    PublicKeyBytes,
+};
 use serde_derive::Deserialize;
-use std::convert::TryInto;
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct BlsFastAggregateVerifyInput {
@@ -18,10 +20,8 @@ pub struct BlsFastAggregateVerify {
     pub output: bool,
 }
 
-impl BlsCase for BlsFastAggregateVerify {}
-
-impl Case for BlsFastAggregateVerify {
-    fn result(&self, _case_index: usize) -> Result<(), Error> {
+impl BlsCase for BlsFastAggregateVerify {
+    fn run<B: BlsBackend>(&self) -> Result<(), Error> {
         let message = hex::decode(&self.input.message[2..])
             .map_err(|e| Error::FailedToParseTest(format!("{:?}", e)))?;
 
@@ -30,9 +30,9 @@ impl Case for BlsFastAggregateVerify {
             .pubkeys
             .iter()
             .try_fold(
-                AggregatePublicKey::new(),
-                |mut agg, pkb| -> Option<AggregatePublicKey> {
-                    let pk: Result<PublicKey, ssz::DecodeError> = pkb.try_into();
+                GenericAggregatePublicKey::<B>::new(),
+                |mut agg, pkb| -> Option<GenericAggregatePublicKey<B>> {
+                    let pk = GenericPublicKey::<B>::from_bytes(pkb.as_slice());
                     agg.add(&pk.ok()?);
                     Some(agg)
                 },
@@ -40,7 +40,9 @@ impl Case for BlsFastAggregateVerify {
             .and_then(|aggregate_pubkey| {
                 hex::decode(&self.input.signature[2..])
                     .ok()
-                    .and_then(|bytes: Vec<u8>| AggregateSignature::from_bytes(&bytes).ok())
+                    .and_then(|bytes: Vec<u8>| {
+                        GenericAggregateSignature::<B>::from_bytes(&bytes).ok()
+                    })
                     .map(|signature| signature.verify(&message, &aggregate_pubkey))
             })
             .unwrap_or(false);
@@ -1,7 +1,7 @@
 use super::*;
 use crate::case_result::compare_result;
 use crate::cases::common::BlsCase;
-use bls::{SecretKey, Signature};
+use bls::{BlsBackend, GenericSecretKey, GenericSignature};
 use serde_derive::Deserialize;
 
 #[derive(Debug, Clone, Deserialize)]
@@ -16,18 +16,16 @@ pub struct BlsSign {
     pub output: String,
 }
 
-impl BlsCase for BlsSign {}
-
-impl Case for BlsSign {
-    fn result(&self, _case_index: usize) -> Result<(), Error> {
+impl BlsCase for BlsSign {
+    fn run<B: BlsBackend>(&self) -> Result<(), Error> {
         // Convert private_key and message to required types
         let sk = hex::decode(&self.input.privkey[2..])
             .map_err(|e| Error::FailedToParseTest(format!("{:?}", e)))?;
-        let sk = SecretKey::from_bytes(&sk).unwrap();
+        let sk = GenericSecretKey::<B>::from_bytes(&sk).unwrap();
         let msg = hex::decode(&self.input.message[2..])
             .map_err(|e| Error::FailedToParseTest(format!("{:?}", e)))?;
 
-        let signature = Signature::new(&msg, &sk);
+        let signature = GenericSignature::<B>::new(&msg, &sk);
 
         // Convert the output to one set of bytes
         let decoded = hex::decode(&self.output[2..])
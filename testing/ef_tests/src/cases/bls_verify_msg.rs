@@ -1,13 +1,12 @@
 use super::*;
 use crate::case_result::compare_result;
 use crate::cases::common::BlsCase;
-use bls::{PublicKey, Signature, SignatureBytes};
+use bls::{BlsBackend, GenericPublicKey, GenericSignature, PublicKeyBytes, SignatureBytes};
 use serde_derive::Deserialize;
-use std::convert::TryInto;
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct BlsVerifyInput {
-    pub pubkey: PublicKey,
+    pub pubkey: PublicKeyBytes,
     pub message: String,
     pub signature: SignatureBytes,
 }
@@ -18,16 +17,16 @@ pub struct BlsVerify {
     pub output: bool,
 }
 
-impl BlsCase for BlsVerify {}
-
-impl Case for BlsVerify {
-    fn result(&self, _case_index: usize) -> Result<(), Error> {
+impl BlsCase for BlsVerify {
+    fn run<B: BlsBackend>(&self) -> Result<(), Error> {
         let message = hex::decode(&self.input.message[2..])
             .map_err(|e| Error::FailedToParseTest(format!("{:?}", e)))?;
 
-        let signature_ok = (&self.input.signature)
-            .try_into()
-            .map(|signature: Signature| signature.verify(&message, &self.input.pubkey))
+        let signature_ok = GenericPublicKey::<B>::from_bytes(self.input.pubkey.as_slice())
+            .and_then(|pubkey| {
+                GenericSignature::<B>::from_bytes(self.input.signature.as_slice())
+                    .map(|signature| signature.verify(&message, &pubkey))
+            })
             .unwrap_or(false);
 
         compare_result::<bool, ()>(&Ok(signature_ok), &Some(self.output))
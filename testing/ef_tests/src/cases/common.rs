@@ -1,6 +1,8 @@
-use crate::cases::LoadCase;
+use crate::cases::{Case, LoadCase};
 use crate::decode::yaml_decode_file;
 use crate::error::Error;
+use bls::impls::milagro::Milagro;
+use bls::BlsBackend;
 use serde_derive::Deserialize;
 use ssz::{Decode, Encode};
 use ssz_derive::{Decode, Encode};
@@ -10,7 +12,12 @@ use std::path::Path;
 use tree_hash::TreeHash;
 
 /// Trait for all BLS cases to eliminate some boilerplate.
-pub trait BlsCase: serde::de::DeserializeOwned {}
+///
+/// Each case is run against every BLS backend compiled into the `bls` crate.
+pub trait BlsCase: serde::de::DeserializeOwned + Debug + Sync {
+    /// Execute the test against a single BLS backend.
+    fn run<B: BlsBackend>(&self) -> Result<(), Error>;
+}
 
 impl<T: BlsCase> LoadCase for T {
     fn load_from_dir(path: &Path) -> Result<Self, Error> {
@@ -18,6 +25,31 @@ impl<T: BlsCase> LoadCase for T {
     }
 }
 
+impl<T: BlsCase> Case for T {
+    fn result(&self, _case_index: usize) -> Result<(), Error> {
+        self.run::<Milagro>()
+            .map_err(|e| with_backend_name("milagro", e))?;
+
+        #[cfg(feature = "supranational")]
+        self.run::<bls::impls::blst::Blst>()
+            .map_err(|e| with_backend_name("blst", e))?;
+
+        Ok(())
+    }
+}
+
+/// Prefix the message in `error` with the name of the BLS backend which produced it.
+fn with_backend_name(backend: &str, error: Error) -> Error {
+    match error {
+        Error::NotEqual(m) => Error::NotEqual(format!("{} backend: {}", backend, m)),
+        Error::DidntFail(m) => Error::DidntFail(format!("{} backend: {}", backend, m)),
+        Error::FailedToParseTest(m) => {
+            Error::FailedToParseTest(format!("{} backend: {}", backend, m))
+        }
+        other => other,
+    }
+}
+
 /// Macro to wrap U128 and U256 so they deserialize correctly.
 macro_rules! uint_wrapper {
     ($wrapper_name:ident, $wrapped_type:ty) => {
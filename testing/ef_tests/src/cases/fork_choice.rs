@@ -0,0 +1,376 @@
+use super::*;
+use crate::decode::{ssz_decode_file, yaml_decode_file};
+use beacon_chain::slot_clock::SlotClock;
+use beacon_chain::test_utils::{BeaconChainHarness, HarnessType};
+use serde_derive::Deserialize;
+use std::time::Duration;
+use types::{Attestation, BeaconState, Checkpoint, EthSpec, Hash256, SignedBeaconBlock, Slot};
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Metadata {
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Head {
+    pub slot: Slot,
+    pub root: Hash256,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Checks {
+    pub head: Option<Head>,
+    pub time: Option<u64>,
+    pub genesis_time: Option<u64>,
+    pub justified_checkpoint: Option<Checkpoint>,
+    pub justified_checkpoint_root: Option<Hash256>,
+    pub finalized_checkpoint: Option<Checkpoint>,
+    pub finalized_checkpoint_root: Option<Hash256>,
+    pub best_justified_checkpoint: Option<Checkpoint>,
+}
+
+/// A single step of a fork choice test.
+///
+/// Blocks and attestations are referred to by the name of their SSZ file when the steps are
+/// loaded from `steps.yaml`, and are replaced by their decoded values afterwards.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum Step<B, A> {
+    Tick { tick: u64 },
+    Block { block: B, valid: Option<bool> },
+    Attestation { attestation: A },
+    Checks { checks: Box<Checks> },
+}
+
+#[derive(Debug)]
+pub struct ForkChoiceTest<E: EthSpec> {
+    pub description: String,
+    pub anchor_state: BeaconState<E>,
+    pub anchor_block: SignedBeaconBlock<E>,
+    pub steps: Vec<Step<SignedBeaconBlock<E>, Attestation<E>>>,
+}
+
+impl<E: EthSpec> LoadCase for ForkChoiceTest<E> {
+    fn load_from_dir(path: &Path) -> Result<Self, Error> {
+        let meta_file = path.join("meta.yaml");
+        let metadata: Metadata = if meta_file.is_file() {
+            yaml_decode_file(&meta_file)?
+        } else {
+            Metadata::default()
+        };
+        let description = metadata
+            .description
+            .unwrap_or_else(|| path.display().to_string());
+
+        let steps: Vec<Step<String, String>> = yaml_decode_file(&path.join("steps.yaml"))?;
+        let steps = steps
+            .into_iter()
+            .map(|step| match step {
+                Step::Tick { tick } => Ok(Step::Tick { tick }),
+                Step::Block { block, valid } => {
+                    ssz_decode_file(&path.join(format!("{}.ssz", block)))
+                        .map(|block| Step::Block { block, valid })
+                }
+                Step::Attestation { attestation } => {
+                    ssz_decode_file(&path.join(format!("{}.ssz", attestation)))
+                        .map(|attestation| Step::Attestation { attestation })
+                }
+                Step::Checks { checks } => Ok(Step::Checks { checks }),
+            })
+            .collect::<Result<_, _>>()?;
+
+        let anchor_state = ssz_decode_file(&path.join("anchor_state.ssz"))?;
+        let anchor_block = ssz_decode_file(&path.join("anchor_block.ssz"))?;
+
+        Ok(Self {
+            description,
+            anchor_state,
+            anchor_block,
+            steps,
+        })
+    }
+}
+
+impl<E: EthSpec> Case for ForkChoiceTest<E> {
+    fn description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn result(&self, _case_index: usize) -> Result<(), Error> {
+        let tester = Tester::new(self.anchor_state.clone(), &self.anchor_block)?;
+
+        for step in &self.steps {
+            match step {
+                Step::Tick { tick } => tester.set_tick(*tick)?,
+                Step::Block { block, valid } => {
+                    tester.process_block(block.clone(), valid.unwrap_or(true))?
+                }
+                Step::Attestation { attestation } => tester.process_attestation(attestation)?,
+                Step::Checks { checks } => tester.check(checks)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Drives a `BeaconChain` through the steps of a fork choice test.
+struct Tester<E: EthSpec> {
+    harness: BeaconChainHarness<HarnessType<E>>,
+}
+
+impl<E: EthSpec> Tester<E> {
+    fn new(
+        anchor_state: BeaconState<E>,
+        anchor_block: &SignedBeaconBlock<E>,
+    ) -> Result<Self, Error> {
+        // The `BeaconChain` derives the genesis block from the genesis state, so only anchors at
+        // genesis can be represented.
+        if anchor_state.slot != E::genesis_epoch().start_slot(E::slots_per_epoch()) {
+            return Err(Error::InternalError(format!(
+                "anchor state at slot {} is not a genesis state",
+                anchor_state.slot
+            )));
+        }
+
+        let harness = BeaconChainHarness::new_from_genesis_state(E::default(), anchor_state);
+
+        let genesis_block_root = harness.chain.genesis_block_root;
+        if genesis_block_root != anchor_block.canonical_root() {
+            return Err(Error::InternalError(format!(
+                "anchor block root {:?} does not match genesis block root {:?}",
+                anchor_block.canonical_root(),
+                genesis_block_root
+            )));
+        }
+
+        Ok(Self { harness })
+    }
+
+    fn set_tick(&self, tick: u64) -> Result<(), Error> {
+        self.harness
+            .chain
+            .slot_clock
+            .set_current_time(Duration::from_secs(tick));
+
+        // Running fork choice allows the justified checkpoint to be updated at the start of an
+        // epoch, as `on_tick` does.
+        self.run_fork_choice()
+    }
+
+    fn process_block(&self, block: SignedBeaconBlock<E>, valid: bool) -> Result<(), Error> {
+        let block_root = block.canonical_root();
+        let result = self.harness.chain.process_block(block);
+
+        match (result, valid) {
+            (Ok(_), true) | (Err(_), false) => Ok(()),
+            (Ok(_), false) => Err(Error::DidntFail(format!(
+                "block {:?} should be invalid",
+                block_root
+            ))),
+            (Err(e), true) => Err(Error::InternalError(format!(
+                "block {:?} failed to import: {:?}",
+                block_root, e
+            ))),
+        }
+    }
+
+    fn process_attestation(&self, attestation: &Attestation<E>) -> Result<(), Error> {
+        self.harness
+            .chain
+            .process_attestation_for_fork_choice(attestation)
+            .map_err(|e| Error::InternalError(format!("attestation failed to apply: {:?}", e)))
+    }
+
+    fn check(&self, checks: &Checks) -> Result<(), Error> {
+        self.run_fork_choice()?;
+
+        let chain = &self.harness.chain;
+        let head = chain
+            .head_info()
+            .map_err(|e| Error::InternalError(format!("failed to read head: {:?}", e)))?;
+        let justified_checkpoint = chain.fork_choice.justified_checkpoint();
+        let finalized_checkpoint = chain.fork_choice.finalized_checkpoint();
+
+        if let Some(expected) = &checks.head {
+            check_equal("head slot", head.slot, expected.slot)?;
+            check_equal("head root", head.block_root, expected.root)?;
+        }
+
+        if let Some(expected) = checks.time {
+            let time = chain.slot_clock.now_duration().map(|now| now.as_secs());
+            check_equal("time", time, Some(expected))?;
+        }
+
+        if let Some(expected) = checks.genesis_time {
+            check_equal("genesis time", head.genesis_time, expected)?;
+        }
+
+        if let Some(expected) = &checks.justified_checkpoint {
+            check_equal("justified checkpoint", &justified_checkpoint, expected)?;
+        }
+
+        if let Some(expected) = checks.justified_checkpoint_root {
+            check_equal(
+                "justified checkpoint root",
+                justified_checkpoint.root,
+                expected,
+            )?;
+        }
+
+        if let Some(expected) = &checks.finalized_checkpoint {
+            check_equal("finalized checkpoint", &finalized_checkpoint, expected)?;
+        }
+
+        if let Some(expected) = checks.finalized_checkpoint_root {
+            check_equal(
+                "finalized checkpoint root",
+                finalized_checkpoint.root,
+                expected,
+            )?;
+        }
+
+        if let Some(expected) = &checks.best_justified_checkpoint {
+            check_equal(
+                "best justified checkpoint",
+                &chain.fork_choice.best_justified_checkpoint(),
+                expected,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    fn run_fork_choice(&self) -> Result<(), Error> {
+        self.harness
+            .chain
+            .fork_choice()
+            .map_err(|e| Error::InternalError(format!("fork choice failed: {:?}", e)))
+    }
+}
+
+fn check_equal<T: Debug + PartialEq>(name: &str, result: T, expected: T) -> Result<(), Error> {
+    if result == expected {
+        Ok(())
+    } else {
+        Err(Error::NotEqual(format!(
+            "{}: got {:?}, expected {:?}",
+            name, result, expected
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use beacon_chain::test_utils::{
+        generate_deterministic_keypairs, AttestationStrategy, BlockStrategy,
+    };
+    use ssz::Encode;
+    use std::fs;
+    use tempfile::tempdir;
+    use types::MinimalEthSpec;
+
+    type E = MinimalEthSpec;
+
+    const VALIDATOR_COUNT: usize = 16;
+    const NUM_BLOCKS: u64 = 3;
+
+    /// Writes a small fork choice test vector, in the layout of the spec tests, for a chain built
+    /// by a `BeaconChainHarness`.
+    ///
+    /// The last block is first offered before its slot (and must be rejected), then again once
+    /// the clock has reached its slot.
+    fn write_vector(path: &Path) {
+        let harness = BeaconChainHarness::new(
+            E::default(),
+            generate_deterministic_keypairs(VALIDATOR_COUNT),
+        );
+        let genesis = harness.chain.head().expect("should get genesis");
+        let genesis_time = genesis.beacon_state.genesis_time;
+        let seconds_per_slot = harness.spec.milliseconds_per_slot / 1000;
+
+        fs::write(
+            path.join("anchor_state.ssz"),
+            genesis.beacon_state.as_ssz_bytes(),
+        )
+        .unwrap();
+        fs::write(
+            path.join("anchor_block.ssz"),
+            genesis.beacon_block.as_ssz_bytes(),
+        )
+        .unwrap();
+
+        let mut roots = vec![];
+        for i in 1..=NUM_BLOCKS {
+            harness.advance_slot();
+            let root = harness.extend_chain(
+                1,
+                BlockStrategy::OnCanonicalHead,
+                AttestationStrategy::AllValidators,
+            );
+            let block = harness
+                .chain
+                .get_block(&root)
+                .expect("should read block")
+                .expect("block should exist");
+            fs::write(path.join(format!("block_{}.ssz", i)), block.as_ssz_bytes()).unwrap();
+            roots.push(root);
+        }
+
+        let time_at = |slot: u64| genesis_time + slot * seconds_per_slot;
+        let steps = format!(
+            "- tick: {early_tick}
+- block: block_1
+- block: block_2
+- block: block_3
+  valid: false
+- checks:
+    head: {{slot: 2, root: '{root_2:?}'}}
+- tick: {tick}
+- block: block_3
+- checks:
+    head: {{slot: 3, root: '{root_3:?}'}}
+    time: {tick}
+    genesis_time: {genesis_time}
+",
+            early_tick = time_at(NUM_BLOCKS - 1),
+            tick = time_at(NUM_BLOCKS),
+            root_2 = roots[1],
+            root_3 = roots[2],
+            genesis_time = genesis_time,
+        );
+        fs::write(path.join("steps.yaml"), steps).unwrap();
+    }
+
+    #[test]
+    fn generated_vector() {
+        let dir = tempdir().unwrap();
+        write_vector(dir.path());
+
+        let case = ForkChoiceTest::<E>::load_from_dir(dir.path()).expect("vector should load");
+        assert_eq!(case.steps.len(), 8);
+        case.result(0).expect("vector should pass");
+    }
+
+    #[test]
+    fn generated_vector_wrong_head() {
+        let dir = tempdir().unwrap();
+        write_vector(dir.path());
+
+        let mut case = ForkChoiceTest::<E>::load_from_dir(dir.path()).expect("vector should load");
+        let last_check = case.steps.last_mut().unwrap();
+        match last_check {
+            Step::Checks { checks } => {
+                checks.head.as_mut().unwrap().root = Hash256::repeat_byte(42);
+            }
+            _ => panic!("the last step should be a check"),
+        }
+
+        match case.result(0) {
+            Err(Error::NotEqual(_)) => (),
+            other => panic!("expected a head mismatch, got {:?}", other),
+        }
+    }
+}
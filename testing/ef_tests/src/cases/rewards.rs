@@ -0,0 +1,122 @@
+use super::*;
+use crate::case_result::compare_result_detailed;
+use crate::decode::{ssz_decode_file, yaml_decode_file};
+use compare_fields_derive::CompareFields;
+use serde_derive::Deserialize;
+use ssz_derive::{Decode, Encode};
+use state_processing::per_epoch_processing::{
+    apply_rewards::{get_attestation_deltas, AttestationDelta, Delta},
+    errors::EpochProcessingError,
+    validator_statuses::ValidatorStatuses,
+};
+use std::path::{Path, PathBuf};
+use types::{BeaconState, EthSpec};
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Metadata {
+    pub description: Option<String>,
+}
+
+/// The per-validator rewards and penalties for a single component, as encoded in the test files.
+#[derive(Debug, Clone, PartialEq, Decode, Encode, CompareFields)]
+pub struct Deltas {
+    #[compare_fields(as_slice)]
+    rewards: Vec<u64>,
+    #[compare_fields(as_slice)]
+    penalties: Vec<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, CompareFields)]
+pub struct AllDeltas {
+    source_deltas: Deltas,
+    target_deltas: Deltas,
+    head_deltas: Deltas,
+    inclusion_delay_deltas: Deltas,
+    inactivity_penalty_deltas: Deltas,
+}
+
+#[derive(Debug, Clone)]
+pub struct RewardsTest<E: EthSpec> {
+    pub path: PathBuf,
+    pub metadata: Metadata,
+    pub pre: BeaconState<E>,
+    pub deltas: AllDeltas,
+}
+
+impl<E: EthSpec> LoadCase for RewardsTest<E> {
+    fn load_from_dir(path: &Path) -> Result<Self, Error> {
+        let metadata_path = path.join("meta.yaml");
+        let metadata: Metadata = if metadata_path.is_file() {
+            yaml_decode_file(&metadata_path)?
+        } else {
+            Metadata::default()
+        };
+        let pre = ssz_decode_file(&path.join("pre.ssz"))?;
+        let source_deltas = ssz_decode_file(&path.join("source_deltas.ssz"))?;
+        let target_deltas = ssz_decode_file(&path.join("target_deltas.ssz"))?;
+        let head_deltas = ssz_decode_file(&path.join("head_deltas.ssz"))?;
+        let inclusion_delay_deltas = ssz_decode_file(&path.join("inclusion_delay_deltas.ssz"))?;
+        let inactivity_penalty_deltas =
+            ssz_decode_file(&path.join("inactivity_penalty_deltas.ssz"))?;
+
+        let deltas = AllDeltas {
+            source_deltas,
+            target_deltas,
+            head_deltas,
+            inclusion_delay_deltas,
+            inactivity_penalty_deltas,
+        };
+
+        Ok(Self {
+            path: path.into(),
+            metadata,
+            pre,
+            deltas,
+        })
+    }
+}
+
+impl<E: EthSpec> Case for RewardsTest<E> {
+    fn description(&self) -> String {
+        self.metadata
+            .description
+            .clone()
+            .unwrap_or_else(String::new)
+    }
+
+    fn result(&self, _case_index: usize) -> Result<(), Error> {
+        let mut state = self.pre.clone();
+        let spec = &E::default_spec();
+
+        let deltas: Result<AllDeltas, EpochProcessingError> = (|| {
+            // Processing requires the committee caches.
+            state.build_all_committee_caches(spec)?;
+
+            let mut validator_statuses = ValidatorStatuses::new(&state, spec)?;
+            validator_statuses.process_attestations(&state, spec)?;
+
+            let deltas = get_attestation_deltas(&state, &validator_statuses, spec)?;
+
+            Ok(convert_all_deltas(&deltas))
+        })();
+
+        compare_result_detailed(&deltas, &Some(self.deltas.clone()))
+    }
+}
+
+fn convert_all_deltas(deltas: &[AttestationDelta]) -> AllDeltas {
+    AllDeltas {
+        source_deltas: convert_deltas(deltas.iter().map(|d| &d.source_delta)),
+        target_deltas: convert_deltas(deltas.iter().map(|d| &d.target_delta)),
+        head_deltas: convert_deltas(deltas.iter().map(|d| &d.head_delta)),
+        inclusion_delay_deltas: convert_deltas(deltas.iter().map(|d| &d.inclusion_delay_delta)),
+        inactivity_penalty_deltas: convert_deltas(
+            deltas.iter().map(|d| &d.inactivity_penalty_delta),
+        ),
+    }
+}
+
+fn convert_deltas<'a>(deltas: impl Iterator<Item = &'a Delta>) -> Deltas {
+    let (rewards, penalties) = deltas.map(|delta| (delta.rewards, delta.penalties)).unzip();
+    Deltas { rewards, penalties }
+}
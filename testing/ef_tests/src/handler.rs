@@ -189,6 +189,25 @@ impl<E: EthSpec + TypeName> Handler for SanitySlotsHandler<E> {
     }
 }
 
+pub struct FinalityHandler<E>(PhantomData<E>);
+
+impl<E: EthSpec + TypeName> Handler for FinalityHandler<E> {
+    // Reuse the blocks case runner.
+    type Case = cases::SanityBlocks<E>;
+
+    fn config_name() -> &'static str {
+        E::name()
+    }
+
+    fn runner_name() -> &'static str {
+        "finality"
+    }
+
+    fn handler_name() -> String {
+        "finality".into()
+    }
+}
+
 pub struct EpochProcessingHandler<E, T>(PhantomData<(E, T)>);
 
 impl<E: EthSpec + TypeName, T: EpochTransition<E>> Handler for EpochProcessingHandler<E, T> {
@@ -207,6 +226,24 @@ impl<E: EthSpec + TypeName, T: EpochTransition<E>> Handler for EpochProcessingHa
     }
 }
 
+pub struct RewardsHandler<E>(PhantomData<E>);
+
+impl<E: EthSpec + TypeName> Handler for RewardsHandler<E> {
+    type Case = cases::RewardsTest<E>;
+
+    fn config_name() -> &'static str {
+        E::name()
+    }
+
+    fn runner_name() -> &'static str {
+        "rewards"
+    }
+
+    fn handler_name() -> String {
+        "core".into()
+    }
+}
+
 pub struct GenesisValidityHandler<E>(PhantomData<E>);
 
 impl<E: EthSpec + TypeName> Handler for GenesisValidityHandler<E> {
@@ -261,6 +298,24 @@ impl<E: EthSpec + TypeName, O: Operation<E>> Handler for OperationsHandler<E, O>
     }
 }
 
+pub struct ForkChoiceHandler<E, H>(PhantomData<(E, H)>);
+
+impl<E: EthSpec + TypeName, H: TypeName> Handler for ForkChoiceHandler<E, H> {
+    type Case = cases::ForkChoiceTest<E>;
+
+    fn config_name() -> &'static str {
+        E::name()
+    }
+
+    fn runner_name() -> &'static str {
+        "fork_choice"
+    }
+
+    fn handler_name() -> String {
+        H::name().into()
+    }
+}
+
 pub struct SszGenericHandler<H>(PhantomData<H>);
 
 impl<H: TypeName> Handler for SszGenericHandler<H> {
@@ -292,3 +347,9 @@ pub struct Uints;
 type_name!(Uints, "uints");
 pub struct Containers;
 type_name!(Containers, "containers");
+
+// Supported fork choice handlers
+pub struct GetHead;
+type_name!(GetHead, "get_head");
+pub struct OnBlock;
+type_name!(OnBlock, "on_block");
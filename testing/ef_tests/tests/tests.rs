@@ -83,31 +83,26 @@ fn sanity_slots() {
 }
 
 #[test]
-#[cfg(not(feature = "fake_crypto"))]
 fn bls_aggregate() {
     BlsAggregateSigsHandler::run();
 }
 
 #[test]
-#[cfg(not(feature = "fake_crypto"))]
 fn bls_sign() {
     BlsSignMsgHandler::run();
 }
 
 #[test]
-#[cfg(not(feature = "fake_crypto"))]
 fn bls_verify() {
     BlsVerifyMsgHandler::run();
 }
 
 #[test]
-#[cfg(not(feature = "fake_crypto"))]
 fn bls_aggregate_verify() {
     BlsAggregateVerifyHandler::run();
 }
 
 #[test]
-#[cfg(not(feature = "fake_crypto"))]
 fn bls_fast_aggregate_verify() {
     BlsFastAggregateVerifyHandler::run();
 }
@@ -218,6 +213,23 @@ fn epoch_processing_final_updates() {
     EpochProcessingHandler::<MainnetEthSpec, FinalUpdates>::run();
 }
 
+// The finality and rewards vectors are not included in the spec tests at the pinned `TESTS_TAG`.
+// Run these with `--ignored` against a release that provides them. Until then, the attestation
+// deltas are checked by a harness test in `beacon_chain`.
+#[test]
+#[ignore]
+fn finality() {
+    FinalityHandler::<MinimalEthSpec>::run();
+    FinalityHandler::<MainnetEthSpec>::run();
+}
+
+#[test]
+#[ignore]
+fn rewards() {
+    RewardsHandler::<MinimalEthSpec>::run();
+    RewardsHandler::<MainnetEthSpec>::run();
+}
+
 #[test]
 fn genesis_initialization() {
     GenesisInitializationHandler::<MinimalEthSpec>::run();
@@ -228,3 +240,20 @@ fn genesis_validity() {
     GenesisValidityHandler::<MinimalEthSpec>::run();
     // Note: there are no genesis validity tests for mainnet
 }
+
+// The fork choice vectors are not included in the spec tests at the pinned `TESTS_TAG`. Run these
+// with `--ignored` against a release that provides them. Until then, the runner is exercised by a
+// vector generated in `cases::fork_choice::tests`.
+#[test]
+#[ignore]
+fn fork_choice_get_head() {
+    ForkChoiceHandler::<MinimalEthSpec, GetHead>::run();
+    ForkChoiceHandler::<MainnetEthSpec, GetHead>::run();
+}
+
+#[test]
+#[ignore]
+fn fork_choice_on_block() {
+    ForkChoiceHandler::<MinimalEthSpec, OnBlock>::run();
+    ForkChoiceHandler::<MainnetEthSpec, OnBlock>::run();
+}
@@ -387,3 +387,87 @@ fn invalid_prev_surrounds_new_multiple() {
     }
     .run()
 }
+
+#[test]
+fn invalid_target_below_lower_bound() {
+    StreamTest {
+        cases: vec![
+            Test::single(attestation_data_builder(2, 3)),
+            Test::single(attestation_data_builder(3, 4)),
+            Test::single(attestation_data_builder(2, 2)).expect_invalid_att(
+                InvalidAttestation::TargetLessThanOrEqLowerBound {
+                    target_epoch: Epoch::new(2),
+                    bound_epoch: Epoch::new(3),
+                },
+            ),
+        ],
+        ..StreamTest::default()
+    }
+    .run()
+}
+
+#[test]
+fn prune_attestations() {
+    let dir = tempfile::tempdir().unwrap();
+    let slashing_db_file = dir.path().join("slashing_protection.sqlite");
+    let slashing_db = SlashingDatabase::create(&slashing_db_file).unwrap();
+
+    let pk = pubkey(0);
+    slashing_db.register_validator(&pk).unwrap();
+
+    for (source, target) in &[(0, 1), (1, 2), (2, 3), (3, 5)] {
+        slashing_db
+            .check_and_insert_attestation(
+                &pk,
+                &attestation_data_builder(*source, *target),
+                DEFAULT_DOMAIN,
+            )
+            .unwrap();
+    }
+
+    slashing_db
+        .prune_all_signed_attestations(std::iter::once(&pk), Epoch::new(3))
+        .unwrap();
+
+    // Attestations with target epoch 3 and below are now refused by the watermark, rather than
+    // being checked against the pruned history.
+    assert_eq!(
+        slashing_db.check_and_insert_attestation(
+            &pk,
+            &attestation_data_builder(2, 2),
+            DEFAULT_DOMAIN
+        ),
+        Err(NotSafe::InvalidAttestation(
+            InvalidAttestation::TargetLessThanOrEqLowerBound {
+                target_epoch: Epoch::new(2),
+                bound_epoch: Epoch::new(3),
+            }
+        ))
+    );
+
+    // Pruning beyond the most recent attestation retains it.
+    slashing_db
+        .prune_all_signed_attestations(std::iter::once(&pk), Epoch::new(100))
+        .unwrap();
+    assert_eq!(
+        slashing_db.check_and_insert_attestation(
+            &pk,
+            &attestation_data_builder(3, 5),
+            DEFAULT_DOMAIN
+        ),
+        Ok(Safe::SameData)
+    );
+    assert_eq!(
+        slashing_db.check_and_insert_attestation(
+            &pk,
+            &attestation_data_builder(3, 4),
+            DEFAULT_DOMAIN
+        ),
+        Err(NotSafe::InvalidAttestation(
+            InvalidAttestation::TargetLessThanOrEqLowerBound {
+                target_epoch: Epoch::new(4),
+                bound_epoch: Epoch::new(5),
+            }
+        ))
+    );
+}
@@ -122,3 +122,98 @@ fn invalid_unregistered_validator() {
     }
     .run()
 }
+
+#[test]
+fn invalid_slot_below_lower_bound() {
+    StreamTest {
+        cases: vec![
+            Test::single(block(5)),
+            Test::single(block(3)).expect_invalid_block(InvalidBlock::SlotViolatesLowerBound {
+                block_slot: Slot::new(3),
+                bound_slot: Slot::new(5),
+            }),
+        ],
+        ..StreamTest::default()
+    }
+    .run()
+}
+
+#[test]
+fn prune_blocks() {
+    let dir = tempfile::tempdir().unwrap();
+    let slashing_db_file = dir.path().join("slashing_protection.sqlite");
+    let slashing_db = SlashingDatabase::create(&slashing_db_file).unwrap();
+
+    let pk = pubkey(0);
+    slashing_db.register_validator(&pk).unwrap();
+
+    for slot in 1..=5 {
+        slashing_db
+            .check_and_insert_block_proposal(&pk, &block(slot), DEFAULT_DOMAIN)
+            .unwrap();
+    }
+
+    slashing_db
+        .prune_all_signed_blocks(std::iter::once(&pk), Slot::new(4))
+        .unwrap();
+    assert_eq!(
+        slashing_db.check_and_insert_block_proposal(&pk, &block(2), DEFAULT_DOMAIN),
+        Err(NotSafe::InvalidBlock(
+            InvalidBlock::SlotViolatesLowerBound {
+                block_slot: Slot::new(2),
+                bound_slot: Slot::new(4),
+            }
+        ))
+    );
+
+    // Pruning beyond the most recent block retains it.
+    slashing_db
+        .prune_all_signed_blocks(std::iter::once(&pk), Slot::new(100))
+        .unwrap();
+    assert_eq!(
+        slashing_db.check_and_insert_block_proposal(&pk, &block(4), DEFAULT_DOMAIN),
+        Err(NotSafe::InvalidBlock(
+            InvalidBlock::SlotViolatesLowerBound {
+                block_slot: Slot::new(4),
+                bound_slot: Slot::new(5),
+            }
+        ))
+    );
+    assert_eq!(
+        slashing_db.check_and_insert_block_proposal(&pk, &block(6), DEFAULT_DOMAIN),
+        Ok(Safe::Valid)
+    );
+}
+
+#[test]
+fn prune_blocks_skips_unregistered() {
+    let dir = tempfile::tempdir().unwrap();
+    let slashing_db_file = dir.path().join("slashing_protection.sqlite");
+    let slashing_db = SlashingDatabase::create(&slashing_db_file).unwrap();
+
+    let pk = pubkey(0);
+    let unregistered = pubkey(1);
+    slashing_db.register_validator(&pk).unwrap();
+
+    for slot in 1..=5 {
+        slashing_db
+            .check_and_insert_block_proposal(&pk, &block(slot), DEFAULT_DOMAIN)
+            .unwrap();
+    }
+
+    assert_eq!(
+        slashing_db
+            .prune_all_signed_blocks(vec![&unregistered, &pk].into_iter(), Slot::new(4))
+            .unwrap(),
+        vec![unregistered]
+    );
+    assert_eq!(
+        slashing_db.check_and_insert_block_proposal(&pk, &block(2), DEFAULT_DOMAIN),
+        Err(NotSafe::InvalidBlock(
+            InvalidBlock::SlotViolatesLowerBound {
+                block_slot: Slot::new(2),
+                bound_slot: Slot::new(4),
+            }
+        ))
+    );
+}
@@ -0,0 +1,148 @@
+//! Types for the slashing protection interchange format described in EIP-3076.
+//!
+//! The interchange format allows slashing protection history to be moved between machines and
+//! between clients. Two variants exist:
+//!
+//! - `complete`: every signed block and attestation is listed for each validator.
+//! - `minimal`: only the most recent block slot and attestation epochs are listed.
+//!
+//! As required by EIP-3076, integers are written as quoted decimal strings. Unquoted integers are
+//! also accepted on import.
+use serde_derive::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::io;
+use types::utils::{quoted_u64, quoted_u64_opt};
+use types::{Epoch, Hash256, PublicKey, Slot};
+
+/// The version of the interchange format produced and accepted by this implementation.
+pub const INTERCHANGE_FORMAT_VERSION: u64 = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum InterchangeFormat {
+    Complete,
+    Minimal,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct InterchangeMetadata {
+    pub interchange_format: InterchangeFormat,
+    #[serde(with = "quoted_u64")]
+    pub interchange_format_version: u64,
+    pub genesis_validators_root: Hash256,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CompleteInterchangeData {
+    pub pubkey: PublicKey,
+    pub signed_blocks: Vec<SignedBlock>,
+    pub signed_attestations: Vec<SignedAttestation>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MinimalInterchangeData {
+    pub pubkey: PublicKey,
+    #[serde(default, with = "quoted_u64_opt")]
+    pub last_signed_block_slot: Option<Slot>,
+    #[serde(default, with = "quoted_u64_opt")]
+    pub last_signed_attestation_source_epoch: Option<Epoch>,
+    #[serde(default, with = "quoted_u64_opt")]
+    pub last_signed_attestation_target_epoch: Option<Epoch>,
+}
+
+/// A block signed by a validator, as represented in the interchange format.
+///
+/// The `signing_root` is optional because some clients do not track it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SignedBlock {
+    #[serde(with = "quoted_u64")]
+    pub slot: Slot,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signing_root: Option<Hash256>,
+}
+
+/// An attestation signed by a validator, as represented in the interchange format.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SignedAttestation {
+    #[serde(with = "quoted_u64")]
+    pub source_epoch: Epoch,
+    #[serde(with = "quoted_u64")]
+    pub target_epoch: Epoch,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signing_root: Option<Hash256>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum InterchangeData {
+    Complete(Vec<CompleteInterchangeData>),
+    Minimal(Vec<MinimalInterchangeData>),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Interchange {
+    pub metadata: InterchangeMetadata,
+    pub data: InterchangeData,
+}
+
+impl Interchange {
+    pub fn from_json_str(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    pub fn from_json_reader(reader: impl io::Read) -> Result<Self, serde_json::Error> {
+        serde_json::from_reader(reader)
+    }
+
+    pub fn write_to(&self, writer: impl io::Write) -> Result<(), serde_json::Error> {
+        serde_json::to_writer(writer, self)
+    }
+
+    /// Check that the `data` matches the format declared in the `metadata`, and that no
+    /// validator appears more than once.
+    pub fn validate(&self) -> Result<(), String> {
+        let pubkeys: Vec<&PublicKey> = match (&self.metadata.interchange_format, &self.data) {
+            (InterchangeFormat::Complete, InterchangeData::Complete(data)) => {
+                data.iter().map(|d| &d.pubkey).collect()
+            }
+            (InterchangeFormat::Minimal, InterchangeData::Minimal(data)) => {
+                data.iter().map(|d| &d.pubkey).collect()
+            }
+            // An empty data list always deserializes as `Complete`, which is fine for either.
+            (InterchangeFormat::Minimal, InterchangeData::Complete(data)) if data.is_empty() => {
+                vec![]
+            }
+            (format, _) => {
+                return Err(format!(
+                    "interchange data does not match declared format {:?}",
+                    format
+                ))
+            }
+        };
+
+        if self.metadata.interchange_format_version != INTERCHANGE_FORMAT_VERSION {
+            return Err(format!(
+                "unsupported interchange format version {}, expected {}",
+                self.metadata.interchange_format_version, INTERCHANGE_FORMAT_VERSION
+            ));
+        }
+
+        let mut seen = HashSet::new();
+        for pubkey in pubkeys {
+            if !seen.insert(pubkey.as_hex_string()) {
+                return Err(format!(
+                    "duplicate entry for validator {}",
+                    pubkey.as_hex_string()
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,301 @@
+#![cfg(test)]
+
+use crate::attestation_tests::attestation_data_builder;
+use crate::block_tests::block;
+use crate::interchange::{
+    CompleteInterchangeData, Interchange, InterchangeData, InterchangeFormat, InterchangeMetadata,
+    MinimalInterchangeData, SignedAttestation, SignedBlock, INTERCHANGE_FORMAT_VERSION,
+};
+use crate::test_utils::{pubkey, DEFAULT_DOMAIN};
+use crate::*;
+use tempfile::{tempdir, TempDir};
+use types::{Epoch, Hash256, Slot};
+
+fn genesis_validators_root() -> Hash256 {
+    Hash256::repeat_byte(0xab)
+}
+
+fn new_db() -> (TempDir, SlashingDatabase) {
+    let dir = tempdir().unwrap();
+    let db = SlashingDatabase::create(&dir.path().join("slashing_protection.sqlite")).unwrap();
+    (dir, db)
+}
+
+fn minimal_interchange(data: Vec<MinimalInterchangeData>) -> Interchange {
+    Interchange {
+        metadata: InterchangeMetadata {
+            interchange_format: InterchangeFormat::Minimal,
+            interchange_format_version: INTERCHANGE_FORMAT_VERSION,
+            genesis_validators_root: genesis_validators_root(),
+        },
+        data: InterchangeData::Minimal(data),
+    }
+}
+
+#[test]
+fn complete_export_import_round_trip() {
+    let (_dir, db) = new_db();
+    db.register_validator(&pubkey(0)).unwrap();
+    db.register_validator(&pubkey(1)).unwrap();
+
+    for slot in &[1, 5, 9] {
+        db.check_and_insert_block_proposal(&pubkey(0), &block(*slot), DEFAULT_DOMAIN)
+            .unwrap();
+    }
+    for (source, target) in &[(0, 1), (1, 2), (2, 4)] {
+        db.check_and_insert_attestation(
+            &pubkey(1),
+            &attestation_data_builder(*source, *target),
+            DEFAULT_DOMAIN,
+        )
+        .unwrap();
+    }
+
+    let exported = db
+        .export_interchange_info(genesis_validators_root(), InterchangeFormat::Complete)
+        .unwrap();
+
+    // Round-trip through JSON.
+    let mut json = vec![];
+    exported.write_to(&mut json).unwrap();
+    let parsed = Interchange::from_json_reader(&json[..]).unwrap();
+    assert_eq!(parsed, exported);
+
+    let (_dir2, db2) = new_db();
+    db2.import_interchange_info(&parsed, genesis_validators_root())
+        .unwrap();
+    let reexported = db2
+        .export_interchange_info(genesis_validators_root(), InterchangeFormat::Complete)
+        .unwrap();
+    assert_eq!(reexported, exported);
+
+    // The imported history must be enforced.
+    assert!(matches!(
+        db2.check_and_insert_block_proposal(&pubkey(0), &block(5), DEFAULT_DOMAIN),
+        Err(NotSafe::InvalidBlock(InvalidBlock::DoubleBlockProposal(_)))
+    ));
+    assert!(matches!(
+        db2.check_and_insert_attestation(
+            &pubkey(1),
+            &attestation_data_builder(0, 5),
+            DEFAULT_DOMAIN
+        ),
+        Err(NotSafe::InvalidAttestation(
+            InvalidAttestation::NewSurroundsPrev { .. }
+        ))
+    ));
+}
+
+#[test]
+fn minimal_export() {
+    let (_dir, db) = new_db();
+    db.register_validator(&pubkey(0)).unwrap();
+    db.register_validator(&pubkey(1)).unwrap();
+
+    for slot in &[3, 7, 4] {
+        db.check_and_insert_block_proposal(&pubkey(0), &block(*slot), DEFAULT_DOMAIN)
+            .unwrap();
+    }
+    db.check_and_insert_attestation(&pubkey(0), &attestation_data_builder(2, 3), DEFAULT_DOMAIN)
+        .unwrap();
+
+    let exported = db
+        .export_interchange_info(genesis_validators_root(), InterchangeFormat::Minimal)
+        .unwrap();
+
+    assert_eq!(
+        exported,
+        minimal_interchange(vec![
+            MinimalInterchangeData {
+                pubkey: pubkey(0),
+                last_signed_block_slot: Some(Slot::new(7)),
+                last_signed_attestation_source_epoch: Some(Epoch::new(2)),
+                last_signed_attestation_target_epoch: Some(Epoch::new(3)),
+            },
+            MinimalInterchangeData {
+                pubkey: pubkey(1),
+                last_signed_block_slot: None,
+                last_signed_attestation_source_epoch: None,
+                last_signed_attestation_target_epoch: None,
+            },
+        ])
+    );
+}
+
+#[test]
+fn minimal_import_takes_maximum() {
+    let (_dir, db) = new_db();
+    db.register_validator(&pubkey(0)).unwrap();
+    db.check_and_insert_block_proposal(&pubkey(0), &block(10), DEFAULT_DOMAIN)
+        .unwrap();
+    db.check_and_insert_attestation(&pubkey(0), &attestation_data_builder(4, 5), DEFAULT_DOMAIN)
+        .unwrap();
+
+    let interchange = minimal_interchange(vec![MinimalInterchangeData {
+        pubkey: pubkey(0),
+        last_signed_block_slot: Some(Slot::new(8)),
+        last_signed_attestation_source_epoch: Some(Epoch::new(6)),
+        last_signed_attestation_target_epoch: Some(Epoch::new(7)),
+    }]);
+    db.import_interchange_info(&interchange, genesis_validators_root())
+        .unwrap();
+
+    let exported = db
+        .export_interchange_info(genesis_validators_root(), InterchangeFormat::Minimal)
+        .unwrap();
+    assert_eq!(
+        exported,
+        minimal_interchange(vec![MinimalInterchangeData {
+            pubkey: pubkey(0),
+            last_signed_block_slot: Some(Slot::new(10)),
+            last_signed_attestation_source_epoch: Some(Epoch::new(6)),
+            last_signed_attestation_target_epoch: Some(Epoch::new(7)),
+        }])
+    );
+
+    // Imported records have no signing root, so they can never be re-signed.
+    assert!(db
+        .check_and_insert_attestation(&pubkey(0), &attestation_data_builder(6, 7), DEFAULT_DOMAIN)
+        .is_err());
+}
+
+#[test]
+fn import_registers_unknown_validators() {
+    let (_dir, db) = new_db();
+    let interchange = minimal_interchange(vec![MinimalInterchangeData {
+        pubkey: pubkey(3),
+        last_signed_block_slot: Some(Slot::new(1)),
+        last_signed_attestation_source_epoch: None,
+        last_signed_attestation_target_epoch: None,
+    }]);
+    db.import_interchange_info(&interchange, genesis_validators_root())
+        .unwrap();
+
+    assert_eq!(
+        db.check_and_insert_block_proposal(&pubkey(3), &block(2), DEFAULT_DOMAIN),
+        Ok(Safe::Valid)
+    );
+}
+
+#[test]
+fn import_wrong_genesis_validators_root() {
+    let (_dir, db) = new_db();
+    let interchange = minimal_interchange(vec![]);
+    assert!(matches!(
+        db.import_interchange_info(&interchange, Hash256::zero()),
+        Err(InterchangeError::GenesisValidatorsMismatch { .. })
+    ));
+}
+
+#[test]
+fn import_mismatched_format() {
+    let (_dir, db) = new_db();
+    let mut interchange = minimal_interchange(vec![]);
+    interchange.data = InterchangeData::Complete(vec![CompleteInterchangeData {
+        pubkey: pubkey(0),
+        signed_blocks: vec![],
+        signed_attestations: vec![],
+    }]);
+    assert!(matches!(
+        db.import_interchange_info(&interchange, genesis_validators_root()),
+        Err(InterchangeError::InvalidInterchange(_))
+    ));
+}
+
+#[test]
+fn parse_complete_json() {
+    let json = format!(
+        r#"{{
+            "metadata": {{
+                "interchange_format": "complete",
+                "interchange_format_version": "{}",
+                "genesis_validators_root": "0x04700007fabc8282644aed6d1c7c9e21d38a03a0c4ba193f3afe428824b3a673"
+            }},
+            "data": [
+                {{
+                    "pubkey": "{}",
+                    "signed_blocks": [
+                        {{ "slot": "81952", "signing_root": "0x4ff6f743a43f3b4f95350831aeaf0a122a1a392922c45d804280284a69eb850b" }},
+                        {{ "slot": "81951" }}
+                    ],
+                    "signed_attestations": [
+                        {{ "source_epoch": "2290", "target_epoch": "3007" }}
+                    ]
+                }}
+            ]
+        }}"#,
+        INTERCHANGE_FORMAT_VERSION,
+        pubkey(0).as_hex_string()
+    );
+    let interchange = Interchange::from_json_str(&json).unwrap();
+    interchange.validate().unwrap();
+    assert_eq!(
+        interchange.metadata.interchange_format,
+        InterchangeFormat::Complete
+    );
+    match &interchange.data {
+        InterchangeData::Complete(data) => {
+            assert_eq!(data[0].signed_blocks[0].slot, Slot::new(81952));
+            assert_eq!(
+                data[0].signed_attestations[0].target_epoch,
+                Epoch::new(3007)
+            );
+        }
+        other => panic!("expected complete data, got {:?}", other),
+    }
+}
+
+/// EIP-3076 requires integers to be quoted strings.
+#[test]
+fn integers_are_quoted() {
+    let interchange = Interchange {
+        metadata: InterchangeMetadata {
+            interchange_format: InterchangeFormat::Complete,
+            interchange_format_version: INTERCHANGE_FORMAT_VERSION,
+            genesis_validators_root: genesis_validators_root(),
+        },
+        data: InterchangeData::Complete(vec![CompleteInterchangeData {
+            pubkey: pubkey(0),
+            signed_blocks: vec![SignedBlock {
+                slot: Slot::new(81952),
+                signing_root: None,
+            }],
+            signed_attestations: vec![SignedAttestation {
+                source_epoch: Epoch::new(2290),
+                target_epoch: Epoch::new(3007),
+                signing_root: None,
+            }],
+        }]),
+    };
+
+    let mut json = vec![];
+    interchange.write_to(&mut json).unwrap();
+    let json = String::from_utf8(json).unwrap();
+
+    assert!(json.contains(&format!(
+        r#""interchange_format_version":"{}""#,
+        INTERCHANGE_FORMAT_VERSION
+    )));
+    assert!(json.contains(r#"{"slot":"81952"}"#));
+    assert!(json.contains(r#"{"source_epoch":"2290","target_epoch":"3007"}"#));
+    assert_eq!(Interchange::from_json_str(&json).unwrap(), interchange);
+}
+
+#[test]
+fn minimal_integers_are_quoted() {
+    let interchange = minimal_interchange(vec![MinimalInterchangeData {
+        pubkey: pubkey(0),
+        last_signed_block_slot: Some(Slot::new(7)),
+        last_signed_attestation_source_epoch: Some(Epoch::new(2)),
+        last_signed_attestation_target_epoch: None,
+    }]);
+
+    let mut json = vec![];
+    interchange.write_to(&mut json).unwrap();
+    let json = String::from_utf8(json).unwrap();
+
+    assert!(json.contains(r#""last_signed_block_slot":"7""#));
+    assert!(json.contains(r#""last_signed_attestation_source_epoch":"2""#));
+    assert!(json.contains(r#""last_signed_attestation_target_epoch":null"#));
+    assert_eq!(Interchange::from_json_str(&json).unwrap(), interchange);
+}
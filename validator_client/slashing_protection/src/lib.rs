@@ -1,5 +1,7 @@
 mod attestation_tests;
 mod block_tests;
+pub mod interchange;
+mod interchange_tests;
 mod parallel_tests;
 mod signed_attestation;
 mod signed_block;
@@ -8,12 +10,15 @@ mod test_utils;
 
 pub use crate::signed_attestation::{InvalidAttestation, SignedAttestation};
 pub use crate::signed_block::{InvalidBlock, SignedBlock};
-pub use crate::slashing_database::SlashingDatabase;
+pub use crate::slashing_database::{InterchangeError, SlashingDatabase};
 use rusqlite::Error as SQLError;
 use std::io::{Error as IOError, ErrorKind};
 use std::string::ToString;
 use types::{Hash256, PublicKey};
 
+/// The filename within the validator directory that contains the slashing protection DB.
+pub const SLASHING_PROTECTION_FILENAME: &str = "slashing_protection.sqlite";
+
 /// The attestation or block is not safe to sign.
 ///
 /// This could be because it's slashable, or because an error occurred.
@@ -20,6 +20,17 @@ pub enum InvalidAttestation {
     PrevSurroundsNew { prev: SignedAttestation },
     /// The attestation is invalid because its source epoch is greater than its target epoch.
     SourceExceedsTarget,
+    /// The attestation's source epoch is less than the validator's low watermark source epoch.
+    SourceLessThanLowerBound {
+        source_epoch: Epoch,
+        bound_epoch: Epoch,
+    },
+    /// The attestation's target epoch is less than or equal to the validator's low watermark
+    /// target epoch.
+    TargetLessThanOrEqLowerBound {
+        target_epoch: Epoch,
+        bound_epoch: Epoch,
+    },
 }
 
 impl SignedAttestation {
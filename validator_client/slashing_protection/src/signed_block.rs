@@ -12,6 +12,11 @@ pub struct SignedBlock {
 #[derive(PartialEq, Debug)]
 pub enum InvalidBlock {
     DoubleBlockProposal(SignedBlock),
+    /// The block's slot is less than or equal to the validator's low watermark.
+    SlotViolatesLowerBound {
+        block_slot: Slot,
+        bound_slot: Slot,
+    },
 }
 
 impl SignedBlock {
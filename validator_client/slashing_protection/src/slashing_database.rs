@@ -1,12 +1,16 @@
+use crate::interchange::{
+    CompleteInterchangeData, Interchange, InterchangeData, InterchangeFormat, InterchangeMetadata,
+    MinimalInterchangeData, INTERCHANGE_FORMAT_VERSION,
+};
 use crate::signed_attestation::InvalidAttestation;
 use crate::signed_block::InvalidBlock;
-use crate::{NotSafe, Safe, SignedAttestation, SignedBlock};
+use crate::{hash256_from_row, NotSafe, Safe, SignedAttestation, SignedBlock};
 use r2d2_sqlite::SqliteConnectionManager;
 use rusqlite::{params, OptionalExtension, Transaction, TransactionBehavior};
 use std::fs::{File, OpenOptions};
 use std::path::Path;
 use std::time::Duration;
-use types::{AttestationData, BeaconBlockHeader, Hash256, PublicKey, SignedRoot};
+use types::{AttestationData, BeaconBlockHeader, Epoch, Hash256, PublicKey, SignedRoot, Slot};
 
 type Pool = r2d2::Pool<SqliteConnectionManager>;
 
@@ -195,16 +199,30 @@ impl SlashingDatabase {
         if let Some(existing_block) = existing_block {
             if existing_block.signing_root == block_header.signing_root(domain) {
                 // Same slot and same hash -> we're re-broadcasting a previously signed block
-                Ok(Safe::SameData)
+                return Ok(Safe::SameData);
             } else {
                 // Same epoch but not the same hash -> it's a DoubleBlockProposal
-                Err(NotSafe::InvalidBlock(InvalidBlock::DoubleBlockProposal(
+                return Err(NotSafe::InvalidBlock(InvalidBlock::DoubleBlockProposal(
                     existing_block,
-                )))
+                )));
             }
-        } else {
-            Ok(Safe::Valid)
         }
+
+        // Refuse any block at or below the low watermark. Blocks below the watermark may have
+        // been pruned or never recorded (e.g. after a minimal interchange import).
+        let min_slot = Self::min_block_slot(txn, validator_id)?;
+        if let Some(bound_slot) = min_slot {
+            if block_header.slot <= bound_slot {
+                return Err(NotSafe::InvalidBlock(
+                    InvalidBlock::SlotViolatesLowerBound {
+                        block_slot: block_header.slot,
+                        bound_slot,
+                    },
+                ));
+            }
+        }
+
+        Ok(Safe::Valid)
     }
 
     /// Check an attestation from `validator_pubkey` for slash safety.
@@ -299,10 +317,61 @@ impl SlashingDatabase {
             ));
         }
 
+        // 4. Check that the attestation is not at or below the low watermark. History below the
+        //    watermark may have been pruned, so these attestations can't be checked for safety.
+        let (min_source, min_target) = Self::min_attestation_epochs(txn, validator_id)?;
+
+        if let Some(bound_epoch) = min_source {
+            if att_source_epoch < bound_epoch {
+                return Err(NotSafe::InvalidAttestation(
+                    InvalidAttestation::SourceLessThanLowerBound {
+                        source_epoch: att_source_epoch,
+                        bound_epoch,
+                    },
+                ));
+            }
+        }
+
+        if let Some(bound_epoch) = min_target {
+            if att_target_epoch <= bound_epoch {
+                return Err(NotSafe::InvalidAttestation(
+                    InvalidAttestation::TargetLessThanOrEqLowerBound {
+                        target_epoch: att_target_epoch,
+                        bound_epoch,
+                    },
+                ));
+            }
+        }
+
         // Everything has been checked, return Valid
         Ok(Safe::Valid)
     }
 
+    /// The low watermark for block proposals: the minimum slot of all blocks signed by
+    /// the validator, or `None` if no blocks have been signed.
+    fn min_block_slot(txn: &Transaction, validator_id: i64) -> Result<Option<Slot>, NotSafe> {
+        Ok(txn.query_row(
+            "SELECT MIN(slot) FROM signed_blocks WHERE validator_id = ?1",
+            params![validator_id],
+            |row| row.get(0),
+        )?)
+    }
+
+    /// The low watermarks for attestations: the minimum source and target epochs of all
+    /// attestations signed by the validator, or `None` if no attestations have been signed.
+    fn min_attestation_epochs(
+        txn: &Transaction,
+        validator_id: i64,
+    ) -> Result<(Option<Epoch>, Option<Epoch>), NotSafe> {
+        Ok(txn.query_row(
+            "SELECT MIN(source_epoch), MIN(target_epoch)
+             FROM signed_attestations
+             WHERE validator_id = ?1",
+            params![validator_id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?)
+    }
+
     /// Insert a block proposal into the slashing database.
     ///
     /// This should *only* be called in the same (exclusive) transaction as `check_block_proposal`
@@ -403,6 +472,469 @@ impl SlashingDatabase {
         txn.commit()?;
         Ok(safe)
     }
+
+    /// Remove all blocks for `public_keys` with slots less than `new_min_slot`.
+    ///
+    /// The most recent block for each validator is always retained, so that the low watermark
+    /// never decreases as a result of pruning.
+    ///
+    /// Keys which are not registered in the database are skipped, rather than failing the
+    /// pruning of every other validator, and are returned to the caller.
+    pub fn prune_all_signed_blocks<'a>(
+        &self,
+        public_keys: impl Iterator<Item = &'a PublicKey>,
+        new_min_slot: Slot,
+    ) -> Result<Vec<PublicKey>, NotSafe> {
+        let mut conn = self.conn_pool.get()?;
+        let txn = conn.transaction_with_behavior(TransactionBehavior::Exclusive)?;
+
+        let mut unregistered = vec![];
+        for pubkey in public_keys {
+            let validator_id = match Self::get_validator_id(&txn, pubkey) {
+                Ok(validator_id) => validator_id,
+                Err(NotSafe::UnregisteredValidator(_)) => {
+                    unregistered.push(pubkey.clone());
+                    continue;
+                }
+                Err(e) => return Err(e),
+            };
+            Self::prune_signed_blocks(&txn, validator_id, new_min_slot)?;
+        }
+
+        txn.commit()?;
+        Ok(unregistered)
+    }
+
+    /// Remove all attestations for `public_keys` with target epochs less than `new_min_target`.
+    ///
+    /// The attestation with the highest target epoch for each validator is always retained.
+    ///
+    /// Unregistered keys are skipped and returned, as for `prune_all_signed_blocks`.
+    pub fn prune_all_signed_attestations<'a>(
+        &self,
+        public_keys: impl Iterator<Item = &'a PublicKey>,
+        new_min_target: Epoch,
+    ) -> Result<Vec<PublicKey>, NotSafe> {
+        let mut conn = self.conn_pool.get()?;
+        let txn = conn.transaction_with_behavior(TransactionBehavior::Exclusive)?;
+
+        let mut unregistered = vec![];
+        for pubkey in public_keys {
+            let validator_id = match Self::get_validator_id(&txn, pubkey) {
+                Ok(validator_id) => validator_id,
+                Err(NotSafe::UnregisteredValidator(_)) => {
+                    unregistered.push(pubkey.clone());
+                    continue;
+                }
+                Err(e) => return Err(e),
+            };
+            Self::prune_signed_attestations(&txn, validator_id, new_min_target)?;
+        }
+
+        txn.commit()?;
+        Ok(unregistered)
+    }
+
+    fn prune_signed_blocks(
+        txn: &Transaction,
+        validator_id: i64,
+        new_min_slot: Slot,
+    ) -> Result<(), NotSafe> {
+        txn.execute(
+            "DELETE FROM signed_blocks
+             WHERE
+                validator_id = ?1 AND
+                slot < ?2 AND
+                slot < (SELECT MAX(slot) FROM signed_blocks WHERE validator_id = ?1)",
+            params![validator_id, new_min_slot],
+        )?;
+        Ok(())
+    }
+
+    fn prune_signed_attestations(
+        txn: &Transaction,
+        validator_id: i64,
+        new_min_target: Epoch,
+    ) -> Result<(), NotSafe> {
+        txn.execute(
+            "DELETE FROM signed_attestations
+             WHERE
+                validator_id = ?1 AND
+                target_epoch < ?2 AND
+                target_epoch < (SELECT MAX(target_epoch)
+                                FROM signed_attestations
+                                WHERE validator_id = ?1)",
+            params![validator_id, new_min_target],
+        )?;
+        Ok(())
+    }
+
+    /// Import slashing protection history from an EIP-3076 interchange.
+    ///
+    /// Validators that are not yet registered are registered. The imported history is merged
+    /// with any existing history so that the database only ever becomes more restrictive:
+    ///
+    /// - For the complete format, every block and attestation is inserted, except where a record
+    ///   for the same slot (or target epoch) already exists, in which case the existing record
+    ///   is kept.
+    /// - For the minimal format, a single block and attestation is recorded for each validator,
+    ///   at the maximum of the existing and imported slot, source epoch and target epoch. Any
+    ///   existing history below the imported values is pruned, raising the low watermark.
+    ///
+    /// Records without a signing root are stored with a zero signing root, which means that
+    /// they can never be re-signed.
+    pub fn import_interchange_info(
+        &self,
+        interchange: &Interchange,
+        genesis_validators_root: Hash256,
+    ) -> Result<(), InterchangeError> {
+        interchange
+            .validate()
+            .map_err(InterchangeError::InvalidInterchange)?;
+
+        if interchange.metadata.genesis_validators_root != genesis_validators_root {
+            return Err(InterchangeError::GenesisValidatorsMismatch {
+                client: genesis_validators_root,
+                interchange_file: interchange.metadata.genesis_validators_root,
+            });
+        }
+
+        let mut conn = self.conn_pool.get()?;
+        let txn = conn.transaction_with_behavior(TransactionBehavior::Exclusive)?;
+
+        match &interchange.data {
+            InterchangeData::Complete(records) => {
+                for record in records {
+                    Self::import_complete_record(&txn, record)?;
+                }
+            }
+            InterchangeData::Minimal(records) => {
+                for record in records {
+                    Self::import_minimal_record(&txn, record)?;
+                }
+            }
+        }
+
+        txn.commit()?;
+        Ok(())
+    }
+
+    fn import_complete_record(
+        txn: &Transaction,
+        record: &CompleteInterchangeData,
+    ) -> Result<(), InterchangeError> {
+        let validator_id = Self::get_or_insert_validator_id(txn, &record.pubkey)?;
+
+        for block in &record.signed_blocks {
+            Self::insert_imported_block(
+                txn,
+                validator_id,
+                block.slot,
+                block.signing_root.unwrap_or_else(Hash256::zero),
+            )?;
+        }
+
+        for attestation in &record.signed_attestations {
+            if attestation.source_epoch > attestation.target_epoch {
+                return Err(InterchangeError::InvalidInterchange(format!(
+                    "attestation for {} has source epoch {} exceeding target epoch {}",
+                    record.pubkey.as_hex_string(),
+                    attestation.source_epoch,
+                    attestation.target_epoch
+                )));
+            }
+            Self::insert_imported_attestation(
+                txn,
+                validator_id,
+                attestation.source_epoch,
+                attestation.target_epoch,
+                attestation.signing_root.unwrap_or_else(Hash256::zero),
+            )?;
+        }
+
+        Ok(())
+    }
+
+    fn import_minimal_record(
+        txn: &Transaction,
+        record: &MinimalInterchangeData,
+    ) -> Result<(), InterchangeError> {
+        let validator_id = Self::get_or_insert_validator_id(txn, &record.pubkey)?;
+
+        if let Some(imported_slot) = record.last_signed_block_slot {
+            let max_slot: Option<Slot> = txn.query_row(
+                "SELECT MAX(slot) FROM signed_blocks WHERE validator_id = ?1",
+                params![validator_id],
+                |row| row.get(0),
+            )?;
+            let slot = max_slot.map_or(imported_slot, |max| std::cmp::max(max, imported_slot));
+            Self::insert_imported_block(txn, validator_id, slot, Hash256::zero())?;
+            // Raise the low watermark to the imported slot, since we don't know which slots
+            // below it were signed.
+            Self::prune_signed_blocks(txn, validator_id, imported_slot)?;
+        }
+
+        match (
+            record.last_signed_attestation_source_epoch,
+            record.last_signed_attestation_target_epoch,
+        ) {
+            (Some(imported_source), Some(imported_target)) => {
+                if imported_source > imported_target {
+                    return Err(InterchangeError::InvalidInterchange(format!(
+                        "minimal record for {} has source epoch {} exceeding target epoch {}",
+                        record.pubkey.as_hex_string(),
+                        imported_source,
+                        imported_target
+                    )));
+                }
+
+                let (max_source, max_target): (Option<Epoch>, Option<Epoch>) = txn.query_row(
+                    "SELECT MAX(source_epoch), MAX(target_epoch)
+                     FROM signed_attestations
+                     WHERE validator_id = ?1",
+                    params![validator_id],
+                    |row| Ok((row.get(0)?, row.get(1)?)),
+                )?;
+                let source =
+                    max_source.map_or(imported_source, |max| std::cmp::max(max, imported_source));
+                let target =
+                    max_target.map_or(imported_target, |max| std::cmp::max(max, imported_target));
+                Self::insert_imported_attestation(
+                    txn,
+                    validator_id,
+                    source,
+                    target,
+                    Hash256::zero(),
+                )?;
+                // Raise the low watermark to the imported target epoch.
+                Self::prune_signed_attestations(txn, validator_id, imported_target)?;
+            }
+            (None, None) => (),
+            _ => {
+                return Err(InterchangeError::InvalidInterchange(format!(
+                    "minimal record for {} must specify both or neither attestation epochs",
+                    record.pubkey.as_hex_string()
+                )))
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Insert a block from an interchange file, keeping any existing block at the same slot.
+    fn insert_imported_block(
+        txn: &Transaction,
+        validator_id: i64,
+        slot: Slot,
+        signing_root: Hash256,
+    ) -> Result<(), InterchangeError> {
+        txn.execute(
+            "INSERT OR IGNORE INTO signed_blocks (validator_id, slot, signing_root)
+             VALUES (?1, ?2, ?3)",
+            params![validator_id, slot, signing_root.as_bytes()],
+        )?;
+        Ok(())
+    }
+
+    /// Insert an attestation from an interchange file, keeping any existing attestation with the
+    /// same target epoch.
+    fn insert_imported_attestation(
+        txn: &Transaction,
+        validator_id: i64,
+        source_epoch: Epoch,
+        target_epoch: Epoch,
+        signing_root: Hash256,
+    ) -> Result<(), InterchangeError> {
+        txn.execute(
+            "INSERT OR IGNORE INTO signed_attestations
+                (validator_id, source_epoch, target_epoch, signing_root)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![
+                validator_id,
+                source_epoch,
+                target_epoch,
+                signing_root.as_bytes()
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Get the database-internal ID for a validator, registering it if necessary.
+    fn get_or_insert_validator_id(
+        txn: &Transaction,
+        public_key: &PublicKey,
+    ) -> Result<i64, NotSafe> {
+        match Self::get_validator_id(txn, public_key) {
+            Err(NotSafe::UnregisteredValidator(_)) => {
+                txn.execute(
+                    "INSERT INTO validators (public_key) VALUES (?1)",
+                    params![&public_key.as_hex_string()],
+                )?;
+                Ok(txn.last_insert_rowid())
+            }
+            result => result,
+        }
+    }
+
+    /// Export the slashing protection history of all registered validators in the given
+    /// interchange `format`.
+    pub fn export_interchange_info(
+        &self,
+        genesis_validators_root: Hash256,
+        format: InterchangeFormat,
+    ) -> Result<Interchange, InterchangeError> {
+        let mut conn = self.conn_pool.get()?;
+        let txn = conn.transaction()?;
+
+        let validators = txn
+            .prepare("SELECT id, public_key FROM validators ORDER BY id ASC")?
+            .query_and_then(params![], |row| {
+                let id: i64 = row.get(0)?;
+                let pubkey_str: String = row.get(1)?;
+                let pubkey = hex::decode(pubkey_str.trim_start_matches("0x"))
+                    .map_err(|e| format!("{:?}", e))
+                    .and_then(|bytes| PublicKey::from_bytes(&bytes).map_err(|e| format!("{:?}", e)))
+                    .map_err(|e| {
+                        InterchangeError::InvalidPubkey(format!("{}: {}", pubkey_str, e))
+                    })?;
+                Ok((id, pubkey))
+            })?
+            .collect::<Result<Vec<_>, InterchangeError>>()?;
+
+        let data = match format {
+            InterchangeFormat::Complete => InterchangeData::Complete(
+                validators
+                    .into_iter()
+                    .map(|(id, pubkey)| Self::export_complete_record(&txn, id, pubkey))
+                    .collect::<Result<_, _>>()?,
+            ),
+            InterchangeFormat::Minimal => InterchangeData::Minimal(
+                validators
+                    .into_iter()
+                    .map(|(id, pubkey)| Self::export_minimal_record(&txn, id, pubkey))
+                    .collect::<Result<_, _>>()?,
+            ),
+        };
+
+        Ok(Interchange {
+            metadata: InterchangeMetadata {
+                interchange_format: format,
+                interchange_format_version: INTERCHANGE_FORMAT_VERSION,
+                genesis_validators_root,
+            },
+            data,
+        })
+    }
+
+    fn export_complete_record(
+        txn: &Transaction,
+        validator_id: i64,
+        pubkey: PublicKey,
+    ) -> Result<CompleteInterchangeData, InterchangeError> {
+        let signed_blocks = txn
+            .prepare(
+                "SELECT slot, signing_root
+                 FROM signed_blocks
+                 WHERE validator_id = ?1
+                 ORDER BY slot ASC",
+            )?
+            .query_and_then(params![validator_id], |row| {
+                Ok(crate::interchange::SignedBlock {
+                    slot: row.get(0)?,
+                    signing_root: non_zero_root(hash256_from_row(1, row)?),
+                })
+            })?
+            .collect::<Result<_, InterchangeError>>()?;
+
+        let signed_attestations = txn
+            .prepare(
+                "SELECT source_epoch, target_epoch, signing_root
+                 FROM signed_attestations
+                 WHERE validator_id = ?1
+                 ORDER BY target_epoch ASC",
+            )?
+            .query_and_then(params![validator_id], |row| {
+                Ok(crate::interchange::SignedAttestation {
+                    source_epoch: row.get(0)?,
+                    target_epoch: row.get(1)?,
+                    signing_root: non_zero_root(hash256_from_row(2, row)?),
+                })
+            })?
+            .collect::<Result<_, InterchangeError>>()?;
+
+        Ok(CompleteInterchangeData {
+            pubkey,
+            signed_blocks,
+            signed_attestations,
+        })
+    }
+
+    fn export_minimal_record(
+        txn: &Transaction,
+        validator_id: i64,
+        pubkey: PublicKey,
+    ) -> Result<MinimalInterchangeData, InterchangeError> {
+        let last_signed_block_slot = txn.query_row(
+            "SELECT MAX(slot) FROM signed_blocks WHERE validator_id = ?1",
+            params![validator_id],
+            |row| row.get(0),
+        )?;
+        let (last_signed_attestation_source_epoch, last_signed_attestation_target_epoch) = txn
+            .query_row(
+                "SELECT MAX(source_epoch), MAX(target_epoch)
+                 FROM signed_attestations
+                 WHERE validator_id = ?1",
+                params![validator_id],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )?;
+
+        Ok(MinimalInterchangeData {
+            pubkey,
+            last_signed_block_slot,
+            last_signed_attestation_source_epoch,
+            last_signed_attestation_target_epoch,
+        })
+    }
+}
+
+/// Records imported without a signing root are stored with a zero root, convert them back.
+fn non_zero_root(root: Hash256) -> Option<Hash256> {
+    if root.is_zero() {
+        None
+    } else {
+        Some(root)
+    }
+}
+
+/// Errors that can occur while importing or exporting slashing protection interchange data.
+#[derive(Debug)]
+pub enum InterchangeError {
+    InvalidInterchange(String),
+    GenesisValidatorsMismatch {
+        interchange_file: Hash256,
+        client: Hash256,
+    },
+    InvalidPubkey(String),
+    NotSafe(NotSafe),
+    SQLError(String),
+}
+
+impl From<NotSafe> for InterchangeError {
+    fn from(error: NotSafe) -> Self {
+        InterchangeError::NotSafe(error)
+    }
+}
+
+impl From<rusqlite::Error> for InterchangeError {
+    fn from(error: rusqlite::Error) -> Self {
+        InterchangeError::SQLError(error.to_string())
+    }
+}
+
+impl From<r2d2::Error> for InterchangeError {
+    fn from(error: r2d2::Error) -> Self {
+        InterchangeError::NotSafe(error.into())
+    }
 }
 
 #[cfg(test)]
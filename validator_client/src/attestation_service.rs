@@ -1,24 +1,27 @@
 use crate::{
+    beacon_node_fallback::{BeaconNodeFallback, RequireSynced},
     duties_service::{DutiesService, DutyAndProof},
-    validator_store::ValidatorStore,
+    validator_store::{publish_outcome, ValidatorStore},
 };
 use environment::RuntimeContext;
-use futures::StreamExt;
-use remote_beacon_node::{PublishStatus, RemoteBeaconNode};
+use futures::{future::join_all, StreamExt};
+use remote_beacon_node::PublishStatus;
+use remote_validator_client::types::{PublishKind, PublishResult};
 use slog::{crit, debug, info, trace};
 use slot_clock::SlotClock;
 use std::collections::HashMap;
+use std::fmt::Debug;
 use std::ops::Deref;
 use std::sync::Arc;
 use tokio::time::{delay_until, interval_at, Duration, Instant};
-use types::{Attestation, ChainSpec, CommitteeIndex, EthSpec, Slot};
+use types::{Attestation, ChainSpec, CommitteeIndex, EthSpec, PublicKey, Slot};
 
 /// Builds an `AttestationService`.
 pub struct AttestationServiceBuilder<T, E: EthSpec> {
     duties_service: Option<DutiesService<T, E>>,
     validator_store: Option<ValidatorStore<T, E>>,
     slot_clock: Option<T>,
-    beacon_node: Option<RemoteBeaconNode<E>>,
+    beacon_nodes: Option<Arc<BeaconNodeFallback<T, E>>>,
     context: Option<RuntimeContext<E>>,
 }
 
@@ -28,7 +31,7 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationServiceBuilder<T, E> {
             duties_service: None,
             validator_store: None,
             slot_clock: None,
-            beacon_node: None,
+            beacon_nodes: None,
             context: None,
         }
     }
@@ -48,8 +51,8 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationServiceBuilder<T, E> {
         self
     }
 
-    pub fn beacon_node(mut self, beacon_node: RemoteBeaconNode<E>) -> Self {
-        self.beacon_node = Some(beacon_node);
+    pub fn beacon_nodes(mut self, beacon_nodes: Arc<BeaconNodeFallback<T, E>>) -> Self {
+        self.beacon_nodes = Some(beacon_nodes);
         self
     }
 
@@ -70,9 +73,9 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationServiceBuilder<T, E> {
                 slot_clock: self
                     .slot_clock
                     .ok_or_else(|| "Cannot build AttestationService without slot_clock")?,
-                beacon_node: self
-                    .beacon_node
-                    .ok_or_else(|| "Cannot build AttestationService without beacon_node")?,
+                beacon_nodes: self
+                    .beacon_nodes
+                    .ok_or_else(|| "Cannot build AttestationService without beacon_nodes")?,
                 context: self
                     .context
                     .ok_or_else(|| "Cannot build AttestationService without runtime_context")?,
@@ -86,7 +89,7 @@ pub struct Inner<T, E: EthSpec> {
     duties_service: DutiesService<T, E>,
     validator_store: ValidatorStore<T, E>,
     slot_clock: T,
-    beacon_node: RemoteBeaconNode<E>,
+    beacon_nodes: Arc<BeaconNodeFallback<T, E>>,
     context: RuntimeContext<E>,
 }
 
@@ -172,6 +175,20 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
             .slot_clock
             .now()
             .ok_or_else(|| "Failed to read slot clock".to_string())?;
+
+        // Prune the slashing protection database once per epoch, off the async runtime.
+        if slot % E::slots_per_epoch() == 0 {
+            let service = self.clone();
+            let current_epoch = slot.epoch(E::slots_per_epoch());
+            self.inner.context.executor.spawn_blocking(
+                move || {
+                    service
+                        .validator_store
+                        .prune_slashing_protection_db(current_epoch)
+                },
+                "slashing_protection_pruning",
+            );
+        }
         let duration_to_next_slot = self
             .slot_clock
             .duration_to_next_slot()
@@ -319,10 +336,14 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
             .epoch(E::slots_per_epoch());
 
         let attestation = self
-            .beacon_node
-            .http
-            .validator()
-            .produce_attestation(slot, committee_index)
+            .beacon_nodes
+            .first_success(RequireSynced::No, |beacon_node| async move {
+                beacon_node
+                    .http
+                    .validator()
+                    .produce_attestation(slot, committee_index)
+                    .await
+            })
             .await
             .map_err(|e| format!("Failed to produce attestation: {:?}", e))?;
 
@@ -330,50 +351,56 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
         // their signature.
         //
         // If any validator is unable to sign, they are simply skipped.
-        let signed_attestations = validator_duties
-            .iter()
-            .filter_map(|duty| {
-                // Ensure that all required fields are present in the validator duty.
-                let (duty_slot, duty_committee_index, validator_committee_position, _) =
-                    if let Some(tuple) = duty.attestation_duties() {
-                        tuple
-                    } else {
-                        crit!(
-                            log,
-                            "Missing validator duties when signing";
-                            "duties" => format!("{:?}", duty)
-                        );
-                        return None;
-                    };
-
-                // Ensure that the attestation matches the duties.
-                if duty_slot != attestation.data.slot
-                    || duty_committee_index != attestation.data.index
-                {
+        let attestation = &attestation;
+        let signing_futures = validator_duties.iter().map(|duty| async move {
+            // Ensure that all required fields are present in the validator duty.
+            let (duty_slot, duty_committee_index, validator_committee_position, _) =
+                if let Some(tuple) = duty.attestation_duties() {
+                    tuple
+                } else {
                     crit!(
                         log,
-                        "Inconsistent validator duties during signing";
-                        "validator" => format!("{:?}", duty.validator_pubkey()),
-                        "duty_slot" => duty_slot,
-                        "attestation_slot" => attestation.data.slot,
-                        "duty_index" => duty_committee_index,
-                        "attestation_index" => attestation.data.index,
+                        "Missing validator duties when signing";
+                        "duties" => format!("{:?}", duty)
                     );
                     return None;
-                }
+                };
 
-                let mut attestation = attestation.clone();
+            // Ensure that the attestation matches the duties.
+            if duty_slot != attestation.data.slot || duty_committee_index != attestation.data.index
+            {
+                crit!(
+                    log,
+                    "Inconsistent validator duties during signing";
+                    "validator" => format!("{:?}", duty.validator_pubkey()),
+                    "duty_slot" => duty_slot,
+                    "attestation_slot" => attestation.data.slot,
+                    "duty_index" => duty_committee_index,
+                    "attestation_index" => attestation.data.index,
+                );
+                return None;
+            }
 
-                self.validator_store
-                    .sign_attestation(
-                        duty.validator_pubkey(),
-                        validator_committee_position,
-                        &mut attestation,
-                        current_epoch,
-                    )
-                    .map(|_| attestation)
-            })
-            .collect::<Vec<_>>();
+            let mut attestation = attestation.clone();
+
+            self.validator_store
+                .sign_attestation(
+                    duty.validator_pubkey(),
+                    validator_committee_position,
+                    &mut attestation,
+                    current_epoch,
+                )
+                .await
+                .map(|_| (duty.validator_pubkey().clone(), attestation))
+        });
+
+        // Sign concurrently, so that the latency of a remote signer is not paid once per
+        // validator.
+        let (signers, signed_attestations): (Vec<_>, Vec<_>) = join_all(signing_futures)
+            .await
+            .into_iter()
+            .flatten()
+            .unzip();
 
         // If there are any signed attestations, publish them to the BN. Otherwise,
         // just return early.
@@ -381,11 +408,23 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
             let num_attestations = signed_attestations.len();
             let beacon_block_root = attestation.data.beacon_block_root;
 
-            self.beacon_node
-                .http
-                .validator()
-                .publish_attestations(signed_attestations)
-                .await
+            let publish_result = self
+                .beacon_nodes
+                .broadcast(RequireSynced::No, |beacon_node| {
+                    let signed_attestations = signed_attestations.clone();
+                    async move {
+                        beacon_node
+                            .http
+                            .validator()
+                            .publish_attestations(signed_attestations)
+                            .await
+                    }
+                })
+                .await;
+
+            self.record_publish_results(&signers, PublishKind::Attestation, slot, &publish_result);
+
+            publish_result
                 .map_err(|e| format!("Failed to publish attestation: {:?}", e))
                 .map(move |publish_status| match publish_status {
                     PublishStatus::Valid => info!(
@@ -442,65 +481,93 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
     ) -> Result<(), String> {
         let log = self.context.log();
 
+        let attestation_data = &attestation.data;
         let aggregated_attestation = self
-            .beacon_node
-            .http
-            .validator()
-            .produce_aggregate_attestation(&attestation.data)
+            .beacon_nodes
+            .first_success(RequireSynced::No, |beacon_node| async move {
+                beacon_node
+                    .http
+                    .validator()
+                    .produce_aggregate_attestation(attestation_data)
+                    .await
+            })
             .await
             .map_err(|e| format!("Failed to produce an aggregate attestation: {:?}", e))?;
 
         // For each validator, clone the `aggregated_attestation` and convert it into
         // a `SignedAggregateAndProof`
-        let signed_aggregate_and_proofs = validator_duties
-            .iter()
-            .filter_map(|duty_and_proof| {
-                // Do not produce a signed aggregator for validators that are not
-                // subscribed aggregators.
-                let selection_proof = duty_and_proof.selection_proof.as_ref()?.clone();
-
-                let (duty_slot, duty_committee_index, _, validator_index) =
-                    duty_and_proof.attestation_duties().or_else(|| {
-                        crit!(log, "Missing duties when signing aggregate");
-                        None
-                    })?;
-
-                let pubkey = &duty_and_proof.duty.validator_pubkey;
-                let slot = attestation.data.slot;
-                let committee_index = attestation.data.index;
-
-                if duty_slot != slot || duty_committee_index != committee_index {
-                    crit!(log, "Inconsistent validator duties during signing");
-                    return None;
-                }
-
-                if let Some(signed_aggregate_and_proof) =
-                    self.validator_store.produce_signed_aggregate_and_proof(
-                        pubkey,
-                        validator_index,
-                        aggregated_attestation.clone(),
-                        selection_proof,
-                    )
-                {
-                    Some(signed_aggregate_and_proof)
-                } else {
-                    crit!(log, "Failed to sign attestation");
+        let attestation = &attestation;
+        let aggregated_attestation = &aggregated_attestation;
+        let signing_futures = validator_duties.iter().map(|duty_and_proof| async move {
+            // Do not produce a signed aggregator for validators that are not
+            // subscribed aggregators.
+            let selection_proof = duty_and_proof.selection_proof.as_ref()?.clone();
+
+            let (duty_slot, duty_committee_index, _, validator_index) =
+                duty_and_proof.attestation_duties().or_else(|| {
+                    crit!(log, "Missing duties when signing aggregate");
                     None
-                }
-            })
-            .collect::<Vec<_>>();
+                })?;
+
+            let pubkey = &duty_and_proof.duty.validator_pubkey;
+            let slot = attestation.data.slot;
+            let committee_index = attestation.data.index;
+
+            if duty_slot != slot || duty_committee_index != committee_index {
+                crit!(log, "Inconsistent validator duties during signing");
+                return None;
+            }
+
+            if let Some(signed_aggregate_and_proof) = self
+                .validator_store
+                .produce_signed_aggregate_and_proof(
+                    pubkey,
+                    validator_index,
+                    aggregated_attestation.clone(),
+                    selection_proof,
+                )
+                .await
+            {
+                Some((pubkey.clone(), signed_aggregate_and_proof))
+            } else {
+                crit!(log, "Failed to sign attestation");
+                None
+            }
+        });
+
+        let (signers, signed_aggregate_and_proofs): (Vec<_>, Vec<_>) = join_all(signing_futures)
+            .await
+            .into_iter()
+            .flatten()
+            .unzip();
 
         // If there any signed aggregates and proofs were produced, publish them to the
         // BN.
         if let Some(first) = signed_aggregate_and_proofs.first().cloned() {
             let attestation = first.message.aggregate;
 
-            let publish_status = self
-                .beacon_node
-                .http
-                .validator()
-                .publish_aggregate_and_proof(signed_aggregate_and_proofs)
-                .await
+            let publish_result = self
+                .beacon_nodes
+                .broadcast(RequireSynced::No, |beacon_node| {
+                    let signed_aggregate_and_proofs = signed_aggregate_and_proofs.clone();
+                    async move {
+                        beacon_node
+                            .http
+                            .validator()
+                            .publish_aggregate_and_proof(signed_aggregate_and_proofs)
+                            .await
+                    }
+                })
+                .await;
+
+            self.record_publish_results(
+                &signers,
+                PublishKind::AggregateAndProof,
+                attestation.data.slot,
+                &publish_result,
+            );
+
+            let publish_status = publish_result
                 .map_err(|e| format!("Failed to publish aggregate and proofs: {:?}", e))?;
             match publish_status {
                 PublishStatus::Valid => info!(
@@ -535,6 +602,27 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
             Ok(())
         }
     }
+
+    /// Records the result of publishing a message signed by each of the `signers`.
+    fn record_publish_results<Err: Debug>(
+        &self,
+        signers: &[PublicKey],
+        kind: PublishKind,
+        slot: Slot,
+        result: &Result<PublishStatus, Err>,
+    ) {
+        let outcome = publish_outcome(result);
+        for pubkey in signers {
+            self.validator_store.record_publish_result(
+                pubkey,
+                PublishResult {
+                    kind,
+                    slot,
+                    outcome: outcome.clone(),
+                },
+            );
+        }
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,417 @@
+//! Allows the validator client to connect to several beacon nodes, falling back from one to the
+//! next when a node is offline or unsynced.
+//!
+//! Requests which *read* from a beacon node (e.g., producing a block) are sent to one node at a
+//! time, trying synced nodes first. Requests which *publish* to a beacon node (e.g., a signed
+//! block) are broadcast to all synced nodes at once.
+
+use crate::is_synced::is_synced;
+use environment::RuntimeContext;
+use futures::future::join_all;
+use futures::StreamExt;
+use parking_lot::RwLock;
+use remote_beacon_node::RemoteBeaconNode;
+use slog::{debug, Logger};
+use slot_clock::SlotClock;
+use std::fmt::Debug;
+use std::future::Future;
+use std::sync::Arc;
+use tokio::time::{interval_at, Duration, Instant};
+use types::{ChainSpec, EthSpec};
+
+/// Delay this period of time after the slot starts. This allows the nodes to process the new slot.
+const TIME_DELAY_FROM_SLOT: Duration = Duration::from_millis(50);
+
+/// Indicates if a request may be sent to a beacon node which is not synced.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RequireSynced {
+    Yes,
+    No,
+}
+
+/// The last-known health of a `CandidateBeaconNode`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CandidateStatus {
+    /// The node has not yet been checked.
+    Uninitialized,
+    /// The node is reachable and synced (see `is_synced`).
+    Synced,
+    /// The node is either unreachable or too far behind the head.
+    NotSynced,
+}
+
+#[derive(Debug)]
+pub enum Error<E> {
+    /// There were no candidates eligible to receive the request.
+    NoCandidates,
+    /// Each eligible candidate returned an error, listed with the endpoint of the candidate.
+    AllFailed(Vec<(String, E)>),
+}
+
+/// A beacon node which may be used by the validator client, along with its last-known status.
+pub struct CandidateBeaconNode<E: EthSpec> {
+    endpoint: String,
+    beacon_node: RemoteBeaconNode<E>,
+    status: RwLock<CandidateStatus>,
+}
+
+impl<E: EthSpec> CandidateBeaconNode<E> {
+    pub fn new(endpoint: String, beacon_node: RemoteBeaconNode<E>) -> Self {
+        Self {
+            endpoint,
+            beacon_node,
+            status: RwLock::new(CandidateStatus::Uninitialized),
+        }
+    }
+
+    /// The URL of the beacon node, as supplied by the user.
+    pub fn endpoint(&self) -> &str {
+        &self.endpoint
+    }
+
+    pub fn beacon_node(&self) -> &RemoteBeaconNode<E> {
+        &self.beacon_node
+    }
+
+    pub fn status(&self) -> CandidateStatus {
+        *self.status.read()
+    }
+
+    /// Query the sync status of the beacon node, updating `self.status`.
+    async fn refresh_status<T: SlotClock>(&self, slot_clock: &T, log: &Logger) {
+        let status = if is_synced(&self.beacon_node, slot_clock, Some(log)).await {
+            CandidateStatus::Synced
+        } else {
+            CandidateStatus::NotSynced
+        };
+
+        *self.status.write() = status;
+    }
+}
+
+/// A collection of `CandidateBeaconNode`, in order of preference.
+pub struct BeaconNodeFallback<T, E: EthSpec> {
+    candidates: Vec<CandidateBeaconNode<E>>,
+    slot_clock: T,
+    log: Logger,
+}
+
+impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
+    pub fn new(candidates: Vec<CandidateBeaconNode<E>>, slot_clock: T, log: Logger) -> Self {
+        Self {
+            candidates,
+            slot_clock,
+            log,
+        }
+    }
+
+    /// The total number of candidates, regardless of their status.
+    pub fn num_total(&self) -> usize {
+        self.candidates.len()
+    }
+
+    /// The number of candidates which were synced at the last status update.
+    pub fn num_synced(&self) -> usize {
+        self.candidates
+            .iter()
+            .filter(|candidate| candidate.status() == CandidateStatus::Synced)
+            .count()
+    }
+
+    /// Refresh the status of all candidates, concurrently.
+    pub async fn update_all_candidates(&self) {
+        join_all(
+            self.candidates
+                .iter()
+                .map(|candidate| candidate.refresh_status(&self.slot_clock, &self.log)),
+        )
+        .await;
+    }
+
+    /// Returns the candidates which may receive a request, most-preferred first.
+    ///
+    /// Synced candidates are always returned first. The remaining candidates are only returned
+    /// when `require_synced == RequireSynced::No`.
+    fn eligible_candidates(&self, require_synced: RequireSynced) -> Vec<&CandidateBeaconNode<E>> {
+        let (synced, not_synced): (Vec<_>, Vec<_>) = self
+            .candidates
+            .iter()
+            .partition(|candidate| candidate.status() == CandidateStatus::Synced);
+
+        match require_synced {
+            RequireSynced::Yes => synced,
+            RequireSynced::No => synced.into_iter().chain(not_synced).collect(),
+        }
+    }
+
+    /// Run `func` against each eligible candidate in turn, returning the first successful result.
+    ///
+    /// Candidates are tried in the order given by `eligible_candidates`.
+    pub async fn first_success<'a, F, O, Err, R>(
+        &'a self,
+        require_synced: RequireSynced,
+        func: F,
+    ) -> Result<O, Error<Err>>
+    where
+        F: Fn(&'a RemoteBeaconNode<E>) -> R,
+        R: Future<Output = Result<O, Err>>,
+        Err: Debug,
+    {
+        let mut errors = vec![];
+
+        for candidate in self.eligible_candidates(require_synced) {
+            match func(&candidate.beacon_node).await {
+                Ok(val) => return Ok(val),
+                Err(e) => {
+                    debug!(
+                        self.log,
+                        "Request to beacon node failed";
+                        "endpoint" => candidate.endpoint(),
+                        "error" => format!("{:?}", e),
+                    );
+                    errors.push((candidate.endpoint.clone(), e));
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Err(Error::NoCandidates)
+        } else {
+            Err(Error::AllFailed(errors))
+        }
+    }
+
+    /// Run `func` against all synced candidates concurrently, returning the result from the
+    /// most-preferred candidate which succeeded.
+    ///
+    /// If there are no synced candidates and `require_synced == RequireSynced::No`, `func` is run
+    /// against all candidates instead.
+    pub async fn broadcast<'a, F, O, Err, R>(
+        &'a self,
+        require_synced: RequireSynced,
+        func: F,
+    ) -> Result<O, Error<Err>>
+    where
+        F: Fn(&'a RemoteBeaconNode<E>) -> R,
+        R: Future<Output = Result<O, Err>>,
+        Err: Debug,
+    {
+        let mut candidates = self.eligible_candidates(RequireSynced::Yes);
+        if candidates.is_empty() && require_synced == RequireSynced::No {
+            candidates = self.candidates.iter().collect();
+        }
+
+        if candidates.is_empty() {
+            return Err(Error::NoCandidates);
+        }
+
+        let results = join_all(
+            candidates
+                .iter()
+                .map(|&candidate| func(&candidate.beacon_node)),
+        )
+        .await;
+
+        let mut first_success = None;
+        let mut errors = vec![];
+
+        for (candidate, result) in candidates.into_iter().zip(results) {
+            match result {
+                Ok(val) => {
+                    if first_success.is_none() {
+                        first_success = Some(val);
+                    }
+                }
+                Err(e) => {
+                    debug!(
+                        self.log,
+                        "Broadcast to beacon node failed";
+                        "endpoint" => candidate.endpoint(),
+                        "error" => format!("{:?}", e),
+                    );
+                    errors.push((candidate.endpoint.clone(), e));
+                }
+            }
+        }
+
+        first_success.ok_or_else(|| Error::AllFailed(errors))
+    }
+}
+
+/// Starts a service which refreshes the status of all beacon nodes shortly after the start of
+/// each slot.
+pub fn start_fallback_updater_service<T: SlotClock + 'static, E: EthSpec>(
+    context: RuntimeContext<E>,
+    beacon_nodes: Arc<BeaconNodeFallback<T, E>>,
+    spec: &ChainSpec,
+) -> Result<(), String> {
+    let duration_to_next_slot = beacon_nodes
+        .slot_clock
+        .duration_to_next_slot()
+        .ok_or_else(|| "Unable to determine duration to next slot".to_string())?;
+
+    let mut interval = {
+        let slot_duration = Duration::from_millis(spec.milliseconds_per_slot);
+        // Note: `interval_at` panics if `slot_duration` is 0
+        interval_at(
+            Instant::now() + duration_to_next_slot + TIME_DELAY_FROM_SLOT,
+            slot_duration,
+        )
+    };
+
+    let interval_fut = async move {
+        while interval.next().await.is_some() {
+            beacon_nodes.update_all_candidates().await;
+        }
+    };
+
+    context.executor.spawn(interval_fut, "fallback");
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use slog::o;
+    use slot_clock::TestingSlotClock;
+    use types::{MinimalEthSpec, Slot};
+
+    type E = MinimalEthSpec;
+
+    /// Builds a fallback with `statuses.len()` candidates, with the given statuses.
+    ///
+    /// No requests are made to the (non-existent) endpoints.
+    fn fallback(statuses: &[CandidateStatus]) -> BeaconNodeFallback<TestingSlotClock, E> {
+        let candidates = statuses
+            .iter()
+            .enumerate()
+            .map(|(i, status)| {
+                let endpoint = format!("http://localhost:{}/", 5052 + i);
+                let candidate = CandidateBeaconNode::new(
+                    endpoint.clone(),
+                    RemoteBeaconNode::new(endpoint).unwrap(),
+                );
+                *candidate.status.write() = *status;
+                candidate
+            })
+            .collect();
+        let slot_clock =
+            TestingSlotClock::new(Slot::new(0), Duration::from_secs(0), Duration::from_secs(1));
+        let log = Logger::root(slog::Discard, o!());
+
+        BeaconNodeFallback::new(candidates, slot_clock, log)
+    }
+
+    /// Returns the index of `beacon_node` within `fallback`.
+    fn index_of(
+        fallback: &BeaconNodeFallback<TestingSlotClock, E>,
+        beacon_node: &RemoteBeaconNode<E>,
+    ) -> usize {
+        fallback
+            .candidates
+            .iter()
+            .position(|candidate| std::ptr::eq(candidate.beacon_node(), beacon_node))
+            .expect("beacon node should be a candidate")
+    }
+
+    #[tokio::test]
+    async fn first_success_prefers_synced() {
+        let fallback = fallback(&[
+            CandidateStatus::NotSynced,
+            CandidateStatus::Synced,
+            CandidateStatus::Synced,
+        ]);
+
+        let tried = RwLock::new(vec![]);
+        let result = fallback
+            .first_success(RequireSynced::No, |beacon_node| {
+                let i = index_of(&fallback, beacon_node);
+                tried.write().push(i);
+                async move {
+                    if i == 1 {
+                        Err("offline")
+                    } else {
+                        Ok(i)
+                    }
+                }
+            })
+            .await;
+
+        assert_eq!(result.unwrap(), 2);
+        assert_eq!(*tried.read(), vec![1, 2]);
+    }
+
+    #[tokio::test]
+    async fn first_success_require_synced() {
+        let fallback = fallback(&[CandidateStatus::NotSynced, CandidateStatus::Uninitialized]);
+
+        let result = fallback
+            .first_success(RequireSynced::Yes, |_| async { Ok::<_, ()>(()) })
+            .await;
+        assert!(matches!(result, Err(Error::NoCandidates)));
+
+        let result = fallback
+            .first_success(RequireSynced::No, |beacon_node| {
+                let i = index_of(&fallback, beacon_node);
+                async move { Err::<(), _>(i) }
+            })
+            .await;
+        match result {
+            Err(Error::AllFailed(errors)) => {
+                assert_eq!(
+                    errors.into_iter().map(|(_, i)| i).collect::<Vec<_>>(),
+                    vec![0, 1]
+                );
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn broadcast_to_synced() {
+        let fallback = fallback(&[
+            CandidateStatus::Synced,
+            CandidateStatus::NotSynced,
+            CandidateStatus::Synced,
+        ]);
+
+        let sent = RwLock::new(vec![]);
+        let result = fallback
+            .broadcast(RequireSynced::No, |beacon_node| {
+                let i = index_of(&fallback, beacon_node);
+                sent.write().push(i);
+                async move {
+                    if i == 0 {
+                        Err("offline")
+                    } else {
+                        Ok(i)
+                    }
+                }
+            })
+            .await;
+
+        assert_eq!(result.unwrap(), 2);
+        assert_eq!(*sent.read(), vec![0, 2]);
+    }
+
+    #[tokio::test]
+    async fn broadcast_without_synced() {
+        let fallback = fallback(&[CandidateStatus::NotSynced, CandidateStatus::NotSynced]);
+
+        let result = fallback
+            .broadcast(RequireSynced::Yes, |_| async { Ok::<_, ()>(()) })
+            .await;
+        assert!(matches!(result, Err(Error::NoCandidates)));
+
+        let sent = RwLock::new(vec![]);
+        let result = fallback
+            .broadcast(RequireSynced::No, |beacon_node| {
+                sent.write().push(index_of(&fallback, beacon_node));
+                async { Ok::<_, ()>(()) }
+            })
+            .await;
+        assert!(result.is_ok());
+        assert_eq!(*sent.read(), vec![0, 1]);
+    }
+}
@@ -1,7 +1,12 @@
-use crate::{duties_service::DutiesService, validator_store::ValidatorStore};
+use crate::{
+    beacon_node_fallback::{BeaconNodeFallback, RequireSynced},
+    duties_service::DutiesService,
+    validator_store::{publish_outcome, ValidatorStore},
+};
 use environment::RuntimeContext;
 use futures::{StreamExt, TryFutureExt};
-use remote_beacon_node::{PublishStatus, RemoteBeaconNode};
+use remote_beacon_node::PublishStatus;
+use remote_validator_client::types::{PublishKind, PublishResult};
 use slog::{crit, error, info, trace};
 use slot_clock::SlotClock;
 use std::ops::Deref;
@@ -17,7 +22,7 @@ pub struct BlockServiceBuilder<T, E: EthSpec> {
     duties_service: Option<DutiesService<T, E>>,
     validator_store: Option<ValidatorStore<T, E>>,
     slot_clock: Option<Arc<T>>,
-    beacon_node: Option<RemoteBeaconNode<E>>,
+    beacon_nodes: Option<Arc<BeaconNodeFallback<T, E>>>,
     context: Option<RuntimeContext<E>>,
 }
 
@@ -27,7 +32,7 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockServiceBuilder<T, E> {
             duties_service: None,
             validator_store: None,
             slot_clock: None,
-            beacon_node: None,
+            beacon_nodes: None,
             context: None,
         }
     }
@@ -47,8 +52,8 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockServiceBuilder<T, E> {
         self
     }
 
-    pub fn beacon_node(mut self, beacon_node: RemoteBeaconNode<E>) -> Self {
-        self.beacon_node = Some(beacon_node);
+    pub fn beacon_nodes(mut self, beacon_nodes: Arc<BeaconNodeFallback<T, E>>) -> Self {
+        self.beacon_nodes = Some(beacon_nodes);
         self
     }
 
@@ -69,9 +74,9 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockServiceBuilder<T, E> {
                 slot_clock: self
                     .slot_clock
                     .ok_or_else(|| "Cannot build BlockService without slot_clock")?,
-                beacon_node: self
-                    .beacon_node
-                    .ok_or_else(|| "Cannot build BlockService without beacon_node")?,
+                beacon_nodes: self
+                    .beacon_nodes
+                    .ok_or_else(|| "Cannot build BlockService without beacon_nodes")?,
                 context: self
                     .context
                     .ok_or_else(|| "Cannot build BlockService without runtime_context")?,
@@ -85,7 +90,7 @@ pub struct Inner<T, E: EthSpec> {
     duties_service: DutiesService<T, E>,
     validator_store: ValidatorStore<T, E>,
     slot_clock: Arc<T>,
-    beacon_node: RemoteBeaconNode<E>,
+    beacon_nodes: Arc<BeaconNodeFallback<T, E>>,
     context: RuntimeContext<E>,
 }
 
@@ -211,27 +216,54 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
         let randao_reveal = self
             .validator_store
             .randao_reveal(&validator_pubkey, slot.epoch(E::slots_per_epoch()))
+            .await
             .ok_or_else(|| "Unable to produce randao reveal".to_string())?;
 
         let block = self
-            .beacon_node
-            .http
-            .validator()
-            .produce_block(slot, randao_reveal)
+            .beacon_nodes
+            .first_success(RequireSynced::No, |beacon_node| {
+                let randao_reveal = randao_reveal.clone();
+                async move {
+                    beacon_node
+                        .http
+                        .validator()
+                        .produce_block(slot, randao_reveal)
+                        .await
+                }
+            })
             .await
             .map_err(|e| format!("Error from beacon node when producing block: {:?}", e))?;
 
         let signed_block = self
             .validator_store
             .sign_block(&validator_pubkey, block, current_slot)
+            .await
             .ok_or_else(|| "Unable to sign block".to_string())?;
 
-        let publish_status = self
-            .beacon_node
-            .http
-            .validator()
-            .publish_block(signed_block.clone())
-            .await
+        let publish_result = self
+            .beacon_nodes
+            .broadcast(RequireSynced::No, |beacon_node| {
+                let signed_block = signed_block.clone();
+                async move {
+                    beacon_node
+                        .http
+                        .validator()
+                        .publish_block(signed_block)
+                        .await
+                }
+            })
+            .await;
+
+        self.validator_store.record_publish_result(
+            &validator_pubkey,
+            PublishResult {
+                kind: PublishKind::Block,
+                slot,
+                outcome: publish_outcome(&publish_result),
+            },
+        );
+
+        let publish_status = publish_result
             .map_err(|e| format!("Error from beacon node when publishing block: {:?}", e))?;
 
         match publish_status {
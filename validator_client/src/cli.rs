@@ -9,10 +9,16 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 validator (e.g., proposing blocks and attestations).",
         )
         .arg(
-            Arg::with_name("server")
-                .long("server")
-                .value_name("NETWORK_ADDRESS")
-                .help("Address to connect to BeaconNode.")
+            Arg::with_name("beacon-nodes")
+                .long("beacon-nodes")
+                .alias("server")
+                .value_name("NETWORK_ADDRESSES")
+                .help(
+                    "Comma-separated addresses of one or more beacon nodes, in order of \
+                    preference. Requests are sent to the first synced node, falling back to \
+                    the others if it fails. Published blocks and attestations are sent to all \
+                    synced nodes.",
+                )
                 .default_value(&DEFAULT_HTTP_SERVER)
                 .takes_value(true),
         )
@@ -36,6 +42,18 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                        the validator client, or if you're certain there are no other \
                        nodes using the same key.",
         ))
+        .arg(
+            Arg::with_name("remote-signer")
+                .long("remote-signer")
+                .value_name("URL")
+                .help(
+                    "The HTTP address of a remote signing service which holds the validator \
+                    voting keys. If present, the voting keystores in --datadir are only used to \
+                    determine the public keys and are never decrypted. Slashing protection is \
+                    still performed locally before any signing request is sent.",
+                )
+                .takes_value(true),
+        )
         .arg(
             Arg::with_name("allow-unsynced")
                 .long("allow-unsynced")
@@ -44,4 +62,52 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                       node is not synced.",
                 ),
         )
+        .arg(
+            Arg::with_name("enable-doppelganger-protection")
+                .long("enable-doppelganger-protection")
+                .help(
+                    "If present, the validator client will not sign any blocks or attestations \
+                    until it has watched the network for --doppelganger-detection-epochs and \
+                    seen no activity from its validators. If any activity is seen, the same \
+                    keys are probably running elsewhere and the validator client shuts down. \
+                    This costs a few epochs of rewards on every restart.",
+                ),
+        )
+        .arg(
+            Arg::with_name("doppelganger-detection-epochs")
+                .long("doppelganger-detection-epochs")
+                .value_name("EPOCHS")
+                .help(
+                    "The number of complete epochs to watch the network for validator \
+                    activity when --enable-doppelganger-protection is present.",
+                )
+                .default_value("2")
+                .takes_value(true),
+        )
+        /* HTTP API related arguments */
+        .arg(
+            Arg::with_name("http")
+                .long("http")
+                .help(
+                    "Enable the HTTP API server for managing validators. Requests must supply \
+                    the token in the api-token.txt file in --datadir. Disabled by default.",
+                )
+                .takes_value(false),
+        )
+        .arg(
+            Arg::with_name("http-address")
+                .long("http-address")
+                .value_name("ADDRESS")
+                .help("Set the listen address for the HTTP API server.")
+                .default_value("127.0.0.1")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("http-port")
+                .long("http-port")
+                .value_name("PORT")
+                .help("Set the listen TCP port for the HTTP API server.")
+                .default_value("5062")
+                .takes_value(true),
+        )
 }
@@ -1,3 +1,4 @@
+use crate::http_api;
 use clap::ArgMatches;
 use clap_utils::{parse_optional, parse_path_with_default_in_home_dir};
 use serde_derive::{Deserialize, Serialize};
@@ -6,8 +7,9 @@ use std::path::PathBuf;
 pub const DEFAULT_HTTP_SERVER: &str = "http://localhost:5052/";
 pub const DEFAULT_DATA_DIR: &str = ".lighthouse/validators";
 pub const DEFAULT_SECRETS_DIR: &str = ".lighthouse/secrets";
+pub const DEFAULT_DOPPELGANGER_DETECTION_EPOCHS: u64 = 2;
 /// Path to the slashing protection database within the datadir.
-pub const SLASHING_PROTECTION_FILENAME: &str = "slashing_protection.sqlite";
+pub use slashing_protection::SLASHING_PROTECTION_FILENAME;
 
 /// Stores the core configuration for this validator instance.
 #[derive(Clone, Serialize, Deserialize)]
@@ -16,15 +18,26 @@ pub struct Config {
     pub data_dir: PathBuf,
     /// The directory containing the passwords to unlock validator keystores.
     pub secrets_dir: PathBuf,
-    /// The http endpoint of the beacon node API.
+    /// The http endpoints of the beacon node APIs, in order of preference.
     ///
-    /// Should be similar to `http://localhost:8080`
-    pub http_server: String,
+    /// Each should be similar to `http://localhost:8080`
+    pub beacon_nodes: Vec<String>,
     /// If true, the validator client will still poll for duties and produce blocks even if the
     /// beacon node is not synced at startup.
     pub allow_unsynced_beacon_node: bool,
     /// If true, register new validator keys with the slashing protection database.
     pub auto_register: bool,
+    /// If set, sign with the remote signer at this URL instead of decrypting local keystores.
+    ///
+    /// Should be similar to `http://localhost:9000`
+    pub remote_signer_url: Option<String>,
+    /// If true, do not sign any blocks or attestations until the validators have been observed to
+    /// be inactive on the network for `doppelganger_detection_epochs`.
+    pub enable_doppelganger_protection: bool,
+    /// The number of epochs to observe the network during doppelganger detection.
+    pub doppelganger_detection_epochs: u64,
+    /// Configuration for the HTTP API used to manage the validators.
+    pub http_api: http_api::Config,
 }
 
 impl Default for Config {
@@ -39,9 +52,13 @@ impl Default for Config {
         Self {
             data_dir,
             secrets_dir,
-            http_server: DEFAULT_HTTP_SERVER.to_string(),
+            beacon_nodes: vec![DEFAULT_HTTP_SERVER.to_string()],
             allow_unsynced_beacon_node: false,
             auto_register: false,
+            remote_signer_url: None,
+            enable_doppelganger_protection: false,
+            doppelganger_detection_epochs: DEFAULT_DOPPELGANGER_DETECTION_EPOCHS,
+            http_api: <_>::default(),
         }
     }
 }
@@ -65,8 +82,17 @@ impl Config {
             ));
         }
 
-        if let Some(server) = parse_optional(cli_args, "server")? {
-            config.http_server = server;
+        if let Some(beacon_nodes) = parse_optional::<String>(cli_args, "beacon-nodes")? {
+            config.beacon_nodes = beacon_nodes
+                .split(',')
+                .map(str::trim)
+                .filter(|endpoint| !endpoint.is_empty())
+                .map(String::from)
+                .collect();
+
+            if config.beacon_nodes.is_empty() {
+                return Err("At least one beacon node must be supplied (--beacon-nodes)".into());
+            }
         }
 
         config.allow_unsynced_beacon_node = cli_args.is_present("allow-unsynced");
@@ -76,13 +102,45 @@ impl Config {
             config.secrets_dir = secrets_dir;
         }
 
-        if !config.secrets_dir.exists() {
+        config.remote_signer_url = parse_optional(cli_args, "remote-signer")?;
+
+        // Passwords are not required when the keys are held by a remote signer.
+        if config.remote_signer_url.is_none() && !config.secrets_dir.exists() {
             return Err(format!(
                 "The directory for validator passwords (--secrets-dir) does not exist: {:?}",
                 config.secrets_dir
             ));
         }
 
+        config.enable_doppelganger_protection =
+            cli_args.is_present("enable-doppelganger-protection");
+
+        if let Some(epochs) = parse_optional::<u64>(cli_args, "doppelganger-detection-epochs")? {
+            if epochs == 0 {
+                return Err("doppelganger-detection-epochs must be at least 1".to_string());
+            }
+            config.doppelganger_detection_epochs = epochs;
+        }
+
+        /*
+         * HTTP API
+         */
+        if cli_args.is_present("http") {
+            config.http_api.enabled = true;
+        }
+
+        if let Some(address) = cli_args.value_of("http-address") {
+            config.http_api.listen_address = address
+                .parse()
+                .map_err(|_| "http-address is not a valid IPv4 address.")?;
+        }
+
+        if let Some(port) = cli_args.value_of("http-port") {
+            config.http_api.port = port
+                .parse::<u16>()
+                .map_err(|_| "http-port is not a valid u16.")?;
+        }
+
         Ok(config)
     }
 }
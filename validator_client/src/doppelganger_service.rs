@@ -0,0 +1,574 @@
+//! Provides protection against running the same validator keys in two validator clients at once
+//! (a "doppelganger").
+//!
+//! The slashing protection database only knows about the messages signed by this validator client,
+//! so it cannot prevent a slashing if the same keys are also active elsewhere. When enabled, the
+//! `DoppelgangerService` prevents the `ValidatorStore` from signing any blocks or attestations
+//! until it has watched the network for a number of complete epochs. If any of the validators
+//! attest or propose during that time, another instance must be running and the validator client
+//! shuts down with an error.
+//!
+//! The epoch in which the validator client starts is not checked, since it may contain messages
+//! signed by a previous run of this validator client. Attestations for an epoch may be included
+//! in the following epoch, so signing is only enabled once the epoch after the last observed epoch
+//! has also been checked for them.
+
+use crate::beacon_node_fallback::{BeaconNodeFallback, RequireSynced};
+use crate::duties_service::DutiesService;
+use crate::validator_store::ValidatorStore;
+use environment::{RuntimeContext, ShutdownReason};
+use futures::StreamExt;
+use rest_types::IndividualVotesResponse;
+use slog::{crit, debug, error, info, warn};
+use slot_clock::SlotClock;
+use std::ops::Deref;
+use std::sync::Arc;
+use tokio::time::{interval_at, Duration, Instant};
+use types::{ChainSpec, Epoch, EthSpec, PublicKey, PublicKeyBytes};
+
+/// The parts of the liveness data for an epoch which must be checked for doppelgangers.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct LivenessCheck {
+    /// Check the attestations for, and blocks proposed in, the epoch itself.
+    current_epoch: bool,
+    /// Check the attestations for the previous epoch which were included in the epoch.
+    previous_epoch: bool,
+}
+
+/// Tracks the number of epochs which have been checked for doppelgangers.
+///
+/// Kept separate from the network requests so that it can be tested.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct DetectionProgress {
+    start_epoch: Epoch,
+    detection_epochs: u64,
+    checked_epochs: u64,
+}
+
+impl DetectionProgress {
+    fn new(start_epoch: Epoch, detection_epochs: u64) -> Self {
+        Self {
+            start_epoch,
+            detection_epochs,
+            checked_epochs: 0,
+        }
+    }
+
+    /// Returns the liveness data which must be checked for `epoch`, which has just ended.
+    ///
+    /// Returns `None` if `epoch` is not checked.
+    fn liveness_check(&self, epoch: Epoch) -> Option<LivenessCheck> {
+        if epoch <= self.start_epoch {
+            return None;
+        }
+
+        Some(LivenessCheck {
+            // Once the required epochs have been observed, only the attestations for the last of
+            // them remain to be checked.
+            current_epoch: self.checked_epochs < self.detection_epochs,
+            previous_epoch: epoch - 1 > self.start_epoch,
+        })
+    }
+
+    /// Records that an epoch was checked and no doppelgangers were found.
+    fn record_success(&mut self) {
+        self.checked_epochs += 1;
+    }
+
+    /// Records that an epoch could not be checked.
+    ///
+    /// Attestations for the prior epoch which were included in the failed epoch have not been
+    /// checked, so the prior epoch no longer counts towards detection.
+    fn record_failure(&mut self) {
+        self.checked_epochs = self.checked_epochs.saturating_sub(1);
+    }
+
+    /// The number of epochs which must still be checked before signing is enabled.
+    fn remaining_epochs(&self) -> u64 {
+        (self.detection_epochs + 1).saturating_sub(self.checked_epochs)
+    }
+
+    /// Returns `true` once `detection_epochs` epochs, and the attestations included for the last of
+    /// them in the following epoch, have been checked.
+    fn is_complete(&self) -> bool {
+        self.remaining_epochs() == 0
+    }
+}
+
+/// Returns the public keys of the validators which attested according to `votes`, considering only
+/// the parts of the epoch given by `check`.
+fn attesters(votes: Vec<IndividualVotesResponse>, check: LivenessCheck) -> Vec<PublicKeyBytes> {
+    votes
+        .into_iter()
+        .filter(|response| {
+            response.vote.as_ref().map_or(false, |vote| {
+                (check.current_epoch && vote.is_current_epoch_attester)
+                    || (check.previous_epoch && vote.is_previous_epoch_attester)
+            })
+        })
+        .map(|response| response.pubkey)
+        .collect()
+}
+
+/// Builds a `DoppelgangerService`.
+pub struct DoppelgangerServiceBuilder<T, E: EthSpec> {
+    validator_store: Option<ValidatorStore<T, E>>,
+    duties_service: Option<DutiesService<T, E>>,
+    slot_clock: Option<T>,
+    beacon_nodes: Option<Arc<BeaconNodeFallback<T, E>>>,
+    context: Option<RuntimeContext<E>>,
+    detection_epochs: Option<u64>,
+}
+
+impl<T: SlotClock + 'static, E: EthSpec> DoppelgangerServiceBuilder<T, E> {
+    pub fn new() -> Self {
+        Self {
+            validator_store: None,
+            duties_service: None,
+            slot_clock: None,
+            beacon_nodes: None,
+            context: None,
+            detection_epochs: None,
+        }
+    }
+
+    pub fn validator_store(mut self, store: ValidatorStore<T, E>) -> Self {
+        self.validator_store = Some(store);
+        self
+    }
+
+    pub fn duties_service(mut self, service: DutiesService<T, E>) -> Self {
+        self.duties_service = Some(service);
+        self
+    }
+
+    pub fn slot_clock(mut self, slot_clock: T) -> Self {
+        self.slot_clock = Some(slot_clock);
+        self
+    }
+
+    pub fn beacon_nodes(mut self, beacon_nodes: Arc<BeaconNodeFallback<T, E>>) -> Self {
+        self.beacon_nodes = Some(beacon_nodes);
+        self
+    }
+
+    pub fn runtime_context(mut self, context: RuntimeContext<E>) -> Self {
+        self.context = Some(context);
+        self
+    }
+
+    pub fn detection_epochs(mut self, detection_epochs: u64) -> Self {
+        self.detection_epochs = Some(detection_epochs);
+        self
+    }
+
+    pub fn build(self) -> Result<DoppelgangerService<T, E>, String> {
+        Ok(DoppelgangerService {
+            inner: Arc::new(Inner {
+                validator_store: self
+                    .validator_store
+                    .ok_or_else(|| "Cannot build DoppelgangerService without validator_store")?,
+                duties_service: self
+                    .duties_service
+                    .ok_or_else(|| "Cannot build DoppelgangerService without duties_service")?,
+                slot_clock: self
+                    .slot_clock
+                    .ok_or_else(|| "Cannot build DoppelgangerService without slot_clock")?,
+                beacon_nodes: self
+                    .beacon_nodes
+                    .ok_or_else(|| "Cannot build DoppelgangerService without beacon_nodes")?,
+                context: self
+                    .context
+                    .ok_or_else(|| "Cannot build DoppelgangerService without runtime_context")?,
+                detection_epochs: self
+                    .detection_epochs
+                    .ok_or_else(|| "Cannot build DoppelgangerService without detection_epochs")?,
+            }),
+        })
+    }
+}
+
+/// Helper to minimise `Arc` usage.
+pub struct Inner<T, E: EthSpec> {
+    validator_store: ValidatorStore<T, E>,
+    duties_service: DutiesService<T, E>,
+    slot_clock: T,
+    beacon_nodes: Arc<BeaconNodeFallback<T, E>>,
+    context: RuntimeContext<E>,
+    detection_epochs: u64,
+}
+
+/// Watches the network for messages from the validators before permitting them to sign.
+pub struct DoppelgangerService<T, E: EthSpec> {
+    inner: Arc<Inner<T, E>>,
+}
+
+impl<T, E: EthSpec> Clone for DoppelgangerService<T, E> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T, E: EthSpec> Deref for DoppelgangerService<T, E> {
+    type Target = Inner<T, E>;
+
+    fn deref(&self) -> &Self::Target {
+        self.inner.deref()
+    }
+}
+
+impl<T: SlotClock + 'static, E: EthSpec> DoppelgangerService<T, E> {
+    /// Starts the service which checks each epoch for doppelgangers, until `detection_epochs`
+    /// have been checked.
+    pub fn start_update_service(self, spec: &ChainSpec) -> Result<(), String> {
+        let log = self.context.log().clone();
+
+        let start_epoch = self
+            .slot_clock
+            .now()
+            .ok_or_else(|| "Unable to determine current slot from clock".to_string())?
+            .epoch(E::slots_per_epoch());
+        let duration_to_next_epoch = self
+            .slot_clock
+            .duration_to_next_epoch(E::slots_per_epoch())
+            .ok_or_else(|| "Unable to determine duration to next epoch".to_string())?;
+
+        info!(
+            log,
+            "Doppelganger detection started";
+            "msg" => "no blocks or attestations will be signed until it completes",
+            "detection_epochs" => self.detection_epochs,
+            "start_epoch" => start_epoch.as_u64(),
+        );
+
+        let mut interval = {
+            let slot_duration = Duration::from_millis(spec.milliseconds_per_slot);
+            // Wait half a slot after the start of each epoch, so that the beacon nodes have
+            // processed the last block of the previous epoch.
+            //
+            // Note: `interval_at` panics if `slot_duration * E::slots_per_epoch()` = 0
+            interval_at(
+                Instant::now() + duration_to_next_epoch + slot_duration / 2,
+                slot_duration * E::slots_per_epoch() as u32,
+            )
+        };
+
+        let executor = self.context.executor.clone();
+
+        let interval_fut = async move {
+            let mut progress = DetectionProgress::new(start_epoch, self.detection_epochs);
+
+            while interval.next().await.is_some() {
+                let log = self.context.log();
+
+                // Check the epoch which has just ended.
+                let epoch = match self.slot_clock.now() {
+                    Some(slot) => slot.epoch(E::slots_per_epoch()) - 1,
+                    None => {
+                        warn!(log, "Doppelganger service unable to read slot clock");
+                        continue;
+                    }
+                };
+
+                let check = match progress.liveness_check(epoch) {
+                    Some(check) => check,
+                    None => continue,
+                };
+
+                match self.check_epoch(epoch, check).await {
+                    Ok(doppelgangers) if doppelgangers.is_empty() => {
+                        progress.record_success();
+                        debug!(
+                            log,
+                            "No doppelgangers detected";
+                            "epoch" => epoch.as_u64(),
+                            "remaining_epochs" => progress.remaining_epochs(),
+                        );
+                    }
+                    Ok(doppelgangers) => {
+                        for pubkey in doppelgangers {
+                            crit!(
+                                log,
+                                "Doppelganger detected";
+                                "msg" => "the validator is active in another validator client",
+                                "voting_pubkey" => format!("{:?}", pubkey),
+                                "epoch" => epoch.as_u64(),
+                            );
+                        }
+                        crit!(
+                            log,
+                            "Shutting down to avoid slashing";
+                            "msg" => "ensure the keys are only used in a single validator client \
+                                before restarting",
+                        );
+                        // Signing has not been permitted, so it remains disabled until the
+                        // process exits.
+                        if let Err(e) = self
+                            .context
+                            .executor
+                            .shutdown_sender()
+                            .try_send(ShutdownReason::Failure("Doppelganger detected"))
+                        {
+                            error!(
+                                log,
+                                "Unable to request shutdown";
+                                "error" => format!("{:?}", e),
+                            );
+                        }
+                        break;
+                    }
+                    Err(e) => {
+                        progress.record_failure();
+                        warn!(
+                            log,
+                            "Unable to check for doppelgangers";
+                            "msg" => "detection will take an additional epoch",
+                            "epoch" => epoch.as_u64(),
+                            "error" => e,
+                        );
+                    }
+                }
+
+                if progress.is_complete() {
+                    self.validator_store.complete_doppelganger_detection();
+                    info!(
+                        log,
+                        "Doppelganger detection complete";
+                        "msg" => "signing is now enabled",
+                        "checked_epochs" => self.detection_epochs,
+                    );
+                    break;
+                }
+            }
+        };
+
+        executor.spawn(interval_fut, "doppelganger_service");
+
+        Ok(())
+    }
+
+    /// Returns the voting public keys of any validators which were live in `epoch`, according to
+    /// the parts of the epoch given by `check`.
+    async fn check_epoch(
+        &self,
+        epoch: Epoch,
+        check: LivenessCheck,
+    ) -> Result<Vec<PublicKeyBytes>, String> {
+        let pubkeys = self.validator_store.voting_pubkeys();
+        if pubkeys.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let pubkey_bytes = pubkeys
+            .iter()
+            .cloned()
+            .map(PublicKeyBytes::from)
+            .collect::<Vec<_>>();
+        let votes = self
+            .beacon_nodes
+            .first_success(RequireSynced::Yes, |beacon_node| {
+                let pubkey_bytes = pubkey_bytes.clone();
+                async move {
+                    beacon_node
+                        .http
+                        .consensus()
+                        .get_individual_votes(epoch, pubkey_bytes)
+                        .await
+                }
+            })
+            .await
+            .map_err(|e| format!("Failed to get individual votes: {:?}", e))?;
+
+        let mut doppelgangers = attesters(votes, check);
+
+        if check.current_epoch {
+            for pubkey in &pubkeys {
+                if self.proposed_in_epoch(pubkey, epoch).await? {
+                    doppelgangers.push(pubkey.clone().into());
+                }
+            }
+        }
+
+        Ok(doppelgangers)
+    }
+    /// Returns `true` if there is a canonical block at any of the proposal slots of
+    /// `validator_pubkey` in `epoch`.
+    ///
+    /// The proposer of each slot is fixed, so such a block must have been signed by the validator.
+    async fn proposed_in_epoch(
+        &self,
+        validator_pubkey: &PublicKey,
+        epoch: Epoch,
+    ) -> Result<bool, String> {
+        let proposal_slots = self
+            .duties_service
+            .validator_duties(validator_pubkey)
+            .into_iter()
+            .filter(|(duty_epoch, _)| *duty_epoch == epoch)
+            .flat_map(|(_, duty_and_proof)| duty_and_proof.duty.block_proposal_slots)
+            .collect::<Vec<_>>();
+
+        for slot in proposal_slots {
+            let (block, _root) = self
+                .beacon_nodes
+                .first_success(RequireSynced::Yes, |beacon_node| async move {
+                    beacon_node.http.beacon().get_block_by_slot(slot).await
+                })
+                .await
+                .map_err(|e| format!("Failed to get block at slot {}: {:?}", slot, e))?;
+
+            // A skipped slot returns the most recent prior block.
+            if block.message.slot == slot {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rest_types::IndividualVote;
+    use types::test_utils::generate_deterministic_keypair;
+
+    const START_EPOCH: u64 = 10;
+    const DETECTION_EPOCHS: u64 = 2;
+
+    fn pubkey(i: usize) -> PublicKeyBytes {
+        generate_deterministic_keypair(i).pk.into()
+    }
+
+    fn vote_response(
+        i: usize,
+        is_current_epoch_attester: bool,
+        is_previous_epoch_attester: bool,
+    ) -> IndividualVotesResponse {
+        IndividualVotesResponse {
+            epoch: Epoch::new(START_EPOCH),
+            pubkey: pubkey(i),
+            validator_index: Some(i),
+            vote: Some(IndividualVote {
+                is_slashed: false,
+                is_withdrawable_in_current_epoch: false,
+                is_active_in_current_epoch: true,
+                is_active_in_previous_epoch: true,
+                current_epoch_effective_balance_gwei: 32_000_000_000,
+                is_current_epoch_attester,
+                is_current_epoch_target_attester: is_current_epoch_attester,
+                is_previous_epoch_attester,
+                is_previous_epoch_target_attester: is_previous_epoch_attester,
+                is_previous_epoch_head_attester: is_previous_epoch_attester,
+            }),
+        }
+    }
+
+    /// Votes where validator 0 is idle, 1 attested in the current epoch and 2 attested in the
+    /// previous epoch. Validator 3 is unknown to the beacon node.
+    fn votes() -> Vec<IndividualVotesResponse> {
+        vec![
+            vote_response(0, false, false),
+            vote_response(1, true, false),
+            vote_response(2, false, true),
+            IndividualVotesResponse {
+                epoch: Epoch::new(START_EPOCH),
+                pubkey: pubkey(3),
+                validator_index: None,
+                vote: None,
+            },
+        ]
+    }
+
+    #[test]
+    fn detects_attesters() {
+        let check = |current_epoch, previous_epoch| {
+            attesters(
+                votes(),
+                LivenessCheck {
+                    current_epoch,
+                    previous_epoch,
+                },
+            )
+        };
+
+        assert_eq!(check(true, true), vec![pubkey(1), pubkey(2)]);
+        assert_eq!(check(true, false), vec![pubkey(1)]);
+        assert_eq!(check(false, true), vec![pubkey(2)]);
+        assert_eq!(check(false, false), vec![]);
+    }
+
+    #[test]
+    fn enables_after_detection_epochs() {
+        let mut progress = DetectionProgress::new(Epoch::new(START_EPOCH), DETECTION_EPOCHS);
+        let epoch = |offset| Epoch::new(START_EPOCH + offset);
+
+        // The start epoch is never checked.
+        assert_eq!(progress.liveness_check(epoch(0)), None);
+
+        // The first epoch after the start epoch is checked, except for the attestations which
+        // were included for the start epoch.
+        assert_eq!(
+            progress.liveness_check(epoch(1)),
+            Some(LivenessCheck {
+                current_epoch: true,
+                previous_epoch: false,
+            })
+        );
+        progress.record_success();
+        assert!(!progress.is_complete());
+
+        assert_eq!(
+            progress.liveness_check(epoch(2)),
+            Some(LivenessCheck {
+                current_epoch: true,
+                previous_epoch: true,
+            })
+        );
+        progress.record_success();
+        assert!(
+            !progress.is_complete(),
+            "attestations for the last epoch may still be included in the next"
+        );
+        assert_eq!(progress.remaining_epochs(), 1);
+
+        // Only the attestations included for the last observed epoch remain to be checked.
+        assert_eq!(
+            progress.liveness_check(epoch(3)),
+            Some(LivenessCheck {
+                current_epoch: false,
+                previous_epoch: true,
+            })
+        );
+        progress.record_success();
+        assert!(progress.is_complete());
+    }
+
+    #[test]
+    fn failed_check_delays_detection() {
+        let mut progress = DetectionProgress::new(Epoch::new(START_EPOCH), DETECTION_EPOCHS);
+
+        for _ in 0..DETECTION_EPOCHS {
+            progress.record_success();
+        }
+
+        // If the attestations for the last observed epoch cannot be checked, that epoch must be
+        // observed again.
+        progress.record_failure();
+        assert_eq!(progress.remaining_epochs(), 2);
+        assert_eq!(
+            progress.liveness_check(Epoch::new(START_EPOCH + DETECTION_EPOCHS + 2)),
+            Some(LivenessCheck {
+                current_epoch: true,
+                previous_epoch: true,
+            })
+        );
+
+        progress.record_success();
+        assert!(!progress.is_complete());
+        progress.record_success();
+        assert!(progress.is_complete());
+    }
+}
@@ -1,8 +1,11 @@
-use crate::{is_synced::is_synced, validator_store::ValidatorStore};
+use crate::{
+    beacon_node_fallback::{BeaconNodeFallback, RequireSynced},
+    validator_store::ValidatorStore,
+};
 use environment::RuntimeContext;
 use futures::StreamExt;
 use parking_lot::RwLock;
-use remote_beacon_node::{PublishStatus, RemoteBeaconNode};
+use remote_beacon_node::PublishStatus;
 use rest_types::{ValidatorDuty, ValidatorDutyBytes, ValidatorSubscription};
 use slog::{debug, error, trace, warn};
 use slot_clock::SlotClock;
@@ -38,7 +41,7 @@ impl DutyAndProof {
     ///
     /// - `self.validator_pubkey` is not known in `validator_store`.
     /// - There's an arith error during computation.
-    pub fn compute_selection_proof<T: SlotClock + 'static, E: EthSpec>(
+    pub async fn compute_selection_proof<T: SlotClock + 'static, E: EthSpec>(
         &mut self,
         validator_store: &ValidatorStore<T, E>,
     ) -> Result<(), String> {
@@ -55,6 +58,7 @@ impl DutyAndProof {
 
         let selection_proof = validator_store
             .produce_selection_proof(&self.duty.validator_pubkey, slot)
+            .await
             .ok_or_else(|| "Failed to produce selection proof".to_string())?;
 
         self.selection_proof = selection_proof
@@ -241,29 +245,35 @@ impl DutiesStore {
         )
     }
 
-    fn insert<T: SlotClock + 'static, E: EthSpec>(
+    async fn insert<T: SlotClock + 'static, E: EthSpec>(
         &self,
         epoch: Epoch,
         mut duties: DutyAndProof,
         slots_per_epoch: u64,
         validator_store: &ValidatorStore<T, E>,
     ) -> Result<InsertOutcome, String> {
-        let mut store = self.store.write();
-
         if !duties_match_epoch(&duties.duty, epoch, slots_per_epoch) {
             return Ok(InsertOutcome::Invalid);
         }
 
+        if self.is_known(&duties, epoch) {
+            return Ok(InsertOutcome::Identical);
+        }
+
+        // Compute the selection proof before taking the write lock, since signing may involve a
+        // request to a remote signer.
+        duties.compute_selection_proof(validator_store).await?;
+
+        let mut store = self.store.write();
+
         // TODO: refactor with Entry.
 
         if let Some(validator_map) = store.get_mut(&duties.duty.validator_pubkey) {
             if let Some(known_duties) = validator_map.get_mut(&epoch) {
+                // The same duties may have been inserted whilst the selection proof was computed.
                 if known_duties.duty == duties.duty {
                     Ok(InsertOutcome::Identical)
                 } else {
-                    // Compute the selection proof.
-                    duties.compute_selection_proof(validator_store)?;
-
                     // Determine if a re-subscription is required.
                     let should_resubscribe = duties.subscription_eq(known_duties);
 
@@ -273,17 +283,11 @@ impl DutiesStore {
                     Ok(InsertOutcome::Replaced { should_resubscribe })
                 }
             } else {
-                // Compute the selection proof.
-                duties.compute_selection_proof(validator_store)?;
-
                 validator_map.insert(epoch, duties);
 
                 Ok(InsertOutcome::NewEpoch)
             }
         } else {
-            // Compute the selection proof.
-            duties.compute_selection_proof(validator_store)?;
-
             let validator_pubkey = duties.duty.validator_pubkey.clone();
 
             let mut validator_map = HashMap::new();
@@ -295,6 +299,34 @@ impl DutiesStore {
         }
     }
 
+    /// Returns `true` if identical duties are already stored for `epoch`.
+    fn is_known(&self, duties: &DutyAndProof, epoch: Epoch) -> bool {
+        self.store
+            .read()
+            .get(&duties.duty.validator_pubkey)
+            .and_then(|validator_map| validator_map.get(&epoch))
+            .map_or(false, |known_duties| known_duties.duty == duties.duty)
+    }
+
+    /// Returns the known duties of `validator_pubkey` for each epoch, in order of epoch.
+    fn validator_duties(&self, validator_pubkey: &PublicKey) -> Vec<(Epoch, DutyAndProof)> {
+        let mut duties = self
+            .store
+            .read()
+            .get(validator_pubkey)
+            .map(|validator_map| {
+                validator_map
+                    .iter()
+                    .map(|(epoch, duties)| (*epoch, duties.clone()))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        duties.sort_by_key(|(epoch, _duties)| *epoch);
+
+        duties
+    }
+
     fn prune(&self, prior_to: Epoch) {
         self.store
             .write()
@@ -308,7 +340,7 @@ impl DutiesStore {
 pub struct DutiesServiceBuilder<T, E: EthSpec> {
     validator_store: Option<ValidatorStore<T, E>>,
     slot_clock: Option<T>,
-    beacon_node: Option<RemoteBeaconNode<E>>,
+    beacon_nodes: Option<Arc<BeaconNodeFallback<T, E>>>,
     context: Option<RuntimeContext<E>>,
     allow_unsynced_beacon_node: bool,
 }
@@ -318,7 +350,7 @@ impl<T: SlotClock + 'static, E: EthSpec> DutiesServiceBuilder<T, E> {
         Self {
             validator_store: None,
             slot_clock: None,
-            beacon_node: None,
+            beacon_nodes: None,
             context: None,
             allow_unsynced_beacon_node: false,
         }
@@ -334,8 +366,8 @@ impl<T: SlotClock + 'static, E: EthSpec> DutiesServiceBuilder<T, E> {
         self
     }
 
-    pub fn beacon_node(mut self, beacon_node: RemoteBeaconNode<E>) -> Self {
-        self.beacon_node = Some(beacon_node);
+    pub fn beacon_nodes(mut self, beacon_nodes: Arc<BeaconNodeFallback<T, E>>) -> Self {
+        self.beacon_nodes = Some(beacon_nodes);
         self
     }
 
@@ -360,9 +392,9 @@ impl<T: SlotClock + 'static, E: EthSpec> DutiesServiceBuilder<T, E> {
                 slot_clock: self
                     .slot_clock
                     .ok_or_else(|| "Cannot build DutiesService without slot_clock")?,
-                beacon_node: self
-                    .beacon_node
-                    .ok_or_else(|| "Cannot build DutiesService without beacon_node")?,
+                beacon_nodes: self
+                    .beacon_nodes
+                    .ok_or_else(|| "Cannot build DutiesService without beacon_nodes")?,
                 context: self
                     .context
                     .ok_or_else(|| "Cannot build DutiesService without runtime_context")?,
@@ -377,10 +409,10 @@ pub struct Inner<T, E: EthSpec> {
     store: Arc<DutiesStore>,
     validator_store: ValidatorStore<T, E>,
     pub(crate) slot_clock: T,
-    pub(crate) beacon_node: RemoteBeaconNode<E>,
+    beacon_nodes: Arc<BeaconNodeFallback<T, E>>,
     context: RuntimeContext<E>,
-    /// If true, the duties service will poll for duties from the beacon node even if it is not
-    /// synced.
+    /// If true, the duties service will poll for duties from the beacon nodes even if none of them
+    /// are synced.
     allow_unsynced_beacon_node: bool,
 }
 
@@ -428,13 +460,49 @@ impl<T: SlotClock + 'static, E: EthSpec> DutiesService<T, E> {
     ///
     /// It is possible that multiple validators have an identical proposal slot, however that is
     /// likely the result of heavy forking (lol) or inconsistent beacon node connections.
+    ///
+    /// Validators which have been disabled since their duties were downloaded are excluded. No
+    /// validators are returned whilst doppelganger detection is in progress.
     pub fn block_producers(&self, slot: Slot) -> Vec<PublicKey> {
-        self.store.block_producers(slot, E::slots_per_epoch())
+        if self.validator_store.doppelganger_detection_pending() {
+            return vec![];
+        }
+
+        self.store
+            .block_producers(slot, E::slots_per_epoch())
+            .into_iter()
+            .filter(|validator_pubkey| self.validator_store.is_enabled(validator_pubkey))
+            .collect()
     }
 
     /// Returns all `ValidatorDuty` for the given `slot`.
+    ///
+    /// Validators which have been disabled since their duties were downloaded are excluded. No
+    /// validators are returned whilst doppelganger detection is in progress.
     pub fn attesters(&self, slot: Slot) -> Vec<DutyAndProof> {
-        self.store.attesters(slot, E::slots_per_epoch())
+        if self.validator_store.doppelganger_detection_pending() {
+            return vec![];
+        }
+
+        self.store
+            .attesters(slot, E::slots_per_epoch())
+            .into_iter()
+            .filter(|duties| self.validator_store.is_enabled(duties.validator_pubkey()))
+            .collect()
+    }
+
+    /// Returns all known duties of `validator_pubkey`, in order of epoch.
+    pub fn validator_duties(&self, validator_pubkey: &PublicKey) -> Vec<(Epoch, DutyAndProof)> {
+        self.store.validator_duties(validator_pubkey)
+    }
+
+    /// Returns `RequireSynced::No` if duties may be fetched from an unsynced beacon node.
+    fn require_synced(&self) -> RequireSynced {
+        if self.allow_unsynced_beacon_node {
+            RequireSynced::No
+        } else {
+            RequireSynced::Yes
+        }
     }
 
     /// Start the service that periodically polls the beacon node for validator duties.
@@ -477,9 +545,7 @@ impl<T: SlotClock + 'static, E: EthSpec> DutiesService<T, E> {
     async fn do_update(self) -> Result<(), ()> {
         let log = self.context.log();
 
-        if !is_synced(&self.beacon_node, &self.slot_clock, None).await
-            && !self.allow_unsynced_beacon_node
-        {
+        if self.beacon_nodes.num_synced() == 0 && self.require_synced() == RequireSynced::Yes {
             return Ok(());
         }
 
@@ -534,11 +600,16 @@ impl<T: SlotClock + 'static, E: EthSpec> DutiesService<T, E> {
     /// Attempt to download the duties of all managed validators for the given `epoch`.
     async fn update_epoch(self, epoch: Epoch) -> Result<(), String> {
         let pubkeys = self.validator_store.voting_pubkeys();
+        let pubkeys = pubkeys.as_slice();
         let all_duties = self
-            .beacon_node
-            .http
-            .validator()
-            .get_duties(epoch, pubkeys.as_slice())
+            .beacon_nodes
+            .first_success(self.require_synced(), |beacon_node| async move {
+                beacon_node
+                    .http
+                    .validator()
+                    .get_duties(epoch, pubkeys)
+                    .await
+            })
             .await
             .map_err(move |e| format!("Failed to get duties for epoch {}: {:?}", epoch, e))?;
 
@@ -552,70 +623,79 @@ impl<T: SlotClock + 'static, E: EthSpec> DutiesService<T, E> {
 
         // For each of the duties, attempt to insert them into our local store and build a
         // list of new or changed selections proofs for any aggregating validators.
-        let validator_subscriptions = all_duties
-            .into_iter()
-            .filter_map(|remote_duties| {
-                // Convert the remote duties into our local representation.
-                let duties: DutyAndProof = remote_duties
-                    .clone()
-                    .try_into()
-                    .map_err(|e| {
-                        error!(
-                            log,
-                            "Unable to convert remote duties";
-                            "error" => e
-                        )
-                    })
-                    .ok()?;
-
-                let validator_pubkey = duties.duty.validator_pubkey.clone();
-
-                // Attempt to update our local store.
-                let outcome = self
-                    .store
-                    .insert(epoch, duties, E::slots_per_epoch(), &self.validator_store)
-                    .map_err(|e| {
-                        error!(
-                            log,
-                            "Unable to store duties";
-                            "error" => e
-                        )
-                    })
-                    .ok()?;
+        let mut validator_subscriptions = vec![];
+        for remote_duties in all_duties {
+            // Convert the remote duties into our local representation.
+            let duties: DutyAndProof = match remote_duties.clone().try_into() {
+                Ok(duties) => duties,
+                Err(e) => {
+                    error!(
+                        log,
+                        "Unable to convert remote duties";
+                        "error" => e
+                    );
+                    continue;
+                }
+            };
 
-                match &outcome {
-                    InsertOutcome::NewValidator => {
-                        debug!(
-                            log,
-                            "First duty assignment for validator";
-                            "proposal_slots" => format!("{:?}", &remote_duties.block_proposal_slots),
-                            "attestation_slot" => format!("{:?}", &remote_duties.attestation_slot),
-                            "validator" => format!("{:?}", &remote_duties.validator_pubkey)
-                        );
-                        new_validator += 1;
-                    }
-                    InsertOutcome::NewEpoch => new_epoch += 1,
-                    InsertOutcome::Identical => identical += 1,
-                    InsertOutcome::Replaced { .. } => replaced += 1,
-                    InsertOutcome::Invalid => invalid += 1,
-                };
-
-                // The selection proof is computed on `store.insert`, so it's necessary to check
-                // with the store that the validator is an aggregator.
-                let is_aggregator = self.store.is_aggregator(&validator_pubkey, &epoch)?;
-
-                if outcome.is_subscription_candidate() {
-                    Some(ValidatorSubscription {
-                        validator_index: remote_duties.validator_index?,
-                        attestation_committee_index: remote_duties.attestation_committee_index?,
-                        slot: remote_duties.attestation_slot?,
+            let validator_pubkey = duties.duty.validator_pubkey.clone();
+
+            // Attempt to update our local store.
+            let outcome = match self
+                .store
+                .insert(epoch, duties, E::slots_per_epoch(), &self.validator_store)
+                .await
+            {
+                Ok(outcome) => outcome,
+                Err(e) => {
+                    error!(
+                        log,
+                        "Unable to store duties";
+                        "error" => e
+                    );
+                    continue;
+                }
+            };
+
+            match &outcome {
+                InsertOutcome::NewValidator => {
+                    debug!(
+                        log,
+                        "First duty assignment for validator";
+                        "proposal_slots" => format!("{:?}", &remote_duties.block_proposal_slots),
+                        "attestation_slot" => format!("{:?}", &remote_duties.attestation_slot),
+                        "validator" => format!("{:?}", &remote_duties.validator_pubkey)
+                    );
+                    new_validator += 1;
+                }
+                InsertOutcome::NewEpoch => new_epoch += 1,
+                InsertOutcome::Identical => identical += 1,
+                InsertOutcome::Replaced { .. } => replaced += 1,
+                InsertOutcome::Invalid => invalid += 1,
+            };
+
+            // The selection proof is computed on `store.insert`, so it's necessary to check
+            // with the store that the validator is an aggregator.
+            let is_aggregator = match self.store.is_aggregator(&validator_pubkey, &epoch) {
+                Some(is_aggregator) => is_aggregator,
+                None => continue,
+            };
+
+            if outcome.is_subscription_candidate() {
+                if let (Some(validator_index), Some(attestation_committee_index), Some(slot)) = (
+                    remote_duties.validator_index,
+                    remote_duties.attestation_committee_index,
+                    remote_duties.attestation_slot,
+                ) {
+                    validator_subscriptions.push(ValidatorSubscription {
+                        validator_index,
+                        attestation_committee_index,
+                        slot,
                         is_aggregator,
-                    })
-                } else {
-                    None
+                    });
                 }
-            })
-            .collect::<Vec<_>>();
+            }
+        }
 
         if invalid > 0 {
             error!(
@@ -652,10 +732,18 @@ impl<T: SlotClock + 'static, E: EthSpec> DutiesService<T, E> {
 
             Ok(())
         } else {
-            self.beacon_node
-                .http
-                .validator()
-                .subscribe(validator_subscriptions)
+            // Subscribe on all nodes, so that any of them may be used to produce aggregates.
+            self.beacon_nodes
+                .broadcast(RequireSynced::No, |beacon_node| {
+                    let validator_subscriptions = validator_subscriptions.clone();
+                    async move {
+                        beacon_node
+                            .http
+                            .validator()
+                            .subscribe(validator_subscriptions)
+                            .await
+                    }
+                })
                 .await
                 .map_err(|e| format!("Failed to subscribe validators: {:?}", e))
                 .map(move |status| {
@@ -0,0 +1,100 @@
+use rand::Rng;
+use remote_validator_client::API_TOKEN_FILENAME;
+use std::fs::{self, File};
+use std::io::Write;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+
+/// The number of random bytes in an API token.
+const TOKEN_LEN: usize = 32;
+
+/// The bearer token which must be supplied with every request to the HTTP API.
+///
+/// The token is stored in `API_TOKEN_FILENAME` in the validator client data directory so that it
+/// persists across restarts and may be read by tools running on the same machine.
+pub struct ApiSecret {
+    token: String,
+    path: PathBuf,
+}
+
+impl ApiSecret {
+    /// Reads the API token from `data_dir`, generating a new random token if none exists.
+    pub fn create_or_open<P: AsRef<Path>>(data_dir: P) -> Result<Self, String> {
+        let path = data_dir.as_ref().join(API_TOKEN_FILENAME);
+
+        if !path.exists() {
+            let bytes: [u8; TOKEN_LEN] = rand::thread_rng().gen();
+            write_token_to_file(&path, &hex::encode(bytes))?;
+        }
+
+        let token = fs::read_to_string(&path)
+            .map_err(|e| format!("Unable to read API token from {:?}: {:?}", path, e))?
+            .trim()
+            .to_string();
+
+        if token.is_empty() {
+            return Err(format!("The API token in {:?} is empty", path));
+        }
+
+        Ok(Self { token, path })
+    }
+
+    /// The path of the file containing the API token.
+    pub fn token_path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns `true` if the value of an `Authorization` header is `Bearer <token>`.
+    pub fn is_authorized(&self, header: &[u8]) -> bool {
+        let expected = format!("Bearer {}", self.token);
+        constant_time_eq(header, expected.as_bytes())
+    }
+}
+
+/// Creates a file with `600 (-rw-------)` permissions containing `token`.
+fn write_token_to_file(path: &Path, token: &str) -> Result<(), String> {
+    let mut file =
+        File::create(path).map_err(|e| format!("Unable to create {:?}: {:?}", path, e))?;
+
+    let mut perm = file
+        .metadata()
+        .map_err(|e| format!("Unable to read metadata of {:?}: {:?}", path, e))?
+        .permissions();
+
+    perm.set_mode(0o600);
+
+    file.set_permissions(perm)
+        .map_err(|e| format!("Unable to set permissions of {:?}: {:?}", path, e))?;
+
+    file.write_all(token.as_bytes())
+        .map_err(|e| format!("Unable to write API token to {:?}: {:?}", path, e))
+}
+
+/// Compares `a` and `b` in time which is independent of their contents, so that a token cannot be
+/// guessed byte-by-byte by timing the responses.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempdir::TempDir;
+
+    #[test]
+    fn create_then_open() {
+        let dir = TempDir::new("api_secret").unwrap();
+
+        let created = ApiSecret::create_or_open(dir.path()).unwrap();
+        let opened = ApiSecret::create_or_open(dir.path()).unwrap();
+
+        assert_eq!(created.token, opened.token);
+        assert_eq!(created.token.len(), TOKEN_LEN * 2);
+
+        let header = format!("Bearer {}", created.token);
+        assert!(opened.is_authorized(header.as_bytes()));
+        assert!(!opened.is_authorized(created.token.as_bytes()));
+        assert!(!opened.is_authorized(b"Bearer "));
+        assert!(!opened.is_authorized(b""));
+    }
+}
@@ -0,0 +1,600 @@
+//! An HTTP API which allows the validators of a running validator client to be inspected and
+//! managed.
+//!
+//! Every request must carry an `Authorization: Bearer <token>` header, where the token is read
+//! from (or generated in) the validator client data directory. See `ApiSecret`.
+
+mod api_secret;
+
+use crate::duties_service::{DutiesService, DutyAndProof};
+use crate::validator_store::ValidatorStore;
+use environment::TaskExecutor;
+use futures::future::TryFutureExt;
+use hyper::header::AUTHORIZATION;
+use hyper::server::conn::AddrStream;
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Method, Request, Response, Server, StatusCode};
+use remote_validator_client::types::{
+    KeystoreValidatorsPostRequest, ValidatorData, ValidatorDutyData, ValidatorPatchRequest,
+};
+use serde::de::DeserializeOwned;
+use serde_derive::{Deserialize, Serialize};
+use slog::{info, warn};
+use slot_clock::SlotClock;
+use std::net::{Ipv4Addr, SocketAddr};
+use std::sync::Arc;
+use types::{Epoch, EthSpec, PublicKey, PublicKeyBytes};
+
+pub use api_secret::ApiSecret;
+
+/// Validator client HTTP API configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Config {
+    /// Enable the HTTP API server.
+    pub enabled: bool,
+    /// The IPv4 address the HTTP API server will listen on.
+    pub listen_address: Ipv4Addr,
+    /// The port the HTTP API server will listen on.
+    pub port: u16,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            enabled: false,
+            listen_address: Ipv4Addr::new(127, 0, 0, 1),
+            port: 5062,
+        }
+    }
+}
+
+/// The state shared between all requests to the HTTP API.
+pub struct Context<T, E: EthSpec> {
+    pub api_secret: ApiSecret,
+    pub validator_store: ValidatorStore<T, E>,
+    pub duties_service: DutiesService<T, E>,
+    pub executor: TaskExecutor,
+}
+
+#[derive(PartialEq, Debug, Clone)]
+enum ApiError {
+    BadRequest(String),
+    Unauthorized(String),
+    NotFound(String),
+    ServerError(String),
+}
+
+type ApiResult = Result<Response<Body>, ApiError>;
+
+impl Into<Response<Body>> for ApiError {
+    fn into(self) -> Response<Body> {
+        let (status_code, desc) = match self {
+            ApiError::BadRequest(desc) => (StatusCode::BAD_REQUEST, desc),
+            ApiError::Unauthorized(desc) => (StatusCode::UNAUTHORIZED, desc),
+            ApiError::NotFound(desc) => (StatusCode::NOT_FOUND, desc),
+            ApiError::ServerError(desc) => (StatusCode::INTERNAL_SERVER_ERROR, desc),
+        };
+        Response::builder()
+            .status(status_code)
+            .header("content-type", "text/plain; charset=utf-8")
+            .body(Body::from(desc))
+            .expect("Response should always be created.")
+    }
+}
+
+/// Starts the HTTP API server, returning the address it is listening on.
+///
+/// The server shuts down when the `executor` exits.
+pub fn start_server<T: SlotClock + 'static, E: EthSpec>(
+    executor: TaskExecutor,
+    config: &Config,
+    ctx: Arc<Context<T, E>>,
+) -> Result<SocketAddr, String> {
+    let log = executor.log().clone();
+
+    let make_service = make_service_fn(move |_socket: &AddrStream| {
+        let ctx = ctx.clone();
+
+        async move {
+            Ok::<_, hyper::Error>(service_fn(move |req: Request<Body>| {
+                let ctx = ctx.clone();
+                async move {
+                    let response = route(req, ctx).await.unwrap_or_else(Into::into);
+                    Ok::<_, hyper::Error>(response)
+                }
+            }))
+        }
+    });
+
+    let bind_addr = (config.listen_address, config.port).into();
+    let server = Server::try_bind(&bind_addr)
+        .map_err(|e| format!("Unable to bind HTTP API to {}: {:?}", bind_addr, e))?
+        .serve(make_service);
+
+    // Determine the address the server is actually listening on.
+    //
+    // This may be different to `bind_addr` if bind port was 0 (this allows the OS to choose a free
+    // port).
+    let actual_listen_addr = server.local_addr();
+
+    let exit = executor.exit();
+    let inner_log = log.clone();
+    let server_future = server
+        .with_graceful_shutdown(async move {
+            let _ = exit.await;
+            info!(inner_log, "HTTP API shutdown");
+        })
+        .map_err(move |e| warn!(log, "HTTP API failed"; "error" => format!("{:?}", e)))
+        .unwrap_or_else(|_| ());
+
+    executor.spawn_without_exit(server_future, "http_api");
+
+    Ok(actual_listen_addr)
+}
+
+async fn route<T: SlotClock + 'static, E: EthSpec>(
+    req: Request<Body>,
+    ctx: Arc<Context<T, E>>,
+) -> ApiResult {
+    let authorized = req.headers().get(AUTHORIZATION).map_or(false, |header| {
+        ctx.api_secret.is_authorized(header.as_bytes())
+    });
+    if !authorized {
+        return Err(ApiError::Unauthorized(
+            "Missing or invalid API token".to_string(),
+        ));
+    }
+
+    let method = req.method().clone();
+    let path = req.uri().path().trim_matches('/').to_string();
+    let segments = path.split('/').collect::<Vec<_>>();
+
+    match (method, segments.as_slice()) {
+        (Method::GET, ["lighthouse", "validators"]) => get_validators(&ctx),
+        (Method::POST, ["lighthouse", "validators", "keystore"]) => {
+            post_validators_keystore(req, ctx).await
+        }
+        (Method::GET, ["lighthouse", "validators", pubkey]) => {
+            get_validator(&ctx, &parse_pubkey(pubkey)?)
+        }
+        (Method::PATCH, ["lighthouse", "validators", pubkey]) => {
+            let pubkey = parse_pubkey(pubkey)?;
+            patch_validator(req, ctx, pubkey).await
+        }
+        (Method::GET, ["lighthouse", "validators", pubkey, "duties"]) => {
+            get_validator_duties(&ctx, &parse_pubkey(pubkey)?)
+        }
+        (Method::GET, ["lighthouse", "validators", pubkey, "publish_results"]) => {
+            get_validator_publish_results(&ctx, &parse_pubkey(pubkey)?)
+        }
+        _ => Err(ApiError::NotFound(
+            "Request path and/or method not found.".to_string(),
+        )),
+    }
+}
+
+/// `GET lighthouse/validators`
+fn get_validators<T: SlotClock + 'static, E: EthSpec>(ctx: &Context<T, E>) -> ApiResult {
+    let validators = ctx
+        .validator_store
+        .validator_statuses()
+        .into_iter()
+        .map(|(pubkey, enabled)| validator_data(pubkey, enabled))
+        .collect::<Vec<_>>();
+
+    json_response(&validators)
+}
+
+/// `GET lighthouse/validators/{pubkey}`
+fn get_validator<T: SlotClock + 'static, E: EthSpec>(
+    ctx: &Context<T, E>,
+    pubkey: &PublicKey,
+) -> ApiResult {
+    let enabled = validator_enabled(ctx, pubkey)?;
+    json_response(&validator_data(pubkey.clone(), enabled))
+}
+
+/// `PATCH lighthouse/validators/{pubkey}`
+async fn patch_validator<T: SlotClock + 'static, E: EthSpec>(
+    req: Request<Body>,
+    ctx: Arc<Context<T, E>>,
+    pubkey: PublicKey,
+) -> ApiResult {
+    let request: ValidatorPatchRequest = parse_json_body(req).await?;
+
+    validator_enabled(&ctx, &pubkey)?;
+    ctx.validator_store
+        .set_validator_enabled(&pubkey, request.enabled)
+        .map_err(ApiError::ServerError)?;
+
+    json_response(&validator_data(pubkey, request.enabled))
+}
+
+/// `POST lighthouse/validators/keystore`
+async fn post_validators_keystore<T: SlotClock + 'static, E: EthSpec>(
+    req: Request<Body>,
+    ctx: Arc<Context<T, E>>,
+) -> ApiResult {
+    let request: KeystoreValidatorsPostRequest = parse_json_body(req).await?;
+    let enabled = request.enable;
+
+    // Decrypting the keystore is slow, so it must not block the executor.
+    let validator_store = ctx.validator_store.clone();
+    let pubkey = ctx
+        .executor
+        .runtime_handle()
+        .spawn_blocking(move || {
+            validator_store.import_keystore(request.keystore, request.password.as_bytes(), enabled)
+        })
+        .await
+        .map_err(|e| ApiError::ServerError(format!("Keystore import task failed: {:?}", e)))?
+        .map_err(ApiError::BadRequest)?;
+
+    json_response(&validator_data(pubkey, enabled))
+}
+
+/// `GET lighthouse/validators/{pubkey}/duties`
+fn get_validator_duties<T: SlotClock + 'static, E: EthSpec>(
+    ctx: &Context<T, E>,
+    pubkey: &PublicKey,
+) -> ApiResult {
+    validator_enabled(ctx, pubkey)?;
+
+    let duties = ctx
+        .duties_service
+        .validator_duties(pubkey)
+        .into_iter()
+        .map(|(epoch, duty)| validator_duty_data(epoch, duty))
+        .collect::<Vec<_>>();
+
+    json_response(&duties)
+}
+
+/// `GET lighthouse/validators/{pubkey}/publish_results`
+fn get_validator_publish_results<T: SlotClock + 'static, E: EthSpec>(
+    ctx: &Context<T, E>,
+    pubkey: &PublicKey,
+) -> ApiResult {
+    validator_enabled(ctx, pubkey)?;
+    json_response(&ctx.validator_store.publish_results(pubkey))
+}
+
+/// Returns whether the validator is enabled, or a `NotFound` error if it is unknown.
+fn validator_enabled<T: SlotClock + 'static, E: EthSpec>(
+    ctx: &Context<T, E>,
+    pubkey: &PublicKey,
+) -> Result<bool, ApiError> {
+    ctx.validator_store
+        .validator_enabled(pubkey)
+        .ok_or_else(|| ApiError::NotFound(format!("Unknown validator {}", pubkey.as_hex_string())))
+}
+
+fn validator_data(pubkey: PublicKey, enabled: bool) -> ValidatorData {
+    ValidatorData {
+        enabled,
+        voting_pubkey: PublicKeyBytes::from(pubkey),
+    }
+}
+
+fn validator_duty_data(epoch: Epoch, duty_and_proof: DutyAndProof) -> ValidatorDutyData {
+    let duty = duty_and_proof.duty;
+    ValidatorDutyData {
+        epoch,
+        validator_index: duty.validator_index,
+        attestation_slot: duty.attestation_slot,
+        attestation_committee_index: duty.attestation_committee_index,
+        attestation_committee_position: duty.attestation_committee_position,
+        block_proposal_slots: duty.block_proposal_slots,
+        is_aggregator: duty_and_proof.selection_proof.is_some(),
+    }
+}
+
+/// Parses a 0x-prefixed, hex-encoded BLS public key.
+fn parse_pubkey(string: &str) -> Result<PublicKey, ApiError> {
+    const PREFIX: &str = "0x";
+    if !string.starts_with(PREFIX) {
+        return Err(ApiError::BadRequest(
+            "Public key must have a 0x prefix".to_string(),
+        ));
+    }
+
+    let bytes = hex::decode(string.trim_start_matches(PREFIX))
+        .map_err(|e| ApiError::BadRequest(format!("Invalid hex string: {:?}", e)))?;
+    PublicKey::from_bytes(&bytes)
+        .map_err(|e| ApiError::BadRequest(format!("Unable to deserialize public key: {:?}", e)))
+}
+
+async fn parse_json_body<T: DeserializeOwned>(req: Request<Body>) -> Result<T, ApiError> {
+    let bytes = hyper::body::to_bytes(req.into_body())
+        .await
+        .map_err(|e| ApiError::ServerError(format!("Unable to get request body: {:?}", e)))?;
+    serde_json::from_slice(&bytes)
+        .map_err(|e| ApiError::BadRequest(format!("Unable to parse JSON body: {:?}", e)))
+}
+
+fn json_response<T: serde::Serialize>(item: &T) -> ApiResult {
+    let body = serde_json::to_vec(item)
+        .map_err(|e| ApiError::ServerError(format!("Unable to serialize response: {:?}", e)))?;
+    Response::builder()
+        .status(StatusCode::OK)
+        .header("content-type", "application/json")
+        .body(Body::from(body))
+        .map_err(|e| ApiError::ServerError(format!("Unable to build response: {:?}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::beacon_node_fallback::BeaconNodeFallback;
+    use crate::config::Config as ValidatorClientConfig;
+    use crate::duties_service::DutiesServiceBuilder;
+    use crate::fork_service::ForkServiceBuilder;
+    use environment::RuntimeContext;
+    use eth2_config::Eth2Config;
+    use eth2_keystore::{KdfParams, Keystore, KeystoreBuilder};
+    use remote_validator_client::{
+        types::KeystoreValidatorsPostRequest, Error as ClientError, ValidatorClientHttpClient,
+    };
+    use slog::{o, Logger};
+    use slot_clock::TestingSlotClock;
+    use std::time::Duration;
+    use tempdir::TempDir;
+    use tokio::runtime::Handle;
+    use types::{
+        test_utils::generate_deterministic_keypair, Hash256, MinimalEthSpec, PublicKeyBytes, Slot,
+    };
+    use validator_dir::DISABLED_FILE;
+
+    type E = MinimalEthSpec;
+
+    const PASSWORD: &str = "password";
+
+    /// Runs the HTTP API for a validator client with no validators.
+    struct Tester {
+        client: ValidatorClientHttpClient,
+        url: String,
+        validator_store: ValidatorStore<TestingSlotClock, E>,
+        validators_dir: TempDir,
+        secrets_dir: TempDir,
+        _exit_signal: exit_future::Signal,
+    }
+
+    impl Tester {
+        fn new() -> Self {
+            let validators_dir = TempDir::new("validators").unwrap();
+            let secrets_dir = TempDir::new("secrets").unwrap();
+
+            let log = Logger::root(slog::Discard, o!());
+            let (exit_signal, exit) = exit_future::signal();
+            let (shutdown_tx, _) = futures::channel::mpsc::channel(1);
+            let context = RuntimeContext {
+                executor: TaskExecutor::new(Handle::current(), exit, log.clone(), shutdown_tx),
+                eth_spec_instance: E::default(),
+                eth2_config: Eth2Config::minimal(),
+            };
+
+            let slot_clock =
+                TestingSlotClock::new(Slot::new(0), Duration::from_secs(0), Duration::from_secs(1));
+            let beacon_nodes = Arc::new(BeaconNodeFallback::new(
+                vec![],
+                slot_clock.clone(),
+                log.clone(),
+            ));
+
+            let config = ValidatorClientConfig {
+                data_dir: validators_dir.path().into(),
+                secrets_dir: secrets_dir.path().into(),
+                ..ValidatorClientConfig::default()
+            };
+
+            let fork_service = ForkServiceBuilder::new()
+                .slot_clock(slot_clock.clone())
+                .beacon_nodes(beacon_nodes.clone())
+                .runtime_context(context.clone())
+                .build()
+                .unwrap();
+            let validator_store = ValidatorStore::load_from_disk(
+                &config,
+                Hash256::zero(),
+                context.eth2_config.spec.clone(),
+                fork_service,
+                log,
+            )
+            .unwrap();
+            let duties_service = DutiesServiceBuilder::new()
+                .validator_store(validator_store.clone())
+                .slot_clock(slot_clock)
+                .beacon_nodes(beacon_nodes)
+                .runtime_context(context.clone())
+                .build()
+                .unwrap();
+
+            let api_secret = ApiSecret::create_or_open(validators_dir.path()).unwrap();
+            let api_token = remote_validator_client::read_api_token(validators_dir.path()).unwrap();
+
+            let ctx = Arc::new(Context {
+                api_secret,
+                validator_store: validator_store.clone(),
+                duties_service,
+                executor: context.executor.clone(),
+            });
+            let http_config = Config {
+                enabled: true,
+                listen_address: Ipv4Addr::new(127, 0, 0, 1),
+                port: 0,
+            };
+            let listen_addr = start_server(context.executor, &http_config, ctx).unwrap();
+            let url = format!("http://{}/", listen_addr);
+
+            Self {
+                client: ValidatorClientHttpClient::new(&url, api_token).unwrap(),
+                url,
+                validator_store,
+                validators_dir,
+                secrets_dir,
+                _exit_signal: exit_signal,
+            }
+        }
+
+        fn client_with_token(&self, api_token: &str) -> ValidatorClientHttpClient {
+            ValidatorClientHttpClient::new(&self.url, api_token.to_string()).unwrap()
+        }
+
+        fn validator_dir_path(&self, pubkey: &PublicKey) -> std::path::PathBuf {
+            self.validators_dir.path().join(pubkey.as_hex_string())
+        }
+    }
+
+    /// Returns a keystore for the deterministic keypair `i`, using a cheap KDF.
+    fn keystore(i: usize) -> (Keystore, PublicKey) {
+        let keypair = generate_deterministic_keypair(i);
+        let keystore = KeystoreBuilder::new(&keypair, PASSWORD.as_bytes(), "".into())
+            .unwrap()
+            .kdf_params(&KdfParams::Pbkdf2 { c: 2 })
+            .build()
+            .unwrap();
+        (keystore, keypair.pk)
+    }
+
+    fn import_request(keystore: Keystore, enable: bool) -> KeystoreValidatorsPostRequest {
+        KeystoreValidatorsPostRequest {
+            password: PASSWORD.to_string(),
+            enable,
+            keystore,
+        }
+    }
+
+    fn assert_status(result: Result<impl std::fmt::Debug, ClientError>, expected: StatusCode) {
+        match result {
+            Err(ClientError::DidNotSucceed { status, .. }) => {
+                assert_eq!(status.as_u16(), expected.as_u16())
+            }
+            other => panic!("expected status {}, got {:?}", expected, other),
+        }
+    }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn missing_or_wrong_token_is_rejected() {
+        let tester = Tester::new();
+
+        assert_status(
+            tester.client_with_token("wrong").get_validators().await,
+            StatusCode::UNAUTHORIZED,
+        );
+        assert_status(
+            tester.client_with_token("").get_validators().await,
+            StatusCode::UNAUTHORIZED,
+        );
+
+        let response = reqwest::get(&format!("{}lighthouse/validators", tester.url))
+            .await
+            .unwrap();
+        assert_eq!(
+            response.status().as_u16(),
+            StatusCode::UNAUTHORIZED.as_u16()
+        );
+
+        assert_eq!(tester.client.get_validators().await.unwrap(), vec![]);
+    }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn import_keystore() {
+        let tester = Tester::new();
+        let (keystore, pubkey) = keystore(0);
+        let pubkey_bytes = PublicKeyBytes::from(pubkey.clone());
+
+        let data = tester
+            .client
+            .post_validators_keystore(&import_request(keystore.clone(), true))
+            .await
+            .unwrap();
+        assert_eq!(data, validator_data(pubkey.clone(), true));
+
+        assert_eq!(
+            tester.client.get_validators().await.unwrap(),
+            vec![data.clone()]
+        );
+        assert_eq!(
+            tester.client.get_validator(&pubkey_bytes).await.unwrap(),
+            Some(data)
+        );
+        assert!(tester.validator_store.is_enabled(&pubkey));
+        assert!(tester.validator_dir_path(&pubkey).exists());
+        assert!(tester
+            .secrets_dir
+            .path()
+            .join(pubkey.as_hex_string())
+            .exists());
+
+        // A validator cannot be imported twice.
+        assert_status(
+            tester
+                .client
+                .post_validators_keystore(&import_request(keystore, true))
+                .await,
+            StatusCode::BAD_REQUEST,
+        );
+    }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn import_keystore_wrong_password() {
+        let tester = Tester::new();
+        let (keystore, pubkey) = keystore(0);
+
+        let mut request = import_request(keystore, true);
+        request.password = "wrong".to_string();
+
+        assert_status(
+            tester.client.post_validators_keystore(&request).await,
+            StatusCode::BAD_REQUEST,
+        );
+        assert_eq!(tester.client.get_validators().await.unwrap(), vec![]);
+        assert!(!tester.validator_dir_path(&pubkey).exists());
+        assert!(!tester
+            .secrets_dir
+            .path()
+            .join(pubkey.as_hex_string())
+            .exists());
+    }
+
+    #[tokio::test(threaded_scheduler)]
+    async fn enable_and_disable() {
+        let tester = Tester::new();
+        let (keystore, pubkey) = keystore(0);
+        let pubkey_bytes = PublicKeyBytes::from(pubkey.clone());
+        let disabled_file = tester.validator_dir_path(&pubkey).join(DISABLED_FILE);
+
+        let data = tester
+            .client
+            .post_validators_keystore(&import_request(keystore, false))
+            .await
+            .unwrap();
+        assert!(!data.enabled);
+        assert!(!tester.validator_store.is_enabled(&pubkey));
+        assert!(disabled_file.exists());
+
+        for &enabled in &[true, false, true] {
+            let data = tester
+                .client
+                .patch_validator(&pubkey_bytes, enabled)
+                .await
+                .unwrap();
+            assert_eq!(data, validator_data(pubkey.clone(), enabled));
+
+            assert_eq!(
+                tester.client.get_validator(&pubkey_bytes).await.unwrap(),
+                Some(data)
+            );
+            assert_eq!(tester.validator_store.is_enabled(&pubkey), enabled);
+            assert_eq!(disabled_file.exists(), !enabled);
+        }
+
+        // Unknown validators cannot be enabled.
+        let unknown = PublicKeyBytes::from(generate_deterministic_keypair(1).pk);
+        assert_status(
+            tester.client.patch_validator(&unknown, true).await,
+            StatusCode::NOT_FOUND,
+        );
+        assert_eq!(tester.client.get_validator(&unknown).await.unwrap(), None);
+    }
+}
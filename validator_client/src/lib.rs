@@ -1,37 +1,47 @@
 mod attestation_service;
+mod beacon_node_fallback;
 mod block_service;
 mod cli;
 mod config;
+mod doppelganger_service;
 mod duties_service;
 mod fork_service;
+mod http_api;
 mod is_synced;
 mod notifier;
+mod signer;
 mod validator_store;
 
 pub use cli::cli_app;
 pub use config::Config;
 
 use attestation_service::{AttestationService, AttestationServiceBuilder};
+use beacon_node_fallback::{
+    start_fallback_updater_service, BeaconNodeFallback, CandidateBeaconNode,
+};
 use block_service::{BlockService, BlockServiceBuilder};
 use clap::ArgMatches;
 use config::SLASHING_PROTECTION_FILENAME;
+use doppelganger_service::{DoppelgangerService, DoppelgangerServiceBuilder};
 use duties_service::{DutiesService, DutiesServiceBuilder};
 use environment::RuntimeContext;
 use fork_service::{ForkService, ForkServiceBuilder};
+use http_api::ApiSecret;
 use notifier::spawn_notifier;
 use remote_beacon_node::RemoteBeaconNode;
 use slog::{error, info, warn, Logger};
 use slot_clock::SlotClock;
 use slot_clock::SystemTimeSlotClock;
+use std::sync::Arc;
 use std::time::{SystemTime, UNIX_EPOCH};
 use tokio::time::{delay_for, Duration};
 use types::EthSpec;
 use validator_store::ValidatorStore;
 
-/// The interval between attempts to contact the beacon node during startup.
+/// The interval between attempts to contact the beacon nodes during startup.
 const RETRY_DELAY: Duration = Duration::from_secs(2);
 
-/// The global timeout for HTTP requests to the beacon node.
+/// The global timeout for HTTP requests to the beacon nodes.
 const HTTP_TIMEOUT: Duration = Duration::from_secs(12);
 
 pub struct ProductionValidatorClient<T: EthSpec> {
@@ -40,6 +50,9 @@ pub struct ProductionValidatorClient<T: EthSpec> {
     fork_service: ForkService<SystemTimeSlotClock, T>,
     block_service: BlockService<SystemTimeSlotClock, T>,
     attestation_service: AttestationService<SystemTimeSlotClock, T>,
+    doppelganger_service: Option<DoppelgangerService<SystemTimeSlotClock, T>>,
+    beacon_nodes: Arc<BeaconNodeFallback<SystemTimeSlotClock, T>>,
+    validator_store: ValidatorStore<SystemTimeSlotClock, T>,
     config: Config,
 }
 
@@ -66,7 +79,7 @@ impl<T: EthSpec> ProductionValidatorClient<T> {
         info!(
             log_1,
             "Starting validator client";
-            "beacon_node" => &config.http_server,
+            "beacon_nodes" => format!("{:?}", &config.beacon_nodes),
             "datadir" => format!("{:?}", config.data_dir),
         );
 
@@ -78,12 +91,25 @@ impl<T: EthSpec> ProductionValidatorClient<T> {
             );
         }
 
-        let beacon_node =
-            RemoteBeaconNode::new_with_timeout(config.http_server.clone(), HTTP_TIMEOUT)
-                .map_err(|e| format!("Unable to init beacon node http client: {}", e))?;
+        let candidates = config
+            .beacon_nodes
+            .iter()
+            .map(|endpoint| {
+                RemoteBeaconNode::new_with_timeout(endpoint.clone(), HTTP_TIMEOUT)
+                    .map(|beacon_node| CandidateBeaconNode::new(endpoint.clone(), beacon_node))
+                    .map_err(|e| {
+                        format!(
+                            "Unable to init beacon node http client for {}: {}",
+                            endpoint, e
+                        )
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
 
         // TODO: check if all logs in wait_for_node are produed while awaiting
-        let beacon_node = wait_for_node(beacon_node, log_2).await?;
+        //
+        // The chain parameters are read from the first node which responds.
+        let beacon_node = wait_for_node(&candidates, log_2).await?;
         let eth2_config = beacon_node
             .http
             .spec()
@@ -158,9 +184,17 @@ impl<T: EthSpec> ProductionValidatorClient<T> {
             Duration::from_millis(context.eth2_config.spec.milliseconds_per_slot),
         );
 
+        let beacon_nodes = Arc::new(BeaconNodeFallback::new(
+            candidates,
+            slot_clock.clone(),
+            context.service_context("fallback".into()).log().clone(),
+        ));
+        // Determine the status of each beacon node before any of the services start.
+        beacon_nodes.update_all_candidates().await;
+
         let fork_service = ForkServiceBuilder::new()
             .slot_clock(slot_clock.clone())
-            .beacon_node(beacon_node.clone())
+            .beacon_nodes(beacon_nodes.clone())
             .runtime_context(context.service_context("fork".into()))
             .build()?;
 
@@ -188,7 +222,7 @@ impl<T: EthSpec> ProductionValidatorClient<T> {
         let duties_service = DutiesServiceBuilder::new()
             .slot_clock(slot_clock.clone())
             .validator_store(validator_store.clone())
-            .beacon_node(beacon_node.clone())
+            .beacon_nodes(beacon_nodes.clone())
             .runtime_context(context.service_context("duties".into()))
             .allow_unsynced_beacon_node(config.allow_unsynced_beacon_node)
             .build()?;
@@ -197,15 +231,30 @@ impl<T: EthSpec> ProductionValidatorClient<T> {
             .duties_service(duties_service.clone())
             .slot_clock(slot_clock.clone())
             .validator_store(validator_store.clone())
-            .beacon_node(beacon_node.clone())
+            .beacon_nodes(beacon_nodes.clone())
             .runtime_context(context.service_context("block".into()))
             .build()?;
 
+        let doppelganger_service = if config.enable_doppelganger_protection {
+            Some(
+                DoppelgangerServiceBuilder::new()
+                    .validator_store(validator_store.clone())
+                    .duties_service(duties_service.clone())
+                    .slot_clock(slot_clock.clone())
+                    .beacon_nodes(beacon_nodes.clone())
+                    .runtime_context(context.service_context("doppelganger".into()))
+                    .detection_epochs(config.doppelganger_detection_epochs)
+                    .build()?,
+            )
+        } else {
+            None
+        };
+
         let attestation_service = AttestationServiceBuilder::new()
             .duties_service(duties_service.clone())
             .slot_clock(slot_clock)
-            .validator_store(validator_store)
-            .beacon_node(beacon_node)
+            .validator_store(validator_store.clone())
+            .beacon_nodes(beacon_nodes.clone())
             .runtime_context(context.service_context("attestation".into()))
             .build()?;
 
@@ -215,6 +264,9 @@ impl<T: EthSpec> ProductionValidatorClient<T> {
             fork_service,
             block_service,
             attestation_service,
+            doppelganger_service,
+            beacon_nodes,
+            validator_store,
             config,
         })
     }
@@ -240,47 +292,95 @@ impl<T: EthSpec> ProductionValidatorClient<T> {
             .start_update_service(&self.context.eth2_config.spec)
             .map_err(|e| format!("Unable to start attestation service: {}", e))?;
 
+        if let Some(doppelganger_service) = &self.doppelganger_service {
+            doppelganger_service
+                .clone()
+                .start_update_service(&self.context.eth2_config.spec)
+                .map_err(|e| format!("Unable to start doppelganger service: {}", e))?;
+        }
+
+        start_fallback_updater_service(
+            self.context.service_context("fallback".into()),
+            self.beacon_nodes.clone(),
+            &self.context.eth2_config.spec,
+        )
+        .map_err(|e| format!("Unable to start beacon node fallback service: {}", e))?;
+
         spawn_notifier(self).map_err(|e| format!("Failed to start notifier: {}", e))?;
 
+        if self.config.http_api.enabled {
+            let api_secret = ApiSecret::create_or_open(&self.config.data_dir)?;
+            let http_context = self.context.service_context("http_api".into());
+            let log = http_context.log().clone();
+            let token_path = api_secret.token_path().to_path_buf();
+
+            let ctx = Arc::new(http_api::Context {
+                api_secret,
+                validator_store: self.validator_store.clone(),
+                duties_service: self.duties_service.clone(),
+                executor: http_context.executor.clone(),
+            });
+
+            let listen_addr =
+                http_api::start_server(http_context.executor, &self.config.http_api, ctx)
+                    .map_err(|e| format!("Unable to start HTTP API server: {}", e))?;
+
+            info!(
+                log,
+                "HTTP API started";
+                "address" => format!("{}", listen_addr.ip()),
+                "port" => listen_addr.port(),
+                "api_token_file" => format!("{:?}", token_path),
+            );
+        }
+
         Ok(())
     }
 }
 
-/// Request the version from the node, looping back and trying again on failure. Exit once the node
-/// has been contacted.
+/// Request the version from each of the nodes in turn, looping back and trying again on failure.
+/// Exit once any node has been contacted, returning that node.
 async fn wait_for_node<E: EthSpec>(
-    beacon_node: RemoteBeaconNode<E>,
+    candidates: &[CandidateBeaconNode<E>],
     log: Logger,
 ) -> Result<RemoteBeaconNode<E>, String> {
-    // Try to get the version string from the node, looping until success is returned.
-    loop {
-        let log = log.clone();
-        let result = beacon_node
-            .clone()
-            .http
-            .node()
-            .get_version()
-            .await
-            .map_err(|e| format!("{:?}", e));
-
-        match result {
-            Ok(version) => {
-                info!(
-                    log,
-                    "Connected to beacon node";
-                    "version" => version,
-                );
+    if candidates.is_empty() {
+        return Err("No beacon nodes were supplied".to_string());
+    }
 
-                return Ok(beacon_node);
-            }
-            Err(e) => {
-                error!(
-                    log,
-                    "Unable to connect to beacon node";
-                    "error" => format!("{:?}", e),
-                );
-                delay_for(RETRY_DELAY).await;
+    // Try to get the version string from the nodes, looping until success is returned.
+    loop {
+        for candidate in candidates {
+            let beacon_node = candidate.beacon_node();
+            let result = beacon_node
+                .http
+                .node()
+                .get_version()
+                .await
+                .map_err(|e| format!("{:?}", e));
+
+            match result {
+                Ok(version) => {
+                    info!(
+                        log,
+                        "Connected to beacon node";
+                        "endpoint" => candidate.endpoint(),
+                        "version" => version,
+                    );
+
+                    return Ok(beacon_node.clone());
+                }
+                Err(e) => {
+                    error!(
+                        log,
+                        "Unable to connect to beacon node";
+                        "endpoint" => candidate.endpoint(),
+                        "error" => format!("{:?}", e),
+                    );
+                }
             }
         }
+
+        delay_for(RETRY_DELAY).await;
     }
 }
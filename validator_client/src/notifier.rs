@@ -1,4 +1,4 @@
-use crate::{is_synced::is_synced, ProductionValidatorClient};
+use crate::ProductionValidatorClient;
 use futures::StreamExt;
 use slog::{error, info};
 use slot_clock::SlotClock;
@@ -10,6 +10,7 @@ pub fn spawn_notifier<T: EthSpec>(client: &ProductionValidatorClient<T>) -> Resu
     let context = client.context.service_context("notifier".into());
     let executor = context.executor.clone();
     let duties_service = client.duties_service.clone();
+    let beacon_nodes = client.beacon_nodes.clone();
     let allow_unsynced_beacon_node = client.config.allow_unsynced_beacon_node;
 
     let slot_duration = Duration::from_millis(context.eth2_config.spec.milliseconds_per_slot);
@@ -26,15 +27,26 @@ pub fn spawn_notifier<T: EthSpec>(client: &ProductionValidatorClient<T>) -> Resu
         let log = context.log();
 
         while interval.next().await.is_some() {
-            if !is_synced(
-                &duties_service.beacon_node,
-                &duties_service.slot_clock,
-                Some(&log),
-            )
-            .await
-                && !allow_unsynced_beacon_node
-            {
-                continue;
+            let num_synced = beacon_nodes.num_synced();
+            let num_total = beacon_nodes.num_total();
+
+            if num_synced > 0 {
+                info!(
+                    log,
+                    "Connected to beacon node(s)";
+                    "synced" => num_synced,
+                    "total" => num_total,
+                );
+            } else {
+                error!(
+                    log,
+                    "No synced beacon nodes";
+                    "total" => num_total,
+                );
+
+                if !allow_unsynced_beacon_node {
+                    continue;
+                }
             }
 
             if let Some(slot) = duties_service.slot_clock.now() {
@@ -0,0 +1,396 @@
+//! Provides the `ValidatorSigner` trait, which abstracts over the method used to produce
+//! signatures for a validator.
+//!
+//! Two implementations are provided:
+//!
+//! - `LocalKeystoreSigner`: signs with a voting keypair decrypted from a local keystore.
+//! - `RemoteSigner`: sends the signing root and its context to an HTTP signing service.
+//!
+//! Slashing protection is *not* the responsibility of a signer; the `ValidatorStore` checks the
+//! local slashing protection database before any signer is asked to sign.
+
+use futures::future::{self, BoxFuture};
+use reqwest::Client;
+use serde_derive::{Deserialize, Serialize};
+use std::time::Duration;
+use types::{Epoch, Fork, Hash256, Keypair, PublicKey, Signature};
+
+/// The timeout applied to each request to a remote signer.
+pub const REMOTE_SIGNER_TIMEOUT: Duration = Duration::from_secs(12);
+
+/// The path of the signing endpoint, relative to the remote signer URL.
+pub const REMOTE_SIGNER_SIGN_PATH: &str = "sign";
+
+#[derive(Debug)]
+pub enum Error {
+    /// The signer does not hold the key for the requested validator.
+    UnknownPubkey(PublicKey),
+    /// An error occurred whilst communicating with a remote signer.
+    Remote(String),
+}
+
+/// The type of message being signed.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MessageType {
+    BlockProposal,
+    Attestation,
+    RandaoReveal,
+    SelectionProof,
+    AggregateAndProof,
+}
+
+/// Information about a message being signed.
+///
+/// This is sent to remote signers alongside the signing root, so that they can apply their own
+/// policies (or slashing protection) before signing.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SigningContext {
+    pub message_type: MessageType,
+    /// The epoch used to compute the `domain`.
+    pub epoch: Epoch,
+    pub domain: Hash256,
+    pub fork: Fork,
+    pub genesis_validators_root: Hash256,
+}
+
+/// A method of producing signatures for validators.
+pub trait ValidatorSigner: Send + Sync {
+    /// Sign `signing_root` with the secret key corresponding to `voting_pubkey`.
+    ///
+    /// Signing may involve I/O, so the returned future must not block the thread polling it.
+    fn sign<'a>(
+        &'a self,
+        voting_pubkey: &'a PublicKey,
+        signing_root: Hash256,
+        context: &'a SigningContext,
+    ) -> BoxFuture<'a, Result<Signature, Error>>;
+}
+
+/// Signs with a keypair held in memory, decrypted from a local EIP-2335 keystore.
+pub struct LocalKeystoreSigner {
+    voting_keypair: Keypair,
+}
+
+impl LocalKeystoreSigner {
+    pub fn new(voting_keypair: Keypair) -> Self {
+        Self { voting_keypair }
+    }
+}
+
+impl ValidatorSigner for LocalKeystoreSigner {
+    fn sign<'a>(
+        &'a self,
+        voting_pubkey: &'a PublicKey,
+        signing_root: Hash256,
+        _context: &'a SigningContext,
+    ) -> BoxFuture<'a, Result<Signature, Error>> {
+        let result = if *voting_pubkey == self.voting_keypair.pk {
+            Ok(Signature::new(
+                signing_root.as_bytes(),
+                &self.voting_keypair.sk,
+            ))
+        } else {
+            Err(Error::UnknownPubkey(voting_pubkey.clone()))
+        };
+
+        Box::pin(future::ready(result))
+    }
+}
+
+/// The JSON body sent to a remote signer.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RemoteSigningRequest {
+    pub pubkey: PublicKey,
+    pub signing_root: Hash256,
+    pub context: SigningContext,
+}
+
+/// The JSON body expected in response from a remote signer.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RemoteSigningResponse {
+    pub signature: Signature,
+}
+
+/// Signs by sending a `RemoteSigningRequest` to an HTTP signing service.
+///
+/// Requests are made with an async HTTP client, so waiting on a slow (or unresponsive) remote
+/// signer never blocks a thread of the async runtime.
+pub struct RemoteSigner {
+    url: String,
+    client: Client,
+}
+
+impl RemoteSigner {
+    /// Create a signer that POSTs to `{url}/sign`, failing any request which takes longer than
+    /// `timeout`.
+    pub fn new(url: String, timeout: Duration) -> Result<Self, String> {
+        let client = Client::builder()
+            .timeout(timeout)
+            .build()
+            .map_err(|e| format!("Unable to build HTTP client: {:?}", e))?;
+
+        Ok(Self {
+            url: format!("{}/{}", url.trim_end_matches('/'), REMOTE_SIGNER_SIGN_PATH),
+            client,
+        })
+    }
+
+    /// The URL to which signing requests are sent.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    async fn send_request(&self, request: RemoteSigningRequest) -> Result<Signature, Error> {
+        let response = self
+            .client
+            .post(&self.url)
+            .json(&request)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| Error::Remote(format!("{:?}", e)))?;
+
+        response
+            .json::<RemoteSigningResponse>()
+            .await
+            .map(|response| response.signature)
+            .map_err(|e| Error::Remote(format!("{:?}", e)))
+    }
+}
+
+impl ValidatorSigner for RemoteSigner {
+    fn sign<'a>(
+        &'a self,
+        voting_pubkey: &'a PublicKey,
+        signing_root: Hash256,
+        context: &'a SigningContext,
+    ) -> BoxFuture<'a, Result<Signature, Error>> {
+        let request = RemoteSigningRequest {
+            pubkey: voting_pubkey.clone(),
+            signing_root,
+            context: context.clone(),
+        };
+
+        Box::pin(self.send_request(request))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::future::Either;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::thread;
+    use tokio::time::delay_for;
+    use types::test_utils::generate_deterministic_keypair;
+
+    /// Spawns a minimal HTTP server which signs every request with `keypair`, returning its URL.
+    ///
+    /// The server handles `num_requests` requests and then exits, returning the requests it
+    /// received via the join handle.
+    fn spawn_stand_in_signer(
+        keypair: Keypair,
+        num_requests: usize,
+    ) -> (String, thread::JoinHandle<Vec<RemoteSigningRequest>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let mut requests = vec![];
+            for stream in listener.incoming().take(num_requests) {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                assert!(request_line.starts_with("POST /sign "));
+
+                let mut content_length = 0;
+                loop {
+                    let mut header = String::new();
+                    reader.read_line(&mut header).unwrap();
+                    if header == "\r\n" {
+                        break;
+                    }
+                    let lower = header.to_lowercase();
+                    if lower.starts_with("content-length:") {
+                        content_length = lower["content-length:".len()..].trim().parse().unwrap();
+                    }
+                }
+
+                let mut body = vec![0; content_length];
+                reader.read_exact(&mut body).unwrap();
+                let request: RemoteSigningRequest = serde_json::from_slice(&body).unwrap();
+
+                let (status, response_body) = if request.pubkey == keypair.pk {
+                    let response = RemoteSigningResponse {
+                        signature: Signature::new(request.signing_root.as_bytes(), &keypair.sk),
+                    };
+                    ("200 OK", serde_json::to_string(&response).unwrap())
+                } else {
+                    ("404 Not Found", String::new())
+                };
+
+                write!(
+                    stream,
+                    "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    response_body.len(),
+                    response_body
+                )
+                .unwrap();
+
+                requests.push(request);
+            }
+            requests
+        });
+
+        (url, handle)
+    }
+
+    /// Spawns a server which accepts a single connection and responds with `500 Internal Server
+    /// Error`, returning its URL.
+    fn spawn_failing_signer() -> (String, thread::JoinHandle<()>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line == "\r\n" {
+                    break;
+                }
+            }
+            write!(
+                stream,
+                "HTTP/1.1 500 Internal Server Error\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+            )
+            .unwrap();
+        });
+
+        (url, handle)
+    }
+
+    /// Spawns a server which accepts a single connection and holds it open for `delay` without
+    /// responding, returning its URL.
+    fn spawn_unresponsive_signer(delay: Duration) -> (String, thread::JoinHandle<()>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let (_stream, _) = listener.accept().unwrap();
+            thread::sleep(delay);
+        });
+
+        (url, handle)
+    }
+
+    fn context() -> SigningContext {
+        SigningContext {
+            message_type: MessageType::Attestation,
+            epoch: Epoch::new(3),
+            domain: Hash256::repeat_byte(7),
+            fork: Fork::default(),
+            genesis_validators_root: Hash256::repeat_byte(42),
+        }
+    }
+
+    #[tokio::test]
+    async fn remote_signer_signs_known_key() {
+        let keypair = generate_deterministic_keypair(0);
+        let (url, server) = spawn_stand_in_signer(keypair.clone(), 1);
+
+        let signer = RemoteSigner::new(url, REMOTE_SIGNER_TIMEOUT).unwrap();
+        let signing_root = Hash256::repeat_byte(1);
+        let signature = signer
+            .sign(&keypair.pk, signing_root, &context())
+            .await
+            .unwrap();
+
+        assert!(signature.verify(signing_root.as_bytes(), &keypair.pk));
+        assert_eq!(
+            signature,
+            LocalKeystoreSigner::new(keypair.clone())
+                .sign(&keypair.pk, signing_root, &context())
+                .await
+                .unwrap()
+        );
+
+        let requests = server.join().unwrap();
+        assert_eq!(
+            requests,
+            vec![RemoteSigningRequest {
+                pubkey: keypair.pk,
+                signing_root,
+                context: context(),
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn remote_signer_unknown_key() {
+        let keypair = generate_deterministic_keypair(0);
+        let (url, server) = spawn_stand_in_signer(keypair, 1);
+
+        let signer = RemoteSigner::new(url, REMOTE_SIGNER_TIMEOUT).unwrap();
+        let other = generate_deterministic_keypair(1);
+        assert!(matches!(
+            signer.sign(&other.pk, Hash256::zero(), &context()).await,
+            Err(Error::Remote(_))
+        ));
+
+        server.join().unwrap();
+    }
+
+    #[tokio::test]
+    async fn remote_signer_failure() {
+        let (url, server) = spawn_failing_signer();
+
+        let signer = RemoteSigner::new(url, REMOTE_SIGNER_TIMEOUT).unwrap();
+        let keypair = generate_deterministic_keypair(0);
+        assert!(matches!(
+            signer.sign(&keypair.pk, Hash256::zero(), &context()).await,
+            Err(Error::Remote(_))
+        ));
+
+        server.join().unwrap();
+    }
+
+    /// A slow remote signer must not block the runtime: on this single-threaded runtime, a timer
+    /// should fire whilst the signing request is outstanding.
+    #[tokio::test]
+    async fn remote_signer_slow_does_not_block() {
+        let (url, server) = spawn_unresponsive_signer(Duration::from_secs(2));
+
+        let signer = RemoteSigner::new(url, Duration::from_millis(500)).unwrap();
+        let keypair = generate_deterministic_keypair(0);
+        let context = context();
+        let sign = signer.sign(&keypair.pk, Hash256::zero(), &context);
+
+        let sign = match future::select(sign, Box::pin(delay_for(Duration::from_millis(50)))).await
+        {
+            Either::Left(_) => panic!("signing should not complete before the timer"),
+            Either::Right((_, sign)) => sign,
+        };
+
+        // The request eventually times out.
+        assert!(matches!(sign.await, Err(Error::Remote(_))));
+
+        server.join().unwrap();
+    }
+
+    #[tokio::test]
+    async fn local_signer_unknown_key() {
+        let signer = LocalKeystoreSigner::new(generate_deterministic_keypair(0));
+        let other = generate_deterministic_keypair(1);
+        assert!(matches!(
+            signer.sign(&other.pk, Hash256::zero(), &context()).await,
+            Err(Error::UnknownPubkey(_))
+        ));
+    }
+}
@@ -1,48 +1,85 @@
 use crate::config::SLASHING_PROTECTION_FILENAME;
+use crate::signer::{
+    LocalKeystoreSigner, MessageType, RemoteSigner, SigningContext, ValidatorSigner,
+    REMOTE_SIGNER_TIMEOUT,
+};
 use crate::{config::Config, fork_service::ForkService};
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
+use remote_beacon_node::PublishStatus;
+use remote_validator_client::types::{Keystore, PublishOutcome, PublishResult};
 use slashing_protection::{NotSafe, Safe, SlashingDatabase};
-use slog::{crit, error, warn, Logger};
+use slog::{crit, debug, error, info, warn, Logger};
 use slot_clock::SlotClock;
-use std::collections::HashMap;
-use std::iter::FromIterator;
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Debug;
+use std::fs;
+use std::iter::{self, FromIterator};
 use std::marker::PhantomData;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use tempdir::TempDir;
 use types::{
-    Attestation, BeaconBlock, ChainSpec, Domain, Epoch, EthSpec, Fork, Hash256, Keypair, PublicKey,
-    SelectionProof, Signature, SignedAggregateAndProof, SignedBeaconBlock, SignedRoot, Slot,
+    AggregateAndProof, Attestation, BeaconBlock, ChainSpec, Domain, Epoch, EthSpec, Fork, Hash256,
+    PublicKey, SelectionProof, Signature, SignedAggregateAndProof, SignedBeaconBlock, SignedRoot,
+    Slot,
+};
+use validator_dir::{
+    Builder as ValidatorDirBuilder, BuilderError, Manager as ValidatorManager, ValidatorDir,
 };
-use validator_dir::{Manager as ValidatorManager, ValidatorDir};
 
-struct LocalValidator {
-    validator_dir: ValidatorDir,
-    voting_keypair: Keypair,
+/// Number of epochs of slashing protection history to keep.
+///
+/// This acts as a maximum safe-guard against clock drift.
+pub const SLASHING_PROTECTION_HISTORY_EPOCHS: u64 = 512;
+
+/// The number of publish results to keep for each validator.
+pub const PUBLISH_RESULTS_PER_VALIDATOR: usize = 32;
+
+struct InitializedValidator {
+    /// Held behind its own lock so that the directory can be updated without holding the lock on
+    /// all validators.
+    validator_dir: Arc<Mutex<ValidatorDir>>,
+    voting_pubkey: PublicKey,
+    signer: Arc<dyn ValidatorSigner>,
+    /// If `false`, no messages are signed for this validator.
+    enabled: bool,
 }
 
-/// We derive our own `PartialEq` to avoid doing equality checks between secret keys.
-///
-/// It's nice to avoid secret key comparisons from a security perspective, but it's also a little
-/// risky when it comes to `HashMap` integrity (that's why we need `PartialEq`).
-///
-/// Currently, we obtain keypairs from keystores where we derive the `PublicKey` from a `SecretKey`
-/// via a hash function. In order to have two equal `PublicKey` with different `SecretKey` we would
-/// need to have either:
-///
-/// - A serious upstream integrity error.
-/// - A hash collision.
+/// Converts the result of broadcasting a message to the beacon nodes into a `PublishOutcome`.
+pub fn publish_outcome<Err: Debug>(result: &Result<PublishStatus, Err>) -> PublishOutcome {
+    match result {
+        Ok(PublishStatus::Valid) => PublishOutcome::Valid,
+        Ok(PublishStatus::Invalid(msg)) => PublishOutcome::Invalid(msg.clone()),
+        Ok(PublishStatus::Unknown) => PublishOutcome::Unknown,
+        Err(e) => PublishOutcome::Failed(format!("{:?}", e)),
+    }
+}
+
+/// We derive our own `PartialEq` since signers (which may hold secret keys) are not comparable.
 ///
-/// It seems reasonable to make these two assumptions in order to avoid the equality checks.
-impl PartialEq for LocalValidator {
+/// Two validators with the same directory and voting public key are considered equal, regardless
+/// of how they sign.
+impl PartialEq for InitializedValidator {
     fn eq(&self, other: &Self) -> bool {
-        self.validator_dir == other.validator_dir
-            && self.voting_keypair.pk == other.voting_keypair.pk
+        self.voting_pubkey == other.voting_pubkey
+            && (Arc::ptr_eq(&self.validator_dir, &other.validator_dir)
+                || *self.validator_dir.lock() == *other.validator_dir.lock())
     }
 }
 
 #[derive(Clone)]
 pub struct ValidatorStore<T, E: EthSpec> {
-    validators: Arc<RwLock<HashMap<PublicKey, LocalValidator>>>,
+    validators: Arc<RwLock<HashMap<PublicKey, InitializedValidator>>>,
+    /// The most recent results of publishing messages signed by each validator, oldest first.
+    publish_results: Arc<RwLock<HashMap<PublicKey, VecDeque<PublishResult>>>>,
+    validators_dir: PathBuf,
+    secrets_dir: PathBuf,
+    /// If `true`, the keys are held by a remote signer rather than decrypted from local keystores.
+    uses_remote_signer: bool,
+    /// If `true`, no blocks or attestations are signed because the `DoppelgangerService` has not
+    /// yet finished checking that the validators are not active elsewhere.
+    doppelganger_detection_pending: Arc<AtomicBool>,
     slashing_protection: SlashingDatabase,
     genesis_validators_root: Hash256,
     spec: Arc<ChainSpec>,
@@ -69,23 +106,83 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
                 )
             })?;
 
-        let validator_key_values = ValidatorManager::open(&config.data_dir)
-            .map_err(|e| format!("unable to read data_dir: {:?}", e))?
-            .decrypt_all_validators(config.secrets_dir.clone(), Some(&log))
-            .map_err(|e| format!("unable to decrypt all validator directories: {:?}", e))?
-            .into_iter()
-            .map(|(kp, dir)| {
-                (
-                    kp.pk.clone(),
-                    LocalValidator {
-                        validator_dir: dir,
-                        voting_keypair: kp,
-                    },
-                )
-            });
+        let validator_manager = ValidatorManager::open(&config.data_dir)
+            .map_err(|e| format!("unable to read data_dir: {:?}", e))?;
+
+        let validators = if let Some(url) = &config.remote_signer_url {
+            // The secret keys are held by the remote signer, so only read the public keys.
+            let remote_signer: Arc<dyn ValidatorSigner> =
+                Arc::new(RemoteSigner::new(url.clone(), REMOTE_SIGNER_TIMEOUT)?);
+
+            validator_manager
+                .open_all_validators()
+                .map_err(|e| format!("unable to open all validator directories: {:?}", e))?
+                .into_iter()
+                .map(|validator_dir| {
+                    let voting_pubkey = validator_dir.voting_public_key().map_err(|e| {
+                        format!(
+                            "unable to read voting public key in {:?}: {:?}",
+                            validator_dir.dir(),
+                            e
+                        )
+                    })?;
+
+                    info!(
+                        log,
+                        "Using remote signer for validator";
+                        "voting_pubkey" => voting_pubkey.as_hex_string(),
+                        "remote_signer" => url,
+                    );
+
+                    Ok((
+                        voting_pubkey.clone(),
+                        InitializedValidator {
+                            enabled: !validator_dir.is_disabled(),
+                            validator_dir: Arc::new(Mutex::new(validator_dir)),
+                            voting_pubkey,
+                            signer: remote_signer.clone(),
+                        },
+                    ))
+                })
+                .collect::<Result<Vec<_>, String>>()?
+        } else {
+            validator_manager
+                .decrypt_all_validators(config.secrets_dir.clone(), Some(&log))
+                .map_err(|e| format!("unable to decrypt all validator directories: {:?}", e))?
+                .into_iter()
+                .map(|(kp, validator_dir)| {
+                    (
+                        kp.pk.clone(),
+                        InitializedValidator {
+                            enabled: !validator_dir.is_disabled(),
+                            validator_dir: Arc::new(Mutex::new(validator_dir)),
+                            voting_pubkey: kp.pk.clone(),
+                            signer: Arc::new(LocalKeystoreSigner::new(kp)),
+                        },
+                    )
+                })
+                .collect()
+        };
+
+        for (_, validator) in &validators {
+            if !validator.enabled {
+                info!(
+                    log,
+                    "Validator is disabled";
+                    "voting_pubkey" => validator.voting_pubkey.as_hex_string(),
+                );
+            }
+        }
 
         Ok(Self {
-            validators: Arc::new(RwLock::new(HashMap::from_iter(validator_key_values))),
+            validators: Arc::new(RwLock::new(HashMap::from_iter(validators))),
+            publish_results: Arc::new(RwLock::new(HashMap::new())),
+            validators_dir: config.data_dir.clone(),
+            secrets_dir: config.secrets_dir.clone(),
+            uses_remote_signer: config.remote_signer_url.is_some(),
+            doppelganger_detection_pending: Arc::new(AtomicBool::new(
+                config.enable_doppelganger_protection,
+            )),
             slashing_protection,
             genesis_validators_root,
             spec: Arc::new(spec),
@@ -106,16 +203,263 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
             .map_err(|e| format!("Error while registering validators: {:?}", e))
     }
 
+    /// Prune the slashing protection database so that it remains performant.
+    ///
+    /// Blocks and attestations older than `SLASHING_PROTECTION_HISTORY_EPOCHS` before
+    /// `current_epoch` are removed. The low watermarks maintained by the database ensure that
+    /// nothing at or below the pruned history can be signed afterwards.
+    pub fn prune_slashing_protection_db(&self, current_epoch: Epoch) {
+        let new_min_target_epoch = match current_epoch
+            .as_u64()
+            .checked_sub(SLASHING_PROTECTION_HISTORY_EPOCHS)
+        {
+            Some(epoch) if epoch > 0 => Epoch::new(epoch),
+            _ => return,
+        };
+        let new_min_slot = new_min_target_epoch.start_slot(E::slots_per_epoch());
+
+        // Release the lock on the validators before touching the database.
+        let pubkeys = self.validators.read().keys().cloned().collect::<Vec<_>>();
+
+        match self
+            .slashing_protection
+            .prune_all_signed_attestations(pubkeys.iter(), new_min_target_epoch)
+        {
+            Ok(unregistered) => self.warn_unregistered_during_pruning(&unregistered),
+            Err(e) => {
+                error!(
+                    self.log,
+                    "Error during pruning of signed attestations";
+                    "error" => format!("{:?}", e),
+                );
+                return;
+            }
+        }
+
+        match self
+            .slashing_protection
+            .prune_all_signed_blocks(pubkeys.iter(), new_min_slot)
+        {
+            Ok(unregistered) => self.warn_unregistered_during_pruning(&unregistered),
+            Err(e) => {
+                error!(
+                    self.log,
+                    "Error during pruning of signed blocks";
+                    "error" => format!("{:?}", e),
+                );
+                return;
+            }
+        }
+
+        debug!(
+            self.log,
+            "Pruned slashing protection database";
+            "new_min_target_epoch" => new_min_target_epoch.as_u64(),
+            "new_min_slot" => new_min_slot.as_u64(),
+        );
+    }
+
+    fn warn_unregistered_during_pruning(&self, unregistered: &[PublicKey]) {
+        for pubkey in unregistered {
+            warn!(
+                self.log,
+                "Skipped pruning for unregistered validator";
+                "voting_pubkey" => pubkey.as_hex_string(),
+            );
+        }
+    }
+
+    /// Returns the voting public keys of all enabled validators.
     pub fn voting_pubkeys(&self) -> Vec<PublicKey> {
         self.validators
             .read()
             .iter()
-            .map(|(pubkey, _dir)| pubkey.clone())
+            .filter(|(_pubkey, validator)| validator.enabled)
+            .map(|(pubkey, _validator)| pubkey.clone())
             .collect()
     }
 
+    /// Returns the number of enabled validators.
     pub fn num_voting_validators(&self) -> usize {
-        self.validators.read().len()
+        self.validators
+            .read()
+            .values()
+            .filter(|validator| validator.enabled)
+            .count()
+    }
+
+    /// Returns the voting public key of every validator, enabled or not, alongside whether it is
+    /// enabled.
+    pub fn validator_statuses(&self) -> Vec<(PublicKey, bool)> {
+        self.validators
+            .read()
+            .iter()
+            .map(|(pubkey, validator)| (pubkey.clone(), validator.enabled))
+            .collect()
+    }
+
+    /// Returns `Some(true)` if the validator is enabled, `Some(false)` if it is disabled or `None`
+    /// if it is unknown.
+    pub fn validator_enabled(&self, validator_pubkey: &PublicKey) -> Option<bool> {
+        self.validators
+            .read()
+            .get(validator_pubkey)
+            .map(|validator| validator.enabled)
+    }
+
+    /// Returns `true` if the validator is known and enabled.
+    pub fn is_enabled(&self, validator_pubkey: &PublicKey) -> bool {
+        self.validator_enabled(validator_pubkey).unwrap_or(false)
+    }
+
+    /// Enables or disables signing for a validator.
+    ///
+    /// The setting is saved in the validator directory, so that it persists across restarts.
+    pub fn set_validator_enabled(
+        &self,
+        validator_pubkey: &PublicKey,
+        enabled: bool,
+    ) -> Result<(), String> {
+        let validator_dir = self
+            .validators
+            .read()
+            .get(validator_pubkey)
+            .map(|validator| validator.validator_dir.clone())
+            .ok_or_else(|| format!("Unknown validator {}", validator_pubkey.as_hex_string()))?;
+
+        // Hold the lock on the directory until `enabled` is updated, so concurrent updates are
+        // applied to the file-system and to `self.validators` in the same order.
+        let mut dir = validator_dir.lock();
+        dir.set_disabled(!enabled)
+            .map_err(|e| format!("Unable to update validator directory: {:?}", e))?;
+
+        if let Some(validator) = self.validators.write().get_mut(validator_pubkey) {
+            validator.enabled = enabled;
+        }
+        drop(dir);
+
+        info!(
+            self.log,
+            "Updated validator";
+            "enabled" => enabled,
+            "voting_pubkey" => validator_pubkey.as_hex_string(),
+        );
+
+        Ok(())
+    }
+
+    /// Imports an EIP-2335 `keystore` as a new validator, storing it in the validators directory
+    /// and its `password` in the secrets directory.
+    ///
+    /// The validator is registered with the slashing protection database, so it must not be
+    /// active on any other validator client.
+    ///
+    /// ## Note
+    ///
+    /// Decrypting the keystore is slow, so this function should not be called from an async
+    /// task.
+    pub fn import_keystore(
+        &self,
+        keystore: Keystore,
+        password: &[u8],
+        enabled: bool,
+    ) -> Result<PublicKey, String> {
+        if self.uses_remote_signer {
+            return Err(
+                "Unable to import keystores when the keys are held by a remote signer".to_string(),
+            );
+        }
+
+        let keypair = keystore
+            .decrypt_keypair(password)
+            .map_err(|e| format!("Unable to decrypt keystore: {:?}", e))?;
+        let voting_pubkey = keypair.pk.clone();
+
+        // Register the validator before writing any files, so that a failure cannot leave behind
+        // a validator directory without slashing protection.
+        self.slashing_protection
+            .register_validators(iter::once(&voting_pubkey))
+            .map_err(|e| format!("Unable to register validator: {:?}", e))?;
+
+        let mut validator_dir =
+            ValidatorDirBuilder::new(self.validators_dir.clone(), self.secrets_dir.clone())
+                .decrypted_voting_keystore(keystore, password, keypair.clone())
+                .store_withdrawal_keystore(false)
+                .build()
+                .map_err(|e| match e {
+                    BuilderError::DirectoryAlreadyExists(_) => {
+                        "The validator already exists".to_string()
+                    }
+                    e => format!("Unable to import keystore: {:?}", e),
+                })?;
+
+        if !enabled {
+            if let Err(e) = validator_dir.set_disabled(true) {
+                // Remove the files written by the builder, otherwise the validator would be
+                // enabled the next time the validator client starts.
+                let dir = validator_dir.dir().clone();
+                drop(validator_dir);
+                let _ = fs::remove_dir_all(dir);
+                let _ = fs::remove_file(self.secrets_dir.join(voting_pubkey.as_hex_string()));
+
+                return Err(format!("Unable to disable validator: {:?}", e));
+            }
+        }
+
+        self.validators.write().insert(
+            voting_pubkey.clone(),
+            InitializedValidator {
+                validator_dir: Arc::new(Mutex::new(validator_dir)),
+                voting_pubkey: voting_pubkey.clone(),
+                signer: Arc::new(LocalKeystoreSigner::new(keypair)),
+                enabled,
+            },
+        );
+
+        info!(
+            self.log,
+            "Imported validator";
+            "enabled" => enabled,
+            "voting_pubkey" => voting_pubkey.as_hex_string(),
+        );
+
+        Ok(voting_pubkey)
+    }
+
+    /// Returns `true` if doppelganger detection is in progress, in which case no blocks or
+    /// attestations will be signed.
+    pub fn doppelganger_detection_pending(&self) -> bool {
+        self.doppelganger_detection_pending.load(Ordering::Relaxed)
+    }
+
+    /// Permits the signing of blocks and attestations, once the `DoppelgangerService` has found no
+    /// other instances of the validators.
+    pub fn complete_doppelganger_detection(&self) {
+        self.doppelganger_detection_pending
+            .store(false, Ordering::Relaxed);
+    }
+
+    /// Records the `result` of publishing a message signed by `validator_pubkey`, discarding the
+    /// oldest result if there are more than `PUBLISH_RESULTS_PER_VALIDATOR`.
+    pub fn record_publish_result(&self, validator_pubkey: &PublicKey, result: PublishResult) {
+        let mut publish_results = self.publish_results.write();
+        let results = publish_results
+            .entry(validator_pubkey.clone())
+            .or_insert_with(VecDeque::new);
+
+        results.push_back(result);
+        while results.len() > PUBLISH_RESULTS_PER_VALIDATOR {
+            results.pop_front();
+        }
+    }
+
+    /// Returns the most recent publish results for `validator_pubkey`, oldest first.
+    pub fn publish_results(&self, validator_pubkey: &PublicKey) -> Vec<PublishResult> {
+        self.publish_results
+            .read()
+            .get(validator_pubkey)
+            .map(|results| results.iter().cloned().collect())
+            .unwrap_or_default()
     }
 
     fn fork(&self) -> Option<Fork> {
@@ -128,31 +472,106 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         self.fork_service.fork()
     }
 
-    pub fn randao_reveal(&self, validator_pubkey: &PublicKey, epoch: Epoch) -> Option<Signature> {
-        // TODO: check this against the slot clock to make sure it's not an early reveal?
-        self.validators
+    /// Sign `signing_root` with the signer for `validator_pubkey`.
+    ///
+    /// Returns `None` (and logs an error) if the validator is unknown or the signer fails.
+    /// Returns `None` if the validator is disabled.
+    async fn sign(
+        &self,
+        validator_pubkey: &PublicKey,
+        signing_root: Hash256,
+        context: SigningContext,
+    ) -> Option<Signature> {
+        // Clone the signer so that the lock is not held whilst signing, which may involve a
+        // network request.
+        let signer = self
+            .validators
             .read()
             .get(validator_pubkey)
-            .and_then(|local_validator| {
-                let voting_keypair = &local_validator.voting_keypair;
-                let domain = self.spec.get_domain(
-                    epoch,
-                    Domain::Randao,
-                    &self.fork()?,
-                    self.genesis_validators_root,
-                );
-                let message = epoch.signing_root(domain);
+            .filter(|validator| validator.enabled)?
+            .signer
+            .clone();
 
-                Some(Signature::new(message.as_bytes(), &voting_keypair.sk))
+        signer
+            .sign(validator_pubkey, signing_root, &context)
+            .await
+            .map_err(|e| {
+                error!(
+                    self.log,
+                    "Unable to sign message";
+                    "message_type" => format!("{:?}", context.message_type),
+                    "validator" => validator_pubkey.as_hex_string(),
+                    "error" => format!("{:?}", e),
+                )
             })
+            .ok()
+    }
+
+    /// Compute the domain for `domain` at `epoch`, returning it with the context for a signer.
+    fn signing_context(
+        &self,
+        message_type: MessageType,
+        domain: Domain,
+        epoch: Epoch,
+        fork: Fork,
+    ) -> SigningContext {
+        SigningContext {
+            message_type,
+            epoch,
+            domain: self
+                .spec
+                .get_domain(epoch, domain, &fork, self.genesis_validators_root),
+            fork,
+            genesis_validators_root: self.genesis_validators_root,
+        }
+    }
+
+    pub async fn randao_reveal(
+        &self,
+        validator_pubkey: &PublicKey,
+        epoch: Epoch,
+    ) -> Option<Signature> {
+        // TODO: check this against the slot clock to make sure it's not an early reveal?
+        if !self.is_enabled(validator_pubkey) {
+            return None;
+        }
+
+        let context = self.signing_context(
+            MessageType::RandaoReveal,
+            Domain::Randao,
+            epoch,
+            self.fork()?,
+        );
+        let message = epoch.signing_root(context.domain);
+
+        self.sign(validator_pubkey, message, context).await
     }
 
-    pub fn sign_block(
+    pub async fn sign_block(
         &self,
         validator_pubkey: &PublicKey,
         block: BeaconBlock<E>,
         current_slot: Slot,
     ) -> Option<SignedBeaconBlock<E>> {
+        // Do not insert blocks into the slashing protection database for disabled validators.
+        if !self.is_enabled(validator_pubkey) {
+            warn!(
+                self.log,
+                "Not signing block for disabled validator";
+                "public_key" => validator_pubkey.as_hex_string(),
+            );
+            return None;
+        }
+
+        if self.doppelganger_detection_pending() {
+            warn!(
+                self.log,
+                "Not signing block during doppelganger detection";
+                "public_key" => validator_pubkey.as_hex_string(),
+            );
+            return None;
+        }
+
         // Make sure the block slot is not higher than the current slot to avoid potential attacks.
         if block.slot > current_slot {
             warn!(
@@ -165,33 +584,29 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         }
 
         // Check for slashing conditions.
-        let fork = self.fork()?;
-        let domain = self.spec.get_domain(
-            block.epoch(),
+        let context = self.signing_context(
+            MessageType::BlockProposal,
             Domain::BeaconProposer,
-            &fork,
-            self.genesis_validators_root,
+            block.epoch(),
+            self.fork()?,
         );
 
         let slashing_status = self.slashing_protection.check_and_insert_block_proposal(
             validator_pubkey,
             &block.block_header(),
-            domain,
+            context.domain,
         );
 
         match slashing_status {
             // We can safely sign this block.
             Ok(Safe::Valid) => {
-                let validators = self.validators.read();
-                let validator = validators.get(validator_pubkey)?;
-                let voting_keypair = &validator.voting_keypair;
-
-                Some(block.sign(
-                    &voting_keypair.sk,
-                    &fork,
-                    self.genesis_validators_root,
-                    &self.spec,
-                ))
+                let message = block.signing_root(context.domain);
+                let signature = self.sign(validator_pubkey, message, context).await?;
+
+                Some(SignedBeaconBlock {
+                    message: block,
+                    signature,
+                })
             }
             Ok(Safe::SameData) => {
                 warn!(
@@ -220,48 +635,60 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         }
     }
 
-    pub fn sign_attestation(
+    pub async fn sign_attestation(
         &self,
         validator_pubkey: &PublicKey,
         validator_committee_position: usize,
         attestation: &mut Attestation<E>,
         current_epoch: Epoch,
     ) -> Option<()> {
+        // Do not insert attestations into the slashing protection database for disabled
+        // validators.
+        if !self.is_enabled(validator_pubkey) {
+            warn!(
+                self.log,
+                "Not signing attestation for disabled validator";
+                "public_key" => validator_pubkey.as_hex_string(),
+            );
+            return None;
+        }
+
+        if self.doppelganger_detection_pending() {
+            warn!(
+                self.log,
+                "Not signing attestation during doppelganger detection";
+                "public_key" => validator_pubkey.as_hex_string(),
+            );
+            return None;
+        }
+
         // Make sure the target epoch is not higher than the current epoch to avoid potential attacks.
         if attestation.data.target.epoch > current_epoch {
             return None;
         }
 
         // Checking for slashing conditions.
-        let fork = self.fork()?;
-
-        let domain = self.spec.get_domain(
-            attestation.data.target.epoch,
+        let context = self.signing_context(
+            MessageType::Attestation,
             Domain::BeaconAttester,
-            &fork,
-            self.genesis_validators_root,
+            attestation.data.target.epoch,
+            self.fork()?,
         );
+
         let slashing_status = self.slashing_protection.check_and_insert_attestation(
             validator_pubkey,
             &attestation.data,
-            domain,
+            context.domain,
         );
 
         match slashing_status {
             // We can safely sign this attestation.
             Ok(Safe::Valid) => {
-                let validators = self.validators.read();
-                let validator = validators.get(validator_pubkey)?;
-                let voting_keypair = &validator.voting_keypair;
+                let message = attestation.data.signing_root(context.domain);
+                let signature = self.sign(validator_pubkey, message, context).await?;
 
                 attestation
-                    .sign(
-                        &voting_keypair.sk,
-                        validator_committee_position,
-                        &fork,
-                        self.genesis_validators_root,
-                        &self.spec,
-                    )
+                    .add_signature(&signature, validator_committee_position)
                     .map_err(|e| {
                         error!(
                             self.log,
@@ -305,43 +732,54 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
     ///
     /// The resulting `SignedAggregateAndProof` is sent on the aggregation channel and cannot be
     /// modified by actors other than the signing validator.
-    pub fn produce_signed_aggregate_and_proof(
+    pub async fn produce_signed_aggregate_and_proof(
         &self,
         validator_pubkey: &PublicKey,
         validator_index: u64,
         aggregate: Attestation<E>,
         selection_proof: SelectionProof,
     ) -> Option<SignedAggregateAndProof<E>> {
-        let validators = self.validators.read();
-        let voting_keypair = &validators.get(validator_pubkey)?.voting_keypair;
+        // Aggregates are only produced for signed attestations, which are prevented during
+        // doppelganger detection. Check anyway, since an aggregate is also evidence of liveness.
+        if self.doppelganger_detection_pending() {
+            return None;
+        }
 
-        Some(SignedAggregateAndProof::from_aggregate(
-            validator_index,
+        let message = AggregateAndProof {
+            aggregator_index: validator_index,
             aggregate,
-            Some(selection_proof),
-            &voting_keypair.sk,
-            &self.fork()?,
-            self.genesis_validators_root,
-            &self.spec,
-        ))
+            selection_proof: selection_proof.into(),
+        };
+
+        let context = self.signing_context(
+            MessageType::AggregateAndProof,
+            Domain::AggregateAndProof,
+            message.aggregate.data.slot.epoch(E::slots_per_epoch()),
+            self.fork()?,
+        );
+        let signing_root = message.signing_root(context.domain);
+        let signature = self.sign(validator_pubkey, signing_root, context).await?;
+
+        Some(SignedAggregateAndProof { message, signature })
     }
 
     /// Produces a `SelectionProof` for the `slot`, signed by with corresponding secret key to
     /// `validator_pubkey`.
-    pub fn produce_selection_proof(
+    pub async fn produce_selection_proof(
         &self,
         validator_pubkey: &PublicKey,
         slot: Slot,
     ) -> Option<SelectionProof> {
-        let validators = self.validators.read();
-        let voting_keypair = &validators.get(validator_pubkey)?.voting_keypair;
-
-        Some(SelectionProof::new::<E>(
-            slot,
-            &voting_keypair.sk,
-            &self.fork()?,
-            self.genesis_validators_root,
-            &self.spec,
-        ))
+        let context = self.signing_context(
+            MessageType::SelectionProof,
+            Domain::SelectionProof,
+            slot.epoch(E::slots_per_epoch()),
+            self.fork()?,
+        );
+        let message = slot.signing_root(context.domain);
+
+        self.sign(validator_pubkey, message, context)
+            .await
+            .map(SelectionProof::from)
     }
 }